@@ -1,12 +1,15 @@
 // Copyright 2025 Irreducible Inc.
 
-use std::{iter, marker::PhantomData};
+use std::{iter, marker::PhantomData, mem::MaybeUninit};
 
 use binius_field::{BinaryField, ExtensionField, Field, TowerField, util::inner_product_unchecked};
-use binius_math::{ArithCircuit, TowerTop, extrapolate_line_scalar};
+use binius_hash::multi_digest::ParallelDigest;
+use binius_math::{ArithCircuit, TowerTop64, extrapolate_line_scalar};
+use binius_maybe_rayon::prelude::*;
 use binius_ntt::AdditiveNTT;
 use binius_utils::checked_arithmetics::checked_log_2;
 use bytemuck::zeroed_vec;
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use itertools::izip;
 
 use super::{memory::CpuMemory, tower_macro::each_tower_subfield};
@@ -20,7 +23,7 @@ use crate::{
 #[derive(Debug, Default)]
 pub struct CpuLayer<F>(PhantomData<F>);
 
-impl<F: TowerTop> ComputeLayer<F> for CpuLayer<F> {
+impl<F: TowerTop64> ComputeLayer<F> for CpuLayer<F> {
 	type Exec<'a> = CpuLayerExecutor<F>;
 	type DevMem = CpuMemory;
 
@@ -83,7 +86,7 @@ impl<F: TowerTop> ComputeLayer<F> for CpuLayer<F> {
 #[derive(Debug)]
 pub struct CpuLayerExecutor<F>(PhantomData<F>);
 
-impl<F: TowerTop> CpuLayerExecutor<F> {
+impl<F: TowerTop64> CpuLayerExecutor<F> {
 	fn map_kernel_mem<'a>(
 		mappings: &'a mut [MemMap<'_, Self, F>],
 		local_buffer_alloc: &'a BumpAllocator<F, <Self as ComputeLayerExecutor<F>>::DevMem>,
@@ -127,7 +130,7 @@ impl<F> Default for CpuLayerExecutor<F> {
 	}
 }
 
-impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
+impl<F: TowerTop64> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 	type OpValue = F;
 	type ExprEval = ArithCircuit<F>;
 	type KernelExec = CpuKernelBuilder;
@@ -198,7 +201,7 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		}
 
 		let result =
-			each_tower_subfield!(a_in.tower_level, inner_product::<_, F>(a_in.slice, b_in));
+			each_tower_subfield!(a_in.tower_level, F, inner_product::<_, F>(a_in.slice, b_in));
 		Ok(result)
 	}
 
@@ -220,6 +223,7 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		// slice.
 		each_tower_subfield!(
 			mat.tower_level,
+			F,
 			compute_left_fold::<_, F>(mat.slice, log_evals_size, vec, out)
 		)
 	}
@@ -242,6 +246,7 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		// slice.
 		each_tower_subfield!(
 			mat.tower_level,
+			F,
 			compute_right_fold::<_, F>(mat.slice, log_evals_size, vec, out)
 		)
 	}
@@ -377,6 +382,51 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		Ok(())
 	}
 
+	fn fold_multilinear(
+		&mut self,
+		evals: &[F],
+		challenge: F,
+		folded: &mut &mut [F],
+	) -> Result<(), Error> {
+		if !folded.len().is_power_of_two() || evals.len() != 2 * folded.len() {
+			return Err(Error::InputValidation(format!(
+				"evals length {} must be twice the folded length {}, which must be a power of two",
+				evals.len(),
+				folded.len()
+			)));
+		}
+
+		let (evals_0, evals_1) = evals.split_at(folded.len());
+		for (out, &x0, &x1) in izip!(&mut **folded, evals_0, evals_1) {
+			*out = extrapolate_line_scalar(x0, x1, challenge);
+		}
+		Ok(())
+	}
+
+	fn hash_leaves<H>(
+		&mut self,
+		data: &[F],
+		prefix: &[u8],
+		digests: &mut [MaybeUninit<Output<H::Digest>>],
+	) -> Result<(), Error>
+	where
+		H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	{
+		if digests.is_empty() || data.len() % digests.len() != 0 {
+			return Err(Error::InputValidation(format!(
+				"data length {} is not a multiple of the number of digests {}",
+				data.len(),
+				digests.len()
+			)));
+		}
+
+		let leaves = data
+			.par_chunks(data.len() / digests.len())
+			.map(|leaf| leaf.iter().copied());
+		H::new_with_prefix(prefix).digest(leaves, digests);
+		Ok(())
+	}
+
 	fn compute_composite(
 		&mut self,
 		inputs: &SlicesBatch<<Self::DevMem as ComputeMemory<F>>::FSlice<'_>>,
@@ -434,6 +484,33 @@ impl<F: TowerField> KernelExecutor<F> for CpuKernelBuilder {
 		Ok(())
 	}
 
+	fn sum_composition_evals_at_point(
+		&mut self,
+		evals_0: &SlicesBatch<<Self::Mem as ComputeMemory<F>>::FSlice<'_>>,
+		evals_1: &SlicesBatch<<Self::Mem as ComputeMemory<F>>::FSlice<'_>>,
+		point: F,
+		composition: &Self::ExprEval,
+		batch_coeff: F,
+		accumulator: &mut Self::Value,
+	) -> Result<(), Error> {
+		if evals_0.n_rows() != evals_1.n_rows() || evals_0.row_len() != evals_1.row_len() {
+			return Err(Error::InputValidation(
+				"evals_0 and evals_1 must have the same shape".into(),
+			));
+		}
+
+		let ret = (0..evals_0.row_len())
+			.map(|i| {
+				let row = iter::zip(evals_0.iter(), evals_1.iter())
+					.map(|(x0, x1)| extrapolate_line_scalar::<F, F>(x0[i], x1[i], point))
+					.collect::<Vec<_>>();
+				composition.evaluate(&row).expect("Evaluation to succeed")
+			})
+			.sum::<F>();
+		*accumulator += ret * batch_coeff;
+		Ok(())
+	}
+
 	fn add(
 		&mut self,
 		log_len: usize,
@@ -492,7 +569,7 @@ pub fn count_total_local_buffer_sizes<F, Mem: ComputeMemory<F>>(
 /// evals is treated as a matrix with `1 << log_query_size` columns and each row is dot-produced
 /// with the corresponding query element. The result is written to the `output` slice of values.
 /// The evals slice may be any field extension defined by the tower family T.
-fn compute_left_fold<EvalType: TowerField, F: TowerTop + ExtensionField<EvalType>>(
+fn compute_left_fold<EvalType: TowerField, F: TowerTop64 + ExtensionField<EvalType>>(
 	evals_as_b128: &[F],
 	log_evals_size: usize,
 	query: &[F],
@@ -546,7 +623,7 @@ fn compute_left_fold<EvalType: TowerField, F: TowerTop + ExtensionField<EvalType
 /// evals is treated as a matrix with `1 << log_query_size` columns and each row is dot-produced
 /// with the corresponding query element. The result is written to the `output` slice of values.
 /// The evals slice may be any field extension defined by the tower family T.
-fn compute_right_fold<EvalType: TowerField, F: TowerTop + ExtensionField<EvalType>>(
+fn compute_right_fold<EvalType: TowerField, F: TowerTop64 + ExtensionField<EvalType>>(
 	evals_as_b128: &[F],
 	log_evals_size: usize,
 	query: &[F],
@@ -1,9 +1,14 @@
 // Copyright 2025 Irreducible Inc.
 
 #[macro_export]
+/// Dispatches on a tower level up to the one of the top field `$top_ty`, which is at least 64 bits.
+///
+/// The levels up to 6 are the canonical subfields, and the level of the top field is the top field
+/// itself, so that the top field need not extend [`binius_math::B128`].
 macro_rules! each_tower_subfield {
     (
         $tower_height:expr,
+        $top_ty:ty,
         $func:ident ::< _ $(, $type_args:ty)* >( $($args:expr),* $(,)? )
     ) => {
         match $tower_height {
@@ -12,7 +17,9 @@ macro_rules! each_tower_subfield {
             4 => $func::< ::binius_math::B16 $(, $type_args,)* >( $($args),* ),
             5 => $func::< ::binius_math::B32 $(, $type_args,)* >( $($args),* ),
             6 => $func::< ::binius_math::B64 $(, $type_args,)* >( $($args),* ),
-            7 => $func::< ::binius_math::B128 $(, $type_args,)* >( $($args),* ),
+            tower_height if tower_height == <$top_ty as ::binius_field::TowerField>::TOWER_LEVEL => {
+                $func::< $top_ty $(, $type_args,)* >( $($args),* )
+            }
 
             _ => {
                 return Err(
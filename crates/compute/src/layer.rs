@@ -1,11 +1,13 @@
 // Copyright 2025 Irreducible Inc.
 
-use std::ops::Range;
+use std::{mem::MaybeUninit, ops::Range};
 
-use binius_field::{BinaryField, ExtensionField, Field};
+use binius_field::{BinaryField, ExtensionField, Field, TowerField};
+use binius_hash::multi_digest::ParallelDigest;
 use binius_math::ArithCircuit;
 use binius_ntt::AdditiveNTT;
 use binius_utils::checked_arithmetics::{checked_int_div, checked_log_2};
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use itertools::Either;
 
 use super::{
@@ -362,6 +364,73 @@ pub trait ComputeLayerExecutor<F: Field> {
 		z: F,
 	) -> Result<(), Error>;
 
+	/// Folds a multilinear polynomial by binding its highest variable to a challenge.
+	///
+	/// This is the sumcheck fold in high-to-low variable binding order. The operation writes the
+	/// result out-of-place into an output buffer, leaving the input evaluations intact.
+	///
+	/// ## Mathematical Definition
+	///
+	/// This operation accepts
+	///
+	/// * $n \in \mathbb{N}$ (`log_2(folded.len())`),
+	/// * $P \in L^{2^{n+1}}$ (`evals`), the evaluations of a multilinear on the hypercube,
+	/// * $r \in L$ (`challenge`),
+	///
+	/// and computes the vector with entries
+	///
+	/// $$
+	/// P\[i\] + r (P\[2^n + i\] - P\[i\]) \quad \forall i \in \{0, \ldots, 2^n - 1\},
+	/// $$
+	///
+	/// which are the evaluations of $P(X_0, \ldots, X_{n-1}, r)$ on the hypercube.
+	///
+	/// ## Arguments
+	///
+	/// * `evals` - the evaluations of the multilinear.
+	/// * `challenge` - the value the highest variable is bound to.
+	/// * `folded` - an output buffer for the evaluations of the folded multilinear.
+	///
+	/// ## Throws
+	///
+	/// * unless `folded.len()` is a power of two and `evals.len()` is twice `folded.len()`
+	fn fold_multilinear(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		challenge: F,
+		folded: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	) -> Result<(), Error>;
+
+	/// Hashes the leaves of a Merkle tree over a batch of vectors into digests in host memory.
+	///
+	/// The data is split into `digests.len()` contiguous leaves of equal length, and the elements
+	/// of each leaf are hashed with `H` after `prefix` into the digest at the same index, as the
+	/// CPU Merkle tree prover does. Hashing the leaves where the codeword was encoded avoids
+	/// copying the codeword back to the host; only the digests, which are much smaller, are
+	/// transferred.
+	///
+	/// ## Arguments
+	///
+	/// * `data` - the elements of the leaves, with a length that is a multiple of the number of
+	///   digests.
+	/// * `prefix` - the bytes hashed before the elements of every leaf, which separate the leaf
+	///   digests from the inner node digests when the tree is domain separated.
+	/// * `digests` - the host buffer for the leaf digests, which is entirely initialized on
+	///   success.
+	///
+	/// ## Throws
+	///
+	/// * if `digests` is empty or the length of `data` is not a multiple of its length.
+	fn hash_leaves<H>(
+		&mut self,
+		data: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		prefix: &[u8],
+		digests: &mut [MaybeUninit<Output<H::Digest>>],
+	) -> Result<(), Error>
+	where
+		F: TowerField,
+		H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>;
+
 	/// Computes the elementwise application of a compiled arithmetic expression to multiple input
 	/// slices.
 	///
@@ -449,6 +518,48 @@ pub trait KernelExecutor<F> {
 		accumulator: &mut Self::Value,
 	) -> Result<(), Error>;
 
+	/// A kernel-local operation that evaluates a composition polynomial over a chunk of the
+	/// hypercube, with the highest variable of the multilinears specialized to a point, and
+	/// returns the sum of the evaluations, scaled by a batching coefficient.
+	///
+	/// The input buffers hold the chunk's evaluations of $m$ multilinears with their highest
+	/// variable set to 0 and to 1. Mathematically, let $P_0, \ldots, P_{m-1}$ and
+	/// $Q_0, \ldots, Q_{m-1}$ be these buffers, each of length $2^n$ elements, $z$ be the point,
+	/// $c$ be the scaling coefficient (`batch_coeff`) and $C(X_0, \ldots, X_{m-1})$ be the
+	/// composition polynomial. The operation computes
+	///
+	/// $$
+	/// \sum_{i=0}^{2^n - 1} c C(P_0\[i\] + z (Q_0\[i\] - P_0\[i\]), \ldots,
+	/// P_{m-1}\[i\] + z (Q_{m-1}\[i\] - P_{m-1}\[i\])).
+	/// $$
+	///
+	/// The result is added back to an accumulator value. This is the summand of the sumcheck
+	/// round polynomial at $z$ contributed by the chunk, without materializing the multilinear
+	/// evaluations at $z$.
+	///
+	/// ## Arguments
+	///
+	/// * `evals_0` - the evaluations of the multilinears with the highest variable set to 0.
+	/// * `evals_1` - the evaluations of the multilinears with the highest variable set to 1.
+	/// * `point` - the value of the highest variable, $z$.
+	/// * `composition` - the compiled composition polynomial expression. This is an output of
+	///   [`ComputeLayer::compile_expr`].
+	/// * `batch_coeff` - the scaling coefficient.
+	/// * `accumulator` - the output where the result is accumulated to.
+	///
+	/// ## Throws
+	///
+	/// * unless `evals_0` and `evals_1` have the same number of rows and row length
+	fn sum_composition_evals_at_point(
+		&mut self,
+		evals_0: &SlicesBatch<<Self::Mem as ComputeMemory<F>>::FSlice<'_>>,
+		evals_1: &SlicesBatch<<Self::Mem as ComputeMemory<F>>::FSlice<'_>>,
+		point: F,
+		composition: &Self::ExprEval,
+		batch_coeff: F,
+		accumulator: &mut Self::Value,
+	) -> Result<(), Error>;
+
 	/// A kernel-local operation that performs point-wise addition of two input buffers into an
 	/// output buffer.
 	///
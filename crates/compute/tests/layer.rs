@@ -129,3 +129,27 @@ fn test_extrapolate_line() {
 	let mut device_memory = vec![B128::ZERO; 1 << (log_len + 3)];
 	binius_compute_test_utils::layer::test_extrapolate_line(&compute, &mut device_memory, log_len);
 }
+
+#[test]
+fn test_fold_multilinear() {
+	let log_len = 10;
+	let compute = <CpuLayer<B128>>::default();
+	let mut device_memory = vec![B128::ZERO; 1 << (log_len + 2)];
+	binius_compute_test_utils::layer::test_generic_fold_multilinear(
+		&compute,
+		&mut device_memory,
+		log_len,
+	);
+}
+
+#[test]
+fn test_sum_composition_evals_at_point() {
+	let log_len = 10;
+	let compute = <CpuLayer<B128>>::default();
+	let mut device_memory = vec![B128::ZERO; 1 << (log_len + 2)];
+	binius_compute_test_utils::layer::test_generic_sum_composition_evals_at_point(
+		&compute,
+		&mut device_memory,
+		log_len,
+	);
+}
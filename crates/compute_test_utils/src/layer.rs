@@ -789,3 +789,125 @@ pub fn test_generic_compute_composite<'a, F: Field, Hal: ComputeLayer<F>>(
 		assert_eq!(*output, input_0_host[i] * input_1_host[i])
 	}
 }
+
+pub fn test_generic_fold_multilinear<'a, F: Field, Hal: ComputeLayer<F>>(
+	hal: &Hal,
+	dev_mem: FSliceMut<'a, F, Hal>,
+	log_len: usize,
+) {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mut host_mem = hal.host_alloc(3 * (1 << log_len));
+	let host_alloc = BumpAllocator::<F, CpuMemory>::new(host_mem.as_mut());
+
+	let evals_host = host_alloc.alloc(2 << log_len).unwrap();
+	let folded_host = host_alloc.alloc(1 << log_len).unwrap();
+
+	evals_host.fill_with(|| F::random(&mut rng));
+
+	let dev_alloc = BumpAllocator::<F, Hal::DevMem>::new(dev_mem);
+	let mut evals_dev = dev_alloc.alloc(2 << log_len).unwrap();
+	let mut folded_dev = dev_alloc.alloc(1 << log_len).unwrap();
+	hal.copy_h2d(evals_host, &mut evals_dev).unwrap();
+
+	let challenge = F::random(&mut rng);
+
+	hal.execute(|exec| {
+		exec.fold_multilinear(Hal::DevMem::as_const(&evals_dev), challenge, &mut folded_dev)?;
+		Ok(Vec::new())
+	})
+	.unwrap();
+
+	hal.copy_d2h(Hal::DevMem::as_const(&folded_dev), folded_host)
+		.unwrap();
+
+	// Folding binds the highest variable, which selects between the two halves.
+	let (evals_0, evals_1) = evals_host.split_at(1 << log_len);
+	let expected_result = iter::zip(evals_0, evals_1)
+		.map(|(x0, x1)| extrapolate_line_scalar(*x0, *x1, challenge))
+		.collect::<Vec<_>>();
+	assert_eq!(folded_host, &expected_result);
+}
+
+pub fn test_generic_sum_composition_evals_at_point<'a, F: Field, Hal: ComputeLayer<F>>(
+	hal: &Hal,
+	dev_mem: FSliceMut<'a, F, Hal>,
+	log_len: usize,
+) {
+	let mut rng = StdRng::seed_from_u64(0);
+	let log_min_chunk_size = 3;
+
+	let mut host_mem = hal.host_alloc(4 * (1 << log_len));
+	let host_alloc = BumpAllocator::<F, CpuMemory>::new(host_mem.as_mut());
+
+	let evals_host = repeat_with(|| {
+		let evals = host_alloc.alloc(1 << log_len).unwrap();
+		evals.fill_with(|| F::random(&mut rng));
+		evals
+	})
+	.take(4)
+	.collect::<Vec<_>>();
+
+	let dev_alloc = BumpAllocator::<F, Hal::DevMem>::new(dev_mem);
+	let evals_dev = evals_host
+		.iter()
+		.map(|evals| {
+			let mut evals_dev = dev_alloc.alloc(1 << log_len).unwrap();
+			hal.copy_h2d(evals, &mut evals_dev).unwrap();
+			Hal::DevMem::to_const(evals_dev)
+		})
+		.collect::<Vec<_>>();
+
+	let point = F::random(&mut rng);
+	let batch_coeff = F::random(&mut rng);
+
+	// The composition has degree 3, so that it is not determined by its values at 0 and 1.
+	let expr =
+		ArithCircuit::var(0) * ArithCircuit::var(1) * ArithCircuit::var(0) + ArithCircuit::var(1);
+	let eval = hal.compile_expr(&expr).unwrap();
+
+	let [actual] = hal
+		.execute(|exec| {
+			let mem_maps = evals_dev
+				.iter()
+				.map(|&data| KernelMemMap::Chunked {
+					data,
+					log_min_chunk_size,
+				})
+				.collect();
+			exec.accumulate_kernels(
+				|kernel_exec, _log_chunks, kernel_data| {
+					let row_len = kernel_data[0].len();
+					let kernel_data = kernel_data
+						.iter()
+						.map(|buffer| buffer.to_ref())
+						.collect::<Vec<_>>();
+					let (evals_0, evals_1) = kernel_data.split_at(2);
+					let mut res = kernel_exec.decl_value(F::ZERO)?;
+					kernel_exec.sum_composition_evals_at_point(
+						&SlicesBatch::new(evals_0.to_vec(), row_len),
+						&SlicesBatch::new(evals_1.to_vec(), row_len),
+						point,
+						&eval,
+						batch_coeff,
+						&mut res,
+					)?;
+					Ok(vec![res])
+				},
+				mem_maps,
+			)
+		})
+		.unwrap()
+		.try_into()
+		.unwrap();
+
+	let expected = (0..1 << log_len)
+		.map(|i| {
+			let x = extrapolate_line_scalar(evals_host[0][i], evals_host[2][i], point);
+			let y = extrapolate_line_scalar(evals_host[1][i], evals_host[3][i], point);
+			expr.evaluate(&[x, y]).unwrap()
+		})
+		.sum::<F>()
+		* batch_coeff;
+	assert_eq!(actual, expected);
+}
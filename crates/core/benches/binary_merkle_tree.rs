@@ -2,7 +2,11 @@
 
 use std::iter::repeat_with;
 
-use binius_core::merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver};
+use binius_core::{
+	fiat_shamir::HasherChallenger,
+	merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver, MerkleTreeScheme},
+	transcript::{ProverTranscript, VerifierTranscript},
+};
 use binius_field::{BinaryField128b, Field};
 use binius_hash::{
 	PseudoCompressionFunction, Vision32Compression, Vision32ParallelDigest, VisionHasherDigest,
@@ -11,16 +15,17 @@ use binius_hash::{
 };
 use criterion::{Criterion, Throughput, criterion_group, criterion_main};
 use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
-use rand::thread_rng;
+use rand::{Rng, thread_rng};
 
 const LOG_ELEMS: usize = 17;
 const LOG_ELEMS_IN_LEAF: usize = 4;
+const N_QUERIES: usize = 128;
 
 type F = BinaryField128b;
 
 fn bench_binary_merkle_tree<H, C>(c: &mut Criterion, compression: C, hash_name: &str)
 where
-	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset + Clone + Sync>,
 	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
 {
 	let merkle_prover = BinaryMerkleTreeProver::<_, H, C>::new(compression);
@@ -46,6 +51,78 @@ fn bench_groestl_merkle_tree(c: &mut Criterion) {
 	bench_binary_merkle_tree::<Groestl256, _>(c, Groestl256ByteCompression, "Grøstl-256");
 }
 
+fn bench_groestl_merkle_openings(c: &mut Criterion) {
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let scheme = merkle_prover.scheme();
+	let mut rng = thread_rng();
+	let data: Vec<F> = repeat_with(|| Field::random(&mut rng))
+		.take(1 << (LOG_ELEMS + LOG_ELEMS_IN_LEAF))
+		.collect();
+	let (_, tree) = merkle_prover.commit(&data, 1 << LOG_ELEMS_IN_LEAF).unwrap();
+	let layer_depth = scheme.optimal_verify_layer(N_QUERIES, LOG_ELEMS);
+	let layer = merkle_prover.layer(&tree, layer_depth).unwrap();
+
+	let indices = repeat_with(|| rng.gen_range(0..1 << LOG_ELEMS))
+		.take(N_QUERIES)
+		.collect::<Vec<_>>();
+	let values = indices
+		.iter()
+		.map(|&index| &data[index << LOG_ELEMS_IN_LEAF..(index + 1) << LOG_ELEMS_IN_LEAF])
+		.collect::<Vec<_>>();
+
+	let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	for &index in &indices {
+		merkle_prover
+			.prove_opening(&tree, layer_depth, index, &mut transcript.message())
+			.unwrap();
+	}
+	let single_proof = transcript.finalize();
+
+	let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	merkle_prover
+		.prove_openings(&tree, layer_depth, &indices, &mut transcript.message())
+		.unwrap();
+	let batch_proof = transcript.finalize();
+
+	let mut group = c.benchmark_group("merkle_tree/Grøstl-256/verify");
+	group.throughput(Throughput::Elements(N_QUERIES as u64));
+	group.bench_function(format!("{N_QUERIES} openings one by one"), |b| {
+		b.iter(|| {
+			let mut transcript =
+				VerifierTranscript::<HasherChallenger<Groestl256>>::new(single_proof.clone());
+			for (&index, values) in indices.iter().zip(&values) {
+				scheme
+					.verify_opening(
+						index,
+						values,
+						layer_depth,
+						LOG_ELEMS,
+						layer,
+						&mut transcript.message(),
+					)
+					.unwrap();
+			}
+		});
+	});
+	group.bench_function(format!("{N_QUERIES} openings batched"), |b| {
+		b.iter(|| {
+			let mut transcript =
+				VerifierTranscript::<HasherChallenger<Groestl256>>::new(batch_proof.clone());
+			scheme
+				.verify_openings(
+					&indices,
+					&values,
+					layer_depth,
+					LOG_ELEMS,
+					layer,
+					&mut transcript.message(),
+				)
+				.unwrap();
+		});
+	});
+	group.finish()
+}
+
 fn bench_vision_merkle_tree(c: &mut Criterion) {
 	bench_binary_merkle_tree::<VisionHasherDigest, _>(c, Vision32Compression, "Vision-32");
 	bench_binary_merkle_tree::<Vision32ParallelDigest, _>(
@@ -56,4 +133,9 @@ fn bench_vision_merkle_tree(c: &mut Criterion) {
 }
 
 criterion_main!(binary_merkle_tree);
-criterion_group!(binary_merkle_tree, bench_groestl_merkle_tree, bench_vision_merkle_tree);
+criterion_group!(
+	binary_merkle_tree,
+	bench_groestl_merkle_tree,
+	bench_groestl_merkle_openings,
+	bench_vision_merkle_tree
+);
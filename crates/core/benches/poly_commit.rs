@@ -6,7 +6,10 @@ use binius_core::{
 	merkle_tree::BinaryMerkleTreeProver,
 	piop,
 	piop::CommitMeta,
-	protocols::{fri, fri::FRIParams},
+	protocols::{
+		fri,
+		fri::{FRIParams, SoundnessModel},
+	},
 };
 use binius_field::{
 	AESTowerField32b, AESTowerField128b, BinaryField, BinaryField32b, BinaryField128b,
@@ -118,6 +121,7 @@ where
 		merkle_scheme,
 		SECURITY_BITS,
 		LOG_INV_RATE,
+		SoundnessModel::Provable,
 	)
 	.unwrap();
 
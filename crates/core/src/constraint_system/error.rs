@@ -56,6 +56,11 @@ pub enum Error {
 	#[error("Non-zero oracles contain zeros")]
 	Zeros,
 
+	#[error(
+		"secret oracle {id} must be constrained by at most one table and not used by flushes, non-zero checks, exponentiations, or other oracles"
+	)]
+	SecretOracleNotIsolated { id: OracleId },
+
 	#[error("False eq evaluation claim")]
 	FalseEqEvaluationClaim,
 
@@ -4,9 +4,11 @@ pub mod channel;
 mod common;
 pub mod error;
 pub mod exp;
+mod params;
 mod prove;
 pub mod validate;
 mod verify;
+mod zk;
 
 #[cfg(test)]
 mod tests;
@@ -17,6 +19,9 @@ use binius_utils::{SerializationMode, SerializeBytes};
 use channel::Flush;
 use digest::{Digest, Output};
 use exp::Exp;
+pub use params::{
+	PresetChallenger, PresetCompression, PresetHash, PresetParallelHash, SecurityParams,
+};
 pub use prove::prove;
 pub use verify::verify;
 
@@ -0,0 +1,104 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_hash::groestl::{Groestl256, Groestl256ByteCompression, Groestl256Parallel};
+
+use crate::{
+	fiat_shamir::HasherChallenger, piop::CommitParamsBuilder, protocols::fri::SoundnessModel,
+};
+
+/// The hash function used to commit the Merkle leaves in the security presets, on the verifier.
+pub type PresetHash = Groestl256;
+
+/// The hash function used to commit the Merkle leaves in the security presets, on the prover.
+///
+/// This computes the same digests as [`PresetHash`], hashing several leaves at once where the
+/// target architecture supports it.
+pub type PresetParallelHash = Groestl256Parallel;
+
+/// The Merkle compression function of the security presets.
+pub type PresetCompression = Groestl256ByteCompression;
+
+/// The Fiat–Shamir challenger of the security presets.
+pub type PresetChallenger = HasherChallenger<Groestl256>;
+
+/// Security parameters of a constraint system proof, as passed to [`super::prove`] and
+/// [`super::verify`].
+///
+/// The number of FRI test queries is derived from the target security level, the Reed–Solomon
+/// rate, the soundness analysis, and the proof-of-work grinding before the query phase, so
+/// together they fix the soundness of the polynomial commitment scheme. The presets bundle
+/// combinations that are vetted for use with the hash functions
+/// [`PresetHash`], [`PresetParallelHash`], and [`PresetCompression`], and the challenger
+/// [`PresetChallenger`].
+///
+/// There is no 128-bit preset: the folding soundness error over the 128-bit extension field grows
+/// with the codeword length, so a 128-bit target cannot be met for any codeword, whatever the
+/// number of queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecurityParams {
+	/// The target security level in bits.
+	pub security_bits: usize,
+	/// The binary logarithm of the inverse Reed–Solomon code rate.
+	pub log_inv_rate: usize,
+	/// The soundness analysis that sizes the number of FRI test queries.
+	pub soundness_model: SoundnessModel,
+	/// The number of leading zero bits of the proof-of-work the prover grinds before the FRI
+	/// query phase.
+	pub grinding_bits: usize,
+}
+
+impl SecurityParams {
+	/// Constructs custom security parameters, with test queries sized by the provable soundness
+	/// analysis and no grinding.
+	pub const fn new(security_bits: usize, log_inv_rate: usize) -> Self {
+		Self {
+			security_bits,
+			log_inv_rate,
+			soundness_model: SoundnessModel::Provable,
+			grinding_bits: 0,
+		}
+	}
+
+	/// Sets the soundness analysis that sizes the number of FRI test queries.
+	pub const fn with_soundness_model(mut self, soundness_model: SoundnessModel) -> Self {
+		self.soundness_model = soundness_model;
+		self
+	}
+
+	/// Sets the number of leading zero bits of the proof-of-work the prover grinds before the FRI
+	/// query phase, each of which halves the soundness error the test queries must achieve.
+	pub const fn with_grinding_bits(mut self, grinding_bits: usize) -> Self {
+		self.grinding_bits = grinding_bits;
+		self
+	}
+
+	/// Returns parameters targeting 80 bits of security, for tests and short-lived proofs.
+	///
+	/// The test queries are sized by the provable soundness analysis, after 12 bits of grinding.
+	pub const fn preset_80_bit() -> Self {
+		Self::new(80, 1)
+			.with_soundness_model(SoundnessModel::Provable)
+			.with_grinding_bits(12)
+	}
+
+	/// Returns parameters targeting 100 bits of security, balancing prover time and proof size.
+	///
+	/// The test queries are sized by the provable soundness analysis, after 16 bits of grinding.
+	pub const fn preset_100_bit() -> Self {
+		Self::new(100, 1)
+			.with_soundness_model(SoundnessModel::Provable)
+			.with_grinding_bits(16)
+	}
+
+	/// Starts choosing the FRI parameters of a commitment with the Merkle tree scheme
+	/// `merkle_scheme` under these security parameters.
+	pub const fn commit_params<'a, MTScheme>(
+		&self,
+		merkle_scheme: &'a MTScheme,
+	) -> CommitParamsBuilder<'a, MTScheme> {
+		CommitParamsBuilder::new(merkle_scheme, self.security_bits)
+			.with_log_inv_rate(self.log_inv_rate)
+			.with_soundness_model(self.soundness_model)
+			.with_grinding_bits(self.grinding_bits)
+	}
+}
@@ -2,7 +2,11 @@
 
 use std::{env, iter, marker::PhantomData};
 
-use binius_compute::{ComputeLayer, ComputeMemory, FSliceMut, cpu::CpuMemory};
+use binius_compute::{
+	ComputeLayer, ComputeMemory, FSliceMut,
+	alloc::{BumpAllocator, ComputeAllocator, HostBumpAllocator},
+	cpu::CpuMemory,
+};
 use binius_field::{
 	BinaryField, ExtensionField, Field, PackedExtension, PackedField, PackedFieldIndexable,
 	RepackedExtension, TowerField,
@@ -27,10 +31,11 @@ use itertools::chain;
 use tracing::instrument;
 
 use super::{
-	ConstraintSystem, Proof,
+	ConstraintSystem, Proof, SecurityParams,
 	channel::Boundary,
 	error::Error,
 	verify::{make_flush_oracles, max_n_vars_and_skip_rounds},
+	zk,
 };
 use crate::{
 	constraint_system::{
@@ -59,6 +64,9 @@ use crate::{
 };
 
 /// Generates a proof that a witness satisfies a constraint system with the standard FRI PCS.
+///
+/// The FRI parameters of the commitment are chosen under `security_params`, which must match
+/// between the prover and the verifier.
 #[allow(clippy::too_many_arguments)]
 #[instrument("constraint_system::prove", skip_all, level = "debug")]
 pub fn prove<Hal, U, Tower, Hash, Compress, Challenger_, Backend>(
@@ -66,8 +74,7 @@ pub fn prove<Hal, U, Tower, Hash, Compress, Challenger_, Backend>(
 	host_mem: <CpuMemory as ComputeMemory<Tower::B128>>::FSliceMut<'_>,
 	dev_mem: FSliceMut<'_, Tower::B128, Hal>,
 	constraint_system: &ConstraintSystem<FExt<Tower>>,
-	log_inv_rate: usize,
-	security_bits: usize,
+	security_params: &SecurityParams,
 	constraint_system_digest: &Output<Hash::Digest>,
 	boundaries: &[Boundary<FExt<Tower>>],
 	table_sizes: &[usize],
@@ -156,8 +163,26 @@ where
 	// Stable sort constraint sets in ascending order by number of variables.
 	table_constraints.sort_by_key(|constraint_set| constraint_set.n_vars);
 
+	// Tables over secret oracles are proven separately, over extended columns that are committed
+	// along with the masking polynomials.
+	let (table_constraints, secret_tables) = zk::split_secret_tables(
+		&mut oracles,
+		table_constraints,
+		&flushes,
+		&non_zero_oracle_ids,
+		&exponents,
+	)?;
+	let hiding = !oracles.secret_ids().is_empty();
+	let mut rng = rand::thread_rng();
+	let masks = zk::make_secret_table_witnesses(&mut witness, &secret_tables, &mut rng)?;
+
 	// Commit polynomials
 	let merkle_prover = BinaryMerkleTreeProver::<_, Hash, _>::new(Compress::default());
+	let merkle_prover = if hiding {
+		merkle_prover.with_salted_leaves(&mut rng)
+	} else {
+		merkle_prover
+	};
 	let merkle_scheme = merkle_prover.scheme();
 
 	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles)?;
@@ -168,12 +193,11 @@ where
 		&witness,
 	)?;
 
-	let fri_params = piop::make_commit_params_with_optimal_arity::<_, FEncode<Tower>, _>(
-		&commit_meta,
-		merkle_scheme,
-		security_bits,
-		log_inv_rate,
-	)?;
+	let mut commit_params = security_params.commit_params(merkle_scheme);
+	if hiding {
+		commit_params = commit_params.with_hiding();
+	}
+	let fri_params = commit_params.build::<_, FEncode<Tower>>(&commit_meta)?;
 	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace())?
 		.precompute_twiddles()
 		.multithreaded();
@@ -181,11 +205,20 @@ where
 	let commit_span =
 		tracing::info_span!("[phase] Commit", phase = "commit", perfetto_category = "phase.main")
 			.entered();
-	let CommitOutput {
-		commitment,
-		committed,
-		codeword,
-	} = piop::commit(&fri_params, &ntt, &merkle_prover, &committed_multilins)?;
+	let (
+		CommitOutput {
+			commitment,
+			committed,
+			codeword,
+		},
+		hiding_multilin,
+	) = if hiding {
+		let (commit_output, hiding_multilin) =
+			piop::commit_hiding(&fri_params, &ntt, &merkle_prover, &committed_multilins, &mut rng)?;
+		(commit_output, Some(hiding_multilin))
+	} else {
+		(piop::commit(&fri_params, &ntt, &merkle_prover, &committed_multilins)?, None)
+	};
 	drop(commit_span);
 
 	// Observe polynomial commitment
@@ -434,6 +467,16 @@ where
 	let zerocheck_eval_claims =
 		sumcheck::make_zerocheck_eval_claims(zerocheck_oracle_metas, zerocheck_output)?;
 
+	let (secret_eval_claims, mask_eval_claims) = zk::prove_secret_tables(
+		&mut oracles,
+		&mut witness,
+		&secret_tables,
+		masks,
+		&mut transcript,
+		domain_factory.clone(),
+		backend,
+	)?;
+
 	drop(zerocheck_span);
 
 	let evalcheck_span = tracing::info_span!(
@@ -450,20 +493,29 @@ where
 	} = greedy_evalcheck::prove::<_, _, FDomain<Tower>, _, _>(
 		&mut oracles,
 		&mut witness,
-		chain!(prodcheck_eval_claims, zerocheck_eval_claims, exp_eval_claims,),
+		chain!(prodcheck_eval_claims, zerocheck_eval_claims, secret_eval_claims, exp_eval_claims,),
 		standard_switchover_heuristic(-2),
 		&mut transcript,
 		&domain_factory,
 		backend,
 	)?;
 
-	// Reduce committed evaluation claims to PIOP sumcheck claims
+	// Decompose any remaining claims on virtual oracles onto their committed ancestors, and reduce
+	// the committed evaluation claims to PIOP sumcheck claims
+	let eval_claims = ring_switch::prove_virtual_claims(
+		&oracles,
+		&oracle_to_commit_index,
+		&committed_multilins,
+		&eval_claims,
+		&mut transcript,
+	)?;
 	let system = ring_switch::EvalClaimSystem::new(
 		&oracles,
 		&commit_meta,
 		&oracle_to_commit_index,
 		&eval_claims,
 	)?;
+	tracing::debug!(summary = %system.summary(), "ring-switch claim system");
 
 	drop(evalcheck_span);
 
@@ -473,10 +525,51 @@ where
 		perfetto_category = "phase.main"
 	)
 	.entered();
+	// The ring switch takes its memory from the host and device memory of the PIOP compiler when
+	// it fits, since the memory is reclaimed before the PIOP compiler runs. With device memory, the
+	// partial evaluations are computed by the compute layer.
+	let mut host_alloc = HostBumpAllocator::new(host_mem);
+	let mut dev_alloc = BumpAllocator::<_, Hal::DevMem>::new(dev_mem);
+	let host_mem_fits = host_alloc.capacity() >= ring_switch::prove_host_mem_size(&system);
 	let ring_switch::ReducedWitness {
-		transparents: transparent_multilins,
-		sumcheck_claims: piop_sumcheck_claims,
-	} = ring_switch::prove(&system, &committed_multilins, &mut transcript, memoized_data)?;
+		transparents: mut transparent_multilins,
+		sumcheck_claims: mut piop_sumcheck_claims,
+	} = if host_mem_fits
+		&& dev_alloc.capacity() >= ring_switch::prove_dev_mem_size::<_, Hal::DevMem>(&system)
+	{
+		ring_switch::prove_with_hal::<_, Tower, _, _, _>(
+			hal,
+			&system,
+			&committed_multilins,
+			&mut transcript,
+			memoized_data,
+			&mut host_alloc,
+			&mut dev_alloc,
+		)?
+	} else if host_mem_fits {
+		ring_switch::prove_with_host_alloc::<Tower, _, _, _>(
+			&system,
+			&committed_multilins,
+			&mut transcript,
+			memoized_data,
+			&mut host_alloc,
+		)?
+	} else {
+		ring_switch::prove::<Tower, _, _, _>(
+			&system,
+			&committed_multilins,
+			&mut transcript,
+			memoized_data,
+		)?
+	};
+	zk::append_mask_piop_claims(
+		mask_eval_claims,
+		&oracle_to_commit_index,
+		&mut transparent_multilins,
+		&mut piop_sumcheck_claims,
+		|transparent| transparent.n_vars(),
+		zk::mask_weights_witness,
+	)?;
 	drop(ring_switch_span);
 
 	// Prove evaluation claims using PIOP compiler
@@ -486,21 +579,41 @@ where
 		perfetto_category = "phase.main"
 	)
 	.entered();
-	piop::prove(
-		hal,
-		host_mem,
-		dev_mem,
-		&fri_params,
-		&ntt,
-		&merkle_prover,
-		&commit_meta,
-		committed,
-		&codeword,
-		&committed_multilins,
-		&transparent_multilins,
-		&piop_sumcheck_claims,
-		&mut transcript,
-	)?;
+	if let Some(hiding_multilin) = &hiding_multilin {
+		piop::prove_zk(
+			hal,
+			&mut host_alloc,
+			&mut dev_alloc,
+			&fri_params,
+			&ntt,
+			&merkle_prover,
+			&commit_meta,
+			&committed,
+			&codeword,
+			&committed_multilins,
+			hiding_multilin,
+			&transparent_multilins,
+			&piop_sumcheck_claims,
+			&mut rng,
+			&mut transcript,
+		)?;
+	} else {
+		piop::prove(
+			hal,
+			&mut host_alloc,
+			&mut dev_alloc,
+			&fri_params,
+			&ntt,
+			&merkle_prover,
+			&commit_meta,
+			&committed,
+			&codeword,
+			&committed_multilins,
+			&transparent_multilins,
+			&piop_sumcheck_claims,
+			&mut transcript,
+		)?;
+	}
 	drop(piop_compiler_span);
 
 	let proof = Proof {
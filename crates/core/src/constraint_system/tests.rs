@@ -1,18 +1,35 @@
 // Copyright 2025 Irreducible Inc.
 
+use std::iter::{self, repeat_with};
+
+use binius_compute::cpu::CpuLayer;
 use binius_field::{
 	Field, PackedBinaryField2x128b, PackedBinaryField256x1b, PackedField, TowerField,
-	arch::OptimalUnderlier256b, tower::CanonicalTowerFamily,
+	arch::{OptimalUnderlier128b, OptimalUnderlier256b},
+	as_packed_field::PackedType,
+	packed::pack_slice,
+	tower::CanonicalTowerFamily,
+};
+use binius_hal::make_portable_backend;
+use binius_math::{
+	ArithCircuit, B1, B32, B128, MLEDirectAdapter, MLEEmbeddingAdapter, MultilinearExtension,
 };
-use binius_math::{B1, B128, MLEDirectAdapter, MLEEmbeddingAdapter, MultilinearExtension};
+use rand::{SeedableRng, rngs::StdRng};
 
 use crate::{
 	constraint_system::{
+		ConstraintSystem, PresetChallenger, PresetCompression, PresetHash, Proof, SecurityParams,
 		channel::{Flush, FlushDirection, OracleOrConst},
+		error::Error,
+		prove,
 		prove::make_masked_flush_witnesses,
+		verify,
 	},
-	oracle::MultilinearOracleSet,
-	witness::MultilinearExtensionIndex,
+	merkle_tree::BinaryMerkleTreeProver,
+	oracle::{Constraint, ConstraintPredicate, ConstraintSet, MultilinearOracleSet, OracleId},
+	piop::CommitMeta,
+	protocols::fri::security_report,
+	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
 
 #[test]
@@ -56,3 +73,159 @@ fn test_make_masked_flush_witnesses_handles_small_n_vars() {
 	)
 	.unwrap();
 }
+
+#[test]
+fn test_security_presets_reach_their_security_level() {
+	let merkle_prover =
+		BinaryMerkleTreeProver::<_, PresetHash, _>::new(PresetCompression::default());
+	let commit_meta = CommitMeta::with_vars([24]);
+	let fri_params = |params: SecurityParams| {
+		params
+			.commit_params(merkle_prover.scheme())
+			.build::<B128, B32>(&commit_meta)
+	};
+
+	let mut n_test_queries = Vec::new();
+	for preset in [
+		SecurityParams::preset_80_bit(),
+		SecurityParams::preset_100_bit(),
+	] {
+		let params = fri_params(preset).unwrap();
+		assert_eq!(params.grinding_bits(), preset.grinding_bits);
+		assert_eq!(params.soundness_model(), preset.soundness_model);
+
+		let report = security_report(&params, merkle_prover.scheme());
+		assert!(report.interactive_bits() >= preset.security_bits as f64);
+		n_test_queries.push(params.n_test_queries());
+	}
+	assert!(0 < n_test_queries[0] && n_test_queries[0] < n_test_queries[1]);
+	assert!(fri_params(SecurityParams::new(128, 2)).is_err());
+}
+
+type U = OptimalUnderlier128b;
+
+const SECRET_N_VARS: usize = 6;
+
+fn zero_constraint_set(
+	oracle_ids: Vec<OracleId>,
+	composition: ArithCircuit<B128>,
+) -> ConstraintSet<B128> {
+	ConstraintSet {
+		n_vars: SECRET_N_VARS,
+		oracle_ids,
+		constraints: vec![Constraint {
+			name: "zero".into(),
+			composition,
+			predicate: ConstraintPredicate::Zero,
+		}],
+	}
+}
+
+// Constrains the secret column a with a * b + c = 0, and the public column d with b + d = 0.
+fn secret_column_constraint_system() -> (ConstraintSystem<B128>, [OracleId; 4]) {
+	let mut oracles = MultilinearOracleSet::<B128>::new();
+	let [a, b, c, d] = oracles.add_committed_multiple(SECRET_N_VARS, B32::TOWER_LEVEL);
+	oracles.mark_secret(a).unwrap();
+
+	let table_constraints = vec![
+		zero_constraint_set(
+			vec![a, b, c],
+			ArithCircuit::var(0) * ArithCircuit::var(1) + ArithCircuit::var(2),
+		),
+		zero_constraint_set(vec![b, d], ArithCircuit::var(0) + ArithCircuit::var(1)),
+	];
+	let constraint_system = ConstraintSystem {
+		oracles,
+		table_constraints,
+		non_zero_oracle_ids: vec![],
+		flushes: vec![],
+		exponents: vec![],
+		channel_count: 0,
+	};
+	(constraint_system, [a, b, c, d])
+}
+
+fn b32_column(values: &[B32]) -> MultilinearWitness<'static, PackedType<U, B128>> {
+	let mle =
+		MultilinearExtension::new(SECRET_N_VARS, pack_slice::<PackedType<U, B32>>(values)).unwrap();
+	MLEEmbeddingAdapter::<_, PackedType<U, B128>>::from(mle).upcast_arc_dyn()
+}
+
+#[test]
+fn test_prove_verify_with_secret_column() {
+	let (constraint_system, [a, b, c, d]) = secret_column_constraint_system();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let a_values = repeat_with(|| B32::random(&mut rng))
+		.take(1 << SECRET_N_VARS)
+		.collect::<Vec<_>>();
+	let b_values = repeat_with(|| B32::random(&mut rng))
+		.take(1 << SECRET_N_VARS)
+		.collect::<Vec<_>>();
+	let c_values = iter::zip(&a_values, &b_values)
+		.map(|(&a, &b)| a * b)
+		.collect::<Vec<_>>();
+
+	let mut witness = MultilinearExtensionIndex::<PackedType<U, B128>>::new();
+	witness
+		.update_multilin_poly([
+			(a, b32_column(&a_values)),
+			(b, b32_column(&b_values)),
+			(c, b32_column(&c_values)),
+			(d, b32_column(&b_values)),
+		])
+		.unwrap();
+
+	let security_params = SecurityParams::preset_80_bit();
+	let digest = constraint_system.digest::<PresetHash>();
+
+	let hal = CpuLayer::<B128>::default();
+	let mut host_mem = vec![B128::ZERO; 1 << 16];
+	let mut dev_mem = vec![B128::ZERO; 1 << 20];
+	let proof =
+		prove::<_, U, CanonicalTowerFamily, PresetHash, PresetCompression, PresetChallenger, _>(
+			&hal,
+			host_mem.as_mut_slice(),
+			dev_mem.as_mut_slice(),
+			&constraint_system,
+			&security_params,
+			&digest,
+			&[],
+			&[],
+			witness,
+			&make_portable_backend(),
+		)
+		.unwrap();
+
+	verify::<U, CanonicalTowerFamily, PresetHash, PresetCompression, PresetChallenger>(
+		&constraint_system,
+		&security_params,
+		&digest,
+		&[],
+		proof,
+	)
+	.unwrap();
+}
+
+#[test]
+fn test_flushed_secret_column_is_rejected() {
+	let (mut constraint_system, [a, ..]) = secret_column_constraint_system();
+	constraint_system.flushes.push(Flush {
+		oracles: vec![OracleOrConst::Oracle(a)],
+		channel_id: 0,
+		direction: FlushDirection::Push,
+		selectors: vec![],
+		multiplicity: 1,
+	});
+	constraint_system.channel_count = 1;
+
+	let digest = constraint_system.digest::<PresetHash>();
+	let result = verify::<U, CanonicalTowerFamily, PresetHash, PresetCompression, PresetChallenger>(
+		&constraint_system,
+		&SecurityParams::new(80, 1),
+		&digest,
+		&[],
+		Proof { transcript: vec![] },
+	);
+	assert!(matches!(result, Err(Error::SecretOracleNotIsolated { id }) if id == a));
+}
@@ -12,10 +12,11 @@ use itertools::{Itertools, chain};
 use tracing::instrument;
 
 use super::{
-	ConstraintSystem, Proof,
+	ConstraintSystem, Proof, SecurityParams,
 	channel::{Boundary, OracleOrConst},
 	error::{Error, VerificationError},
 	exp::{self, reorder_exponents},
+	zk,
 };
 use crate::{
 	constraint_system::{
@@ -37,12 +38,14 @@ use crate::{
 };
 
 /// Verifies a proof against a constraint system.
+///
+/// The FRI parameters of the commitment are chosen under `security_params`, which must match
+/// between the prover and the verifier.
 #[instrument("constraint_system::verify", skip_all, level = "debug")]
 #[allow(clippy::too_many_arguments)]
 pub fn verify<U, Tower, Hash, Compress, Challenger_>(
 	constraint_system: &ConstraintSystem<FExt<Tower>>,
-	log_inv_rate: usize,
-	security_bits: usize,
+	security_params: &SecurityParams,
 	constraint_system_digest: &Output<Hash>,
 	boundaries: &[Boundary<FExt<Tower>>],
 	proof: Proof,
@@ -51,7 +54,7 @@ where
 	U: TowerUnderlier<Tower>,
 	Tower: TowerFamily,
 	Tower::B128: binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower>,
-	Hash: Digest + BlockSizeUser + OutputSizeUser,
+	Hash: Digest + BlockSizeUser + OutputSizeUser + Clone + Send + Sync,
 	Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
 	Challenger_: Challenger + Default,
 {
@@ -82,19 +85,32 @@ where
 	// Stable sort constraint sets in ascending order by number of variables.
 	table_constraints.sort_by_key(|constraint_set| constraint_set.n_vars);
 
+	let (table_constraints, secret_tables) = zk::split_secret_tables(
+		&mut oracles,
+		table_constraints,
+		&flushes,
+		&non_zero_oracle_ids,
+		&exponents,
+	)?;
+	let hiding = !oracles.secret_ids().is_empty();
+
 	let Proof { transcript } = proof;
 
 	let mut transcript = VerifierTranscript::<Challenger_>::new(transcript);
 	transcript.observe().write_slice(boundaries);
 
 	let merkle_scheme = BinaryMerkleTreeScheme::<_, Hash, _>::new(Compress::default());
+	let merkle_scheme = if hiding {
+		merkle_scheme.with_salted_leaves()
+	} else {
+		merkle_scheme
+	};
 	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles)?;
-	let fri_params = piop::make_commit_params_with_optimal_arity::<_, FEncode<Tower>, _>(
-		&commit_meta,
-		&merkle_scheme,
-		security_bits,
-		log_inv_rate,
-	)?;
+	let mut commit_params = security_params.commit_params(&merkle_scheme);
+	if hiding {
+		commit_params = commit_params.with_hiding();
+	}
+	let fri_params = commit_params.build::<_, FEncode<Tower>>(&commit_meta)?;
 
 	// Read polynomial commitment polynomials
 	let mut reader = transcript.message();
@@ -188,14 +204,19 @@ where
 	let zerocheck_eval_claims =
 		sumcheck::make_zerocheck_eval_claims(zerocheck_oracle_metas, zerocheck_output)?;
 
+	let (secret_eval_claims, mask_eval_claims) =
+		zk::verify_secret_tables(&mut oracles, &secret_tables, &mut transcript)?;
+
 	// Evalcheck
 	let eval_claims = greedy_evalcheck::verify(
 		&mut oracles,
-		chain!(prodcheck_eval_claims, zerocheck_eval_claims, exp_eval_claims,),
+		chain!(prodcheck_eval_claims, zerocheck_eval_claims, secret_eval_claims, exp_eval_claims,),
 		&mut transcript,
 	)?;
 
-	// Reduce committed evaluation claims to PIOP sumcheck claims
+	// Decompose any remaining claims on virtual oracles onto their committed ancestors, and reduce
+	// the committed evaluation claims to PIOP sumcheck claims
+	let eval_claims = ring_switch::verify_virtual_claims(&oracles, &eval_claims, &mut transcript)?;
 	let system = ring_switch::EvalClaimSystem::new(
 		&oracles,
 		&commit_meta,
@@ -204,20 +225,40 @@ where
 	)?;
 
 	let ring_switch::ReducedClaim {
-		transparents,
-		sumcheck_claims: piop_sumcheck_claims,
-	} = ring_switch::verify(&system, &mut transcript)?;
+		mut transparents,
+		sumcheck_claims: mut piop_sumcheck_claims,
+	} = ring_switch::verify::<Tower, _>(&system, &mut transcript)?;
+	zk::append_mask_piop_claims(
+		mask_eval_claims,
+		&oracle_to_commit_index,
+		&mut transparents,
+		&mut piop_sumcheck_claims,
+		|transparent| transparent.n_vars(),
+		zk::mask_weights_transparent,
+	)?;
 
 	// Prove evaluation claims using PIOP compiler
-	piop::verify(
-		&commit_meta,
-		&merkle_scheme,
-		&fri_params,
-		&commitment,
-		&transparents,
-		&piop_sumcheck_claims,
-		&mut transcript,
-	)?;
+	if hiding {
+		piop::verify_zk(
+			&commit_meta,
+			&merkle_scheme,
+			&fri_params,
+			&commitment,
+			&transparents,
+			&piop_sumcheck_claims,
+			&mut transcript,
+		)?;
+	} else {
+		piop::verify(
+			&commit_meta,
+			&merkle_scheme,
+			&fri_params,
+			&commitment,
+			&transparents,
+			&piop_sumcheck_claims,
+			&mut transcript,
+		)?;
+	}
 
 	transcript.finalize()?;
 
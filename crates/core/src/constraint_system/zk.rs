@@ -0,0 +1,546 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Proving the constraints of tables over secret oracles.
+//!
+//! A table constraining an oracle marked secret with [`MultilinearOracleSet::mark_secret`] is not
+//! proven with the batched zerocheck, whose round messages and final evaluations would leak the
+//! secret columns. For a table over $n$ variables instead:
+//!
+//! 1. Before the commitment, each secret column $s$ gets a committed extension $s'$ over $n + 1$
+//!    variables and the top field, with $s'(X, 0) = s(X)$ and uniformly random $s'(X, 1)$. The
+//!    other columns of the table are repeated to $n + 1$ variables. The prover also commits the
+//!    coefficients of a random masking polynomial, see [`crate::protocols::sumcheck::zk`].
+//! 2. After the commitment, the verifier samples $r$, and each zero constraint $C$ of the table
+//!    becomes the sumcheck constraint $\sum_{x, y} \text{eq}((r, 0), (x, y)) \cdot C(x, y) = 0$
+//!    over the extended columns, which only involves the rows with $y = 0$. The constraints are
+//!    proven with a masked sumcheck.
+//! 3. The sumcheck ends with the evaluations of the extended columns at a random point $(p, q)$,
+//!    where $s'(p, q) = (1 - q) \cdot s(p) + q \cdot s'(p, 1)$ is blinded by the random half. The
+//!    evaluation of the masking polynomial is reduced to a PIOP sumcheck claim on its committed
+//!    coefficients.
+//!
+//! The batch is committed with [`crate::piop::commit_hiding`] and opened with
+//! [`crate::piop::prove_zk`], so that neither the commitment nor the openings leak the committed
+//! columns. The secret oracles are only hidden if nothing else constrains them, which
+//! [`split_secret_tables`] checks.
+
+use std::iter;
+
+use binius_field::{
+	ExtensionField, Field, PackedExtension, PackedField, TowerField, packed::pack_slice,
+};
+use binius_hal::ComputationBackend;
+use binius_math::{
+	ArithCircuit, EvaluationDomainFactory, EvaluationOrder, MLEDirectAdapter, MultilinearExtension,
+	eq_ind_partial_eval,
+};
+use binius_utils::{
+	bail,
+	checked_arithmetics::{log2_ceil_usize, log2_strict_usize},
+	sparse_index::SparseIndex,
+};
+use itertools::chain;
+use rand::RngCore;
+
+use super::{
+	channel::{Flush, OracleOrConst},
+	error::Error,
+	exp::Exp,
+};
+use crate::{
+	fiat_shamir::{CanSample, Challenger},
+	oracle::{
+		Constraint, ConstraintPredicate, MultilinearOracleSet, MultilinearPolyVariant, OracleId,
+		SizedConstraintSet,
+	},
+	piop::PIOPSumcheckClaim,
+	polynomial::{self, MultivariatePoly},
+	protocols::{
+		evalcheck::EvalcheckMultilinearClaim,
+		sumcheck::{
+			self, standard_switchover_heuristic,
+			zk::{MaskedSumcheckProver, MaskingPolynomial, mask_claim, mask_eval_weights},
+		},
+	},
+	transcript::{ProverTranscript, VerifierTranscript},
+	transparent::eq_ind::EqIndPartialEval,
+	witness::{MultilinearExtensionIndex, MultilinearWitness},
+};
+
+/// A table constraining secret oracles, proven with a masked sumcheck over extended columns.
+#[derive(Debug, Clone)]
+pub struct SecretTable<F: TowerField> {
+	/// The constraints of the table over the extended columns, as sumcheck constraints multiplied
+	/// by the equality indicator. The equality indicator is the last multilinear and is only added
+	/// after the commitment.
+	constraint_set: SizedConstraintSet<F>,
+	/// The secret oracles of the table and their committed extensions.
+	extended: Vec<(OracleId, OracleId)>,
+	/// The public oracles of the table and their repetitions.
+	repeated: Vec<(OracleId, OracleId)>,
+	/// The committed coefficients of the masking polynomial.
+	mask_id: OracleId,
+	/// The degree of the masking polynomial, which is the individual degree of the constraints.
+	mask_degree: usize,
+}
+
+impl<F: TowerField> SecretTable<F> {
+	fn new(
+		oracles: &mut MultilinearOracleSet<F>,
+		constraint_set: SizedConstraintSet<F>,
+	) -> Result<Self, Error> {
+		let SizedConstraintSet {
+			n_vars,
+			oracle_ids,
+			constraints,
+		} = constraint_set;
+
+		let mut extended = Vec::new();
+		let mut repeated = Vec::new();
+		let oracle_ids = oracle_ids
+			.into_iter()
+			.map(|id| {
+				if oracles.is_secret(id) {
+					let extended_id = oracles.add_committed(n_vars + 1, F::TOWER_LEVEL);
+					extended.push((id, extended_id));
+					Ok(extended_id)
+				} else {
+					let repeated_id = oracles.add_repeating(id, 1)?;
+					repeated.push((id, repeated_id));
+					Ok(repeated_id)
+				}
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let eq_ind = ArithCircuit::var(oracle_ids.len());
+		let constraints = constraints
+			.into_iter()
+			.map(
+				|Constraint {
+				     name,
+				     composition,
+				     predicate,
+				 }| match predicate {
+					ConstraintPredicate::Zero => Ok(Constraint {
+						name,
+						composition: composition * eq_ind.clone(),
+						predicate: ConstraintPredicate::Sum(F::ZERO),
+					}),
+					ConstraintPredicate::Sum(_) => {
+						Err(sumcheck::Error::MixedBatchingNotSupported.into())
+					}
+				},
+			)
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		// The masked claim has the degree of the optimized compositions, and at least the degree
+		// of the masking composite.
+		let mask_degree = constraints
+			.iter()
+			.map(|constraint| constraint.composition.clone().optimize().degree())
+			.max()
+			.unwrap_or(0)
+			.max(1);
+		let mask_id = oracles.add_committed(mask_n_vars(n_vars + 1, mask_degree), F::TOWER_LEVEL);
+
+		Ok(Self {
+			constraint_set: SizedConstraintSet {
+				n_vars: n_vars + 1,
+				oracle_ids,
+				constraints,
+			},
+			extended,
+			repeated,
+			mask_id,
+			mask_degree,
+		})
+	}
+
+	/// Samples the point of the equality indicator, which selects the rows of the original table.
+	fn sample_eq_ind(&self, transcript: &mut impl CanSample<F>) -> EqIndPartialEval<F> {
+		let mut point = transcript.sample_vec(self.constraint_set.n_vars - 1);
+		point.push(F::ZERO);
+		EqIndPartialEval::new(point)
+	}
+
+	/// Adds the equality indicator to the oracle set and returns the constraint set over it.
+	fn with_eq_ind(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		eq_ind: EqIndPartialEval<F>,
+	) -> Result<(OracleId, SizedConstraintSet<F>), Error> {
+		let eq_ind_id = oracles.add_transparent(eq_ind)?;
+		let mut constraint_set = self.constraint_set.clone();
+		constraint_set.oracle_ids.push(eq_ind_id);
+		Ok((eq_ind_id, constraint_set))
+	}
+
+	fn mask_eval_claim(&self, point: &[F], eval: F) -> MaskEvalClaim<F> {
+		let mut weights = mask_eval_weights(self.mask_degree, point);
+		weights.resize(1 << mask_n_vars(point.len(), self.mask_degree), F::ZERO);
+		MaskEvalClaim {
+			mask_id: self.mask_id,
+			weights,
+			eval,
+		}
+	}
+}
+
+/// The number of variables of the committed coefficients of a masking polynomial.
+fn mask_n_vars(n_vars: usize, degree: usize) -> usize {
+	log2_ceil_usize(n_vars * (degree + 1))
+}
+
+/// A claimed evaluation of a masking polynomial, which is the inner product of its committed
+/// coefficients with public weights.
+#[derive(Debug, Clone)]
+pub struct MaskEvalClaim<F: Field> {
+	mask_id: OracleId,
+	/// The weights of the coefficients, padded with zeros to the committed length.
+	weights: Vec<F>,
+	eval: F,
+}
+
+/// Splits the table constraint sets over secret oracles off the others and extends their columns.
+///
+/// The committed extensions of the secret columns and the committed masking coefficients are added
+/// to the oracle set, so the prover and the verifier call this before the commitment.
+///
+/// ## Throws
+///
+/// * [`Error::SecretOracleNotIsolated`] if a secret oracle is constrained by more than one table,
+///   or is used by a flush, a non-zero check, an exponentiation, or another oracle
+pub fn split_secret_tables<F: TowerField>(
+	oracles: &mut MultilinearOracleSet<F>,
+	table_constraints: Vec<SizedConstraintSet<F>>,
+	flushes: &[Flush<F>],
+	non_zero_oracle_ids: &[OracleId],
+	exponents: &[Exp<F>],
+) -> Result<(Vec<SizedConstraintSet<F>>, Vec<SecretTable<F>>), Error> {
+	if oracles.secret_ids().is_empty() {
+		return Ok((table_constraints, Vec::new()));
+	}
+	check_secret_oracles_isolated(
+		oracles,
+		&table_constraints,
+		flushes,
+		non_zero_oracle_ids,
+		exponents,
+	)?;
+
+	let (secret_tables, public_tables) =
+		table_constraints
+			.into_iter()
+			.partition::<Vec<_>, _>(|constraint_set| {
+				constraint_set
+					.oracle_ids
+					.iter()
+					.any(|&id| oracles.is_secret(id))
+			});
+	let secret_tables = secret_tables
+		.into_iter()
+		.map(|constraint_set| SecretTable::new(oracles, constraint_set))
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok((public_tables, secret_tables))
+}
+
+fn check_secret_oracles_isolated<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	table_constraints: &[SizedConstraintSet<F>],
+	flushes: &[Flush<F>],
+	non_zero_oracle_ids: &[OracleId],
+	exponents: &[Exp<F>],
+) -> Result<(), Error> {
+	let oracle_id = |oracle_or_const: &OracleOrConst<F>| match oracle_or_const {
+		OracleOrConst::Oracle(id) => Some(*id),
+		OracleOrConst::Const { .. } => None,
+	};
+	let flushed = flushes.iter().flat_map(|flush| {
+		chain!(flush.oracles.iter().filter_map(oracle_id), flush.selectors.iter().copied())
+	});
+	let exponentiated = exponents.iter().flat_map(|exp| {
+		chain!(exp.bits_ids.iter().copied(), oracle_id(&exp.base), [exp.exp_result_id])
+	});
+	let inner = oracles
+		.polys()
+		.flat_map(|oracle| inner_oracle_ids(&oracle.variant));
+	if let Some(id) = chain!(flushed, non_zero_oracle_ids.iter().copied(), exponentiated, inner)
+		.find(|&id| oracles.is_secret(id))
+	{
+		bail!(Error::SecretOracleNotIsolated { id });
+	}
+
+	for &id in oracles.secret_ids() {
+		let n_tables = table_constraints
+			.iter()
+			.filter(|constraint_set| constraint_set.oracle_ids.contains(&id))
+			.count();
+		if n_tables > 1 {
+			bail!(Error::SecretOracleNotIsolated { id });
+		}
+	}
+	Ok(())
+}
+
+/// Returns the oracles a virtual oracle is defined from.
+fn inner_oracle_ids<F: TowerField>(variant: &MultilinearPolyVariant<F>) -> Vec<OracleId> {
+	match variant {
+		MultilinearPolyVariant::Committed
+		| MultilinearPolyVariant::Transparent(_)
+		| MultilinearPolyVariant::Structured(_) => Vec::new(),
+		MultilinearPolyVariant::Repeating { id, .. } => vec![*id],
+		MultilinearPolyVariant::Projected(projected) => vec![projected.id()],
+		MultilinearPolyVariant::Shifted(shifted) => vec![shifted.id()],
+		MultilinearPolyVariant::Packed(packed) => vec![packed.id()],
+		MultilinearPolyVariant::LinearCombination(linear_combination) => {
+			linear_combination.polys().collect()
+		}
+		MultilinearPolyVariant::ZeroPadded(padded) => vec![padded.id()],
+		MultilinearPolyVariant::Composite(composite) => composite.polys().collect(),
+	}
+}
+
+/// Fills in the witnesses of the extended and repeated columns of the secret tables, and samples
+/// their masking polynomials, whose coefficients are filled in as the witnesses of the mask
+/// oracles.
+pub fn make_secret_table_witnesses<P>(
+	witness: &mut MultilinearExtensionIndex<P>,
+	secret_tables: &[SecretTable<P::Scalar>],
+	mut rng: impl RngCore,
+) -> Result<Vec<MaskingPolynomial<P::Scalar>>, Error>
+where
+	P: PackedField<Scalar: TowerField>,
+{
+	secret_tables
+		.iter()
+		.map(|table| {
+			let n_vars = table.constraint_set.n_vars;
+			let half_len = 1 << (n_vars - 1);
+
+			for &(id, extended_id) in &table.extended {
+				let secret = witness.get_multilin_poly(id)?;
+				let mut evals = (0..half_len)
+					.map(|i| secret.evaluate_on_hypercube(i))
+					.collect::<Result<Vec<_>, _>>()?;
+				evals.extend(iter::repeat_with(|| P::Scalar::random(&mut rng)).take(half_len));
+				witness.update_multilin_poly([(
+					extended_id,
+					multilin_from_scalars(n_vars, &evals)?,
+				)])?;
+			}
+
+			for &(id, repeated_id) in &table.repeated {
+				let column = witness.get_multilin_poly(id)?;
+				let evals = (0..half_len << 1)
+					.map(|i| column.evaluate_on_hypercube(i % half_len))
+					.collect::<Result<Vec<_>, _>>()?;
+				witness.update_multilin_poly([(
+					repeated_id,
+					multilin_from_scalars(n_vars, &evals)?,
+				)])?;
+			}
+
+			let mask = MaskingPolynomial::random(n_vars, table.mask_degree, &mut rng);
+			let mask_n_vars = mask_n_vars(n_vars, table.mask_degree);
+			let mut coeffs = mask.coeffs();
+			coeffs.resize(1 << mask_n_vars, P::Scalar::ZERO);
+			witness.update_multilin_poly([(
+				table.mask_id,
+				multilin_from_scalars(mask_n_vars, &coeffs)?,
+			)])?;
+			Ok(mask)
+		})
+		.collect()
+}
+
+fn multilin_from_scalars<P: PackedField>(
+	n_vars: usize,
+	scalars: &[P::Scalar],
+) -> Result<MultilinearWitness<'static, P>, Error> {
+	let multilin = MultilinearExtension::new(n_vars, pack_slice(scalars))?;
+	Ok(MLEDirectAdapter::from(multilin).upcast_arc_dyn())
+}
+
+/// Proves the constraints of the secret tables with masked sumchecks, one table after the other.
+///
+/// Returns the evaluation claims on the extended and repeated columns and the claimed evaluations
+/// of the masking polynomials.
+pub fn prove_secret_tables<P, FDomain, Challenger_, Backend>(
+	oracles: &mut MultilinearOracleSet<P::Scalar>,
+	witness: &mut MultilinearExtensionIndex<P>,
+	secret_tables: &[SecretTable<P::Scalar>],
+	masks: Vec<MaskingPolynomial<P::Scalar>>,
+	transcript: &mut ProverTranscript<Challenger_>,
+	domain_factory: impl EvaluationDomainFactory<FDomain>,
+	backend: &Backend,
+) -> Result<(Vec<EvalcheckMultilinearClaim<P::Scalar>>, Vec<MaskEvalClaim<P::Scalar>>), Error>
+where
+	P: PackedField<Scalar: TowerField + ExtensionField<FDomain>>
+		+ PackedExtension<P::Scalar, PackedSubfield = P>
+		+ PackedExtension<FDomain>,
+	FDomain: Field,
+	Challenger_: Challenger,
+	Backend: ComputationBackend,
+{
+	let mut eval_claims = Vec::new();
+	let mut mask_eval_claims = Vec::with_capacity(secret_tables.len());
+	for (table, mask) in iter::zip(secret_tables, masks) {
+		let eq_ind = table.sample_eq_ind(transcript);
+		let eq_ind_witness =
+			MLEDirectAdapter::from(eq_ind.multilinear_extension::<P, _>(backend)?).upcast_arc_dyn();
+		let (eq_ind_id, constraint_set) = table.with_eq_ind(oracles, eq_ind)?;
+		witness.update_multilin_poly([(eq_ind_id, eq_ind_witness)])?;
+
+		transcript.message().write_scalar(mask.sum());
+
+		let n_composites = constraint_set.constraints.len();
+		let (_, meta) = sumcheck::constraint_set_sumcheck_claim(constraint_set.clone())?;
+		let prover = sumcheck::prove::constraint_set_sumcheck_prover(
+			EvaluationOrder::LowToHigh,
+			constraint_set,
+			witness,
+			domain_factory.clone(),
+			standard_switchover_heuristic(-2),
+			backend,
+		)?;
+		let prover = MaskedSumcheckProver::new(prover, n_composites, mask)?;
+		let mut output = sumcheck::batch_prove(vec![prover], transcript)?;
+
+		let mask_eval = output.multilinear_evals[0]
+			.pop()
+			.expect("the masked claim has the mask as its last multilinear");
+		mask_eval_claims.push(table.mask_eval_claim(&output.challenges, mask_eval));
+		eval_claims.extend(sumcheck::make_eval_claims(EvaluationOrder::LowToHigh, [meta], output)?);
+	}
+	Ok((eval_claims, mask_eval_claims))
+}
+
+/// Verifies the constraints of the secret tables, as proven by [`prove_secret_tables`].
+pub fn verify_secret_tables<F, Challenger_>(
+	oracles: &mut MultilinearOracleSet<F>,
+	secret_tables: &[SecretTable<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(Vec<EvalcheckMultilinearClaim<F>>, Vec<MaskEvalClaim<F>>), Error>
+where
+	F: TowerField,
+	Challenger_: Challenger,
+{
+	let mut eval_claims = Vec::new();
+	let mut mask_eval_claims = Vec::with_capacity(secret_tables.len());
+	for table in secret_tables {
+		let eq_ind = table.sample_eq_ind(transcript);
+		let (_, constraint_set) = table.with_eq_ind(oracles, eq_ind)?;
+
+		let mask_sum = transcript.message().read_scalar()?;
+
+		let (claim, meta) = sumcheck::constraint_set_sumcheck_claim(constraint_set)?;
+		let masked_claim = mask_claim(&claim, mask_sum)?;
+		let mut output =
+			sumcheck::batch_verify(EvaluationOrder::LowToHigh, &[masked_claim], transcript)?;
+
+		let mask_eval = output.multilinear_evals[0]
+			.pop()
+			.expect("the masked claim has the mask as its last multilinear");
+		mask_eval_claims.push(table.mask_eval_claim(&output.challenges, mask_eval));
+		eval_claims.extend(sumcheck::make_eval_claims(EvaluationOrder::LowToHigh, [meta], output)?);
+	}
+	Ok((eval_claims, mask_eval_claims))
+}
+
+/// Appends the PIOP sumcheck claims of the mask evaluations, with the weights of each as a new
+/// transparent.
+///
+/// The transparents are inserted so that they stay sorted by number of variables, and the
+/// transparent indices of the other claims are shifted accordingly.
+///
+/// ## Preconditions
+///
+/// * `transparents` are in ascending order by number of variables.
+pub fn append_mask_piop_claims<F, T>(
+	mask_eval_claims: Vec<MaskEvalClaim<F>>,
+	oracle_to_commit_index: &SparseIndex<usize>,
+	transparents: &mut Vec<T>,
+	claims: &mut Vec<PIOPSumcheckClaim<F>>,
+	transparent_n_vars: impl Fn(&T) -> usize,
+	make_transparent: impl Fn(usize, Vec<F>) -> Result<T, Error>,
+) -> Result<(), Error>
+where
+	F: Field,
+{
+	for MaskEvalClaim {
+		mask_id,
+		weights,
+		eval,
+	} in mask_eval_claims
+	{
+		let n_vars = log2_strict_usize(weights.len());
+		let index =
+			transparents.partition_point(|transparent| transparent_n_vars(transparent) <= n_vars);
+		transparents.insert(index, make_transparent(n_vars, weights)?);
+		for claim in claims.iter_mut() {
+			if claim.transparent >= index {
+				claim.transparent += 1;
+			}
+		}
+
+		let committed = *oracle_to_commit_index
+			.get(mask_id.index())
+			.expect("pre-condition: mask oracles are committed");
+		claims.push(PIOPSumcheckClaim {
+			n_vars,
+			committed,
+			transparent: index,
+			sum: eval,
+		});
+	}
+	Ok(())
+}
+
+/// Returns the weights of a mask evaluation as a transparent multilinear witness.
+pub fn mask_weights_witness<P: PackedField>(
+	n_vars: usize,
+	weights: Vec<P::Scalar>,
+) -> Result<MultilinearWitness<'static, P>, Error> {
+	multilin_from_scalars(n_vars, &weights)
+}
+
+/// Returns the weights of a mask evaluation as a transparent polynomial.
+pub fn mask_weights_transparent<'a, F: TowerField>(
+	n_vars: usize,
+	weights: Vec<F>,
+) -> Result<Box<dyn MultivariatePoly<F> + 'a>, Error> {
+	debug_assert_eq!(weights.len(), 1 << n_vars);
+	Ok(Box::new(MaskWeights { weights }))
+}
+
+/// The multilinear extension of the weights of a mask evaluation.
+#[derive(Debug)]
+struct MaskWeights<F: Field> {
+	weights: Vec<F>,
+}
+
+impl<F: TowerField> MultivariatePoly<F> for MaskWeights<F> {
+	fn n_vars(&self) -> usize {
+		log2_strict_usize(self.weights.len())
+	}
+
+	fn degree(&self) -> usize {
+		self.n_vars()
+	}
+
+	fn evaluate(&self, query: &[F]) -> Result<F, polynomial::Error> {
+		if query.len() != self.n_vars() {
+			bail!(polynomial::Error::IncorrectQuerySize {
+				expected: self.n_vars(),
+				actual: query.len(),
+			});
+		}
+		Ok(iter::zip(&self.weights, eq_ind_partial_eval::<F>(query))
+			.map(|(&weight, eq)| weight * eq)
+			.sum())
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		F::TOWER_LEVEL
+	}
+}
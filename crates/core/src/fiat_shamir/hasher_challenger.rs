@@ -49,6 +49,15 @@ where
 			buffer: initial_digest,
 		})
 	}
+
+	/// Constructs a challenger whose state is bound to a caller-supplied seed.
+	///
+	/// The seed is absorbed before any prover messages, so that all challenges depend on it. It
+	/// should identify the deployment configuration, such as the protocol version and a digest of
+	/// the public parameters. The default challenger is the challenger with an empty seed.
+	pub fn with_seed(seed: &[u8]) -> Self {
+		Self::new(H::digest(seed))
+	}
 }
 
 impl<H> Default for HasherChallenger<H>
@@ -56,11 +65,13 @@ where
 	H: Digest + BlockSizeUser + FixedOutputReset,
 {
 	fn default() -> Self {
-		Self::new(H::digest([]))
+		Self::with_seed(&[])
 	}
 }
 
-impl<H: Digest + BlockSizeUser + FixedOutputReset + Default> Challenger for HasherChallenger<H> {
+impl<H: Digest + BlockSizeUser + FixedOutputReset + Default + Clone> Challenger
+	for HasherChallenger<H>
+{
 	/// This returns the inner challenger which implements `[BufMut]`
 	fn observer(&mut self) -> &mut impl BufMut {
 		match self {
@@ -304,4 +315,31 @@ mod tests {
 		let final_hasher_out = hasher.finalize_reset();
 		assert_eq!(final_hasher_out[..7], out_again);
 	}
+
+	#[test]
+	fn test_seeded_challenger() {
+		let sample = |challenger: &mut HasherChallenger<Groestl256>| {
+			challenger.observer().put_slice(&[0x48, 0x55]);
+			let mut out = [0u8; 32];
+			challenger.sampler().copy_to_slice(&mut out);
+			out
+		};
+
+		assert_eq!(
+			sample(&mut HasherChallenger::with_seed(&[])),
+			sample(&mut HasherChallenger::default())
+		);
+		assert_eq!(
+			sample(&mut HasherChallenger::with_seed(b"binius-v1")),
+			sample(&mut HasherChallenger::with_seed(b"binius-v1"))
+		);
+		assert_ne!(
+			sample(&mut HasherChallenger::with_seed(b"binius-v1")),
+			sample(&mut HasherChallenger::with_seed(b"binius-v2"))
+		);
+		assert_ne!(
+			sample(&mut HasherChallenger::with_seed(b"binius-v1")),
+			sample(&mut HasherChallenger::default())
+		);
+	}
 }
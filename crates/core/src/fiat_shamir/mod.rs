@@ -8,7 +8,12 @@ pub use hasher_challenger::HasherChallenger;
 pub use sampling::*;
 
 /// A Fiat-Shamir challenger that can observe prover messages and sample verifier randomness.
-pub trait Challenger {
+///
+/// Challengers are cloneable so that a prover can search for a proof-of-work nonce against a
+/// snapshot of the challenger state, see [`ProverTranscript::grind`].
+///
+/// [`ProverTranscript::grind`]: crate::transcript::ProverTranscript::grind
+pub trait Challenger: Clone {
 	/// Returns an infinite buffer for reading pseudo-random bytes.
 	fn sampler(&mut self) -> &mut impl Buf;
 
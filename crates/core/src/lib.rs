@@ -22,6 +22,8 @@ pub mod polynomial;
 pub mod protocols;
 #[allow(clippy::module_inception)]
 pub mod reed_solomon;
+#[cfg(feature = "reference")]
+pub mod reference;
 pub mod ring_switch;
 pub mod tensor_algebra;
 pub mod transcript;
@@ -5,11 +5,15 @@ use std::{array, fmt::Debug, mem::MaybeUninit};
 use binius_field::TowerField;
 use binius_hash::{PseudoCompressionFunction, multi_digest::ParallelDigest};
 use binius_maybe_rayon::{prelude::*, slice::ParallelSlice};
-use binius_utils::{bail, checked_arithmetics::log2_strict_usize, mem::slice_assume_init_mut};
-use digest::{FixedOutputReset, Output, crypto_common::BlockSizeUser};
+use binius_utils::{
+	bail,
+	checked_arithmetics::{log2_ceil_usize, log2_strict_usize},
+	mem::slice_assume_init_mut,
+};
+use digest::{Digest, FixedOutputReset, Output, crypto_common::BlockSizeUser};
 use tracing::instrument;
 
-use super::errors::Error;
+use super::{errors::Error, storage::NodeStorage};
 
 /// A binary Merkle tree that commits batches of vectors.
 ///
@@ -21,7 +25,203 @@ pub struct BinaryMerkleTree<D> {
 	/// Base-2 logarithm of the number of leaves
 	pub log_len: usize,
 	/// The inner nodes, arranged as a flattened array of layers with the root at the end
-	pub inner_nodes: Vec<D>,
+	///
+	/// After [`BinaryMerkleTree::prune_to_layer`], only the layers from the root down to the
+	/// pruning depth are kept.
+	pub inner_nodes: NodeStorage<D>,
+	/// The salts compressed into the leaf digests, one per leaf, or empty if the leaves are not
+	/// salted
+	pub salts: Vec<D>,
+}
+
+/// How the leaf digests of a vector whose number of leaves is not a power of two are padded to
+/// the leaves of a full tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LeafPadding {
+	/// Only vectors with a power-of-two number of leaves are committed.
+	#[default]
+	None,
+	/// The leaf digests are followed by all-zero digests.
+	ZeroDigest,
+	/// The leaf digests are followed by copies of the digest of the last leaf.
+	DuplicateLast,
+}
+
+impl LeafPadding {
+	/// Returns the base-2 logarithm of the number of leaves of the tree over `n_leaves` leaves.
+	pub fn log_len(self, n_leaves: usize) -> Result<usize, Error> {
+		if n_leaves == 0 || (self == Self::None && !n_leaves.is_power_of_two()) {
+			bail!(Error::PowerOfTwoLengthRequired);
+		}
+		Ok(log2_ceil_usize(n_leaves))
+	}
+
+	/// Returns the digest that pads the leaves after the last one.
+	fn padding<D: Clone + Default>(self, last: &D) -> Result<D, Error> {
+		match self {
+			Self::None => bail!(Error::PowerOfTwoLengthRequired),
+			Self::ZeroDigest => Ok(D::default()),
+			Self::DuplicateLast => Ok(last.clone()),
+		}
+	}
+
+	/// Pads the digests of the leaves up to the next power of two.
+	pub fn pad<D: Clone + Default>(self, digests: &mut Vec<D>) -> Result<(), Error> {
+		let len = 1 << self.log_len(digests.len())?;
+		if len != digests.len() {
+			let padding = self.padding(&digests[digests.len() - 1])?;
+			digests.resize(len, padding);
+		}
+		Ok(())
+	}
+
+	/// Pads the digests of the first `n_leaves` leaves, which are initialized, to the rest of the
+	/// slice.
+	pub(super) fn pad_uninit<D: Clone + Default>(
+		self,
+		digests: &mut [MaybeUninit<D>],
+		n_leaves: usize,
+	) -> Result<(), Error> {
+		if n_leaves == digests.len() {
+			return Ok(());
+		}
+		let last = unsafe {
+			// SAFETY: the first `n_leaves` digests are initialized
+			digests[n_leaves - 1].assume_init_ref()
+		};
+		let padding = self.padding(last)?;
+		for digest in &mut digests[n_leaves..] {
+			digest.write(padding.clone());
+		}
+		Ok(())
+	}
+}
+
+/// How the hashes of the leaves and of the inner nodes of a tree are told apart.
+///
+/// Without separation, the digest of a leaf and the compression of two children share one domain,
+/// so a compression function that hashes the concatenation of its inputs lets the bytes of two
+/// children be opened as the values of a leaf. With separation, the input of every hash is
+/// prefixed with a domain byte: the values of a leaf are hashed after the leaf domain byte, and
+/// the digests of two children are hashed after the inner domain byte. Since the compression
+/// function takes no input besides the two digests, the inner nodes of a separated tree are hashed
+/// with the hash function of the leaves instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DomainSeparation {
+	/// The hash inputs are not prefixed, and the inner nodes are compressed.
+	#[default]
+	None,
+	/// The hash inputs of the leaves and of the inner nodes are prefixed with distinct domain
+	/// bytes.
+	Tagged,
+	/// As [`Self::Tagged`], and the domain byte is followed by the depth of the node, as a
+	/// little-endian `u64`.
+	TaggedWithDepth,
+}
+
+/// The domain byte prefixed to the hash input of the leaves.
+const LEAF_DOMAIN: u8 = 1;
+
+/// The domain byte prefixed to the hash input of the inner nodes.
+const INNER_DOMAIN: u8 = 2;
+
+/// The bytes prefixed to the hash input of a node, which are empty without domain separation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodePrefix {
+	bytes: [u8; 1 + size_of::<u64>()],
+	len: usize,
+}
+
+impl AsRef<[u8]> for NodePrefix {
+	fn as_ref(&self) -> &[u8] {
+		&self.bytes[..self.len]
+	}
+}
+
+impl DomainSeparation {
+	/// Returns the prefix of the hash input of a leaf of a tree of depth `depth`.
+	pub fn leaf_prefix(self, depth: usize) -> NodePrefix {
+		self.prefix(LEAF_DOMAIN, depth)
+	}
+
+	/// Returns the prefix of the hash input of an inner node at depth `depth`, where the root is
+	/// at depth zero.
+	pub fn inner_prefix(self, depth: usize) -> NodePrefix {
+		self.prefix(INNER_DOMAIN, depth)
+	}
+
+	fn prefix(self, domain: u8, depth: usize) -> NodePrefix {
+		let mut bytes = [0; 1 + size_of::<u64>()];
+		bytes[0] = domain;
+		bytes[1..].copy_from_slice(&(depth as u64).to_le_bytes());
+		let len = match self {
+			Self::None => 0,
+			Self::Tagged => 1,
+			Self::TaggedWithDepth => bytes.len(),
+		};
+		NodePrefix { bytes, len }
+	}
+
+	/// Returns the digest of the inner node at depth `depth` with the given children.
+	///
+	/// Without separation, the children are compressed with `compression`. Otherwise, they are
+	/// hashed with `H` after [`Self::inner_prefix`].
+	pub fn compress_inner<H, C>(
+		self,
+		compression: &C,
+		children: [Output<H>; 2],
+		depth: usize,
+	) -> Output<H>
+	where
+		H: Digest,
+		C: PseudoCompressionFunction<Output<H>, 2>,
+	{
+		if self == Self::None {
+			return compression.compress(children);
+		}
+		let [left, right] = children;
+		H::new_with_prefix(self.inner_prefix(depth))
+			.chain_update(left)
+			.chain_update(right)
+			.finalize()
+	}
+}
+
+/// The [`DomainSeparation`] of the nodes of a tree that is rooted at depth `root_depth` of the
+/// tree it is committed in, which is zero unless it is a subtree built or rebuilt separately.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct NodeTags {
+	pub separation: DomainSeparation,
+	pub root_depth: usize,
+}
+
+impl NodeTags {
+	pub const fn new(separation: DomainSeparation) -> Self {
+		Self {
+			separation,
+			root_depth: 0,
+		}
+	}
+
+	/// Returns the prefix of the hash input of a leaf of the tree, whose depth is `log_len`.
+	pub fn leaf_prefix(self, log_len: usize) -> NodePrefix {
+		self.separation.leaf_prefix(self.root_depth + log_len)
+	}
+
+	/// Returns the digest of the inner node at depth `depth` of the tree with the given children.
+	fn compress_inner<H, C>(
+		self,
+		compression: &C,
+		children: [Output<H>; 2],
+		depth: usize,
+	) -> Output<H>
+	where
+		H: Digest,
+		C: PseudoCompressionFunction<Output<H>, 2>,
+	{
+		self.separation
+			.compress_inner::<H, C>(compression, children, self.root_depth + depth)
+	}
 }
 
 pub fn build<F, H, C>(
@@ -46,39 +246,581 @@ where
 
 	let log_len = log2_strict_usize(len);
 
-	internal_build(
+	internal_build::<H::Digest, C>(
 		compression,
-		|inner_nodes| hash_interleaved::<_, H>(elements, inner_nodes),
+		NodeTags::default(),
+		|inner_nodes| hash_interleaved::<_, H>(&[], elements, inner_nodes),
 		log_len,
+		None,
+		Vec::new(),
+		NodeStorage::memory(tree_len(log_len)),
 	)
 }
 
-fn internal_build<Digest, C>(
+/// Builds a Merkle tree over the batched elements, hashing and folding the leaves in chunks.
+///
+/// The leaves are split into contiguous chunks of `2^log_chunk_len` leaves, and each chunk is
+/// hashed and folded into the root of its subtree in a single task, so that the digests of the
+/// lower layers are compressed while they are still in cache. The layers above the subtree roots
+/// are folded as in [`build`], and the result is identical to it. If `max_threads` is given, the
+/// work on each layer is split into at most that many tasks.
+///
+/// If `salts` is not empty, it holds one salt per leaf, and each leaf digest is compressed with its
+/// salt before the tree is folded, so that the digests of the leaves reveal nothing about their
+/// values.
+pub fn build_chunked<F, H, C>(
 	compression: &C,
-	// Must either successfully initialize the passed in slice or return error
-	hash_leaves: impl FnOnce(&mut [MaybeUninit<Digest>]) -> Result<(), Error>,
+	elements: &[F],
+	batch_size: usize,
+	log_chunk_len: usize,
+	max_threads: Option<usize>,
+	salts: Vec<Output<H::Digest>>,
+) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+{
+	let len = elements.len().checked_div(batch_size).unwrap_or_default();
+	build_chunked_into::<F, H, C>(
+		compression,
+		elements,
+		batch_size,
+		log_chunk_len,
+		max_threads,
+		salts,
+		DomainSeparation::None,
+		NodeStorage::memory(tree_len(log2_ceil_usize(len))),
+	)
+}
+
+/// Builds a Merkle tree as [`build_chunked`] into the given storage, which must be allocated for
+/// the nodes of the tree, separating the hashes of its nodes as configured by `separation`.
+pub fn build_chunked_into<F, H, C>(
+	compression: &C,
+	elements: &[F],
+	batch_size: usize,
+	log_chunk_len: usize,
+	max_threads: Option<usize>,
+	salts: Vec<Output<H::Digest>>,
+	separation: DomainSeparation,
+	inner_nodes: NodeStorage<Output<H::Digest>>,
+) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+{
+	build_chunked_tagged_into::<F, H, C>(
+		compression,
+		NodeTags::new(separation),
+		elements,
+		batch_size,
+		log_chunk_len,
+		max_threads,
+		salts,
+		inner_nodes,
+	)
+}
+
+/// Builds a Merkle tree as [`build_chunked_into`], separating the hashes of its nodes with `tags`.
+///
+/// A subtree of a committed tree is built from its leaves with the tags of the committed tree
+/// offset by the depth of the subtree root.
+#[instrument("BinaryMerkleTree::build_chunked", skip_all, level = "debug")]
+#[allow(clippy::too_many_arguments)]
+pub(super) fn build_chunked_tagged_into<F, H, C>(
+	compression: &C,
+	tags: NodeTags,
+	elements: &[F],
+	batch_size: usize,
+	log_chunk_len: usize,
+	max_threads: Option<usize>,
+	salts: Vec<Output<H::Digest>>,
+	mut inner_nodes: NodeStorage<Output<H::Digest>>,
+) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+{
+	if batch_size == 0 || elements.len() % batch_size != 0 {
+		bail!(Error::IncorrectBatchSize);
+	}
+
+	let len = elements.len() / batch_size;
+
+	if !len.is_power_of_two() {
+		bail!(Error::PowerOfTwoLengthRequired);
+	}
+
+	let log_len = log2_strict_usize(len);
+	let log_chunk_len = log_chunk_len.min(log_len);
+	check_salts_len(&salts, log_len)?;
+
+	let total_length = tree_len(log_len);
+	let mut layers = split_layers(inner_nodes.uninit_mut(total_length), log_len);
+	let (chunked_layers, _) = layers.split_at_mut(log_chunk_len + 1);
+	hash_and_fold_chunks::<F, H, C>(
+		compression,
+		tags,
+		log_len,
+		elements,
+		batch_size,
+		group_chunk_layers(chunked_layers, log_chunk_len),
+		&salts,
+		max_threads,
+	)?;
+	fold_upper_layers::<H::Digest, C>(compression, tags, &mut layers[log_chunk_len..], max_threads);
+
+	unsafe {
+		// SAFETY: all layers were initialized above
+		inner_nodes.assume_init(total_length);
+	}
+	Ok(BinaryMerkleTree {
+		log_len,
+		inner_nodes,
+		salts,
+	})
+}
+
+/// Splits the storage of the nodes of a tree with `2^log_len` leaves into its layers, from the
+/// leaves up to the root.
+fn split_layers<D>(
+	mut remaining: &mut [MaybeUninit<D>],
+	log_len: usize,
+) -> Vec<&mut [MaybeUninit<D>]> {
+	let mut layers = Vec::with_capacity(log_len + 1);
+	for i in 0..=log_len {
+		let (layer, next_remaining) = remaining.split_at_mut(1 << (log_len - i));
+		layers.push(layer);
+		remaining = next_remaining;
+	}
+	layers
+}
+
+/// The parts of the lower layers of a tree that belong to the subtree of each chunk of leaves, from
+/// the leaves up to the subtree root.
+type ChunkLayers<'a, D> = Vec<Vec<&'a mut [MaybeUninit<D>]>>;
+
+/// Groups the parts of the lower `log_chunk_len + 1` layers that belong to the subtree of each
+/// chunk of `2^log_chunk_len` leaves.
+fn group_chunk_layers<'a, D>(
+	chunked_layers: &'a mut [&mut [MaybeUninit<D>]],
+	log_chunk_len: usize,
+) -> ChunkLayers<'a, D> {
+	let n_chunks = chunked_layers[0].len() >> log_chunk_len;
+	let mut chunk_layers = (0..n_chunks)
+		.map(|_| Vec::with_capacity(log_chunk_len + 1))
+		.collect::<Vec<_>>();
+	for (i, layer) in chunked_layers.iter_mut().enumerate() {
+		for (chunk_layers, layer_chunk) in chunk_layers
+			.iter_mut()
+			.zip(layer.chunks_mut(1 << (log_chunk_len - i)))
+		{
+			chunk_layers.push(layer_chunk);
+		}
+	}
+	chunk_layers
+}
+
+/// Hashes chunks of leaves and folds each chunk into the root of its subtree, in one task per
+/// chunk.
+///
+/// `chunk_layers` holds the parts of the layers of each chunk subtree, as grouped by
+/// [`group_chunk_layers`], and `elements` the batched elements of all leaves of the chunks. The
+/// leaves are salted with `salts` if it is not empty, and the hashes of the nodes are separated
+/// with `tags` as the nodes of a tree of depth `log_len`.
+#[allow(clippy::too_many_arguments)]
+fn hash_and_fold_chunks<F, H, C>(
+	compression: &C,
+	tags: NodeTags,
 	log_len: usize,
-) -> Result<BinaryMerkleTree<Digest>, Error>
+	elements: &[F],
+	batch_size: usize,
+	chunk_layers: ChunkLayers<'_, Output<H::Digest>>,
+	salts: &[Output<H::Digest>],
+	max_threads: Option<usize>,
+) -> Result<(), Error>
 where
-	Digest: Clone + Send + Sync,
-	C: PseudoCompressionFunction<Digest, 2> + Sync,
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
 {
-	let total_length = (1 << (log_len + 1)) - 1;
-	let mut inner_nodes = Vec::with_capacity(total_length);
+	let chunk_len = elements.len() / batch_size / chunk_layers.len();
+	let chunks = elements
+		.par_chunks(batch_size * chunk_len)
+		.map(|chunk_elements| {
+			chunk_elements
+				.par_chunks(batch_size)
+				.with_min_len(chunk_len)
+				.map(|leaf| leaf.iter().copied())
+		});
+	hash_and_fold_leaf_chunks::<F, H, C, _>(
+		compression,
+		tags,
+		log_len,
+		chunks,
+		chunk_layers,
+		salts,
+		max_threads,
+	)
+}
 
-	hash_leaves(&mut inner_nodes.spare_capacity_mut()[..(1 << log_len)])?;
+/// Hashes chunks of leaves given by an iterator over the elements of each leaf, and folds each
+/// chunk into the root of its subtree, as [`hash_and_fold_chunks`].
+fn hash_and_fold_leaf_chunks<F, H, C, Chunks>(
+	compression: &C,
+	tags: NodeTags,
+	log_len: usize,
+	chunks: Chunks,
+	chunk_layers: ChunkLayers<'_, Output<H::Digest>>,
+	salts: &[Output<H::Digest>],
+	max_threads: Option<usize>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+	Chunks: IndexedParallelIterator<Item: IndexedParallelIterator<Item: IntoIterator<Item = F>>>,
+{
+	let n_chunks = chunk_layers.len();
+	chunk_layers
+		.into_par_iter()
+		.zip(chunks)
+		.enumerate()
+		.with_min_len(min_task_len(n_chunks, max_threads))
+		.try_for_each(|(chunk_index, (mut chunk_layers, leaves))| -> Result<_, Error> {
+			// The parallelism is across chunks, so each chunk is hashed by a single task.
+			let chunk_len = chunk_layers[0].len();
+			hash_iterated::<_, H, _>(tags.leaf_prefix(log_len).as_ref(), leaves, chunk_layers[0])?;
+			if !salts.is_empty() {
+				let chunk_salts = &salts[chunk_index * chunk_len..(chunk_index + 1) * chunk_len];
+				for (leaf, salt) in chunk_layers[0].iter_mut().zip(chunk_salts) {
+					let leaf = unsafe {
+						// SAFETY: the leaves of the chunk were just initialized by hash_iterated
+						leaf.assume_init_mut()
+					};
+					*leaf = compression.compress([leaf.clone(), salt.clone()]);
+				}
+			}
+			for i in 1..chunk_layers.len() {
+				let (prev_layers, next_layers) = chunk_layers.split_at_mut(i);
+				let prev_layer = unsafe {
+					// SAFETY: the previous layer of the chunk was initialized in the last iteration
+					slice_assume_init_mut(prev_layers[i - 1])
+				};
+				for (prev_pair, next_digest) in
+					prev_layer.chunks_exact(2).zip(next_layers[0].iter_mut())
+				{
+					next_digest.write(tags.compress_inner::<H::Digest, C>(
+						compression,
+						array::from_fn(|j| prev_pair[j].clone()),
+						log_len - i,
+					));
+				}
+			}
+			Ok(())
+		})
+}
+
+/// Folds the layers above the first one, which must be initialized, up to the root.
+fn fold_upper_layers<H, C>(
+	compression: &C,
+	tags: NodeTags,
+	layers: &mut [&mut [MaybeUninit<Output<H>>]],
+	max_threads: Option<usize>,
+) where
+	H: Digest,
+	C: PseudoCompressionFunction<Output<H>, 2> + Sync,
+{
+	let Some((first_layer, upper_layers)) = layers.split_first_mut() else {
+		return;
+	};
+	let mut prev_layer = unsafe {
+		// SAFETY: the first layer is initialized by the caller
+		slice_assume_init_mut(first_layer)
+	};
+	for next_layer in upper_layers.iter_mut() {
+		compress_layer::<H, C>(compression, tags, prev_layer, next_layer, max_threads);
+
+		prev_layer = unsafe {
+			// SAFETY: next_layer was just initialized by compress_layer
+			slice_assume_init_mut(next_layer)
+		};
+	}
+}
+
+/// Builds a binary Merkle tree over leaves that arrive in append-only chunks.
+///
+/// The elements of the leaves are pushed with [`Self::push_leaves`] in any number of pieces, and
+/// every complete chunk of `2^log_chunk_len` leaves is hashed and folded into the root of its
+/// subtree as soon as it arrives, as in [`build_chunked`]. Only the elements of an incomplete
+/// chunk are buffered, so the committed vector never needs to be held in memory at once. The
+/// tree returned by [`Self::finalize`] is identical to the one built by [`build_chunked`] over
+/// the concatenation of the pushed elements.
+#[derive(Debug)]
+pub struct BinaryMerkleTreeBuilder<'a, F, H: ParallelDigest, C> {
+	compression: &'a C,
+	tags: NodeTags,
+	batch_size: usize,
+	log_len: usize,
+	log_chunk_len: usize,
+	max_threads: Option<usize>,
+	/// The nodes of the tree, which are initialized as they are computed
+	inner_nodes: NodeStorage<Output<H::Digest>>,
+	salts: Vec<Output<H::Digest>>,
+	/// The number of leaves hashed so far, which is a multiple of the chunk length
+	n_hashed_leaves: usize,
+	/// The elements pushed after the last complete chunk
+	pending: Vec<F>,
+}
+
+impl<'a, F, H, C> BinaryMerkleTreeBuilder<'a, F, H, C>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+{
+	/// Starts building a tree over `2^log_len` leaves of `batch_size` elements each into
+	/// `inner_nodes`, which must be allocated for [`tree_len`] nodes.
+	///
+	/// The other arguments are as for [`build_chunked_into`].
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(
+		compression: &'a C,
+		log_len: usize,
+		batch_size: usize,
+		log_chunk_len: usize,
+		max_threads: Option<usize>,
+		salts: Vec<Output<H::Digest>>,
+		separation: DomainSeparation,
+		inner_nodes: NodeStorage<Output<H::Digest>>,
+	) -> Result<Self, Error> {
+		if batch_size == 0 {
+			bail!(Error::IncorrectBatchSize);
+		}
+		check_salts_len(&salts, log_len)?;
+		Ok(Self {
+			compression,
+			tags: NodeTags::new(separation),
+			batch_size,
+			log_len,
+			log_chunk_len: log_chunk_len.min(log_len),
+			max_threads,
+			inner_nodes,
+			salts,
+			n_hashed_leaves: 0,
+			pending: Vec::new(),
+		})
+	}
+
+	/// Appends the elements of the next leaves, hashing and folding every chunk they complete.
+	///
+	/// The elements need not fill whole leaves or chunks; the remainder is buffered until the
+	/// next call.
+	pub fn push_leaves(&mut self, mut values: &[F]) -> Result<(), Error> {
+		let total_len = self.batch_size << self.log_len;
+		let pushed_len = self.n_hashed_leaves * self.batch_size + self.pending.len();
+		if pushed_len + values.len() > total_len {
+			bail!(Error::IncorrectVectorLen {
+				expected: total_len
+			});
+		}
+
+		let chunk_elements_len = self.batch_size << self.log_chunk_len;
+		if !self.pending.is_empty() {
+			let n_missing = (chunk_elements_len - self.pending.len()).min(values.len());
+			let (head, tail) = values.split_at(n_missing);
+			self.pending.extend_from_slice(head);
+			values = tail;
+			if self.pending.len() < chunk_elements_len {
+				return Ok(());
+			}
+			let pending = std::mem::take(&mut self.pending);
+			self.hash_chunks(&pending)?;
+		}
+
+		let (chunks, rest) =
+			values.split_at(values.len() / chunk_elements_len * chunk_elements_len);
+		if !chunks.is_empty() {
+			self.hash_chunks(chunks)?;
+		}
+		self.pending.extend_from_slice(rest);
+		Ok(())
+	}
+
+	/// Appends the next leaves, given by an iterator over the elements of each leaf.
+	///
+	/// Whole chunks of leaves that follow the leaves hashed so far are hashed from the iterator in
+	/// place, so a caller holding the leaves in another layout, such as packed field elements,
+	/// need not copy them. Otherwise the elements are buffered as by [`Self::push_leaves`].
+	pub fn push_leaves_iterated<ParIter>(&mut self, leaves: ParIter) -> Result<(), Error>
+	where
+		ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+	{
+		let n_leaves = leaves.len();
+		let chunk_len = 1 << self.log_chunk_len;
+		if !self.pending.is_empty() || n_leaves % chunk_len != 0 {
+			let values = leaves.flat_map_iter(|leaf| leaf).collect::<Vec<_>>();
+			return self.push_leaves(&values);
+		}
+		if self.n_hashed_leaves + n_leaves > 1 << self.log_len {
+			bail!(Error::IncorrectVectorLen {
+				expected: self.batch_size << self.log_len
+			});
+		}
+		if n_leaves == 0 {
+			return Ok(());
+		}
+
+		let chunks = leaves
+			.chunks(chunk_len)
+			.map(move |chunk_leaves| chunk_leaves.into_par_iter().with_min_len(chunk_len));
+		self.hash_leaf_chunks(chunks, n_leaves)
+	}
+
+	/// Hashes and folds whole chunks of leaves following the ones hashed so far.
+	fn hash_chunks(&mut self, elements: &[F]) -> Result<(), Error> {
+		let n_leaves = elements.len() / self.batch_size;
+		let batch_size = self.batch_size;
+		let chunk_len = 1 << self.log_chunk_len;
+		let chunks = elements
+			.par_chunks(batch_size * chunk_len)
+			.map(move |chunk_elements| {
+				chunk_elements
+					.par_chunks(batch_size)
+					.with_min_len(chunk_len)
+					.map(|leaf| leaf.iter().copied())
+			});
+		self.hash_leaf_chunks(chunks, n_leaves)
+	}
+
+	/// Hashes and folds the `n_leaves` leaves following the ones hashed so far, given as whole
+	/// chunks of leaves.
+	fn hash_leaf_chunks<Chunks>(&mut self, chunks: Chunks, n_leaves: usize) -> Result<(), Error>
+	where
+		Chunks:
+			IndexedParallelIterator<Item: IndexedParallelIterator<Item: IntoIterator<Item = F>>>,
+	{
+		let log_chunk_len = self.log_chunk_len;
+		let first_leaf = self.n_hashed_leaves;
+		let mut layers =
+			split_layers(self.inner_nodes.uninit_mut(tree_len(self.log_len)), self.log_len);
+		let mut chunked_layers = layers[..=log_chunk_len]
+			.iter_mut()
+			.enumerate()
+			.map(|(i, layer)| &mut layer[first_leaf >> i..(first_leaf + n_leaves) >> i])
+			.collect::<Vec<_>>();
+		let salts = if self.salts.is_empty() {
+			&[]
+		} else {
+			&self.salts[first_leaf..first_leaf + n_leaves]
+		};
+		hash_and_fold_leaf_chunks::<F, H, C, _>(
+			self.compression,
+			self.tags,
+			self.log_len,
+			chunks,
+			group_chunk_layers(&mut chunked_layers, log_chunk_len),
+			salts,
+			self.max_threads,
+		)?;
+
+		self.n_hashed_leaves += n_leaves;
+		Ok(())
+	}
+
+	/// Folds the roots of the chunk subtrees up to the root and returns the tree.
+	///
+	/// Fails if fewer than `2^log_len` leaves were pushed.
+	pub fn finalize(mut self) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error> {
+		if self.n_hashed_leaves != 1 << self.log_len {
+			bail!(Error::IncorrectVectorLen {
+				expected: self.batch_size << self.log_len
+			});
+		}
+
+		let total_length = tree_len(self.log_len);
+		let mut layers = split_layers(self.inner_nodes.uninit_mut(total_length), self.log_len);
+		fold_upper_layers::<H::Digest, C>(
+			self.compression,
+			self.tags,
+			&mut layers[self.log_chunk_len..],
+			self.max_threads,
+		);
+
+		unsafe {
+			// SAFETY: the chunk subtrees were initialized by hash_chunks, since all leaves were
+			// hashed, and the upper layers by fold_upper_layers
+			self.inner_nodes.assume_init(total_length);
+		}
+		Ok(BinaryMerkleTree {
+			log_len: self.log_len,
+			inner_nodes: self.inner_nodes,
+			salts: self.salts,
+		})
+	}
+}
+
+/// Returns the number of nodes in a tree with `2^log_len` leaves.
+pub const fn tree_len(log_len: usize) -> usize {
+	(1 << (log_len + 1)) - 1
+}
+
+fn check_salts_len<D>(salts: &[D], log_len: usize) -> Result<(), Error> {
+	if !salts.is_empty() && salts.len() != 1 << log_len {
+		bail!(Error::IncorrectVectorLen {
+			expected: 1 << log_len
+		});
+	}
+	Ok(())
+}
 
-	let (prev_layer, mut remaining) = inner_nodes.spare_capacity_mut().split_at_mut(1 << log_len);
+pub(super) fn internal_build<H, C>(
+	compression: &C,
+	tags: NodeTags,
+	// Must either successfully initialize the passed in slice or return error, hashing the leaves
+	// after the leaf prefix of `tags`
+	hash_leaves: impl FnOnce(&mut [MaybeUninit<Output<H>>]) -> Result<(), Error>,
+	log_len: usize,
+	max_threads: Option<usize>,
+	salts: Vec<Output<H>>,
+	// Must be allocated for the nodes of the tree
+	mut inner_nodes: NodeStorage<Output<H>>,
+) -> Result<BinaryMerkleTree<Output<H>>, Error>
+where
+	H: Digest,
+	C: PseudoCompressionFunction<Output<H>, 2> + Sync,
+{
+	check_salts_len(&salts, log_len)?;
+
+	let total_length = tree_len(log_len);
+	let nodes = inner_nodes.uninit_mut(total_length);
+
+	hash_leaves(&mut nodes[..(1 << log_len)])?;
+
+	let (prev_layer, mut remaining) = nodes.split_at_mut(1 << log_len);
 
 	let mut prev_layer = unsafe {
 		// SAFETY: prev-layer was initialized by hash_leaves
 		slice_assume_init_mut(prev_layer)
 	};
+	if !salts.is_empty() {
+		let min_len = min_task_len(salts.len(), max_threads);
+		prev_layer
+			.par_iter_mut()
+			.zip(salts.par_iter())
+			.with_min_len(min_len)
+			.for_each(|(leaf, salt)| {
+				*leaf = compression.compress([leaf.clone(), salt.clone()]);
+			});
+	}
 	for i in 1..(log_len + 1) {
 		let (next_layer, next_remaining) = remaining.split_at_mut(1 << (log_len - i));
 		remaining = next_remaining;
 
-		compress_layer(compression, prev_layer, next_layer);
+		compress_layer::<H, C>(compression, tags, prev_layer, next_layer, max_threads);
 
 		prev_layer = unsafe {
 			// SAFETY: next_layer was just initialized by compress_layer
@@ -88,13 +830,14 @@ where
 
 	unsafe {
 		// SAFETY: inner_nodes should be entirely initialized by now
-		// Note that we don't incrementally update inner_nodes.len() since
-		// that doesn't play well with using split_at_mut on spare capacity.
-		inner_nodes.set_len(total_length);
+		// Note that we don't incrementally update the initialized length since
+		// that doesn't play well with using split_at_mut on the uninitialized storage.
+		inner_nodes.assume_init(total_length);
 	}
 	Ok(BinaryMerkleTree {
 		log_len,
 		inner_nodes,
+		salts,
 	})
 }
 
@@ -110,10 +853,95 @@ where
 	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
 	ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
 {
-	internal_build(
+	build_from_iterator_with_max_threads::<F, H, C, _>(
 		compression,
-		|inner_nodes| hash_iterated::<F, H, _>(iterated_chunks, inner_nodes),
+		iterated_chunks,
 		log_len,
+		None,
+		Vec::new(),
+		DomainSeparation::None,
+		NodeStorage::memory(tree_len(log_len)),
+	)
+}
+
+/// Builds a Merkle tree over the leaves yielded by the iterator, splitting the hashing and folding
+/// of each layer into at most `max_threads` tasks, if given.
+///
+/// The leaves are salted with `salts` if it is not empty, as in [`build_chunked`], the hashes of
+/// the nodes are separated as configured by `separation`, and the nodes are written to
+/// `inner_nodes`, which must be allocated for [`tree_len`] nodes.
+pub fn build_from_iterator_with_max_threads<F, H, C, ParIter>(
+	compression: &C,
+	iterated_chunks: ParIter,
+	log_len: usize,
+	max_threads: Option<usize>,
+	salts: Vec<Output<H::Digest>>,
+	separation: DomainSeparation,
+	inner_nodes: NodeStorage<Output<H::Digest>>,
+) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+	ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+{
+	let tags = NodeTags::new(separation);
+	internal_build::<H::Digest, C>(
+		compression,
+		tags,
+		|inner_nodes| {
+			hash_iterated::<F, H, _>(
+				tags.leaf_prefix(log_len).as_ref(),
+				iterated_chunks.with_min_len(min_task_len(1 << log_len, max_threads)),
+				inner_nodes,
+			)
+		},
+		log_len,
+		max_threads,
+		salts,
+		inner_nodes,
+	)
+}
+
+/// Builds the top of a Merkle tree whose subtrees were built separately.
+///
+/// When the leaves are split into $2^k$ contiguous ranges of equal power-of-two size, the roots of
+/// the Merkle trees over each range are the layer at depth $k$ of the tree over all leaves. This
+/// builds the tree with that layer as its leaves, so its root is the root of the full tree, and the
+/// authentication path of a leaf is its branch in the subtree followed by the branch of the
+/// subtree root in the returned tree.
+///
+/// The inner nodes are hashed as the top of a tree separated as configured by `separation`, so
+/// with domain separation, each subtree must have been built with the same separation offset by
+/// the depth of its root, as by [`BinaryMerkleTreeProver::commit_subtree`].
+///
+/// [`BinaryMerkleTreeProver::commit_subtree`]: super::BinaryMerkleTreeProver::commit_subtree
+pub fn build_from_subtree_roots<H, C>(
+	compression: &C,
+	separation: DomainSeparation,
+	subtree_roots: &[Output<H>],
+) -> Result<BinaryMerkleTree<Output<H>>, Error>
+where
+	H: Digest,
+	C: PseudoCompressionFunction<Output<H>, 2> + Sync,
+{
+	if !subtree_roots.len().is_power_of_two() {
+		bail!(Error::PowerOfTwoLengthRequired);
+	}
+
+	internal_build::<H, C>(
+		compression,
+		NodeTags::new(separation),
+		|inner_nodes| {
+			for (node, root) in inner_nodes.iter_mut().zip(subtree_roots) {
+				node.write(root.clone());
+			}
+			Ok(())
+		},
+		log2_strict_usize(subtree_roots.len()),
+		None,
+		Vec::new(),
+		NodeStorage::memory(tree_len(log2_strict_usize(subtree_roots.len()))),
 	)
 }
 
@@ -129,6 +957,9 @@ impl<D: Clone> BinaryMerkleTree<D> {
 		if layer_depth > self.log_len {
 			bail!(Error::IncorrectLayerDepth);
 		}
+		if layer_depth > self.stored_depth() {
+			bail!(Error::PrunedLayer { layer_depth });
+		}
 		let range_start = self.inner_nodes.len() + 1 - (1 << (layer_depth + 1));
 
 		Ok(&self.inner_nodes[range_start..range_start + (1 << layer_depth)])
@@ -144,38 +975,145 @@ impl<D: Clone> BinaryMerkleTree<D> {
 			});
 		}
 
-		let branch = (0..self.log_len - layer_depth)
-			.map(|j| {
-				let node_index = (((1 << j) - 1) << (self.log_len + 1 - j)) | (index >> j) ^ 1;
-				self.inner_nodes[node_index].clone()
-			})
-			.collect();
+		self.node_branch(index, self.log_len, layer_depth)
+	}
+
+	/// Get the Merkle branch of the node at `node_index` in the layer at `node_depth`, up to the
+	/// layer at `layer_depth`.
+	pub(super) fn node_branch(
+		&self,
+		node_index: usize,
+		node_depth: usize,
+		layer_depth: usize,
+	) -> Result<Vec<D>, Error> {
+		(layer_depth + 1..=node_depth)
+			.rev()
+			.enumerate()
+			.map(|(j, depth)| Ok(self.layer(depth)?[(node_index >> j) ^ 1].clone()))
+			.collect()
+	}
+
+	/// Returns the depth of the deepest layer kept in the tree, which is the depth of the leaves
+	/// unless the tree was pruned.
+	pub fn stored_depth(&self) -> usize {
+		log2_strict_usize(self.inner_nodes.len() + 1) - 1
+	}
+
+	/// Drops the layers below `layer_depth`, keeping the layers from the root down to it.
+	///
+	/// The layers at and above the optimal verification layer suffice for the verifier, while the
+	/// leaf layer alone takes half of the tree. The openings of a pruned tree are proven with
+	/// [`super::MerkleTreeProver::prove_opening_from_leaves`], which re-derives the part of the
+	/// path below the kept layers from the values of the leaves. Pruning a tree to a layer at or
+	/// below its stored depth keeps it unchanged.
+	pub fn prune_to_layer(&mut self, layer_depth: usize) -> Result<(), Error> {
+		if layer_depth > self.log_len {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		if layer_depth < self.stored_depth() {
+			let range_start = self.inner_nodes.len() - tree_len(layer_depth);
+			self.inner_nodes = self.inner_nodes[range_start..].to_vec().into();
+		}
+		Ok(())
+	}
 
+	/// Get the digests that authenticate the leaves at the given indices together against the
+	/// layer at `layer_depth`.
+	///
+	/// The leaves are taken in ascending order of index, without duplicates. Walking up from the
+	/// leaves one layer at a time, the digest of the sibling of every node on the paths is
+	/// included, in ascending order of node index, unless the sibling is itself on a path, in
+	/// which case the verifier computes it. Each digest is thus sent at most once, however many
+	/// paths share it.
+	///
+	/// Throws if an index is out of range
+	pub fn multi_branch(&self, indices: &[usize], layer_depth: usize) -> Result<Vec<D>, Error> {
+		if layer_depth > self.log_len || indices.iter().any(|&index| index >= 1 << self.log_len) {
+			return Err(Error::IndexOutOfRange {
+				max: (1 << self.log_len) - 1,
+			});
+		}
+
+		let mut nodes = sorted_distinct(indices);
+		let mut branch = Vec::new();
+		for depth in (layer_depth + 1..=self.log_len).rev() {
+			let layer = self.layer(depth)?;
+			branch.extend(missing_siblings(&nodes).map(|sibling| layer[sibling].clone()));
+			nodes.dedup_by_key(|node| *node >> 1);
+			for node in &mut nodes {
+				*node >>= 1;
+			}
+		}
 		Ok(branch)
 	}
 }
 
+/// Returns the indices in ascending order without duplicates.
+pub(super) fn sorted_distinct(indices: &[usize]) -> Vec<usize> {
+	let mut sorted = indices.to_vec();
+	sorted.sort_unstable();
+	sorted.dedup();
+	sorted
+}
+
+/// Returns the indices of the siblings of the sorted, distinct nodes that are not among the nodes
+/// themselves, in ascending order.
+pub(super) fn missing_siblings(nodes: &[usize]) -> impl Iterator<Item = usize> + '_ {
+	nodes
+		.iter()
+		.enumerate()
+		.filter(|&(i, &node)| {
+			let sibling = node ^ 1;
+			let sibling_is_node = if node & 1 == 0 {
+				nodes.get(i + 1) == Some(&sibling)
+			} else {
+				i > 0 && nodes[i - 1] == sibling
+			};
+			!sibling_is_node
+		})
+		.map(|(_, &node)| node ^ 1)
+}
+
 #[tracing::instrument("MerkleTree::compress_layer", skip_all, level = "debug")]
-fn compress_layer<D, C>(compression: &C, prev_layer: &[D], next_layer: &mut [MaybeUninit<D>])
-where
-	D: Clone + Send + Sync,
-	C: PseudoCompressionFunction<D, 2> + Sync,
+fn compress_layer<H, C>(
+	compression: &C,
+	tags: NodeTags,
+	prev_layer: &[Output<H>],
+	next_layer: &mut [MaybeUninit<Output<H>>],
+	max_threads: Option<usize>,
+) where
+	H: Digest,
+	C: PseudoCompressionFunction<Output<H>, 2> + Sync,
 {
+	let depth = log2_strict_usize(next_layer.len());
+	let min_len = min_task_len(next_layer.len(), max_threads);
 	prev_layer
 		.par_chunks_exact(2)
 		.zip(next_layer.par_iter_mut())
+		.with_min_len(min_len)
 		.for_each(|(prev_pair, next_digest)| {
-			next_digest.write(compression.compress(array::from_fn(|i| prev_pair[i].clone())));
+			next_digest.write(tags.compress_inner::<H, C>(
+				compression,
+				array::from_fn(|i| prev_pair[i].clone()),
+				depth,
+			));
 		})
 }
 
+/// Returns the minimum number of items per task that splits `len` items into at most
+/// `max_threads` tasks.
+fn min_task_len(len: usize, max_threads: Option<usize>) -> usize {
+	max_threads.map_or(1, |max_threads| len.div_ceil(max_threads.max(1)))
+}
+
 /// Hashes the elements in chunks of a vector into digests.
 ///
 /// Given a vector of elements and an output buffer of N hash digests, this splits the elements
-/// into N equal-sized chunks and hashes each chunks into the corresponding output digest. This
-/// returns the number of elements hashed into each digest.
+/// into N equal-sized chunks and hashes each chunks, after `prefix`, into the corresponding output
+/// digest. This returns the number of elements hashed into each digest.
 #[tracing::instrument("hash_interleaved", skip_all, level = "debug")]
-fn hash_interleaved<F, H>(
+pub(super) fn hash_interleaved<F, H>(
+	prefix: &[u8],
 	elems: &[F],
 	digests: &mut [MaybeUninit<Output<H::Digest>>],
 ) -> Result<(), Error>
@@ -192,10 +1130,12 @@ where
 	let hash_data_iter = elems
 		.par_chunks(elems.len() / digests.len())
 		.map(|s| s.iter().copied());
-	hash_iterated::<_, H, _>(hash_data_iter, digests)
+	hash_iterated::<_, H, _>(prefix, hash_data_iter, digests)
 }
 
-fn hash_iterated<F, H, ParIter>(
+/// Hashes the elements yielded for each digest after `prefix`.
+pub(super) fn hash_iterated<F, H, ParIter>(
+	prefix: &[u8],
 	iterated_chunks: ParIter,
 	digests: &mut [MaybeUninit<Output<H::Digest>>],
 ) -> Result<(), Error>
@@ -204,7 +1144,7 @@ where
 	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
 	ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
 {
-	let hasher = H::new();
+	let hasher = H::new_with_prefix(prefix);
 	hasher.digest(iterated_chunks, digests);
 
 	Ok(())
@@ -14,8 +14,16 @@ pub enum Error {
 	PowerOfTwoLengthRequired,
 	#[error("The layer does not exist in the Merkle tree")]
 	IncorrectLayerDepth,
+	#[error("the layer at depth {layer_depth} was pruned from the Merkle tree")]
+	PrunedLayer { layer_depth: usize },
+	#[error("the leaves are salted, so the vector must be verified with its salts")]
+	SaltsRequired,
 	#[error("transcript error: {0}")]
 	Transcript(#[from] transcript::Error),
+	#[error("I/O error: {0}")]
+	Io(#[from] std::io::Error),
+	#[error("compute layer error: {0}")]
+	ComputeLayer(#[from] binius_compute::Error),
 	#[error("verification failure: {0}")]
 	Verification(#[from] VerificationError),
 }
@@ -26,6 +34,29 @@ pub enum VerificationError {
 	IncorrectVectorLength,
 	#[error("the shape of the proof is incorrect")]
 	IncorrectProofShape,
-	#[error("the proof is invalid")]
-	InvalidProof,
+	#[error("the vector does not hash to the committed root")]
+	IncorrectVectorRoot,
+	#[error("the layer at depth {layer_depth} does not hash to the committed root")]
+	IncorrectLayerRoot { layer_depth: usize },
+	#[error(
+		"the authentication path of node {index} at depth {node_depth} does not match digest \
+		{layer_index} of the layer at depth {layer_depth}"
+	)]
+	IncorrectPath {
+		index: usize,
+		node_depth: usize,
+		layer_index: usize,
+		layer_depth: usize,
+	},
+	#[error(
+		"the authentication path of node {index} at depth {node_depth} does not match its \
+		ancestor at depth {ancestor_depth}, computed from the layer"
+	)]
+	IncorrectPathAboveLayer {
+		index: usize,
+		node_depth: usize,
+		ancestor_depth: usize,
+	},
+	#[error("the leaf {index} is opened more than once with different values")]
+	InconsistentOpenings { index: usize },
 }
@@ -1,10 +1,13 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use binius_maybe_rayon::iter::IndexedParallelIterator;
+use std::ops::Range;
+
+use binius_maybe_rayon::prelude::*;
+use binius_utils::{DeserializeBytes, SerializationMode, SerializeBytes, bail};
 use bytes::{Buf, BufMut};
 
-use super::errors::Error;
-use crate::transcript::{TranscriptReader, TranscriptWriter};
+use super::errors::{Error, VerificationError};
+use crate::transcript::{self, TranscriptReader, TranscriptWriter};
 
 /// A Merkle tree commitment.
 ///
@@ -20,19 +23,101 @@ pub struct Commitment<Digest> {
 
 /// A Merkle tree scheme.
 pub trait MerkleTreeScheme<T>: Sync {
-	type Digest: Clone + PartialEq + Eq;
+	type Digest: Clone + PartialEq + Eq + Send + Sync;
+
+	/// Returns the height of the Merkle cap committed to in place of a single root.
+	///
+	/// A cap of height `k` consists of the `2^k` digests at depth `k` of the tree, or of the
+	/// leaves if the tree is shallower. Openings are never proven or verified to a layer above the
+	/// cap, which shortens every authentication path by `k` digests at the cost of a larger
+	/// commitment.
+	fn cap_height(&self) -> usize {
+		0
+	}
+
+	/// Returns the root of a tree whose layer at the cap height is `cap`.
+	///
+	/// The default implementation supports only schemes without a cap, whose cap is the root.
+	fn cap_root(&self, cap: &[Self::Digest]) -> Result<Self::Digest, Error> {
+		match cap {
+			[root] => Ok(root.clone()),
+			_ => bail!(VerificationError::IncorrectVectorLength),
+		}
+	}
+
+	/// Reads the commitment to a tree of depth `tree_depth` written by
+	/// [`MerkleTreeProver::write_commitment`].
+	///
+	/// The proof holds the Merkle cap of the tree, from which the root is recomputed. Returns the
+	/// commitment together with the cap, which is the layer of digests at the cap height and
+	/// consists of the root alone when the cap height is zero. A verifier holding the cap checks
+	/// openings against it directly, so authentication paths stop at the cap instead of the root.
+	#[allow(clippy::type_complexity)]
+	fn read_commitment<B: Buf>(
+		&self,
+		tree_depth: usize,
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(Commitment<Self::Digest>, Vec<Self::Digest>), Error>
+	where
+		Self::Digest: DeserializeBytes,
+	{
+		let cap = proof.read_vec(1 << self.cap_height().min(tree_depth))?;
+		let root = self.cap_root(&cap)?;
+		let commitment = Commitment {
+			root,
+			depth: tree_depth,
+		};
+		Ok((commitment, cap))
+	}
 
 	/// Returns the optimal layer that the verifier should verify only once.
 	fn optimal_verify_layer(&self, n_queries: usize, tree_depth: usize) -> usize;
 
+	/// Returns the number of digests in the authentication path of a leaf, from the leaf up to,
+	/// but not including, the node at `opening_depth`.
+	///
+	/// This is one digest per level in a binary tree; schemes with a higher branching factor send
+	/// several sibling digests per node but cross fewer levels.
+	fn opening_path_len(&self, tree_depth: usize, opening_depth: usize) -> usize {
+		tree_depth.saturating_sub(opening_depth)
+	}
+
 	/// Returns the total byte-size of a proof for multiple opening queries.
 	///
 	/// ## Arguments
 	///
 	/// * `len` - the length of the committed vector
 	/// * `n_queries` - the number of opening queries
+	/// * `layer_depth` - the depth of the layer the openings are verified against; layer digests at
+	///   or above the cap height are part of the commitment and are not counted
 	fn proof_size(&self, len: usize, n_queries: usize, layer_depth: usize) -> Result<usize, Error>;
 
+	/// Returns the byte-size of the batch opening proof of the entries at the given indices, as
+	/// generated by [`MerkleTreeProver::prove_openings`], verified against the layer at
+	/// `layer_depth` of a tree of depth `tree_depth`.
+	///
+	/// Unlike [`Self::proof_size`], this accounts for the digests that the paths of the concrete
+	/// indices share. The default implementation assumes the openings are sent one by one.
+	fn proof_size_exact(
+		&self,
+		indices: &[usize],
+		tree_depth: usize,
+		layer_depth: usize,
+	) -> Result<usize, Error> {
+		self.proof_size(1 << tree_depth, indices.len(), layer_depth)
+	}
+
+	/// Returns the layer minimizing [`Self::proof_size_exact`] for the given indices, preferring
+	/// the shallowest among equally small ones.
+	fn optimal_verify_layer_exact(&self, indices: &[usize], tree_depth: usize) -> usize {
+		(self.cap_height().min(tree_depth)..=tree_depth)
+			.min_by_key(|&layer_depth| {
+				self.proof_size_exact(indices, tree_depth, layer_depth)
+					.unwrap_or(usize::MAX)
+			})
+			.unwrap_or(tree_depth)
+	}
+
 	/// Verify the opening of the full vector.
 	fn verify_vector(
 		&self,
@@ -41,6 +126,30 @@ pub trait MerkleTreeScheme<T>: Sync {
 		batch_size: usize,
 	) -> Result<(), Error>;
 
+	/// Verify the opening of the full vector, reading any data besides the vector that the
+	/// scheme needs from the proof.
+	///
+	/// The proof is generated with [`MerkleTreeProver::prove_vector_opening`]. Schemes that hash
+	/// the leaves from their values alone need no such data and verify the vector with
+	/// [`Self::verify_vector`].
+	fn verify_vector_opening<B: Buf>(
+		&self,
+		root: &Self::Digest,
+		data: &[T],
+		batch_size: usize,
+		_proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		self.verify_vector(root, data, batch_size)
+	}
+
+	/// Returns the number of digests in the proof of an opening of the full vector, which has
+	/// `n_leaves` leaves.
+	///
+	/// See [`Self::verify_vector_opening`].
+	fn vector_opening_len(&self, _n_leaves: usize) -> usize {
+		0
+	}
+
 	/// Verify a given layer of the Merkle tree.
 	///
 	/// When a protocol requires verification of many openings at independent and randomly sampled
@@ -54,6 +163,9 @@ pub trait MerkleTreeScheme<T>: Sync {
 	) -> Result<(), Error>;
 
 	/// Verify an opening proof for an entry in a committed vector at the given index.
+	///
+	/// The layer depth must not be smaller than the cap height, since the prover's authentication
+	/// paths stop at the cap.
 	fn verify_opening<B: Buf>(
 		&self,
 		index: usize,
@@ -63,6 +175,133 @@ pub trait MerkleTreeScheme<T>: Sync {
 		layer_digests: &[Self::Digest],
 		proof: &mut TranscriptReader<B>,
 	) -> Result<(), Error>;
+
+	/// Verify an opening proof for an entry in a committed vector at the given index, where the
+	/// opening was proven to `opening_depth` rather than to the depth of the verified layer.
+	///
+	/// Openings against the same tree may be proven to different depths, for example when a batch
+	/// of queries mixes codewords of different sizes. The opening depth must not exceed
+	/// `layer_depth`; the part of the authentication path above the verified layer is checked
+	/// against `layer_digests`.
+	///
+	/// The default implementation supports only openings proven to the verified layer, which it
+	/// verifies with [`Self::verify_opening`].
+	#[allow(clippy::too_many_arguments)]
+	fn verify_opening_at_depth<B: Buf>(
+		&self,
+		index: usize,
+		values: &[T],
+		opening_depth: usize,
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		if opening_depth != layer_depth {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		self.verify_opening(index, values, layer_depth, tree_depth, layer_digests, proof)
+	}
+
+	/// Verify an opening for an entry in a committed vector at the given index, given the
+	/// authentication path already read from the proof.
+	///
+	/// The path holds the sibling digests ordered from the leaf up to, but not including, the
+	/// node at the opening depth, which is `tree_depth - path.len()`. The opening depth may be
+	/// smaller than `layer_depth`, in which case the path extends above the verified layer and
+	/// its upper part is checked against `layer_digests`. Separating reading from verification
+	/// lets a verifier read many openings sequentially from the transcript and then verify them
+	/// concurrently.
+	///
+	/// The default implementation supports only paths that end at the verified layer, which it
+	/// serializes back into a proof for [`Self::verify_opening`].
+	fn verify_opening_path(
+		&self,
+		index: usize,
+		values: &[T],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		path: &[Self::Digest],
+	) -> Result<(), Error>
+	where
+		Self::Digest: SerializeBytes,
+	{
+		if path.len() != self.opening_path_len(tree_depth, layer_depth) {
+			bail!(VerificationError::IncorrectProofShape);
+		}
+		let mut buffer = Vec::new();
+		for digest in path {
+			digest
+				.serialize(&mut buffer, SerializationMode::CanonicalTower)
+				.map_err(transcript::Error::from)?;
+		}
+		self.verify_opening(
+			index,
+			values,
+			layer_depth,
+			tree_depth,
+			layer_digests,
+			&mut TranscriptReader::from_buffer(&mut buffer.as_slice()),
+		)
+	}
+
+	/// Verify a batch opening proof for the entries of a committed vector at the given indices.
+	///
+	/// The indices may be in any order and may repeat, and `values` holds the values of the entry
+	/// at each of them. The proof is generated with [`MerkleTreeProver::prove_openings`]. Schemes
+	/// that share digests between the authentication paths send each of them once, so the proof
+	/// is smaller than the openings of the entries one by one, and the verifier hashes each
+	/// distinct leaf and shared inner node once.
+	fn verify_openings<B: Buf>(
+		&self,
+		indices: &[usize],
+		values: &[&[T]],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		if values.len() != indices.len() {
+			return Err(Error::IncorrectVectorLen {
+				expected: indices.len(),
+			});
+		}
+		for (&index, values) in indices.iter().zip(values) {
+			self.verify_opening(index, values, layer_depth, tree_depth, layer_digests, proof)?;
+		}
+		Ok(())
+	}
+
+	/// Verify an opening proof for a pair of sibling entries in a committed vector.
+	///
+	/// The entries at indices `2 * pair_index` and `2 * pair_index + 1` share a parent node, so
+	/// when both are opened, the verifier hashes the pair of leaves itself and the authentication
+	/// path starts one level up, saving one digest compared with opening either entry alone. The
+	/// proof is generated with [`MerkleTreeProver::prove_pair_opening`].
+	///
+	/// ## Arguments
+	///
+	/// * `pair_index` - the index of the parent node of the sibling entries
+	/// * `values` - the values of the left and the right sibling entry
+	///
+	/// The default implementation verifies the openings of the two entries one by one with
+	/// [`Self::verify_opening`], matching the default [`MerkleTreeProver::prove_pair_opening`], so
+	/// a scheme overrides either both or neither.
+	fn verify_pair_opening<B: Buf>(
+		&self,
+		pair_index: usize,
+		values: [&[T]; 2],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		for (index, values) in [2 * pair_index, 2 * pair_index + 1].into_iter().zip(values) {
+			self.verify_opening(index, values, layer_depth, tree_depth, layer_digests, proof)?;
+		}
+		Ok(())
+	}
 }
 
 /// A Merkle tree prover for a particular scheme.
@@ -85,6 +324,32 @@ pub trait MerkleTreeProver<T>: Sync {
 		batch_size: usize,
 	) -> Result<(Commitment<<Self::Scheme as MerkleTreeScheme<T>>::Digest>, Self::Committed), Error>;
 
+	/// Returns the Merkle cap of a committed tree of depth `tree_depth`, which is its layer at the
+	/// cap height of the scheme.
+	fn cap<'a>(
+		&self,
+		committed: &'a Self::Committed,
+		tree_depth: usize,
+	) -> Result<&'a [<Self::Scheme as MerkleTreeScheme<T>>::Digest], Error> {
+		self.layer(committed, self.scheme().cap_height().min(tree_depth))
+	}
+
+	/// Writes a commitment to the proof, to be read with [`MerkleTreeScheme::read_commitment`].
+	///
+	/// Only the Merkle cap is written, which is the root alone when the cap height is zero.
+	fn write_commitment<B: BufMut>(
+		&self,
+		commitment: &Commitment<<Self::Scheme as MerkleTreeScheme<T>>::Digest>,
+		committed: &Self::Committed,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error>
+	where
+		<Self::Scheme as MerkleTreeScheme<T>>::Digest: SerializeBytes,
+	{
+		proof.write_slice(self.cap(committed, commitment.depth)?);
+		Ok(())
+	}
+
 	/// Commit interleaved elements from iterator by val
 	#[allow(clippy::type_complexity)]
 	fn commit_iterated<ParIter>(
@@ -95,6 +360,38 @@ pub trait MerkleTreeProver<T>: Sync {
 	where
 		ParIter: IndexedParallelIterator<Item: IntoIterator<Item = T>>;
 
+	/// Commits a vector of `2^log_len` leaves of `batch_size` elements each, given as consecutive
+	/// pieces of whole leaves.
+	///
+	/// Each piece is an iterator over the elements of its leaves. Provers that hash the leaves as
+	/// they arrive overlap producing the next piece, which may be costly, such as encoding it, with
+	/// hashing the previous one, and hash the pieces without copying them. The default
+	/// implementation collects the pieces and commits them with [`Self::commit`]. The commitment
+	/// and the committed data are those of [`Self::commit`] over the concatenated pieces.
+	#[allow(clippy::type_complexity)]
+	fn commit_pieces<Piece, E>(
+		&self,
+		log_len: usize,
+		batch_size: usize,
+		pieces: impl Iterator<Item = Result<Piece, E>> + Send,
+	) -> Result<(Commitment<<Self::Scheme as MerkleTreeScheme<T>>::Digest>, Self::Committed), E>
+	where
+		T: Send,
+		Piece: IndexedParallelIterator<Item: IntoIterator<Item = T>>,
+		E: From<Error> + Send,
+	{
+		let mut data = Vec::with_capacity(batch_size << log_len);
+		for piece in pieces {
+			data.extend(piece?.flat_map_iter(|leaf| leaf).collect::<Vec<_>>());
+		}
+		if data.len() != batch_size << log_len {
+			bail!(Error::IncorrectVectorLen {
+				expected: batch_size << log_len
+			});
+		}
+		Ok(self.commit(&data, batch_size)?)
+	}
+
 	/// Returns the internal digest layer at the given depth.
 	fn layer<'a>(
 		&self,
@@ -107,7 +404,8 @@ pub trait MerkleTreeProver<T>: Sync {
 	/// ## Arguments
 	///
 	/// * `committed` - helper data generated during commitment
-	/// * `layer_depth` - depth of the layer to prove inclusion in
+	/// * `layer_depth` - depth of the layer to prove inclusion in, which must not be smaller than
+	///   the cap height of the scheme
 	/// * `index` - the entry index
 	fn prove_opening<B: BufMut>(
 		&self,
@@ -116,4 +414,80 @@ pub trait MerkleTreeProver<T>: Sync {
 		index: usize,
 		proof: &mut TranscriptWriter<B>,
 	) -> Result<(), Error>;
+
+	/// Generate an opening proof as [`Self::prove_opening`] for a committed tree whose lower
+	/// layers may have been pruned.
+	///
+	/// `leaf_values` returns the values of the leaves in the given range of indices, concatenated.
+	/// A prover whose tree was pruned below the opening layer hashes the leaves under the deepest
+	/// kept node above the entry to re-derive its path; the default implementation never prunes
+	/// and ignores them.
+	fn prove_opening_from_leaves<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		index: usize,
+		_leaf_values: impl FnOnce(Range<usize>) -> Vec<T>,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		self.prove_opening(committed, layer_depth, index, proof)
+	}
+
+	/// Generate a batch opening proof for the entries of a committed vector at the given indices.
+	///
+	/// The proof is verified with [`MerkleTreeScheme::verify_openings`]. The default
+	/// implementation concatenates the openings of the entries one by one.
+	fn prove_openings<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		indices: &[usize],
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		for &index in indices {
+			self.prove_opening(committed, layer_depth, index, proof)?;
+		}
+		Ok(())
+	}
+
+	/// Generate the proof of an opening of the full committed vector.
+	///
+	/// The vector itself is not written to the proof. The proof is verified with
+	/// [`MerkleTreeScheme::verify_vector_opening`].
+	fn prove_vector_opening<B: BufMut>(
+		&self,
+		_committed: &Self::Committed,
+		_proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+
+	/// Generate an opening proof for a pair of sibling entries in a committed vector.
+	///
+	/// The proof omits the leaf-level sibling digest, since the verifier receives both sibling
+	/// entries and hashes them itself. It is verified with
+	/// [`MerkleTreeScheme::verify_pair_opening`].
+	///
+	/// ## Arguments
+	///
+	/// * `committed` - helper data generated during commitment
+	/// * `layer_depth` - depth of the layer to prove inclusion in, which must be smaller than the
+	///   tree depth
+	/// * `pair_index` - the index of the parent node of the entries `2 * pair_index` and `2 *
+	///   pair_index + 1`
+	///
+	/// The default implementation writes the openings of the two entries one by one with
+	/// [`Self::prove_opening`], which saves no digests.
+	fn prove_pair_opening<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		pair_index: usize,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		for index in [2 * pair_index, 2 * pair_index + 1] {
+			self.prove_opening(committed, layer_depth, index, proof)?;
+		}
+		Ok(())
+	}
 }
@@ -4,12 +4,25 @@ mod binary_merkle_tree;
 mod errors;
 #[allow(clippy::module_inception)]
 mod merkle_tree_vcs;
+mod nary_merkle_tree;
 mod prover;
 mod scheme;
+mod storage;
 #[cfg(test)]
 mod tests;
 
 pub use binary_merkle_tree::*;
+pub use errors::{Error, VerificationError};
 pub use merkle_tree_vcs::*;
-pub use prover::BinaryMerkleTreeProver;
-pub use scheme::BinaryMerkleTreeScheme;
+pub use nary_merkle_tree::{NaryMerkleTree, is_nary_layer, nary_parent_log_arity, nary_path_len};
+pub use prover::{
+	BinaryMerkleTreeProver, Blake3MerkleTreeProver, DEFAULT_LOG_LEAF_CHUNK_LEN,
+	Keccak256MerkleTreeProver, NaryMerkleTreeProver, QuaternaryMerkleTreeProver,
+	Sha256MerkleTreeProver, StreamingCommitter, VisionMerkleTreeProver,
+};
+pub use scheme::{
+	BinaryMerkleTreeScheme, Blake3MerkleTreeScheme, Keccak256MerkleTreeScheme,
+	NaryMerkleTreeScheme, QuaternaryMerkleTreeScheme, Sha256MerkleTreeScheme,
+	VisionMerkleTreeScheme,
+};
+pub use storage::{MappableNode, MappedNodes, NodeStorage, StorageConfig};
@@ -0,0 +1,248 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::mem::MaybeUninit;
+
+use binius_field::TowerField;
+use binius_hash::{PseudoCompressionFunction, multi_digest::ParallelDigest};
+use binius_maybe_rayon::prelude::*;
+use binius_utils::{bail, checked_arithmetics::log2_strict_usize, mem::slice_assume_init_mut};
+use digest::{FixedOutputReset, Output, crypto_common::BlockSizeUser};
+use tracing::instrument;
+
+use super::{
+	binary_merkle_tree::{hash_interleaved, hash_iterated},
+	errors::Error,
+};
+
+/// A Merkle tree with `2^log_arity` children per inner node that commits batches of vectors.
+///
+/// Depths are measured in binary levels, as for [`super::BinaryMerkleTree`], so the leaves of a
+/// tree over `2^log_len` entries are at depth `log_len` and each inner node is `log_arity` binary
+/// levels above its children. The tree therefore has layers at the depths that differ from
+/// `log_len` by a multiple of `log_arity`. When `log_arity` does not divide `log_len`, the layer
+/// closest to the root holds fewer than `2^log_arity` digests, and the root compresses them
+/// together with default digests.
+#[derive(Debug, Clone)]
+pub struct NaryMerkleTree<D> {
+	/// Base-2 logarithm of the number of leaves
+	pub log_len: usize,
+	/// Base-2 logarithm of the number of children of an inner node
+	pub log_arity: usize,
+	/// The inner nodes, arranged as a flattened array of layers with the root at the end
+	pub inner_nodes: Vec<D>,
+}
+
+/// Returns whether a Merkle tree of the given depth and arity has a layer at `layer_depth`.
+pub const fn is_nary_layer(log_arity: usize, tree_depth: usize, layer_depth: usize) -> bool {
+	layer_depth <= tree_depth && ((tree_depth - layer_depth) % log_arity == 0 || layer_depth == 0)
+}
+
+/// Returns the number of sibling digests on the path from a node at `node_depth` up to, but not
+/// including, its ancestor at `ancestor_depth` in a Merkle tree of the given arity.
+///
+/// Each level of `2^log_arity` children contributes `2^log_arity - 1` siblings, and the partial
+/// level below the root of a tree whose depth is not a multiple of `log_arity` contributes one
+/// less than its number of digests.
+pub const fn nary_path_len(log_arity: usize, node_depth: usize, ancestor_depth: usize) -> usize {
+	let height = node_depth - ancestor_depth;
+	(height / log_arity) * ((1 << log_arity) - 1) + (1 << (height % log_arity)) - 1
+}
+
+/// Returns the base-2 logarithm of the number of children of the parent of a node at the given
+/// depth, which is smaller than `log_arity` only for the children of a partial root.
+pub const fn nary_parent_log_arity(log_arity: usize, depth: usize) -> usize {
+	if depth < log_arity { depth } else { log_arity }
+}
+
+/// Returns the depths of the layers of a Merkle tree, from the leaves to the root.
+fn layer_depths(log_arity: usize, log_len: usize) -> impl Iterator<Item = usize> {
+	(log_len % log_arity..=log_len)
+		.rev()
+		.step_by(log_arity)
+		.chain((log_len % log_arity != 0).then_some(0))
+}
+
+pub fn build<F, H, C, const N: usize>(
+	compression: &C,
+	elements: &[F],
+	batch_size: usize,
+) -> Result<NaryMerkleTree<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, N> + Sync,
+{
+	if elements.len() % batch_size != 0 {
+		bail!(Error::IncorrectBatchSize);
+	}
+
+	let len = elements.len() / batch_size;
+
+	if !len.is_power_of_two() {
+		bail!(Error::PowerOfTwoLengthRequired);
+	}
+
+	let log_len = log2_strict_usize(len);
+
+	internal_build(
+		compression,
+		|inner_nodes| hash_interleaved::<_, H>(&[], elements, inner_nodes),
+		log_len,
+	)
+}
+
+#[instrument("NaryMerkleTree::build", skip_all, level = "debug")]
+pub fn build_from_iterator<F, H, C, ParIter, const N: usize>(
+	compression: &C,
+	iterated_chunks: ParIter,
+	log_len: usize,
+) -> Result<NaryMerkleTree<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, N> + Sync,
+	ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+{
+	internal_build(
+		compression,
+		|inner_nodes| hash_iterated::<F, H, _>(&[], iterated_chunks, inner_nodes),
+		log_len,
+	)
+}
+
+fn internal_build<Digest, C, const N: usize>(
+	compression: &C,
+	// Must either successfully initialize the passed in slice or return error
+	hash_leaves: impl FnOnce(&mut [MaybeUninit<Digest>]) -> Result<(), Error>,
+	log_len: usize,
+) -> Result<NaryMerkleTree<Digest>, Error>
+where
+	Digest: Clone + Default + Send + Sync,
+	C: PseudoCompressionFunction<Digest, N> + Sync,
+{
+	let log_arity = const {
+		assert!(N >= 2 && N.is_power_of_two(), "the arity must be a power of two");
+		N.ilog2() as usize
+	};
+	let total_length = layer_depths(log_arity, log_len)
+		.map(|depth| 1 << depth)
+		.sum();
+	let mut inner_nodes = Vec::with_capacity(total_length);
+
+	hash_leaves(&mut inner_nodes.spare_capacity_mut()[..(1 << log_len)])?;
+
+	let (prev_layer, mut remaining) = inner_nodes.spare_capacity_mut().split_at_mut(1 << log_len);
+
+	let mut prev_layer = unsafe {
+		// SAFETY: prev-layer was initialized by hash_leaves
+		slice_assume_init_mut(prev_layer)
+	};
+	for depth in layer_depths(log_arity, log_len).skip(1) {
+		let (next_layer, next_remaining) = remaining.split_at_mut(1 << depth);
+		remaining = next_remaining;
+
+		compress_layer(compression, prev_layer, next_layer);
+
+		prev_layer = unsafe {
+			// SAFETY: next_layer was just initialized by compress_layer
+			slice_assume_init_mut(next_layer)
+		};
+	}
+
+	unsafe {
+		// SAFETY: inner_nodes should be entirely initialized by now
+		// Note that we don't incrementally update inner_nodes.len() since
+		// that doesn't play well with using split_at_mut on spare capacity.
+		inner_nodes.set_len(total_length);
+	}
+	Ok(NaryMerkleTree {
+		log_len,
+		log_arity,
+		inner_nodes,
+	})
+}
+
+impl<D: Clone> NaryMerkleTree<D> {
+	pub fn root(&self) -> D {
+		self.inner_nodes
+			.last()
+			.expect("MerkleTree inner nodes can't be empty")
+			.clone()
+	}
+
+	pub fn layer(&self, layer_depth: usize) -> Result<&[D], Error> {
+		if !is_nary_layer(self.log_arity, self.log_len, layer_depth) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let range_start = layer_depths(self.log_arity, self.log_len)
+			.take_while(|&depth| depth > layer_depth)
+			.map(|depth| 1 << depth)
+			.sum::<usize>();
+
+		Ok(&self.inner_nodes[range_start..range_start + (1 << layer_depth)])
+	}
+
+	/// Get a Merkle branch for the given index
+	///
+	/// The branch holds the siblings of each node on the path in the order of their positions
+	/// among the children of the parent, skipping the node itself. Throws if the index is out of
+	/// range.
+	pub fn branch(&self, index: usize, layer_depth: usize) -> Result<Vec<D>, Error> {
+		if index >= 1 << self.log_len {
+			return Err(Error::IndexOutOfRange {
+				max: (1 << self.log_len) - 1,
+			});
+		}
+		if !is_nary_layer(self.log_arity, self.log_len, layer_depth) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+
+		let mut branch =
+			Vec::with_capacity(nary_path_len(self.log_arity, self.log_len, layer_depth));
+		let mut index = index;
+		for depth in
+			layer_depths(self.log_arity, self.log_len).take_while(|&depth| depth > layer_depth)
+		{
+			let layer = self.layer(depth)?;
+			let log_arity = nary_parent_log_arity(self.log_arity, depth);
+			let group_start = (index >> log_arity) << log_arity;
+			branch.extend(
+				(group_start..group_start + (1 << log_arity))
+					.filter(|&i| i != index)
+					.map(|i| layer[i].clone()),
+			);
+			index >>= log_arity;
+		}
+
+		Ok(branch)
+	}
+}
+
+#[tracing::instrument("NaryMerkleTree::compress_layer", skip_all, level = "debug")]
+fn compress_layer<D, C, const N: usize>(
+	compression: &C,
+	prev_layer: &[D],
+	next_layer: &mut [MaybeUninit<D>],
+) where
+	D: Clone + Default + Send + Sync,
+	C: PseudoCompressionFunction<D, N> + Sync,
+{
+	prev_layer
+		.par_chunks_exact(prev_layer.len() / next_layer.len())
+		.zip(next_layer.par_iter_mut())
+		.for_each(|(children, next_digest)| {
+			next_digest.write(compress_padded(compression, children));
+		})
+}
+
+/// Compresses up to `N` child digests, padding them with default digests.
+///
+/// Only the children of the root of a tree whose depth is not a multiple of the log arity are
+/// padded.
+pub(super) fn compress_padded<D, C, const N: usize>(compression: &C, children: &[D]) -> D
+where
+	D: Clone + Default,
+	C: PseudoCompressionFunction<D, N>,
+{
+	compression.compress(std::array::from_fn(|i| children.get(i).cloned().unwrap_or_default()))
+}
@@ -1,39 +1,383 @@
 // Copyright 2024-2025 Irreducible Inc.
 
+use std::{ops::Range, sync::Mutex};
+
+use binius_compute::{ComputeLayer, ComputeLayerExecutor, FSlice, SizedSlice};
 use binius_field::TowerField;
-use binius_hash::{PseudoCompressionFunction, multi_digest::ParallelDigest};
+use binius_hash::{
+	PseudoCompressionFunction, Vision32Digest, Vision32ParallelDigest,
+	Vision32PermutationCompression, VisionHasherDigest,
+	blake3::{Blake3, Blake3Compression},
+	keccak::{Keccak256, Keccak256Compression},
+	multi_digest::ParallelDigest,
+	sha2::{Sha256, Sha256Compression},
+};
 use binius_maybe_rayon::iter::IndexedParallelIterator;
+use binius_utils::{
+	bail,
+	checked_arithmetics::{log2_ceil_usize, log2_strict_usize},
+};
 use bytes::BufMut;
 use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
-use getset::Getters;
+use getset::{CopyGetters, Getters};
+use rand::{RngCore, SeedableRng, rngs::StdRng};
 
 use super::{
-	binary_merkle_tree::{self, BinaryMerkleTree},
+	binary_merkle_tree::{
+		self, BinaryMerkleTree, BinaryMerkleTreeBuilder, DomainSeparation, LeafPadding, NodeTags,
+	},
 	errors::Error,
-	merkle_tree_vcs::{Commitment, MerkleTreeProver},
-	scheme::BinaryMerkleTreeScheme,
+	merkle_tree_vcs::{Commitment, MerkleTreeProver, MerkleTreeScheme},
+	nary_merkle_tree::{self, NaryMerkleTree, nary_parent_log_arity},
+	scheme::{BinaryMerkleTreeScheme, NaryMerkleTreeScheme, VisionMerkleTreeScheme},
+	storage::{MappableNode, NodeStorage, StorageConfig},
 };
 use crate::transcript::TranscriptWriter;
 
-#[derive(Debug, Getters)]
+/// The default base-2 logarithm of the number of leaves hashed and folded together in one task.
+///
+/// The digests of a chunk of `2^10` leaves take 32 KiB for a 256-bit hash, which fits in the L1 or
+/// L2 cache of common CPUs while leaving enough chunks to balance the load across threads.
+pub const DEFAULT_LOG_LEAF_CHUNK_LEN: usize = 10;
+
+#[derive(Debug, Getters, CopyGetters)]
 pub struct BinaryMerkleTreeProver<T, H: ParallelDigest, C> {
 	#[getset(get = "pub")]
 	scheme: BinaryMerkleTreeScheme<T, H::Digest, C>,
+	/// The base-2 logarithm of the number of leaves hashed and folded together in one task.
+	#[getset(get_copy = "pub")]
+	log_leaf_chunk_len: usize,
+	/// The maximum number of threads that build a tree, if the parallelism is bounded.
+	#[getset(get_copy = "pub")]
+	max_threads: Option<usize>,
+	/// The source of the leaf salts, if the leaves are salted.
+	salt_rng: Option<Mutex<StdRng>>,
+	/// Where the nodes of the committed trees are stored.
+	#[getset(get = "pub")]
+	storage: StorageConfig,
 }
 
+/// A prover for the [`super::Blake3MerkleTreeScheme`].
+pub type Blake3MerkleTreeProver<T> = BinaryMerkleTreeProver<T, Blake3, Blake3Compression>;
+
+/// A prover for the [`super::Sha256MerkleTreeScheme`].
+pub type Sha256MerkleTreeProver<T> = BinaryMerkleTreeProver<T, Sha256, Sha256Compression>;
+
+/// A prover for the [`super::Keccak256MerkleTreeScheme`].
+pub type Keccak256MerkleTreeProver<T> = BinaryMerkleTreeProver<T, Keccak256, Keccak256Compression>;
+
 impl<T, C, H: ParallelDigest> BinaryMerkleTreeProver<T, H, C> {
 	pub fn new(compression: C) -> Self {
+		Self::from_scheme(BinaryMerkleTreeScheme::new(compression))
+	}
+
+	/// Constructs a prover that commits to a Merkle cap of height `cap_height`.
+	///
+	/// See [`BinaryMerkleTreeScheme::with_cap_height`].
+	pub fn with_cap_height(compression: C, cap_height: usize) -> Self {
+		Self::from_scheme(BinaryMerkleTreeScheme::with_cap_height(compression, cap_height))
+	}
+
+	const fn from_scheme(scheme: BinaryMerkleTreeScheme<T, H::Digest, C>) -> Self {
 		Self {
-			scheme: BinaryMerkleTreeScheme::new(compression),
+			scheme,
+			log_leaf_chunk_len: DEFAULT_LOG_LEAF_CHUNK_LEN,
+			max_threads: None,
+			salt_rng: None,
+			storage: StorageConfig::Memory,
+		}
+	}
+
+	/// Sets the number of leaves hashed and folded together in one task when committing to a
+	/// vector to `2^log_leaf_chunk_len`.
+	///
+	/// Smaller chunks balance the load across more threads, and larger chunks fold more layers
+	/// while the digests are in cache. The chunk size does not change the committed tree.
+	pub const fn with_log_leaf_chunk_len(mut self, log_leaf_chunk_len: usize) -> Self {
+		self.log_leaf_chunk_len = log_leaf_chunk_len;
+		self
+	}
+
+	/// Bounds the number of threads that build a Merkle tree to `max_threads`.
+	///
+	/// The hashing and folding are split into at most `max_threads` tasks at a time, so that at
+	/// most that many threads of the thread pool work on a commitment and the others remain
+	/// available for concurrent work.
+	pub const fn with_max_threads(mut self, max_threads: usize) -> Self {
+		self.max_threads = Some(max_threads);
+		self
+	}
+
+	/// Salts the leaves of every committed tree with random digests drawn from a generator seeded
+	/// by `rng`, which makes the commitments hiding.
+	///
+	/// See [`BinaryMerkleTreeScheme::with_salted_leaves`].
+	pub fn with_salted_leaves(mut self, mut rng: impl RngCore) -> Self {
+		let mut seed = <StdRng as SeedableRng>::Seed::default();
+		rng.fill_bytes(&mut seed);
+		self.scheme = self.scheme.with_salted_leaves();
+		self.salt_rng = Some(Mutex::new(StdRng::from_seed(seed)));
+		self
+	}
+
+	/// Pads the leaves of committed vectors whose number of leaves is not a power of two.
+	///
+	/// See [`BinaryMerkleTreeScheme::with_padding`].
+	pub fn with_padding(mut self, padding: LeafPadding) -> Self {
+		self.scheme = self.scheme.with_padding(padding);
+		self
+	}
+
+	/// Tags the digests of the leaves and of the inner nodes of every committed tree.
+	///
+	/// See [`BinaryMerkleTreeScheme::with_domain_separation`].
+	pub fn with_domain_separation(mut self, separation: DomainSeparation) -> Self {
+		self.scheme = self.scheme.with_domain_separation(separation);
+		self
+	}
+
+	/// Stores the nodes of the committed trees as configured by `storage`.
+	///
+	/// With [`StorageConfig::Mapped`], the trees are kept in memory-mapped files, so vectors whose
+	/// trees exceed the available memory can be committed. The openings read the nodes back from
+	/// the file as they are proven.
+	pub fn with_storage(mut self, storage: StorageConfig) -> Self {
+		self.storage = storage;
+		self
+	}
+}
+
+impl<F, H, C> BinaryMerkleTreeProver<F, H, C>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset + Clone + Sync>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+	Output<H::Digest>: MappableNode,
+{
+	/// Returns random salts for the leaves of a tree with `n_leaves` leaves, or no salts if the
+	/// leaves are not salted.
+	fn salts(&self, n_leaves: usize) -> Vec<Output<H::Digest>> {
+		let Some(salt_rng) = &self.salt_rng else {
+			return Vec::new();
+		};
+		let mut rng = salt_rng
+			.lock()
+			.expect("the salt generator is never poisoned");
+		(0..n_leaves)
+			.map(|_| {
+				let mut salt = Output::<H::Digest>::default();
+				rng.fill_bytes(&mut salt);
+				salt
+			})
+			.collect()
+	}
+
+	/// Allocates the storage for the nodes of a tree with `2^log_len` leaves.
+	fn allocate_nodes(&self, log_len: usize) -> Result<NodeStorage<Output<H::Digest>>, Error> {
+		NodeStorage::allocate(&self.storage, binary_merkle_tree::tree_len(log_len))
+	}
+
+	/// Returns the tags of the nodes of the committed trees.
+	fn tags(&self) -> NodeTags {
+		NodeTags::new(*self.scheme.domain_separation())
+	}
+
+	/// Returns the cap height of a committed tree, which is capped by the tree depth.
+	fn cap_height(&self, tree: &BinaryMerkleTree<Output<H::Digest>>) -> usize {
+		self.scheme.cap_height().min(tree.log_len)
+	}
+
+	fn commitment(
+		&self,
+		tree: &BinaryMerkleTree<Output<H::Digest>>,
+	) -> Commitment<Output<H::Digest>> {
+		Commitment {
+			root: tree.root(),
+			depth: tree.log_len,
 		}
 	}
+
+	/// Commits to a vector whose number of leaves is not a power of two, padding the leaf digests
+	/// as configured in the scheme.
+	#[allow(clippy::type_complexity)]
+	fn commit_padded(
+		&self,
+		data: &[F],
+		n_leaves: usize,
+	) -> Result<(Commitment<Output<H::Digest>>, BinaryMerkleTree<Output<H::Digest>>), Error> {
+		let padding = *self.scheme.padding();
+		let log_len = padding.log_len(n_leaves)?;
+		let tags = self.tags();
+		let tree = binary_merkle_tree::internal_build::<H::Digest, _>(
+			self.scheme.compression(),
+			tags,
+			|digests| {
+				binary_merkle_tree::hash_interleaved::<_, H>(
+					tags.leaf_prefix(log_len).as_ref(),
+					data,
+					&mut digests[..n_leaves],
+				)?;
+				padding.pad_uninit(digests, n_leaves)
+			},
+			log_len,
+			self.max_threads,
+			self.salts(1 << log_len),
+			self.allocate_nodes(log_len)?,
+		)?;
+		let commitment = self.commitment(&tree);
+		Ok((commitment, tree))
+	}
+
+	/// Commits a batch of vectors held in the device memory of a compute layer.
+	///
+	/// The leaves are hashed on the device with [`ComputeLayerExecutor::hash_leaves`], so only
+	/// their digests are copied to the host, where the tree is folded. The commitment and tree are
+	/// identical to those of [`MerkleTreeProver::commit`] over the same elements.
+	#[allow(clippy::type_complexity)]
+	pub fn commit_on_device<Hal: ComputeLayer<F>>(
+		&self,
+		hal: &Hal,
+		data: FSlice<'_, F, Hal>,
+		batch_size: usize,
+	) -> Result<(Commitment<Output<H::Digest>>, BinaryMerkleTree<Output<H::Digest>>), Error> {
+		if batch_size == 0 || data.len() % batch_size != 0 {
+			bail!(Error::IncorrectBatchSize);
+		}
+		let len = data.len() / batch_size;
+		if !len.is_power_of_two() {
+			bail!(Error::PowerOfTwoLengthRequired);
+		}
+
+		let log_len = log2_strict_usize(len);
+		let tags = self.tags();
+		let tree = binary_merkle_tree::internal_build::<H::Digest, _>(
+			self.scheme.compression(),
+			tags,
+			|digests| {
+				hal.execute(|exec| {
+					exec.hash_leaves::<H>(data, tags.leaf_prefix(log_len).as_ref(), digests)?;
+					Ok(Vec::new())
+				})?;
+				Ok(())
+			},
+			log_len,
+			self.max_threads,
+			self.salts(len),
+			self.allocate_nodes(log_len)?,
+		)?;
+		let commitment = self.commitment(&tree);
+		Ok((commitment, tree))
+	}
+
+	/// Builds the subtree of a committed tree whose root is at depth `root_depth`, over the batch
+	/// of vectors that the subtree commits.
+	///
+	/// The nodes are hashed as the nodes of the committed tree, so the roots of the subtrees over
+	/// all ranges of leaves are combined into the committed tree by
+	/// [`binary_merkle_tree::build_from_subtree_roots`] with the domain separation of the scheme.
+	///
+	/// ## Arguments
+	///
+	/// * `data` - the batched elements of the leaves of the subtree
+	/// * `batch_size` - the number of elements of each leaf
+	/// * `root_depth` - the depth of the subtree root in the committed tree
+	pub fn commit_subtree(
+		&self,
+		data: &[F],
+		batch_size: usize,
+		root_depth: usize,
+	) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error> {
+		let n_leaves = data.len().checked_div(batch_size).unwrap_or_default();
+		let tags = NodeTags {
+			root_depth,
+			..self.tags()
+		};
+		binary_merkle_tree::build_chunked_tagged_into::<_, H, _>(
+			self.scheme.compression(),
+			tags,
+			data,
+			batch_size,
+			self.log_leaf_chunk_len,
+			self.max_threads,
+			self.salts(n_leaves),
+			self.allocate_nodes(log2_ceil_usize(n_leaves))?,
+		)
+	}
+
+	/// Starts a commitment to a vector of `2^log_len` leaves of `batch_size` elements each, whose
+	/// elements are pushed in append-only pieces.
+	///
+	/// The leaves are hashed and folded as they arrive, so the vector never needs to be
+	/// materialized. The commitment and tree are identical to those of
+	/// [`MerkleTreeProver::commit`] over the concatenation of the pushed elements.
+	pub fn stream_commit(
+		&self,
+		log_len: usize,
+		batch_size: usize,
+	) -> Result<StreamingCommitter<'_, F, H, C>, Error> {
+		let builder = BinaryMerkleTreeBuilder::new(
+			self.scheme.compression(),
+			log_len,
+			batch_size,
+			self.log_leaf_chunk_len,
+			self.max_threads,
+			self.salts(1 << log_len),
+			*self.scheme.domain_separation(),
+			self.allocate_nodes(log_len)?,
+		)?;
+		Ok(StreamingCommitter {
+			prover: self,
+			builder,
+		})
+	}
+}
+
+/// A commitment in progress to a vector whose elements arrive in append-only pieces.
+///
+/// Created by [`BinaryMerkleTreeProver::stream_commit`].
+pub struct StreamingCommitter<'a, F, H: ParallelDigest, C> {
+	prover: &'a BinaryMerkleTreeProver<F, H, C>,
+	builder: BinaryMerkleTreeBuilder<'a, F, H, C>,
+}
+
+impl<F, H, C> StreamingCommitter<'_, F, H, C>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset + Clone + Sync>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+	Output<H::Digest>: MappableNode,
+{
+	/// Appends the elements of the next leaves. See [`BinaryMerkleTreeBuilder::push_leaves`].
+	pub fn push_leaves(&mut self, values: &[F]) -> Result<(), Error> {
+		self.builder.push_leaves(values)
+	}
+
+	/// Appends the next leaves, given by an iterator over the elements of each leaf. See
+	/// [`BinaryMerkleTreeBuilder::push_leaves_iterated`].
+	pub fn push_leaves_iterated<ParIter>(&mut self, leaves: ParIter) -> Result<(), Error>
+	where
+		ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+	{
+		self.builder.push_leaves_iterated(leaves)
+	}
+
+	/// Completes the commitment once all leaves were pushed.
+	#[allow(clippy::type_complexity)]
+	pub fn finalize(
+		self,
+	) -> Result<(Commitment<Output<H::Digest>>, BinaryMerkleTree<Output<H::Digest>>), Error> {
+		let tree = self.builder.finalize()?;
+		let commitment = self.prover.commitment(&tree);
+		Ok((commitment, tree))
+	}
 }
 
 impl<F, H, C> MerkleTreeProver<F> for BinaryMerkleTreeProver<F, H, C>
 where
 	F: TowerField,
-	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset + Clone + Sync>,
 	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+	Output<H::Digest>: MappableNode,
 {
 	type Scheme = BinaryMerkleTreeScheme<F, H::Digest, C>;
 	type Committed = BinaryMerkleTree<Output<H::Digest>>;
@@ -47,13 +391,423 @@ where
 		data: &[F],
 		batch_size: usize,
 	) -> Result<(Commitment<Output<H::Digest>>, Self::Committed), Error> {
-		let tree =
-			binary_merkle_tree::build::<_, H, _>(self.scheme.compression(), data, batch_size)?;
+		let n_leaves = data.len().checked_div(batch_size).unwrap_or_default();
+		if batch_size != 0 && data.len() % batch_size == 0 && !n_leaves.is_power_of_two() {
+			return self.commit_padded(data, n_leaves);
+		}
 
-		let commitment = Commitment {
-			root: tree.root(),
-			depth: tree.log_len,
+		let tree = binary_merkle_tree::build_chunked_into::<_, H, _>(
+			self.scheme.compression(),
+			data,
+			batch_size,
+			self.log_leaf_chunk_len,
+			self.max_threads,
+			self.salts(n_leaves),
+			*self.scheme.domain_separation(),
+			self.allocate_nodes(log2_ceil_usize(n_leaves))?,
+		)?;
+
+		let commitment = self.commitment(&tree);
+
+		Ok((commitment, tree))
+	}
+
+	fn layer<'a>(
+		&self,
+		committed: &'a Self::Committed,
+		depth: usize,
+	) -> Result<&'a [Output<H::Digest>], Error> {
+		committed.layer(depth)
+	}
+
+	fn prove_opening<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		index: usize,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		if layer_depth < self.cap_height(committed) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let branch = committed.branch(index, layer_depth)?;
+		if !committed.salts.is_empty() {
+			proof.write(&committed.salts[index]);
+		}
+		proof.write_slice(&branch);
+		Ok(())
+	}
+
+	fn prove_opening_from_leaves<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		index: usize,
+		leaf_values: impl FnOnce(Range<usize>) -> Vec<F>,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		let stored_depth = committed.stored_depth();
+		if stored_depth == committed.log_len {
+			return self.prove_opening(committed, layer_depth, index, proof);
+		}
+		if layer_depth < self.cap_height(committed) || layer_depth > stored_depth {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		if index >= 1 << committed.log_len {
+			bail!(Error::IndexOutOfRange {
+				max: (1 << committed.log_len) - 1,
+			});
+		}
+
+		// Rebuild the subtree under the deepest kept node above the entry.
+		let log_subtree_len = committed.log_len - stored_depth;
+		let node_index = index >> log_subtree_len;
+		let leaves = node_index << log_subtree_len..(node_index + 1) << log_subtree_len;
+		let salts = if committed.salts.is_empty() {
+			Vec::new()
+		} else {
+			committed.salts[leaves.clone()].to_vec()
+		};
+		let values = leaf_values(leaves.clone());
+		let tags = NodeTags {
+			root_depth: stored_depth,
+			..self.tags()
 		};
+		let subtree = binary_merkle_tree::build_chunked_tagged_into::<_, H, _>(
+			self.scheme.compression(),
+			tags,
+			&values,
+			values.len() >> log_subtree_len,
+			self.log_leaf_chunk_len,
+			self.max_threads,
+			salts,
+			NodeStorage::memory(binary_merkle_tree::tree_len(log_subtree_len)),
+		)?;
+		debug_assert_eq!(subtree.root(), committed.layer(stored_depth)?[node_index]);
+
+		let mut branch = subtree.branch(index - leaves.start, 0)?;
+		branch.extend(committed.node_branch(node_index, stored_depth, layer_depth)?);
+		if !committed.salts.is_empty() {
+			proof.write(&committed.salts[index]);
+		}
+		proof.write_slice(&branch);
+		Ok(())
+	}
+
+	fn prove_openings<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		indices: &[usize],
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		if layer_depth < self.cap_height(committed) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let branch = committed.multi_branch(indices, layer_depth)?;
+		if !committed.salts.is_empty() {
+			for index in binary_merkle_tree::sorted_distinct(indices) {
+				proof.write(&committed.salts[index]);
+			}
+		}
+		proof.write_slice(&branch);
+		Ok(())
+	}
+
+	fn prove_vector_opening<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		proof.write_slice(&committed.salts);
+		Ok(())
+	}
+
+	fn prove_pair_opening<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		pair_index: usize,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		if layer_depth < self.cap_height(committed) || layer_depth >= committed.log_len {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let branch = committed.branch(pair_index << 1, layer_depth)?;
+		if !committed.salts.is_empty() {
+			proof.write_slice(&committed.salts[pair_index << 1..(pair_index + 1) << 1]);
+		}
+		proof.write_slice(&branch[1..]);
+		Ok(())
+	}
+
+	#[allow(clippy::type_complexity)]
+	fn commit_iterated<ParIter>(
+		&self,
+		iterated_chunks: ParIter,
+		log_len: usize,
+	) -> Result<(Commitment<<Self::Scheme as MerkleTreeScheme<F>>::Digest>, Self::Committed), Error>
+	where
+		ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+	{
+		let tree = binary_merkle_tree::build_from_iterator_with_max_threads::<F, H, C, _>(
+			self.scheme.compression(),
+			iterated_chunks,
+			log_len,
+			self.max_threads,
+			self.salts(1 << log_len),
+			*self.scheme.domain_separation(),
+			self.allocate_nodes(log_len)?,
+		)?;
+
+		let commitment = self.commitment(&tree);
+
+		Ok((commitment, tree))
+	}
+
+	#[allow(clippy::type_complexity)]
+	fn commit_pieces<Piece, E>(
+		&self,
+		log_len: usize,
+		batch_size: usize,
+		mut pieces: impl Iterator<Item = Result<Piece, E>> + Send,
+	) -> Result<(Commitment<Output<H::Digest>>, Self::Committed), E>
+	where
+		Piece: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+		E: From<Error> + Send,
+	{
+		let mut committer = self.stream_commit(log_len, batch_size)?;
+		let mut next_piece = pieces.next().transpose()?;
+		while let Some(piece) = next_piece {
+			// Producing the next piece overlaps with hashing this one.
+			let (produced, pushed) = binius_maybe_rayon::join(
+				|| pieces.next().transpose(),
+				|| committer.push_leaves_iterated(piece),
+			);
+			pushed?;
+			next_piece = produced?;
+		}
+		Ok(committer.finalize()?)
+	}
+}
+
+/// A prover for the [`VisionMerkleTreeScheme`].
+///
+/// The tree is built over the byte digests of the Vision hasher, which are then read as
+/// [`Vision32Digest`]s.
+pub struct VisionMerkleTreeProver<T> {
+	scheme: VisionMerkleTreeScheme<T>,
+	inner: BinaryMerkleTreeProver<T, Vision32ParallelDigest, Vision32PermutationCompression>,
+}
+
+impl<T> VisionMerkleTreeProver<T> {
+	pub fn new() -> Self {
+		Self::with_cap_height(0)
+	}
+
+	/// Constructs a prover that commits to a Merkle cap of height `cap_height`.
+	pub fn with_cap_height(cap_height: usize) -> Self {
+		Self {
+			scheme: VisionMerkleTreeScheme::with_cap_height(cap_height),
+			inner: BinaryMerkleTreeProver::with_cap_height(
+				Vision32PermutationCompression,
+				cap_height,
+			),
+		}
+	}
+}
+
+impl<T> Default for VisionMerkleTreeProver<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<F: TowerField> VisionMerkleTreeProver<F> {
+	fn cap_height(&self, tree: &BinaryMerkleTree<Vision32Digest>) -> usize {
+		self.scheme.cap_height().min(tree.log_len)
+	}
+}
+
+/// Reads the byte digests of a tree built by the Vision hasher as field element digests.
+fn into_vision_tree(
+	commitment: Commitment<Output<VisionHasherDigest>>,
+	tree: BinaryMerkleTree<Output<VisionHasherDigest>>,
+) -> (Commitment<Vision32Digest>, BinaryMerkleTree<Vision32Digest>) {
+	let commitment = Commitment {
+		root: commitment.root.into(),
+		depth: commitment.depth,
+	};
+	let tree = BinaryMerkleTree {
+		log_len: tree.log_len,
+		inner_nodes: tree
+			.inner_nodes
+			.iter()
+			.copied()
+			.map(Into::into)
+			.collect::<Vec<_>>()
+			.into(),
+		salts: tree.salts.into_iter().map(Into::into).collect(),
+	};
+	(commitment, tree)
+}
+
+impl<F: TowerField> MerkleTreeProver<F> for VisionMerkleTreeProver<F> {
+	type Scheme = VisionMerkleTreeScheme<F>;
+	type Committed = BinaryMerkleTree<Vision32Digest>;
+
+	fn scheme(&self) -> &Self::Scheme {
+		&self.scheme
+	}
+
+	fn commit(
+		&self,
+		data: &[F],
+		batch_size: usize,
+	) -> Result<(Commitment<Vision32Digest>, Self::Committed), Error> {
+		let (commitment, tree) = self.inner.commit(data, batch_size)?;
+		Ok(into_vision_tree(commitment, tree))
+	}
+
+	fn commit_iterated<ParIter>(
+		&self,
+		iterated_chunks: ParIter,
+		log_len: usize,
+	) -> Result<(Commitment<Vision32Digest>, Self::Committed), Error>
+	where
+		ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+	{
+		let (commitment, tree) = self.inner.commit_iterated(iterated_chunks, log_len)?;
+		Ok(into_vision_tree(commitment, tree))
+	}
+
+	fn commit_pieces<Piece, E>(
+		&self,
+		log_len: usize,
+		batch_size: usize,
+		pieces: impl Iterator<Item = Result<Piece, E>> + Send,
+	) -> Result<(Commitment<Vision32Digest>, Self::Committed), E>
+	where
+		Piece: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+		E: From<Error> + Send,
+	{
+		let (commitment, tree) = self.inner.commit_pieces(log_len, batch_size, pieces)?;
+		Ok(into_vision_tree(commitment, tree))
+	}
+
+	fn layer<'a>(
+		&self,
+		committed: &'a Self::Committed,
+		depth: usize,
+	) -> Result<&'a [Vision32Digest], Error> {
+		committed.layer(depth)
+	}
+
+	fn prove_opening<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		index: usize,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		if layer_depth < self.cap_height(committed) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let branch = committed.branch(index, layer_depth)?;
+		if !committed.salts.is_empty() {
+			proof.write(&committed.salts[index]);
+		}
+		proof.write_slice(&branch);
+		Ok(())
+	}
+
+	fn prove_openings<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		indices: &[usize],
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		if layer_depth < self.cap_height(committed) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let branch = committed.multi_branch(indices, layer_depth)?;
+		if !committed.salts.is_empty() {
+			for index in binary_merkle_tree::sorted_distinct(indices) {
+				proof.write(&committed.salts[index]);
+			}
+		}
+		proof.write_slice(&branch);
+		Ok(())
+	}
+
+	fn prove_vector_opening<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		proof.write_slice(&committed.salts);
+		Ok(())
+	}
+
+	fn prove_pair_opening<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		pair_index: usize,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		if layer_depth < self.cap_height(committed) || layer_depth >= committed.log_len {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let branch = committed.branch(pair_index << 1, layer_depth)?;
+		if !committed.salts.is_empty() {
+			proof.write_slice(&committed.salts[pair_index << 1..(pair_index + 1) << 1]);
+		}
+		proof.write_slice(&branch[1..]);
+		Ok(())
+	}
+}
+
+#[derive(Debug, Getters)]
+pub struct NaryMerkleTreeProver<T, H: ParallelDigest, C, const N: usize> {
+	#[getset(get = "pub")]
+	scheme: NaryMerkleTreeScheme<T, H::Digest, C, N>,
+}
+
+/// A prover for the [`super::QuaternaryMerkleTreeScheme`].
+pub type QuaternaryMerkleTreeProver<T, H, C> = NaryMerkleTreeProver<T, H, C, 4>;
+
+impl<T, C, H: ParallelDigest, const N: usize> NaryMerkleTreeProver<T, H, C, N> {
+	pub fn new(compression: C) -> Self {
+		Self {
+			scheme: NaryMerkleTreeScheme::new(compression),
+		}
+	}
+}
+
+impl<F, H, C, const N: usize> MerkleTreeProver<F> for NaryMerkleTreeProver<F, H, C, N>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset + Clone + Sync>,
+	C: PseudoCompressionFunction<Output<H::Digest>, N> + Sync,
+{
+	type Scheme = NaryMerkleTreeScheme<F, H::Digest, C, N>;
+	type Committed = NaryMerkleTree<Output<H::Digest>>;
+
+	fn scheme(&self) -> &Self::Scheme {
+		&self.scheme
+	}
+
+	fn commit(
+		&self,
+		data: &[F],
+		batch_size: usize,
+	) -> Result<(Commitment<Output<H::Digest>>, Self::Committed), Error> {
+		let tree =
+			nary_merkle_tree::build::<_, H, _, N>(self.scheme.compression(), data, batch_size)?;
+
+		let commitment = nary_commitment(&tree);
 
 		Ok((commitment, tree))
 	}
@@ -78,29 +832,50 @@ where
 		Ok(())
 	}
 
+	fn prove_pair_opening<B: BufMut>(
+		&self,
+		committed: &Self::Committed,
+		layer_depth: usize,
+		pair_index: usize,
+		proof: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		if layer_depth >= committed.log_len {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let index = pair_index << 1;
+		let mut branch = committed.branch(index, layer_depth)?;
+		// The siblings of the left entry are ordered by position, so the right entry, which is
+		// next to it, comes right after the siblings before the pair.
+		let log_arity = nary_parent_log_arity(committed.log_arity, committed.log_len);
+		branch.remove(index & ((1 << log_arity) - 1));
+		proof.write_slice(&branch);
+		Ok(())
+	}
+
 	#[allow(clippy::type_complexity)]
 	fn commit_iterated<ParIter>(
 		&self,
 		iterated_chunks: ParIter,
 		log_len: usize,
-	) -> Result<
-		(Commitment<<Self::Scheme as super::MerkleTreeScheme<F>>::Digest>, Self::Committed),
-		Error,
-	>
+	) -> Result<(Commitment<<Self::Scheme as MerkleTreeScheme<F>>::Digest>, Self::Committed), Error>
 	where
 		ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
 	{
-		let tree = binary_merkle_tree::build_from_iterator::<F, H, C, _>(
+		let tree = nary_merkle_tree::build_from_iterator::<F, H, C, _, N>(
 			self.scheme.compression(),
 			iterated_chunks,
 			log_len,
 		)?;
 
-		let commitment = Commitment {
-			root: tree.root(),
-			depth: tree.log_len,
-		};
+		let commitment = nary_commitment(&tree);
 
 		Ok((commitment, tree))
 	}
 }
+
+fn nary_commitment<D: Clone>(tree: &NaryMerkleTree<D>) -> Commitment<D> {
+	Commitment {
+		root: tree.root(),
+		depth: tree.log_len,
+	}
+}
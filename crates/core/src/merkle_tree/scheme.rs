@@ -1,9 +1,16 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::{array, fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, marker::PhantomData};
 
 use binius_field::TowerField;
-use binius_hash::{PseudoCompressionFunction, hash_serialize};
+use binius_hash::{
+	PseudoCompressionFunction, Vision32Digest, Vision32PermutationCompression, VisionHasherDigest,
+	blake3::{Blake3, Blake3Compression},
+	hash_serialize, hash_serialize_batch, hash_serialize_batch_with_prefix,
+	hash_serialize_with_prefix,
+	keccak::{Keccak256, Keccak256Compression},
+	sha2::{Sha256, Sha256Compression},
+};
 use binius_utils::{
 	bail,
 	checked_arithmetics::{log2_ceil_usize, log2_strict_usize},
@@ -13,8 +20,10 @@ use digest::{Digest, Output, core_api::BlockSizeUser};
 use getset::Getters;
 
 use super::{
+	binary_merkle_tree::{DomainSeparation, LeafPadding, missing_siblings, sorted_distinct},
 	errors::{Error, VerificationError},
 	merkle_tree_vcs::MerkleTreeScheme,
+	nary_merkle_tree::{compress_padded, is_nary_layer, nary_parent_log_arity, nary_path_len},
 };
 use crate::transcript::TranscriptReader;
 
@@ -22,71 +31,369 @@ use crate::transcript::TranscriptReader;
 pub struct BinaryMerkleTreeScheme<T, H, C> {
 	#[getset(get = "pub")]
 	compression: C,
+	cap_height: usize,
+	/// Whether each leaf digest is compressed with a random salt, which is revealed with the
+	/// openings of the leaf.
+	salted: bool,
+	/// How vectors whose number of leaves is not a power of two are padded.
+	#[getset(get = "pub")]
+	padding: LeafPadding,
+	/// How the hashes of the leaves and of the inner nodes are separated.
+	#[getset(get = "pub")]
+	domain_separation: DomainSeparation,
 	// This makes it so that `BinaryMerkleTreeScheme` remains Send + Sync
 	// See https://doc.rust-lang.org/nomicon/phantom-data.html#table-of-phantomdata-patterns
 	_phantom: PhantomData<fn() -> (T, H)>,
 }
 
+/// A binary Merkle tree scheme that hashes with BLAKE3.
+pub type Blake3MerkleTreeScheme<T> = BinaryMerkleTreeScheme<T, Blake3, Blake3Compression>;
+
+/// A binary Merkle tree scheme that hashes with SHA-256, on the SHA extensions of the CPU when
+/// available.
+pub type Sha256MerkleTreeScheme<T> = BinaryMerkleTreeScheme<T, Sha256, Sha256Compression>;
+
+/// A binary Merkle tree scheme that hashes with Keccak-256, for verifiers on the EVM.
+pub type Keccak256MerkleTreeScheme<T> = BinaryMerkleTreeScheme<T, Keccak256, Keccak256Compression>;
+
 impl<T, H, C> BinaryMerkleTreeScheme<T, H, C> {
 	pub fn new(compression: C) -> Self {
+		Self::with_cap_height(compression, 0)
+	}
+
+	/// Constructs a scheme that commits to the `2^cap_height` digests at depth `cap_height`
+	/// instead of a single root.
+	pub fn with_cap_height(compression: C, cap_height: usize) -> Self {
 		Self {
 			compression,
+			cap_height,
+			salted: false,
+			padding: LeafPadding::None,
+			domain_separation: DomainSeparation::None,
 			_phantom: PhantomData,
 		}
 	}
+
+	/// Makes the scheme hiding by salting the leaves.
+	///
+	/// Each leaf digest is compressed with a random salt chosen by the prover, so the digests in
+	/// the authentication paths reveal nothing about the values of the leaves that are not
+	/// opened. The salt of an opened leaf is sent before its authentication path.
+	pub const fn with_salted_leaves(mut self) -> Self {
+		self.salted = true;
+		self
+	}
+
+	/// Returns whether the leaves are salted. See [`Self::with_salted_leaves`].
+	pub const fn is_salted(&self) -> bool {
+		self.salted
+	}
+
+	/// Commits to vectors with any number of leaves, padding their leaf digests to the next power
+	/// of two as given by `padding`.
+	///
+	/// The commitment is to the tree over the padded leaves, whose depth is the base-2 logarithm
+	/// of the padded length, and the entries of the vector are opened as usual.
+	pub const fn with_padding(mut self, padding: LeafPadding) -> Self {
+		self.padding = padding;
+		self
+	}
+
+	/// Separates the hashes of the leaves and of the inner nodes as given by `separation`, so that
+	/// no node can be opened as a node of the other kind or, with
+	/// [`DomainSeparation::TaggedWithDepth`], at another depth.
+	///
+	/// The values of every leaf are hashed after the leaf prefix, before the digest is salted, and
+	/// the children of every inner node are hashed after the inner prefix, including the nodes the
+	/// verifier folds from a layer or a cap. Padding digests are not hashed, so they are not
+	/// prefixed.
+	pub const fn with_domain_separation(mut self, separation: DomainSeparation) -> Self {
+		self.domain_separation = separation;
+		self
+	}
+}
+
+impl<T, H, C> BinaryMerkleTreeScheme<T, H, C>
+where
+	H: Digest,
+	C: PseudoCompressionFunction<Output<H>, 2>,
+{
+	/// Verifies the authentication path of the node at the given index and depth against a layer.
+	///
+	/// The path holds the sibling digests ordered from the node up to, but not including, the node
+	/// at the opening depth, which is `node_depth - path.len()`. See
+	/// [`MerkleTreeScheme::verify_opening_path`].
+	fn verify_node_path(
+		&self,
+		node_index: usize,
+		mut digest: Output<H>,
+		node_depth: usize,
+		layer_depth: usize,
+		layer_digests: &[Output<H>],
+		path: &[Output<H>],
+	) -> Result<(), Error>
+	where
+		C: Sync,
+	{
+		if (1 << layer_depth) != layer_digests.len() {
+			bail!(VerificationError::IncorrectVectorLength);
+		}
+
+		if layer_depth > node_depth {
+			bail!(Error::IncorrectLayerDepth);
+		}
+
+		if path.len() < node_depth - layer_depth || path.len() > node_depth {
+			bail!(VerificationError::IncorrectVectorLength);
+		}
+
+		let (path_below_layer, path_above_layer) = path.split_at(node_depth - layer_depth);
+		let mut index = node_index;
+		let mut depth = node_depth;
+		for branch_node in path_below_layer {
+			depth -= 1;
+			digest = self.compress_branch(index, depth, digest, branch_node);
+			index >>= 1;
+		}
+
+		if digest != layer_digests[index] {
+			bail!(VerificationError::IncorrectPath {
+				index: node_index,
+				node_depth,
+				layer_index: index,
+				layer_depth,
+			});
+		}
+		if path_above_layer.is_empty() {
+			return Ok(());
+		}
+
+		// The opening was proven to a shallower depth than the verified layer. Fold the path up to
+		// the opening depth and compare against the ancestor computed from the layer, which
+		// authenticates the siblings above the layer.
+		let ancestor_height = path_above_layer.len();
+		let subtree_start = (index >> ancestor_height) << ancestor_height;
+		let expected_ancestor = self.fold_layer(
+			&layer_digests[subtree_start..subtree_start + (1 << ancestor_height)],
+			layer_depth,
+		)?;
+		for branch_node in path_above_layer {
+			depth -= 1;
+			digest = self.compress_branch(index, depth, digest, branch_node);
+			index >>= 1;
+		}
+
+		if digest != expected_ancestor {
+			bail!(VerificationError::IncorrectPathAboveLayer {
+				index: node_index,
+				node_depth,
+				ancestor_depth: layer_depth - ancestor_height,
+			});
+		}
+		Ok(())
+	}
+
+	/// Compresses the digest of a leaf with its salt, which is the first entry of the path, if the
+	/// leaves are salted. Returns the leaf digest and the rest of the path.
+	fn salt_leaf<'a>(
+		&self,
+		leaf_digest: Output<H>,
+		path: &'a [Output<H>],
+	) -> Result<(Output<H>, &'a [Output<H>]), Error> {
+		if !self.salted {
+			return Ok((leaf_digest, path));
+		}
+		let Some((salt, path)) = path.split_first() else {
+			bail!(VerificationError::IncorrectProofShape);
+		};
+		Ok((self.compression.compress([leaf_digest, salt.clone()]), path))
+	}
+
+	/// Compresses the digests of two children into the digest of their parent at `depth`.
+	fn compress_node(&self, children: [Output<H>; 2], depth: usize) -> Output<H> {
+		self.domain_separation
+			.compress_inner::<H, C>(&self.compression, children, depth)
+	}
+
+	/// Compresses a node digest with its sibling, ordered by the parity of the node index, into
+	/// the digest of their parent at `parent_depth`.
+	fn compress_branch(
+		&self,
+		index: usize,
+		parent_depth: usize,
+		digest: Output<H>,
+		sibling: &Output<H>,
+	) -> Output<H> {
+		let children = if index & 1 == 0 {
+			[digest, sibling.clone()]
+		} else {
+			[sibling.clone(), digest]
+		};
+		self.compress_node(children, parent_depth)
+	}
+
+	/// Folds the digests of consecutive nodes at depth `depth`, whose number is a power of two,
+	/// into the digest of their common ancestor.
+	fn fold_layer(&self, digests: &[Output<H>], depth: usize) -> Result<Output<H>, Error> {
+		if !digests.len().is_power_of_two() {
+			bail!(Error::PowerOfTwoLengthRequired);
+		}
+		if digests.len() == 1 {
+			return Ok(digests[0].clone());
+		}
+
+		// Only the parents of the layer are buffered, rather than a copy of the layer itself.
+		let mut depth = depth - 1;
+		let mut parents = digests
+			.chunks_exact(2)
+			.map(|pair| self.compress_node([pair[0].clone(), pair[1].clone()], depth))
+			.collect::<Vec<_>>();
+		while parents.len() > 1 {
+			depth -= 1;
+			for i in 0..parents.len() / 2 {
+				parents[i] =
+					self.compress_node([parents[2 * i].clone(), parents[2 * i + 1].clone()], depth);
+			}
+			parents.truncate(parents.len() / 2);
+		}
+		Ok(parents.swap_remove(0))
+	}
 }
 
 impl<F, H, C> MerkleTreeScheme<F> for BinaryMerkleTreeScheme<F, H, C>
 where
 	F: TowerField,
-	H: Digest + BlockSizeUser,
+	H: Digest + BlockSizeUser + Clone + Send + Sync,
 	C: PseudoCompressionFunction<Output<H>, 2> + Sync,
 {
 	type Digest = Output<H>;
 
+	fn cap_height(&self) -> usize {
+		self.cap_height
+	}
+
+	fn cap_root(&self, cap: &[Self::Digest]) -> Result<Self::Digest, Error> {
+		self.fold_layer(cap, log2_ceil_usize(cap.len()))
+	}
+
+	/// The salt of the leaf comes first in the path when the leaves are salted.
+	fn opening_path_len(&self, tree_depth: usize, opening_depth: usize) -> usize {
+		tree_depth.saturating_sub(opening_depth) + usize::from(self.salted)
+	}
+
 	/// This layer allows minimizing the proof size.
+	///
+	/// Layers above the cap are never optimal, because the verifier already holds the cap.
 	fn optimal_verify_layer(&self, n_queries: usize, tree_depth: usize) -> usize {
-		log2_ceil_usize(n_queries).min(tree_depth)
+		log2_ceil_usize(n_queries)
+			.max(self.cap_height)
+			.min(tree_depth)
 	}
 
 	fn proof_size(&self, len: usize, n_queries: usize, layer_depth: usize) -> Result<usize, Error> {
-		if !len.is_power_of_two() {
-			bail!(Error::PowerOfTwoLengthRequired)
-		}
-
-		let log_len = log2_strict_usize(len);
+		let log_len = self.padding.log_len(len)?;
 
 		if layer_depth > log_len {
 			bail!(Error::IncorrectLayerDepth)
 		}
 
-		Ok(((log_len - layer_depth - 1) * n_queries + (1 << layer_depth))
+		// Paths stop at the cap, and the cap digests are sent with the commitment.
+		let cap_height = self.cap_height.min(log_len);
+		let layer_depth = layer_depth.max(cap_height);
+		let n_layer_digests = if layer_depth > cap_height {
+			1 << layer_depth
+		} else {
+			0
+		};
+
+		let n_salts = if self.salted { n_queries } else { 0 };
+		Ok(((log_len - layer_depth).saturating_sub(1) * n_queries + n_layer_digests + n_salts)
+			* <H as Digest>::output_size())
+	}
+
+	fn proof_size_exact(
+		&self,
+		indices: &[usize],
+		tree_depth: usize,
+		layer_depth: usize,
+	) -> Result<usize, Error> {
+		let cap_height = self.cap_height.min(tree_depth);
+		if layer_depth > tree_depth || layer_depth < cap_height {
+			bail!(Error::IncorrectLayerDepth);
+		}
+
+		let n_siblings = batch_sibling_counts(indices, tree_depth)?;
+		let n_layer_digests = if layer_depth > cap_height {
+			1 << layer_depth
+		} else {
+			0
+		};
+		let n_salts = if self.salted {
+			sorted_distinct(indices).len()
+		} else {
+			0
+		};
+		Ok((n_siblings[layer_depth..].iter().sum::<usize>() + n_layer_digests + n_salts)
 			* <H as Digest>::output_size())
 	}
 
+	/// The sizes of all layers are computed together from the sibling counts of one pass up the
+	/// tree.
+	fn optimal_verify_layer_exact(&self, indices: &[usize], tree_depth: usize) -> usize {
+		let Ok(n_siblings) = batch_sibling_counts(indices, tree_depth) else {
+			return self.optimal_verify_layer(indices.len(), tree_depth);
+		};
+
+		// The number of digests below and in each layer, from the cap down to the leaves.
+		let cap_height = self.cap_height.min(tree_depth);
+		let mut n_path_digests = n_siblings[cap_height..].iter().sum::<usize>();
+		let mut optimal = (cap_height, n_path_digests);
+		for layer_depth in cap_height + 1..=tree_depth {
+			n_path_digests -= n_siblings[layer_depth - 1];
+			let n_digests = n_path_digests + (1 << layer_depth);
+			if n_digests < optimal.1 {
+				optimal = (layer_depth, n_digests);
+			}
+		}
+		optimal.0
+	}
+
 	fn verify_vector(
 		&self,
 		root: &Self::Digest,
 		data: &[F],
 		batch_size: usize,
+	) -> Result<(), Error> {
+		if self.salted {
+			bail!(Error::SaltsRequired);
+		}
+		self.verify_salted_vector(root, data, batch_size, &[])
+	}
+
+	fn verify_vector_opening<B: Buf>(
+		&self,
+		root: &Self::Digest,
+		data: &[F],
+		batch_size: usize,
+		proof: &mut TranscriptReader<B>,
 	) -> Result<(), Error> {
 		if data.len() % batch_size != 0 {
 			bail!(Error::IncorrectBatchSize);
 		}
+		let salts = proof.read_vec(self.vector_opening_len(data.len() / batch_size))?;
+		self.verify_salted_vector(root, data, batch_size, &salts)
+	}
 
-		let mut digests = data
-			.chunks(batch_size)
-			.map(|chunk| {
-				hash_serialize::<F, H>(chunk)
-					.expect("values are of TowerField type which we expect to be serializable")
-			})
-			.collect::<Vec<_>>();
-
-		fold_digests_vector_inplace(&self.compression, &mut digests)?;
-		if digests[0] != *root {
-			bail!(VerificationError::InvalidProof)
+	/// The proof holds the salts of all leaves when the leaves are salted.
+	/// The padding leaves are salted as well.
+	fn vector_opening_len(&self, n_leaves: usize) -> usize {
+		if !self.salted {
+			return 0;
+		}
+		match self.padding.log_len(n_leaves) {
+			Ok(log_len) => 1 << log_len,
+			Err(_) => n_leaves,
 		}
-		Ok(())
 	}
 
 	fn verify_layer(
@@ -99,70 +406,851 @@ where
 			bail!(VerificationError::IncorrectVectorLength)
 		}
 
-		let mut digests = layer_digests.to_owned();
-
-		fold_digests_vector_inplace(&self.compression, &mut digests)?;
-
-		if digests[0] != *root {
-			bail!(VerificationError::InvalidProof)
+		if self.fold_layer(layer_digests, layer_depth)? != *root {
+			bail!(VerificationError::IncorrectLayerRoot { layer_depth })
 		}
 		Ok(())
 	}
 
 	fn verify_opening<B: Buf>(
 		&self,
-		mut index: usize,
+		index: usize,
 		values: &[F],
 		layer_depth: usize,
 		tree_depth: usize,
 		layer_digests: &[Self::Digest],
 		proof: &mut TranscriptReader<B>,
 	) -> Result<(), Error> {
-		if (1 << layer_depth) != layer_digests.len() {
-			bail!(VerificationError::IncorrectVectorLength);
+		if layer_depth < self.cap_height.min(tree_depth) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let path = proof.read_vec(self.opening_path_len(tree_depth, layer_depth))?;
+		self.verify_opening_path(index, values, layer_depth, tree_depth, layer_digests, &path)
+	}
+
+	fn verify_opening_at_depth<B: Buf>(
+		&self,
+		index: usize,
+		values: &[F],
+		opening_depth: usize,
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		if opening_depth > layer_depth || opening_depth < self.cap_height.min(tree_depth) {
+			bail!(Error::IncorrectLayerDepth);
 		}
+		let path = proof.read_vec(self.opening_path_len(tree_depth, opening_depth))?;
+		self.verify_opening_path(index, values, layer_depth, tree_depth, layer_digests, &path)
+	}
 
+	fn verify_opening_path(
+		&self,
+		index: usize,
+		values: &[F],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		path: &[Self::Digest],
+	) -> Result<(), Error> {
 		if index >= (1 << tree_depth) {
 			bail!(Error::IndexOutOfRange {
 				max: (1 << tree_depth) - 1
 			});
 		}
 
-		let mut leaf_digest = hash_serialize::<F, H>(values)
-			.expect("values are of TowerField type which we expect to be serializable");
-		for branch_node in proof.read_vec(tree_depth - layer_depth)? {
-			leaf_digest = self.compression.compress(if index & 1 == 0 {
-				[leaf_digest, branch_node]
-			} else {
-				[branch_node, leaf_digest]
+		let leaf_digest = self.hash_leaf(values, tree_depth);
+		let (leaf_digest, path) = self.salt_leaf(leaf_digest, path)?;
+		self.verify_node_path(index, leaf_digest, tree_depth, layer_depth, layer_digests, path)
+	}
+
+	/// The salts of the distinct opened leaves, in ascending order of index, precede the sibling
+	/// digests when the leaves are salted. See [`super::BinaryMerkleTree::multi_branch`] for the
+	/// order of the sibling digests.
+	fn verify_openings<B: Buf>(
+		&self,
+		indices: &[usize],
+		values: &[&[F]],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		if values.len() != indices.len() {
+			bail!(Error::IncorrectVectorLen {
+				expected: indices.len(),
 			});
-			index >>= 1;
 		}
+		if layer_depth > tree_depth || layer_depth < self.cap_height.min(tree_depth) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		if (1 << layer_depth) != layer_digests.len() {
+			bail!(VerificationError::IncorrectVectorLength);
+		}
+		if indices.iter().any(|&index| index >= 1 << tree_depth) {
+			bail!(Error::IndexOutOfRange {
+				max: (1 << tree_depth) - 1
+			});
+		}
+
+		// Sort the openings by index, checking that repeated openings agree, so that every distinct
+		// leaf is hashed once.
+		let mut openings = indices.iter().copied().zip(values).collect::<Vec<_>>();
+		openings.sort_unstable_by_key(|&(index, _)| index);
+		for pair in openings.windows(2) {
+			if pair[0].0 == pair[1].0 && pair[0].1 != pair[1].1 {
+				bail!(VerificationError::InconsistentOpenings { index: pair[0].0 });
+			}
+		}
+		openings.dedup_by_key(|&mut (index, _)| index);
+
+		let mut leaves = openings
+			.into_iter()
+			.map(|(index, values)| (index, self.hash_leaf(values, tree_depth)))
+			.collect::<Vec<_>>();
+
+		if self.salted {
+			let salts = proof.read_vec(leaves.len())?;
+			for ((_, digest), salt) in leaves.iter_mut().zip(salts) {
+				*digest = self.compression.compress([digest.clone(), salt]);
+			}
+		}
+
+		// Each node is tracked with the smallest opened leaf below it, to locate failures.
+		let mut nodes = leaves
+			.into_iter()
+			.map(|(index, digest)| (index, index, digest))
+			.collect::<Vec<_>>();
+		for depth in (layer_depth..tree_depth).rev() {
+			let node_indices = nodes.iter().map(|&(node, ..)| node).collect::<Vec<_>>();
+			let n_siblings = missing_siblings(&node_indices).count();
+			let mut siblings = proof.read_vec(n_siblings)?.into_iter();
+
+			let mut parents = Vec::with_capacity(nodes.len());
+			let mut nodes_iter = nodes.into_iter().peekable();
+			while let Some((node, leaf, digest)) = nodes_iter.next() {
+				let parent_digest = if node & 1 == 0 {
+					match nodes_iter.next_if(|&(next, ..)| next == node | 1) {
+						Some((_, _, right)) => self.compress_node([digest, right], depth),
+						None => self.compress_branch(
+							node,
+							depth,
+							digest,
+							&siblings
+								.next()
+								.expect("one sibling was read per missing sibling"),
+						),
+					}
+				} else {
+					self.compress_branch(
+						node,
+						depth,
+						digest,
+						&siblings
+							.next()
+							.expect("one sibling was read per missing sibling"),
+					)
+				};
+				parents.push((node >> 1, leaf, parent_digest));
+			}
+			nodes = parents;
+		}
+
+		for (node, leaf, digest) in nodes {
+			if digest != layer_digests[node] {
+				bail!(VerificationError::IncorrectPath {
+					index: leaf,
+					node_depth: tree_depth,
+					layer_index: node,
+					layer_depth,
+				});
+			}
+		}
+		Ok(())
+	}
+
+	fn verify_pair_opening<B: Buf>(
+		&self,
+		pair_index: usize,
+		values: [&[F]; 2],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		if layer_depth >= tree_depth || layer_depth < self.cap_height {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		if pair_index >= (1 << (tree_depth - 1)) {
+			bail!(Error::IndexOutOfRange {
+				max: (1 << (tree_depth - 1)) - 1
+			});
+		}
+
+		let path = proof.read_vec(tree_depth - 1 - layer_depth + 2 * usize::from(self.salted))?;
+		let [left, right] = values.map(|values| self.hash_leaf(values, tree_depth));
+		// The salts of both leaves precede the path when the leaves are salted.
+		let (left, path) = self.salt_leaf(left, &path)?;
+		let (right, path) = self.salt_leaf(right, path)?;
+		let parent_digest = self.compress_node([left, right], tree_depth - 1);
+		self.verify_node_path(
+			pair_index,
+			parent_digest,
+			tree_depth - 1,
+			layer_depth,
+			layer_digests,
+			path,
+		)
+	}
+}
+
+impl<F, H, C> BinaryMerkleTreeScheme<F, H, C>
+where
+	F: TowerField,
+	H: Digest + BlockSizeUser + Clone + Send + Sync,
+	C: PseudoCompressionFunction<Output<H>, 2> + Sync,
+{
+	/// Hashes the values of a leaf of a tree of depth `tree_depth`.
+	fn hash_leaf(&self, values: &[F], tree_depth: usize) -> Output<H> {
+		hash_serialize_with_prefix::<F, H>(
+			self.domain_separation.leaf_prefix(tree_depth).as_ref(),
+			values,
+		)
+		.expect("values are of TowerField type which we expect to be serializable")
+	}
+
+	/// Verifies a full vector against the root, compressing each leaf digest with its salt if
+	/// `salts` is not empty.
+	fn verify_salted_vector(
+		&self,
+		root: &Output<H>,
+		data: &[F],
+		batch_size: usize,
+		salts: &[Output<H>],
+	) -> Result<(), Error> {
+		if data.len() % batch_size != 0 {
+			bail!(Error::IncorrectBatchSize);
+		}
+
+		let tree_depth = self.padding.log_len(data.len() / batch_size)?;
+		let mut digests = hash_serialize_batch_with_prefix::<F, H>(
+			self.domain_separation.leaf_prefix(tree_depth).as_ref(),
+			data,
+			batch_size,
+		);
+		self.padding.pad(&mut digests)?;
+		if !salts.is_empty() {
+			if salts.len() != digests.len() {
+				bail!(VerificationError::IncorrectProofShape);
+			}
+			for (digest, salt) in digests.iter_mut().zip(salts) {
+				*digest = self.compression.compress([digest.clone(), salt.clone()]);
+			}
+		}
+
+		if self.fold_layer(&digests, tree_depth)? != *root {
+			bail!(VerificationError::IncorrectVectorRoot)
+		}
+		Ok(())
+	}
+}
+
+/// A binary Merkle tree scheme whose digests are vectors of field elements, for recursion.
+///
+/// Leaves are hashed with the Vision Mark-32 sponge and inner nodes are compressed with a single
+/// truncated Vision Mark-32 permutation, so that verifying an opening inside a Binius constraint
+/// system is cheap. The digests are [`Vision32Digest`]s, which serialize to the same bytes as the
+/// byte digests of the Vision hasher, so the proofs have the layout of a
+/// [`BinaryMerkleTreeScheme`] over [`VisionHasherDigest`].
+pub struct VisionMerkleTreeScheme<T> {
+	inner: BinaryMerkleTreeScheme<T, VisionHasherDigest, Vision32PermutationCompression>,
+}
+
+impl<T> VisionMerkleTreeScheme<T> {
+	pub fn new() -> Self {
+		Self::with_cap_height(0)
+	}
+
+	/// Constructs a scheme that commits to the `2^cap_height` digests at depth `cap_height`
+	/// instead of a single root.
+	pub fn with_cap_height(cap_height: usize) -> Self {
+		Self {
+			inner: BinaryMerkleTreeScheme::with_cap_height(
+				Vision32PermutationCompression,
+				cap_height,
+			),
+		}
+	}
+}
+
+impl<T> Default for VisionMerkleTreeScheme<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn vision_digests_to_bytes(digests: &[Vision32Digest]) -> Vec<Output<VisionHasherDigest>> {
+	digests.iter().map(|&digest| digest.into()).collect()
+}
+
+impl<F: TowerField> MerkleTreeScheme<F> for VisionMerkleTreeScheme<F> {
+	type Digest = Vision32Digest;
+
+	fn cap_height(&self) -> usize {
+		self.inner.cap_height()
+	}
+
+	fn cap_root(&self, cap: &[Self::Digest]) -> Result<Self::Digest, Error> {
+		self.inner
+			.cap_root(&vision_digests_to_bytes(cap))
+			.map(Into::into)
+	}
+
+	fn optimal_verify_layer(&self, n_queries: usize, tree_depth: usize) -> usize {
+		self.inner.optimal_verify_layer(n_queries, tree_depth)
+	}
+
+	fn proof_size(&self, len: usize, n_queries: usize, layer_depth: usize) -> Result<usize, Error> {
+		self.inner.proof_size(len, n_queries, layer_depth)
+	}
+
+	fn proof_size_exact(
+		&self,
+		indices: &[usize],
+		tree_depth: usize,
+		layer_depth: usize,
+	) -> Result<usize, Error> {
+		self.inner
+			.proof_size_exact(indices, tree_depth, layer_depth)
+	}
+
+	fn optimal_verify_layer_exact(&self, indices: &[usize], tree_depth: usize) -> usize {
+		self.inner.optimal_verify_layer_exact(indices, tree_depth)
+	}
+
+	fn verify_vector(
+		&self,
+		root: &Self::Digest,
+		data: &[F],
+		batch_size: usize,
+	) -> Result<(), Error> {
+		self.inner.verify_vector(&(*root).into(), data, batch_size)
+	}
+
+	fn verify_layer(
+		&self,
+		root: &Self::Digest,
+		layer_depth: usize,
+		layer_digests: &[Self::Digest],
+	) -> Result<(), Error> {
+		self.inner.verify_layer(
+			&(*root).into(),
+			layer_depth,
+			&vision_digests_to_bytes(layer_digests),
+		)
+	}
+
+	fn verify_opening<B: Buf>(
+		&self,
+		index: usize,
+		values: &[F],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		self.inner.verify_opening(
+			index,
+			values,
+			layer_depth,
+			tree_depth,
+			&vision_digests_to_bytes(layer_digests),
+			proof,
+		)
+	}
+
+	fn verify_opening_at_depth<B: Buf>(
+		&self,
+		index: usize,
+		values: &[F],
+		opening_depth: usize,
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		self.inner.verify_opening_at_depth(
+			index,
+			values,
+			opening_depth,
+			layer_depth,
+			tree_depth,
+			&vision_digests_to_bytes(layer_digests),
+			proof,
+		)
+	}
+
+	fn verify_opening_path(
+		&self,
+		index: usize,
+		values: &[F],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		path: &[Self::Digest],
+	) -> Result<(), Error> {
+		self.inner.verify_opening_path(
+			index,
+			values,
+			layer_depth,
+			tree_depth,
+			&vision_digests_to_bytes(layer_digests),
+			&vision_digests_to_bytes(path),
+		)
+	}
+
+	fn verify_openings<B: Buf>(
+		&self,
+		indices: &[usize],
+		values: &[&[F]],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		self.inner.verify_openings(
+			indices,
+			values,
+			layer_depth,
+			tree_depth,
+			&vision_digests_to_bytes(layer_digests),
+			proof,
+		)
+	}
+
+	fn verify_pair_opening<B: Buf>(
+		&self,
+		pair_index: usize,
+		values: [&[F]; 2],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		self.inner.verify_pair_opening(
+			pair_index,
+			values,
+			layer_depth,
+			tree_depth,
+			&vision_digests_to_bytes(layer_digests),
+			proof,
+		)
+	}
+}
+
+// Returns the number of sibling digests that a batch opening of the leaves at the given indices
+// sends for each layer of a binary tree, indexed by the depth of the layer above the siblings.
+fn batch_sibling_counts(indices: &[usize], tree_depth: usize) -> Result<Vec<usize>, Error> {
+	if indices.iter().any(|&index| index >= 1 << tree_depth) {
+		bail!(Error::IndexOutOfRange {
+			max: (1 << tree_depth) - 1
+		});
+	}
+
+	let mut nodes = sorted_distinct(indices);
+	let mut n_siblings = vec![0; tree_depth];
+	for count in n_siblings.iter_mut().rev() {
+		*count = missing_siblings(&nodes).count();
+		nodes.dedup_by_key(|node| *node >> 1);
+		for node in &mut nodes {
+			*node >>= 1;
+		}
+	}
+	Ok(n_siblings)
+}
 
-		(leaf_digest == layer_digests[index])
-			.then_some(())
-			.ok_or_else(|| VerificationError::InvalidProof.into())
+// Merkle-tree-like folding of a borrowed layer, returning the root.
+//
+// Only the parents of the layer are buffered, rather than a copy of the layer itself.
+fn fold_digests_vector<C, D, const N: usize>(compression: &C, digests: &[D]) -> Result<D, Error>
+where
+	C: PseudoCompressionFunction<D, N> + Sync,
+	D: Clone + Default + Send + Sync + Debug,
+{
+	if !digests.len().is_power_of_two() {
+		bail!(Error::PowerOfTwoLengthRequired);
 	}
+	if digests.len() == 1 {
+		return Ok(digests[0].clone());
+	}
+
+	let mut parents = digests
+		.chunks(N)
+		.map(|children| compress_padded(compression, children))
+		.collect::<Vec<_>>();
+	fold_digests_vector_inplace(compression, &mut parents)?;
+	Ok(parents.swap_remove(0))
 }
 
-// Merkle-tree-like folding
-fn fold_digests_vector_inplace<C, D>(compression: &C, digests: &mut [D]) -> Result<(), Error>
+// Merkle-tree-like folding, for any power-of-two arity `N`.
+//
+// When the number of digests is not a power of `N`, the last fold compresses fewer than `N`
+// digests, padded with default digests, matching the root of an [`super::NaryMerkleTree`].
+fn fold_digests_vector_inplace<C, D, const N: usize>(
+	compression: &C,
+	digests: &mut [D],
+) -> Result<(), Error>
 where
-	C: PseudoCompressionFunction<D, 2> + Sync,
+	C: PseudoCompressionFunction<D, N> + Sync,
 	D: Clone + Default + Send + Sync + Debug,
 {
 	if !digests.len().is_power_of_two() {
 		bail!(Error::PowerOfTwoLengthRequired);
 	}
 
-	let mut len = digests.len() / 2;
+	let mut len = digests.len();
 
-	while len != 0 {
+	while len > 1 {
+		let n_children = len.min(N);
+		len /= n_children;
 		for i in 0..len {
-			digests[i] = compression.compress(array::from_fn(|j| digests[2 * i + j].clone()));
+			digests[i] =
+				compress_padded(compression, &digests[i * n_children..(i + 1) * n_children]);
 		}
-		len /= 2;
 	}
 
 	Ok(())
 }
+
+/// A Merkle tree scheme with `N` children per inner node, for a power of two `N`.
+///
+/// Compared with [`BinaryMerkleTreeScheme`], the tree has `log2(N)` times fewer levels, so the
+/// prover computes `N - 1` times fewer compressions, each of `N` digests, and the verifier computes
+/// `log2(N)` times fewer per opening. In exchange, an authentication path has `N - 1` siblings on
+/// every `log2(N)`-th binary level. Depths are measured in binary levels, as for the binary scheme,
+/// so a tree over `2^tree_depth` leaves only has layers at the depths that differ from
+/// `tree_depth` by a multiple of `log2(N)`, and at depth 0; see [`super::NaryMerkleTree`].
+///
+/// The scheme does not support Merkle caps, so its commitments are single roots. It still hashes
+/// a power-of-two number of digests to a root with [`MerkleTreeScheme::cap_root`], as the upper
+/// levels of a tree with `N` children per node, which combines the roots of several trees.
+#[derive(Debug, Getters)]
+pub struct NaryMerkleTreeScheme<T, H, C, const N: usize> {
+	#[getset(get = "pub")]
+	compression: C,
+	// This makes it so that `NaryMerkleTreeScheme` remains Send + Sync
+	// See https://doc.rust-lang.org/nomicon/phantom-data.html#table-of-phantomdata-patterns
+	_phantom: PhantomData<fn() -> (T, H)>,
+}
+
+/// A Merkle tree scheme with four children per inner node.
+///
+/// An authentication path has three siblings on every other binary level, which is `3/2` digests
+/// per level instead of one, while the prover computes a third as many compressions as for
+/// [`BinaryMerkleTreeScheme`] and the verifier half as many per opening.
+pub type QuaternaryMerkleTreeScheme<T, H, C> = NaryMerkleTreeScheme<T, H, C, 4>;
+
+impl<T, H, C, const N: usize> NaryMerkleTreeScheme<T, H, C, N> {
+	const LOG_ARITY: usize = {
+		assert!(N >= 2 && N.is_power_of_two(), "the arity must be a power of two");
+		N.ilog2() as usize
+	};
+
+	pub fn new(compression: C) -> Self {
+		let _ = Self::LOG_ARITY;
+		Self {
+			compression,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<T, H, C, const N: usize> NaryMerkleTreeScheme<T, H, C, N>
+where
+	H: Digest,
+	C: PseudoCompressionFunction<Output<H>, N> + Sync,
+{
+	/// Verifies the authentication path of the node at the given index and depth against a layer.
+	///
+	/// The path holds the sibling digests from the node up to, but not including, the node at the
+	/// opening depth, which is determined by the length of the path. See
+	/// [`MerkleTreeScheme::verify_opening_path`].
+	fn verify_node_path(
+		&self,
+		node_index: usize,
+		mut digest: Output<H>,
+		node_depth: usize,
+		layer_depth: usize,
+		layer_digests: &[Output<H>],
+		mut path: &[Output<H>],
+	) -> Result<(), Error> {
+		if (1 << layer_depth) != layer_digests.len() {
+			bail!(VerificationError::IncorrectVectorLength);
+		}
+
+		if !is_nary_layer(Self::LOG_ARITY, node_depth, layer_depth) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+
+		let mut index = node_index;
+		let mut depth = node_depth;
+		while depth > layer_depth {
+			(digest, path) = self.compress_branch(index, depth, digest, path)?;
+			index >>= nary_parent_log_arity(Self::LOG_ARITY, depth);
+			depth -= nary_parent_log_arity(Self::LOG_ARITY, depth);
+		}
+
+		if digest != layer_digests[index] {
+			bail!(VerificationError::IncorrectPath {
+				index: node_index,
+				node_depth,
+				layer_index: index,
+				layer_depth,
+			});
+		}
+		if path.is_empty() {
+			return Ok(());
+		}
+
+		// The opening was proven to a shallower depth than the verified layer. Fold the path up to
+		// the opening depth and compare against the ancestor computed from the layer, which
+		// authenticates the siblings above the layer.
+		let layer_index = index;
+		while !path.is_empty() {
+			if depth == 0 {
+				bail!(VerificationError::IncorrectVectorLength);
+			}
+			(digest, path) = self.compress_branch(index, depth, digest, path)?;
+			index >>= nary_parent_log_arity(Self::LOG_ARITY, depth);
+			depth -= nary_parent_log_arity(Self::LOG_ARITY, depth);
+		}
+
+		let ancestor_height = layer_depth - depth;
+		let subtree_start = (layer_index >> ancestor_height) << ancestor_height;
+		let expected_ancestor = fold_digests_vector(
+			&self.compression,
+			&layer_digests[subtree_start..subtree_start + (1 << ancestor_height)],
+		)?;
+		if digest != expected_ancestor {
+			bail!(VerificationError::IncorrectPathAboveLayer {
+				index: node_index,
+				node_depth,
+				ancestor_depth: depth,
+			});
+		}
+		Ok(())
+	}
+
+	/// Compresses a node digest with its siblings taken from the front of the path, returning the
+	/// parent digest and the rest of the path.
+	fn compress_branch<'p>(
+		&self,
+		index: usize,
+		depth: usize,
+		digest: Output<H>,
+		path: &'p [Output<H>],
+	) -> Result<(Output<H>, &'p [Output<H>]), Error> {
+		let log_arity = nary_parent_log_arity(Self::LOG_ARITY, depth);
+		let n_siblings = (1 << log_arity) - 1;
+		if path.len() < n_siblings {
+			bail!(VerificationError::IncorrectVectorLength);
+		}
+		let (siblings, path) = path.split_at(n_siblings);
+
+		let position = index & n_siblings;
+		let mut siblings = siblings.iter();
+		let children = (0..=n_siblings)
+			.map(|i| {
+				if i == position {
+					digest.clone()
+				} else {
+					siblings
+						.next()
+						.expect("there is a sibling for every other position")
+						.clone()
+				}
+			})
+			.collect::<Vec<_>>();
+		Ok((compress_padded(&self.compression, &children), path))
+	}
+}
+
+impl<F, H, C, const N: usize> MerkleTreeScheme<F> for NaryMerkleTreeScheme<F, H, C, N>
+where
+	F: TowerField,
+	H: Digest + BlockSizeUser + Clone + Send + Sync,
+	C: PseudoCompressionFunction<Output<H>, N> + Sync,
+{
+	type Digest = Output<H>;
+
+	/// The digests are folded to the root as [`MerkleTreeScheme::verify_layer`] folds a layer, `N`
+	/// at a time, with a last fold of fewer than `N` digests when their number is not a power of
+	/// `N`.
+	fn cap_root(&self, cap: &[Self::Digest]) -> Result<Self::Digest, Error> {
+		fold_digests_vector(&self.compression, cap)
+	}
+
+	/// This layer allows minimizing the proof size.
+	///
+	/// The depth is rounded up to the nearest depth at which the tree has a layer.
+	fn optimal_verify_layer(&self, n_queries: usize, tree_depth: usize) -> usize {
+		let layer_depth = log2_ceil_usize(n_queries).min(tree_depth);
+		tree_depth - (tree_depth - layer_depth) / Self::LOG_ARITY * Self::LOG_ARITY
+	}
+
+	fn opening_path_len(&self, tree_depth: usize, opening_depth: usize) -> usize {
+		nary_path_len(Self::LOG_ARITY, tree_depth, opening_depth.min(tree_depth))
+	}
+
+	fn proof_size(&self, len: usize, n_queries: usize, layer_depth: usize) -> Result<usize, Error> {
+		if !len.is_power_of_two() {
+			bail!(Error::PowerOfTwoLengthRequired)
+		}
+
+		let log_len = log2_strict_usize(len);
+
+		if !is_nary_layer(Self::LOG_ARITY, log_len, layer_depth) {
+			bail!(Error::IncorrectLayerDepth)
+		}
+
+		let n_layer_digests = if layer_depth > 0 { 1 << layer_depth } else { 0 };
+
+		Ok((nary_path_len(Self::LOG_ARITY, log_len, layer_depth) * n_queries + n_layer_digests)
+			* <H as Digest>::output_size())
+	}
+
+	fn verify_vector(
+		&self,
+		root: &Self::Digest,
+		data: &[F],
+		batch_size: usize,
+	) -> Result<(), Error> {
+		if data.len() % batch_size != 0 {
+			bail!(Error::IncorrectBatchSize);
+		}
+
+		let mut digests = hash_serialize_batch::<F, H>(data, batch_size);
+
+		fold_digests_vector_inplace(&self.compression, &mut digests)?;
+		if digests[0] != *root {
+			bail!(VerificationError::IncorrectVectorRoot)
+		}
+		Ok(())
+	}
+
+	fn verify_layer(
+		&self,
+		root: &Self::Digest,
+		layer_depth: usize,
+		layer_digests: &[Self::Digest],
+	) -> Result<(), Error> {
+		if 1 << layer_depth != layer_digests.len() {
+			bail!(VerificationError::IncorrectVectorLength)
+		}
+
+		if fold_digests_vector(&self.compression, layer_digests)? != *root {
+			bail!(VerificationError::IncorrectLayerRoot { layer_depth })
+		}
+		Ok(())
+	}
+
+	fn verify_opening<B: Buf>(
+		&self,
+		index: usize,
+		values: &[F],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		if !is_nary_layer(Self::LOG_ARITY, tree_depth, layer_depth) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let path = proof.read_vec(nary_path_len(Self::LOG_ARITY, tree_depth, layer_depth))?;
+		self.verify_opening_path(index, values, layer_depth, tree_depth, layer_digests, &path)
+	}
+
+	fn verify_opening_at_depth<B: Buf>(
+		&self,
+		index: usize,
+		values: &[F],
+		opening_depth: usize,
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		if opening_depth > layer_depth || !is_nary_layer(Self::LOG_ARITY, tree_depth, opening_depth)
+		{
+			bail!(Error::IncorrectLayerDepth);
+		}
+		let path = proof.read_vec(nary_path_len(Self::LOG_ARITY, tree_depth, opening_depth))?;
+		self.verify_opening_path(index, values, layer_depth, tree_depth, layer_digests, &path)
+	}
+
+	fn verify_opening_path(
+		&self,
+		index: usize,
+		values: &[F],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		path: &[Self::Digest],
+	) -> Result<(), Error> {
+		if index >= (1 << tree_depth) {
+			bail!(Error::IndexOutOfRange {
+				max: (1 << tree_depth) - 1
+			});
+		}
+
+		let leaf_digest = hash_serialize::<F, H>(values)
+			.expect("values are of TowerField type which we expect to be serializable");
+		self.verify_node_path(index, leaf_digest, tree_depth, layer_depth, layer_digests, path)
+	}
+
+	/// Verifies an opening proof for a pair of sibling entries.
+	///
+	/// The pair shares its parent with the other entries of its group, whose digests start the
+	/// authentication path.
+	fn verify_pair_opening<B: Buf>(
+		&self,
+		pair_index: usize,
+		values: [&[F]; 2],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		if layer_depth >= tree_depth || !is_nary_layer(Self::LOG_ARITY, tree_depth, layer_depth) {
+			bail!(Error::IncorrectLayerDepth);
+		}
+		if pair_index >= (1 << (tree_depth - 1)) {
+			bail!(Error::IndexOutOfRange {
+				max: (1 << (tree_depth - 1)) - 1
+			});
+		}
+
+		let path = proof.read_vec(nary_path_len(Self::LOG_ARITY, tree_depth, layer_depth) - 1)?;
+		let [left, right] = values.map(|values| {
+			hash_serialize::<F, H>(values)
+				.expect("values are of TowerField type which we expect to be serializable")
+		});
+
+		let log_arity = nary_parent_log_arity(Self::LOG_ARITY, tree_depth);
+		let (others, path) = path.split_at((1 << log_arity) - 2);
+		let index = pair_index << 1;
+		let position = index & ((1 << log_arity) - 1);
+		let mut children = others.to_vec();
+		children.splice(position..position, [left, right]);
+		let parent_digest = compress_padded(&self.compression, &children);
+		self.verify_node_path(
+			index >> log_arity,
+			parent_digest,
+			tree_depth - log_arity,
+			layer_depth,
+			layer_digests,
+			path,
+		)
+	}
+}
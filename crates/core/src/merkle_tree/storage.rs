@@ -0,0 +1,193 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{
+	fmt::{self, Debug},
+	marker::PhantomData,
+	mem::{MaybeUninit, size_of},
+	ops::Deref,
+	path::PathBuf,
+	slice,
+};
+
+use digest::generic_array::{ArrayLength, GenericArray};
+use memmap2::MmapMut;
+
+use super::errors::Error;
+
+/// Where a prover stores the nodes of the Merkle trees it commits to.
+///
+/// The same configuration selects where the committed codewords are stored, see
+/// [`crate::protocols::fri::CodewordStorage`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StorageConfig {
+	/// The nodes are held in memory.
+	#[default]
+	Memory,
+	/// The nodes are held in memory-mapped temporary files in the given directory.
+	///
+	/// The operating system writes the pages of the nodes back to the file under memory pressure
+	/// and reads them back when openings are proven, so trees larger than the available memory
+	/// can be committed. Only the Merkle cap, which is part of the commitment, is kept in memory
+	/// by the prover.
+	Mapped(PathBuf),
+}
+
+mod sealed {
+	use digest::generic_array::{ArrayLength, GenericArray};
+
+	pub trait Sealed {}
+
+	impl<N: ArrayLength<u8>> Sealed for GenericArray<u8, N> {}
+}
+
+/// A node type that may be stored in a memory-mapped file.
+///
+/// The nodes are read back by reinterpreting the bytes of the mapping, so this is only sound for
+/// plain byte-like types. The trait is sealed, and implemented only for byte arrays.
+///
+/// # Safety
+///
+/// Every bit pattern of the size of the type must be a valid value, the type must have no padding
+/// and must not need to be dropped, and its alignment must be at most the page size.
+pub unsafe trait MappableNode: sealed::Sealed {}
+
+// SAFETY: byte arrays are valid for any bit pattern, have no padding and no drop glue, and have an
+// alignment of one
+unsafe impl<N: ArrayLength<u8>> MappableNode for GenericArray<u8, N> {}
+
+/// The nodes of a Merkle tree, held either in memory or in a memory-mapped file.
+pub enum NodeStorage<D> {
+	Memory(Vec<D>),
+	Mapped(MappedNodes<D>),
+}
+
+impl<D> NodeStorage<D> {
+	/// Allocates uninitialized storage for `len` nodes in memory.
+	pub fn memory(len: usize) -> Self {
+		Self::Memory(Vec::with_capacity(len))
+	}
+
+	/// Allocates uninitialized storage for `len` nodes as configured.
+	pub fn allocate(config: &StorageConfig, len: usize) -> Result<Self, Error>
+	where
+		D: MappableNode,
+	{
+		match config {
+			StorageConfig::Memory => Ok(Self::memory(len)),
+			StorageConfig::Mapped(dir) => Ok(Self::Mapped(MappedNodes::allocate(dir, len)?)),
+		}
+	}
+
+	/// Returns the uninitialized storage for `len` nodes.
+	///
+	/// The storage must have been allocated for at least `len` nodes.
+	pub(super) fn uninit_mut(&mut self, len: usize) -> &mut [MaybeUninit<D>] {
+		match self {
+			Self::Memory(nodes) => &mut nodes.spare_capacity_mut()[..len],
+			Self::Mapped(nodes) => &mut nodes.uninit_mut()[..len],
+		}
+	}
+
+	/// Marks the first `len` nodes as initialized.
+	///
+	/// # Safety
+	///
+	/// The first `len` nodes returned by [`Self::uninit_mut`] must have been initialized.
+	pub(super) unsafe fn assume_init(&mut self, len: usize) {
+		match self {
+			Self::Memory(nodes) => unsafe {
+				// SAFETY: `memory` reserved capacity for at least `len` nodes, and the caller
+				// guarantees that the first `len` of them are initialized
+				nodes.set_len(len)
+			},
+			Self::Mapped(nodes) => nodes.len = len,
+		}
+	}
+}
+
+impl<D> Deref for NodeStorage<D> {
+	type Target = [D];
+
+	fn deref(&self) -> &[D] {
+		match self {
+			Self::Memory(nodes) => nodes,
+			Self::Mapped(nodes) => nodes,
+		}
+	}
+}
+
+impl<D> From<Vec<D>> for NodeStorage<D> {
+	fn from(nodes: Vec<D>) -> Self {
+		Self::Memory(nodes)
+	}
+}
+
+/// Clones the nodes into memory.
+impl<D: Clone> Clone for NodeStorage<D> {
+	fn clone(&self) -> Self {
+		Self::Memory(self.to_vec())
+	}
+}
+
+impl<D: PartialEq> PartialEq for NodeStorage<D> {
+	fn eq(&self, other: &Self) -> bool {
+		**self == **other
+	}
+}
+
+impl<D: Debug> Debug for NodeStorage<D> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Memory(nodes) => f.debug_tuple("Memory").field(nodes).finish(),
+			Self::Mapped(nodes) => f.debug_tuple("Mapped").field(&nodes.len).finish(),
+		}
+	}
+}
+
+/// Merkle tree nodes in an anonymous memory-mapped temporary file.
+pub struct MappedNodes<D> {
+	mmap: MmapMut,
+	/// The number of initialized nodes
+	len: usize,
+	capacity: usize,
+	_phantom: PhantomData<D>,
+}
+
+impl<D: MappableNode> MappedNodes<D> {
+	fn allocate(dir: &PathBuf, capacity: usize) -> Result<Self, Error> {
+		let file = tempfile::tempfile_in(dir)?;
+		// Mappings of zero bytes are not supported on all platforms.
+		file.set_len((capacity * size_of::<D>()).max(1) as u64)?;
+		let mmap = unsafe {
+			// SAFETY: the file is unlinked on creation, so no other process modifies it
+			MmapMut::map_mut(&file)?
+		};
+		Ok(Self {
+			mmap,
+			len: 0,
+			capacity,
+			_phantom: PhantomData,
+		})
+	}
+}
+
+impl<D> MappedNodes<D> {
+	fn uninit_mut(&mut self) -> &mut [MaybeUninit<D>] {
+		unsafe {
+			// SAFETY: the mapping holds `capacity` nodes and is page aligned, which suffices for
+			// a MappableNode
+			slice::from_raw_parts_mut(self.mmap.as_mut_ptr().cast(), self.capacity)
+		}
+	}
+}
+
+impl<D> Deref for MappedNodes<D> {
+	type Target = [D];
+
+	fn deref(&self) -> &[D] {
+		unsafe {
+			// SAFETY: the first `len` nodes were initialized, see NodeStorage::assume_init
+			slice::from_raw_parts(self.mmap.as_ptr().cast(), self.len)
+		}
+	}
+}
@@ -3,12 +3,36 @@
 use core::slice;
 use std::iter::repeat_with;
 
-use binius_field::{BinaryField16b, Field};
-use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
-use rand::{SeedableRng, rngs::StdRng};
+use binius_compute::cpu::CpuLayer;
+use binius_fast_compute::{layer::FastCpuLayer, memory::PackedMemorySlice};
+use binius_field::{
+	BinaryField16b, BinaryField128b, Field, PackedField, arch::OptimalUnderlier,
+	as_packed_field::PackedType, tower::CanonicalTowerFamily,
+};
+use binius_hash::{
+	PseudoCompressionFunction,
+	blake3::Blake3Compression,
+	groestl::{Groestl256, Groestl256ByteCompression, Groestl256Parallel},
+	hash_serialize_with_prefix,
+	sha2::Sha256Compression,
+};
+use binius_maybe_rayon::prelude::*;
+use binius_utils::checked_arithmetics::log2_ceil_usize;
+use bytes::Buf;
+use digest::{Digest, Output};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sha2::Sha256;
 
-use super::{BinaryMerkleTreeProver, MerkleTreeProver, MerkleTreeScheme};
-use crate::{fiat_shamir::HasherChallenger, transcript::ProverTranscript};
+use super::{
+	BinaryMerkleTreeProver, Blake3MerkleTreeProver, DomainSeparation, Error, LeafPadding,
+	MerkleTreeProver, MerkleTreeScheme, NaryMerkleTreeProver, NodeStorage,
+	QuaternaryMerkleTreeProver, StorageConfig, VerificationError, VisionMerkleTreeProver, build,
+	build_chunked, build_from_subtree_roots, is_nary_layer,
+};
+use crate::{
+	fiat_shamir::HasherChallenger,
+	transcript::{ProverTranscript, TranscriptReader, VerifierTranscript},
+};
 
 #[test]
 fn test_binary_merkle_vcs_commit_prove_open_correctly() {
@@ -44,6 +68,517 @@ fn test_binary_merkle_vcs_commit_prove_open_correctly() {
 	}
 }
 
+#[test]
+fn test_blake3_merkle_vcs_commit_prove_open_correctly() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = Blake3MerkleTreeProver::new(Blake3Compression::default());
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 2).unwrap();
+	assert_eq!(commitment.root, tree.root());
+
+	for index in 0..16 {
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Sha256>>::new();
+		mr_prover
+			.prove_opening(&tree, 0, index, &mut proof_writer.message())
+			.unwrap();
+
+		let mut proof_reader = proof_writer.into_verifier();
+		mr_prover
+			.scheme()
+			.verify_opening(
+				index,
+				&data[2 * index..2 * index + 2],
+				0,
+				4,
+				&[commitment.root],
+				&mut proof_reader.message(),
+			)
+			.unwrap();
+	}
+}
+
+#[test]
+fn test_vision_merkle_vcs_commit_prove_open_correctly() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = VisionMerkleTreeProver::with_cap_height(1);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 2).unwrap();
+	assert_eq!(commitment.root, tree.root());
+	assert_eq!(mr_prover.cap(&tree, commitment.depth).unwrap().len(), 2);
+	mr_prover
+		.scheme()
+		.verify_vector(&commitment.root, &data, 2)
+		.unwrap();
+
+	let layer = mr_prover.layer(&tree, 2).unwrap();
+	mr_prover
+		.scheme()
+		.verify_layer(&commitment.root, 2, layer)
+		.unwrap();
+
+	for index in 0..16 {
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Sha256>>::new();
+		mr_prover
+			.prove_opening(&tree, 2, index, &mut proof_writer.message())
+			.unwrap();
+		mr_prover
+			.prove_pair_opening(&tree, 2, index / 2, &mut proof_writer.message())
+			.unwrap();
+
+		let mut proof_reader = proof_writer.into_verifier();
+		mr_prover
+			.scheme()
+			.verify_opening(
+				index,
+				&data[2 * index..2 * index + 2],
+				2,
+				4,
+				layer,
+				&mut proof_reader.message(),
+			)
+			.unwrap();
+		let pair = index / 2;
+		mr_prover
+			.scheme()
+			.verify_pair_opening(
+				pair,
+				[
+					&data[4 * pair..4 * pair + 2],
+					&data[4 * pair + 2..4 * pair + 4],
+				],
+				2,
+				4,
+				layer,
+				&mut proof_reader.message(),
+			)
+			.unwrap();
+	}
+}
+
+#[test]
+fn test_build_from_subtree_roots_matches_full_tree() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+
+	for separation in [
+		DomainSeparation::None,
+		DomainSeparation::Tagged,
+		DomainSeparation::TaggedWithDepth,
+	] {
+		let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+			.with_domain_separation(separation);
+		let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+
+		let subtrees = data
+			.chunks(8)
+			.map(|chunk| mr_prover.commit_subtree(chunk, 1, 2).unwrap())
+			.collect::<Vec<_>>();
+		let subtree_roots = subtrees
+			.iter()
+			.map(|subtree| subtree.root())
+			.collect::<Vec<_>>();
+		let top = build_from_subtree_roots::<Groestl256, _>(
+			&Groestl256ByteCompression,
+			separation,
+			&subtree_roots,
+		)
+		.unwrap();
+		assert_eq!(top.root(), commitment.root);
+
+		for index in 0..data.len() {
+			let mut branch = subtrees[index / 8].branch(index % 8, 0).unwrap();
+			branch.extend(top.branch(index / 8, 0).unwrap());
+			assert_eq!(branch, tree.branch(index, 0).unwrap());
+		}
+
+		assert!(matches!(
+			build_from_subtree_roots::<Groestl256, _>(
+				&Groestl256ByteCompression,
+				separation,
+				&subtree_roots[..3]
+			),
+			Err(Error::PowerOfTwoLengthRequired)
+		));
+	}
+}
+
+#[test]
+fn test_chunked_build_matches_unchunked_build() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(64)
+		.collect::<Vec<BinaryField16b>>();
+	let tree = build::<_, Groestl256, _>(&Groestl256ByteCompression, &data, 2).unwrap();
+
+	for log_chunk_len in [0, 1, 3, 5, 8] {
+		for max_threads in [None, Some(1), Some(3)] {
+			let chunked_tree = build_chunked::<_, Groestl256, _>(
+				&Groestl256ByteCompression,
+				&data,
+				2,
+				log_chunk_len,
+				max_threads,
+				Vec::new(),
+			)
+			.unwrap();
+			assert_eq!(chunked_tree.inner_nodes, tree.inner_nodes);
+		}
+	}
+
+	assert!(matches!(
+		build_chunked::<_, Groestl256, _>(
+			&Groestl256ByteCompression,
+			&data[..48],
+			2,
+			2,
+			None,
+			Vec::new()
+		),
+		Err(Error::PowerOfTwoLengthRequired)
+	));
+}
+
+#[test]
+fn test_binary_merkle_vcs_commit_with_leaf_chunks_and_max_threads() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(256)
+		.collect::<Vec<BinaryField16b>>();
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let (commitment, _) = mr_prover.commit(&data, 4).unwrap();
+
+	let chunked_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_log_leaf_chunk_len(2)
+		.with_max_threads(2);
+	assert_eq!(chunked_prover.log_leaf_chunk_len(), 2);
+	assert_eq!(chunked_prover.max_threads(), Some(2));
+	let (chunked_commitment, _) = chunked_prover.commit(&data, 4).unwrap();
+	assert_eq!(chunked_commitment.root, commitment.root);
+
+	let iterated_commitment = chunked_prover
+		.commit_iterated(data.par_chunks(4).map(|leaf| leaf.iter().copied()), 6)
+		.unwrap()
+		.0;
+	assert_eq!(iterated_commitment.root, commitment.root);
+}
+
+#[test]
+fn test_binary_merkle_vcs_stream_commit_matches_commit() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(3 << 6)
+		.collect::<Vec<BinaryField16b>>();
+
+	for log_leaf_chunk_len in [0, 2, 10] {
+		let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+			.with_log_leaf_chunk_len(log_leaf_chunk_len);
+		let (commitment, tree) = mr_prover.commit(&data, 3).unwrap();
+
+		// Pieces that split leaves and chunks at arbitrary points.
+		for piece_len in [1, 5, 12, 64, 3 << 6] {
+			let mut committer = mr_prover.stream_commit(6, 3).unwrap();
+			for piece in data.chunks(piece_len) {
+				committer.push_leaves(piece).unwrap();
+			}
+			let (streamed_commitment, streamed_tree) = committer.finalize().unwrap();
+			assert_eq!(streamed_commitment, commitment);
+			assert_eq!(streamed_tree.inner_nodes, tree.inner_nodes);
+		}
+	}
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_log_leaf_chunk_len(2);
+
+	// The tree is incomplete until all leaves are pushed.
+	let mut committer = mr_prover.stream_commit(6, 3).unwrap();
+	committer.push_leaves(&data[..100]).unwrap();
+	assert!(matches!(committer.finalize(), Err(Error::IncorrectVectorLen { .. })));
+
+	// No more leaves than the tree holds can be pushed.
+	let mut committer = mr_prover.stream_commit(6, 3).unwrap();
+	committer.push_leaves(&data).unwrap();
+	assert!(matches!(committer.push_leaves(&data[..1]), Err(Error::IncorrectVectorLen { .. })));
+
+	// Streamed commitments to salted leaves open like any other.
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_log_leaf_chunk_len(2)
+		.with_salted_leaves(StdRng::seed_from_u64(1));
+	let mut committer = mr_prover.stream_commit(6, 3).unwrap();
+	for piece in data.chunks(7) {
+		committer.push_leaves(piece).unwrap();
+	}
+	let (commitment, tree) = committer.finalize().unwrap();
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_opening(&tree, 0, 5, &mut proof_writer.message())
+		.unwrap();
+	let mut proof_reader = proof_writer.into_verifier();
+	mr_prover
+		.scheme()
+		.verify_opening(5, &data[15..18], 0, 6, &[commitment.root], &mut proof_reader.message())
+		.unwrap();
+}
+
+#[test]
+fn test_binary_merkle_vcs_proof_size_exact() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(1 << 8)
+		.collect::<Vec<BinaryField16b>>();
+
+	let plain_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let salted_prover =
+		BinaryMerkleTreeProver::<_, Groestl256, _>::with_cap_height(Groestl256ByteCompression, 2)
+			.with_salted_leaves(StdRng::seed_from_u64(1));
+	for mr_prover in [plain_prover, salted_prover] {
+		let scheme = mr_prover.scheme();
+		let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+		let cap_height = scheme.cap_height();
+
+		for n_queries in [1, 5, 40, 300] {
+			let indices = repeat_with(|| rng.gen_range(0..1 << 8))
+				.take(n_queries)
+				.collect::<Vec<usize>>();
+
+			let sizes = (cap_height..=8)
+				.map(|layer_depth| {
+					let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+					mr_prover
+						.prove_openings(&tree, layer_depth, &indices, &mut proof_writer.message())
+						.unwrap();
+					let n_layer_digests = if layer_depth > cap_height {
+						1 << layer_depth
+					} else {
+						0
+					};
+					let size = proof_writer.finalize().len() + n_layer_digests * 32;
+					assert_eq!(scheme.proof_size_exact(&indices, 8, layer_depth).unwrap(), size);
+					size
+				})
+				.collect::<Vec<_>>();
+
+			let optimal_layer = scheme.optimal_verify_layer_exact(&indices, 8);
+			assert_eq!(sizes[optimal_layer - cap_height], *sizes.iter().min().unwrap());
+			assert!(
+				sizes[..optimal_layer - cap_height]
+					.iter()
+					.all(|&size| size > sizes[optimal_layer - cap_height])
+			);
+		}
+
+		assert!(scheme.proof_size_exact(&[1 << 8], 8, 4).is_err());
+		if cap_height > 0 {
+			assert!(scheme.proof_size_exact(&[0], 8, 0).is_err());
+		}
+	}
+}
+
+#[test]
+fn test_binary_merkle_vcs_batch_openings_share_paths() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(1 << 8)
+		.collect::<Vec<BinaryField16b>>();
+	let indices = repeat_with(|| rng.gen_range(0..1 << 8))
+		.take(40)
+		.chain([7, 7, 6])
+		.collect::<Vec<usize>>();
+	let values = indices
+		.iter()
+		.map(|&index| slice::from_ref(&data[index]))
+		.collect::<Vec<_>>();
+
+	let plain_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let salted_prover =
+		BinaryMerkleTreeProver::<_, Groestl256, _>::with_cap_height(Groestl256ByteCompression, 2)
+			.with_salted_leaves(StdRng::seed_from_u64(1));
+	for mr_prover in [plain_prover, salted_prover] {
+		let scheme = mr_prover.scheme();
+		let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+		for layer_depth in 2..=8 {
+			let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_openings(&tree, layer_depth, &indices, &mut proof_writer.message())
+				.unwrap();
+			let proof = proof_writer.finalize();
+
+			// The shared digests are sent once, so the batch is smaller than the single openings.
+			let mut single_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			for &index in &indices {
+				mr_prover
+					.prove_opening(&tree, layer_depth, index, &mut single_writer.message())
+					.unwrap();
+			}
+			let single_proof_len = single_writer.finalize().len();
+			if layer_depth < 8 {
+				assert!(proof.len() < single_proof_len);
+			} else {
+				assert!(proof.len() <= single_proof_len);
+			}
+
+			let mut proof_reader =
+				VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+			scheme
+				.verify_openings(
+					&indices,
+					&values,
+					layer_depth,
+					8,
+					layer,
+					&mut proof_reader.message(),
+				)
+				.unwrap();
+			proof_reader.finalize().unwrap();
+
+			// A wrong value fails, and so does a repeated index opened to different values.
+			let mut wrong_values = values.clone();
+			wrong_values[3] = slice::from_ref(&data[indices[3] ^ 1]);
+			let mut proof_reader =
+				VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+			assert!(
+				scheme
+					.verify_openings(
+						&indices,
+						&wrong_values,
+						layer_depth,
+						8,
+						layer,
+						&mut proof_reader.message(),
+					)
+					.is_err()
+			);
+			let mut inconsistent_values = values.clone();
+			inconsistent_values[41] = slice::from_ref(&data[6]);
+			let mut proof_reader = VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof);
+			assert!(matches!(
+				scheme.verify_openings(
+					&indices,
+					&inconsistent_values,
+					layer_depth,
+					8,
+					layer,
+					&mut proof_reader.message(),
+				),
+				Err(Error::Verification(VerificationError::InconsistentOpenings { index: 7 }))
+			));
+		}
+	}
+
+	// Schemes without shared paths fall back to the openings one by one.
+	let mr_prover = QuaternaryMerkleTreeProver::<_, Sha256, _>::new(Sha256Compression::default());
+	let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_openings(&tree, 0, &indices, &mut proof_writer.message())
+		.unwrap();
+	let mut proof_reader = proof_writer.into_verifier();
+	mr_prover
+		.scheme()
+		.verify_openings(&indices, &values, 0, 8, &[commitment.root], &mut proof_reader.message())
+		.unwrap();
+	proof_reader.finalize().unwrap();
+}
+
+#[test]
+fn test_binary_merkle_vcs_commit_on_device_matches_commit() {
+	type Packed = PackedType<OptimalUnderlier, BinaryField128b>;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	for batch_size in [1, 3, 8] {
+		let data = repeat_with(|| Field::random(&mut rng))
+			.take(batch_size << 5)
+			.collect::<Vec<BinaryField128b>>();
+		let (commitment, tree) = mr_prover.commit(&data, batch_size).unwrap();
+
+		let (cpu_commitment, cpu_tree) = mr_prover
+			.commit_on_device(&CpuLayer::<BinaryField128b>::default(), &data, batch_size)
+			.unwrap();
+		assert_eq!(cpu_commitment, commitment);
+		assert_eq!(cpu_tree.inner_nodes, tree.inner_nodes);
+
+		let packed_data = data
+			.chunks(Packed::WIDTH)
+			.map(|chunk| Packed::from_scalars(chunk.iter().copied()))
+			.collect::<Vec<_>>();
+		let (fast_commitment, fast_tree) = mr_prover
+			.commit_on_device(
+				&FastCpuLayer::<CanonicalTowerFamily, Packed>::default(),
+				PackedMemorySlice::new_slice(&packed_data),
+				batch_size,
+			)
+			.unwrap();
+		assert_eq!(fast_commitment, commitment);
+		assert_eq!(fast_tree.inner_nodes, tree.inner_nodes);
+	}
+
+	let data = vec![BinaryField128b::ZERO; 48];
+	assert!(matches!(
+		mr_prover.commit_on_device(&CpuLayer::<BinaryField128b>::default(), &data, 1),
+		Err(Error::PowerOfTwoLengthRequired)
+	));
+}
+
+#[test]
+fn test_binary_merkle_vcs_mapped_storage_matches_memory() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let mapped_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_storage(StorageConfig::Mapped(std::env::temp_dir()));
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(64)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 2).unwrap();
+	let (mapped_commitment, mapped_tree) = mapped_prover.commit(&data, 2).unwrap();
+	assert!(matches!(mapped_tree.inner_nodes, NodeStorage::Mapped(_)));
+	assert_eq!(mapped_commitment, commitment);
+	assert_eq!(mapped_tree.inner_nodes, tree.inner_nodes);
+
+	let mut committer = mapped_prover.stream_commit(5, 2).unwrap();
+	for chunk in data.chunks(6) {
+		committer.push_leaves(chunk).unwrap();
+	}
+	let (streamed_commitment, streamed_tree) = committer.finalize().unwrap();
+	assert_eq!(streamed_commitment, commitment);
+	assert_eq!(streamed_tree.inner_nodes, tree.inner_nodes);
+
+	for (i, values) in data.chunks(2).enumerate() {
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		mapped_prover
+			.prove_opening(&mapped_tree, 0, i, &mut proof_writer.message())
+			.unwrap();
+
+		let mut proof_reader = proof_writer.into_verifier();
+		mapped_prover
+			.scheme()
+			.verify_opening(i, values, 0, 5, &[commitment.root], &mut proof_reader.message())
+			.unwrap();
+	}
+}
+
 #[test]
 fn test_binary_merkle_vcs_commit_layer_prove_open_correctly() {
 	let mut rng = StdRng::seed_from_u64(0);
@@ -100,3 +635,1310 @@ fn test_binary_merkle_vcs_verify_vector() {
 		.verify_vector(&commitment.root, &data, 1)
 		.unwrap();
 }
+
+#[test]
+fn test_binary_merkle_vcs_verify_layer_rejects_tampered_layer() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	for layer_depth in 0..5 {
+		let mut layer = mr_prover.layer(&tree, layer_depth).unwrap().to_vec();
+		*layer.last_mut().unwrap() = Default::default();
+		assert!(
+			mr_prover
+				.scheme()
+				.verify_layer(&commitment.root, layer_depth, &layer)
+				.is_err()
+		);
+	}
+}
+
+#[test]
+fn test_binary_merkle_vcs_verification_errors_locate_failure() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+	let scheme = mr_prover.scheme();
+
+	let layer_depth = 2;
+	let mut layer = mr_prover.layer(&tree, layer_depth).unwrap().to_vec();
+	let index = 13;
+	let mut tampered_path = tree.branch(index, layer_depth).unwrap();
+	tampered_path[1] = Default::default();
+	assert!(matches!(
+		scheme.verify_opening_path(
+			index,
+			&data[index..index + 1],
+			layer_depth,
+			5,
+			&layer,
+			&tampered_path
+		),
+		Err(Error::Verification(VerificationError::IncorrectPath {
+			index: 13,
+			node_depth: 5,
+			layer_index: 1,
+			layer_depth: 2,
+		}))
+	));
+
+	// An opening proven to the root is checked against the ancestor computed from the layer.
+	let mut tampered_path = tree.branch(index, 0).unwrap();
+	tampered_path[4] = Default::default();
+	assert!(matches!(
+		scheme.verify_opening_path(
+			index,
+			&data[index..index + 1],
+			layer_depth,
+			5,
+			&layer,
+			&tampered_path
+		),
+		Err(Error::Verification(VerificationError::IncorrectPathAboveLayer {
+			index: 13,
+			node_depth: 5,
+			ancestor_depth: 0,
+		}))
+	));
+
+	layer[0] = Default::default();
+	assert!(matches!(
+		scheme.verify_layer(&commitment.root, layer_depth, &layer),
+		Err(Error::Verification(VerificationError::IncorrectLayerRoot { layer_depth: 2 }))
+	));
+}
+
+#[test]
+fn test_binary_merkle_vcs_verify_opening_path() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	let layer_depth = 2;
+	let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+	for (i, value) in data.iter().enumerate() {
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		mr_prover
+			.prove_opening(&tree, layer_depth, i, &mut proof_writer.message())
+			.unwrap();
+
+		let mut proof_reader = proof_writer.into_verifier();
+		let path = proof_reader.message().read_vec(5 - layer_depth).unwrap();
+		let scheme = mr_prover.scheme();
+		scheme
+			.verify_opening_path(i, slice::from_ref(value), layer_depth, 5, layer, &path)
+			.unwrap();
+		assert!(
+			scheme
+				.verify_opening_path(i, slice::from_ref(value), layer_depth, 5, layer, &path[1..])
+				.is_err()
+		);
+		assert!(
+			scheme
+				.verify_opening_path(i ^ 1, slice::from_ref(value), layer_depth, 5, layer, &path)
+				.is_err()
+		);
+	}
+}
+
+/// A scheme implementing only the required methods of [`MerkleTreeScheme`], by forwarding them to
+/// the wrapped scheme, to exercise the default implementations of the others.
+struct RequiredMethodsScheme<'a, S>(&'a S);
+
+impl<T, S: MerkleTreeScheme<T>> MerkleTreeScheme<T> for RequiredMethodsScheme<'_, S> {
+	type Digest = S::Digest;
+
+	fn optimal_verify_layer(&self, n_queries: usize, tree_depth: usize) -> usize {
+		self.0.optimal_verify_layer(n_queries, tree_depth)
+	}
+
+	fn proof_size(&self, len: usize, n_queries: usize, layer_depth: usize) -> Result<usize, Error> {
+		self.0.proof_size(len, n_queries, layer_depth)
+	}
+
+	fn verify_vector(
+		&self,
+		root: &Self::Digest,
+		data: &[T],
+		batch_size: usize,
+	) -> Result<(), Error> {
+		self.0.verify_vector(root, data, batch_size)
+	}
+
+	fn verify_layer(
+		&self,
+		root: &Self::Digest,
+		layer_depth: usize,
+		layer_digests: &[Self::Digest],
+	) -> Result<(), Error> {
+		self.0.verify_layer(root, layer_depth, layer_digests)
+	}
+
+	fn verify_opening<B: Buf>(
+		&self,
+		index: usize,
+		values: &[T],
+		layer_depth: usize,
+		tree_depth: usize,
+		layer_digests: &[Self::Digest],
+		proof: &mut TranscriptReader<B>,
+	) -> Result<(), Error> {
+		self.0
+			.verify_opening(index, values, layer_depth, tree_depth, layer_digests, proof)
+	}
+}
+
+#[test]
+fn test_default_verify_opening_path() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let scheme = RequiredMethodsScheme(mr_prover.scheme());
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	let layer_depth = 2;
+	let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+	for (i, value) in data.iter().enumerate() {
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		mr_prover
+			.prove_opening(&tree, layer_depth, i, &mut proof_writer.message())
+			.unwrap();
+
+		let mut proof_reader = proof_writer.into_verifier();
+		let path = proof_reader.message().read_vec(5 - layer_depth).unwrap();
+		scheme
+			.verify_opening_path(i, slice::from_ref(value), layer_depth, 5, layer, &path)
+			.unwrap();
+		assert!(matches!(
+			scheme.verify_opening_path(
+				i,
+				slice::from_ref(value),
+				layer_depth,
+				5,
+				layer,
+				&path[1..]
+			),
+			Err(Error::Verification(VerificationError::IncorrectProofShape))
+		));
+		assert!(
+			scheme
+				.verify_opening_path(i ^ 1, slice::from_ref(value), layer_depth, 5, layer, &path)
+				.is_err()
+		);
+	}
+}
+
+#[test]
+fn test_default_verify_opening_at_depth() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let scheme = RequiredMethodsScheme(mr_prover.scheme());
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	let layer_depth = 2;
+	let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_opening(&tree, layer_depth, 5, &mut proof_writer.message())
+		.unwrap();
+	mr_prover
+		.prove_opening(&tree, layer_depth - 1, 5, &mut proof_writer.message())
+		.unwrap();
+
+	let mut proof_reader = proof_writer.into_verifier();
+	scheme
+		.verify_opening_at_depth(
+			5,
+			slice::from_ref(&data[5]),
+			layer_depth,
+			layer_depth,
+			5,
+			layer,
+			&mut proof_reader.message(),
+		)
+		.unwrap();
+	// Openings proven above the verified layer are not supported by the default implementation.
+	assert!(matches!(
+		scheme.verify_opening_at_depth(
+			5,
+			slice::from_ref(&data[5]),
+			layer_depth - 1,
+			layer_depth,
+			5,
+			layer,
+			&mut proof_reader.message(),
+		),
+		Err(Error::IncorrectLayerDepth)
+	));
+}
+
+#[test]
+fn test_default_pair_opening() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let scheme = RequiredMethodsScheme(mr_prover.scheme());
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	let layer_depth = 2;
+	let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+	for pair_index in 0..16 {
+		// The pair opening of the default implementation is the two openings one by one.
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		for index in [2 * pair_index, 2 * pair_index + 1] {
+			mr_prover
+				.prove_opening(&tree, layer_depth, index, &mut proof_writer.message())
+				.unwrap();
+		}
+
+		let mut proof_reader = proof_writer.into_verifier();
+		scheme
+			.verify_pair_opening(
+				pair_index,
+				[
+					slice::from_ref(&data[2 * pair_index]),
+					slice::from_ref(&data[2 * pair_index + 1]),
+				],
+				layer_depth,
+				5,
+				layer,
+				&mut proof_reader.message(),
+			)
+			.unwrap();
+		proof_reader.finalize().unwrap();
+	}
+}
+
+#[test]
+fn test_binary_merkle_vcs_verify_openings_at_variable_depths() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	let layer_depth = 3;
+	let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+	let opening_depths = [3, 0, 2, 1, 3];
+	let indices = [0, 7, 13, 22, 31];
+
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	for (&index, &opening_depth) in indices.iter().zip(&opening_depths) {
+		mr_prover
+			.prove_opening(&tree, opening_depth, index, &mut proof_writer.message())
+			.unwrap();
+	}
+
+	let mut proof_reader = proof_writer.into_verifier();
+	let scheme = mr_prover.scheme();
+	for (&index, &opening_depth) in indices.iter().zip(&opening_depths) {
+		scheme
+			.verify_opening_at_depth(
+				index,
+				slice::from_ref(&data[index]),
+				opening_depth,
+				layer_depth,
+				5,
+				layer,
+				&mut proof_reader.message(),
+			)
+			.unwrap();
+	}
+	proof_reader.finalize().unwrap();
+
+	// An opening proven to a shallower depth with a tampered upper path is rejected.
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_opening(&tree, 0, 5, &mut proof_writer.message())
+		.unwrap();
+	let mut proof_reader = proof_writer.into_verifier();
+	let mut path = proof_reader.message().read_vec(5).unwrap();
+	path[4] = path[3];
+	assert!(
+		scheme
+			.verify_opening_path(5, slice::from_ref(&data[5]), layer_depth, 5, layer, &path)
+			.is_err()
+	);
+
+	// Openings cannot be proven to a deeper layer than the verified one.
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_opening(&tree, 4, 5, &mut proof_writer.message())
+		.unwrap();
+	let mut proof_reader = proof_writer.into_verifier();
+	assert!(
+		scheme
+			.verify_opening_at_depth(
+				5,
+				slice::from_ref(&data[5]),
+				4,
+				layer_depth,
+				5,
+				layer,
+				&mut proof_reader.message(),
+			)
+			.is_err()
+	);
+}
+
+#[test]
+fn test_binary_merkle_vcs_cap_commit_prove_open_correctly() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let cap_height = 2;
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::with_cap_height(
+		Groestl256ByteCompression,
+		cap_height,
+	);
+	let scheme = mr_prover.scheme();
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	let cap = mr_prover.cap(&tree, commitment.depth).unwrap();
+	assert_eq!(cap, mr_prover.layer(&tree, cap_height).unwrap());
+	scheme
+		.verify_layer(&commitment.root, cap_height, cap)
+		.unwrap();
+	assert_eq!(scheme.optimal_verify_layer(1, 5), cap_height);
+
+	for (i, value) in data.iter().enumerate() {
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		mr_prover
+			.prove_opening(&tree, cap_height, i, &mut proof_writer.message())
+			.unwrap();
+
+		let mut proof_reader = proof_writer.into_verifier();
+		scheme
+			.verify_opening(
+				i,
+				slice::from_ref(value),
+				cap_height,
+				5,
+				cap,
+				&mut proof_reader.message(),
+			)
+			.unwrap();
+		proof_reader.finalize().unwrap();
+	}
+
+	// Openings are neither proven nor verified above the cap.
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	assert!(
+		mr_prover
+			.prove_opening(&tree, 0, 0, &mut proof_writer.message())
+			.is_err()
+	);
+	let mut proof_reader = proof_writer.into_verifier();
+	assert!(
+		scheme
+			.verify_opening(
+				0,
+				slice::from_ref(&data[0]),
+				0,
+				5,
+				&[commitment.root],
+				&mut proof_reader.message(),
+			)
+			.is_err()
+	);
+
+	// The cap is part of the commitment, so only the paths below it count towards the proof size.
+	let uncapped_prover =
+		BinaryMerkleTreeProver::<BinaryField16b, Groestl256, _>::new(Groestl256ByteCompression);
+	assert_eq!(
+		scheme.proof_size(32, 1, 0).unwrap(),
+		uncapped_prover
+			.scheme()
+			.proof_size(32, 1, cap_height)
+			.unwrap() - (1 << cap_height) * 32
+	);
+}
+
+#[test]
+fn test_binary_merkle_vcs_cap_commitment_transcript() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	for cap_height in [0, 2, 7] {
+		let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::with_cap_height(
+			Groestl256ByteCompression,
+			cap_height,
+		);
+
+		let data = repeat_with(|| Field::random(&mut rng))
+			.take(32)
+			.collect::<Vec<BinaryField16b>>();
+		let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+		let cap = mr_prover.cap(&tree, commitment.depth).unwrap();
+		assert_eq!(cap.len(), 1 << cap_height.min(5));
+
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		mr_prover
+			.write_commitment(&commitment, &tree, &mut proof_writer.message())
+			.unwrap();
+
+		let mut proof_reader = proof_writer.into_verifier();
+		let (read_commitment, read_cap) = mr_prover
+			.scheme()
+			.read_commitment(5, &mut proof_reader.message())
+			.unwrap();
+		assert_eq!(read_commitment, commitment);
+		assert_eq!(read_cap, cap);
+		proof_reader.finalize().unwrap();
+	}
+}
+
+#[test]
+fn test_binary_merkle_vcs_pruned_tree_openings() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let batch_size = 3;
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(batch_size << 5)
+		.collect::<Vec<BinaryField16b>>();
+
+	for salted in [false, true] {
+		let mut mr_prover =
+			BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+		if salted {
+			mr_prover = mr_prover.with_salted_leaves(StdRng::seed_from_u64(1));
+		}
+		let (commitment, tree) = mr_prover.commit(&data, batch_size).unwrap();
+
+		let mut pruned_tree = tree.clone();
+		pruned_tree.prune_to_layer(2).unwrap();
+		assert_eq!(pruned_tree.stored_depth(), 2);
+		assert_eq!(pruned_tree.inner_nodes.len(), 7);
+		assert_eq!(pruned_tree.root(), commitment.root);
+		assert_eq!(pruned_tree.layer(2).unwrap(), tree.layer(2).unwrap());
+		assert!(matches!(pruned_tree.layer(3), Err(Error::PrunedLayer { layer_depth: 3 })));
+
+		for layer_depth in 0..=2 {
+			let layer = tree.layer(layer_depth).unwrap();
+			for (i, values) in data.chunks(batch_size).enumerate() {
+				let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				mr_prover
+					.prove_opening(&tree, layer_depth, i, &mut proof_writer.message())
+					.unwrap();
+
+				let mut pruned_proof_writer =
+					ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				mr_prover
+					.prove_opening_from_leaves(
+						&pruned_tree,
+						layer_depth,
+						i,
+						|leaves| data[leaves.start * batch_size..leaves.end * batch_size].to_vec(),
+						&mut pruned_proof_writer.message(),
+					)
+					.unwrap();
+
+				let proof = proof_writer.finalize();
+				assert_eq!(pruned_proof_writer.finalize(), proof);
+
+				let mut proof_reader =
+					VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof);
+				mr_prover
+					.scheme()
+					.verify_opening(i, values, layer_depth, 5, layer, &mut proof_reader.message())
+					.unwrap();
+			}
+		}
+
+		// Openings against the pruned layers need the layers themselves.
+		let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		assert!(
+			mr_prover
+				.prove_opening(&pruned_tree, 0, 0, &mut proof_writer.message())
+				.is_err()
+		);
+		assert!(
+			mr_prover
+				.prove_opening_from_leaves(
+					&pruned_tree,
+					3,
+					0,
+					|_| unreachable!(),
+					&mut proof_writer.message()
+				)
+				.is_err()
+		);
+	}
+}
+
+#[test]
+fn test_binary_merkle_vcs_sibling_pair_openings() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let scheme = mr_prover.scheme();
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	for layer_depth in 0..5 {
+		let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+		for (pair_index, pair) in data.chunks_exact(2).enumerate() {
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_pair_opening(&tree, layer_depth, pair_index, &mut proof_writer.message())
+				.unwrap();
+
+			// The pair opening saves one digest over opening a single entry.
+			let mut single_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_opening(&tree, layer_depth, 2 * pair_index, &mut single_writer.message())
+				.unwrap();
+			assert_eq!(proof_writer.finalize().len() + 32, single_writer.finalize().len());
+
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_pair_opening(&tree, layer_depth, pair_index, &mut proof_writer.message())
+				.unwrap();
+			let mut proof_reader = proof_writer.into_verifier();
+			scheme
+				.verify_pair_opening(
+					pair_index,
+					[slice::from_ref(&pair[0]), slice::from_ref(&pair[1])],
+					layer_depth,
+					5,
+					layer,
+					&mut proof_reader.message(),
+				)
+				.unwrap();
+			proof_reader.finalize().unwrap();
+
+			// Swapping the siblings invalidates the opening.
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_pair_opening(&tree, layer_depth, pair_index, &mut proof_writer.message())
+				.unwrap();
+			let mut proof_reader = proof_writer.into_verifier();
+			assert!(
+				scheme
+					.verify_pair_opening(
+						pair_index,
+						[slice::from_ref(&pair[1]), slice::from_ref(&pair[0])],
+						layer_depth,
+						5,
+						layer,
+						&mut proof_reader.message(),
+					)
+					.is_err()
+			);
+		}
+	}
+
+	// The leaves have no sibling pairs above them.
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	assert!(
+		mr_prover
+			.prove_pair_opening(&tree, 5, 0, &mut proof_writer.message())
+			.is_err()
+	);
+}
+
+#[test]
+fn test_binary_merkle_vcs_padded_leaves() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let batch_size = 3;
+	for n_leaves in [1, 5, 11, 16] {
+		let data = repeat_with(|| Field::random(&mut rng))
+			.take(batch_size * n_leaves)
+			.collect::<Vec<BinaryField16b>>();
+		let log_len = log2_ceil_usize(n_leaves);
+
+		let unpadded_prover =
+			BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+		if !n_leaves.is_power_of_two() {
+			assert!(matches!(
+				unpadded_prover.commit(&data, batch_size),
+				Err(Error::PowerOfTwoLengthRequired)
+			));
+		}
+
+		for padding in [LeafPadding::ZeroDigest, LeafPadding::DuplicateLast] {
+			let mr_prover =
+				BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+					.with_padding(padding);
+			let scheme = mr_prover.scheme();
+			let (commitment, tree) = mr_prover.commit(&data, batch_size).unwrap();
+			assert_eq!(commitment.depth, log_len);
+			scheme
+				.verify_vector(&commitment.root, &data, batch_size)
+				.unwrap();
+			assert!(
+				scheme
+					.proof_size(n_leaves, 4, 0)
+					.is_ok_and(|size| size == scheme.proof_size(1 << log_len, 4, 0).unwrap())
+			);
+
+			// The padding leaves are the zero digest or the digest of the last leaf.
+			let leaves = tree.layer(log_len).unwrap();
+			for leaf in &leaves[n_leaves..] {
+				let expected = match padding {
+					LeafPadding::ZeroDigest => Output::<Groestl256>::default(),
+					_ => leaves[n_leaves - 1],
+				};
+				assert_eq!(*leaf, expected);
+			}
+
+			for (i, values) in data.chunks(batch_size).enumerate() {
+				let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				mr_prover
+					.prove_opening(&tree, 0, i, &mut proof_writer.message())
+					.unwrap();
+
+				let mut proof_reader = proof_writer.into_verifier();
+				scheme
+					.verify_opening(
+						i,
+						values,
+						0,
+						log_len,
+						&[commitment.root],
+						&mut proof_reader.message(),
+					)
+					.unwrap();
+			}
+
+			// Salted padding leaves are opened with the vector.
+			let salted_prover =
+				BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+					.with_padding(padding)
+					.with_salted_leaves(StdRng::seed_from_u64(1));
+			let (commitment, tree) = salted_prover.commit(&data, batch_size).unwrap();
+			assert_eq!(salted_prover.scheme().vector_opening_len(n_leaves), 1 << log_len);
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			salted_prover
+				.prove_vector_opening(&tree, &mut proof_writer.message())
+				.unwrap();
+			let mut proof_reader = proof_writer.into_verifier();
+			salted_prover
+				.scheme()
+				.verify_vector_opening(
+					&commitment.root,
+					&data,
+					batch_size,
+					&mut proof_reader.message(),
+				)
+				.unwrap();
+			proof_reader.finalize().unwrap();
+		}
+	}
+}
+
+#[test]
+fn test_binary_merkle_vcs_domain_separated_hash_inputs() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_domain_separation(DomainSeparation::Tagged);
+	let parallel_prover =
+		BinaryMerkleTreeProver::<_, Groestl256Parallel, _>::new(Groestl256ByteCompression)
+			.with_domain_separation(DomainSeparation::Tagged);
+	let (commitment, tree) = mr_prover.commit(&data, 2).unwrap();
+	let (parallel_commitment, _) = parallel_prover.commit(&data, 2).unwrap();
+	assert_eq!(parallel_commitment, commitment);
+
+	// The leaves are the values hashed after the leaf domain byte.
+	let leaves = tree.layer(4).unwrap();
+	for (leaf, values) in leaves.iter().zip(data.chunks(2)) {
+		assert_eq!(
+			*leaf,
+			hash_serialize_with_prefix::<BinaryField16b, Groestl256>(&[1], values).unwrap()
+		);
+	}
+
+	// The inner nodes are their children hashed after the inner domain byte.
+	for depth in 0..4 {
+		let children = tree.layer(depth + 1).unwrap();
+		for (node, pair) in tree.layer(depth).unwrap().iter().zip(children.chunks(2)) {
+			let expected = Groestl256::new_with_prefix([2])
+				.chain_update(pair[0])
+				.chain_update(pair[1])
+				.finalize();
+			assert_eq!(*node, expected);
+		}
+	}
+	assert_ne!(
+		tree.layer(3).unwrap()[0],
+		Groestl256ByteCompression.compress([leaves[0], leaves[1]])
+	);
+
+	parallel_prover
+		.scheme()
+		.verify_vector(&commitment.root, &data, 2)
+		.unwrap();
+}
+
+#[test]
+fn test_binary_merkle_vcs_domain_separated_nodes() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let batch_size = 2;
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(batch_size << 5)
+		.collect::<Vec<BinaryField16b>>();
+
+	let untagged_prover =
+		BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let (untagged_commitment, _) = untagged_prover.commit(&data, batch_size).unwrap();
+
+	let mut roots = vec![untagged_commitment.root];
+	for separation in [DomainSeparation::Tagged, DomainSeparation::TaggedWithDepth] {
+		let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::with_cap_height(
+			Groestl256ByteCompression,
+			1,
+		)
+		.with_log_leaf_chunk_len(2)
+		.with_domain_separation(separation);
+		let scheme = mr_prover.scheme();
+		let (commitment, tree) = mr_prover.commit(&data, batch_size).unwrap();
+		assert!(!roots.contains(&commitment.root));
+		roots.push(commitment.root);
+
+		// Every way of building the tree tags its nodes alike.
+		let (iterated_commitment, _) = mr_prover
+			.commit_iterated(data.par_chunks(batch_size).map(|leaf| leaf.to_vec()), 5)
+			.unwrap();
+		assert_eq!(iterated_commitment, commitment);
+		let mut committer = mr_prover.stream_commit(5, batch_size).unwrap();
+		committer.push_leaves(&data).unwrap();
+		let (streamed_commitment, _) = committer.finalize().unwrap();
+		assert_eq!(streamed_commitment, commitment);
+
+		scheme
+			.verify_vector(&commitment.root, &data, batch_size)
+			.unwrap();
+		assert_eq!(
+			scheme
+				.cap_root(mr_prover.cap(&tree, commitment.depth).unwrap())
+				.unwrap(),
+			commitment.root
+		);
+		assert!(
+			untagged_prover
+				.scheme()
+				.verify_vector(&commitment.root, &data, batch_size)
+				.is_err()
+		);
+
+		let mut pruned_tree = tree.clone();
+		pruned_tree.prune_to_layer(3).unwrap();
+		for layer_depth in 1..=3 {
+			let layer = tree.layer(layer_depth).unwrap();
+			scheme
+				.verify_layer(&commitment.root, layer_depth, layer)
+				.unwrap();
+			for (i, values) in data.chunks(batch_size).enumerate() {
+				let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				mr_prover
+					.prove_opening(&tree, layer_depth, i, &mut proof_writer.message())
+					.unwrap();
+
+				// Rebuilding a pruned subtree from its leaves tags it at its depth in the tree.
+				let mut pruned_proof_writer =
+					ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				mr_prover
+					.prove_opening_from_leaves(
+						&pruned_tree,
+						layer_depth,
+						i,
+						|leaves| data[leaves.start * batch_size..leaves.end * batch_size].to_vec(),
+						&mut pruned_proof_writer.message(),
+					)
+					.unwrap();
+				let proof = proof_writer.finalize();
+				assert_eq!(pruned_proof_writer.finalize(), proof);
+
+				let mut proof_reader =
+					VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+				scheme
+					.verify_opening(i, values, layer_depth, 5, layer, &mut proof_reader.message())
+					.unwrap();
+
+				let mut proof_reader =
+					VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof);
+				assert!(
+					untagged_prover
+						.scheme()
+						.verify_opening(
+							i,
+							values,
+							layer_depth,
+							5,
+							layer,
+							&mut proof_reader.message()
+						)
+						.is_err()
+				);
+			}
+
+			let indices = [0, 1, 6, 17, 30];
+			let values = indices
+				.iter()
+				.map(|&i| &data[i * batch_size..(i + 1) * batch_size])
+				.collect::<Vec<_>>();
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_openings(&tree, layer_depth, &indices, &mut proof_writer.message())
+				.unwrap();
+			let mut proof_reader = proof_writer.into_verifier();
+			scheme
+				.verify_openings(
+					&indices,
+					&values,
+					layer_depth,
+					5,
+					layer,
+					&mut proof_reader.message(),
+				)
+				.unwrap();
+
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_pair_opening(&tree, layer_depth, 3, &mut proof_writer.message())
+				.unwrap();
+			let mut proof_reader = proof_writer.into_verifier();
+			scheme
+				.verify_pair_opening(
+					3,
+					[
+						&data[6 * batch_size..7 * batch_size],
+						&data[7 * batch_size..8 * batch_size],
+					],
+					layer_depth,
+					5,
+					layer,
+					&mut proof_reader.message(),
+				)
+				.unwrap();
+		}
+	}
+}
+
+#[test]
+fn test_binary_merkle_vcs_salted_leaves() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_log_leaf_chunk_len(2)
+		.with_salted_leaves(StdRng::seed_from_u64(1));
+	let scheme = mr_prover.scheme();
+	assert!(scheme.is_salted());
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+	assert_eq!(commitment.root, tree.root());
+
+	// The salts hide the leaves, so committing to the same data twice gives different roots.
+	let (other_commitment, _) = mr_prover.commit(&data, 1).unwrap();
+	assert_ne!(commitment.root, other_commitment.root);
+	let (unsalted_commitment, _) =
+		BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+			.commit(&data, 1)
+			.unwrap();
+	assert_ne!(commitment.root, unsalted_commitment.root);
+
+	// The full vector is verified with its salts.
+	assert!(matches!(scheme.verify_vector(&commitment.root, &data, 1), Err(Error::SaltsRequired)));
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_vector_opening(&tree, &mut proof_writer.message())
+		.unwrap();
+	assert_eq!(proof_writer.finalize().len(), scheme.vector_opening_len(32) * 32);
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_vector_opening(&tree, &mut proof_writer.message())
+		.unwrap();
+	let mut proof_reader = proof_writer.into_verifier();
+	scheme
+		.verify_vector_opening(&commitment.root, &data, 1, &mut proof_reader.message())
+		.unwrap();
+	proof_reader.finalize().unwrap();
+
+	for layer_depth in 0..5 {
+		let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+		for (i, value) in data.iter().enumerate() {
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_opening(&tree, layer_depth, i, &mut proof_writer.message())
+				.unwrap();
+			let proof = proof_writer.finalize();
+			assert_eq!(proof.len(), scheme.opening_path_len(5, layer_depth) * 32);
+
+			let mut proof_reader =
+				VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+			scheme
+				.verify_opening(
+					i,
+					slice::from_ref(value),
+					layer_depth,
+					5,
+					layer,
+					&mut proof_reader.message(),
+				)
+				.unwrap();
+			proof_reader.finalize().unwrap();
+
+			// An opening with a tampered salt does not verify.
+			let mut tampered = proof;
+			tampered[0] ^= 1;
+			let mut proof_reader =
+				VerifierTranscript::<HasherChallenger<Groestl256>>::new(tampered);
+			assert!(
+				scheme
+					.verify_opening(
+						i,
+						slice::from_ref(value),
+						layer_depth,
+						5,
+						layer,
+						&mut proof_reader.message(),
+					)
+					.is_err()
+			);
+		}
+
+		if layer_depth < 5 {
+			for (pair_index, pair) in data.chunks_exact(2).enumerate() {
+				let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				mr_prover
+					.prove_pair_opening(&tree, layer_depth, pair_index, &mut proof_writer.message())
+					.unwrap();
+				let mut proof_reader = proof_writer.into_verifier();
+				scheme
+					.verify_pair_opening(
+						pair_index,
+						[slice::from_ref(&pair[0]), slice::from_ref(&pair[1])],
+						layer_depth,
+						5,
+						layer,
+						&mut proof_reader.message(),
+					)
+					.unwrap();
+				proof_reader.finalize().unwrap();
+			}
+		}
+	}
+}
+
+fn check_nary_merkle_vcs_commit_layer_prove_open<const N: usize>()
+where
+	Sha256Compression: PseudoCompressionFunction<Output<Sha256>, N>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = NaryMerkleTreeProver::<_, Sha256, _, N>::new(Sha256Compression::default());
+	let log_arity = N.ilog2() as usize;
+
+	for log_len in 0..8 {
+		let data = repeat_with(|| Field::random(&mut rng))
+			.take(1 << log_len)
+			.collect::<Vec<BinaryField16b>>();
+		let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+
+		assert_eq!(commitment.root, tree.root());
+		mr_prover
+			.scheme()
+			.verify_vector(&commitment.root, &data, 1)
+			.unwrap();
+
+		for layer_depth in 0..=log_len {
+			if !is_nary_layer(log_arity, log_len, layer_depth) {
+				assert!(matches!(
+					mr_prover.layer(&tree, layer_depth),
+					Err(Error::IncorrectLayerDepth)
+				));
+				continue;
+			}
+
+			let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+			mr_prover
+				.scheme()
+				.verify_layer(&commitment.root, layer_depth, layer)
+				.unwrap();
+			for (i, value) in data.iter().enumerate() {
+				let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				mr_prover
+					.prove_opening(&tree, layer_depth, i, &mut proof_writer.message())
+					.unwrap();
+
+				let mut proof_reader = proof_writer.into_verifier();
+				mr_prover
+					.scheme()
+					.verify_opening(
+						i,
+						slice::from_ref(value),
+						layer_depth,
+						log_len,
+						layer,
+						&mut proof_reader.message(),
+					)
+					.unwrap();
+				proof_reader.finalize().unwrap();
+			}
+		}
+	}
+}
+
+#[test]
+fn test_nary_merkle_vcs_commit_layer_prove_open_correctly() {
+	check_nary_merkle_vcs_commit_layer_prove_open::<2>();
+	check_nary_merkle_vcs_commit_layer_prove_open::<4>();
+	check_nary_merkle_vcs_commit_layer_prove_open::<8>();
+}
+
+#[test]
+fn test_nary_merkle_tree_of_arity_two_matches_binary_tree() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let nary_prover = NaryMerkleTreeProver::<_, Sha256, _, 2>::new(Sha256Compression::default());
+	let binary_prover =
+		BinaryMerkleTreeProver::<BinaryField16b, Sha256, _>::new(Sha256Compression::default());
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (nary_commitment, nary_tree) = nary_prover.commit(&data, 1).unwrap();
+	let (binary_commitment, binary_tree) = binary_prover.commit(&data, 1).unwrap();
+
+	assert_eq!(nary_commitment, binary_commitment);
+	assert_eq!(nary_tree.inner_nodes, *binary_tree.inner_nodes);
+	for index in 0..data.len() {
+		assert_eq!(nary_tree.branch(index, 1).unwrap(), binary_tree.branch(index, 1).unwrap());
+	}
+}
+
+#[test]
+fn test_quaternary_merkle_vcs_proof_size() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = QuaternaryMerkleTreeProver::<_, Sha256, _>::new(Sha256Compression::default());
+	let scheme = mr_prover.scheme();
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(1 << 7)
+		.collect::<Vec<BinaryField16b>>();
+	let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	let n_queries = 5;
+	let layer_depth = scheme.optimal_verify_layer(n_queries, 7);
+	assert_eq!(layer_depth, 3);
+
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof_writer
+		.message()
+		.write_slice(mr_prover.layer(&tree, layer_depth).unwrap());
+	for index in 0..n_queries {
+		mr_prover
+			.prove_opening(&tree, layer_depth, 20 * index, &mut proof_writer.message())
+			.unwrap();
+	}
+	assert_eq!(
+		scheme
+			.proof_size(data.len(), n_queries, layer_depth)
+			.unwrap(),
+		proof_writer.finalize().len()
+	);
+	assert_eq!(scheme.opening_path_len(7, layer_depth), 6);
+	assert_eq!(scheme.opening_path_len(7, 0), 10);
+}
+
+#[test]
+fn test_quaternary_merkle_vcs_verify_openings_at_variable_depths() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = QuaternaryMerkleTreeProver::<_, Sha256, _>::new(Sha256Compression::default());
+	let scheme = mr_prover.scheme();
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(32)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	let layer_depth = 3;
+	let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+	scheme
+		.verify_layer(&commitment.root, layer_depth, layer)
+		.unwrap();
+	let opening_depths = [3, 0, 1, 0, 3];
+	let indices = [0, 7, 13, 22, 31];
+
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	for (&index, &opening_depth) in indices.iter().zip(&opening_depths) {
+		mr_prover
+			.prove_opening(&tree, opening_depth, index, &mut proof_writer.message())
+			.unwrap();
+	}
+
+	let mut proof_reader = proof_writer.into_verifier();
+	for (&index, &opening_depth) in indices.iter().zip(&opening_depths) {
+		scheme
+			.verify_opening_at_depth(
+				index,
+				slice::from_ref(&data[index]),
+				opening_depth,
+				layer_depth,
+				5,
+				layer,
+				&mut proof_reader.message(),
+			)
+			.unwrap();
+	}
+	proof_reader.finalize().unwrap();
+
+	// An opening proven to a shallower depth with a tampered upper path is rejected.
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_opening(&tree, 0, 5, &mut proof_writer.message())
+		.unwrap();
+	let mut proof_reader = proof_writer.into_verifier();
+	let mut path = proof_reader
+		.message()
+		.read_vec(scheme.opening_path_len(5, 0))
+		.unwrap();
+	let last = path.len() - 1;
+	path[last] = path[0];
+	assert!(matches!(
+		scheme.verify_opening_path(5, slice::from_ref(&data[5]), layer_depth, 5, layer, &path),
+		Err(Error::Verification(VerificationError::IncorrectPathAboveLayer { .. }))
+	));
+
+	// The tree has no layer at an even depth.
+	assert!(matches!(
+		scheme.verify_opening(
+			5,
+			slice::from_ref(&data[5]),
+			2,
+			5,
+			mr_prover.layer(&tree, 1).unwrap(),
+			&mut ProverTranscript::<HasherChallenger<Groestl256>>::new()
+				.into_verifier()
+				.message(),
+		),
+		Err(Error::IncorrectLayerDepth)
+	));
+}
+
+fn check_nary_merkle_vcs_sibling_pair_openings<const N: usize>()
+where
+	Sha256Compression: PseudoCompressionFunction<Output<Sha256>, N>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = NaryMerkleTreeProver::<_, Sha256, _, N>::new(Sha256Compression::default());
+	let scheme = mr_prover.scheme();
+	let log_arity = N.ilog2() as usize;
+
+	for log_len in 1..8 {
+		let data = repeat_with(|| Field::random(&mut rng))
+			.take(1 << log_len)
+			.collect::<Vec<BinaryField16b>>();
+		let (_, tree) = mr_prover.commit(&data, 1).unwrap();
+
+		for layer_depth in (0..log_len).filter(|&depth| is_nary_layer(log_arity, log_len, depth)) {
+			let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+			for (pair_index, pair) in data.chunks_exact(2).enumerate() {
+				// The pair opening saves one digest over opening a single entry.
+				let mut single_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				mr_prover
+					.prove_opening(&tree, layer_depth, 2 * pair_index, &mut single_writer.message())
+					.unwrap();
+				let single_len = single_writer.finalize().len();
+
+				let prove_pair = || {
+					let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+					mr_prover
+						.prove_pair_opening(
+							&tree,
+							layer_depth,
+							pair_index,
+							&mut proof_writer.message(),
+						)
+						.unwrap();
+					proof_writer
+				};
+				assert_eq!(prove_pair().finalize().len() + 32, single_len);
+
+				let mut proof_reader = prove_pair().into_verifier();
+				scheme
+					.verify_pair_opening(
+						pair_index,
+						[slice::from_ref(&pair[0]), slice::from_ref(&pair[1])],
+						layer_depth,
+						log_len,
+						layer,
+						&mut proof_reader.message(),
+					)
+					.unwrap();
+				proof_reader.finalize().unwrap();
+
+				// Swapping the siblings invalidates the opening.
+				assert!(
+					scheme
+						.verify_pair_opening(
+							pair_index,
+							[slice::from_ref(&pair[1]), slice::from_ref(&pair[0])],
+							layer_depth,
+							log_len,
+							layer,
+							&mut prove_pair().into_verifier().message(),
+						)
+						.is_err()
+				);
+			}
+		}
+	}
+}
+
+#[test]
+fn test_nary_merkle_vcs_sibling_pair_openings() {
+	check_nary_merkle_vcs_sibling_pair_openings::<2>();
+	check_nary_merkle_vcs_sibling_pair_openings::<4>();
+	check_nary_merkle_vcs_sibling_pair_openings::<8>();
+}
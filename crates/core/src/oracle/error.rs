@@ -24,6 +24,10 @@ pub enum Error {
 	NotEnoughVarsForPacking { n_vars: usize, log_degree: usize },
 	#[error("no oracle exists in this MultilinearOracleSet with id {0}")]
 	InvalidOracleId(OracleId),
+	#[error("oracle {0} has no new ID in the remapping")]
+	UnmappedOracleId(OracleId),
+	#[error("cannot remap oracle {from} to {to}, which conflicts with an existing mapping")]
+	ConflictingOracleIdMapping { from: OracleId, to: OracleId },
 	#[error("tower_level ({tower_level}) exceeds maximum")]
 	TowerLevelTooHigh { tower_level: usize },
 	#[error("constraint set is empty")]
@@ -32,4 +36,6 @@ pub enum Error {
 		"expected constraint set to contain only constraints with n_vars={expected}, but found n_vars={got}"
 	)]
 	ConstraintSetNvarsMismatch { got: usize, expected: usize },
+	#[error("oracle {0} is not committed and cannot be marked secret")]
+	SecretOracleNotCommitted(OracleId),
 }
@@ -11,9 +11,11 @@ mod constraint;
 mod error;
 mod multilinear;
 mod oracle_id;
+mod remap;
 
 pub use composite::*;
 pub use constraint::*;
 pub use error::Error;
 pub use multilinear::*;
 pub use oracle_id::*;
+pub use remap::OracleIdRemapping;
@@ -383,12 +383,15 @@ impl<F: TowerField> MultilinearOracleSetAddition<'_, F> {
 #[deserialize_bytes(eval_generics(F = BinaryField128b))]
 pub struct MultilinearOracleSet<F: TowerField> {
 	oracles: Vec<MultilinearPolyOracle<F>>,
+	/// The committed oracles marked secret, in ascending order.
+	secret: Vec<OracleId>,
 }
 
 impl<F: TowerField> MultilinearOracleSet<F> {
 	pub const fn new() -> Self {
 		Self {
 			oracles: Vec::new(),
+			secret: Vec::new(),
 		}
 	}
 
@@ -532,6 +535,37 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 		self.add().composite_mle(n_vars, inner, comp)
 	}
 
+	/// Marks a committed oracle as secret.
+	///
+	/// The constraint system prover hides secret oracles: the batch is committed and opened with a
+	/// hiding commitment, and the table constraining a secret oracle is proven with a masked
+	/// sumcheck over its columns extended with random evaluations, see
+	/// [`crate::protocols::sumcheck::zk`]. Tables over public oracles only keep the cheaper
+	/// zerocheck. A secret oracle may be constrained by a single table and must not be flushed,
+	/// checked non-zero, exponentiated, or referenced by another oracle.
+	pub fn mark_secret(&mut self, id: OracleId) -> Result<(), Error> {
+		if !self.is_valid_oracle_id(id) {
+			bail!(Error::InvalidOracleId(id));
+		}
+		if !self[id].variant.is_committed() {
+			bail!(Error::SecretOracleNotCommitted(id));
+		}
+		if let Err(index) = self.secret.binary_search(&id) {
+			self.secret.insert(index, id);
+		}
+		Ok(())
+	}
+
+	/// Returns whether an oracle is marked secret.
+	pub fn is_secret(&self, id: OracleId) -> bool {
+		self.secret.binary_search(&id).is_ok()
+	}
+
+	/// Returns the oracles marked secret, in ascending order.
+	pub fn secret_ids(&self) -> &[OracleId] {
+		&self.secret
+	}
+
 	pub fn n_vars(&self, id: OracleId) -> usize {
 		self[id].n_vars()
 	}
@@ -984,6 +1018,7 @@ mod tests {
 	use binius_field::{BinaryField1b, BinaryField128b, Field, TowerField};
 
 	use super::MultilinearOracleSet;
+	use crate::oracle::Error;
 
 	#[test]
 	fn add_projection_with_all_vars() {
@@ -996,4 +1031,25 @@ mod tests {
 			.unwrap();
 		let _ = &oracles[projected];
 	}
+
+	#[test]
+	fn mark_secret_committed_oracles() {
+		type F = BinaryField128b;
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let [a, b] = oracles.add_committed_multiple(5, BinaryField1b::TOWER_LEVEL);
+		let shifted = oracles
+			.add_shifted(a, 1, 5, super::ShiftVariant::LogicalLeft)
+			.unwrap();
+
+		oracles.mark_secret(b).unwrap();
+		oracles.mark_secret(a).unwrap();
+		oracles.mark_secret(b).unwrap();
+		assert_eq!(oracles.secret_ids(), &[a, b]);
+		assert!(oracles.is_secret(a));
+		assert!(!oracles.is_secret(shifted));
+		assert!(matches!(
+			oracles.mark_secret(shifted),
+			Err(Error::SecretOracleNotCommitted(id)) if id == shifted
+		));
+	}
 }
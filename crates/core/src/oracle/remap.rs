@@ -0,0 +1,150 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_utils::{bail, sparse_index::SparseIndex};
+
+use super::{Error, OracleId};
+
+/// A one-to-one renumbering of oracle IDs.
+///
+/// Optimization passes over a [`super::MultilinearOracleSet`] and serialization round-trips may
+/// assign new IDs to existing oracles. A remapping records the new ID of each retained oracle, so
+/// that data keyed by the old IDs, such as evaluation claims, witness indices, and the index from
+/// oracle IDs to committed IDs, can be translated consistently. Translating data that references
+/// an oracle without a new ID fails, rather than silently dropping it.
+#[derive(Debug, Clone, Default)]
+pub struct OracleIdRemapping {
+	forward: SparseIndex<OracleId>,
+	backward: SparseIndex<OracleId>,
+}
+
+impl OracleIdRemapping {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Constructs a remapping from pairs of old and new IDs.
+	pub fn from_pairs(
+		pairs: impl IntoIterator<Item = (OracleId, OracleId)>,
+	) -> Result<Self, Error> {
+		let mut remapping = Self::new();
+		for (from, to) in pairs {
+			remapping.insert(from, to)?;
+		}
+		Ok(remapping)
+	}
+
+	/// Records that the oracle with ID `from` has the new ID `to`.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ConflictingOracleIdMapping`] if `from` already has a different new ID, or `to` is
+	///   already the new ID of a different oracle
+	pub fn insert(&mut self, from: OracleId, to: OracleId) -> Result<(), Error> {
+		let existing_to = self.forward.get(from.index()).copied();
+		let existing_from = self.backward.get(to.index()).copied();
+		if existing_to.is_some_and(|existing_to| existing_to != to)
+			|| existing_from.is_some_and(|existing_from| existing_from != from)
+		{
+			bail!(Error::ConflictingOracleIdMapping { from, to });
+		}
+		self.forward.set(from.index(), to);
+		self.backward.set(to.index(), from);
+		Ok(())
+	}
+
+	/// Returns the new ID of an oracle, if it has one.
+	pub fn get(&self, id: OracleId) -> Option<OracleId> {
+		self.forward.get(id.index()).copied()
+	}
+
+	/// Returns the new ID of an oracle.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::UnmappedOracleId`] if the oracle has no new ID
+	pub fn remap(&self, id: OracleId) -> Result<OracleId, Error> {
+		self.get(id).ok_or(Error::UnmappedOracleId(id))
+	}
+
+	/// Returns the number of remapped oracles.
+	pub fn len(&self) -> usize {
+		self.forward.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.forward.is_empty()
+	}
+
+	/// Iterates over the pairs of old and new IDs, in ascending order by old ID.
+	pub fn iter(&self) -> impl Iterator<Item = (OracleId, OracleId)> + '_ {
+		self.forward
+			.iter()
+			.map(|(from, &to)| (OracleId::from_index(from), to))
+	}
+
+	/// Returns the remapping from the new IDs back to the old IDs.
+	pub fn inverse(&self) -> Self {
+		Self {
+			forward: self.backward.clone(),
+			backward: self.forward.clone(),
+		}
+	}
+
+	/// Returns the remapping that applies this remapping and then `next`.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::UnmappedOracleId`] if a new ID of this remapping is not remapped by `next`
+	pub fn then(&self, next: &Self) -> Result<Self, Error> {
+		Self::from_pairs(
+			self.iter()
+				.map(|(from, to)| Ok((from, next.remap(to)?)))
+				.collect::<Result<Vec<_>, Error>>()?,
+		)
+	}
+
+	/// Re-keys a sparse index by oracle ID from the old IDs to the new IDs.
+	///
+	/// This translates, for example, the index from oracle IDs to committed IDs returned by
+	/// [`crate::piop::make_oracle_commit_meta`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::UnmappedOracleId`] if an oracle with an entry in the index has no new ID
+	pub fn remap_sparse_index<T>(&self, index: SparseIndex<T>) -> Result<SparseIndex<T>, Error> {
+		let mut remapped = SparseIndex::new();
+		for (id, value) in index {
+			remapped.set(self.remap(OracleId::from_index(id))?.index(), value);
+		}
+		Ok(remapped)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_oracle_id_remapping() {
+		let id = OracleId::from_index;
+		let remapping = OracleIdRemapping::from_pairs([(id(0), id(2)), (id(3), id(0))]).unwrap();
+		assert_eq!(remapping.remap(id(3)).unwrap(), id(0));
+		assert!(matches!(remapping.remap(id(1)), Err(Error::UnmappedOracleId(_))));
+		assert_eq!(remapping.inverse().remap(id(2)).unwrap(), id(0));
+
+		let next = OracleIdRemapping::from_pairs([(id(2), id(1)), (id(0), id(5))]).unwrap();
+		let composed = remapping.then(&next).unwrap();
+		assert_eq!(composed.iter().collect::<Vec<_>>(), vec![(id(0), id(1)), (id(3), id(5))]);
+
+		let mut index = SparseIndex::new();
+		index.set(0, "a");
+		index.set(3, "b");
+		let remapped = remapping.remap_sparse_index(index).unwrap();
+		assert_eq!(remapped.iter().collect::<Vec<_>>(), vec![(0, &"b"), (2, &"a")]);
+
+		assert!(matches!(
+			OracleIdRemapping::from_pairs([(id(0), id(1)), (id(2), id(1))]),
+			Err(Error::ConflictingOracleIdMapping { .. })
+		));
+	}
+}
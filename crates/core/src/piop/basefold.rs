@@ -0,0 +1,713 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A BaseFold commitment scheme for the batches of multilinears committed by the PIOP compiler.
+//!
+//! BaseFold [ZLT23] commits to the interleaved message with a random foldable code instead of a
+//! Reed–Solomon code. A foldable code of dimension $2^k$ is defined recursively: the codeword of a
+//! message $m$ interleaves $A + T \cdot B$ and $A + (T + 1) \cdot B$, where $A$ and $B$ are the
+//! codewords of dimension $2^{k-1}$ of the even and odd entries of $m$, and $T$ is a public random
+//! diagonal matrix. The code of dimension one is the repetition code.
+//!
+//! Folding a codeword with a challenge $r$ yields the codeword of the message folded at $r$ in its
+//! lowest variable, just like FRI folding of the additive NTT encoding. The code is therefore
+//! opened with the same interleaved sumcheck protocol as the FRI-Binius backend, except that the
+//! prover commits every folded codeword and the codeword of dimension one, which repeats the
+//! evaluation of the message, is sent as that evaluation.
+//!
+//! The entries of the random diagonals are derived from a public seed one by one, so that the
+//! verifier only computes those its queries need.
+//!
+//! [ZLT23]: <https://eprint.iacr.org/2023/1705>
+
+use std::{borrow::Borrow, iter, marker::PhantomData};
+
+use binius_compute::{ComputeLayer, alloc::ComputeAllocator, cpu::CpuMemory};
+use binius_field::{
+	BinaryField, ExtensionField, Field, PackedExtension, PackedField, PackedFieldIndexable,
+	TowerField,
+	packed::{get_packed_slice, iter_packed_slice_with_offset},
+};
+use binius_math::MultilinearPoly;
+use binius_maybe_rayon::prelude::*;
+use binius_utils::{DeserializeBytes, SerializeBytes, bail, sorting::is_sorted_ascending};
+use bytemuck::zeroed_vec;
+use bytes::{Buf, BufMut};
+use itertools::izip;
+use rand::{SeedableRng, rngs::StdRng};
+use tracing::instrument;
+
+use super::{
+	error::{Error, VerificationError},
+	prove::{InterleavedFolder, merge_multilins, packed_committed, prove_packed_committed},
+	verify::{CommitMeta, InterleavedFoldVerifier, PIOPSumcheckClaim, verify_with_folder},
+};
+use crate::{
+	fiat_shamir::{CanSampleBits, Challenger},
+	merkle_tree::{MerkleTreeProver, MerkleTreeScheme},
+	oracle::OracleId,
+	polynomial::MultivariatePoly,
+	protocols::fri::{CommitOutput, FoldRoundOutput},
+	transcript::{ProverTranscript, TranscriptReader, TranscriptWriter, VerifierTranscript},
+};
+
+/// Parameters of the BaseFold commitment scheme.
+///
+/// The random foldable code encodes messages over `F` with random diagonals over `FEncode`, so that
+/// encoding and folding multiply by subfield elements.
+#[derive(Debug, Clone)]
+pub struct BaseFoldParams<F, FEncode> {
+	log_msg_len: usize,
+	log_inv_rate: usize,
+	n_test_queries: usize,
+	seed: u64,
+	_marker: PhantomData<(F, FEncode)>,
+}
+
+impl<F, FEncode> BaseFoldParams<F, FEncode>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	/// Constructs BaseFold parameters.
+	///
+	/// ## Arguments
+	///
+	/// * `log_msg_len` - the binary logarithm of the length of the committed message
+	/// * `log_inv_rate` - the negative binary logarithm of the rate of the code
+	/// * `n_test_queries` - the number of queries in the query phase
+	/// * `seed` - the seed from which the random diagonals of the code are derived
+	pub fn new(
+		log_msg_len: usize,
+		log_inv_rate: usize,
+		n_test_queries: usize,
+		seed: u64,
+	) -> Result<Self, Error> {
+		if log_msg_len == 0 {
+			bail!(Error::InvalidBaseFoldParams(
+				"the message must have at least two elements".to_string()
+			));
+		}
+		if log_inv_rate == 0 {
+			bail!(Error::InvalidBaseFoldParams("the rate must be less than one".to_string()));
+		}
+
+		Ok(Self {
+			log_msg_len,
+			log_inv_rate,
+			n_test_queries,
+			seed,
+			_marker: PhantomData,
+		})
+	}
+
+	/// Constructs BaseFold parameters for a committed batch, choosing the number of test queries
+	/// for the target security level.
+	///
+	/// Unlike Reed–Solomon codes, random foldable codes are not maximum distance separable, so the
+	/// distance of the code cannot be derived from its rate. `relative_distance` must be a lower
+	/// bound on the relative minimum distance $\Delta$ of the code, such as the bound that [ZLT23]
+	/// proves to hold with high probability over the random diagonals for the chosen field and
+	/// rate. The number of queries follows the unique decoding analysis, where each query fails to
+	/// detect a codeword that is far from the code with probability $1 - \Delta / 2$.
+	///
+	/// [ZLT23]: <https://eprint.iacr.org/2023/1705>
+	pub fn with_security(
+		commit_meta: &CommitMeta,
+		log_inv_rate: usize,
+		relative_distance: f64,
+		security_bits: usize,
+		seed: u64,
+	) -> Result<Self, Error> {
+		if !(relative_distance > 0.0
+			&& relative_distance <= 1.0 - 2.0_f64.powi(-(log_inv_rate as i32)))
+		{
+			bail!(Error::InvalidBaseFoldParams(
+				"the relative distance must be positive and at most that of an MDS code of the same \
+				rate"
+					.to_string()
+			));
+		}
+
+		let log_msg_len = commit_meta.total_vars();
+		let field_size = 2.0_f64.powi(F::N_BITS as i32);
+		let sumcheck_err = (2 * log_msg_len) as f64 / field_size;
+		let folding_err = 2.0_f64.powi((log_msg_len + log_inv_rate) as i32) / field_size;
+		let per_query_err = 1.0 - relative_distance / 2.0;
+		let allowed_err = 2.0_f64.powi(-(security_bits as i32)) - sumcheck_err - folding_err;
+		if allowed_err <= 0.0 {
+			bail!(Error::InvalidBaseFoldParams(
+				"cannot attain the security target with the code parameters".to_string()
+			));
+		}
+		let n_test_queries = allowed_err.log(per_query_err).ceil() as usize;
+		Self::new(log_msg_len, log_inv_rate, n_test_queries, seed)
+	}
+
+	/// The binary logarithm of the length of the committed message.
+	pub const fn log_msg_len(&self) -> usize {
+		self.log_msg_len
+	}
+
+	/// The negative binary logarithm of the rate of the code.
+	pub const fn log_inv_rate(&self) -> usize {
+		self.log_inv_rate
+	}
+
+	/// The binary logarithm of the length of the codeword.
+	pub const fn log_len(&self) -> usize {
+		self.log_msg_len + self.log_inv_rate
+	}
+
+	/// The number of queries in the query phase.
+	pub const fn n_test_queries(&self) -> usize {
+		self.n_test_queries
+	}
+
+	/// The seed from which the random diagonals of the code are derived.
+	pub const fn seed(&self) -> u64 {
+		self.seed
+	}
+
+	/// Returns the entry at `index` of the random diagonal that combines the codewords of
+	/// dimension $2^{level - 1}$ into those of dimension $2^{level}$.
+	fn twiddle(&self, level: usize, index: usize) -> FEncode {
+		let mut rng_seed = [0u8; 32];
+		rng_seed[..8].copy_from_slice(&self.seed.to_le_bytes());
+		rng_seed[8..16].copy_from_slice(&(level as u64).to_le_bytes());
+		rng_seed[16..24].copy_from_slice(&(index as u64).to_le_bytes());
+		<FEncode as Field>::random(StdRng::from_seed(rng_seed))
+	}
+
+	/// Folds the pair of entries of a codeword of dimension $2^{level}$ at the given index.
+	fn fold_pair(&self, level: usize, index: usize, pair: [F; 2], challenge: F) -> F {
+		let [lo, hi] = pair;
+		// The pair is (A + t B, A + (t + 1) B) at the index of the codewords A and B.
+		let b = lo + hi;
+		let a = lo + b * self.twiddle(level, index);
+		a + challenge * (a + b)
+	}
+
+	/// Encodes a message with the random foldable code.
+	fn encode(&self, message: &[F]) -> Vec<F> {
+		debug_assert_eq!(message.len(), 1 << self.log_msg_len);
+
+		// The codewords of dimension one are the repetitions of the message entries.
+		let mut codewords = message
+			.iter()
+			.flat_map(|&value| iter::repeat_n(value, 1 << self.log_inv_rate))
+			.collect::<Vec<_>>();
+		for level in 1..=self.log_msg_len {
+			let log_len = level + self.log_inv_rate;
+			let twiddles = (0..1 << (log_len - 1))
+				.into_par_iter()
+				.map(|index| self.twiddle(level, index))
+				.collect::<Vec<_>>();
+
+			// The message entries of the codeword at index i among those of dimension 2^{level}
+			// are the entries of the codewords at indices i and i + n / 2 of the previous level.
+			let (evens, odds) = codewords.split_at(codewords.len() / 2);
+			let mut next = vec![F::ZERO; codewords.len()];
+			next.par_chunks_mut(1 << log_len)
+				.zip(evens.par_chunks(1 << (log_len - 1)))
+				.zip(odds.par_chunks(1 << (log_len - 1)))
+				.for_each(|((codeword, a), b)| {
+					for (pair, &a, &b, &twiddle) in
+						izip!(codeword.chunks_exact_mut(2), a, b, &twiddles)
+					{
+						let a_plus_tb = a + b * twiddle;
+						pair[0] = a_plus_tb;
+						pair[1] = a_plus_tb + b;
+					}
+				});
+			codewords = next;
+		}
+		codewords
+	}
+}
+
+/// Commits a batch of multilinear polynomials with BaseFold.
+///
+/// The batch is merged into one message as in [`super::commit`], so the multilinears must be in
+/// ascending order by the number of variables in the packed multilinear.
+#[instrument("piop::basefold::commit", skip_all, level = "debug")]
+pub fn commit<F, FEncode, P, M, MTScheme, MTProver>(
+	params: &BaseFoldParams<F, FEncode>,
+	merkle_prover: &MTProver,
+	multilins: &[M],
+) -> Result<CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P>,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	let packed_multilins = multilins
+		.iter()
+		.enumerate()
+		.map(|(i, unpacked_committed)| {
+			packed_committed(OracleId::from_index(i), unpacked_committed)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	if !is_sorted_ascending(packed_multilins.iter().map(|mle| mle.n_vars())) {
+		return Err(Error::CommittedsNotSorted);
+	}
+
+	let total_len = packed_multilins
+		.iter()
+		.map(|mle| 1usize << mle.n_vars())
+		.sum::<usize>();
+	if params.log_msg_len() < P::LOG_WIDTH || total_len > 1 << params.log_msg_len() {
+		bail!(Error::CommitParamsTooSmall);
+	}
+
+	let mut message = zeroed_vec::<P>(1 << (params.log_msg_len() - P::LOG_WIDTH));
+	merge_multilins(&packed_multilins, &mut message);
+
+	let codeword = params.encode(&PackedField::iter_slice(&message).collect::<Vec<_>>());
+	let (commitment, committed) = merkle_prover
+		.commit(&codeword, 2)
+		.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+
+	Ok(CommitOutput {
+		commitment: commitment.root,
+		committed,
+		codeword: codeword
+			.chunks(P::WIDTH)
+			.map(|chunk| P::from_scalars(chunk.iter().copied()))
+			.collect::<Vec<_>>()
+			.into(),
+	})
+}
+
+/// Proves a batch of sumcheck claims that are products of committed polynomials from a batch
+/// committed with [`commit`] and transparent polynomials.
+///
+/// This is the BaseFold counterpart of [`super::prove`], with the same arguments except for the
+/// NTT, which BaseFold does not use.
+#[allow(clippy::too_many_arguments)]
+#[instrument("piop::basefold::prove", skip_all, level = "debug")]
+pub fn prove<Hal, F, FEncode, P, M, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	params: &BaseFoldParams<F, FEncode>,
+	merkle_prover: &MTProver,
+	commit_meta: &CommitMeta,
+	committed: &MTProver::Committed,
+	codeword: &[P],
+	committed_multilins: &[M],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	check_params(params, commit_meta)?;
+	if codeword.len() << P::LOG_WIDTH != 1 << params.log_len() {
+		bail!(Error::InvalidBaseFoldParams(
+			"the codeword length does not match the code parameters".to_string()
+		));
+	}
+
+	let packed_committed_multilins = committed_multilins
+		.iter()
+		.enumerate()
+		.map(|(i, unpacked_committed)| {
+			packed_committed(OracleId::from_index(i), unpacked_committed)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let folder = BaseFoldFolder {
+		params,
+		merkle_prover,
+		codeword,
+		committed,
+		round_committed: Vec::with_capacity(params.log_msg_len() - 1),
+		final_value: None,
+	};
+	prove_packed_committed(
+		hal,
+		host_alloc,
+		dev_alloc,
+		commit_meta,
+		&packed_committed_multilins,
+		transparent_multilins,
+		claims,
+		folder,
+		transcript,
+		&(),
+	)
+}
+
+/// Verifies a batch of sumcheck claims proven with [`prove`].
+///
+/// This is the BaseFold counterpart of [`super::verify`].
+#[instrument("piop::basefold::verify", skip_all)]
+pub fn verify<'a, F, FEncode, Challenger_, MTScheme>(
+	commit_meta: &CommitMeta,
+	merkle_scheme: &MTScheme,
+	params: &BaseFoldParams<F, FEncode>,
+	commitment: &MTScheme::Digest,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	Challenger_: Challenger,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+{
+	check_params(params, commit_meta)?;
+
+	let verifier = BaseFoldVerifier {
+		params,
+		merkle_scheme,
+		commitments: vec![commitment.clone()],
+	};
+	verify_with_folder(commit_meta, transparents, claims, verifier, transcript, &())
+}
+
+fn check_params<F, FEncode>(
+	params: &BaseFoldParams<F, FEncode>,
+	commit_meta: &CommitMeta,
+) -> Result<(), Error>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	if params.log_msg_len() != commit_meta.total_vars() {
+		bail!(Error::CommitParamsMismatch {
+			expected: commit_meta.total_vars(),
+			actual: params.log_msg_len(),
+		});
+	}
+	Ok(())
+}
+
+/// Samples the indices of the queried leaves of the committed codeword, in ascending order and
+/// without duplicates.
+fn sample_query_indices<F, FEncode>(
+	params: &BaseFoldParams<F, FEncode>,
+	sampler: &mut impl CanSampleBits<u32>,
+) -> Vec<usize>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	let mut indices = (0..params.n_test_queries)
+		.map(|_| sampler.sample_bits(params.log_len() - 1) as usize)
+		.collect::<Vec<_>>();
+	indices.sort_unstable();
+	indices.dedup();
+	indices
+}
+
+/// Returns the indices of the leaves of the oracle committed after `oracle` fold rounds that the
+/// queries open.
+fn oracle_indices(indices: &[usize], oracle: usize) -> Vec<usize> {
+	let mut oracle_indices = indices
+		.iter()
+		.map(|index| index >> oracle)
+		.collect::<Vec<_>>();
+	oracle_indices.dedup();
+	oracle_indices
+}
+
+/// The prover of the BaseFold opening, which commits to every folded codeword.
+struct BaseFoldFolder<'a, F, FEncode, P, MTProver>
+where
+	F: BinaryField,
+	MTProver: MerkleTreeProver<F>,
+{
+	params: &'a BaseFoldParams<F, FEncode>,
+	merkle_prover: &'a MTProver,
+	codeword: &'a [P],
+	committed: &'a MTProver::Committed,
+	round_committed: Vec<(Vec<F>, MTProver::Committed)>,
+	/// The evaluation of the message, once all fold rounds are executed.
+	final_value: Option<F>,
+}
+
+impl<F, FEncode, P, MTScheme, MTProver> BaseFoldFolder<'_, F, FEncode, P, MTProver>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	/// Writes the openings of the leaves at the given indices of an oracle.
+	fn prove_openings<B: BufMut>(
+		&self,
+		oracle: usize,
+		indices: &[usize],
+		advice: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		let committed = match oracle {
+			0 => self.committed,
+			_ => &self.round_committed[oracle - 1].1,
+		};
+		let tree_depth = self.params.log_len() - oracle - 1;
+		let layer_depth = self
+			.merkle_prover
+			.scheme()
+			.optimal_verify_layer(self.params.n_test_queries(), tree_depth);
+		let layer = self
+			.merkle_prover
+			.layer(committed, layer_depth)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		advice.write_slice(layer);
+
+		for &index in indices {
+			match oracle {
+				0 => advice.write_scalar_iter(
+					iter_packed_slice_with_offset(self.codeword, 2 * index).take(2),
+				),
+				_ => {
+					advice.write_scalar_slice(&self.round_committed[oracle - 1].0[2 * index..][..2])
+				}
+			}
+		}
+		self.merkle_prover
+			.prove_openings(committed, layer_depth, indices, advice)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))
+	}
+}
+
+impl<F, FEncode, P, MTScheme, MTProver> InterleavedFolder<F>
+	for BaseFoldFolder<'_, F, FEncode, P, MTProver>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	type Digest = MTScheme::Digest;
+
+	fn log_batch_size(&self) -> usize {
+		0
+	}
+
+	fn current_codeword_len(&self) -> usize {
+		match self.round_committed.last() {
+			Some((codeword, _)) => codeword.len(),
+			None => self.codeword.len() << P::LOG_WIDTH,
+		}
+	}
+
+	fn execute_fold_round(&mut self, challenge: F) -> Result<FoldRoundOutput<Self::Digest>, Error> {
+		let level = self.params.log_msg_len() - self.round_committed.len();
+		if self.final_value.is_some() {
+			bail!(Error::InvalidBaseFoldParams(
+				"attempted to fold more times than the message has variables".to_string()
+			));
+		}
+
+		let params = self.params;
+		let folded_len = 1 << (level - 1 + params.log_inv_rate());
+		let folded = match self.round_committed.last() {
+			Some((codeword, _)) => (0..folded_len)
+				.into_par_iter()
+				.map(|index| {
+					let pair = [codeword[2 * index], codeword[2 * index + 1]];
+					params.fold_pair(level, index, pair, challenge)
+				})
+				.collect::<Vec<_>>(),
+			None => (0..folded_len)
+				.into_par_iter()
+				.map(|index| {
+					let pair = [
+						get_packed_slice(self.codeword, 2 * index),
+						get_packed_slice(self.codeword, 2 * index + 1),
+					];
+					params.fold_pair(level, index, pair, challenge)
+				})
+				.collect::<Vec<_>>(),
+		};
+
+		// The codeword of dimension one repeats the evaluation of the message, which is sent
+		// instead of a commitment.
+		if level == 1 {
+			self.final_value = Some(folded[0]);
+			return Ok(FoldRoundOutput::NoCommitment);
+		}
+
+		let (commitment, committed) = self
+			.merkle_prover
+			.commit(&folded, 2)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		self.round_committed.push((folded, committed));
+		Ok(FoldRoundOutput::Commitment(commitment.root))
+	}
+
+	fn finish_proof<Challenger_: Challenger>(
+		self,
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error> {
+		let Some(final_value) = self.final_value else {
+			bail!(Error::InvalidBaseFoldParams(
+				"attempted to finish the proof before executing all fold rounds".to_string()
+			));
+		};
+		transcript.message().write_scalar(final_value);
+
+		let indices = sample_query_indices(self.params, transcript);
+		let mut advice = transcript.decommitment();
+		for oracle in 0..self.params.log_msg_len() {
+			self.prove_openings(oracle, &oracle_indices(&indices, oracle), &mut advice)?;
+		}
+		Ok(())
+	}
+}
+
+/// The verifier of the BaseFold opening.
+struct BaseFoldVerifier<'a, F, FEncode, MTScheme>
+where
+	F: BinaryField,
+	MTScheme: MerkleTreeScheme<F>,
+{
+	params: &'a BaseFoldParams<F, FEncode>,
+	merkle_scheme: &'a MTScheme,
+	/// The commitments to the committed codeword and to the folded codewords read so far.
+	commitments: Vec<MTScheme::Digest>,
+}
+
+impl<F, FEncode, MTScheme> BaseFoldVerifier<'_, F, FEncode, MTScheme>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+{
+	/// Reads the openings of the leaves at the given indices of an oracle and verifies them
+	/// against its commitment, returning the values of the leaves.
+	fn read_and_verify_openings<B: Buf>(
+		&self,
+		oracle: usize,
+		indices: &[usize],
+		advice: &mut TranscriptReader<B>,
+	) -> Result<Vec<[F; 2]>, Error> {
+		let tree_depth = self.params.log_len() - oracle - 1;
+		let layer_depth = self
+			.merkle_scheme
+			.optimal_verify_layer(self.params.n_test_queries(), tree_depth);
+		let layer = advice
+			.read_vec(1 << layer_depth)
+			.map_err(VerificationError::Transcript)?;
+		self.merkle_scheme
+			.verify_layer(&self.commitments[oracle], layer_depth, &layer)
+			.map_err(|source| VerificationError::InvalidBaseFoldOpening { oracle, source })?;
+
+		let openings = indices
+			.iter()
+			.map(|_| {
+				let values = advice
+					.read_scalar_slice::<F>(2)
+					.map_err(VerificationError::Transcript)?;
+				Ok([values[0], values[1]])
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		let values = openings
+			.iter()
+			.map(|pair| pair.as_slice())
+			.collect::<Vec<_>>();
+		self.merkle_scheme
+			.verify_openings(indices, &values, layer_depth, tree_depth, &layer, advice)
+			.map_err(|source| VerificationError::InvalidBaseFoldOpening { oracle, source })?;
+
+		Ok(openings)
+	}
+}
+
+impl<F, FEncode, MTScheme> InterleavedFoldVerifier<F> for BaseFoldVerifier<'_, F, FEncode, MTScheme>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+{
+	fn observe_fold_round<Challenger_: Challenger>(
+		&mut self,
+		round: usize,
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Error> {
+		// Every fold but the last is committed.
+		if round + 1 < self.params.log_msg_len() {
+			let commitment = transcript
+				.message()
+				.read()
+				.map_err(VerificationError::Transcript)?;
+			self.commitments.push(commitment);
+		}
+		Ok(())
+	}
+
+	fn verify<Challenger_: Challenger>(
+		self,
+		challenges: &[F],
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<F, Error> {
+		let n_oracles = self.params.log_msg_len();
+		assert_eq!(challenges.len(), n_oracles);
+
+		let final_value = transcript
+			.message()
+			.read_scalar::<F>()
+			.map_err(VerificationError::Transcript)?;
+
+		let indices = sample_query_indices(self.params, transcript);
+		let mut advice = transcript.decommitment();
+		let openings = (0..n_oracles)
+			.map(|oracle| {
+				let oracle_indices = oracle_indices(&indices, oracle);
+				let openings =
+					self.read_and_verify_openings(oracle, &oracle_indices, &mut advice)?;
+				Ok((oracle_indices, openings))
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		for &query_index in &indices {
+			let mut folded = None;
+			for (oracle, ((oracle_indices, openings), &challenge)) in
+				iter::zip(&openings, challenges).enumerate()
+			{
+				let index = query_index >> oracle;
+				let position = oracle_indices
+					.binary_search(&index)
+					.map_err(|_| Error::ParameterError)?;
+				let pair = openings[position];
+				if let Some(folded) = folded {
+					let opened = pair[(query_index >> (oracle - 1)) & 1];
+					if opened != folded {
+						bail!(VerificationError::IncorrectBaseFoldFold {
+							query_index,
+							oracle
+						});
+					}
+				}
+				let level = n_oracles - oracle;
+				folded = Some(self.params.fold_pair(level, index, pair, challenge));
+			}
+			if folded != Some(final_value) {
+				bail!(VerificationError::IncorrectBaseFoldFold {
+					query_index,
+					oracle: n_oracles,
+				});
+			}
+		}
+
+		Ok(final_value)
+	}
+}
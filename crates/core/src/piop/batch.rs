@@ -0,0 +1,334 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::Field;
+use binius_utils::{bail, sorting::is_sorted_ascending};
+
+use super::{
+	error::Error,
+	verify::{CommitMeta, PIOPSumcheckClaim},
+};
+
+/// Metadata about a single commitment to the committed multilinears of several independent
+/// constraint systems.
+///
+/// Each constraint system indexes its committed multilinears according to its own [`CommitMeta`].
+/// The joint batch interleaves them so that the multilinears remain in ascending order by number
+/// of variables. Multilinears with the same number of variables are ordered first by the index of
+/// their constraint system and then by their index within that system. The committed
+/// multilinears merged with [`Self::merge_committed`] are committed by [`super::commit`] with a
+/// single Merkle root and FRI instance, and the sumcheck claims of all systems merged with
+/// [`Self::merge_claims`] are proven and verified together by [`super::prove`] and
+/// [`super::verify`].
+#[derive(Debug)]
+pub struct BatchCommitMeta {
+	commit_meta: CommitMeta,
+	/// For each constraint system, maps the committed indices of that system to indices in the
+	/// joint batch.
+	committed_index_maps: Vec<Vec<usize>>,
+}
+
+impl BatchCommitMeta {
+	/// Constructs a new [`BatchCommitMeta`] from the commit metadata of each constraint system.
+	pub fn new(commit_metas: &[CommitMeta]) -> Self {
+		let n_vars_bound = commit_metas
+			.iter()
+			.map(|meta| meta.n_multilins_by_vars().len())
+			.max()
+			.unwrap_or(0);
+		let n_multilins_by_vars = (0..n_vars_bound)
+			.map(|n_vars| {
+				commit_metas
+					.iter()
+					.map(|meta| meta.n_multilins_by_vars().get(n_vars).copied().unwrap_or(0))
+					.sum()
+			})
+			.collect();
+		let mut commit_meta = CommitMeta::new(n_multilins_by_vars);
+
+		// The next unassigned joint index for each number of variables.
+		let mut next_indices = (0..n_vars_bound)
+			.map(|n_vars| commit_meta.range_by_vars(n_vars).start)
+			.collect::<Vec<_>>();
+		let committed_index_maps = commit_metas
+			.iter()
+			.map(|meta| {
+				meta.n_multilins_by_vars()
+					.iter()
+					.enumerate()
+					.flat_map(|(n_vars, &count)| {
+						let start = next_indices[n_vars];
+						next_indices[n_vars] += count;
+						start..start + count
+					})
+					.collect()
+			})
+			.collect::<Vec<Vec<_>>>();
+
+		// The joint batch records tower levels only if every constraint system does.
+		if let Some(tower_levels_by_system) = commit_metas
+			.iter()
+			.map(CommitMeta::tower_levels)
+			.collect::<Option<Vec<_>>>()
+		{
+			let mut tower_levels = vec![0; commit_meta.total_multilins()];
+			for (system_tower_levels, index_map) in tower_levels_by_system
+				.into_iter()
+				.zip(&committed_index_maps)
+			{
+				for (&tower_level, &index) in system_tower_levels.iter().zip(index_map) {
+					tower_levels[index] = tower_level;
+				}
+			}
+			commit_meta = commit_meta
+				.with_tower_levels(tower_levels)
+				.expect("the index maps cover every joint committed index");
+		}
+
+		Self {
+			commit_meta,
+			committed_index_maps,
+		}
+	}
+
+	/// Returns the metadata of the joint committed batch.
+	pub fn commit_meta(&self) -> &CommitMeta {
+		&self.commit_meta
+	}
+
+	/// Returns the number of constraint systems in the batch.
+	pub fn n_systems(&self) -> usize {
+		self.committed_index_maps.len()
+	}
+
+	/// Returns the index in the joint batch of a committed multilinear of a constraint system.
+	pub fn committed_index(&self, system: usize, committed: usize) -> Result<usize, Error> {
+		let index_map = self.index_map(system)?;
+		index_map
+			.get(committed)
+			.copied()
+			.ok_or(Error::InvalidCommittedId {
+				max_index: index_map.len(),
+			})
+	}
+
+	/// Merges the committed multilinears of each constraint system into the joint commitment
+	/// order.
+	///
+	/// The result is suitable as the committed multilinears argument of [`super::commit`] and
+	/// [`super::prove`].
+	pub fn merge_committed<T: Clone>(
+		&self,
+		committed_by_system: &[impl AsRef<[T]>],
+	) -> Result<Vec<T>, Error> {
+		self.check_n_systems(committed_by_system.len())?;
+
+		let mut merged = vec![None; self.commit_meta.total_multilins()];
+		for (system, (committed, index_map)) in committed_by_system
+			.iter()
+			.zip(&self.committed_index_maps)
+			.enumerate()
+		{
+			let committed = committed.as_ref();
+			if committed.len() != index_map.len() {
+				bail!(Error::IncorrectNumberOfCommitted {
+					system,
+					expected: index_map.len(),
+					actual: committed.len(),
+				});
+			}
+			for (item, &index) in committed.iter().zip(index_map) {
+				merged[index] = Some(item.clone());
+			}
+		}
+		Ok(merged
+			.into_iter()
+			.map(|item| item.expect("index maps are a bijection onto the joint batch"))
+			.collect())
+	}
+
+	/// Merges the transparent polynomials and sumcheck claims of each constraint system into
+	/// inputs for a joint PIOP invocation.
+	///
+	/// The transparents of each system must be in ascending order by number of variables. The
+	/// merged transparents are ordered in the same way as the committed multilinears, and the
+	/// claims have their committed and transparent indices remapped to the joint batch.
+	///
+	/// ## Arguments
+	///
+	/// * `transparents_by_system` - the transparent polynomials of each constraint system
+	/// * `claims_by_system` - the sumcheck claims of each constraint system, referencing the
+	///   committed and transparent polynomials of that system
+	/// * `n_vars` - returns the number of variables of a transparent polynomial
+	pub fn merge_claims<F: Field, T: Clone>(
+		&self,
+		transparents_by_system: &[impl AsRef<[T]>],
+		claims_by_system: &[impl AsRef<[PIOPSumcheckClaim<F>]>],
+		n_vars: impl Fn(&T) -> usize,
+	) -> Result<(Vec<T>, Vec<PIOPSumcheckClaim<F>>), Error> {
+		self.check_n_systems(transparents_by_system.len())?;
+		self.check_n_systems(claims_by_system.len())?;
+
+		let mut keyed_transparents = Vec::new();
+		let mut transparent_index_maps = Vec::with_capacity(transparents_by_system.len());
+		for (system, transparents) in transparents_by_system.iter().enumerate() {
+			let transparents = transparents.as_ref();
+			if !is_sorted_ascending(transparents.iter().map(&n_vars)) {
+				bail!(Error::TransparentsNotSorted);
+			}
+			for (index, transparent) in transparents.iter().enumerate() {
+				keyed_transparents.push((n_vars(transparent), system, index, transparent));
+			}
+			transparent_index_maps.push(vec![0; transparents.len()]);
+		}
+
+		// Sorting by (n_vars, system, index) matches the ordering of the committed multilinears.
+		keyed_transparents
+			.sort_unstable_by_key(|&(n_vars, system, index, _)| (n_vars, system, index));
+		let merged_transparents = keyed_transparents
+			.into_iter()
+			.enumerate()
+			.map(|(joint_index, (_, system, index, transparent))| {
+				transparent_index_maps[system][index] = joint_index;
+				transparent.clone()
+			})
+			.collect();
+
+		let merged_claims = claims_by_system
+			.iter()
+			.zip(&transparent_index_maps)
+			.enumerate()
+			.flat_map(|(system, (claims, transparent_index_map))| {
+				claims.as_ref().iter().map(move |claim| {
+					let committed = self.committed_index(system, claim.committed)?;
+					let transparent = transparent_index_map
+						.get(claim.transparent)
+						.copied()
+						.ok_or(Error::InvalidTransparentId {
+							max_index: transparent_index_map.len(),
+						})?;
+					Ok(PIOPSumcheckClaim {
+						n_vars: claim.n_vars,
+						committed,
+						transparent,
+						sum: claim.sum,
+					})
+				})
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		Ok((merged_transparents, merged_claims))
+	}
+
+	/// Returns the indices in the joint batch of the committed multilinears of a constraint system.
+	pub(super) fn index_map(&self, system: usize) -> Result<&[usize], Error> {
+		self.committed_index_maps
+			.get(system)
+			.map(Vec::as_slice)
+			.ok_or(Error::InvalidSystemIndex {
+				system,
+				n_systems: self.n_systems(),
+			})
+	}
+
+	fn check_n_systems(&self, n_systems: usize) -> Result<(), Error> {
+		if n_systems != self.n_systems() {
+			bail!(Error::IncorrectNumberOfSystems {
+				expected: self.n_systems(),
+				actual: n_systems,
+			});
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::BinaryField128b;
+
+	use super::*;
+
+	#[test]
+	fn test_batch_commit_meta_index_maps() {
+		let batch_meta = BatchCommitMeta::new(&[
+			CommitMeta::with_vars([4, 6, 6]),
+			CommitMeta::with_vars([4, 5]),
+		]);
+
+		assert_eq!(batch_meta.commit_meta().n_multilins_by_vars(), &[0, 0, 0, 0, 2, 1, 2]);
+		assert_eq!(batch_meta.n_systems(), 2);
+
+		let joint_indices = |system, count| {
+			(0..count)
+				.map(|i| batch_meta.committed_index(system, i).unwrap())
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(joint_indices(0, 3), vec![0, 3, 4]);
+		assert_eq!(joint_indices(1, 2), vec![1, 2]);
+		assert!(batch_meta.committed_index(1, 2).is_err());
+		assert!(matches!(
+			batch_meta.committed_index(2, 0),
+			Err(Error::InvalidSystemIndex {
+				system: 2,
+				n_systems: 2
+			})
+		));
+
+		let merged = batch_meta
+			.merge_committed(&[vec!["a0", "a1", "a2"], vec!["b0", "b1"]])
+			.unwrap();
+		assert_eq!(merged, vec!["a0", "b0", "b1", "a1", "a2"]);
+		assert_eq!(batch_meta.commit_meta().tower_levels(), None);
+
+		let batch_meta = BatchCommitMeta::new(&[
+			CommitMeta::with_vars([4, 6, 6])
+				.with_tower_levels(vec![0, 3, 5])
+				.unwrap(),
+			CommitMeta::with_vars([4, 5])
+				.with_tower_levels(vec![7, 1])
+				.unwrap(),
+		]);
+		assert_eq!(batch_meta.commit_meta().tower_levels(), Some(&[0, 7, 1, 3, 5][..]));
+	}
+
+	#[test]
+	fn test_batch_commit_meta_merge_claims() {
+		let batch_meta =
+			BatchCommitMeta::new(&[CommitMeta::with_vars([4, 6]), CommitMeta::with_vars([4, 5])]);
+
+		let claim = |n_vars, committed, transparent| PIOPSumcheckClaim {
+			n_vars,
+			committed,
+			transparent,
+			sum: BinaryField128b::ONE,
+		};
+		let (transparents, claims) = batch_meta
+			.merge_claims(
+				&[vec![4, 6], vec![4, 5]],
+				&[
+					vec![claim(4, 0, 0), claim(6, 1, 1)],
+					vec![claim(4, 0, 0), claim(5, 1, 1)],
+				],
+				|&n_vars| n_vars,
+			)
+			.unwrap();
+		assert_eq!(transparents, vec![4, 4, 5, 6]);
+		assert_eq!(
+			claims,
+			vec![
+				claim(4, 0, 0),
+				claim(6, 3, 3),
+				claim(4, 1, 1),
+				claim(5, 2, 2)
+			]
+		);
+
+		assert!(matches!(
+			batch_meta.merge_claims::<BinaryField128b, _>(
+				&[vec![6, 4], vec![4, 5]],
+				&[vec![], vec![]],
+				|&n_vars| n_vars
+			),
+			Err(Error::TransparentsNotSorted)
+		));
+	}
+}
@@ -1,5 +1,7 @@
 // Copyright 2024-2025 Irreducible Inc.
 
+use std::iter;
+
 use binius_field::{
 	TowerField,
 	as_packed_field::{PackScalar, PackedType},
@@ -7,9 +9,14 @@ use binius_field::{
 };
 use binius_utils::sparse_index::SparseIndex;
 
-use super::{error::Error, util::ResizeableIndex, verify::CommitMeta};
+use super::{
+	error::Error, rate_batches::RateBatchedCommitMeta, util::ResizeableIndex, verify::CommitMeta,
+};
 use crate::{
-	oracle::{MultilinearOracleSet, MultilinearPolyOracle, MultilinearPolyVariant},
+	oracle::{
+		MultilinearOracleSet, MultilinearPolyOracle, MultilinearPolyVariant, OracleId,
+		OracleIdRemapping,
+	},
 	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
 
@@ -36,6 +43,7 @@ pub fn make_oracle_commit_meta<F: TowerField>(
 	struct CommitIDFirstPass {
 		n_packed_vars: usize,
 		idx_in_bucket: usize,
+		tower_level: usize,
 	}
 
 	// First pass: count the number of multilinears and index within buckets
@@ -51,6 +59,7 @@ pub fn make_oracle_commit_meta<F: TowerField>(
 				CommitIDFirstPass {
 					n_packed_vars,
 					idx_in_bucket: *n_multilins_for_vars,
+					tower_level: oracle.binary_tower_level(),
 				},
 			);
 			*n_multilins_for_vars += 1;
@@ -61,18 +70,75 @@ pub fn make_oracle_commit_meta<F: TowerField>(
 
 	// Second pass: use commit_meta counts to finalized indices with offsets
 	let mut index = SparseIndex::with_capacity(oracles.size());
+	let mut tower_levels = vec![0; commit_meta.total_multilins()];
 	for id in 0..oracles.size() {
 		if let Some(CommitIDFirstPass {
 			n_packed_vars,
 			idx_in_bucket,
+			tower_level,
 		}) = first_pass_index.get(id)
 		{
 			let offset = commit_meta.range_by_vars(*n_packed_vars).start;
 			index.set(id, offset + *idx_in_bucket);
+			tower_levels[offset + *idx_in_bucket] = *tower_level;
 		}
 	}
 
-	Ok((commit_meta, index))
+	Ok((commit_meta.with_tower_levels(tower_levels)?, index))
+}
+
+/// Indexes the committed oracles in a [`MultilinearOracleSet`] like [`make_oracle_commit_meta`],
+/// splitting them into one commitment for each Reed–Solomon rate.
+///
+/// Small auxiliary columns can afford a low rate, which needs fewer FRI queries and so shrinks the
+/// proof, while the largest columns dominate the prover cost and are better encoded at a high
+/// rate. The oracles are grouped into rate batches by the binary logarithm of the inverse rate
+/// returned by `log_inv_rate` for each committed oracle, as described by [`RateBatchedCommitMeta`].
+/// The returned index maps oracle IDs to joint committed IDs, which are the same as those
+/// returned by [`make_oracle_commit_meta`]. The FRI parameters of the rate batches are chosen with
+/// [`super::CommitParamsBuilder::build_rate_batches`].
+pub fn make_oracle_rate_batched_commit_meta<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	log_inv_rate: impl Fn(&MultilinearPolyOracle<F>) -> usize,
+) -> Result<(RateBatchedCommitMeta, SparseIndex<usize>), Error> {
+	let (commit_meta, index) = make_oracle_commit_meta(oracles)?;
+	let mut log_inv_rates = vec![0; commit_meta.total_multilins()];
+	for (id, &committed) in index.iter() {
+		log_inv_rates[committed] = log_inv_rate(&oracles[OracleId::from_index(id)]);
+	}
+	let rate_batched_meta = RateBatchedCommitMeta::new(commit_meta, &log_inv_rates)?;
+	Ok((rate_batched_meta, index))
+}
+
+/// Translates committed IDs across a renumbering of the oracles.
+///
+/// Given the indices from oracle IDs to committed IDs returned by [`make_oracle_commit_meta`]
+/// for an oracle set before and after a renumbering, returns the new committed ID of each old
+/// committed ID. The committed IDs may change because they are ordered by oracle ID within each
+/// number of variables.
+pub fn remap_committed_ids(
+	old_index: &SparseIndex<usize>,
+	new_index: &SparseIndex<usize>,
+	remapping: &OracleIdRemapping,
+) -> Result<Vec<usize>, Error> {
+	let mut committed_ids = vec![None; old_index.len()];
+	for (id, &old_committed) in old_index.iter() {
+		let new_id = remapping.remap(OracleId::from_index(id))?;
+		let new_committed = new_index
+			.get(new_id.index())
+			.copied()
+			.ok_or(Error::OracleToCommitIndexMissingEntry { id: new_id })?;
+		*committed_ids
+			.get_mut(old_committed)
+			.ok_or(Error::InvalidCommittedId {
+				max_index: old_index.len(),
+			})? = Some(new_committed);
+	}
+	committed_ids
+		.into_iter()
+		.enumerate()
+		.map(|(index, id)| id.ok_or(Error::CommittedMissing { index }))
+		.collect()
 }
 
 /// Collects the committed multilinear witnesses from the witness index and returns them in order.
@@ -107,6 +173,40 @@ where
 		.collect())
 }
 
+/// Collects the chunked committed multilinear witnesses of an oracle set and returns them in
+/// order.
+///
+/// This is the streaming counterpart of [`collect_committed_witnesses`], for witnesses that are
+/// not held in a witness index. `witness_chunks` returns, for a committed oracle, an iterator over
+/// the chunks of its packed evaluations, which is not consumed here. The iterators are returned in
+/// the commitment order, for [`super::commit_streaming`] to read one chunk at a time.
+///
+/// ## Preconditions
+///
+/// * `oracle_to_commit_index` must be correctly constructed, as in [`collect_committed_witnesses`].
+pub fn collect_committed_witness_chunks<F, I>(
+	commit_meta: &CommitMeta,
+	oracle_to_commit_index: &SparseIndex<usize>,
+	oracles: &MultilinearOracleSet<F>,
+	mut witness_chunks: impl FnMut(OracleId) -> Result<I, Error>,
+) -> Result<Vec<I>, Error>
+where
+	F: TowerField,
+{
+	let mut witnesses = iter::repeat_with(|| None)
+		.take(commit_meta.total_multilins())
+		.collect::<Vec<_>>();
+	for oracle_id in oracles.ids() {
+		if let Some(commit_idx) = oracle_to_commit_index.get(oracle_id.index()) {
+			witnesses[*commit_idx] = Some(witness_chunks(oracle_id)?);
+		}
+	}
+	Ok(witnesses
+		.into_iter()
+		.map(|witness| witness.expect("pre-condition: oracle_to_commit index is surjective"))
+		.collect())
+}
+
 fn n_packed_vars_for_committed_oracle<F: TowerField>(oracle: &MultilinearPolyOracle<F>) -> usize {
 	let n_vars = oracle.n_vars();
 	let tower_level = oracle.binary_tower_level();
@@ -148,5 +248,32 @@ mod tests {
 		assert_eq!(index.get(batch_2_2_ids[0].index()).copied(), Some(10));
 		assert_eq!(index.get(batch_2_2_ids[1].index()).copied(), Some(11));
 		assert_eq!(index.get(repeat.index()).copied(), None);
+		assert_eq!(commit_meta.tower_levels(), Some(&[0, 0, 0, 0, 2, 2, 0, 0, 2, 2, 2, 2][..]));
+	}
+
+	#[test]
+	fn test_make_oracle_rate_batched_commit_meta() {
+		let mut oracles = MultilinearOracleSet::<BinaryField128b>::new();
+
+		let aux_ids = oracles.add_committed_multiple::<2>(8, 0);
+		let trace_ids = oracles.add_committed_multiple::<2>(12, 0);
+
+		// The auxiliary columns are encoded at rate 1/8, and the trace at rate 1/2.
+		let (rate_batched_meta, index) = make_oracle_rate_batched_commit_meta(&oracles, |oracle| {
+			if oracle.n_vars() < 12 { 3 } else { 1 }
+		})
+		.unwrap();
+		assert_eq!(rate_batched_meta.log_inv_rates().collect::<Vec<_>>(), vec![1, 3]);
+
+		let (commit_meta, single_rate_index) = make_oracle_commit_meta(&oracles).unwrap();
+		assert_eq!(
+			rate_batched_meta.commit_meta().n_multilins_by_vars(),
+			commit_meta.n_multilins_by_vars()
+		);
+		for id in aux_ids.iter().chain(&trace_ids) {
+			assert_eq!(index.get(id.index()), single_rate_index.get(id.index()));
+		}
+		assert_eq!(rate_batched_meta.committed_location(0).unwrap(), (1, 0));
+		assert_eq!(rate_batched_meta.committed_location(3).unwrap(), (0, 1));
 	}
 }
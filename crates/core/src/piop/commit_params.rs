@@ -0,0 +1,475 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::iter;
+
+use binius_field::{BinaryField, ExtensionField};
+use binius_ntt::{AdditiveNTT, SingleThreadedNTT};
+use binius_utils::bail;
+
+use super::{
+	error::Error,
+	rate_batches::RateBatchedCommitMeta,
+	verify::{CommitMeta, hiding_commit_meta},
+};
+use crate::{
+	merkle_tree::MerkleTreeScheme,
+	protocols::fri::{
+		self, FRICostModel, FRIParams, FRIVerifierCostModel, SoundnessModel,
+		calculate_n_test_queries, calculate_n_test_queries_with_soundness_model,
+		estimate_optimal_arity, estimate_optimal_arity_with_cost_model,
+	},
+	reed_solomon::reed_solomon::ReedSolomonCode,
+};
+
+/// How a [`CommitParamsBuilder`] chooses the FRI folding arities.
+#[derive(Debug, Clone)]
+enum FoldArities<'a> {
+	/// The constant arity that minimizes the estimated proof size.
+	OptimalConstant,
+	/// The per-round schedule that minimizes the estimated proof size, interleaving
+	/// `2^log_batch_size` codewords, or as many as the first arity folds if not given.
+	OptimalSchedule { log_batch_size: Option<usize> },
+	/// The constant arity that minimizes the estimated cost under a machine-calibrated cost model.
+	CostModel(&'a FRICostModel),
+	/// The constant arity that minimizes the verifier's cost under a cost model.
+	VerifierCostModel(&'a FRIVerifierCostModel),
+	/// An explicit schedule.
+	Schedule {
+		log_batch_size: usize,
+		fold_arities: Vec<usize>,
+	},
+}
+
+/// Chooses the FRI parameters of a committed batch.
+///
+/// The builder is configured with the protocol parameters, such as the security target, the code
+/// rate and the soundness analysis, and with the strategy for choosing the folding arities. The
+/// parameters of a batch are then chosen with [`Self::build`]. By default, the code has inverse
+/// rate 2, the test queries are sized with [`SoundnessModel::Provable`] without proof-of-work
+/// grinding, and every round folds by the constant arity that minimizes the estimated proof size.
+///
+/// The Merkle tree scheme determines the size of the digests in the proof, and the Merkle layers
+/// the verifier opens when the arities are chosen with [`Self::with_verifier_cost_model`].
+#[derive(Debug, Clone)]
+pub struct CommitParamsBuilder<'a, MTScheme> {
+	merkle_scheme: &'a MTScheme,
+	security_bits: usize,
+	log_inv_rate: usize,
+	soundness_model: SoundnessModel,
+	grinding_bits: usize,
+	fold_arities: FoldArities<'a>,
+	hiding: bool,
+}
+
+impl<'a, MTScheme> CommitParamsBuilder<'a, MTScheme> {
+	/// Starts choosing the parameters of commitments with the Merkle tree scheme `merkle_scheme`
+	/// that achieve `security_bits` bits of security.
+	pub const fn new(merkle_scheme: &'a MTScheme, security_bits: usize) -> Self {
+		Self {
+			merkle_scheme,
+			security_bits,
+			log_inv_rate: 1,
+			soundness_model: SoundnessModel::Provable,
+			grinding_bits: 0,
+			fold_arities: FoldArities::OptimalConstant,
+			hiding: false,
+		}
+	}
+
+	/// The Merkle tree scheme of the commitments.
+	pub const fn merkle_scheme(&self) -> &'a MTScheme {
+		self.merkle_scheme
+	}
+
+	/// Sets the binary logarithm of the inverse Reed–Solomon code rate.
+	pub const fn with_log_inv_rate(mut self, log_inv_rate: usize) -> Self {
+		self.log_inv_rate = log_inv_rate;
+		self
+	}
+
+	/// Sets the soundness analysis that sizes the number of test queries, either the provable
+	/// bound or the more aggressive list-decoding conjecture.
+	pub const fn with_soundness_model(mut self, soundness_model: SoundnessModel) -> Self {
+		self.soundness_model = soundness_model;
+		self
+	}
+
+	/// Sets the number of leading zero bits of the proof-of-work that the prover grinds before the
+	/// FRI query phase.
+	///
+	/// Every bit of grinding halves the soundness error that the test queries must achieve, so
+	/// fewer queries are needed for the security target, as with [`FRIParams::with_grinding`].
+	pub const fn with_grinding_bits(mut self, grinding_bits: usize) -> Self {
+		self.grinding_bits = grinding_bits;
+		self
+	}
+
+	/// Chooses the per-round folding schedule that minimizes the estimated proof size.
+	///
+	/// Where the default folds by the same arity in every round, this chooses the schedule with
+	/// [`fri::estimate_optimal_arity_schedule`], which typically folds the long early oracles by
+	/// more variables than the short late ones. The first arity is also the interleaved batch size.
+	pub fn with_optimal_arity_schedule(mut self) -> Self {
+		self.fold_arities = FoldArities::OptimalSchedule {
+			log_batch_size: None,
+		};
+		self
+	}
+
+	/// Chooses an explicit interleaved batch size and the folding schedule that minimizes the
+	/// estimated proof size for it.
+	///
+	/// Where [`Self::with_optimal_arity_schedule`] takes the batch size to be the first arity, this
+	/// trades the cost of encoding a longer code against the larger leaves and shallower Merkle
+	/// tree of the first oracle. The number of test queries is calculated for the code of dimension
+	/// `commit_meta.total_vars() - log_batch_size`, and the first arity of the schedule is raised
+	/// to the batch size if it is smaller.
+	pub const fn with_log_batch_size(mut self, log_batch_size: usize) -> Self {
+		self.fold_arities = FoldArities::OptimalSchedule {
+			log_batch_size: Some(log_batch_size),
+		};
+		self
+	}
+
+	/// Chooses the constant folding arity that minimizes the estimated cost under a
+	/// machine-calibrated [`FRICostModel`], typically obtained with [`FRICostModel::calibrate`]
+	/// once per process.
+	pub const fn with_cost_model(mut self, cost_model: &'a FRICostModel) -> Self {
+		self.fold_arities = FoldArities::CostModel(cost_model);
+		self
+	}
+
+	/// Chooses the constant folding arity that minimizes the verifier's cost under a
+	/// [`FRIVerifierCostModel`].
+	///
+	/// Every constant arity is tried, and the parameters are priced with
+	/// [`FRIVerifierCostModel::verifier_cost`], including the Merkle layers the scheme opens. A
+	/// verifier paying mostly for hash invocations, such as one running in a smart contract, is
+	/// typically best served by larger arities than one paying mostly for proof bytes.
+	pub const fn with_verifier_cost_model(mut self, cost_model: &'a FRIVerifierCostModel) -> Self {
+		self.fold_arities = FoldArities::VerifierCostModel(cost_model);
+		self
+	}
+
+	/// Uses an explicit interleaved batch size and FRI folding schedule.
+	///
+	/// This is for deployments whose parameters are fixed externally, for example by a verifier
+	/// contract. The batch size and the arities determine the Reed–Solomon code and the length of
+	/// the terminal codeword, which is `commit_meta.total_vars() + log_inv_rate -
+	/// sum(fold_arities)`. The number of test queries is derived from the security target.
+	///
+	/// ## Arguments
+	///
+	/// * `log_batch_size` - the binary logarithm of the number of interleaved codewords.
+	/// * `fold_arities` - the number of variables folded between consecutive committed oracles.
+	pub fn with_arity_schedule(mut self, log_batch_size: usize, fold_arities: Vec<usize>) -> Self {
+		self.fold_arities = FoldArities::Schedule {
+			log_batch_size,
+			fold_arities,
+		};
+		self
+	}
+
+	/// Chooses the parameters for committing a batch with [`super::commit_hiding`].
+	///
+	/// The parameters are chosen for [`CommitMeta::with_hiding_multilin`] of the batch. The hiding
+	/// multilinear has as many variables as the batch, or more if the FRI queries would otherwise
+	/// open more symbols of a codeword than its message has random coefficients.
+	pub const fn with_hiding(mut self) -> Self {
+		self.hiding = true;
+		self
+	}
+
+	/// Chooses the FRI parameters of a committed batch.
+	///
+	/// ## Throws
+	///
+	/// * [`fri::Error::InvalidArgs`] if an explicit batch size exceeds the number of committed
+	///   variables
+	/// * [`fri::Error::FoldArityIsZero`] if any arity of an explicit schedule is zero
+	/// * [`fri::Error::FirstFoldArityTooSmall`] if the first arity of an explicit schedule is less
+	///   than its batch size
+	/// * [`fri::Error::InvalidFoldAritySequence`] if the arities fold all committed variables
+	/// * [`fri::Error::InvalidArgs`] if the grinding bits exceed 32 or the security target
+	/// * [`fri::Error::ParameterError`] if the security target cannot be met with the code
+	/// * [`Error::CommittedTowerLevelTooHigh`] if a committed multilinear is above the tower level
+	///   of `F`
+	pub fn build<F, FEncode>(
+		&self,
+		commit_meta: &CommitMeta,
+	) -> Result<FRIParams<F, FEncode>, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+		MTScheme: MerkleTreeScheme<F>,
+	{
+		self.build_with_rate(commit_meta, self.log_inv_rate)
+	}
+
+	/// Chooses the FRI parameters of each rate batch of a [`RateBatchedCommitMeta`], as
+	/// [`Self::build`] does with the rate of the batch in place of the configured one.
+	pub fn build_rate_batches<F, FEncode>(
+		&self,
+		rate_batched_meta: &RateBatchedCommitMeta,
+	) -> Result<Vec<FRIParams<F, FEncode>>, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+		MTScheme: MerkleTreeScheme<F>,
+	{
+		iter::zip(rate_batched_meta.batch_commit_metas(), rate_batched_meta.log_inv_rates())
+			.map(|(commit_meta, log_inv_rate)| self.build_with_rate(commit_meta, log_inv_rate))
+			.collect()
+	}
+
+	fn build_with_rate<F, FEncode>(
+		&self,
+		commit_meta: &CommitMeta,
+		log_inv_rate: usize,
+	) -> Result<FRIParams<F, FEncode>, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+		MTScheme: MerkleTreeScheme<F>,
+	{
+		commit_meta.check_top_field::<F>()?;
+
+		if !self.hiding {
+			return self.choose(commit_meta, log_inv_rate);
+		}
+
+		// The number of queries does not depend on the message length, so a long enough hiding
+		// multilinear always has enough random coefficients.
+		let mut n_hiding_vars = commit_meta.total_vars();
+		loop {
+			let fri_params =
+				self.choose(&commit_meta.with_hiding_multilin(n_hiding_vars)?, log_inv_rate)?;
+			match hiding_commit_meta(commit_meta, &fri_params) {
+				Ok(_) => return Ok(fri_params),
+				Err(Error::HidingQueriesExceedRandomness { .. }) => n_hiding_vars += 1,
+				Err(err) => return Err(err),
+			}
+		}
+	}
+
+	fn choose<F, FEncode>(
+		&self,
+		commit_meta: &CommitMeta,
+		log_inv_rate: usize,
+	) -> Result<FRIParams<F, FEncode>, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+		MTScheme: MerkleTreeScheme<F>,
+	{
+		let total_vars = commit_meta.total_vars();
+		let digest_size = size_of::<MTScheme::Digest>();
+
+		// Choose the NTT with the maximum domain size, to be independent of the commit parameters.
+		// We then choose FRI parameters based on a compatible subspace of the NTT, and then create
+		// another NTT object for encoding, using the appropriate subspace.
+		let ntt = SingleThreadedNTT::<FEncode>::new(FEncode::N_BITS)?;
+
+		let params = match &self.fold_arities {
+			FoldArities::OptimalConstant => {
+				let arity =
+					estimate_optimal_arity(total_vars + log_inv_rate, digest_size, size_of::<F>());
+				self.with_constant_arity(&ntt, total_vars, log_inv_rate, arity)
+			}
+			FoldArities::CostModel(cost_model) => {
+				// The number of test queries depends only on the code, not on the folding arity.
+				let rs_code = ReedSolomonCode::<FEncode>::new(total_vars, log_inv_rate)?;
+				let arity = estimate_optimal_arity_with_cost_model(
+					total_vars + log_inv_rate,
+					self.n_test_queries::<F, _>(&rs_code)?,
+					digest_size,
+					size_of::<F>(),
+					cost_model,
+				);
+				self.with_constant_arity(&ntt, total_vars, log_inv_rate, arity)
+			}
+			FoldArities::VerifierCostModel(cost_model) => {
+				let mut optimal = None;
+				for arity in 1..=(total_vars + log_inv_rate).max(1) {
+					let params = self.with_constant_arity(&ntt, total_vars, log_inv_rate, arity)?;
+					let cost = cost_model.verifier_cost(&params, self.merkle_scheme);
+					if optimal
+						.as_ref()
+						.is_none_or(|(optimal_cost, _)| cost < *optimal_cost)
+					{
+						optimal = Some((cost, params));
+					}
+				}
+				let (_, params) = optimal.expect("at least one arity is tried");
+				Ok(params)
+			}
+			FoldArities::OptimalSchedule {
+				log_batch_size: None,
+			} => {
+				// The number of test queries depends only weakly on the batch size, so estimate it
+				// with the non-interleaved code and recalculate it for the chosen code.
+				let rs_code = ReedSolomonCode::<FEncode>::new(total_vars, log_inv_rate)?;
+				let fold_arities = fri::estimate_optimal_arity_schedule(
+					total_vars + log_inv_rate,
+					log_inv_rate,
+					self.n_test_queries::<F, _>(&rs_code)?,
+					digest_size,
+					size_of::<F>(),
+				);
+				let log_batch_size = fold_arities.first().copied().unwrap_or(0);
+				self.with_schedule(&ntt, total_vars, log_inv_rate, log_batch_size, fold_arities)
+			}
+			&FoldArities::OptimalSchedule {
+				log_batch_size: Some(log_batch_size),
+			} => {
+				check_log_batch_size(total_vars, log_batch_size)?;
+
+				let rs_code =
+					ReedSolomonCode::<FEncode>::new(total_vars - log_batch_size, log_inv_rate)?;
+				let mut fold_arities = fri::estimate_optimal_arity_schedule(
+					total_vars + log_inv_rate,
+					log_inv_rate,
+					self.n_test_queries::<F, _>(&rs_code)?,
+					digest_size,
+					size_of::<F>(),
+				);
+
+				// The folding must leave at least one variable of the message unfolded. If the
+				// first fold cannot cover the batch within that, the whole message is sent
+				// interleaved.
+				let max_n_folded = total_vars.saturating_sub(1);
+				if log_batch_size > max_n_folded {
+					fold_arities.clear();
+				}
+				if let Some(first_arity) = fold_arities.first_mut() {
+					*first_arity = (*first_arity).max(log_batch_size);
+				}
+				// Raising the first arity may overshoot the terminal codeword, so cut the schedule
+				// short.
+				let mut n_folded = 0;
+				fold_arities.retain_mut(|arity| {
+					*arity = (*arity).min(max_n_folded - n_folded);
+					n_folded += *arity;
+					*arity > 0
+				});
+
+				self.with_schedule(&ntt, total_vars, log_inv_rate, log_batch_size, fold_arities)
+			}
+			FoldArities::Schedule {
+				log_batch_size,
+				fold_arities,
+			} => self.with_schedule(
+				&ntt,
+				total_vars,
+				log_inv_rate,
+				*log_batch_size,
+				fold_arities.clone(),
+			),
+		}?;
+
+		// Grinding only changes the number of test queries, which the arities are chosen for with
+		// the same grinding.
+		Ok(params.with_grinding(self.security_bits, self.grinding_bits)?)
+	}
+
+	fn with_constant_arity<F, FEncode>(
+		&self,
+		ntt: &impl AdditiveNTT<FEncode>,
+		total_vars: usize,
+		log_inv_rate: usize,
+		arity: usize,
+	) -> Result<FRIParams<F, FEncode>, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+	{
+		let params = FRIParams::choose_with_constant_fold_arity(
+			ntt,
+			total_vars,
+			self.security_bits,
+			log_inv_rate,
+			arity,
+			self.soundness_model,
+		)?;
+		Ok(params)
+	}
+
+	fn with_schedule<F, FEncode>(
+		&self,
+		ntt: &impl AdditiveNTT<FEncode>,
+		total_vars: usize,
+		log_inv_rate: usize,
+		log_batch_size: usize,
+		fold_arities: Vec<usize>,
+	) -> Result<FRIParams<F, FEncode>, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+	{
+		choose_with_arity_schedule(
+			ntt,
+			total_vars,
+			self.security_bits,
+			log_inv_rate,
+			self.soundness_model,
+			log_batch_size,
+			fold_arities,
+		)
+	}
+
+	fn n_test_queries<F, FEncode>(&self, rs_code: &ReedSolomonCode<FEncode>) -> Result<usize, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+	{
+		let n_test_queries = calculate_n_test_queries_with_soundness_model::<F, _>(
+			self.security_bits,
+			self.grinding_bits,
+			self.soundness_model,
+			rs_code,
+		)?;
+		Ok(n_test_queries)
+	}
+}
+
+/// Constructs FRI parameters with an explicit interleaved batch size and folding schedule.
+fn choose_with_arity_schedule<F, FEncode>(
+	ntt: &impl AdditiveNTT<FEncode>,
+	total_vars: usize,
+	security_bits: usize,
+	log_inv_rate: usize,
+	soundness_model: SoundnessModel,
+	log_batch_size: usize,
+	fold_arities: Vec<usize>,
+) -> Result<FRIParams<F, FEncode>, Error>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	check_log_batch_size(total_vars, log_batch_size)?;
+	if let Some(index) = fold_arities.iter().position(|&arity| arity == 0) {
+		bail!(fri::Error::FoldArityIsZero { index });
+	}
+	if fold_arities
+		.first()
+		.is_some_and(|&arity| arity < log_batch_size)
+	{
+		bail!(fri::Error::FirstFoldArityTooSmall);
+	}
+
+	let rs_code =
+		ReedSolomonCode::with_ntt_subspace(ntt, total_vars - log_batch_size, log_inv_rate)?;
+	let n_test_queries = calculate_n_test_queries::<F, _>(security_bits, &rs_code)?;
+	let params = FRIParams::new(rs_code, log_batch_size, fold_arities, n_test_queries)?
+		.with_soundness_model(security_bits, soundness_model)?;
+	Ok(params)
+}
+
+fn check_log_batch_size(total_vars: usize, log_batch_size: usize) -> Result<(), Error> {
+	if log_batch_size > total_vars {
+		bail!(fri::Error::InvalidArgs(format!(
+			"log_batch_size {log_batch_size} exceeds the {total_vars} committed variables"
+		)));
+	}
+	Ok(())
+}
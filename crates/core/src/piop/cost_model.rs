@@ -0,0 +1,215 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{
+	hint::black_box,
+	time::{Duration, Instant},
+};
+
+use binius_field::{BinaryField, ExtensionField, util::powers};
+
+use super::{
+	error::Error,
+	verify::{CommitMeta, PIOPSumcheckClaim},
+};
+use crate::{
+	merkle_tree::MerkleTreeProver,
+	protocols::fri::{FRICostModel, FRIParams},
+};
+
+/// Number of field multiplications timed by the multiplication calibration benchmark.
+const CALIBRATION_N_MULS: usize = 1 << 14;
+
+/// The widest SIMD instruction set enabled for the target the prover is compiled for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimdClass {
+	Portable,
+	Neon,
+	Sse,
+	Avx2,
+	Avx512,
+}
+
+impl SimdClass {
+	/// Detects the SIMD class from the target features enabled at compile time.
+	pub const fn detect() -> Self {
+		if cfg!(target_feature = "avx512f") {
+			Self::Avx512
+		} else if cfg!(target_feature = "avx2") {
+			Self::Avx2
+		} else if cfg!(target_feature = "sse2") {
+			Self::Sse
+		} else if cfg!(target_feature = "neon") {
+			Self::Neon
+		} else {
+			Self::Portable
+		}
+	}
+}
+
+/// Throughput of an accelerator relative to one CPU thread.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpuProfile {
+	/// The ratio of the accelerator's throughput to the single-thread throughput measured for the
+	/// CPU, for the data-parallel phases of the prover.
+	pub speedup: f64,
+}
+
+/// Machine-dependent costs that determine the running time of the PIOP prover.
+///
+/// The per-operation costs are for a single thread. Use [`Self::measure`] to obtain them with
+/// quick microbenchmarks on the current machine, or fill them in from a profile of the machine a
+/// job will be dispatched to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HardwareProfile {
+	/// Number of threads the prover runs on.
+	pub n_threads: usize,
+	/// The SIMD instruction set the prover is compiled for.
+	pub simd_class: SimdClass,
+	/// Time in nanoseconds to hash one byte of Merkle leaf data.
+	pub hash_ns_per_byte: f64,
+	/// Time in nanoseconds for one Merkle compression of two digests.
+	pub compression_ns: f64,
+	/// Time in nanoseconds for one additive NTT butterfly over the encoding field.
+	pub butterfly_ns: f64,
+	/// Time in nanoseconds for one multiplication in the top field.
+	pub mul_ns: f64,
+	/// The accelerator offloading the data-parallel phases, if any.
+	pub gpu: Option<GpuProfile>,
+}
+
+impl HardwareProfile {
+	/// Measures the per-operation costs on the current machine, with the thread count of the
+	/// current thread pool and no accelerator.
+	pub fn measure<F, FEncode, MTProver>(merkle_prover: &MTProver) -> Result<Self, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+		MTProver: MerkleTreeProver<F>,
+	{
+		let FRICostModel {
+			hash_ns_per_byte,
+			compression_ns,
+			butterfly_ns,
+			..
+		} = FRICostModel::calibrate::<F, FEncode, _>(merkle_prover, 0.0)?;
+
+		let values = powers(F::MULTIPLICATIVE_GENERATOR)
+			.take(CALIBRATION_N_MULS)
+			.collect::<Vec<_>>();
+		let start = Instant::now();
+		let product = values
+			.iter()
+			.fold(F::ONE, |acc, &value| acc * black_box(value));
+		black_box(product);
+		let mul_ns = start.elapsed().as_nanos() as f64 / CALIBRATION_N_MULS as f64;
+
+		Ok(Self {
+			n_threads: binius_maybe_rayon::current_num_threads(),
+			simd_class: SimdClass::detect(),
+			hash_ns_per_byte,
+			compression_ns,
+			butterfly_ns,
+			mul_ns,
+			gpu: None,
+		})
+	}
+
+	/// The factor by which the data-parallel phases are faster than on a single thread.
+	fn parallel_speedup(&self) -> f64 {
+		self.gpu
+			.map_or(self.n_threads.max(1) as f64, |gpu| gpu.speedup)
+	}
+}
+
+/// Estimated running time of each phase of the PIOP prover, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProverTimeEstimate {
+	/// Reed–Solomon encoding of the committed batch.
+	pub encode_ns: f64,
+	/// Merkle commitment to the codeword.
+	pub merkle_commit_ns: f64,
+	/// The sumcheck over the products of committed and transparent multilinears.
+	pub sumcheck_ns: f64,
+	/// FRI folding and the commitments to the folded oracles.
+	pub fri_ns: f64,
+}
+
+impl ProverTimeEstimate {
+	pub fn total_ns(&self) -> f64 {
+		self.encode_ns + self.merkle_commit_ns + self.sumcheck_ns + self.fri_ns
+	}
+
+	pub fn total(&self) -> Duration {
+		Duration::from_nanos(self.total_ns() as u64)
+	}
+}
+
+/// Estimates the time to commit a batch and prove sumcheck claims on it with [`super::commit`]
+/// and [`super::prove`].
+///
+/// The estimate counts the dominant operations of each phase and prices them with the hardware
+/// profile, assuming perfect scaling of the data-parallel work across threads. It is meant for
+/// scheduling jobs of different sizes against each other, and is typically within a small
+/// constant factor of the measured time.
+pub fn estimate_prover_time<F, FEncode>(
+	commit_meta: &CommitMeta,
+	fri_params: &FRIParams<F, FEncode>,
+	claims: &[PIOPSumcheckClaim<F>],
+	profile: &HardwareProfile,
+) -> ProverTimeEstimate
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	let speedup = profile.parallel_speedup();
+	let field_size = size_of::<F>() as f64;
+	let merkle_cost = |n_leaves: f64, n_elems: f64| {
+		(n_elems * field_size).mul_add(profile.hash_ns_per_byte, n_leaves * profile.compression_ns)
+	};
+
+	// Each element of the top field is encoded as `DEGREE` elements of the encoding field.
+	let rs_log_len = fri_params.rs_code().log_len();
+	let n_butterflies = (rs_log_len << rs_log_len.saturating_sub(1)) as f64
+		* (1usize << fri_params.log_batch_size()) as f64
+		* <F as ExtensionField<FEncode>>::DEGREE as f64;
+	let encode_ns = n_butterflies * profile.butterfly_ns / speedup;
+
+	let codeword_len = (1usize << fri_params.log_len()) as f64;
+	let merkle_commit_ns = merkle_cost((1usize << rs_log_len) as f64, codeword_len) / speedup;
+
+	// Each round of a degree-2 sumcheck evaluates the composite at 3 points over half of the
+	// remaining hypercube, and then folds both multilinears, for about 6 + 2 * 2 multiplications
+	// per hypercube vertex over all rounds. Committed multilinears without claims are still
+	// folded to obtain their evaluations.
+	let claims_muls = claims
+		.iter()
+		.map(|claim| 6.0 * (1usize << claim.n_vars) as f64)
+		.sum::<f64>();
+	let fold_muls = commit_meta
+		.n_multilins_by_vars()
+		.iter()
+		.enumerate()
+		.map(|(n_vars, &count)| 4.0 * count as f64 * (1usize << n_vars) as f64)
+		.sum::<f64>();
+	let sumcheck_ns = (claims_muls + fold_muls) * profile.mul_ns / speedup;
+
+	// Folding halves the codeword in each round, for about two multiplications per codeword
+	// element in total, and every oracle after the first is committed.
+	let mut log_oracle_len = fri_params.log_len();
+	let mut fri_commit_ns = 0.0;
+	for &arity in fri_params.fold_arities().iter().skip(1) {
+		log_oracle_len = log_oracle_len.saturating_sub(arity);
+		fri_commit_ns += merkle_cost(
+			(1usize << log_oracle_len.saturating_sub(arity)) as f64,
+			(1usize << log_oracle_len) as f64,
+		);
+	}
+	let fri_ns = (2.0 * codeword_len).mul_add(profile.mul_ns, fri_commit_ns) / speedup;
+
+	ProverTimeEstimate {
+		encode_ns,
+		merkle_commit_ns,
+		sumcheck_ns,
+		fri_ns,
+	}
+}
@@ -1,9 +1,10 @@
 // Copyright 2024-2025 Irreducible Inc.
 
 use crate::{
-	oracle::OracleId,
+	merkle_tree,
+	oracle::{self, OracleId},
 	polynomial,
-	protocols::{fri, sumcheck},
+	protocols::{fri, stir, sumcheck},
 	reed_solomon, transcript, witness,
 };
 
@@ -15,14 +16,81 @@ pub enum Error {
 	TransparentsNotSorted,
 	#[error("committed polynomial witness for oracle {id} is missing packed evaluations")]
 	CommittedPackedEvaluationsMissing { id: OracleId },
+	#[error("committed multilinear {index} was already added")]
+	CommittedAlreadyAdded { index: usize },
+	#[error("committed multilinear {index} is missing")]
+	CommittedMissing { index: usize },
+	#[error(
+		"the chunks of committed multilinear {index} hold {actual} packed elements, expected {expected}"
+	)]
+	CommittedChunksLengthMismatch {
+		index: usize,
+		expected: usize,
+		actual: usize,
+	},
+	#[error("committed multilinear {index} has {actual} packed variables, expected {expected}")]
+	CommittedVariablesMismatch {
+		index: usize,
+		expected: usize,
+		actual: usize,
+	},
+	#[error("the commitment parameters are too small for the committed batch")]
+	CommitParamsTooSmall,
+	#[error(
+		"the commitment parameters are for a message with {actual} variables, but the committed \
+		batch has {expected}"
+	)]
+	CommitParamsMismatch { expected: usize, actual: usize },
+	#[error(
+		"the FRI queries open up to {n_opened} symbols of each codeword, more than the {n_random} \
+		random coefficients of its message"
+	)]
+	HidingQueriesExceedRandomness { n_opened: usize, n_random: usize },
+	#[error("invalid BaseFold parameters: {0}")]
+	InvalidBaseFoldParams(String),
+	#[error("the opened leaves are inconsistent with the parameters")]
+	ParameterError,
 	#[error("invalid committed ID")]
 	InvalidCommittedId { max_index: usize },
 	#[error("invalid transparent ID")]
 	InvalidTransparentId { max_index: usize },
+	#[error("expected {expected} constraint systems in the batch, got {actual}")]
+	IncorrectNumberOfSystems { expected: usize, actual: usize },
+	#[error("constraint system {system} is out of range for a batch of {n_systems}")]
+	InvalidSystemIndex { system: usize, n_systems: usize },
+	#[error("constraint system {system} has {actual} committed multilinears, expected {expected}")]
+	IncorrectNumberOfCommitted {
+		system: usize,
+		expected: usize,
+		actual: usize,
+	},
+	#[error("expected {expected} committed multilinears, got {actual}")]
+	IncorrectNumberOfMultilins { expected: usize, actual: usize },
+	#[error("expected a rate for each of the {expected} committed multilinears, got {actual}")]
+	IncorrectNumberOfRates { expected: usize, actual: usize },
+	#[error(
+		"expected a tower level for each of the {expected} committed multilinears, got {actual}"
+	)]
+	IncorrectNumberOfTowerLevels { expected: usize, actual: usize },
+	#[error(
+		"a committed multilinear has tower level {tower_level}, above the top field at tower level \
+		{max}"
+	)]
+	CommittedTowerLevelTooHigh { tower_level: usize, max: usize },
+	#[error("expected {expected} rate batches, got {actual}")]
+	IncorrectNumberOfRateBatches { expected: usize, actual: usize },
+	#[error("expected {expected} commitments, got {actual}")]
+	IncorrectNumberOfCommitments { expected: usize, actual: usize },
 	#[error("the number of variables recorded for oracle {id} is incorrect")]
 	OracleToCommitIndexMalformed { id: OracleId },
+	#[error("oracle {id} is missing from the index of committed oracles")]
+	OracleToCommitIndexMissingEntry { id: OracleId },
 	#[error("the number of variables of the polynomials in sumcheck claim {index} do not match")]
 	SumcheckClaimVariablesMismatch { index: usize },
+	#[error("committed multilinear {index} has fewer variables than the packed field width")]
+	CommittedSmallerThanPackedField { index: usize },
+	#[error("compute layer error: {0}")]
+	ComputeLayer(#[from] binius_compute::Error),
 	#[error("Compute layer allocation error: {0}")]
 	Alloc(#[from] binius_compute::alloc::Error),
 	#[error("binius_math error: {0}")]
@@ -33,14 +101,26 @@ pub enum Error {
 	Polynomial(#[from] polynomial::Error),
 	#[error("FRI error: {0}")]
 	FRI(#[from] fri::Error),
+	#[error("STIR error: {0}")]
+	STIR(#[from] stir::Error),
 	#[error("Sumcheck error: {0}")]
 	Sumcheck(#[from] sumcheck::Error),
 	#[error("witness error: {0}")]
 	Witness(#[from] witness::Error),
+	#[error("oracle error: {0}")]
+	Oracle(#[from] oracle::Error),
 	#[error("NTT error: {0}")]
 	NTT(#[from] binius_ntt::Error),
+	#[error("vector commit error: {0}")]
+	VectorCommit(#[source] Box<dyn std::error::Error + Send + Sync>),
 	#[error("verification error: {0}")]
 	VerificationError(#[from] VerificationError),
+	#[error("proof {index} of the batch is invalid: {source}")]
+	InvalidBatchedProof {
+		index: usize,
+		#[source]
+		source: Box<Error>,
+	},
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -49,6 +129,26 @@ pub enum VerificationError {
 	IncorrectTransparentEvaluation { index: usize },
 	#[error("sumcheck final evaluation is incorrect")]
 	IncorrectSumcheckEvaluation,
+	#[error("the roots of the parts do not hash to the incremental commitment")]
+	IncorrectPartCommitments,
+	#[error("the BaseFold opening of oracle {oracle} is invalid: {source}")]
+	InvalidBaseFoldOpening {
+		oracle: usize,
+		#[source]
+		source: merkle_tree::Error,
+	},
+	#[error("incorrect BaseFold folding for the query at index {query_index} into oracle {oracle}")]
+	IncorrectBaseFoldFold { query_index: usize, oracle: usize },
+	#[error("the column openings of the STIR-committed codeword are invalid: {source}")]
+	InvalidSTIRColumnOpening {
+		#[source]
+		source: merkle_tree::Error,
+	},
+	#[error(
+		"the column of the STIR-committed codeword at position {position} does not match the \
+		combined message"
+	)]
+	IncorrectSTIRColumn { position: usize },
 	#[error("Transcript error: {0}")]
 	Transcript(#[from] transcript::Error),
 }
@@ -0,0 +1,204 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{BinaryField, ExtensionField, Field};
+use binius_ntt::AdditiveNTT;
+use binius_utils::bail;
+
+use super::{
+	error::Error,
+	multi_commit::{MultiCommitMeta, SplitClaims},
+	verify::{CommitMeta, PIOPSumcheckClaim},
+};
+use crate::{
+	fiat_shamir::CanSample,
+	merkle_tree::MerkleTreeScheme,
+	protocols::fri::{CommitOutput, FRIParams},
+};
+
+/// Metadata about a batch of multilinears committed in parts as their witnesses become available.
+///
+/// The committed multilinears are grouped into parts, such as the committed columns of each table
+/// of a constraint system. Each part is committed as its own codeword, at its own size, by a
+/// [`super::IncrementalCommitter`] as soon as all of its multilinears are added, so that the
+/// encoding and hashing of a part overlap with the witness generation of the others. The
+/// commitment is the root of a Merkle tree over the roots of the parts, see
+/// [`IncrementalCommitOutput`].
+///
+/// [`super::prove_incremental`] opens all parts in a single FRI instance, as
+/// [`super::prove_with_static`] does for two batches: the codeword of a part with the most
+/// variables is folded from the first round, and the codeword of each other part is added to the
+/// round oracle committed once the folded codeword reaches its length. The FRI parameters are
+/// those of a part with the most variables, and must commit a round oracle other than the last
+/// after each number of fold rounds at which a part joins.
+///
+/// The claims of a proof reference the committed multilinears by a joint index, which runs over
+/// the committed multilinears of each part in turn.
+#[derive(Debug)]
+pub struct IncrementalCommitMeta {
+	/// The parts as separate commitments, which routes the claims to each.
+	parts: MultiCommitMeta,
+	/// The maximum number of variables of any part.
+	total_vars: usize,
+}
+
+impl IncrementalCommitMeta {
+	/// Constructs a new [`IncrementalCommitMeta`].
+	///
+	/// ## Arguments
+	///
+	/// * `part_metas` - the metadata of the committed batch of each part
+	///
+	/// ## Panics
+	///
+	/// * if `part_metas` is empty
+	pub fn new(part_metas: Vec<CommitMeta>) -> Self {
+		let total_vars = part_metas
+			.iter()
+			.map(|part_meta| part_meta.total_vars())
+			.max()
+			.expect("an incremental commitment has at least one part");
+		Self {
+			parts: MultiCommitMeta::new(part_metas),
+			total_vars,
+		}
+	}
+
+	/// Returns the number of parts.
+	pub fn n_parts(&self) -> usize {
+		self.parts.n_commitments()
+	}
+
+	/// Returns the metadata of the committed batch of each part.
+	pub fn part_metas(&self) -> &[CommitMeta] {
+		self.parts.commit_metas()
+	}
+
+	/// Returns the maximum number of variables of any part, which is the number of fold rounds of
+	/// the FRI instance that opens the parts.
+	pub fn total_vars(&self) -> usize {
+		self.total_vars
+	}
+
+	/// Returns the total number of committed multilinears over all parts.
+	pub fn total_multilins(&self) -> usize {
+		self.parts.total_multilins()
+	}
+
+	/// Returns the part of a committed multilinear and its index within the part.
+	pub fn committed_location(&self, committed: usize) -> Result<(usize, usize), Error> {
+		self.parts.committed_location(committed)
+	}
+
+	/// Returns the number of fold rounds after which the codeword of a part joins the FRI
+	/// instance, which is also the sumcheck round that the claims on the part start at.
+	pub(super) fn entry_round(&self, part: usize) -> usize {
+		self.total_vars - self.part_metas()[part].total_vars()
+	}
+
+	/// Returns the part whose codeword FRI folds from the first round, which is the first part with
+	/// the most variables.
+	pub(super) fn folded_part(&self) -> usize {
+		(0..self.n_parts())
+			.find(|&part| self.entry_round(part) == 0)
+			.expect("the part with the most variables joins in the first round")
+	}
+
+	/// Samples the coefficient of the codeword of each part in the FRI instance.
+	///
+	/// The coefficient of the part folded from the first round is one, and those of the other
+	/// parts are sampled in order, after the roots of all parts are observed.
+	pub(super) fn sample_coeffs<F: Field>(&self, transcript: &mut impl CanSample<F>) -> Vec<F> {
+		let folded_part = self.folded_part();
+		(0..self.n_parts())
+			.map(|part| {
+				if part == folded_part {
+					F::ONE
+				} else {
+					transcript.sample()
+				}
+			})
+			.collect()
+	}
+
+	/// Returns the FRI parameters that each part joining the FRI instance after the first round is
+	/// committed with.
+	///
+	/// The parts with the most variables are committed with `fri_params` and have no entry, and
+	/// the others with the parameters derived by [`FRIParams::batched_codeword_params`] for their
+	/// entry round.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::CommitParamsMismatch`] if `fri_params` do not fold the parts with the most
+	///   variables
+	/// * [`Error::FRI`] if `fri_params` commit no round oracle where a part joins
+	pub(super) fn batched_fri_params<F, FEncode>(
+		&self,
+		fri_params: &FRIParams<F, FEncode>,
+		ntt: &impl AdditiveNTT<FEncode>,
+	) -> Result<Vec<Option<FRIParams<F, FEncode>>>, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+	{
+		if fri_params.n_fold_rounds() != self.total_vars {
+			bail!(Error::CommitParamsMismatch {
+				expected: self.total_vars,
+				actual: fri_params.n_fold_rounds(),
+			});
+		}
+
+		(0..self.n_parts())
+			.map(|part| {
+				let entry_round = self.entry_round(part);
+				let batched_fri_params = (entry_round != 0)
+					.then(|| fri_params.batched_codeword_params(ntt, entry_round))
+					.transpose()?;
+				Ok(batched_fri_params)
+			})
+			.collect()
+	}
+
+	/// Splits the committed multilinears, in joint order, into the committed multilinears of each
+	/// part.
+	pub(super) fn split_committed<T: Clone>(&self, committed: &[T]) -> Result<Vec<Vec<T>>, Error> {
+		self.parts.split_committed(committed)
+	}
+
+	/// Splits claims referencing the joint committed indices into the claims on each part, see
+	/// [`MultiCommitMeta::split_claims`].
+	pub(super) fn split_claims<F: Field>(
+		&self,
+		n_transparents: usize,
+		claims: &[PIOPSumcheckClaim<F>],
+	) -> Result<Vec<SplitClaims<F>>, Error> {
+		self.parts.split_claims(n_transparents, claims)
+	}
+}
+
+/// The output of an [`super::IncrementalCommitter`].
+#[derive(Debug)]
+pub struct IncrementalCommitOutput<P, Digest, Committed> {
+	/// The commitment to all parts, which is the root of a Merkle tree over their roots.
+	pub commitment: Digest,
+	/// The commitment, Merkle tree and codeword of each part.
+	pub parts: Vec<CommitOutput<P, Digest, Committed>>,
+}
+
+/// Hashes the roots of the parts of an incremental commitment into the commitment.
+///
+/// The roots are the nodes of the upper levels of a Merkle tree, padded to a power of two by
+/// repeating the last one, and are hashed as [`MerkleTreeScheme::cap_root`] hashes a Merkle cap, so
+/// that the commitment to a single part is its root.
+pub(super) fn combine_part_commitments<F, MTScheme: MerkleTreeScheme<F>>(
+	merkle_scheme: &MTScheme,
+	part_commitments: &[MTScheme::Digest],
+) -> Result<MTScheme::Digest, Error> {
+	let mut roots = part_commitments.to_vec();
+	if let Some(last) = roots.last().cloned() {
+		roots.resize(roots.len().next_power_of_two(), last);
+	}
+	merkle_scheme
+		.cap_root(&roots)
+		.map_err(|err| Error::VectorCommit(Box::new(err)))
+}
@@ -0,0 +1,373 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{borrow::Borrow, fmt, iter::Peekable};
+
+use binius_field::{BinaryField, ExtensionField, TowerField};
+use binius_utils::{DeserializeBytes, SerializationMode};
+use itertools::izip;
+
+use super::{
+	error::{Error, VerificationError},
+	verify::{CommitMeta, PIOPSumcheckClaim, make_sumcheck_claim_descs},
+};
+use crate::{
+	merkle_tree::MerkleTreeScheme,
+	polynomial::MultivariatePoly,
+	protocols::fri::{FRIParams, vcs_optimal_layers_depths_iter},
+	transcript,
+};
+
+/// The contents of a section of a PIOP proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofSectionContents<F, Digest> {
+	/// The evaluations of the committed and transparent multilinears with `n_vars` variables at
+	/// the sumcheck challenges, sent when their sumcheck claim finishes.
+	MultilinearEvals { n_vars: usize, evals: Vec<F> },
+	/// The round polynomial of a round of the interleaved sumcheck, without its coefficient of
+	/// lowest degree.
+	SumcheckRound { round: usize, coeffs: Vec<F> },
+	/// The Merkle root of a folded FRI oracle.
+	FRICommitment { oracle: usize, root: Digest },
+	/// The codeword of the last FRI oracle.
+	TerminateCodeword { values: Vec<F> },
+	/// The opening of the codeword of the last FRI oracle against its commitment, such as the
+	/// salts of its leaves when the Merkle tree scheme is hiding.
+	TerminateCodewordOpening { digests: Vec<Digest> },
+	/// The layer of the Merkle tree of a FRI oracle that the query openings are verified against.
+	MerkleLayer {
+		oracle: usize,
+		layer_depth: usize,
+		digests: Vec<Digest>,
+	},
+	/// The proof-of-work nonce the prover sends before the FRI query phase.
+	ProofOfWorkNonce { nonce: u64 },
+	/// The opening of a coset of a FRI oracle in the query phase.
+	QueryOpening {
+		query: usize,
+		oracle: usize,
+		values: Vec<F>,
+		path: Vec<Digest>,
+	},
+}
+
+impl<F, Digest> ProofSectionContents<F, Digest> {
+	/// A label for the kind of section, shared by all sections of the same kind.
+	pub const fn kind(&self) -> &'static str {
+		match self {
+			Self::MultilinearEvals { .. } => "multilinear_evals",
+			Self::SumcheckRound { .. } => "sumcheck_round",
+			Self::FRICommitment { .. } => "fri_commitment",
+			Self::TerminateCodeword { .. } => "fri_terminate_codeword",
+			Self::TerminateCodewordOpening { .. } => "fri_terminate_codeword_opening",
+			Self::MerkleLayer { .. } => "fri_merkle_layer",
+			Self::ProofOfWorkNonce { .. } => "fri_proof_of_work_nonce",
+			Self::QueryOpening { .. } => "fri_query_opening",
+		}
+	}
+}
+
+impl<F, Digest> fmt::Display for ProofSectionContents<F, Digest> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::MultilinearEvals { n_vars, evals } => {
+				write!(f, "multilinear evals, {n_vars} vars ({} values)", evals.len())
+			}
+			Self::SumcheckRound { round, coeffs } => {
+				write!(f, "sumcheck round {round} ({} coeffs)", coeffs.len())
+			}
+			Self::FRICommitment { oracle, .. } => write!(f, "FRI commitment {oracle}"),
+			Self::TerminateCodeword { values } => {
+				write!(f, "FRI terminate codeword ({} values)", values.len())
+			}
+			Self::TerminateCodewordOpening { digests } => {
+				write!(f, "FRI terminate codeword opening ({} digests)", digests.len())
+			}
+			Self::MerkleLayer {
+				oracle,
+				layer_depth,
+				digests,
+			} => write!(
+				f,
+				"FRI oracle {oracle} Merkle layer at depth {layer_depth} ({} digests)",
+				digests.len()
+			),
+			Self::ProofOfWorkNonce { nonce } => write!(f, "FRI proof-of-work nonce {nonce}"),
+			Self::QueryOpening {
+				query,
+				oracle,
+				values,
+				path,
+			} => write!(
+				f,
+				"FRI query {query} opening of oracle {oracle} ({} values, {} digests)",
+				values.len(),
+				path.len()
+			),
+		}
+	}
+}
+
+/// A parsed section of a PIOP proof, with its location in the proof bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofSection<F, Digest> {
+	/// The offset of the section in the proof, in bytes.
+	pub offset: usize,
+	/// The size of the section, in bytes.
+	pub len: usize,
+	pub contents: ProofSectionContents<F, Digest>,
+}
+
+/// The sections of a PIOP proof, in the order they appear in the transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofInspection<F, Digest> {
+	pub sections: Vec<ProofSection<F, Digest>>,
+}
+
+impl<F, Digest> ProofInspection<F, Digest> {
+	/// The total size of the parsed sections, in bytes.
+	pub fn len(&self) -> usize {
+		self.sections.iter().map(|section| section.len).sum()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.sections.is_empty()
+	}
+
+	/// The total size of the sections of each kind, in bytes, in order of first appearance.
+	pub fn len_by_kind(&self) -> Vec<(&'static str, usize)> {
+		let mut lens = Vec::<(&'static str, usize)>::new();
+		for section in &self.sections {
+			let kind = section.contents.kind();
+			match lens.iter_mut().find(|(other, _)| *other == kind) {
+				Some((_, len)) => *len += section.len,
+				None => lens.push((kind, section.len)),
+			}
+		}
+		lens
+	}
+}
+
+/// Reads consecutive sections from the proof bytes.
+struct ProofParser<'a, F, Digest> {
+	proof: &'a [u8],
+	remaining: &'a [u8],
+	sections: Vec<ProofSection<F, Digest>>,
+}
+
+impl<F, Digest> ProofParser<'_, F, Digest> {
+	fn offset(&self) -> usize {
+		self.proof.len() - self.remaining.len()
+	}
+
+	fn read_vec<T: DeserializeBytes>(&mut self, n: usize) -> Result<Vec<T>, Error> {
+		// Every value occupies at least one byte, so reject lengths beyond the proof up front.
+		if n > self.remaining.len() {
+			return Err(VerificationError::Transcript(transcript::Error::NotEnoughBytes).into());
+		}
+		(0..n)
+			.map(|_| {
+				T::deserialize(&mut self.remaining, SerializationMode::CanonicalTower)
+					.map_err(|err| VerificationError::Transcript(err.into()).into())
+			})
+			.collect()
+	}
+
+	fn read_section(
+		&mut self,
+		read: impl FnOnce(&mut Self) -> Result<ProofSectionContents<F, Digest>, Error>,
+	) -> Result<(), Error> {
+		let offset = self.offset();
+		let contents = read(self)?;
+		self.sections.push(ProofSection {
+			offset,
+			len: self.offset() - offset,
+			contents,
+		});
+		Ok(())
+	}
+}
+
+/// The shape of a batched sumcheck claim: its number of variables, number of multilinears, and
+/// the maximum degree of the round polynomials while it is active.
+#[derive(Clone, Copy)]
+struct ClaimShape {
+	n_vars: usize,
+	n_multilinears: usize,
+	degree: usize,
+}
+
+/// Reads the multilinear evaluations of the claims that finish at `round` and returns the degree
+/// of the round polynomial of this round.
+fn read_finished_claims<F, Digest>(
+	parser: &mut ProofParser<F, Digest>,
+	claims: &mut Peekable<impl Iterator<Item = ClaimShape>>,
+	round: usize,
+) -> Result<usize, Error>
+where
+	F: DeserializeBytes,
+{
+	while let Some(claim) = claims.next_if(|claim| claim.n_vars == round) {
+		parser.read_section(|parser| {
+			Ok(ProofSectionContents::MultilinearEvals {
+				n_vars: claim.n_vars,
+				evals: parser.read_vec(claim.n_multilinears)?,
+			})
+		})?;
+	}
+	Ok(claims.peek().map_or(0, |claim| claim.degree))
+}
+
+/// Parses a PIOP proof into labeled sections without verifying it.
+///
+/// The arguments other than `proof` are those of [`super::verify`], which determine the shape of
+/// the proof. The proof must contain exactly the transcript written by [`super::prove`], starting
+/// with the first sumcheck round, and the parser reads both the message and the decommitment
+/// tapes. No challenges are sampled and no checks are performed beyond the proof having the
+/// expected length, so the result is meant for auditing, debugging, and proof size reports. Since
+/// the number of distinct query indices depends on the challenges, the query openings are read
+/// until the end of the proof.
+pub fn inspect_proof<'a, F, FEncode, MTScheme>(
+	commit_meta: &CommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &FRIParams<F, FEncode>,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	proof: &[u8],
+) -> Result<ProofInspection<F, MTScheme::Digest>, Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+{
+	let sumcheck_claim_descs = make_sumcheck_claim_descs(
+		commit_meta,
+		transparents.iter().map(|poly| poly.borrow().n_vars()),
+		claims,
+	)?;
+
+	// The shapes of the batched sumcheck claims, in the order that `verify` batches them.
+	let mut claim_shapes = sumcheck_claim_descs
+		.iter()
+		.enumerate()
+		.filter(|(_, desc)| !desc.committed_indices.is_empty())
+		.map(|(n_vars, desc)| ClaimShape {
+			n_vars,
+			n_multilinears: desc.n_committed() + desc.n_transparent(),
+			degree: if desc.composite_sums.is_empty() { 0 } else { 2 },
+		})
+		.collect::<Vec<_>>();
+	for i in (0..claim_shapes.len()).rev().skip(1) {
+		claim_shapes[i].degree = claim_shapes[i].degree.max(claim_shapes[i + 1].degree);
+	}
+	let mut claim_shapes = claim_shapes.into_iter().peekable();
+
+	let mut parser = ProofParser {
+		proof,
+		remaining: proof,
+		sections: Vec::new(),
+	};
+
+	let mut arities = fri_params.fold_arities().iter();
+	let mut next_commit_round = arities.next().copied();
+	let mut n_oracles = 1;
+	for round in 0..commit_meta.total_vars() {
+		let degree = read_finished_claims(&mut parser, &mut claim_shapes, round)?;
+		parser.read_section(|parser| {
+			Ok(ProofSectionContents::SumcheckRound {
+				round,
+				coeffs: parser.read_vec(degree)?,
+			})
+		})?;
+
+		if next_commit_round == Some(round + 1) {
+			parser.read_section(|parser| {
+				let root = parser
+					.read_vec(1)?
+					.pop()
+					.expect("read_vec returns the requested number of values");
+				Ok(ProofSectionContents::FRICommitment {
+					oracle: n_oracles,
+					root,
+				})
+			})?;
+			n_oracles += 1;
+			next_commit_round = arities.next().map(|arity| round + 1 + arity);
+		}
+	}
+	read_finished_claims(&mut parser, &mut claim_shapes, commit_meta.total_vars())?;
+
+	let terminate_codeword_len =
+		1 << (fri_params.n_final_challenges() + fri_params.rs_code().log_inv_rate());
+	parser.read_section(|parser| {
+		Ok(ProofSectionContents::TerminateCodeword {
+			values: parser.read_vec(terminate_codeword_len)?,
+		})
+	})?;
+	let terminate_opening_len =
+		merkle_scheme.vector_opening_len(terminate_codeword_len >> fri_params.n_final_challenges());
+	if terminate_opening_len != 0 {
+		parser.read_section(|parser| {
+			Ok(ProofSectionContents::TerminateCodewordOpening {
+				digests: parser.read_vec(terminate_opening_len)?,
+			})
+		})?;
+	}
+
+	let layer_depths =
+		vcs_optimal_layers_depths_iter(fri_params, merkle_scheme).collect::<Vec<_>>();
+	for (oracle, &layer_depth) in layer_depths.iter().enumerate() {
+		parser.read_section(|parser| {
+			Ok(ProofSectionContents::MerkleLayer {
+				oracle,
+				layer_depth,
+				digests: parser.read_vec(1 << layer_depth)?,
+			})
+		})?;
+	}
+
+	if fri_params.grinding_bits() > 0 {
+		parser.read_section(|parser| {
+			let nonce = parser
+				.read_vec(1)?
+				.pop()
+				.expect("read_vec returns the requested number of values");
+			Ok(ProofSectionContents::ProofOfWorkNonce { nonce })
+		})?;
+	}
+
+	// Colliding query indices are opened once, so the proof has at most `n_test_queries` query
+	// openings, and only the challenges determine how many.
+	for query in 0..fri_params.n_test_queries() {
+		if parser.remaining.is_empty() {
+			break;
+		}
+		let mut log_n_cosets = fri_params.index_bits();
+		for (oracle, (&arity, &layer_depth)) in
+			izip!(fri_params.fold_arities(), &layer_depths).enumerate()
+		{
+			if oracle != 0 {
+				log_n_cosets -= arity;
+			}
+			parser.read_section(|parser| {
+				Ok(ProofSectionContents::QueryOpening {
+					query,
+					oracle,
+					values: parser.read_vec(1 << arity)?,
+					path: parser
+						.read_vec(merkle_scheme.opening_path_len(log_n_cosets, layer_depth))?,
+				})
+			})?;
+		}
+	}
+
+	if !parser.remaining.is_empty() {
+		return Err(VerificationError::Transcript(transcript::Error::TranscriptNotEmpty {
+			remaining: parser.remaining.len(),
+		})
+		.into());
+	}
+
+	Ok(ProofInspection {
+		sections: parser.sections,
+	})
+}
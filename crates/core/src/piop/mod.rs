@@ -29,18 +29,58 @@
 //! At the end of the interleaved sumcheck-FRI invocation, the verifier tests consistency of the
 //! claimed piecewise evaluations against the final FRI output.
 //!
+//! The [`basefold`] module provides an alternative commitment scheme that encodes the batch with a
+//! random foldable code, the [`stir`] module one that tests the proximity of the Reed–Solomon
+//! codeword with STIR instead of FRI, and [`PIOPCommitmentScheme`] abstracts over the three.
+//!
 //! [DP24]: <https://eprint.iacr.org/2024/504>
 
+pub mod basefold;
+mod batch;
 pub mod commit;
+mod commit_params;
+mod cost_model;
 mod error;
+mod incremental;
+mod inspect;
 mod logging;
+mod multi_commit;
+mod multi_point;
+mod observer;
+mod options;
 mod prove;
+mod rate_batches;
+mod scheme;
+mod scratch;
+mod static_commit;
+pub mod stir;
 #[cfg(test)]
 mod tests;
 mod util;
 mod verify;
+mod witness_layout;
 
+pub use batch::BatchCommitMeta;
 pub use commit::*;
+pub use commit_params::CommitParamsBuilder;
+pub use cost_model::{
+	GpuProfile, HardwareProfile, ProverTimeEstimate, SimdClass, estimate_prover_time,
+};
 pub use error::*;
+pub use incremental::{IncrementalCommitMeta, IncrementalCommitOutput};
+pub use inspect::{ProofInspection, ProofSection, ProofSectionContents, inspect_proof};
+pub use multi_commit::{MultiCommitMeta, SplitClaims};
+pub use multi_point::{CommittedEvalClaim, MultiPointClaims};
+pub use observer::{PIOPPhase, PhaseObserver, PhaseTimings};
+pub use options::PIOPOptions;
 pub use prove::*;
-pub use verify::{CommitMeta, PIOPSumcheckClaim, make_commit_params_with_optimal_arity, verify};
+pub use rate_batches::RateBatchedCommitMeta;
+pub use scheme::PIOPCommitmentScheme;
+pub use scratch::ProveScratch;
+pub use static_commit::{StaticCommitMeta, StaticCommitment};
+pub use verify::{
+	BatchVerifyInstance, CommitMeta, PIOPSumcheckClaim, make_commit_params_with_optimal_arity,
+	verify, verify_batch, verify_incremental, verify_multi_commit, verify_rate_batches,
+	verify_with_options, verify_with_static, verify_zk,
+};
+pub use witness_layout::{CommittedColumn, CommittedWitnessBuffer};
@@ -0,0 +1,236 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::iter;
+
+use binius_field::Field;
+use binius_utils::bail;
+
+use super::{
+	error::Error,
+	verify::{CommitMeta, PIOPSumcheckClaim},
+};
+
+/// Metadata about several independent commitments to batches of multilinears opened in one proof.
+///
+/// A proof may open more than one commitment, for instance a commitment to preprocessed columns
+/// that is computed once and reused across proofs and a commitment to the witness of each proof.
+/// Unlike a [`super::StaticCommitment`], whose codeword joins the FRI instance of the dynamic
+/// batch, each commitment has its own [`CommitMeta`] and FRI parameters, and is opened with its
+/// own interleaved sumcheck and FRI instance on a shared transcript, by
+/// [`super::prove_multi_commit`] and [`super::verify_multi_commit`].
+///
+/// The claims of the proof reference the committed multilinears by a joint index, which runs over
+/// the committed multilinears of each commitment in turn, and are routed to the commitment that
+/// holds the multilinear they reference.
+#[derive(Debug)]
+pub struct MultiCommitMeta {
+	commit_metas: Vec<CommitMeta>,
+	/// Maps each joint committed index to its commitment and its index within the commitment.
+	committed_locations: Vec<(usize, usize)>,
+}
+
+impl MultiCommitMeta {
+	/// Constructs a new [`MultiCommitMeta`].
+	///
+	/// ## Arguments
+	///
+	/// * `commit_metas` - the metadata of the committed batch of each commitment, in the order the
+	///   commitments are opened
+	pub fn new(commit_metas: Vec<CommitMeta>) -> Self {
+		let committed_locations = commit_metas
+			.iter()
+			.enumerate()
+			.flat_map(|(commitment, commit_meta)| {
+				(0..commit_meta.total_multilins()).map(move |index| (commitment, index))
+			})
+			.collect();
+		Self::with_locations(commit_metas, committed_locations)
+	}
+
+	/// Constructs a new [`MultiCommitMeta`] with the joint committed indices assigned by
+	/// `committed_locations`.
+	///
+	/// The indices within each commitment must be assigned in ascending order of joint index.
+	pub(super) fn with_locations(
+		commit_metas: Vec<CommitMeta>,
+		committed_locations: Vec<(usize, usize)>,
+	) -> Self {
+		debug_assert_eq!(
+			committed_locations.len(),
+			commit_metas
+				.iter()
+				.map(|commit_meta| commit_meta.total_multilins())
+				.sum::<usize>()
+		);
+		Self {
+			commit_metas,
+			committed_locations,
+		}
+	}
+
+	/// Returns the number of commitments.
+	pub fn n_commitments(&self) -> usize {
+		self.commit_metas.len()
+	}
+
+	/// Returns the metadata of the committed batch of each commitment.
+	pub fn commit_metas(&self) -> &[CommitMeta] {
+		&self.commit_metas
+	}
+
+	/// Returns the total number of committed multilinears over all commitments.
+	pub fn total_multilins(&self) -> usize {
+		self.committed_locations.len()
+	}
+
+	/// Returns the commitment of a committed multilinear and its index within the commitment.
+	pub fn committed_location(&self, committed: usize) -> Result<(usize, usize), Error> {
+		self.committed_locations
+			.get(committed)
+			.copied()
+			.ok_or(Error::InvalidCommittedId {
+				max_index: self.committed_locations.len(),
+			})
+	}
+
+	/// Splits the committed multilinears, in joint order, into the committed multilinears of each
+	/// commitment.
+	pub fn split_committed<T: Clone>(&self, committed: &[T]) -> Result<Vec<Vec<T>>, Error> {
+		if committed.len() != self.committed_locations.len() {
+			bail!(Error::IncorrectNumberOfMultilins {
+				expected: self.committed_locations.len(),
+				actual: committed.len(),
+			});
+		}
+		let mut splits = self
+			.commit_metas
+			.iter()
+			.map(|commit_meta| Vec::with_capacity(commit_meta.total_multilins()))
+			.collect::<Vec<_>>();
+		for (item, &(commitment, _)) in iter::zip(committed, &self.committed_locations) {
+			splits[commitment].push(item.clone());
+		}
+		Ok(splits)
+	}
+
+	/// Splits sumcheck claims referencing the joint committed indices into the claims of each
+	/// commitment.
+	///
+	/// Returns, for each commitment, the indices of the transparent polynomials its claims
+	/// reference, in ascending order, and the claims with their committed and transparent indices
+	/// remapped to the commitment. Since the transparents of the joint claims are in ascending
+	/// order by number of variables, so are the transparents selected for each commitment.
+	///
+	/// ## Arguments
+	///
+	/// * `n_transparents` - the number of transparent polynomials referenced by the joint claims
+	/// * `claims` - the sumcheck claims referencing the joint committed indices
+	pub fn split_claims<F: Field>(
+		&self,
+		n_transparents: usize,
+		claims: &[PIOPSumcheckClaim<F>],
+	) -> Result<Vec<SplitClaims<F>>, Error> {
+		let mut referenced = vec![vec![false; n_transparents]; self.n_commitments()];
+		for claim in claims {
+			let (commitment, _) = self.committed_location(claim.committed)?;
+			if claim.transparent >= n_transparents {
+				bail!(Error::InvalidTransparentId {
+					max_index: n_transparents,
+				});
+			}
+			referenced[commitment][claim.transparent] = true;
+		}
+
+		let mut splits = referenced
+			.into_iter()
+			.map(|referenced| {
+				let transparent_indices = (0..n_transparents)
+					.filter(|&index| referenced[index])
+					.collect::<Vec<_>>();
+				SplitClaims {
+					transparent_indices,
+					claims: Vec::new(),
+				}
+			})
+			.collect::<Vec<_>>();
+		for claim in claims {
+			let (commitment, committed) = self.committed_location(claim.committed)?;
+			let split = &mut splits[commitment];
+			let transparent = split
+				.transparent_indices
+				.binary_search(&claim.transparent)
+				.expect("referenced transparents are selected for the commitment above");
+			split.claims.push(PIOPSumcheckClaim {
+				n_vars: claim.n_vars,
+				committed,
+				transparent,
+				sum: claim.sum,
+			});
+		}
+		Ok(splits)
+	}
+}
+
+/// The sumcheck claims of one commitment of a [`MultiCommitMeta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitClaims<F: Field> {
+	/// The indices of the transparent polynomials of the joint claims referenced by the
+	/// commitment, in ascending order.
+	pub transparent_indices: Vec<usize>,
+	/// The claims of the commitment, referencing committed multilinears by their index within the
+	/// commitment and transparents by their position in `transparent_indices`.
+	pub claims: Vec<PIOPSumcheckClaim<F>>,
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::BinaryField128b;
+
+	use super::*;
+
+	#[test]
+	fn test_multi_commit_meta_split() {
+		let meta = MultiCommitMeta::new(vec![
+			CommitMeta::with_vars([6, 8]),
+			CommitMeta::with_vars([4, 5, 5]),
+		]);
+
+		assert_eq!(meta.n_commitments(), 2);
+		assert_eq!(meta.total_multilins(), 5);
+		assert_eq!(meta.committed_location(1).unwrap(), (0, 1));
+		assert_eq!(meta.committed_location(2).unwrap(), (1, 0));
+		assert!(meta.committed_location(5).is_err());
+		assert_eq!(
+			meta.split_committed(&["a", "b", "c", "d", "e"]).unwrap(),
+			vec![vec!["a", "b"], vec!["c", "d", "e"]]
+		);
+
+		let claim = |n_vars, committed, transparent| PIOPSumcheckClaim {
+			n_vars,
+			committed,
+			transparent,
+			sum: BinaryField128b::ONE,
+		};
+		let splits = meta
+			.split_claims(3, &[claim(8, 1, 2), claim(4, 2, 0), claim(5, 4, 1)])
+			.unwrap();
+		assert_eq!(
+			splits,
+			vec![
+				SplitClaims {
+					transparent_indices: vec![2],
+					claims: vec![claim(8, 1, 0)],
+				},
+				SplitClaims {
+					transparent_indices: vec![0, 1],
+					claims: vec![claim(4, 0, 0), claim(5, 2, 1)],
+				},
+			]
+		);
+
+		assert!(matches!(
+			meta.split_claims(2, &[claim(8, 1, 2)]),
+			Err(Error::InvalidTransparentId { .. })
+		));
+	}
+}
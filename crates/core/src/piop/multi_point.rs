@@ -0,0 +1,125 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{Field, PackedField};
+use binius_math::{MultilinearExtension, eq_ind_partial_eval};
+use binius_utils::bail;
+
+use super::{
+	error::Error,
+	verify::{CommitMeta, PIOPSumcheckClaim},
+};
+use crate::transparent::eq_ind::EqIndPartialEval;
+
+/// A claimed evaluation of a committed multilinear at a point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommittedEvalClaim<F: Field> {
+	/// Index of the committed multilinear.
+	pub committed: usize,
+	/// The evaluation point, with one coordinate per variable of the committed multilinear.
+	pub eval_point: Vec<F>,
+	/// The claimed evaluation.
+	pub eval: F,
+}
+
+/// Evaluation claims at several distinct points on one committed batch, as PIOP sumcheck claims.
+///
+/// The evaluation of a multilinear $t$ at a point $r$ is the sum over the hypercube of $t$ times
+/// the equality indicator $\text{eq}(X, r)$. Each distinct evaluation point therefore becomes one
+/// transparent multilinear, and each evaluation claim a [`PIOPSumcheckClaim`] on the product of a
+/// committed multilinear and the transparent of its point. [`super::prove`] and
+/// [`super::verify`] batch all of these into their interleaved sumcheck, so the claims are opened
+/// with a single FRI proof, without first reducing them to a common point.
+///
+/// Claims sharing a point share its transparent, so the sumcheck cost grows with the number of
+/// distinct points rather than the number of claims.
+#[derive(Debug, Clone)]
+pub struct MultiPointClaims<F: Field> {
+	/// The distinct evaluation points, in ascending order by number of variables.
+	eval_points: Vec<Vec<F>>,
+	sumcheck_claims: Vec<PIOPSumcheckClaim<F>>,
+}
+
+impl<F: Field> MultiPointClaims<F> {
+	/// Constructs the sumcheck claims for evaluation claims on the batch described by
+	/// `commit_meta`.
+	pub fn new(commit_meta: &CommitMeta, claims: &[CommittedEvalClaim<F>]) -> Result<Self, Error> {
+		let committed_n_vars = commit_meta
+			.n_multilins_by_vars()
+			.iter()
+			.enumerate()
+			.flat_map(|(n_vars, &count)| std::iter::repeat_n(n_vars, count))
+			.collect::<Vec<_>>();
+
+		for (index, claim) in claims.iter().enumerate() {
+			let Some(&n_vars) = committed_n_vars.get(claim.committed) else {
+				bail!(Error::InvalidCommittedId {
+					max_index: committed_n_vars.len(),
+				});
+			};
+			if claim.eval_point.len() != n_vars {
+				bail!(Error::SumcheckClaimVariablesMismatch { index });
+			}
+		}
+
+		let mut eval_points = Vec::<Vec<F>>::new();
+		for claim in claims {
+			if !eval_points.contains(&claim.eval_point) {
+				eval_points.push(claim.eval_point.clone());
+			}
+		}
+		eval_points.sort_by_key(|point| point.len());
+
+		let sumcheck_claims = claims
+			.iter()
+			.map(|claim| PIOPSumcheckClaim {
+				n_vars: claim.eval_point.len(),
+				committed: claim.committed,
+				transparent: eval_points
+					.iter()
+					.position(|point| point == &claim.eval_point)
+					.expect("every claim point is in eval_points"),
+				sum: claim.eval,
+			})
+			.collect();
+
+		Ok(Self {
+			eval_points,
+			sumcheck_claims,
+		})
+	}
+
+	/// Returns the distinct evaluation points, in ascending order by number of variables.
+	pub fn eval_points(&self) -> &[Vec<F>] {
+		&self.eval_points
+	}
+
+	/// Returns the sumcheck claims, which reference the transparents by index into
+	/// [`Self::eval_points`].
+	pub fn sumcheck_claims(&self) -> &[PIOPSumcheckClaim<F>] {
+		&self.sumcheck_claims
+	}
+
+	/// Returns the equality indicators of the evaluation points, as transparent polynomials for
+	/// [`super::verify`].
+	pub fn transparent_polys(&self) -> Vec<EqIndPartialEval<F>> {
+		self.eval_points
+			.iter()
+			.map(|point| EqIndPartialEval::new(point.clone()))
+			.collect()
+	}
+
+	/// Returns the equality indicators of the evaluation points, as transparent multilinears for
+	/// [`super::prove`].
+	pub fn transparent_multilins<P>(&self) -> Result<Vec<MultilinearExtension<P>>, Error>
+	where
+		P: PackedField<Scalar = F>,
+	{
+		self.eval_points
+			.iter()
+			.map(|point| {
+				let mle = MultilinearExtension::new(point.len(), eq_ind_partial_eval::<P>(point))?;
+				Ok(mle)
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,121 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{
+	collections::HashMap,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+/// A phase of committing, proving, or verifying with the PIOP compiler, reported to a
+/// [`PhaseObserver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PIOPPhase {
+	/// The Reed–Solomon encoding of the committed message.
+	Ntt,
+	/// The construction of the Merkle tree over the committed codeword.
+	MerkleCommit,
+	/// The sumcheck rounds of the interleaved sumcheck and FRI protocol.
+	Sumcheck,
+	/// The FRI fold rounds, including the commitments to the round oracles.
+	FriFold,
+	/// The FRI terminal codeword and query phase.
+	FriQuery,
+}
+
+/// Receives the start and end of the phases of committing, proving, and verifying.
+///
+/// The sumcheck and fold phases are entered once per round. In a prover round, the sumcheck fold
+/// runs concurrently with the FRI fold, so the callbacks may be invoked from several threads, and
+/// a [`PIOPPhase::Sumcheck`] phase may overlap a [`PIOPPhase::FriFold`] phase. A phase never
+/// overlaps itself.
+///
+/// The unit type is the observer that ignores all phases.
+pub trait PhaseObserver: Sync {
+	/// Called when a phase starts.
+	fn on_phase_start(&self, phase: PIOPPhase) {
+		let _ = phase;
+	}
+
+	/// Called when a phase ends.
+	fn on_phase_end(&self, phase: PIOPPhase) {
+		let _ = phase;
+	}
+}
+
+impl PhaseObserver for () {}
+
+impl<O: PhaseObserver + ?Sized> PhaseObserver for &O {
+	fn on_phase_start(&self, phase: PIOPPhase) {
+		(**self).on_phase_start(phase)
+	}
+
+	fn on_phase_end(&self, phase: PIOPPhase) {
+		(**self).on_phase_end(phase)
+	}
+}
+
+/// Runs `f` as a phase reported to `observer`.
+pub(super) fn observe_phase<T>(
+	observer: &impl PhaseObserver,
+	phase: PIOPPhase,
+	f: impl FnOnce() -> T,
+) -> T {
+	observer.on_phase_start(phase);
+	let result = f();
+	observer.on_phase_end(phase);
+	result
+}
+
+/// A [`PhaseObserver`] that accumulates the wall-clock time spent in each phase.
+#[derive(Debug, Default)]
+pub struct PhaseTimings {
+	phases: Mutex<HashMap<PIOPPhase, PhaseTiming>>,
+}
+
+#[derive(Debug, Default)]
+struct PhaseTiming {
+	started: Option<Instant>,
+	total: Duration,
+	count: usize,
+}
+
+impl PhaseTimings {
+	/// Constructs an observer with no recorded phases.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the total time spent in a phase over all of its completed runs.
+	pub fn total(&self, phase: PIOPPhase) -> Duration {
+		self.phases
+			.lock()
+			.expect("mutex is always available")
+			.get(&phase)
+			.map_or(Duration::ZERO, |timing| timing.total)
+	}
+
+	/// Returns the number of completed runs of a phase.
+	pub fn count(&self, phase: PIOPPhase) -> usize {
+		self.phases
+			.lock()
+			.expect("mutex is always available")
+			.get(&phase)
+			.map_or(0, |timing| timing.count)
+	}
+}
+
+impl PhaseObserver for PhaseTimings {
+	fn on_phase_start(&self, phase: PIOPPhase) {
+		let mut phases = self.phases.lock().expect("mutex is always available");
+		phases.entry(phase).or_default().started = Some(Instant::now());
+	}
+
+	fn on_phase_end(&self, phase: PIOPPhase) {
+		let mut phases = self.phases.lock().expect("mutex is always available");
+		let timing = phases.entry(phase).or_default();
+		if let Some(started) = timing.started.take() {
+			timing.total += started.elapsed();
+			timing.count += 1;
+		}
+	}
+}
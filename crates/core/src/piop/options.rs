@@ -0,0 +1,70 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::fmt;
+
+use super::observer::PhaseObserver;
+use crate::merkle_tree::StorageConfig;
+
+/// Options for committing, proving, and verifying with the PIOP compiler that do not affect the
+/// proof.
+///
+/// The options are passed to [`super::commit_with_options`], [`super::prove_with_options`], and
+/// [`super::verify_with_options`], each of which reads only the options that apply to it. By
+/// default, the committed codeword is held in memory and no phases are reported.
+#[derive(Clone)]
+pub struct PIOPOptions<'a> {
+	storage: StorageConfig,
+	observer: &'a dyn PhaseObserver,
+}
+
+impl<'a> PIOPOptions<'a> {
+	/// Constructs the default options.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets where the committed codeword is stored until the FRI query phase.
+	///
+	/// With [`StorageConfig::Mapped`], the codeword is held in a memory-mapped temporary file and
+	/// the query phase only reads back the opened cosets. Together with a Merkle tree prover
+	/// configured with the same storage, this allows committing codewords larger than the
+	/// available memory. The commitment is the same for every storage.
+	pub fn with_storage(mut self, storage: StorageConfig) -> Self {
+		self.storage = storage;
+		self
+	}
+
+	/// Sets the observer that the phases of committing, proving, and verifying are reported to,
+	/// for instance a [`super::PhaseTimings`] to time them.
+	pub fn with_observer(mut self, observer: &'a dyn PhaseObserver) -> Self {
+		self.observer = observer;
+		self
+	}
+
+	/// Where the committed codeword is stored.
+	pub fn storage(&self) -> &StorageConfig {
+		&self.storage
+	}
+
+	/// The observer that the phases are reported to.
+	pub fn observer(&self) -> &'a dyn PhaseObserver {
+		self.observer
+	}
+}
+
+impl Default for PIOPOptions<'_> {
+	fn default() -> Self {
+		Self {
+			storage: StorageConfig::Memory,
+			observer: &(),
+		}
+	}
+}
+
+impl fmt::Debug for PIOPOptions<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PIOPOptions")
+			.field("storage", &self.storage)
+			.finish_non_exhaustive()
+	}
+}
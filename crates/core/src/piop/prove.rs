@@ -1,17 +1,17 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::{borrow::Cow, ops::Deref};
+use std::{borrow::Cow, iter, ops::Deref, slice};
 
 use binius_compute::{
-	ComputeLayer, ComputeMemory,
+	ComputeLayer, ComputeMemory, FSlice, FSliceMut, SizedSlice,
 	alloc::{BumpAllocator, ComputeAllocator, HostBumpAllocator},
 	cpu::CpuMemory,
 };
 use binius_field::{
-	BinaryField, PackedExtension, PackedField, PackedFieldIndexable, TowerField,
-	packed::PackedSliceMut,
+	BinaryField, ExtensionField, Field, PackedExtension, PackedField, PackedFieldIndexable,
+	TowerField, packed::PackedSliceMut,
 };
-use binius_math::{MLEDirectAdapter, MultilinearExtension, MultilinearPoly};
+use binius_math::{MultilinearExtension, MultilinearPoly};
 use binius_maybe_rayon::{iter::IntoParallelIterator, prelude::*};
 use binius_ntt::AdditiveNTT;
 use binius_utils::{
@@ -20,26 +20,35 @@ use binius_utils::{
 	random_access_sequence::{RandomAccessSequenceMut, SequenceSubrangeMut},
 	sorting::is_sorted_ascending,
 };
-use itertools::{Itertools, chain};
+use bytemuck::zeroed_vec;
+use itertools::{Itertools, chain, izip};
+use rand::RngCore;
 
 use super::{
 	error::Error,
-	verify::{PIOPSumcheckClaim, make_sumcheck_claim_descs},
+	incremental::{IncrementalCommitMeta, IncrementalCommitOutput, combine_part_commitments},
+	multi_commit::MultiCommitMeta,
+	observer::{PIOPPhase, PhaseObserver, observe_phase},
+	options::PIOPOptions,
+	rate_batches::RateBatchedCommitMeta,
+	static_commit::StaticCommitment,
+	verify::{PIOPSumcheckClaim, hiding_commit_meta, make_sumcheck_claim_descs},
+	witness_layout::CommittedWitnessBuffer,
 };
 use crate::{
 	fiat_shamir::{CanSample, Challenger},
-	merkle_tree::{MerkleTreeProver, MerkleTreeScheme},
+	merkle_tree::{MerkleTreeProver, MerkleTreeScheme, StorageConfig},
 	oracle::OracleId,
 	piop::{
 		CommitMeta,
 		logging::{FriFoldRoundsData, SumcheckBatchProverDimensionsData},
 	},
 	protocols::{
-		fri::{self, FRIFolder, FRIParams, FoldRoundOutput},
+		fri::{self, FRIFolder, FRIMask, FRIParams, FoldRoundOutput},
 		sumcheck::{
-			self, SumcheckClaim,
+			SumcheckClaim,
 			prove::{SumcheckProver, front_loaded::BatchProver as SumcheckBatchProver},
-			v3::bivariate_product::BivariateSumcheckProver,
+			v3::bivariate_product::{BivariateSumcheckProver, SumcheckMultilinear},
 		},
 	},
 	transcript::ProverTranscript,
@@ -75,7 +84,7 @@ fn reverse_index_bits<T: Copy>(collection: &mut impl RandomAccessSequenceMut<T>)
 // * multilinears are sorted in ascending order by number of packed variables
 // * `message_buffer` is initialized to all zeros
 // * `message_buffer` is larger than the total number of scalars in the multilinears
-fn merge_multilins<F, P, Data>(
+pub(super) fn merge_multilins<F, P, Data>(
 	multilins: &[MultilinearExtension<P, Data>],
 	message_buffer: &mut [P],
 ) where
@@ -137,6 +146,31 @@ pub fn commit<F, FEncode, P, M, NTT, MTScheme, MTProver>(
 	merkle_prover: &MTProver,
 	multilins: &[M],
 ) -> Result<fri::CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	M: MultilinearPoly<P>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	commit_with_options(fri_params, ntt, merkle_prover, multilins, &PIOPOptions::default())
+}
+
+/// Commits a batch of multilinear polynomials like [`commit`], with the given options.
+///
+/// The codeword is stored as configured by [`PIOPOptions::with_storage`]. The start and end of the
+/// Reed–Solomon encoding and of the Merkle tree construction are reported to the observer as the
+/// [`PIOPPhase::Ntt`] and [`PIOPPhase::MerkleCommit`] phases. The result is identical to calling
+/// [`commit`].
+pub fn commit_with_options<F, FEncode, P, M, NTT, MTScheme, MTProver>(
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	multilins: &[M],
+	options: &PIOPOptions,
+) -> Result<fri::CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
 where
 	F: TowerField,
 	FEncode: BinaryField,
@@ -152,34 +186,1575 @@ where
 		.map(|(i, unpacked_committed)| {
 			packed_committed(OracleId::from_index(i), unpacked_committed)
 		})
-		.collect::<Result<Vec<_>, _>>()?;
-	if !is_sorted_ascending(packed_multilins.iter().map(|mle| mle.n_vars())) {
-		return Err(Error::CommittedsNotSorted);
+		.collect::<Result<Vec<_>, _>>()?;
+	if !is_sorted_ascending(packed_multilins.iter().map(|mle| mle.n_vars())) {
+		return Err(Error::CommittedsNotSorted);
+	}
+
+	let log_message_len = fri_params.rs_code().log_dim() + fri_params.log_batch_size();
+	let message_len = 1 << log_message_len.saturating_sub(P::LOG_WIDTH);
+	let mut codeword = fri::CodewordStorage::zeroed(
+		options.storage(),
+		message_len << fri_params.rs_code().log_inv_rate(),
+	)?;
+	merge_multilins(&packed_multilins, &mut codeword[..message_len]);
+
+	let observer = options.observer();
+	observe_phase(&observer, PIOPPhase::Ntt, || {
+		fri::encode_interleaved_prefilled(fri_params, ntt, &mut codeword)
+	})?;
+	let output = observe_phase(&observer, PIOPPhase::MerkleCommit, || {
+		fri::commit_encoded(fri_params, merkle_prover, codeword)
+	})?;
+
+	Ok(output)
+}
+
+/// Commits a batch of multilinear polynomials like [`commit`], with a random hiding multilinear
+/// appended to the batch.
+///
+/// The hiding multilinear has one variable fewer than the FRI parameters, which must be chosen for
+/// [`CommitMeta::with_hiding_multilin`] of the batch. Having the most variables, it is the last
+/// multilinear in commitment order, with committed index `multilins.len()`. The message is written
+/// in reverse commitment order, so its evaluations occupy the lower half of the message, at offset
+/// zero, and the committed multilinears the upper half. The lower half of the message is the first
+/// half of the coefficients of each interleaved Reed–Solomon codeword, so any set of symbols of a
+/// codeword no larger than that is uniformly random, whatever the committed multilinears. The batch
+/// is proven with [`prove_zk`], which takes the returned hiding multilinear.
+///
+/// ## Throws
+///
+/// * [`Error::CommitParamsMismatch`] if the batch and the hiding multilinear do not fill the
+///   message of the parameters
+pub fn commit_hiding<F, FEncode, P, M, NTT, MTScheme, MTProver>(
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	multilins: &[M],
+	mut rng: impl RngCore,
+) -> Result<
+	(fri::CommitOutput<P, MTScheme::Digest, MTProver::Committed>, MultilinearExtension<P>),
+	Error,
+>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	M: MultilinearPoly<P>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	let n_fold_rounds = fri_params.n_fold_rounds();
+	let n_hiding_vars = n_fold_rounds.saturating_sub(1);
+	let hiding_multilin = MultilinearExtension::new(
+		n_hiding_vars,
+		iter::repeat_with(|| P::random(&mut rng))
+			.take(1 << n_hiding_vars.saturating_sub(P::LOG_WIDTH))
+			.collect(),
+	)?;
+
+	let mut packed_multilins = multilins
+		.iter()
+		.enumerate()
+		.map(|(i, unpacked_committed)| {
+			packed_committed(OracleId::from_index(i), unpacked_committed)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	packed_multilins
+		.push(MultilinearExtension::new(n_hiding_vars, Cow::Borrowed(hiding_multilin.evals()))?);
+	if !is_sorted_ascending(packed_multilins.iter().map(|mle| mle.n_vars())) {
+		return Err(Error::CommittedsNotSorted);
+	}
+
+	// The hiding multilinear comes last in commitment order, and `merge_multilins` writes the
+	// multilinears in reverse order, so the hiding multilinear is written at offset zero and fills
+	// the lower half of the message as long as the batch fits in the upper half.
+	let hiding_meta = CommitMeta::with_vars(packed_multilins.iter().map(|mle| mle.n_vars()));
+	if hiding_meta.total_vars() != n_fold_rounds {
+		bail!(Error::CommitParamsMismatch {
+			expected: hiding_meta.total_vars(),
+			actual: n_fold_rounds,
+		});
+	}
+
+	let output = fri::commit_interleaved_with(fri_params, ntt, merkle_prover, |message_buffer| {
+		merge_multilins(&packed_multilins, message_buffer)
+	})?;
+	Ok((output, hiding_multilin))
+}
+
+/// Returns the number of packed variables and the scalar offset into the interleaved message of
+/// each committed multilinear, by commitment index.
+pub(super) fn message_slots(commit_meta: &CommitMeta) -> Vec<(usize, usize)> {
+	// Multilinears are written into the message in reverse commitment order, so the offset of
+	// each is the total size of all multilinears after it.
+	let n_varss = commit_meta
+		.n_multilins_by_vars()
+		.iter()
+		.enumerate()
+		.flat_map(|(n_vars, &count)| iter::repeat_n(n_vars, count))
+		.collect::<Vec<_>>();
+	let mut slots = vec![(0, 0); n_varss.len()];
+	let mut offset = 0;
+	for (slot, &n_vars) in iter::zip(&mut slots, &n_varss).rev() {
+		*slot = (n_vars, offset);
+		offset += 1 << n_vars;
+	}
+	slots
+}
+
+/// Commits a batch of multilinear polynomials stored in a [`CommittedWitnessBuffer`].
+///
+/// The result is identical to calling [`commit`] with the multilinears written to the buffer in
+/// commitment order. Since the buffer already holds the multilinears in the layout of the
+/// interleaved message, the message is read with one contiguous copy, and the only remaining
+/// per-multilinear work is the bit-reversal permutation required by the encoder, done in place.
+pub fn commit_witness_buffer<F, FEncode, P, NTT, MTScheme, MTProver>(
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	buffer: &CommittedWitnessBuffer<P>,
+) -> Result<fri::CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	let message = buffer.message();
+	let log_message_len = fri_params.rs_code().log_dim() + fri_params.log_batch_size();
+	if message.len() << P::LOG_WIDTH > 1 << log_message_len {
+		bail!(Error::CommitParamsTooSmall);
+	}
+
+	let output = fri::commit_interleaved_with(fri_params, ntt, merkle_prover, |message_buffer| {
+		let message_buffer = &mut message_buffer[..message.len()];
+		message_buffer.copy_from_slice(message);
+
+		reverse_slot_index_bits(message_buffer, buffer.slots());
+	})?;
+
+	Ok(output)
+}
+
+/// Reverses the bits of the scalar indices within each committed multilinear of an interleaved
+/// message that holds the multilinears in natural order, as laid out by [`message_slots`].
+fn reverse_slot_index_bits<P: PackedField>(message_buffer: &mut [P], slots: &[(usize, usize)]) {
+	// Slots are in descending order of size from the start of the message, so the packed
+	// chunks of the large multilinears can be split off in order.
+	let mut full_packed_chunks = Vec::new();
+	let mut remaining_buffer = message_buffer;
+	let mut slots_iter = slots.iter().rev().peekable();
+	while let Some(&&(n_vars, _)) = slots_iter.peek() {
+		if n_vars < P::LOG_WIDTH {
+			break;
+		}
+		let (chunk, rest) = remaining_buffer.split_at_mut(1 << (n_vars - P::LOG_WIDTH));
+		full_packed_chunks.push(chunk);
+		remaining_buffer = rest;
+		slots_iter.next();
+	}
+	full_packed_chunks
+		.into_par_iter()
+		.for_each(|chunk| reverse_index_bits(&mut PackedSliceMut::new(chunk)));
+
+	let mut scalar_offset = 0;
+	let mut remaining_buffer = PackedSliceMut::new(remaining_buffer);
+	for &(n_vars, _) in slots_iter {
+		let len = 1 << n_vars;
+		reverse_index_bits(&mut SequenceSubrangeMut::new(
+			&mut remaining_buffer,
+			scalar_offset,
+			len,
+		));
+		scalar_offset += len;
+	}
+}
+
+/// Checks that the device-resident committed multilinears match the commit metadata, and returns
+/// the number of packed variables and the scalar offset into the interleaved message of each.
+fn device_committed_slots<F, Mem: ComputeMemory<F>>(
+	commit_meta: &CommitMeta,
+	committed: &[Mem::FSlice<'_>],
+) -> Result<Vec<(usize, usize)>, Error> {
+	let slots = message_slots(commit_meta);
+	if committed.len() < slots.len() {
+		bail!(Error::CommittedMissing {
+			index: committed.len()
+		});
+	}
+	if committed.len() > slots.len() {
+		bail!(Error::InvalidCommittedId {
+			max_index: slots.len()
+		});
+	}
+	for (index, (fslice, &(n_vars, _))) in iter::zip(committed, &slots).enumerate() {
+		if fslice.len() != 1 << n_vars {
+			bail!(Error::CommittedVariablesMismatch {
+				index,
+				expected: n_vars,
+				actual: fslice.len().ilog2() as usize,
+			});
+		}
+	}
+	Ok(slots)
+}
+
+/// Commits a batch of multilinear polynomials that reside in compute-layer device memory.
+///
+/// Each device buffer holds the packed form of a committed multilinear over `F`, in commitment
+/// order, as for [`prove_device_committed`]. The buffers are copied from the device directly into
+/// their positions in the interleaved message, so the witnesses are never materialized separately
+/// in host memory. The result is identical to calling [`commit`] with the same multilinears.
+pub fn commit_device<Hal, F, FEncode, P, NTT, MTScheme, MTProver>(
+	hal: &Hal,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	commit_meta: &CommitMeta,
+	committed: &[FSlice<'_, F, Hal>],
+) -> Result<fri::CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+where
+	Hal: ComputeLayer<F>,
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedFieldIndexable<Scalar = F> + PackedExtension<FEncode>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	let slots = device_committed_slots::<F, Hal::DevMem>(commit_meta, committed)?;
+	let total_vars = commit_meta.total_vars();
+	let log_message_len = fri_params.rs_code().log_dim() + fri_params.log_batch_size();
+	if total_vars > log_message_len {
+		bail!(Error::CommitParamsTooSmall);
+	}
+
+	let mut copy_result = Ok(());
+	let output = fri::commit_interleaved_with(fri_params, ntt, merkle_prover, |message_buffer| {
+		let message_scalars = P::unpack_scalars_mut(message_buffer);
+		copy_result = iter::zip(committed, &slots).try_for_each(|(&fslice, &(n_vars, offset))| {
+			hal.copy_d2h(fslice, &mut message_scalars[offset..offset + (1 << n_vars)])
+		});
+		if copy_result.is_ok() {
+			reverse_slot_index_bits(message_buffer, &slots);
+		}
+	})?;
+	copy_result?;
+
+	Ok(output)
+}
+
+/// Commits a batch of multilinears in parts, encoding and hashing each part as soon as all of its
+/// committed multilinears are added.
+///
+/// Unlike [`commit`], which requires all committed multilinears up front, this accepts each
+/// committed multilinear as soon as its witness is generated, in any order, and copies it into its
+/// position in the message of its part, or a whole table of them at once with
+/// [`Self::add_batch`]. The parts are described by an [`IncrementalCommitMeta`], and the committed
+/// multilinears are identified by their joint index.
+///
+/// The Reed–Solomon encoding mixes all positions of a message, so a part can only be encoded once
+/// it is complete. Adding the last multilinear of a part encodes the part and builds its Merkle
+/// tree right away, so the commitment of each part overlaps with the witness generation of the
+/// others, and the caller may drop the witness of a part once it is added. [`Self::finish`] only
+/// hashes the roots of the parts into the commitment. The commitment is opened with
+/// [`prove_incremental`].
+pub struct IncrementalCommitter<'a, F, FEncode, P, NTT, MTProver>
+where
+	F: BinaryField,
+	FEncode: BinaryField,
+	MTProver: MerkleTreeProver<F>,
+{
+	fri_params: &'a FRIParams<F, FEncode>,
+	ntt: &'a NTT,
+	merkle_prover: &'a MTProver,
+	meta: &'a IncrementalCommitMeta,
+	storage: StorageConfig,
+	/// The FRI parameters of the parts that join the FRI instance after the first round.
+	batched_fri_params: Vec<Option<FRIParams<F, FEncode>>>,
+	/// The number of packed variables and the scalar offset into the message of its part of each
+	/// committed multilinear, by joint index.
+	slots: Vec<(usize, usize)>,
+	added: Vec<bool>,
+	parts:
+		Vec<PartState<P, <MTProver::Scheme as MerkleTreeScheme<F>>::Digest, MTProver::Committed>>,
+}
+
+/// The state of a part of an [`IncrementalCommitter`].
+enum PartState<P, Digest, Committed> {
+	/// Some committed multilinears of the part are missing. The message is written to the
+	/// beginning of the codeword buffer, which is allocated when the first multilinear is added.
+	Pending {
+		codeword: Option<fri::CodewordStorage<P>>,
+		n_missing: usize,
+	},
+	/// All committed multilinears of the part are added, and the part is committed.
+	Committed(fri::CommitOutput<P, Digest, Committed>),
+}
+
+impl<'a, F, FEncode, P, NTT, MTScheme, MTProver>
+	IncrementalCommitter<'a, F, FEncode, P, NTT, MTProver>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	/// Constructs a new committer for the parts described by `meta`.
+	///
+	/// `fri_params` are the parameters of the FRI instance that opens the parts, see
+	/// [`IncrementalCommitMeta`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::CommitParamsMismatch`] if `fri_params` do not fold the parts with the most
+	///   variables
+	/// * [`Error::FRI`] if `fri_params` commit no round oracle where a part joins
+	pub fn new(
+		fri_params: &'a FRIParams<F, FEncode>,
+		ntt: &'a NTT,
+		merkle_prover: &'a MTProver,
+		meta: &'a IncrementalCommitMeta,
+	) -> Result<Self, Error> {
+		Self::with_storage(fri_params, ntt, merkle_prover, &StorageConfig::Memory, meta)
+	}
+
+	/// Constructs a new committer for the parts described by `meta`, storing the message and
+	/// codeword of each part as configured by `storage`.
+	///
+	/// With [`StorageConfig::Mapped`], the message of each part is written directly into a
+	/// memory-mapped temporary file, so neither the committed witness nor its codewords need to
+	/// fit in memory.
+	pub fn with_storage(
+		fri_params: &'a FRIParams<F, FEncode>,
+		ntt: &'a NTT,
+		merkle_prover: &'a MTProver,
+		storage: &StorageConfig,
+		meta: &'a IncrementalCommitMeta,
+	) -> Result<Self, Error> {
+		let batched_fri_params = meta.batched_fri_params(fri_params, ntt)?;
+
+		let slots_by_part = meta
+			.part_metas()
+			.iter()
+			.map(message_slots)
+			.collect::<Vec<_>>();
+		let slots = (0..meta.total_multilins())
+			.map(|index| {
+				let (part, index) = meta.committed_location(index)?;
+				Ok(slots_by_part[part][index])
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		let parts = meta
+			.part_metas()
+			.iter()
+			.map(|part_meta| PartState::Pending {
+				codeword: None,
+				n_missing: part_meta.total_multilins(),
+			})
+			.collect();
+		Ok(Self {
+			fri_params,
+			ntt,
+			merkle_prover,
+			meta,
+			storage: storage.clone(),
+			batched_fri_params,
+			added: vec![false; slots.len()],
+			slots,
+			parts,
+		})
+	}
+
+	/// Returns whether all committed multilinears have been added.
+	pub fn is_complete(&self) -> bool {
+		self.added.iter().all(|&added| added)
+	}
+
+	/// Returns the FRI parameters that a part is committed with.
+	pub fn part_fri_params(&self, part: usize) -> &FRIParams<F, FEncode> {
+		self.batched_fri_params[part]
+			.as_ref()
+			.unwrap_or(self.fri_params)
+	}
+
+	/// Returns the root of the Merkle tree of a part, once all of its committed multilinears have
+	/// been added.
+	pub fn part_commitment(&self, part: usize) -> Option<&MTScheme::Digest> {
+		match self.parts.get(part)? {
+			PartState::Committed(output) => Some(&output.commitment),
+			PartState::Pending { .. } => None,
+		}
+	}
+
+	/// Adds the committed multilinear with the given joint index.
+	///
+	/// The multilinear may be defined over a subfield of `F`, as with [`commit`]. If it is the last
+	/// multilinear of its part, the part is encoded and committed.
+	pub fn add<M: MultilinearPoly<P>>(&mut self, index: usize, multilin: &M) -> Result<(), Error> {
+		self.add_batch(index, slice::from_ref(multilin))
+	}
+
+	/// Adds the committed multilinears with consecutive joint indices, starting at `first_index`.
+	///
+	/// This suits witness generation that completes one table at a time: the committed columns of
+	/// a table are added together as soon as the table is filled, after which the caller may drop
+	/// them. All multilinears are checked before any is written, so the committer is unchanged if
+	/// this returns an error on a multilinear.
+	///
+	/// Each part completed by the batch is encoded and its Merkle tree built before this returns.
+	/// When the parts of `meta` are the tables, each batch commits its table, and only the root of
+	/// its tree, returned by [`Self::part_commitment`], enters the commitment, which
+	/// [`Self::finish`] builds from the roots of all parts.
+	pub fn add_batch<M: MultilinearPoly<P>>(
+		&mut self,
+		first_index: usize,
+		multilins: &[M],
+	) -> Result<(), Error> {
+		let packed_multilins = iter::zip(first_index.., multilins)
+			.map(|(index, multilin)| {
+				let &(n_vars, _) = self.slots.get(index).ok_or(Error::InvalidCommittedId {
+					max_index: self.slots.len(),
+				})?;
+				if self.added[index] {
+					bail!(Error::CommittedAlreadyAdded { index });
+				}
+
+				let packed_multilin = packed_committed(OracleId::from_index(index), multilin)?;
+				if packed_multilin.n_vars() != n_vars {
+					bail!(Error::CommittedVariablesMismatch {
+						index,
+						expected: n_vars,
+						actual: packed_multilin.n_vars(),
+					});
+				}
+				Ok(packed_multilin)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		for (index, packed_multilin) in iter::zip(first_index.., &packed_multilins) {
+			self.write_message(index, packed_multilin.evals())?;
+		}
+		for index in first_index..first_index + packed_multilins.len() {
+			self.mark_added(index)?;
+		}
+		Ok(())
+	}
+
+	/// Adds the committed multilinear with the given joint index from a sequence of chunks of its
+	/// packed evaluations.
+	///
+	/// The chunks hold the evaluations of the packed multilinear over `F`, as described in
+	/// [`commit`], in order. Each chunk is copied into the slot of the multilinear in the message
+	/// of its part before the next is requested, so the caller can generate or load the witness one
+	/// chunk at a time. The chunks must hold exactly the packed evaluations of the multilinear; if
+	/// they do not, the multilinear is not added, and may be added again. If it is the last
+	/// multilinear of its part, the part is encoded and committed.
+	pub fn add_chunks<C: AsRef<[P]>>(
+		&mut self,
+		index: usize,
+		chunks: impl IntoIterator<Item = C>,
+	) -> Result<(), Error> {
+		let &(n_vars, offset) = self.slots.get(index).ok_or(Error::InvalidCommittedId {
+			max_index: self.slots.len(),
+		})?;
+		if self.added[index] {
+			bail!(Error::CommittedAlreadyAdded { index });
+		}
+
+		let len = 1 << n_vars.saturating_sub(P::LOG_WIDTH);
+		if n_vars < P::LOG_WIDTH {
+			// The multilinear occupies part of a single packed element, which is small enough to
+			// gather before writing.
+			let packed_evals = chunks
+				.into_iter()
+				.flat_map(|chunk| chunk.as_ref().to_vec())
+				.collect::<Vec<_>>();
+			if packed_evals.len() != len {
+				bail!(Error::CommittedChunksLengthMismatch {
+					index,
+					expected: len,
+					actual: packed_evals.len(),
+				});
+			}
+			self.write_message(index, &packed_evals)?;
+		} else {
+			let message = self.message_mut(index)?;
+			let slot = &mut message[offset >> P::LOG_WIDTH..][..len];
+			let mut n_packed = 0;
+			for chunk in chunks {
+				let chunk = chunk.as_ref();
+				if let Some(dst) = slot.get_mut(n_packed..n_packed + chunk.len()) {
+					dst.copy_from_slice(chunk);
+				}
+				n_packed += chunk.len();
+			}
+			if n_packed != len {
+				bail!(Error::CommittedChunksLengthMismatch {
+					index,
+					expected: len,
+					actual: n_packed,
+				});
+			}
+			reverse_index_bits(&mut PackedSliceMut::new(slot));
+		}
+
+		self.mark_added(index)
+	}
+
+	/// Returns the message of the part of a committed multilinear, allocating the codeword buffer
+	/// of the part if it is the first multilinear written to it.
+	fn message_mut(&mut self, index: usize) -> Result<&mut [P], Error> {
+		let (part, _) = self.meta.committed_location(index)?;
+		let (message_len, codeword_len) = self.part_buffer_lens(part);
+
+		let PartState::Pending { codeword, .. } = &mut self.parts[part] else {
+			unreachable!("a committed part has no missing multilinears");
+		};
+		if codeword.is_none() {
+			*codeword = Some(fri::CodewordStorage::zeroed(&self.storage, codeword_len)?);
+		}
+		let codeword = codeword
+			.as_mut()
+			.expect("the codeword buffer is allocated above");
+		Ok(&mut codeword[..message_len])
+	}
+
+	/// Returns the number of packed elements of the message and of the codeword of a part.
+	fn part_buffer_lens(&self, part: usize) -> (usize, usize) {
+		let part_fri_params = self.part_fri_params(part);
+		let log_message_len =
+			part_fri_params.rs_code().log_dim() + part_fri_params.log_batch_size();
+		let message_len = 1 << log_message_len.saturating_sub(P::LOG_WIDTH);
+		(message_len, message_len << part_fri_params.rs_code().log_inv_rate())
+	}
+
+	/// Writes the packed evaluations of a committed multilinear into its slot of the message of
+	/// its part.
+	fn write_message(&mut self, index: usize, packed_evals: &[P]) -> Result<(), Error> {
+		let (n_vars, offset) = self.slots[index];
+		let message = self.message_mut(index)?;
+		if n_vars >= P::LOG_WIDTH {
+			let chunk = &mut message[offset >> P::LOG_WIDTH..][..1 << (n_vars - P::LOG_WIDTH)];
+			chunk.copy_from_slice(&packed_evals[..chunk.len()]);
+			reverse_index_bits(&mut PackedSliceMut::new(chunk));
+		} else {
+			let packed_eval = packed_evals[0];
+			let len = 1 << n_vars;
+			let mut message = PackedSliceMut::new(message);
+			let mut packed_chunk = SequenceSubrangeMut::new(&mut message, offset, len);
+			for i in 0..len {
+				packed_chunk.set(i, packed_eval.get(i));
+			}
+			reverse_index_bits(&mut packed_chunk);
+		}
+		Ok(())
+	}
+
+	/// Marks a written committed multilinear as added, and commits its part if it is complete.
+	fn mark_added(&mut self, index: usize) -> Result<(), Error> {
+		self.added[index] = true;
+		let (part, _) = self.meta.committed_location(index)?;
+		if let PartState::Pending { n_missing, .. } = &mut self.parts[part] {
+			*n_missing -= 1;
+			if *n_missing == 0 {
+				self.commit_part(part)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Encodes and commits a part with no missing multilinears.
+	fn commit_part(&mut self, part: usize) -> Result<(), Error> {
+		let PartState::Pending {
+			codeword,
+			n_missing: 0,
+		} = &mut self.parts[part]
+		else {
+			return Ok(());
+		};
+		let codeword = match codeword.take() {
+			Some(codeword) => codeword,
+			// A part without committed multilinears has an all-zero message.
+			None => fri::CodewordStorage::zeroed(&self.storage, self.part_buffer_lens(part).1)?,
+		};
+		let output = fri::commit_interleaved_prefilled(
+			self.part_fri_params(part),
+			self.ntt,
+			self.merkle_prover,
+			codeword,
+		)?;
+		self.parts[part] = PartState::Committed(output);
+		Ok(())
+	}
+
+	/// Hashes the roots of the parts into the commitment, once all committed multilinears have
+	/// been added.
+	///
+	/// Every part is already committed as it completes, so this only builds the upper levels of
+	/// the Merkle tree over the roots of the parts.
+	pub fn finish(
+		mut self,
+	) -> Result<IncrementalCommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error> {
+		if let Some(index) = self.added.iter().position(|&added| !added) {
+			bail!(Error::CommittedMissing { index });
+		}
+		for part in 0..self.parts.len() {
+			self.commit_part(part)?;
+		}
+
+		let parts = self
+			.parts
+			.into_iter()
+			.map(|part| match part {
+				PartState::Committed(output) => output,
+				PartState::Pending { .. } => unreachable!("all parts are committed above"),
+			})
+			.collect::<Vec<_>>();
+		let part_commitments = parts
+			.iter()
+			.map(|part| part.commitment.clone())
+			.collect::<Vec<_>>();
+		let commitment = combine_part_commitments(self.merkle_prover.scheme(), &part_commitments)?;
+		Ok(IncrementalCommitOutput { commitment, parts })
+	}
+}
+
+/// Commits a batch of multilinear polynomials in parts whose witnesses are read as sequences of
+/// chunks.
+///
+/// This is the streaming counterpart of [`commit_with_options`], for committed witnesses larger
+/// than the available memory. Each element of `witness_chunks` yields the chunks of the packed
+/// evaluations of one committed multilinear, in joint order, as returned by
+/// [`super::collect_committed_witness_chunks`] for a single part. Every chunk is copied into the
+/// message of its part before the next one is requested, so only one chunk of the witness needs
+/// to be resident at a time, and each part is encoded and committed as soon as its last chunk has
+/// been consumed, as with [`IncrementalCommitter`].
+///
+/// With [`StorageConfig::Mapped`], the messages and codewords are held in memory-mapped temporary
+/// files throughout, and with a Merkle tree prover configured with the same storage, no part of
+/// the commitment needs to fit in memory.
+pub fn commit_streaming<F, FEncode, P, C, I, NTT, MTScheme, MTProver>(
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	storage: &StorageConfig,
+	meta: &IncrementalCommitMeta,
+	witness_chunks: impl IntoIterator<Item = I>,
+) -> Result<IncrementalCommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	C: AsRef<[P]>,
+	I: IntoIterator<Item = C>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	let mut committer =
+		IncrementalCommitter::with_storage(fri_params, ntt, merkle_prover, storage, meta)?;
+	for (index, chunks) in witness_chunks.into_iter().enumerate() {
+		committer.add_chunks(index, chunks)?;
+	}
+	committer.finish()
+}
+
+/// The commitment outputs of each rate batch of a [`RateBatchedCommitMeta`].
+pub type RateBatchedCommitOutput<P, Digest, Committed> =
+	Vec<fri::CommitOutput<P, Digest, Committed>>;
+
+/// Commits each rate batch of a [`RateBatchedCommitMeta`] as its own FRI codeword.
+///
+/// ## Arguments
+///
+/// * `fri_params` - the FRI parameters of each rate batch, as returned by
+///   [`super::CommitParamsBuilder::build_rate_batches`]
+/// * `ntt` - an NTT whose domain contains the evaluation domains of all rate batches
+/// * `rate_batched_meta` - the metadata about the rate batches
+/// * `multilins` - the committed multilinears in joint commitment order, as required by [`commit`]
+pub fn commit_rate_batches<F, FEncode, P, M, NTT, MTScheme, MTProver>(
+	fri_params: &[FRIParams<F, FEncode>],
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	rate_batched_meta: &RateBatchedCommitMeta,
+	multilins: &[M],
+) -> Result<RateBatchedCommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	M: MultilinearPoly<P> + Clone,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	check_n_rate_batches(rate_batched_meta, fri_params.len())?;
+	let multilins_by_batch = rate_batched_meta.split_committed(multilins)?;
+	iter::zip(fri_params, &multilins_by_batch)
+		.map(|(fri_params, multilins)| commit(fri_params, ntt, merkle_prover, multilins))
+		.collect()
+}
+
+/// Proves a batch of sumcheck claims that are products of committed polynomials from a committed
+/// batch and transparent polynomials.
+///
+/// The arguments corresponding to the committed multilinears must be the output of [`commit`].
+/// The committed data is borrowed, so that a commitment to multilinears shared across proofs can
+/// be opened repeatedly.
+///
+/// The temporary host and device buffers of the proof are allocated from the unallocated capacity
+/// of `host_alloc` and `dev_alloc`, and are reclaimed by the allocators when the proof is done, so
+/// that the memory can be reused by the caller's subsequent phases and proofs.
+#[allow(clippy::too_many_arguments)]
+pub fn prove<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	commit_meta: &CommitMeta,
+	committed: &MTProver::Committed,
+	codeword: &[P],
+	committed_multilins: &[M],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedExtension<FEncode>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	prove_with_options(
+		hal,
+		host_alloc,
+		dev_alloc,
+		fri_params,
+		ntt,
+		merkle_prover,
+		commit_meta,
+		committed,
+		codeword,
+		committed_multilins,
+		transparent_multilins,
+		claims,
+		transcript,
+		&PIOPOptions::default(),
+	)
+}
+
+/// Proves a batch of sumcheck claims like [`prove`], with the given options.
+///
+/// The start and end of the sumcheck, FRI fold, and FRI query phases are reported to the observer
+/// of `options`.
+#[allow(clippy::too_many_arguments)]
+pub fn prove_with_options<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	commit_meta: &CommitMeta,
+	committed: &MTProver::Committed,
+	codeword: &[P],
+	committed_multilins: &[M],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+	options: &PIOPOptions,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedExtension<FEncode>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	// The committed multilinears provided by argument are committed *small field* multilinears.
+	// Create multilinears representing the packed polynomials here. Eventually, we would like to
+	// refactor the calling code so that the PIOP only handles *big field* multilinear witnesses.
+	let packed_committed_multilins = committed_multilins
+		.iter()
+		.enumerate()
+		.map(|(i, unpacked_committed)| {
+			packed_committed(OracleId::from_index(i), unpacked_committed)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	prove_packed_committed(
+		hal,
+		host_alloc,
+		dev_alloc,
+		commit_meta,
+		&packed_committed_multilins,
+		transparent_multilins,
+		claims,
+		FRIFolder::new(fri_params, ntt, merkle_prover, codeword, committed)?,
+		transcript,
+		&options.observer(),
+	)
+}
+
+/// Proves a batch of sumcheck claims like [`prove`], hiding the committed multilinears.
+///
+/// The batch must be committed with [`commit_hiding`], with FRI parameters chosen for
+/// [`CommitMeta::with_hiding_multilin`] of `commit_meta`, and `hiding_multilin` is the random
+/// multilinear it returns. Each symbol of the committed codeword that the FRI queries open is then
+/// the sum of a symbol of the encoding of the random first half of the message and a symbol
+/// depending on the witness. As long as the queries open no more symbols of a codeword than it has
+/// random coefficients, which is checked, the opened symbols are uniformly random and independent
+/// of the committed multilinears.
+///
+/// Before the interleaved sumcheck and FRI protocol, the prover commits a random mask for each
+/// committed multilinear and for the hiding multilinear in the same way, and sends the sum of each
+/// claim with the committed multilinear replaced by its mask. After receiving a random coefficient,
+/// the prover proves the claims on the committed multilinears plus the coefficient times their
+/// masks, and FRI folds the same linear combination of the committed and the mask codewords. The
+/// sumcheck round messages, the claimed evaluations, and the FRI round oracles and terminal
+/// codeword are thereby masked as well.
+///
+/// The Merkle paths of the queries include the digests of unopened leaves, so the commitments
+/// should use a Merkle tree prover with salted leaves.
+///
+/// The proof must be verified with [`super::verify_zk`].
+///
+/// ## Throws
+///
+/// * [`fri::Error::InvalidArgs`] if the FRI parameters send no round oracle
+/// * [`Error::CommitParamsMismatch`] if the FRI parameters are not chosen for the hiding batch
+/// * [`Error::HidingQueriesExceedRandomness`] if the FRI queries open more symbols of a codeword
+///   than it has random coefficients
+#[allow(clippy::too_many_arguments)]
+pub fn prove_zk<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	commit_meta: &CommitMeta,
+	committed: &MTProver::Committed,
+	codeword: &[P],
+	committed_multilins: &[M],
+	hiding_multilin: &MultilinearExtension<P>,
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	mut rng: impl RngCore,
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedExtension<FEncode>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	let hiding_meta = hiding_commit_meta(commit_meta, fri_params)?;
+	if hiding_multilin.n_vars() + 1 != fri_params.n_fold_rounds() {
+		bail!(Error::CommittedVariablesMismatch {
+			index: commit_meta.total_multilins(),
+			expected: fri_params.n_fold_rounds() - 1,
+			actual: hiding_multilin.n_vars(),
+		});
+	}
+
+	let mut packed_committed_multilins = committed_multilins
+		.iter()
+		.enumerate()
+		.map(|(i, unpacked_committed)| {
+			packed_committed(OracleId::from_index(i), unpacked_committed)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	packed_committed_multilins.push(MultilinearExtension::new(
+		hiding_multilin.n_vars(),
+		Cow::Borrowed(hiding_multilin.evals()),
+	)?);
+	if !is_sorted_ascending(packed_committed_multilins.iter().map(|mle| mle.n_vars())) {
+		return Err(Error::CommittedsNotSorted);
+	}
+
+	let masks = packed_committed_multilins
+		.iter()
+		.map(|multilin| {
+			let evals = iter::repeat_with(|| P::random(&mut rng))
+				.take(multilin.evals().len())
+				.collect();
+			MultilinearExtension::new(multilin.n_vars(), evals)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	let mask_output =
+		fri::commit_interleaved_with(fri_params, ntt, merkle_prover, |message_buffer| {
+			merge_multilins(&masks, message_buffer)
+		})?;
+	transcript.message().write(&mask_output.commitment);
+
+	let mask_sums = claims
+		.iter()
+		.map(|claim| {
+			let mask = masks
+				.get(claim.committed)
+				.ok_or(Error::InvalidCommittedId {
+					max_index: masks.len(),
+				})?;
+			let transparent = transparent_multilins.get(claim.transparent).ok_or(
+				Error::InvalidTransparentId {
+					max_index: transparent_multilins.len(),
+				},
+			)?;
+			mask_sum(mask, transparent)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	transcript.message().write_scalar_slice(&mask_sums);
+
+	let coeff: F = transcript.sample();
+	let packed_coeff = P::broadcast(coeff);
+	let masked_multilins = iter::zip(&packed_committed_multilins, &masks)
+		.map(|(multilin, mask)| {
+			let evals = iter::zip(multilin.evals(), mask.evals())
+				.map(|(&eval, &mask_eval)| eval + mask_eval * packed_coeff)
+				.collect::<Vec<P>>();
+			MultilinearExtension::new(multilin.n_vars(), evals)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	let masked_claims = iter::zip(claims, &mask_sums)
+		.map(|(claim, &mask_sum)| PIOPSumcheckClaim {
+			sum: claim.sum + coeff * mask_sum,
+			..claim.clone()
+		})
+		.collect::<Vec<_>>();
+
+	let fri_folder = FRIFolder::new(fri_params, ntt, merkle_prover, codeword, committed)?
+		.with_mask(FRIMask {
+			codeword: &mask_output.codeword,
+			committed: &mask_output.committed,
+			coeff,
+		})?;
+	prove_packed_committed(
+		hal,
+		host_alloc,
+		dev_alloc,
+		&hiding_meta,
+		&masked_multilins,
+		transparent_multilins,
+		&masked_claims,
+		fri_folder,
+		transcript,
+		&(),
+	)
+}
+
+/// Computes the sum over the hypercube of the product of a mask and a transparent multilinear.
+fn mask_sum<F, P, M>(mask: &MultilinearExtension<P>, transparent: &M) -> Result<F, Error>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P>,
+{
+	let n_vars = transparent.n_vars();
+	let mut transparent_evals = zeroed_vec::<P>(1 << n_vars.saturating_sub(P::LOG_WIDTH));
+	transparent.subcube_evals(n_vars, 0, 0, &mut transparent_evals)?;
+	let sum = iter::zip(
+		PackedField::iter_slice(mask.evals()),
+		PackedField::iter_slice(&transparent_evals),
+	)
+	.take(1 << n_vars)
+	.map(|(mask, transparent)| mask * transparent)
+	.sum();
+	Ok(sum)
+}
+
+/// Proves a batch of sumcheck claims over a [`StaticCommitment`] and a dynamic batch, opening both
+/// committed codewords with a single FRI proof.
+///
+/// The dynamic batch must be committed with [`StaticCommitment::commit_dynamic`], and its
+/// commitment written to the transcript before. The claims reference the committed multilinears by
+/// their index in [`StaticCommitment::batch_meta`] and the transparent polynomials in ascending
+/// order by number of variables, as returned by [`super::BatchCommitMeta::merge_claims`].
+///
+/// The prover observes the static commitment and samples a coefficient. The claims on each batch
+/// are proven with their own batched sumcheck. FRI folds the codeword of the longer batch, and once
+/// the folded codeword reaches the length of the codeword of the shorter batch, adds the
+/// coefficient times that codeword to it. The sumcheck of the shorter batch starts in that round,
+/// so that it shares the remaining challenges. The proof must be verified with
+/// [`super::verify_with_static`].
+///
+/// ## Arguments
+///
+/// * `committed` - the committed Merkle tree of the dynamic batch
+/// * `codeword` - the codeword of the dynamic batch
+/// * `static_multilins` - the multilinears of the static commitment
+/// * `dynamic_multilins` - the multilinears of the dynamic batch
+#[allow(clippy::too_many_arguments)]
+pub fn prove_with_static<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	static_commitment: &StaticCommitment<F, FEncode, P, MTScheme::Digest, MTProver::Committed>,
+	committed: &MTProver::Committed,
+	codeword: &[P],
+	static_multilins: &[M],
+	dynamic_multilins: &[M],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedExtension<FEncode>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Clone + Send + Sync,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	let meta = static_commitment.meta();
+	transcript.observe().write(static_commitment.commitment());
+	let coeff: F = transcript.sample();
+
+	let splits = meta.split_claims(transparent_multilins.len(), claims)?;
+	let packed_committed_by_system = [static_multilins, dynamic_multilins]
+		.into_iter()
+		.map(|multilins| {
+			multilins
+				.iter()
+				.enumerate()
+				.map(|(i, unpacked_committed)| {
+					packed_committed(OracleId::from_index(i), unpacked_committed)
+				})
+				.collect::<Result<Vec<_>, _>>()
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	let static_codeword: &[P] = static_commitment.codeword();
+	let codewords = [
+		(static_codeword, static_commitment.committed()),
+		(codeword, committed),
+	];
+
+	let (long_system, short_system) = meta.folded_systems();
+	let entry_round = meta.entry_round();
+	let (long_codeword, long_committed) = codewords[long_system];
+	let (short_codeword, short_committed) = codewords[short_system];
+	let fri_folder = FRIFolder::new(
+		static_commitment.fri_params(),
+		ntt,
+		merkle_prover,
+		long_codeword,
+		long_committed,
+	)?
+	.with_batched_at_round(entry_round, short_codeword, short_committed, coeff)?;
+
+	// The buffers of the proof are allocated from the unallocated capacity of the caller's
+	// allocators, which is reclaimed when the proof is done.
+	let host_alloc = HostBumpAllocator::new(host_alloc.remaining());
+	let dev_alloc = BumpAllocator::<_, Hal::DevMem>::new(dev_alloc.remaining());
+
+	let packed_committed_fslices_by_system = packed_committed_by_system
+		.iter()
+		.map(|packed_committed_multilins| {
+			copy_packed_committed_to_device(hal, &dev_alloc, packed_committed_multilins)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	let packed_committed_fslices_by_system = packed_committed_fslices_by_system
+		.iter()
+		.map(|fslices_mut| {
+			fslices_mut
+				.iter()
+				.map(|fslice_mut| Hal::DevMem::as_const(fslice_mut))
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	let make_system_provers = |system: usize| {
+		let split = &splits[system];
+		let transparent_multilins = split
+			.transparent_indices
+			.iter()
+			.map(|&index| transparent_multilins[index].clone())
+			.collect::<Vec<_>>();
+		make_sumcheck_provers(
+			hal,
+			&host_alloc,
+			&dev_alloc,
+			meta.system_meta(system),
+			&packed_committed_fslices_by_system[system],
+			&transparent_multilins,
+			&split.claims,
+		)
+	};
+	let long_provers = make_system_provers(long_system)?;
+	let short_provers = make_system_provers(short_system)?;
+
+	prove_interleaved_sumcheck(
+		meta.system_meta(long_system).total_vars(),
+		long_provers,
+		vec![(entry_round, short_provers)],
+		fri_folder,
+		transcript,
+		&(),
+	)
+}
+
+/// Proves a batch of sumcheck claims over the committed multilinears of an incremental
+/// commitment.
+///
+/// The commitment must be the output of an [`IncrementalCommitter`] for `meta`, and its
+/// [`IncrementalCommitOutput::commitment`] written to the transcript before. The claims reference
+/// the committed multilinears by their joint index in `meta` and the transparent polynomials in
+/// ascending order by number of variables, as for [`prove`].
+///
+/// The prover sends the roots of the parts, which the verifier hashes into the commitment, and
+/// samples a coefficient for each part but the one folded from the first round. The claims on
+/// each part are proven with their own batched sumcheck, which starts in the round where the
+/// codeword of the part joins the FRI instance, so that it shares the remaining challenges. FRI
+/// folds the codeword of the first part with the most variables, and adds the coefficient times
+/// the codeword of each other part to the round oracle of its length. The proof must be verified
+/// with [`super::verify_incremental`].
+///
+/// ## Arguments
+///
+/// * `fri_params` - the FRI parameters the committer was constructed with
+/// * `output` - the output of the committer
+/// * `committed_multilins` - the committed multilinears in joint order
+#[allow(clippy::too_many_arguments)]
+pub fn prove_incremental<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	meta: &IncrementalCommitMeta,
+	output: &IncrementalCommitOutput<P, MTScheme::Digest, MTProver::Committed>,
+	committed_multilins: &[M],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedExtension<FEncode>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Clone + Send + Sync,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	if output.parts.len() != meta.n_parts() {
+		bail!(Error::IncorrectNumberOfCommitments {
+			expected: meta.n_parts(),
+			actual: output.parts.len(),
+		});
+	}
+
+	for part in &output.parts {
+		transcript.message().write(&part.commitment);
+	}
+	let coeffs = meta.sample_coeffs(transcript);
+
+	let splits = meta.split_claims(transparent_multilins.len(), claims)?;
+	let packed_committed_by_part = meta
+		.split_committed(committed_multilins)?
+		.iter()
+		.map(|multilins| {
+			multilins
+				.iter()
+				.enumerate()
+				.map(|(i, unpacked_committed)| {
+					packed_committed(OracleId::from_index(i), unpacked_committed)
+				})
+				.collect::<Result<Vec<_>, _>>()
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let folded_part = meta.folded_part();
+	let late_parts = (0..meta.n_parts())
+		.filter(|&part| part != folded_part)
+		.collect::<Vec<_>>();
+	let mut fri_folder = FRIFolder::new(
+		fri_params,
+		ntt,
+		merkle_prover,
+		&output.parts[folded_part].codeword,
+		&output.parts[folded_part].committed,
+	)?;
+	for &part in &late_parts {
+		let part_output = &output.parts[part];
+		fri_folder = fri_folder.with_batched_at_round(
+			meta.entry_round(part),
+			&part_output.codeword,
+			&part_output.committed,
+			coeffs[part],
+		)?;
+	}
+
+	// The buffers of the proof are allocated from the unallocated capacity of the caller's
+	// allocators, which is reclaimed when the proof is done.
+	let host_alloc = HostBumpAllocator::new(host_alloc.remaining());
+	let dev_alloc = BumpAllocator::<_, Hal::DevMem>::new(dev_alloc.remaining());
+
+	let packed_committed_fslices_by_part = packed_committed_by_part
+		.iter()
+		.map(|packed_committed_multilins| {
+			copy_packed_committed_to_device(hal, &dev_alloc, packed_committed_multilins)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	let packed_committed_fslices_by_part = packed_committed_fslices_by_part
+		.iter()
+		.map(|fslices_mut| {
+			fslices_mut
+				.iter()
+				.map(|fslice_mut| Hal::DevMem::as_const(fslice_mut))
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	let make_part_provers = |part: usize| {
+		let split = &splits[part];
+		let transparent_multilins = split
+			.transparent_indices
+			.iter()
+			.map(|&index| transparent_multilins[index].clone())
+			.collect::<Vec<_>>();
+		make_sumcheck_provers(
+			hal,
+			&host_alloc,
+			&dev_alloc,
+			&meta.part_metas()[part],
+			&packed_committed_fslices_by_part[part],
+			&transparent_multilins,
+			&split.claims,
+		)
+	};
+	let folded_provers = make_part_provers(folded_part)?;
+	let late_sumchecks = late_parts
+		.iter()
+		.map(|&part| Ok((meta.entry_round(part), make_part_provers(part)?)))
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	prove_interleaved_sumcheck(
+		meta.total_vars(),
+		folded_provers,
+		late_sumchecks,
+		fri_folder,
+		transcript,
+		&(),
+	)
+}
+
+/// Proves a batch of sumcheck claims given the packed committed multilinears, copying them to
+/// device memory.
+///
+/// The committed codeword is opened by `folder`, which is folded with the sumcheck challenges.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn prove_packed_committed<Hal, F, P, M, Data, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	commit_meta: &CommitMeta,
+	packed_committed_multilins: &[MultilinearExtension<P, Data>],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	folder: impl InterleavedFolder<F>,
+	transcript: &mut ProverTranscript<Challenger_>,
+	observer: &impl PhaseObserver,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Data: Deref<Target = [P]>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	// The buffers of the proof are allocated from the unallocated capacity of the caller's
+	// allocators, which is reclaimed when the proof is done.
+	let host_alloc = HostBumpAllocator::new(host_alloc.remaining());
+	let dev_alloc = BumpAllocator::<_, Hal::DevMem>::new(dev_alloc.remaining());
+
+	let packed_committed_fslices_mut =
+		copy_packed_committed_to_device(hal, &dev_alloc, packed_committed_multilins)?;
+	let packed_committed_fslices = packed_committed_fslices_mut
+		.iter()
+		.map(|fslice_mut| Hal::DevMem::as_const(fslice_mut))
+		.collect::<Vec<_>>();
+
+	prove_with_committed_fslices(
+		hal,
+		&host_alloc,
+		&dev_alloc,
+		commit_meta,
+		&packed_committed_fslices,
+		transparent_multilins,
+		claims,
+		folder,
+		transcript,
+		observer,
+	)
+}
+
+/// Copies the packed committed multilinears to device memory allocated from `dev_alloc`.
+fn copy_packed_committed_to_device<'a, F, P, Data, Hal>(
+	hal: &Hal,
+	dev_alloc: &'a BumpAllocator<'_, F, Hal::DevMem>,
+	packed_committed_multilins: &[MultilinearExtension<P, Data>],
+) -> Result<Vec<FSliceMut<'a, F, Hal>>, Error>
+where
+	F: TowerField,
+	P: PackedFieldIndexable<Scalar = F>,
+	Data: Deref<Target = [P]>,
+	Hal: ComputeLayer<F>,
+{
+	packed_committed_multilins
+		.iter()
+		.map(|packed_committed_multilin| {
+			let unpacked_hypercube_evals = P::unpack_scalars(packed_committed_multilin.evals());
+			let mut allocated_mem = dev_alloc.alloc(1 << packed_committed_multilin.n_vars())?;
+			let _ = hal.copy_h2d(
+				&unpacked_hypercube_evals[..1 << packed_committed_multilin.n_vars()],
+				&mut allocated_mem,
+			);
+			Ok(allocated_mem)
+		})
+		.collect()
+}
+
+/// Proves a batch of sumcheck claims over the committed multilinears of several independent
+/// commitments.
+///
+/// The claims reference the committed multilinears by their joint index in `multi_commit_meta`
+/// and the transparent polynomials in ascending order by number of variables, as for [`prove`].
+/// They are split by commitment with [`MultiCommitMeta::split_claims`], and each commitment is
+/// proven in order with [`prove`] on the shared transcript, reusing the memory of the same host
+/// and device allocators. The commitments are not written to the transcript, so that commitments
+/// known to the verifier out of band need not be sent.
+///
+/// ## Arguments
+///
+/// * `fri_params` - the FRI parameters of each commitment
+/// * `committed` - the committed Merkle tree of each commitment
+/// * `codewords` - the codeword of each commitment
+/// * `committed_multilins` - the committed multilinears in joint order
+#[allow(clippy::too_many_arguments)]
+pub fn prove_multi_commit<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	fri_params: &[&FRIParams<F, FEncode>],
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	multi_commit_meta: &MultiCommitMeta,
+	committed: &[&MTProver::Committed],
+	codewords: &[&[P]],
+	committed_multilins: &[M],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedExtension<FEncode>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Clone + Send + Sync,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	for n_commitments in [fri_params.len(), committed.len(), codewords.len()] {
+		check_n_commitments(multi_commit_meta, n_commitments)?;
 	}
 
-	let output = fri::commit_interleaved_with(fri_params, ntt, merkle_prover, |message_buffer| {
-		merge_multilins(&packed_multilins, message_buffer)
-	})?;
+	let committed_by_commitment = multi_commit_meta.split_committed(committed_multilins)?;
+	let splits = multi_commit_meta.split_claims(transparent_multilins.len(), claims)?;
+	for (fri_params, commit_meta, committed, codeword, committed_multilins, split) in izip!(
+		fri_params,
+		multi_commit_meta.commit_metas(),
+		committed,
+		codewords,
+		&committed_by_commitment,
+		&splits
+	) {
+		let transparent_multilins = split
+			.transparent_indices
+			.iter()
+			.map(|&index| transparent_multilins[index].clone())
+			.collect::<Vec<_>>();
+		prove(
+			hal,
+			&mut *host_alloc,
+			&mut *dev_alloc,
+			fri_params,
+			ntt,
+			merkle_prover,
+			commit_meta,
+			committed,
+			codeword,
+			committed_multilins,
+			&transparent_multilins,
+			&split.claims,
+			transcript,
+		)?;
+	}
+	Ok(())
+}
 
-	Ok(output)
+fn check_n_commitments(
+	multi_commit_meta: &MultiCommitMeta,
+	n_commitments: usize,
+) -> Result<(), Error> {
+	if n_commitments != multi_commit_meta.n_commitments() {
+		bail!(Error::IncorrectNumberOfCommitments {
+			expected: multi_commit_meta.n_commitments(),
+			actual: n_commitments,
+		});
+	}
+	Ok(())
+}
+
+/// Proves a batch of sumcheck claims over committed multilinears split into one commitment for
+/// each rate.
+///
+/// The claims reference the committed multilinears in joint commitment order and the transparent
+/// polynomials in ascending order by number of variables, as for [`prove`]. Each rate batch is
+/// proven in ascending order of rate as an independent commitment with its own FRI proof, with
+/// [`prove_multi_commit`].
+#[allow(clippy::too_many_arguments)]
+pub fn prove_rate_batches<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	fri_params: &[FRIParams<F, FEncode>],
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	rate_batched_meta: &RateBatchedCommitMeta,
+	commit_outputs: &[fri::CommitOutput<P, MTScheme::Digest, MTProver::Committed>],
+	committed_multilins: &[M],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedExtension<FEncode>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Clone + Send + Sync,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	check_n_rate_batches(rate_batched_meta, fri_params.len())?;
+	check_n_rate_batches(rate_batched_meta, commit_outputs.len())?;
+
+	prove_multi_commit(
+		hal,
+		host_alloc,
+		dev_alloc,
+		&fri_params.iter().collect::<Vec<_>>(),
+		ntt,
+		merkle_prover,
+		rate_batched_meta.multi_commit_meta(),
+		&commit_outputs
+			.iter()
+			.map(|commit_output| &commit_output.committed)
+			.collect::<Vec<_>>(),
+		&commit_outputs
+			.iter()
+			.map(|commit_output| &*commit_output.codeword)
+			.collect::<Vec<_>>(),
+		committed_multilins,
+		transparent_multilins,
+		claims,
+		transcript,
+	)
+}
+
+fn check_n_rate_batches(
+	rate_batched_meta: &RateBatchedCommitMeta,
+	n_batches: usize,
+) -> Result<(), Error> {
+	if n_batches != rate_batched_meta.n_batches() {
+		bail!(Error::IncorrectNumberOfRateBatches {
+			expected: rate_batched_meta.n_batches(),
+			actual: n_batches,
+		});
+	}
+	Ok(())
 }
 
 /// Proves a batch of sumcheck claims that are products of committed polynomials from a committed
-/// batch and transparent polynomials.
+/// batch and transparent polynomials, where the committed multilinears reside in compute-layer
+/// device memory.
 ///
-/// The arguments corresponding to the committed multilinears must be the output of [`commit`].
+/// This is [`prove`] for witnesses that were generated on the device, such as those registered in
+/// a [`crate::witness::DeviceWitnessIndex`]. Each device buffer holds the packed form of a
+/// committed multilinear over `F`, in commitment order, and is opened in place rather than being
+/// copied to the device from host memory. The arguments corresponding to the committed
+/// multilinears must be the output of [`commit_device`] or [`commit`] for the same multilinears.
 #[allow(clippy::too_many_arguments)]
-pub fn prove<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+pub fn prove_device_committed<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
 	hal: &Hal,
-	host_mem: <CpuMemory as ComputeMemory<F>>::FSliceMut<'_>,
-	dev_mem: <<Hal as ComputeLayer<F>>::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
 	fri_params: &FRIParams<F, FEncode>,
 	ntt: &NTT,
 	merkle_prover: &MTProver,
 	commit_meta: &CommitMeta,
-	committed: MTProver::Committed,
+	committed: &MTProver::Committed,
 	codeword: &[P],
-	committed_multilins: &[M],
+	committed_multilins: &[FSlice<'_, F, Hal>],
 	transparent_multilins: &[M],
 	claims: &[PIOPSumcheckClaim<F>],
 	transcript: &mut ProverTranscript<Challenger_>,
@@ -198,71 +1773,105 @@ where
 	Challenger_: Challenger,
 	Hal: ComputeLayer<F> + Default,
 {
-	let host_alloc = HostBumpAllocator::new(host_mem);
+	device_committed_slots::<F, Hal::DevMem>(commit_meta, committed_multilins)?;
 
-	let dev_alloc = BumpAllocator::<_, Hal::DevMem>::new(dev_mem);
+	let host_alloc = HostBumpAllocator::new(host_alloc.remaining());
+	let dev_alloc = BumpAllocator::<_, Hal::DevMem>::new(dev_alloc.remaining());
 
-	// Map of n_vars to sumcheck claim descriptions
-	let sumcheck_claim_descs = make_sumcheck_claim_descs(
+	prove_with_committed_fslices(
+		hal,
+		&host_alloc,
+		&dev_alloc,
 		commit_meta,
-		transparent_multilins.iter().map(|poly| poly.n_vars()),
+		committed_multilins,
+		transparent_multilins,
+		claims,
+		FRIFolder::new(fri_params, ntt, merkle_prover, codeword, committed)?,
+		transcript,
+		&(),
+	)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_with_committed_fslices<'a, 'alloc, Hal, F, P, M, Challenger_>(
+	hal: &'a Hal,
+	host_alloc: &'a HostBumpAllocator<'a, F>,
+	dev_alloc: &'a BumpAllocator<'alloc, F, Hal::DevMem>,
+	commit_meta: &CommitMeta,
+	packed_committed_fslices: &'a [FSlice<'_, F, Hal>],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	folder: impl InterleavedFolder<F>,
+	transcript: &mut ProverTranscript<Challenger_>,
+	observer: &impl PhaseObserver,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	P: PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	let sumcheck_provers = make_sumcheck_provers(
+		hal,
+		host_alloc,
+		dev_alloc,
+		commit_meta,
+		packed_committed_fslices,
+		transparent_multilins,
 		claims,
 	)?;
 
-	// The committed multilinears provided by argument are committed *small field* multilinears.
-	// Create multilinears representing the packed polynomials here. Eventually, we would like to
-	// refactor the calling code so that the PIOP only handles *big field* multilinear witnesses.
-	let packed_committed_multilins = committed_multilins
-		.iter()
-		.enumerate()
-		.map(|(i, unpacked_committed)| {
-			packed_committed(OracleId::from_index(i), unpacked_committed)
-				.map(MLEDirectAdapter::from)
-		})
-		.collect::<Result<Vec<_>, _>>()?;
+	prove_interleaved_sumcheck(
+		commit_meta.total_vars(),
+		sumcheck_provers,
+		Vec::new(),
+		folder,
+		transcript,
+		observer,
+	)?;
 
-	let packed_committed_fslices_mut = packed_committed_multilins
-		.iter()
-		.map(|packed_committed_multilin| {
-			let hypercube_evals = packed_committed_multilin
-				.packed_evals()
-				.expect("Prover should always populate witnesses");
-			let unpacked_hypercube_evals = P::unpack_scalars(hypercube_evals);
-			let mut allocated_mem = dev_alloc.alloc(1 << packed_committed_multilin.n_vars())?;
-			let _ = hal.copy_h2d(
-				&unpacked_hypercube_evals[..1 << packed_committed_multilin.n_vars()],
-				&mut allocated_mem,
-			);
-			Ok(allocated_mem)
-		})
-		.collect::<Result<Vec<_>, Error>>()?;
+	Ok(())
+}
 
-	let packed_committed_fslices = packed_committed_fslices_mut
-		.iter()
-		.map(|fslice_mut| Hal::DevMem::as_const(fslice_mut))
-		.collect::<Vec<_>>();
+/// Constructs the sumcheck provers of the claims on a committed batch, one for each number of
+/// variables of the committed multilinears, in ascending order.
+fn make_sumcheck_provers<'a, 'alloc, Hal, F, P, M>(
+	hal: &'a Hal,
+	host_alloc: &'a HostBumpAllocator<'a, F>,
+	dev_alloc: &'a BumpAllocator<'alloc, F, Hal::DevMem>,
+	commit_meta: &CommitMeta,
+	packed_committed_fslices: &'a [FSlice<'_, F, Hal>],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+) -> Result<Vec<BivariateSumcheckProver<'a, 'alloc, F, Hal>>, Error>
+where
+	F: TowerField,
+	P: PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P>,
+	Hal: ComputeLayer<F>,
+{
+	commit_meta.check_top_field::<F>()?;
+
+	// Map of n_vars to sumcheck claim descriptions
+	let sumcheck_claim_descs = make_sumcheck_claim_descs(
+		commit_meta,
+		transparent_multilins.iter().map(|poly| poly.n_vars()),
+		claims,
+	)?;
 
-	let transparent_fslices_mut = transparent_multilins
+	// Transparent multilinears are generated chunk-wise directly into device memory, so they need
+	// not be materialized on the host. The device buffers are owned by the sumcheck provers and
+	// folded in place.
+	let mut transparent_fslices_mut = transparent_multilins
 		.iter()
 		.map(|transparent_multilin| {
-			let hypercube_evals = transparent_multilin
-				.packed_evals()
-				.expect("Prover should always populate witnesses");
-			let unpacked_hypercube_evals = P::unpack_scalars(hypercube_evals);
 			let mut allocated_mem = dev_alloc.alloc(1 << transparent_multilin.n_vars())?;
-			let _ = hal.copy_h2d(
-				&unpacked_hypercube_evals[..1 << transparent_multilin.n_vars()],
-				&mut allocated_mem,
-			);
-			Ok(allocated_mem)
+			write_multilin_to_device(hal, transparent_multilin, &mut allocated_mem)?;
+			Ok(Some(allocated_mem))
 		})
 		.collect::<Result<Vec<_>, Error>>()?;
 
-	let transparent_fslices = transparent_fslices_mut
-		.iter()
-		.map(|fslice_mut| Hal::DevMem::as_const(fslice_mut))
-		.collect::<Vec<_>>();
-
 	let non_empty_sumcheck_descs = sumcheck_claim_descs
 		.iter()
 		.enumerate()
@@ -277,61 +1886,159 @@ where
 		let multilins = chain!(
 			packed_committed_fslices[desc.committed_indices.clone()]
 				.iter()
-				.map(|fslice| Hal::DevMem::narrow(fslice)),
-			transparent_fslices[desc.transparent_indices.clone()]
-				.iter()
-				.map(|fslice| Hal::DevMem::narrow(fslice))
+				.map(|fslice| SumcheckMultilinear::PreFold(Hal::DevMem::narrow(fslice))),
+			transparent_fslices_mut[desc.transparent_indices.clone()]
+				.iter_mut()
+				.map(|fslice| {
+					SumcheckMultilinear::PostFold(
+						fslice
+							.take()
+							.expect("transparent index ranges of claim descriptions are disjoint"),
+					)
+				})
 		)
 		.collect::<Vec<_>>();
 
 		let claim = SumcheckClaim::new(_n_vars, multilins.len(), desc.composite_sums.clone())?;
 
-		sumcheck_provers.push(BivariateSumcheckProver::new(
-			hal,
-			&dev_alloc,
-			&host_alloc,
-			&claim,
-			multilins,
+		sumcheck_provers.push(BivariateSumcheckProver::new_with_multilins(
+			hal, dev_alloc, host_alloc, &claim, multilins,
 		)?);
 	}
 
-	prove_interleaved_fri_sumcheck(
-		commit_meta.total_vars(),
-		fri_params,
-		ntt,
-		merkle_prover,
-		sumcheck_provers,
-		codeword,
-		&committed,
-		transcript,
-	)?;
-
-	Ok(())
+	Ok(sumcheck_provers)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn prove_interleaved_fri_sumcheck<F, FEncode, P, NTT, MTScheme, MTProver, Challenger_>(
-	n_rounds: usize,
-	fri_params: &FRIParams<F, FEncode>,
-	ntt: &NTT,
-	merkle_prover: &MTProver,
-	sumcheck_provers: Vec<impl SumcheckProver<F>>,
-	codeword: &[P],
-	committed: &MTProver::Committed,
-	transcript: &mut ProverTranscript<Challenger_>,
+/// Base-2 logarithm of the number of scalars generated per chunk when writing multilinears without
+/// materialized evaluations to device memory.
+const LOG_DEVICE_WRITE_CHUNK_SIZE: usize = 12;
+
+/// Writes the hypercube evaluations of a multilinear into a device memory buffer.
+///
+/// Multilinears with materialized packed evaluations are copied directly. Otherwise, the
+/// evaluations are generated with [`MultilinearPoly::subcube_evals`] into a small host staging
+/// buffer and copied to the device one chunk at a time.
+fn write_multilin_to_device<F, P, M, Hal>(
+	hal: &Hal,
+	multilin: &M,
+	dst: &mut FSliceMut<'_, F, Hal>,
 ) -> Result<(), Error>
 where
 	F: TowerField,
+	P: PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P>,
+	Hal: ComputeLayer<F>,
+{
+	let n_vars = multilin.n_vars();
+	if let Some(packed_evals) = multilin.packed_evals() {
+		hal.copy_h2d(&P::unpack_scalars(packed_evals)[..1 << n_vars], dst)?;
+		return Ok(());
+	}
+
+	let log_chunk_size = n_vars.min(LOG_DEVICE_WRITE_CHUNK_SIZE);
+	let mut staging = zeroed_vec::<P>(1 << log_chunk_size.saturating_sub(P::LOG_WIDTH));
+	if log_chunk_size == n_vars {
+		multilin.subcube_evals(n_vars, 0, 0, &mut staging)?;
+		hal.copy_h2d(&P::unpack_scalars(&staging)[..1 << n_vars], dst)?;
+		return Ok(());
+	}
+
+	let dst_chunks =
+		Hal::DevMem::slice_chunks_mut(Hal::DevMem::slice_mut(dst, ..), 1 << log_chunk_size);
+	for (chunk_index, mut dst_chunk) in dst_chunks.enumerate() {
+		multilin.subcube_evals(log_chunk_size, chunk_index, 0, &mut staging)?;
+		hal.copy_h2d(P::unpack_scalars(&staging), &mut dst_chunk)?;
+	}
+	Ok(())
+}
+
+/// The prover of the opening of a committed codeword in the interleaved sumcheck and folding
+/// protocol, which folds the codeword with the sumcheck challenges.
+///
+/// The fold rounds must reduce the codeword to the evaluation of its message as a multilinear at
+/// the challenges, as [`FRIFolder`] does.
+pub(super) trait InterleavedFolder<F: Field> {
+	type Digest: SerializeBytes;
+
+	/// The binary logarithm of the number of interleaved codewords folded in the first rounds.
+	fn log_batch_size(&self) -> usize;
+
+	/// The length of the codeword to be folded in the next round.
+	fn current_codeword_len(&self) -> usize;
+
+	/// Folds the codeword with the challenge of a round, returning the commitment to the folded
+	/// codeword if one is sent.
+	fn execute_fold_round(&mut self, challenge: F) -> Result<FoldRoundOutput<Self::Digest>, Error>;
+
+	/// Writes the terminal messages and the query proofs after the last fold round.
+	fn finish_proof<Challenger_: Challenger>(
+		self,
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error>;
+}
+
+impl<F, FEncode, P, NTT, MTScheme, MTProver> InterleavedFolder<F>
+	for FRIFolder<'_, F, FEncode, P, NTT, MTProver, MTScheme>
+where
+	F: TowerField + ExtensionField<FEncode>,
 	FEncode: BinaryField,
-	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	P: PackedField<Scalar = F>,
 	NTT: AdditiveNTT<FEncode> + Sync,
 	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
 	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
-	Challenger_: Challenger,
 {
-	let mut fri_prover = FRIFolder::new(fri_params, ntt, merkle_prover, codeword, committed)?;
+	type Digest = MTScheme::Digest;
+
+	fn log_batch_size(&self) -> usize {
+		self.params().log_batch_size()
+	}
+
+	fn current_codeword_len(&self) -> usize {
+		FRIFolder::current_codeword_len(self)
+	}
+
+	fn execute_fold_round(&mut self, challenge: F) -> Result<FoldRoundOutput<Self::Digest>, Error> {
+		Ok(FRIFolder::execute_fold_round(self, challenge)?)
+	}
+
+	fn finish_proof<Challenger_: Challenger>(
+		self,
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error> {
+		Ok(FRIFolder::finish_proof(self, transcript)?)
+	}
+}
 
-	let mut sumcheck_batch_prover = SumcheckBatchProver::new(sumcheck_provers, transcript)?;
+/// Runs the interleaved sumcheck and folding protocol.
+///
+/// `late_sumchecks` are further batches of sumcheck provers with the round each starts at, for the
+/// multilinears of codewords that join the folded codeword after that many fold rounds. Their
+/// round messages follow those of the first batch in each round from then on, in order.
+fn prove_interleaved_sumcheck<F, Prover, Challenger_>(
+	n_rounds: usize,
+	sumcheck_provers: Vec<Prover>,
+	late_sumchecks: Vec<(usize, Vec<Prover>)>,
+	mut folder: impl InterleavedFolder<F>,
+	transcript: &mut ProverTranscript<Challenger_>,
+	observer: &impl PhaseObserver,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	Prover: SumcheckProver<F> + Send,
+	Challenger_: Challenger,
+{
+	let mut sumcheck_batch_prover = observe_phase(observer, PIOPPhase::Sumcheck, || {
+		SumcheckBatchProver::new(sumcheck_provers, transcript)
+	})?;
+	let mut late_batch_provers = late_sumchecks
+		.into_iter()
+		.map(|(start_round, provers)| {
+			let batch_prover = observe_phase(observer, PIOPPhase::Sumcheck, || {
+				SumcheckBatchProver::new(provers, transcript)
+			})?;
+			Ok((start_round, batch_prover))
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
 
 	for round in 0..n_rounds {
 		let _span =
@@ -355,54 +2062,93 @@ where
 			dimensions_data = ?provers_dimensions_data,
 		)
 		.entered();
-		sumcheck_batch_prover.send_round_proof(&mut transcript.message())?;
+		observe_phase(observer, PIOPPhase::Sumcheck, || {
+			sumcheck_batch_prover.send_round_proof(&mut transcript.message())?;
+			for (start_round, late_batch_prover) in &mut late_batch_provers {
+				if round >= *start_round {
+					late_batch_prover.send_round_proof(&mut transcript.message())?;
+				}
+			}
+			Ok::<_, Error>(())
+		})?;
 		drop(bivariate_sumcheck_calculate_coeffs_span);
+		drop(bivariate_sumcheck_span);
 
+		// Once the challenge is sampled, folding the sumcheck multilinears and folding the FRI
+		// codeword are independent of each other until the next round, so the sumcheck fold is
+		// spawned while the FRI fold runs on this thread. Both folds share the caller's thread
+		// pool, so this only helps when neither fold alone keeps every worker busy.
 		let challenge = transcript.sample();
-		let bivariate_sumcheck_all_folds_span = tracing::debug_span!(
-			"[task] (PIOP Compiler) Fold (All Rounds)",
-			phase = "piop_compiler",
-			round = round,
-			perfetto_category = "task.main",
-			dimensions_data = ?provers_dimensions_data,
-		)
-		.entered();
-		sumcheck_batch_prover.receive_challenge(challenge)?;
-		drop(bivariate_sumcheck_all_folds_span);
-		drop(bivariate_sumcheck_span);
+		let mut receive_challenge_result = Ok(());
+		// The spawned fold may run on another thread, which does not inherit the round span.
+		let round_span = tracing::Span::current();
+		let fold_round_output = binius_maybe_rayon::in_place_scope(|scope| {
+			scope.spawn(|_| {
+				let _round_span = round_span.enter();
+				let _span = tracing::debug_span!(
+					"[task] (PIOP Compiler) Fold (All Rounds)",
+					phase = "piop_compiler",
+					round = round,
+					perfetto_category = "task.main",
+					dimensions_data = ?provers_dimensions_data,
+				)
+				.entered();
+				receive_challenge_result = observe_phase(observer, PIOPPhase::Sumcheck, || {
+					sumcheck_batch_prover.receive_challenge(challenge)?;
+					for (start_round, late_batch_prover) in &mut late_batch_provers {
+						if round >= *start_round {
+							late_batch_prover.receive_challenge(challenge)?;
+						}
+					}
+					Ok::<_, Error>(())
+				});
+			});
 
-		let dimensions_data = FriFoldRoundsData::new(
-			round,
-			fri_params.log_batch_size(),
-			fri_prover.current_codeword_len(),
-		);
-		let fri_fold_rounds_span = tracing::debug_span!(
-			"[step] FRI Fold Rounds",
-			phase = "piop_compiler",
-			round = round,
-			perfetto_category = "phase.sub",
-			?dimensions_data,
-		)
-		.entered();
-		match fri_prover.execute_fold_round(challenge)? {
+			let dimensions_data = FriFoldRoundsData::new(
+				round,
+				folder.log_batch_size(),
+				folder.current_codeword_len(),
+			);
+			let _span = tracing::debug_span!(
+				"[step] FRI Fold Rounds",
+				phase = "piop_compiler",
+				round = round,
+				perfetto_category = "phase.sub",
+				?dimensions_data,
+			)
+			.entered();
+			observe_phase(observer, PIOPPhase::FriFold, || folder.execute_fold_round(challenge))
+		});
+		receive_challenge_result?;
+		match fold_round_output? {
 			FoldRoundOutput::NoCommitment => {}
 			FoldRoundOutput::Commitment(round_commitment) => {
 				transcript.message().write(&round_commitment);
 			}
 		}
-		drop(fri_fold_rounds_span);
 	}
 
-	sumcheck_batch_prover.finish(&mut transcript.message())?;
-	fri_prover.finish_proof(transcript)?;
+	observe_phase(observer, PIOPPhase::Sumcheck, || {
+		sumcheck_batch_prover.finish(&mut transcript.message())?;
+		for (_, late_batch_prover) in late_batch_provers {
+			late_batch_prover.finish(&mut transcript.message())?;
+		}
+		Ok::<_, Error>(())
+	})?;
+	observe_phase(observer, PIOPPhase::FriQuery, || folder.finish_proof(transcript))?;
 	Ok(())
 }
 
+/// Checks the sumcheck claims output by the PIOP against the committed and transparent witnesses.
+///
+/// Rather than stopping at the first unsatisfied claim, this returns a [`SumcheckWitnessReport`]
+/// with an entry for every unsatisfied claim, naming the claim and the committed and transparent
+/// multilinears it references. An error is only returned if the witnesses cannot be read.
 pub fn validate_sumcheck_witness<F, P, M>(
 	committed_multilins: &[M],
 	transparent_multilins: &[M],
 	claims: &[PIOPSumcheckClaim<F>],
-) -> Result<(), Error>
+) -> Result<SumcheckWitnessReport<F>, Error>
 where
 	F: TowerField,
 	P: PackedField<Scalar = F>,
@@ -416,15 +2162,36 @@ where
 		})
 		.collect::<Result<Vec<_>, _>>()?;
 
+	let mut mismatches = Vec::new();
 	for (i, claim) in claims.iter().enumerate() {
-		let committed = &packed_committed[claim.committed];
+		let mismatch = |kind| SumcheckClaimMismatch {
+			claim: i,
+			committed: claim.committed,
+			transparent: claim.transparent,
+			kind,
+		};
+
+		let Some(committed) = packed_committed.get(claim.committed) else {
+			mismatches.push(mismatch(SumcheckMismatchKind::CommittedMissing));
+			continue;
+		};
+		let Some(transparent) = transparent_multilins.get(claim.transparent) else {
+			mismatches.push(mismatch(SumcheckMismatchKind::TransparentMissing));
+			continue;
+		};
 		if committed.n_vars() != claim.n_vars {
-			bail!(sumcheck::Error::NumberOfVariablesMismatch);
+			mismatches.push(mismatch(SumcheckMismatchKind::CommittedVariables {
+				expected: claim.n_vars,
+				actual: committed.n_vars(),
+			}));
+			continue;
 		}
-
-		let transparent = &transparent_multilins[claim.transparent];
 		if transparent.n_vars() != claim.n_vars {
-			bail!(sumcheck::Error::NumberOfVariablesMismatch);
+			mismatches.push(mismatch(SumcheckMismatchKind::TransparentVariables {
+				expected: claim.n_vars,
+				actual: transparent.n_vars(),
+			}));
+			continue;
 		}
 
 		let sum = (0..(1 << claim.n_vars))
@@ -441,12 +2208,61 @@ where
 			.sum::<F>();
 
 		if sum != claim.sum {
-			bail!(sumcheck::Error::SumcheckNaiveValidationFailure {
-				composition_index: i,
-			});
+			mismatches.push(mismatch(SumcheckMismatchKind::Sum {
+				expected: claim.sum,
+				actual: sum,
+			}));
 		}
 	}
-	Ok(())
+	Ok(SumcheckWitnessReport { mismatches })
+}
+
+/// The result of checking sumcheck claims against their witnesses with
+/// [`validate_sumcheck_witness`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SumcheckWitnessReport<F> {
+	/// The unsatisfied claims, in ascending order of claim index.
+	pub mismatches: Vec<SumcheckClaimMismatch<F>>,
+}
+
+impl<F> SumcheckWitnessReport<F> {
+	/// Returns whether the witnesses satisfy all claims.
+	pub fn is_satisfied(&self) -> bool {
+		self.mismatches.is_empty()
+	}
+
+	/// Returns the unsatisfied claim with the lowest index, if any.
+	pub fn first_mismatch(&self) -> Option<&SumcheckClaimMismatch<F>> {
+		self.mismatches.first()
+	}
+}
+
+/// A sumcheck claim that is not satisfied by its witnesses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SumcheckClaimMismatch<F> {
+	/// The index of the claim.
+	pub claim: usize,
+	/// The index of the committed multilinear referenced by the claim.
+	pub committed: usize,
+	/// The index of the transparent multilinear referenced by the claim.
+	pub transparent: usize,
+	/// How the claim is not satisfied.
+	pub kind: SumcheckMismatchKind<F>,
+}
+
+/// The way in which a [`SumcheckClaimMismatch`] is not satisfied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SumcheckMismatchKind<F> {
+	/// The claim references a committed multilinear that is not in the witness.
+	CommittedMissing,
+	/// The claim references a transparent multilinear that is not in the witness.
+	TransparentMissing,
+	/// The packed committed multilinear has a different number of variables than the claim.
+	CommittedVariables { expected: usize, actual: usize },
+	/// The transparent multilinear has a different number of variables than the claim.
+	TransparentVariables { expected: usize, actual: usize },
+	/// The sum over the hypercube of the product of the multilinears differs from the claimed sum.
+	Sum { expected: F, actual: F },
 }
 
 /// Creates a multilinear extension of the packed evaluations of a small-field multilinear.
@@ -456,7 +2272,7 @@ where
 /// $n < \kappa$, which is when a polynomial is too full to have even a single packed evaluation,
 /// the polynomial is extended by padding with more variables, which corresponds to repeating its
 /// subcube evaluations.
-fn packed_committed<F, P, M>(
+pub(super) fn packed_committed<F, P, M>(
 	id: OracleId,
 	unpacked_committed: &M,
 ) -> Result<MultilinearExtension<P, Cow<'_, [P]>>, Error>
@@ -482,8 +2298,10 @@ where
 	Ok(packed_committed)
 }
 
+/// Returns the packed evaluation of a multilinear with fewer variables than its extension degree,
+/// repeating its hypercube evaluations to fill the packed field element.
 #[inline]
-fn padded_packed_eval<F, P, M>(multilin: &M) -> F
+pub(crate) fn padded_packed_eval<F, P, M>(multilin: &M) -> F
 where
 	F: TowerField,
 	P: PackedField<Scalar = F>,
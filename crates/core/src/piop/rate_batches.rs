@@ -0,0 +1,210 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::Field;
+use binius_utils::bail;
+
+use super::{
+	error::Error,
+	multi_commit::{MultiCommitMeta, SplitClaims},
+	verify::{CommitMeta, PIOPSumcheckClaim},
+};
+
+/// Metadata about a batch of committed multilinears split into independent commitments by
+/// Reed–Solomon rate.
+///
+/// The soundness of the PIOP compiler is dominated by the columns with the most variables, so it
+/// can pay off to spend redundancy only on the columns that need it. The committed multilinears,
+/// indexed as usual by a joint [`CommitMeta`], are partitioned into rate batches, one for each
+/// distinct rate in ascending order. Each rate batch is a separate commitment with its own Merkle
+/// root, codeword, and FRI proof, committed and opened in order on a shared transcript by
+/// [`super::commit_rate_batches`], [`super::prove_rate_batches`], and
+/// [`super::verify_rate_batches`]. This is a [`MultiCommitMeta`] whose commitments are chosen by
+/// rate, so it only shrinks the proof when the queries saved on the low-rate batches outweigh the
+/// Merkle roots and fold rounds of the extra FRI proofs.
+///
+/// Mixing rates within a single commitment is not supported: [`super::commit`] encodes the whole
+/// batch with one rate. The batches are not folded into a single FRI instance either, because
+/// Reed–Solomon codes of different rates over the same domain are nested, folding preserves the
+/// rate of each codeword, and the proximity bound of a combined codeword would degrade to that of
+/// the highest rate.
+#[derive(Debug)]
+pub struct RateBatchedCommitMeta {
+	commit_meta: CommitMeta,
+	log_inv_rates: Vec<usize>,
+	/// The rate batches, as independent commitments of the joint committed multilinears.
+	batches: MultiCommitMeta,
+}
+
+impl RateBatchedCommitMeta {
+	/// Constructs a new [`RateBatchedCommitMeta`].
+	///
+	/// ## Arguments
+	///
+	/// * `commit_meta` - the metadata of the joint batch of committed multilinears
+	/// * `log_inv_rates` - the binary logarithm of the inverse rate of each committed multilinear,
+	///   by joint committed index
+	pub fn new(commit_meta: CommitMeta, log_inv_rates: &[usize]) -> Result<Self, Error> {
+		if log_inv_rates.len() != commit_meta.total_multilins() {
+			bail!(Error::IncorrectNumberOfRates {
+				expected: commit_meta.total_multilins(),
+				actual: log_inv_rates.len(),
+			});
+		}
+
+		let mut distinct_rates = log_inv_rates.to_vec();
+		distinct_rates.sort_unstable();
+		distinct_rates.dedup();
+
+		let committed_n_vars = commit_meta
+			.n_multilins_by_vars()
+			.iter()
+			.enumerate()
+			.flat_map(|(n_vars, &count)| std::iter::repeat_n(n_vars, count))
+			.collect::<Vec<_>>();
+
+		let mut committed_locations = vec![(0, 0); log_inv_rates.len()];
+		let batch_commit_metas = distinct_rates
+			.iter()
+			.enumerate()
+			.map(|(batch, &log_inv_rate)| {
+				let committed_indices = (0..log_inv_rates.len())
+					.filter(|&index| log_inv_rates[index] == log_inv_rate)
+					.collect::<Vec<_>>();
+				for (local_index, &index) in committed_indices.iter().enumerate() {
+					committed_locations[index] = (batch, local_index);
+				}
+				// The joint order is ascending by number of variables, and so is any subsequence.
+				let batch_commit_meta = CommitMeta::with_vars(
+					committed_indices
+						.iter()
+						.map(|&index| committed_n_vars[index]),
+				);
+				match commit_meta.tower_levels() {
+					Some(tower_levels) => batch_commit_meta
+						.with_tower_levels(
+							committed_indices
+								.iter()
+								.map(|&index| tower_levels[index])
+								.collect(),
+						)
+						.expect("there is a tower level for each multilinear in the batch"),
+					None => batch_commit_meta,
+				}
+			})
+			.collect();
+
+		Ok(Self {
+			commit_meta,
+			log_inv_rates: distinct_rates,
+			batches: MultiCommitMeta::with_locations(batch_commit_metas, committed_locations),
+		})
+	}
+
+	/// Returns the metadata of the joint batch of committed multilinears.
+	pub fn commit_meta(&self) -> &CommitMeta {
+		&self.commit_meta
+	}
+
+	/// Returns the rate batches as independent commitments of the joint committed multilinears.
+	pub fn multi_commit_meta(&self) -> &MultiCommitMeta {
+		&self.batches
+	}
+
+	/// Returns the number of rate batches.
+	pub fn n_batches(&self) -> usize {
+		self.batches.n_commitments()
+	}
+
+	/// Returns the binary logarithm of the inverse rate of each rate batch, in ascending order.
+	pub fn log_inv_rates(&self) -> impl Iterator<Item = usize> + '_ {
+		self.log_inv_rates.iter().copied()
+	}
+
+	/// Returns the metadata of the committed multilinears in each rate batch.
+	pub fn batch_commit_metas(&self) -> impl Iterator<Item = &CommitMeta> + '_ {
+		self.batches.commit_metas().iter()
+	}
+
+	/// Returns the rate batch of a committed multilinear and its index within the batch.
+	pub fn committed_location(&self, committed: usize) -> Result<(usize, usize), Error> {
+		self.batches.committed_location(committed)
+	}
+
+	/// Splits the committed multilinears, in joint order, into the committed multilinears of each
+	/// rate batch.
+	pub fn split_committed<T: Clone>(&self, committed: &[T]) -> Result<Vec<Vec<T>>, Error> {
+		self.batches.split_committed(committed)
+	}
+
+	/// Splits sumcheck claims referencing the joint batch into the claims of each rate batch.
+	///
+	/// See [`MultiCommitMeta::split_claims`].
+	///
+	/// ## Arguments
+	///
+	/// * `n_transparents` - the number of transparent polynomials referenced by the joint claims
+	/// * `claims` - the sumcheck claims referencing the joint batch
+	pub fn split_claims<F: Field>(
+		&self,
+		n_transparents: usize,
+		claims: &[PIOPSumcheckClaim<F>],
+	) -> Result<Vec<SplitClaims<F>>, Error> {
+		self.batches.split_claims(n_transparents, claims)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::BinaryField128b;
+
+	use super::*;
+
+	#[test]
+	fn test_rate_batched_commit_meta_split() {
+		let commit_meta = CommitMeta::with_vars([4, 5, 6, 6]);
+		let meta = RateBatchedCommitMeta::new(commit_meta, &[2, 1, 1, 2]).unwrap();
+
+		assert_eq!(meta.n_batches(), 2);
+		assert_eq!(meta.log_inv_rates().collect::<Vec<_>>(), vec![1, 2]);
+		let batch_n_multilins = meta
+			.batch_commit_metas()
+			.map(|meta| meta.n_multilins_by_vars().to_vec())
+			.collect::<Vec<_>>();
+		assert_eq!(batch_n_multilins, vec![vec![0, 0, 0, 0, 0, 1, 1], vec![0, 0, 0, 0, 1, 0, 1]]);
+
+		assert_eq!(meta.committed_location(3).unwrap(), (1, 1));
+		assert!(meta.committed_location(4).is_err());
+		assert_eq!(
+			meta.split_committed(&["a", "b", "c", "d"]).unwrap(),
+			vec![vec!["b", "c"], vec!["a", "d"]]
+		);
+
+		let claim = |n_vars, committed, transparent| PIOPSumcheckClaim {
+			n_vars,
+			committed,
+			transparent,
+			sum: BinaryField128b::ONE,
+		};
+		let splits = meta
+			.split_claims(3, &[claim(4, 0, 0), claim(6, 2, 2), claim(6, 3, 2)])
+			.unwrap();
+		assert_eq!(
+			splits,
+			vec![
+				SplitClaims {
+					transparent_indices: vec![2],
+					claims: vec![claim(6, 1, 0)],
+				},
+				SplitClaims {
+					transparent_indices: vec![0, 2],
+					claims: vec![claim(4, 0, 0), claim(6, 1, 1)],
+				},
+			]
+		);
+
+		assert!(matches!(
+			RateBatchedCommitMeta::new(CommitMeta::with_vars([4]), &[1, 2]),
+			Err(Error::IncorrectNumberOfRates { .. })
+		));
+	}
+}
@@ -0,0 +1,363 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::borrow::Borrow;
+
+use binius_compute::{ComputeLayer, alloc::ComputeAllocator, cpu::CpuMemory};
+use binius_field::{
+	BinaryField, ExtensionField, PackedExtension, PackedField, PackedFieldIndexable, TowerField,
+};
+use binius_math::MultilinearPoly;
+use binius_ntt::AdditiveNTT;
+use binius_utils::{DeserializeBytes, SerializeBytes};
+
+use super::{
+	basefold::{self, BaseFoldParams},
+	error::Error,
+	prove, stir,
+	verify::{self, CommitMeta, PIOPSumcheckClaim},
+};
+use crate::{
+	fiat_shamir::Challenger,
+	merkle_tree::{MerkleTreeProver, MerkleTreeScheme},
+	polynomial::MultivariatePoly,
+	protocols::{
+		fri::{CommitOutput, FRIParams},
+		stir::STIRParams,
+	},
+	transcript::{ProverTranscript, VerifierTranscript},
+};
+
+/// A commitment scheme for the batches of multilinears committed by the PIOP compiler.
+///
+/// The scheme commits a batch of multilinears and proves the sumcheck claims output by the PIOP
+/// on them. It is implemented by [`FRIParams`] for FRI-Binius, by [`BaseFoldParams`] for
+/// BaseFold, and by [`STIRParams`] for the Reed–Solomon code tested with STIR, so that a prover and
+/// verifier written against this trait can be run with any of them to compare their proof sizes
+/// and prover times.
+///
+/// The methods take the same arguments as [`super::commit`], [`super::prove`], and
+/// [`super::verify`]. The NTT is only used by schemes that encode with a Reed–Solomon code.
+pub trait PIOPCommitmentScheme<F, FEncode>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	/// Commits a batch of multilinear polynomials, in ascending order by the number of variables
+	/// in the packed multilinear.
+	fn commit<P, M, NTT, MTScheme, MTProver>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MTProver,
+		multilins: &[M],
+	) -> Result<CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+	where
+		P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+		M: MultilinearPoly<P>,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme>;
+
+	/// Proves a batch of sumcheck claims on the committed multilinears.
+	#[allow(clippy::too_many_arguments)]
+	fn prove<Hal, P, M, NTT, MTScheme, MTProver, Challenger_>(
+		&self,
+		hal: &Hal,
+		host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+		dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+		ntt: &NTT,
+		merkle_prover: &MTProver,
+		commit_meta: &CommitMeta,
+		committed: &MTProver::Committed,
+		codeword: &[P],
+		committed_multilins: &[M],
+		transparent_multilins: &[M],
+		claims: &[PIOPSumcheckClaim<F>],
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		P: PackedField<Scalar = F>
+			+ PackedExtension<F, PackedSubfield = P>
+			+ PackedExtension<FEncode>
+			+ PackedFieldIndexable<Scalar = F>,
+		M: MultilinearPoly<P> + Send + Sync,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+		Challenger_: Challenger,
+		Hal: ComputeLayer<F> + Default;
+
+	/// Verifies a batch of sumcheck claims on the committed multilinears.
+	fn verify<'a, Challenger_, MTScheme>(
+		&self,
+		commit_meta: &CommitMeta,
+		merkle_scheme: &MTScheme,
+		commitment: &MTScheme::Digest,
+		transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+		claims: &[PIOPSumcheckClaim<F>],
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		Challenger_: Challenger,
+		MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>;
+}
+
+impl<F, FEncode> PIOPCommitmentScheme<F, FEncode> for FRIParams<F, FEncode>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	fn commit<P, M, NTT, MTScheme, MTProver>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MTProver,
+		multilins: &[M],
+	) -> Result<CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+	where
+		P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+		M: MultilinearPoly<P>,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	{
+		prove::commit(self, ntt, merkle_prover, multilins)
+	}
+
+	fn prove<Hal, P, M, NTT, MTScheme, MTProver, Challenger_>(
+		&self,
+		hal: &Hal,
+		host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+		dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+		ntt: &NTT,
+		merkle_prover: &MTProver,
+		commit_meta: &CommitMeta,
+		committed: &MTProver::Committed,
+		codeword: &[P],
+		committed_multilins: &[M],
+		transparent_multilins: &[M],
+		claims: &[PIOPSumcheckClaim<F>],
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		P: PackedField<Scalar = F>
+			+ PackedExtension<F, PackedSubfield = P>
+			+ PackedExtension<FEncode>
+			+ PackedFieldIndexable<Scalar = F>,
+		M: MultilinearPoly<P> + Send + Sync,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+		Challenger_: Challenger,
+		Hal: ComputeLayer<F> + Default,
+	{
+		prove::prove(
+			hal,
+			host_alloc,
+			dev_alloc,
+			self,
+			ntt,
+			merkle_prover,
+			commit_meta,
+			committed,
+			codeword,
+			committed_multilins,
+			transparent_multilins,
+			claims,
+			transcript,
+		)
+	}
+
+	fn verify<'a, Challenger_, MTScheme>(
+		&self,
+		commit_meta: &CommitMeta,
+		merkle_scheme: &MTScheme,
+		commitment: &MTScheme::Digest,
+		transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+		claims: &[PIOPSumcheckClaim<F>],
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		Challenger_: Challenger,
+		MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+	{
+		verify::verify(
+			commit_meta,
+			merkle_scheme,
+			self,
+			commitment,
+			transparents,
+			claims,
+			transcript,
+		)
+	}
+}
+
+impl<F, FEncode> PIOPCommitmentScheme<F, FEncode> for BaseFoldParams<F, FEncode>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	fn commit<P, M, NTT, MTScheme, MTProver>(
+		&self,
+		_ntt: &NTT,
+		merkle_prover: &MTProver,
+		multilins: &[M],
+	) -> Result<CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+	where
+		P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+		M: MultilinearPoly<P>,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	{
+		basefold::commit(self, merkle_prover, multilins)
+	}
+
+	fn prove<Hal, P, M, NTT, MTScheme, MTProver, Challenger_>(
+		&self,
+		hal: &Hal,
+		host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+		dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+		_ntt: &NTT,
+		merkle_prover: &MTProver,
+		commit_meta: &CommitMeta,
+		committed: &MTProver::Committed,
+		codeword: &[P],
+		committed_multilins: &[M],
+		transparent_multilins: &[M],
+		claims: &[PIOPSumcheckClaim<F>],
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		P: PackedField<Scalar = F>
+			+ PackedExtension<F, PackedSubfield = P>
+			+ PackedExtension<FEncode>
+			+ PackedFieldIndexable<Scalar = F>,
+		M: MultilinearPoly<P> + Send + Sync,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+		Challenger_: Challenger,
+		Hal: ComputeLayer<F> + Default,
+	{
+		basefold::prove(
+			hal,
+			host_alloc,
+			dev_alloc,
+			self,
+			merkle_prover,
+			commit_meta,
+			committed,
+			codeword,
+			committed_multilins,
+			transparent_multilins,
+			claims,
+			transcript,
+		)
+	}
+
+	fn verify<'a, Challenger_, MTScheme>(
+		&self,
+		commit_meta: &CommitMeta,
+		merkle_scheme: &MTScheme,
+		commitment: &MTScheme::Digest,
+		transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+		claims: &[PIOPSumcheckClaim<F>],
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		Challenger_: Challenger,
+		MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+	{
+		basefold::verify(
+			commit_meta,
+			merkle_scheme,
+			self,
+			commitment,
+			transparents,
+			claims,
+			transcript,
+		)
+	}
+}
+
+impl<F, FEncode> PIOPCommitmentScheme<F, FEncode> for STIRParams<F, FEncode>
+where
+	F: TowerField + ExtensionField<FEncode> + PackedExtension<FEncode, Scalar = F>,
+	FEncode: BinaryField,
+{
+	fn commit<P, M, NTT, MTScheme, MTProver>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MTProver,
+		multilins: &[M],
+	) -> Result<CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+	where
+		P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+		M: MultilinearPoly<P>,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	{
+		stir::commit(self, ntt, merkle_prover, multilins)
+	}
+
+	fn prove<Hal, P, M, NTT, MTScheme, MTProver, Challenger_>(
+		&self,
+		hal: &Hal,
+		host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+		dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+		ntt: &NTT,
+		merkle_prover: &MTProver,
+		commit_meta: &CommitMeta,
+		committed: &MTProver::Committed,
+		codeword: &[P],
+		committed_multilins: &[M],
+		transparent_multilins: &[M],
+		claims: &[PIOPSumcheckClaim<F>],
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		P: PackedField<Scalar = F>
+			+ PackedExtension<F, PackedSubfield = P>
+			+ PackedExtension<FEncode>
+			+ PackedFieldIndexable<Scalar = F>,
+		M: MultilinearPoly<P> + Send + Sync,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+		Challenger_: Challenger,
+		Hal: ComputeLayer<F> + Default,
+	{
+		stir::prove(
+			hal,
+			host_alloc,
+			dev_alloc,
+			self,
+			ntt,
+			merkle_prover,
+			commit_meta,
+			committed,
+			codeword,
+			committed_multilins,
+			transparent_multilins,
+			claims,
+			transcript,
+		)
+	}
+
+	fn verify<'a, Challenger_, MTScheme>(
+		&self,
+		commit_meta: &CommitMeta,
+		merkle_scheme: &MTScheme,
+		commitment: &MTScheme::Digest,
+		transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+		claims: &[PIOPSumcheckClaim<F>],
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		Challenger_: Challenger,
+		MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+	{
+		stir::verify(commit_meta, merkle_scheme, self, commitment, transparents, claims, transcript)
+	}
+}
@@ -0,0 +1,92 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_compute::alloc::HostBumpAllocator;
+use binius_field::Field;
+
+use super::verify::CommitMeta;
+
+/// Reusable temporary memory for repeated calls to [`super::prove`].
+///
+/// [`super::prove`] and the sumcheck provers it runs draw all of their temporary buffers, such as
+/// the device copies of the committed multilinears, the fold buffers, and the final evaluations,
+/// from host and device memory provided by the caller. When proving many small instances, freshly
+/// allocating that memory for every proof dominates the cost of the proofs themselves. This object
+/// owns the memory across invocations, growing it when an instance needs more than any previous
+/// one, and never releasing it. For compute layers whose device memory is host memory, such as the
+/// CPU layer, [`Self::allocators`] provides both allocators of a proof.
+///
+/// The contents of the buffers between invocations are unspecified.
+#[derive(Debug, Default)]
+pub struct ProveScratch<F> {
+	host_mem: Vec<F>,
+	dev_mem: Vec<F>,
+}
+
+impl<F: Field> ProveScratch<F> {
+	pub fn new() -> Self {
+		Self {
+			host_mem: Vec::new(),
+			dev_mem: Vec::new(),
+		}
+	}
+
+	/// Returns the sizes, in scalars, of the host and device memory required to prove a batch of
+	/// sumcheck claims on the committed batch described by `commit_meta`.
+	///
+	/// ## Arguments
+	///
+	/// * `commit_meta` - metadata about the committed batch
+	/// * `transparent_n_vars` - the number of variables of each transparent multilinear
+	pub fn required_sizes(
+		commit_meta: &CommitMeta,
+		transparent_n_vars: impl IntoIterator<Item = usize>,
+	) -> (usize, usize) {
+		let mut host_size = commit_meta.total_multilins();
+		// Each committed multilinear is copied to the device and needs a separate fold buffer.
+		let mut dev_size = commit_meta
+			.n_multilins_by_vars()
+			.iter()
+			.enumerate()
+			.map(|(n_vars, &count)| count << (n_vars + 1))
+			.sum::<usize>();
+		// Transparent multilinears are folded in place.
+		for n_vars in transparent_n_vars {
+			host_size += 1;
+			dev_size += 1 << n_vars;
+		}
+		(host_size, dev_size)
+	}
+
+	/// Returns host and device buffers of at least the given sizes, growing the owned memory if
+	/// necessary.
+	pub fn buffers(&mut self, host_size: usize, dev_size: usize) -> (&mut [F], &mut [F]) {
+		if self.host_mem.len() < host_size {
+			self.host_mem.resize(host_size, F::ZERO);
+		}
+		if self.dev_mem.len() < dev_size {
+			self.dev_mem.resize(dev_size, F::ZERO);
+		}
+		(&mut self.host_mem[..host_size], &mut self.dev_mem[..dev_size])
+	}
+
+	/// Returns host and device allocators with the memory required to prove a batch of sumcheck
+	/// claims on the committed batch described by `commit_meta`, growing the owned memory if
+	/// necessary.
+	///
+	/// The arguments are as for [`Self::required_sizes`]. The device allocator draws from host
+	/// memory, so this is for compute layers whose device memory is host memory.
+	pub fn allocators(
+		&mut self,
+		commit_meta: &CommitMeta,
+		transparent_n_vars: impl IntoIterator<Item = usize>,
+	) -> (HostBumpAllocator<'_, F>, HostBumpAllocator<'_, F>) {
+		let (host_size, dev_size) = Self::required_sizes(commit_meta, transparent_n_vars);
+		let (host_mem, dev_mem) = self.buffers(host_size, dev_size);
+		(HostBumpAllocator::new(host_mem), HostBumpAllocator::new(dev_mem))
+	}
+
+	/// The sizes, in scalars, of the host and device memory currently owned.
+	pub fn capacity(&self) -> (usize, usize) {
+		(self.host_mem.len(), self.dev_mem.len())
+	}
+}
@@ -0,0 +1,345 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::iter;
+
+use binius_field::{BinaryField, Field, PackedExtension, PackedField, TowerField};
+use binius_math::MultilinearPoly;
+use binius_ntt::AdditiveNTT;
+use binius_utils::bail;
+use getset::Getters;
+
+use super::{
+	batch::BatchCommitMeta,
+	error::Error,
+	multi_commit::{MultiCommitMeta, SplitClaims},
+	prove::{merge_multilins, packed_committed},
+	verify::{CommitMeta, PIOPSumcheckClaim},
+};
+use crate::{
+	merkle_tree::{MerkleTreeProver, MerkleTreeScheme},
+	oracle::OracleId,
+	protocols::fri::{self, CodewordStorage, CommitOutput, FRIParams},
+};
+
+/// The index of the static batch in the joint layout of a [`StaticCommitMeta`].
+pub(super) const STATIC_SYSTEM: usize = 0;
+/// The index of the dynamic batch in the joint layout of a [`StaticCommitMeta`].
+pub(super) const DYNAMIC_SYSTEM: usize = 1;
+
+/// Metadata about a [`StaticCommitment`] and the dynamic batches opened with it.
+///
+/// The static and the dynamic batch are committed separately, each at its own size. The claims of
+/// a proof reference the committed multilinears of both batches by their index in the joint layout
+/// of [`Self::batch_meta`], as merged by [`BatchCommitMeta::merge_claims`].
+#[derive(Debug)]
+pub struct StaticCommitMeta {
+	/// The joint layout of the static and the dynamic batch, which the claims index.
+	batch_meta: BatchCommitMeta,
+	/// The static and the dynamic batch as separate commitments, which routes the claims to each.
+	multi_commit_meta: MultiCommitMeta,
+}
+
+impl StaticCommitMeta {
+	/// Constructs the metadata of a static batch described by `commit_meta`, opened together with
+	/// dynamic batches described by `dynamic_meta`.
+	pub fn new(commit_meta: CommitMeta, dynamic_meta: CommitMeta) -> Self {
+		let commit_metas = [commit_meta, dynamic_meta];
+		let batch_meta = BatchCommitMeta::new(&commit_metas);
+		let mut committed_locations = vec![(0, 0); batch_meta.commit_meta().total_multilins()];
+		for system in [STATIC_SYSTEM, DYNAMIC_SYSTEM] {
+			let index_map = batch_meta
+				.index_map(system)
+				.expect("the joint layout has a static and a dynamic batch");
+			for (index, &joint_index) in index_map.iter().enumerate() {
+				committed_locations[joint_index] = (system, index);
+			}
+		}
+		let multi_commit_meta =
+			MultiCommitMeta::with_locations(commit_metas.into(), committed_locations);
+		Self {
+			batch_meta,
+			multi_commit_meta,
+		}
+	}
+
+	/// Returns the joint layout of the static and the dynamic batch, which the claims index.
+	pub fn batch_meta(&self) -> &BatchCommitMeta {
+		&self.batch_meta
+	}
+
+	/// Returns the metadata of the static batch.
+	pub fn commit_meta(&self) -> &CommitMeta {
+		self.system_meta(STATIC_SYSTEM)
+	}
+
+	/// Returns the metadata of the dynamic batch committed in each proof.
+	pub fn dynamic_meta(&self) -> &CommitMeta {
+		self.system_meta(DYNAMIC_SYSTEM)
+	}
+
+	/// Returns the metadata of the static or the dynamic batch.
+	pub(super) fn system_meta(&self, system: usize) -> &CommitMeta {
+		&self.multi_commit_meta.commit_metas()[system]
+	}
+
+	/// Returns the batch whose codeword FRI folds from the first round, and the batch whose
+	/// codeword joins the FRI instance after [`Self::entry_round`] fold rounds.
+	///
+	/// The longer batch is folded from the first round, and the dynamic batch if both have the
+	/// same size.
+	pub(super) fn folded_systems(&self) -> (usize, usize) {
+		if self.commit_meta().total_vars() > self.dynamic_meta().total_vars() {
+			(STATIC_SYSTEM, DYNAMIC_SYSTEM)
+		} else {
+			(DYNAMIC_SYSTEM, STATIC_SYSTEM)
+		}
+	}
+
+	/// Returns the number of fold rounds after which the codeword of the shorter batch joins the
+	/// FRI instance, when the folded codeword of the longer batch reaches its length.
+	pub(super) fn entry_round(&self) -> usize {
+		self.commit_meta()
+			.total_vars()
+			.abs_diff(self.dynamic_meta().total_vars())
+	}
+
+	/// Splits claims referencing the joint layout into the claims on the static and on the
+	/// dynamic batch, see [`MultiCommitMeta::split_claims`].
+	pub(super) fn split_claims<F: Field>(
+		&self,
+		n_transparents: usize,
+		claims: &[PIOPSumcheckClaim<F>],
+	) -> Result<Vec<SplitClaims<F>>, Error> {
+		self.multi_commit_meta.split_claims(n_transparents, claims)
+	}
+}
+
+/// A commitment to a batch of static multilinears, computed once and reused across proofs.
+///
+/// Some committed columns, such as lookup tables and program ROMs, are identical in every proof.
+/// Their codeword and Merkle tree are computed once by [`StaticCommitment::new`], and only the
+/// dynamic batch is committed for each proof, by [`StaticCommitment::commit_dynamic`].
+///
+/// Each batch is committed at its own size. [`super::prove_with_static`] opens both codewords in a
+/// single FRI instance: the codeword of the longer batch is folded from the first round, and the
+/// codeword of the shorter batch is added to the round oracle committed once the folded codeword
+/// reaches its length, see [`fri::FRIFolder::with_batched_at_round`]. The verifier, which knows
+/// the static commitment out of band, mirrors it with [`super::verify_with_static`].
+#[derive(Debug, Getters)]
+pub struct StaticCommitment<F, FEncode, P, Digest, Committed>
+where
+	F: BinaryField,
+	FEncode: BinaryField,
+{
+	/// Metadata about the static and the dynamic batch.
+	#[getset(get = "pub")]
+	meta: StaticCommitMeta,
+	/// The FRI parameters of the longer batch, which are those of the FRI instance that opens both
+	/// batches.
+	#[getset(get = "pub")]
+	fri_params: FRIParams<F, FEncode>,
+	/// The FRI parameters of the shorter batch, which joins the FRI instance at a round oracle, if
+	/// the batches differ in size.
+	batched_fri_params: Option<FRIParams<F, FEncode>>,
+	/// The Merkle root of the static codeword.
+	#[getset(get = "pub")]
+	commitment: Digest,
+	/// The prover-side Merkle tree of the static codeword.
+	#[getset(get = "pub")]
+	committed: Committed,
+	/// The encoded static batch.
+	#[getset(get = "pub")]
+	codeword: CodewordStorage<P>,
+}
+
+impl<F, FEncode, P, Digest, Committed> StaticCommitment<F, FEncode, P, Digest, Committed>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+{
+	/// Commits a batch of static multilinears, to be opened together with dynamic batches of the
+	/// shape `dynamic_meta`.
+	///
+	/// The multilinears must satisfy the same requirements as those passed to [`super::commit`].
+	/// `fri_params` must be chosen for the longer of the two batches, and must commit a round
+	/// oracle other than the last after as many fold rounds as the longer batch has variables more
+	/// than the shorter, unless both have the same size. The shorter batch is committed with the
+	/// parameters derived from them by [`FRIParams::batched_codeword_params`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::CommitParamsMismatch`] if the FRI parameters are not chosen for the longer batch
+	/// * [`Error::FRI`] if the FRI parameters commit no round oracle where the shorter batch joins
+	/// * [`Error::IncorrectNumberOfCommitted`] if the number of multilinears does not match
+	///   `commit_meta`
+	/// * [`Error::CommittedVariablesMismatch`] if a multilinear does not match `commit_meta`
+	pub fn new<M, NTT, MTScheme, MTProver>(
+		commit_meta: CommitMeta,
+		dynamic_meta: CommitMeta,
+		fri_params: FRIParams<F, FEncode>,
+		ntt: &NTT,
+		merkle_prover: &MTProver,
+		multilins: &[M],
+	) -> Result<Self, Error>
+	where
+		M: MultilinearPoly<P>,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F, Digest = Digest>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme, Committed = Committed>,
+	{
+		let meta = StaticCommitMeta::new(commit_meta, dynamic_meta);
+		let (long_system, _) = meta.folded_systems();
+		let long_vars = meta.system_meta(long_system).total_vars();
+		if fri_params.n_fold_rounds() != long_vars {
+			bail!(Error::CommitParamsMismatch {
+				expected: long_vars,
+				actual: fri_params.n_fold_rounds(),
+			});
+		}
+
+		let entry_round = meta.entry_round();
+		let batched_fri_params = (entry_round != 0)
+			.then(|| fri_params.batched_codeword_params(ntt, entry_round))
+			.transpose()?;
+		let static_fri_params = match &batched_fri_params {
+			Some(batched_fri_params) if long_system != STATIC_SYSTEM => batched_fri_params,
+			_ => &fri_params,
+		};
+		let CommitOutput {
+			commitment,
+			committed,
+			codeword,
+		} = commit_system(
+			meta.commit_meta(),
+			STATIC_SYSTEM,
+			static_fri_params,
+			ntt,
+			merkle_prover,
+			multilins,
+		)?;
+		Ok(Self {
+			meta,
+			fri_params,
+			batched_fri_params,
+			commitment,
+			committed,
+			codeword,
+		})
+	}
+
+	/// Returns the metadata of the static batch.
+	pub fn commit_meta(&self) -> &CommitMeta {
+		self.meta.commit_meta()
+	}
+
+	/// Returns the metadata of the dynamic batch committed in each proof.
+	pub fn dynamic_meta(&self) -> &CommitMeta {
+		self.meta.dynamic_meta()
+	}
+
+	/// Returns the joint layout of the static and the dynamic batch, which the claims index.
+	pub fn batch_meta(&self) -> &BatchCommitMeta {
+		self.meta.batch_meta()
+	}
+
+	/// Commits the dynamic batch of a proof.
+	///
+	/// The commitment must be written to the transcript before the proof, and is opened together
+	/// with the static commitment by [`super::prove_with_static`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::IncorrectNumberOfCommitted`] if the number of multilinears does not match
+	///   [`Self::dynamic_meta`]
+	/// * [`Error::CommittedVariablesMismatch`] if a multilinear does not match
+	///   [`Self::dynamic_meta`]
+	pub fn commit_dynamic<M, NTT, MTScheme, MTProver>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MTProver,
+		multilins: &[M],
+	) -> Result<CommitOutput<P, Digest, Committed>, Error>
+	where
+		M: MultilinearPoly<P>,
+		NTT: AdditiveNTT<FEncode> + Sync,
+		MTScheme: MerkleTreeScheme<F, Digest = Digest>,
+		MTProver: MerkleTreeProver<F, Scheme = MTScheme, Committed = Committed>,
+	{
+		commit_system(
+			self.dynamic_meta(),
+			DYNAMIC_SYSTEM,
+			self.system_fri_params(DYNAMIC_SYSTEM),
+			ntt,
+			merkle_prover,
+			multilins,
+		)
+	}
+
+	/// Returns the FRI parameters that the static or the dynamic batch is committed with.
+	pub(super) fn system_fri_params(&self, system: usize) -> &FRIParams<F, FEncode> {
+		let (long_system, _) = self.meta.folded_systems();
+		match &self.batched_fri_params {
+			Some(batched_fri_params) if system != long_system => batched_fri_params,
+			_ => &self.fri_params,
+		}
+	}
+}
+
+/// Commits the multilinears of the batch `system`, described by `commit_meta`.
+fn commit_system<F, FEncode, P, M, NTT, MTScheme, MTProver>(
+	commit_meta: &CommitMeta,
+	system: usize,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	multilins: &[M],
+) -> Result<CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+where
+	F: TowerField,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	M: MultilinearPoly<P>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	if commit_meta.total_vars() != fri_params.n_fold_rounds() {
+		bail!(Error::CommitParamsMismatch {
+			expected: commit_meta.total_vars(),
+			actual: fri_params.n_fold_rounds(),
+		});
+	}
+	if multilins.len() != commit_meta.total_multilins() {
+		bail!(Error::IncorrectNumberOfCommitted {
+			system,
+			expected: commit_meta.total_multilins(),
+			actual: multilins.len(),
+		});
+	}
+
+	let expected_n_vars = commit_meta
+		.n_multilins_by_vars()
+		.iter()
+		.enumerate()
+		.flat_map(|(n_vars, &count)| iter::repeat_n(n_vars, count));
+	let packed_multilins = iter::zip(multilins, expected_n_vars)
+		.enumerate()
+		.map(|(i, (multilin, expected))| {
+			let packed = packed_committed(OracleId::from_index(i), multilin)?;
+			if packed.n_vars() != expected {
+				bail!(Error::CommittedVariablesMismatch {
+					index: i,
+					expected,
+					actual: packed.n_vars(),
+				});
+			}
+			Ok(packed)
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	let output = fri::commit_interleaved_with(fri_params, ntt, merkle_prover, |message_buffer| {
+		merge_multilins(&packed_multilins, message_buffer)
+	})?;
+	Ok(output)
+}
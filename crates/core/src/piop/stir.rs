@@ -0,0 +1,523 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A commitment scheme for the batches of multilinears committed by the PIOP compiler that tests
+//! the proximity of the committed codeword with STIR.
+//!
+//! The batch is merged into one message as in [`super::commit`] and committed with
+//! [`stir::commit_interleaved`], as an interleaved Reed–Solomon codeword of $2^b$ messages of
+//! dimension $2^k$. Unlike FRI folding, STIR folding does not reduce the codeword to an evaluation
+//! of the message, since every round after the first folds a quotient of the word sent by the
+//! prover. The sumcheck claims are therefore bound to the committed message as in Ligero [AHIV17]:
+//! the fold rounds of the interleaved sumcheck only record the challenges, and after the sumcheck
+//! the prover sends the combination of the interleaved messages by the tensor of the first $b$
+//! challenges, whose evaluation at the remaining $k$ challenges is the evaluation of the committed
+//! message. The verifier checks the combination against the same combination of the columns of
+//! the committed codeword at random positions, and STIR proves that the committed codeword is
+//! close to the interleaved code.
+//!
+//! The combination has $2^k$ elements, so the dimension of the code trades the size of the proof
+//! against the length of the codeword that STIR tests.
+//!
+//! [AHIV17]: <https://eprint.iacr.org/2022/1608>
+
+use std::{borrow::Borrow, iter, ops::Deref};
+
+use binius_compute::{ComputeLayer, alloc::ComputeAllocator, cpu::CpuMemory};
+use binius_field::{
+	BinaryField, ExtensionField, Field, PackedExtension, PackedField, PackedFieldIndexable,
+	TowerField,
+	packed::{iter_packed_slice_with_offset, len_packed_slice},
+};
+use binius_math::{MultilinearExtension, MultilinearPoly, eq_ind_partial_eval};
+use binius_maybe_rayon::prelude::*;
+use binius_ntt::{AdditiveNTT, NTTShape, SingleThreadedNTT};
+use binius_utils::{DeserializeBytes, SerializeBytes, bail, sorting::is_sorted_ascending};
+use bytemuck::zeroed_vec;
+use bytes::{Buf, BufMut};
+use tracing::instrument;
+
+use super::{
+	error::{Error, VerificationError},
+	prove::{InterleavedFolder, merge_multilins, packed_committed, prove_packed_committed},
+	verify::{CommitMeta, InterleavedFoldVerifier, PIOPSumcheckClaim, verify_with_folder},
+};
+use crate::{
+	fiat_shamir::{CanSampleBits, Challenger},
+	merkle_tree::{MerkleTreeProver, MerkleTreeScheme},
+	oracle::OracleId,
+	polynomial::MultivariatePoly,
+	protocols::{
+		fri::{CommitOutput, FoldRoundOutput},
+		stir::{self, STIRParams},
+	},
+	transcript::{ProverTranscript, TranscriptReader, TranscriptWriter, VerifierTranscript},
+};
+
+/// Commits a batch of multilinear polynomials with the interleaved Reed–Solomon code of the STIR
+/// parameters.
+///
+/// The batch is merged into one message as in [`super::commit`], so the multilinears must be in
+/// ascending order by the number of variables in the packed multilinear.
+#[instrument("piop::stir::commit", skip_all, level = "debug")]
+pub fn commit<F, FEncode, P, M, NTT, MTScheme, MTProver>(
+	params: &STIRParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	multilins: &[M],
+) -> Result<CommitOutput<P, MTScheme::Digest, MTProver::Committed>, Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	M: MultilinearPoly<P>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	let packed_multilins = multilins
+		.iter()
+		.enumerate()
+		.map(|(i, unpacked_committed)| {
+			packed_committed(OracleId::from_index(i), unpacked_committed)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	if !is_sorted_ascending(packed_multilins.iter().map(|mle| mle.n_vars())) {
+		return Err(Error::CommittedsNotSorted);
+	}
+
+	let message = merged_message(params, &packed_multilins)?;
+	Ok(stir::commit_interleaved(params, ntt, merkle_prover, &message)?)
+}
+
+/// Proves a batch of sumcheck claims that are products of committed polynomials from a batch
+/// committed with [`commit`] and transparent polynomials.
+///
+/// This is the STIR counterpart of [`super::prove`], with the same arguments.
+#[allow(clippy::too_many_arguments)]
+#[instrument("piop::stir::prove", skip_all, level = "debug")]
+pub fn prove<Hal, F, FEncode, P, M, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &Hal,
+	host_alloc: &mut impl ComputeAllocator<F, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<F, Hal::DevMem>,
+	params: &STIRParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	commit_meta: &CommitMeta,
+	committed: &MTProver::Committed,
+	codeword: &[P],
+	committed_multilins: &[M],
+	transparent_multilins: &[M],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode> + PackedExtension<FEncode, Scalar = F>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<F, PackedSubfield = P>
+		+ PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Challenger_: Challenger,
+	Hal: ComputeLayer<F> + Default,
+{
+	check_params(params, commit_meta)?;
+
+	let packed_committed_multilins = committed_multilins
+		.iter()
+		.enumerate()
+		.map(|(i, unpacked_committed)| {
+			packed_committed(OracleId::from_index(i), unpacked_committed)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let folder = STIRFolder {
+		params,
+		ntt,
+		merkle_prover,
+		message: merged_message(params, &packed_committed_multilins)?,
+		codeword,
+		committed,
+		challenges: Vec::with_capacity(commit_meta.total_vars()),
+	};
+	prove_packed_committed(
+		hal,
+		host_alloc,
+		dev_alloc,
+		commit_meta,
+		&packed_committed_multilins,
+		transparent_multilins,
+		claims,
+		folder,
+		transcript,
+		&(),
+	)
+}
+
+/// Verifies a batch of sumcheck claims proven with [`prove`].
+///
+/// This is the STIR counterpart of [`super::verify`].
+#[instrument("piop::stir::verify", skip_all)]
+pub fn verify<'a, F, FEncode, Challenger_, MTScheme>(
+	commit_meta: &CommitMeta,
+	merkle_scheme: &MTScheme,
+	params: &STIRParams<F, FEncode>,
+	commitment: &MTScheme::Digest,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode> + PackedExtension<FEncode, Scalar = F>,
+	FEncode: BinaryField,
+	Challenger_: Challenger,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+{
+	check_params(params, commit_meta)?;
+
+	let verifier = STIRFoldVerifier {
+		params,
+		merkle_scheme,
+		commitment,
+	};
+	verify_with_folder(commit_meta, transparents, claims, verifier, transcript, &())
+}
+
+fn check_params<F, FEncode>(
+	params: &STIRParams<F, FEncode>,
+	commit_meta: &CommitMeta,
+) -> Result<(), Error>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	let log_msg_len = params.log_dim() + params.log_batch_size();
+	if log_msg_len != commit_meta.total_vars() {
+		bail!(Error::CommitParamsMismatch {
+			expected: commit_meta.total_vars(),
+			actual: log_msg_len,
+		});
+	}
+	Ok(())
+}
+
+/// Merges the packed multilinears into the interleaved message of the STIR parameters.
+fn merged_message<F, FEncode, P, Data>(
+	params: &STIRParams<F, FEncode>,
+	packed_multilins: &[MultilinearExtension<P, Data>],
+) -> Result<Vec<P>, Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>,
+	Data: Deref<Target = [P]>,
+{
+	let log_msg_len = params.log_dim() + params.log_batch_size();
+	let total_len = packed_multilins
+		.iter()
+		.map(|mle| 1usize << mle.n_vars())
+		.sum::<usize>();
+	if log_msg_len < P::LOG_WIDTH || total_len > 1 << log_msg_len {
+		bail!(Error::CommitParamsTooSmall);
+	}
+
+	let mut message = zeroed_vec::<P>(1 << (log_msg_len - P::LOG_WIDTH));
+	merge_multilins(packed_multilins, &mut message);
+	Ok(message)
+}
+
+/// Combines the interleaved messages with the tensor of the interleaving challenges.
+fn combine_interleaved<F, P>(message: &[P], interleave_challenges: &[F]) -> Vec<F>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+{
+	let log_batch_size = interleave_challenges.len();
+	let interleave_tensor = eq_ind_partial_eval::<F>(interleave_challenges);
+	(0..len_packed_slice(message) >> log_batch_size)
+		.into_par_iter()
+		.map(|index| {
+			iter::zip(
+				iter_packed_slice_with_offset(message, index << log_batch_size),
+				&interleave_tensor,
+			)
+			.map(|(value, &weight)| value * weight)
+			.sum()
+		})
+		.collect()
+}
+
+/// Samples the indices of the leaves of the committed codeword whose columns are checked, in
+/// ascending order and without duplicates.
+///
+/// As many leaves are opened as the first STIR round queries, since that is the number of queries
+/// that detect a word far from the committed code.
+fn sample_column_indices<F, FEncode>(
+	params: &STIRParams<F, FEncode>,
+	sampler: &mut impl CanSampleBits<u32>,
+) -> Vec<usize>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	let mut indices = (0..params.n_queries(0))
+		.map(|_| sampler.sample_bits(log_n_leaves(params)) as usize)
+		.collect::<Vec<_>>();
+	indices.sort_unstable();
+	indices.dedup();
+	indices
+}
+
+/// The binary logarithm of the number of leaves of the committed codeword, each of which holds
+/// the columns of the codeword at $2^{k_0 - b}$ consecutive positions.
+fn log_n_leaves<F, FEncode>(params: &STIRParams<F, FEncode>) -> usize
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	params.log_len() - params.fold_arities()[0]
+}
+
+/// The prover of the STIR opening, which records the challenges of the fold rounds.
+struct STIRFolder<'a, F, FEncode, P, NTT, MTProver>
+where
+	F: BinaryField,
+	FEncode: BinaryField,
+	MTProver: MerkleTreeProver<F>,
+{
+	params: &'a STIRParams<F, FEncode>,
+	ntt: &'a NTT,
+	merkle_prover: &'a MTProver,
+	/// The committed interleaved message.
+	message: Vec<P>,
+	codeword: &'a [P],
+	committed: &'a MTProver::Committed,
+	challenges: Vec<F>,
+}
+
+impl<F, FEncode, P, NTT, MTScheme, MTProver> STIRFolder<'_, F, FEncode, P, NTT, MTProver>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	/// Writes the openings of the leaves of the committed codeword at the given indices.
+	fn prove_column_openings<B: BufMut>(
+		&self,
+		indices: &[usize],
+		advice: &mut TranscriptWriter<B>,
+	) -> Result<(), Error> {
+		let log_leaf_size = self.params.fold_arities()[0];
+		let layer_depth = self
+			.merkle_prover
+			.scheme()
+			.optimal_verify_layer(self.params.n_queries(0), log_n_leaves(self.params));
+		let layer = self
+			.merkle_prover
+			.layer(self.committed, layer_depth)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		advice.write_slice(layer);
+
+		for &index in indices {
+			advice.write_scalar_iter(
+				iter_packed_slice_with_offset(self.codeword, index << log_leaf_size)
+					.take(1 << log_leaf_size),
+			);
+		}
+		self.merkle_prover
+			.prove_openings(self.committed, layer_depth, indices, advice)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))
+	}
+}
+
+impl<F, FEncode, P, NTT, MTScheme, MTProver> InterleavedFolder<F>
+	for STIRFolder<'_, F, FEncode, P, NTT, MTProver>
+where
+	F: TowerField + ExtensionField<FEncode> + PackedExtension<FEncode, Scalar = F>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	type Digest = MTScheme::Digest;
+
+	fn log_batch_size(&self) -> usize {
+		self.params.log_batch_size()
+	}
+
+	fn current_codeword_len(&self) -> usize {
+		1 << self.params.log_len()
+	}
+
+	fn execute_fold_round(&mut self, challenge: F) -> Result<FoldRoundOutput<Self::Digest>, Error> {
+		if self.challenges.len() == self.params.log_dim() + self.params.log_batch_size() {
+			bail!(stir::Error::InvalidArgs(
+				"attempted to fold more times than the message has variables".to_string()
+			));
+		}
+		self.challenges.push(challenge);
+		Ok(FoldRoundOutput::NoCommitment)
+	}
+
+	fn finish_proof<Challenger_: Challenger>(
+		self,
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error> {
+		let params = self.params;
+		if self.challenges.len() != params.log_dim() + params.log_batch_size() {
+			bail!(stir::Error::InvalidArgs(
+				"attempted to finish the proof before executing all fold rounds".to_string()
+			));
+		}
+
+		let combined =
+			combine_interleaved(&self.message, &self.challenges[..params.log_batch_size()]);
+		transcript.message().write_scalar_slice(&combined);
+
+		let indices = sample_column_indices(params, transcript);
+		self.prove_column_openings(&indices, &mut transcript.decommitment())?;
+
+		stir::prove(
+			params,
+			self.ntt,
+			self.merkle_prover,
+			self.codeword,
+			self.committed,
+			transcript,
+		)?;
+		Ok(())
+	}
+}
+
+/// The verifier of the STIR opening.
+struct STIRFoldVerifier<'a, F, FEncode, MTScheme>
+where
+	F: BinaryField,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F>,
+{
+	params: &'a STIRParams<F, FEncode>,
+	merkle_scheme: &'a MTScheme,
+	commitment: &'a MTScheme::Digest,
+}
+
+impl<F, FEncode, MTScheme> STIRFoldVerifier<'_, F, FEncode, MTScheme>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+{
+	/// Reads the openings of the leaves of the committed codeword at the given indices and
+	/// verifies them against the commitment, returning the values of the leaves.
+	fn read_and_verify_column_openings<B: Buf>(
+		&self,
+		indices: &[usize],
+		advice: &mut TranscriptReader<B>,
+	) -> Result<Vec<Vec<F>>, Error> {
+		let tree_depth = log_n_leaves(self.params);
+		let layer_depth = self
+			.merkle_scheme
+			.optimal_verify_layer(self.params.n_queries(0), tree_depth);
+		let layer = advice
+			.read_vec(1 << layer_depth)
+			.map_err(VerificationError::Transcript)?;
+		self.merkle_scheme
+			.verify_layer(self.commitment, layer_depth, &layer)
+			.map_err(|source| VerificationError::InvalidSTIRColumnOpening { source })?;
+
+		let openings = indices
+			.iter()
+			.map(|_| advice.read_scalar_slice::<F>(1 << self.params.fold_arities()[0]))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(VerificationError::Transcript)?;
+		let values = openings.iter().map(Vec::as_slice).collect::<Vec<_>>();
+		self.merkle_scheme
+			.verify_openings(indices, &values, layer_depth, tree_depth, &layer, advice)
+			.map_err(|source| VerificationError::InvalidSTIRColumnOpening { source })?;
+
+		Ok(openings)
+	}
+}
+
+impl<F, FEncode, MTScheme> InterleavedFoldVerifier<F> for STIRFoldVerifier<'_, F, FEncode, MTScheme>
+where
+	F: TowerField + ExtensionField<FEncode> + PackedExtension<FEncode, Scalar = F>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+{
+	fn observe_fold_round<Challenger_: Challenger>(
+		&mut self,
+		_round: usize,
+		_transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Error> {
+		// The fold rounds only sample the challenges.
+		Ok(())
+	}
+
+	fn verify<Challenger_: Challenger>(
+		self,
+		challenges: &[F],
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<F, Error> {
+		let params = self.params;
+		let log_batch_size = params.log_batch_size();
+		assert_eq!(challenges.len(), params.log_dim() + log_batch_size);
+		let (interleave_challenges, eval_challenges) = challenges.split_at(log_batch_size);
+
+		let combined = transcript
+			.message()
+			.read_scalar_slice::<F>(1 << params.log_dim())
+			.map_err(VerificationError::Transcript)?;
+
+		// Encode the combined message like the committed messages are encoded.
+		let ntt = SingleThreadedNTT::with_subspace(params.subspace())?;
+		let log_inv_rate = params.log_inv_rate();
+		let log_code_len = params.log_dim() + log_inv_rate;
+		let mut combined_codeword = Vec::with_capacity(1 << log_code_len);
+		for _ in 0..1 << log_inv_rate {
+			combined_codeword.extend_from_slice(&combined);
+		}
+		let shape = NTTShape {
+			log_y: log_code_len,
+			..Default::default()
+		};
+		ntt.forward_transform_ext(
+			&mut combined_codeword,
+			shape,
+			0,
+			params.log_domain_size() - log_code_len,
+			log_inv_rate,
+		)?;
+
+		let indices = sample_column_indices(params, transcript);
+		let openings =
+			self.read_and_verify_column_openings(&indices, &mut transcript.decommitment())?;
+
+		// Each leaf holds the columns of the committed codeword at consecutive positions.
+		let interleave_tensor = eq_ind_partial_eval::<F>(interleave_challenges);
+		let log_columns_per_leaf = params.fold_arities()[0] - log_batch_size;
+		for (&index, leaf) in iter::zip(&indices, &openings) {
+			for (i, column) in leaf.chunks(1 << log_batch_size).enumerate() {
+				let position = (index << log_columns_per_leaf) + i;
+				let combined_value = iter::zip(column, &interleave_tensor)
+					.map(|(&value, &weight)| value * weight)
+					.sum::<F>();
+				if combined_value != combined_codeword[position] {
+					bail!(VerificationError::IncorrectSTIRColumn { position });
+				}
+			}
+		}
+
+		stir::verify(params, self.merkle_scheme, self.commitment, transcript)?;
+
+		let eval_tensor = eq_ind_partial_eval::<F>(eval_challenges);
+		Ok(iter::zip(&combined, &eval_tensor)
+			.map(|(&value, &weight)| value * weight)
+			.sum())
+	}
+}
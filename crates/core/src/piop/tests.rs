@@ -2,31 +2,72 @@
 
 use std::iter::repeat_with;
 
-use binius_compute::cpu::CpuLayer;
+use binius_compute::{
+	alloc::{ComputeAllocator, HostBumpAllocator},
+	cpu::{CpuLayer, CpuMemory},
+};
 use binius_field::{
-	BinaryField, Field, PackedBinaryField2x128b, PackedExtension, PackedField, PackedFieldIndexable,
+	BinaryField, ExtensionField, Field, PackedBinaryField2x128b, PackedExtension, PackedField,
+	PackedFieldIndexable,
+};
+use binius_hash::{
+	blake3::Blake3Compression,
+	groestl::{Groestl256, Groestl256ByteCompression},
+	keccak::Keccak256Compression,
+	sha2::Sha256Compression,
 };
-use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
 use binius_math::{
-	B8, B16, B128, MLEDirectAdapter, MultilinearExtension, MultilinearPoly, TowerTop,
+	B8, B16, B128, MLEDirectAdapter, MultilinearExtension, MultilinearPoly, MultilinearQuery,
+	MultilinearQueryRef, TowerTop,
 };
 use binius_ntt::SingleThreadedNTT;
-use binius_utils::{DeserializeBytes, SerializeBytes};
+use binius_utils::{DeserializeBytes, SerializationError, SerializationMode, SerializeBytes};
+use either::Either;
+use itertools::izip;
 use rand::{Rng, SeedableRng, rngs::StdRng};
+use sha2::Sha256;
 
 use super::{
-	PIOPSumcheckClaim, prove,
-	prove::commit,
-	verify,
-	verify::{CommitMeta, make_commit_params_with_optimal_arity},
+	BatchCommitMeta, BatchVerifyInstance, CommitParamsBuilder, CommittedEvalClaim,
+	CommittedWitnessBuffer, Error, GpuProfile, HardwareProfile, IncrementalCommitMeta,
+	IncrementalCommitter, MultiCommitMeta, MultiPointClaims, PIOPCommitmentScheme, PIOPOptions,
+	PIOPPhase, PIOPSumcheckClaim, PhaseTimings, ProofSectionContents, ProveScratch,
+	RateBatchedCommitMeta, StaticCommitment, VerificationError,
+	basefold::BaseFoldParams,
+	collect_committed_witness_chunks, estimate_prover_time, inspect_proof, make_oracle_commit_meta,
+	prove,
+	prove::{
+		SumcheckMismatchKind, commit, commit_device, commit_hiding, commit_rate_batches,
+		commit_streaming, commit_with_options, commit_witness_buffer, prove_device_committed,
+		prove_incremental, prove_multi_commit, prove_rate_batches, prove_with_options,
+		prove_with_static, prove_zk, validate_sumcheck_witness,
+	},
+	remap_committed_ids, verify,
+	verify::{
+		CommitMeta, make_commit_params_with_optimal_arity, verify_incremental, verify_multi_commit,
+		verify_rate_batches, verify_with_static, verify_zk,
+	},
+	verify_batch, verify_with_options,
 };
 use crate::{
 	fiat_shamir::HasherChallenger,
-	merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver, MerkleTreeScheme},
+	merkle_tree::{
+		BinaryMerkleTreeProver, Blake3MerkleTreeProver, Keccak256MerkleTreeProver,
+		MerkleTreeProver, MerkleTreeScheme, QuaternaryMerkleTreeProver, Sha256MerkleTreeProver,
+		StorageConfig, VisionMerkleTreeProver,
+	},
+	oracle::{MultilinearOracleSet, OracleId, OracleIdRemapping},
 	polynomial::MultivariatePoly,
-	protocols::fri::CommitOutput,
-	transcript::ProverTranscript,
+	protocols::{
+		fri::{
+			self, CodewordStorage, CommitOutput, FRICostModel, FRIParams, FRIVerifierCostModel,
+			SoundnessModel,
+		},
+		stir::STIRParams,
+	},
+	transcript::{ProverTranscript, VerifierTranscript},
 	transparent,
+	witness::DeviceWitnessIndex,
 };
 
 const SECURITY_BITS: usize = 32;
@@ -118,8 +159,32 @@ fn commit_prove_verify<FDomain, FEncode, F, P, MTScheme>(
 		merkle_scheme,
 		SECURITY_BITS,
 		log_inv_rate,
+		SoundnessModel::Provable,
 	)
 	.unwrap();
+	commit_prove_verify_with_params::<FDomain, _, _, P, _>(
+		commit_meta,
+		&fri_params,
+		n_transparents,
+		merkle_prover,
+	);
+}
+
+fn commit_prove_verify_with_params<FDomain, FEncode, F, P, MTScheme>(
+	commit_meta: &CommitMeta,
+	fri_params: &FRIParams<F, FEncode>,
+	n_transparents: usize,
+	merkle_prover: &impl MerkleTreeProver<F, Scheme = MTScheme>,
+) where
+	FDomain: BinaryField,
+	FEncode: BinaryField,
+	F: TowerTop,
+	P: PackedFieldIndexable<Scalar = F>
+		+ PackedExtension<FDomain>
+		+ PackedExtension<FEncode>
+		+ PackedExtension<F, PackedSubfield = P>,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes + DeserializeBytes>,
+{
 	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
 
 	let mut rng = StdRng::seed_from_u64(0);
@@ -132,7 +197,7 @@ fn commit_prove_verify<FDomain, FEncode, F, P, MTScheme>(
 		commitment,
 		committed,
 		codeword,
-	} = commit(&fri_params, &ntt, merkle_prover, &committed_multilins).unwrap();
+	} = commit(fri_params, &ntt, merkle_prover, &committed_multilins).unwrap();
 
 	let transparent_multilins_by_vars = commit_meta
 		.n_multilins_by_vars()
@@ -149,8 +214,100 @@ fn commit_prove_verify<FDomain, FEncode, F, P, MTScheme>(
 	let sumcheck_claims =
 		make_sumcheck_claims(&committed_multilins, transparent_multilins.as_slice());
 
+	prove_verify(
+		commit_meta,
+		fri_params,
+		&ntt,
+		merkle_prover,
+		CommitOutput {
+			commitment,
+			committed,
+			codeword,
+		},
+		&committed_multilins,
+		&transparent_mles,
+		&sumcheck_claims,
+	);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_verify<FEncode, F, P, MTScheme, MTProver>(
+	commit_meta: &CommitMeta,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &SingleThreadedNTT<FEncode>,
+	merkle_prover: &MTProver,
+	commit_output: CommitOutput<P, MTScheme::Digest, MTProver::Committed>,
+	committed_multilins: &[MLEDirectAdapter<P>],
+	transparent_mles: &[MultilinearExtension<P>],
+	sumcheck_claims: &[PIOPSumcheckClaim<F>],
+) where
+	FEncode: BinaryField,
+	F: TowerTop,
+	P: PackedFieldIndexable<Scalar = F>
+		+ PackedExtension<FEncode>
+		+ PackedExtension<F, PackedSubfield = P>,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes + DeserializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	let CommitOutput {
+		commitment,
+		committed,
+		codeword,
+	} = commit_output;
+
 	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
 	proof.message().write(&commitment);
+	prove_claims(
+		commit_meta,
+		fri_params,
+		ntt,
+		merkle_prover,
+		&committed,
+		&codeword,
+		committed_multilins,
+		transparent_mles,
+		sumcheck_claims,
+		&mut proof,
+	);
+
+	let mut proof = proof.into_verifier();
+	let commitment = proof.message().read().unwrap();
+	verify_claims(
+		commit_meta,
+		merkle_prover.scheme(),
+		fri_params,
+		&commitment,
+		transparent_mles,
+		sumcheck_claims,
+		&mut proof,
+	);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_claims<FEncode, F, P, MTScheme, MTProver>(
+	commit_meta: &CommitMeta,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &SingleThreadedNTT<FEncode>,
+	merkle_prover: &MTProver,
+	committed: &MTProver::Committed,
+	codeword: &[P],
+	committed_multilins: &[MLEDirectAdapter<P>],
+	transparent_mles: &[MultilinearExtension<P>],
+	sumcheck_claims: &[PIOPSumcheckClaim<F>],
+	proof: &mut ProverTranscript<HasherChallenger<Groestl256>>,
+) where
+	FEncode: BinaryField,
+	F: TowerTop,
+	P: PackedFieldIndexable<Scalar = F>
+		+ PackedExtension<FEncode>
+		+ PackedExtension<F, PackedSubfield = P>,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+{
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
 
 	let host_mem_size_committed = committed_multilins.len();
 	let dev_mem_size_committed = committed_multilins
@@ -159,33 +316,62 @@ fn commit_prove_verify<FDomain, FEncode, F, P, MTScheme>(
 		.sum::<usize>();
 
 	let host_mem_size_transparent = transparent_multilins.len();
+	// Transparent multilinears are folded in place, so need no device memory for folding.
 	let dev_mem_size_transparent = transparent_multilins
 		.iter()
-		.map(|multilin| 1 << (multilin.n_vars() + 1))
+		.map(|multilin| 1 << multilin.n_vars())
 		.sum::<usize>();
 
 	let hal = CpuLayer::<F>::default();
-	let mut host_mem = vec![F::ZERO; host_mem_size_committed + host_mem_size_transparent];
-	let mut dev_mem = vec![F::ZERO; dev_mem_size_committed + dev_mem_size_transparent - 1];
-	prove(
+	let host_mem_size = host_mem_size_committed + host_mem_size_transparent;
+	let dev_mem_size = dev_mem_size_committed + dev_mem_size_transparent;
+	let mut host_mem = vec![F::ZERO; host_mem_size];
+	let mut dev_mem = vec![F::ZERO; dev_mem_size];
+	let mut host_alloc = HostBumpAllocator::new(host_mem.as_mut_slice());
+	let mut dev_alloc = HostBumpAllocator::new(dev_mem.as_mut_slice());
+	let timings = PhaseTimings::new();
+	prove_with_options(
 		&hal,
-		&mut host_mem,
-		&mut dev_mem,
-		&fri_params,
-		&ntt,
+		&mut host_alloc,
+		&mut dev_alloc,
+		fri_params,
+		ntt,
 		merkle_prover,
 		commit_meta,
 		committed,
-		&codeword,
-		&committed_multilins,
+		codeword,
+		committed_multilins,
 		&transparent_multilins,
-		&sumcheck_claims,
-		&mut proof,
+		sumcheck_claims,
+		proof,
+		&PIOPOptions::new().with_observer(&timings),
 	)
 	.unwrap();
 
-	let mut proof = proof.into_verifier();
+	// Every fold round is reported, and the query phase once.
+	assert_eq!(timings.count(PIOPPhase::FriFold), commit_meta.total_vars());
+	assert_eq!(timings.count(PIOPPhase::FriQuery), 1);
+	assert_eq!(timings.count(PIOPPhase::Ntt), 0);
 
+	// The buffers of the proof are reclaimed by the allocators once it is done.
+	assert_eq!(host_alloc.capacity(), host_mem_size);
+	assert_eq!(dev_alloc.capacity(), dev_mem_size);
+}
+
+fn verify_claims<FEncode, F, P, MTScheme>(
+	commit_meta: &CommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &FRIParams<F, FEncode>,
+	commitment: &MTScheme::Digest,
+	transparent_mles: &[MultilinearExtension<P>],
+	sumcheck_claims: &[PIOPSumcheckClaim<F>],
+	proof: &mut VerifierTranscript<HasherChallenger<Groestl256>>,
+) where
+	FEncode: BinaryField,
+	F: TowerTop + ExtensionField<FEncode>,
+	P: PackedField<Scalar = F> + PackedExtension<F, PackedSubfield = P>,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+{
 	let transparent_polys = transparent_mles
 		.iter()
 		.map(|mle| {
@@ -201,17 +387,72 @@ fn commit_prove_verify<FDomain, FEncode, F, P, MTScheme>(
 		.map(|poly| poly as &dyn MultivariatePoly<F>)
 		.collect::<Vec<_>>();
 
-	let commitment = proof.message().read().unwrap();
-	verify(
+	let timings = PhaseTimings::new();
+	verify_with_options(
 		commit_meta,
 		merkle_scheme,
-		&fri_params,
-		&commitment,
+		fri_params,
+		commitment,
 		&transparent_polys,
-		&sumcheck_claims,
-		&mut proof,
+		sumcheck_claims,
+		proof,
+		&PIOPOptions::new().with_observer(&timings),
 	)
 	.unwrap();
+
+	assert_eq!(timings.count(PIOPPhase::FriFold), commit_meta.total_vars());
+	assert_eq!(timings.count(PIOPPhase::FriQuery), 1);
+}
+
+#[test]
+fn test_validate_sumcheck_witness_report() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins =
+		generate_multilins::<PackedBinaryField2x128b>(&[0, 0, 0, 1, 1], &mut rng)
+			.into_iter()
+			.map(MLEDirectAdapter::from)
+			.collect::<Vec<_>>();
+	let transparent_multilins =
+		generate_multilins::<PackedBinaryField2x128b>(&[0, 0, 0, 1, 1], &mut rng)
+			.into_iter()
+			.map(MLEDirectAdapter::from)
+			.collect::<Vec<_>>();
+	let mut claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let report =
+		validate_sumcheck_witness(&committed_multilins, &transparent_multilins, &claims).unwrap();
+	assert!(report.is_satisfied());
+
+	claims[1].sum += B128::ONE;
+	claims.push(PIOPSumcheckClaim {
+		n_vars: 3,
+		committed: 1,
+		transparent: 0,
+		sum: B128::ZERO,
+	});
+	let report =
+		validate_sumcheck_witness(&committed_multilins, &transparent_multilins, &claims).unwrap();
+	assert_eq!(report.mismatches.len(), 2);
+
+	let first_mismatch = report.first_mismatch().unwrap();
+	assert_eq!(
+		(first_mismatch.claim, first_mismatch.committed, first_mismatch.transparent),
+		(1, 1, 1)
+	);
+	assert_eq!(
+		first_mismatch.kind,
+		SumcheckMismatchKind::Sum {
+			expected: claims[1].sum,
+			actual: claims[1].sum + B128::ONE,
+		}
+	);
+	assert_eq!(
+		report.mismatches[1].kind,
+		SumcheckMismatchKind::CommittedVariables {
+			expected: 3,
+			actual: 4,
+		}
+	);
 }
 
 #[test]
@@ -223,6 +464,309 @@ fn test_commit_meta_total_vars() {
 	assert_eq!(commit_meta.total_vars(), 9);
 }
 
+#[test]
+fn test_commit_meta_serialization_round_trip() {
+	let mode = SerializationMode::CanonicalTower;
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7])
+		.with_tower_levels(vec![0, 3, 7, 7])
+		.unwrap();
+
+	let mut buf = Vec::new();
+	commit_meta.serialize(&mut buf, mode).unwrap();
+	let loaded = CommitMeta::deserialize(buf.as_slice(), mode).unwrap();
+	assert_eq!(loaded.n_multilins_by_vars(), commit_meta.n_multilins_by_vars());
+	assert_eq!(loaded.tower_levels(), commit_meta.tower_levels());
+	assert_eq!(loaded.total_vars(), commit_meta.total_vars());
+	assert_eq!(loaded.range_by_vars(6), commit_meta.range_by_vars(6));
+
+	let json = serde_json::to_string(&commit_meta).unwrap();
+	let loaded: CommitMeta = serde_json::from_str(&json).unwrap();
+	assert_eq!(loaded.tower_levels(), commit_meta.tower_levels());
+
+	// Tower levels that do not match the number of multilinears are rejected.
+	let mut invalid_buf = Vec::new();
+	CommitMeta::with_vars([4, 4])
+		.serialize(&mut invalid_buf, mode)
+		.unwrap();
+	let n_multilins_len = invalid_buf.len() - 1;
+	invalid_buf.truncate(n_multilins_len);
+	Some(vec![0usize])
+		.serialize(&mut invalid_buf, mode)
+		.unwrap();
+	assert!(matches!(
+		CommitMeta::deserialize(invalid_buf.as_slice(), mode),
+		Err(SerializationError::InvalidConstruction { .. })
+	));
+
+	// An unknown version is rejected.
+	buf[0] += 1;
+	assert!(matches!(
+		CommitMeta::deserialize(buf.as_slice(), mode),
+		Err(SerializationError::UnsupportedVersion { .. })
+	));
+}
+
+#[test]
+fn test_estimate_prover_time() {
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let profile = HardwareProfile::measure::<B128, B16, _>(&merkle_prover).unwrap();
+	assert!(profile.n_threads >= 1);
+	assert!(profile.mul_ns.is_finite() && profile.mul_ns >= 0.0);
+
+	let estimate = |n_vars: usize, profile: &HardwareProfile| {
+		let commit_meta = CommitMeta::with_vars([n_vars, n_vars]);
+		let fri_params = make_commit_params_with_optimal_arity::<B128, B16, _>(
+			&commit_meta,
+			merkle_prover.scheme(),
+			SECURITY_BITS,
+			1,
+			SoundnessModel::Provable,
+		)
+		.unwrap();
+		let claims = (0..2)
+			.map(|committed| PIOPSumcheckClaim {
+				n_vars,
+				committed,
+				transparent: 0,
+				sum: B128::ZERO,
+			})
+			.collect::<Vec<_>>();
+		estimate_prover_time(&commit_meta, &fri_params, &claims, profile)
+	};
+
+	let profile = HardwareProfile {
+		n_threads: 1,
+		mul_ns: 10.0,
+		butterfly_ns: 5.0,
+		hash_ns_per_byte: 2.0,
+		compression_ns: 200.0,
+		..profile
+	};
+	let small = estimate(8, &profile);
+	let large = estimate(12, &profile);
+	assert!(large.total_ns() > small.total_ns());
+	assert!(large.sumcheck_ns > small.sumcheck_ns);
+
+	let multi_threaded = estimate(
+		12,
+		&HardwareProfile {
+			n_threads: 4,
+			..profile
+		},
+	);
+	assert!(multi_threaded.total_ns() < large.total_ns());
+
+	let accelerated = estimate(
+		12,
+		&HardwareProfile {
+			gpu: Some(GpuProfile { speedup: 64.0 }),
+			..profile
+		},
+	);
+	assert!(accelerated.total() < multi_threaded.total());
+}
+
+#[test]
+fn test_commit_params_builder_with_cost_model() {
+	let commit_meta = CommitMeta::with_vars([6, 8, 10, 12]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	// When hashing is expensive, folding by more variables before each commitment pays off.
+	let proof_size_model = FRICostModel::default();
+	let hash_heavy_model = FRICostModel {
+		hash_ns_per_byte: 100.0,
+		..Default::default()
+	};
+	let proof_size_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_cost_model(&proof_size_model)
+		.build::<B128, B16>(&commit_meta)
+		.unwrap();
+	let hash_heavy_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_cost_model(&hash_heavy_model)
+		.build::<B128, B16>(&commit_meta)
+		.unwrap();
+	assert!(hash_heavy_params.fold_arities()[0] >= proof_size_params.fold_arities()[0]);
+
+	for fri_params in [proof_size_params, hash_heavy_params] {
+		commit_prove_verify_with_params::<B8, _, _, PackedBinaryField2x128b, _>(
+			&commit_meta,
+			&fri_params,
+			2,
+			&merkle_prover,
+		);
+	}
+}
+
+#[test]
+fn test_commit_prove_verify_with_arity_schedule() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_arity_schedule(2, vec![3, 2, 1])
+		.build::<B128, B16>(&commit_meta)
+		.unwrap();
+	assert_eq!(fri_params.log_batch_size(), 2);
+	assert_eq!(fri_params.fold_arities(), &[3, 2, 1]);
+	commit_prove_verify_with_params::<B8, _, _, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		&fri_params,
+		2,
+		&merkle_prover,
+	);
+
+	// The builder sizes the test queries of the same schedule with the chosen soundness model.
+	let conjectured_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_soundness_model(SoundnessModel::ConjecturedListDecoding)
+		.with_arity_schedule(2, vec![3, 2, 1])
+		.build::<B128, B16>(&commit_meta)
+		.unwrap();
+	assert_eq!(conjectured_params.fold_arities(), fri_params.fold_arities());
+	assert_eq!(conjectured_params.soundness_model(), SoundnessModel::ConjecturedListDecoding);
+	assert!(conjectured_params.n_test_queries() < fri_params.n_test_queries());
+	commit_prove_verify_with_params::<B8, _, _, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		&conjectured_params,
+		2,
+		&merkle_prover,
+	);
+
+	let schedule_error = |log_batch_size, fold_arities| {
+		CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+			.with_arity_schedule(log_batch_size, fold_arities)
+			.build::<B128, B16>(&commit_meta)
+			.unwrap_err()
+	};
+	assert!(matches!(
+		schedule_error(2, vec![3, 0]),
+		Error::FRI(fri::Error::FoldArityIsZero { index: 1 })
+	));
+	assert!(matches!(
+		schedule_error(2, vec![1, 2]),
+		Error::FRI(fri::Error::FirstFoldArityTooSmall)
+	));
+	assert!(matches!(
+		schedule_error(2, vec![4, 4]),
+		Error::FRI(fri::Error::InvalidFoldAritySequence)
+	));
+	assert!(matches!(schedule_error(10, vec![]), Error::FRI(fri::Error::InvalidArgs(_))));
+}
+
+#[test]
+fn test_commit_prove_verify_with_optimal_arity_schedule() {
+	let commit_meta = CommitMeta::with_vars([6, 8, 10, 12]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_optimal_arity_schedule()
+		.build::<B128, B16>(&commit_meta)
+		.unwrap();
+	assert_eq!(fri_params.log_batch_size(), fri_params.fold_arities()[0]);
+	commit_prove_verify_with_params::<B8, _, _, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		&fri_params,
+		2,
+		&merkle_prover,
+	);
+}
+
+#[test]
+fn test_commit_prove_verify_with_verifier_cost_model() {
+	let commit_meta = CommitMeta::with_vars([6, 8, 10, 12]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let make_fri_params = |cost_model: &FRIVerifierCostModel| {
+		CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+			.with_verifier_cost_model(cost_model)
+			.build::<B128, B16>(&commit_meta)
+			.unwrap()
+	};
+
+	// Counting only proof bytes, the search does no worse than the proof-size heuristic.
+	let proof_size_params = make_fri_params(&FRIVerifierCostModel::default());
+	let heuristic_params = make_commit_params_with_optimal_arity::<B128, B16, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	assert!(proof_size_params.estimate_proof_size(32) <= heuristic_params.estimate_proof_size(32));
+
+	// Folding a coset takes more multiplications the larger it is, so a verifier paying mostly for
+	// multiplications folds by fewer variables at a time.
+	let mul_heavy_params = make_fri_params(&FRIVerifierCostModel {
+		mul_cost: 1e6,
+		..Default::default()
+	});
+	assert!(mul_heavy_params.fold_arities()[0] < proof_size_params.fold_arities()[0]);
+
+	for fri_params in [proof_size_params, mul_heavy_params] {
+		commit_prove_verify_with_params::<B8, _, _, PackedBinaryField2x128b, _>(
+			&commit_meta,
+			&fri_params,
+			2,
+			&merkle_prover,
+		);
+	}
+}
+
+#[test]
+fn test_commit_prove_verify_with_log_batch_size() {
+	let commit_meta = CommitMeta::with_vars([6, 8, 10, 12]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	for log_batch_size in [0, 3, 6] {
+		let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+			.with_log_batch_size(log_batch_size)
+			.build::<B128, B16>(&commit_meta)
+			.unwrap();
+		assert_eq!(fri_params.log_batch_size(), log_batch_size);
+		assert_eq!(fri_params.rs_code().log_dim(), commit_meta.total_vars() - log_batch_size);
+		assert!(fri_params.fold_arities()[0] >= log_batch_size);
+		commit_prove_verify_with_params::<B8, _, _, PackedBinaryField2x128b, _>(
+			&commit_meta,
+			&fri_params,
+			2,
+			&merkle_prover,
+		);
+	}
+
+	assert!(matches!(
+		CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+			.with_log_batch_size(commit_meta.total_vars() + 1)
+			.build::<B128, B16>(&commit_meta),
+		Err(Error::FRI(fri::Error::InvalidArgs(_)))
+	));
+}
+
+#[test]
+fn test_commit_prove_verify_conjectured_soundness() {
+	let commit_meta = CommitMeta::with_vars([6, 6, 8, 9]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let make_params = |soundness_model| {
+		make_commit_params_with_optimal_arity::<B128, B16, _>(
+			&commit_meta,
+			merkle_prover.scheme(),
+			SECURITY_BITS,
+			1,
+			soundness_model,
+		)
+		.unwrap()
+	};
+
+	let provable = make_params(SoundnessModel::Provable);
+	let fri_params = make_params(SoundnessModel::ConjecturedListDecoding);
+	assert_eq!(fri_params.soundness_model(), SoundnessModel::ConjecturedListDecoding);
+	assert!(fri_params.n_test_queries() < provable.n_test_queries());
+	commit_prove_verify_with_params::<B8, _, _, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		&fri_params,
+		2,
+		&merkle_prover,
+	);
+}
+
 #[test]
 fn test_with_one_poly() {
 	let commit_meta = CommitMeta::with_vars([4]);
@@ -312,3 +856,2243 @@ fn test_commit_prove_verify() {
 		log_inv_rate,
 	);
 }
+
+#[test]
+fn test_commit_prove_verify_blake3() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = Blake3MerkleTreeProver::new(Blake3Compression::default());
+	let n_transparents = 2;
+	let log_inv_rate = 1;
+
+	commit_prove_verify::<B8, B16, B128, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		n_transparents,
+		&merkle_prover,
+		log_inv_rate,
+	);
+}
+
+#[test]
+fn test_commit_prove_verify_sha256() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = Sha256MerkleTreeProver::new(Sha256Compression::default());
+	let n_transparents = 2;
+	let log_inv_rate = 1;
+
+	commit_prove_verify::<B8, B16, B128, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		n_transparents,
+		&merkle_prover,
+		log_inv_rate,
+	);
+}
+
+#[test]
+fn test_commit_prove_verify_salted_leaves() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_salted_leaves(StdRng::seed_from_u64(1));
+	let n_transparents = 2;
+	let log_inv_rate = 1;
+
+	commit_prove_verify::<B8, B16, B128, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		n_transparents,
+		&merkle_prover,
+		log_inv_rate,
+	);
+}
+
+#[test]
+fn test_commit_prove_verify_zk() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_salted_leaves(StdRng::seed_from_u64(1));
+	// The hiding multilinear is large enough for the random half of each message to hide the
+	// symbols opened by the test queries.
+	let hiding_meta = commit_meta.with_hiding_multilin(10).unwrap();
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_arity_schedule(2, vec![2, 2, 2])
+		.build::<B128, B16>(&hiding_meta)
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins =
+		generate_multilins::<PackedBinaryField2x128b>(commit_meta.n_multilins_by_vars(), &mut rng)
+			.into_iter()
+			.map(MLEDirectAdapter::from)
+			.collect::<Vec<_>>();
+	let (
+		CommitOutput {
+			commitment,
+			committed,
+			codeword,
+		},
+		hiding_multilin,
+	) = commit_hiding(
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&committed_multilins,
+		StdRng::seed_from_u64(3),
+	)
+	.unwrap();
+
+	let transparent_mles =
+		generate_multilins::<PackedBinaryField2x128b>(&[0, 0, 0, 0, 1, 0, 1, 1], &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let dev_mem_size = committed_multilins
+		.iter()
+		.chain(&transparent_multilins)
+		.map(|multilin| 1 << (multilin.n_vars() + 1))
+		.sum::<usize>()
+		+ (1 << (hiding_multilin.n_vars() + 1));
+	let hal = CpuLayer::<B128>::default();
+	let mut host_mem =
+		vec![B128::ZERO; committed_multilins.len() + 1 + transparent_multilins.len()];
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.message().write(&commitment);
+	prove_zk(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&commit_meta,
+		&committed,
+		&codeword,
+		&committed_multilins,
+		&hiding_multilin,
+		&transparent_multilins,
+		&sumcheck_claims,
+		StdRng::seed_from_u64(2),
+		&mut proof,
+	)
+	.unwrap();
+
+	let transparent_polys = transparent_mles
+		.iter()
+		.map(|mle| {
+			transparent::MultilinearExtensionTransparent::<
+				PackedBinaryField2x128b,
+				PackedBinaryField2x128b,
+			>::from_values_and_mu(mle.evals().to_vec(), mle.n_vars())
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<B128>)
+		.collect::<Vec<_>>();
+	let proof_bytes = proof.finalize();
+	let verify_zk_claims = |claims: &[PIOPSumcheckClaim<B128>]| {
+		let mut proof =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof_bytes.clone());
+		let commitment = proof.message().read().unwrap();
+		verify_zk(
+			&commit_meta,
+			merkle_prover.scheme(),
+			&fri_params,
+			&commitment,
+			&transparent_polys,
+			claims,
+			&mut proof,
+		)
+	};
+	verify_zk_claims(&sumcheck_claims).unwrap();
+
+	let mut wrong_claims = sumcheck_claims.clone();
+	wrong_claims[0].sum += B128::ONE;
+	assert!(verify_zk_claims(&wrong_claims).is_err());
+}
+
+/// Returns the rank of a matrix over a field, given by its rows.
+fn rank<F: Field>(mut rows: Vec<Vec<F>>) -> usize {
+	let n_cols = rows.first().map_or(0, |row| row.len());
+	let mut rank = 0;
+	for col in 0..n_cols {
+		let Some(pivot) = (rank..rows.len()).find(|&i| rows[i][col] != F::ZERO) else {
+			continue;
+		};
+		rows.swap(rank, pivot);
+		let inv = rows[rank][col].invert().expect("pivot is non-zero");
+		let pivot_row = rows[rank].iter().map(|&x| x * inv).collect::<Vec<_>>();
+		for row in &mut rows[rank + 1..] {
+			let factor = row[col];
+			for (x, &pivot_x) in izip!(row.iter_mut(), &pivot_row) {
+				*x -= factor * pivot_x;
+			}
+		}
+		rows[rank] = pivot_row;
+		rank += 1;
+	}
+	rank
+}
+
+#[test]
+fn test_commit_hiding_opened_symbols_independent_of_witness() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([4, 5]);
+	let hiding_meta = commit_meta.with_hiding_multilin(6).unwrap();
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_arity_schedule(1, vec![2, 2])
+		.build::<B128, B16>(&hiding_meta)
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	// Two witnesses committed with the same randomness share the hiding multilinear.
+	let mut rng = StdRng::seed_from_u64(0);
+	let commit_witness = |rng: &mut StdRng| {
+		let multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), rng)
+			.into_iter()
+			.map(MLEDirectAdapter::from)
+			.collect::<Vec<_>>();
+		commit_hiding(&fri_params, &ntt, &merkle_prover, &multilins, StdRng::seed_from_u64(1))
+			.unwrap()
+	};
+	let (output_1, hiding_multilin_1) = commit_witness(&mut rng);
+	let (output_2, hiding_multilin_2) = commit_witness(&mut rng);
+	assert_eq!(hiding_multilin_1.evals(), hiding_multilin_2.evals());
+	assert_ne!(&*output_1.codeword, &*output_2.codeword);
+
+	// Open as many random leaves as there are random coefficients in the first half of the
+	// message.
+	let log_leaf_len = fri_params.fold_arities()[0];
+	let n_random = 1 << (fri_params.n_fold_rounds() - 1);
+	let opened = rand::seq::index::sample(
+		&mut rng,
+		1 << (fri_params.log_len() - log_leaf_len),
+		n_random >> log_leaf_len,
+	)
+	.into_iter()
+	.flat_map(|leaf| (leaf << log_leaf_len)..((leaf + 1) << log_leaf_len))
+	.collect::<Vec<_>>();
+
+	// The opened symbols are the sum of a linear function of the random coefficients and a
+	// function of the witness. The linear function is onto if it has full rank, in which case the
+	// opened symbols are uniformly random whatever the witness.
+	let random_encodings = (0..n_random)
+		.map(|i| {
+			let mut codeword = vec![P::zero(); 1 << (fri_params.log_len() - P::LOG_WIDTH)];
+			P::unpack_scalars_mut(&mut codeword)[i] = B128::ONE;
+			fri::encode_interleaved_prefilled(&fri_params, &ntt, &mut codeword).unwrap();
+			P::unpack_scalars(&codeword).to_vec()
+		})
+		.collect::<Vec<_>>();
+	let opened_rows = opened
+		.iter()
+		.map(|&index| {
+			random_encodings
+				.iter()
+				.map(|encoding| encoding[index])
+				.collect()
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(rank(opened_rows), opened.len());
+}
+
+#[test]
+fn test_verify_batch() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let transparent_mles = generate_multilins::<P>(&[0, 0, 0, 0, 1, 0, 1, 1], &mut rng);
+	let transparent_polys = transparent_mles
+		.iter()
+		.map(|mle| {
+			transparent::MultilinearExtensionTransparent::<P, P>::from_values_and_mu(
+				mle.evals().to_vec(),
+				mle.n_vars(),
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<B128>)
+		.collect::<Vec<_>>();
+
+	let mut claims = Vec::new();
+	let mut proofs = Vec::new();
+	for _ in 0..3 {
+		let committed_multilins =
+			generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+				.into_iter()
+				.map(MLEDirectAdapter::from)
+				.collect::<Vec<_>>();
+		let transparent_multilins = transparent_mles
+			.iter()
+			.map(|mle| MLEDirectAdapter::from(mle.clone()))
+			.collect::<Vec<_>>();
+		let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+		let CommitOutput {
+			commitment,
+			committed,
+			codeword,
+		} = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+		let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		proof.message().write(&commitment);
+		prove_claims(
+			&commit_meta,
+			&fri_params,
+			&ntt,
+			&merkle_prover,
+			&committed,
+			&codeword,
+			&committed_multilins,
+			&transparent_mles,
+			&sumcheck_claims,
+			&mut proof,
+		);
+		claims.push(sumcheck_claims);
+		proofs.push(proof.finalize());
+	}
+
+	let verify_proofs = |claims: &[Vec<PIOPSumcheckClaim<B128>>]| {
+		let mut transcripts = proofs
+			.iter()
+			.map(|proof| VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone()))
+			.collect::<Vec<_>>();
+		let commitments = transcripts
+			.iter_mut()
+			.map(|transcript| transcript.message().read().unwrap())
+			.collect::<Vec<_>>();
+		let mut instances = izip!(&commitments, claims, &mut transcripts)
+			.map(|(commitment, claims, transcript)| BatchVerifyInstance {
+				commitment,
+				transparents: &transparent_polys,
+				claims,
+				transcript,
+			})
+			.collect::<Vec<_>>();
+		verify_batch(&commit_meta, merkle_prover.scheme(), &fri_params, &mut instances)?;
+		for transcript in transcripts {
+			transcript.finalize().unwrap();
+		}
+		Ok::<_, Error>(())
+	};
+	verify_proofs(&claims).unwrap();
+
+	let mut wrong_claims = claims.clone();
+	wrong_claims[1][0].sum += B128::ONE;
+	assert!(matches!(
+		verify_proofs(&wrong_claims),
+		Err(Error::InvalidBatchedProof { index: 1, .. })
+	));
+}
+
+#[test]
+fn test_commit_prove_verify_keccak256() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = Keccak256MerkleTreeProver::new(Keccak256Compression);
+	let n_transparents = 2;
+	let log_inv_rate = 1;
+
+	commit_prove_verify::<B8, B16, B128, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		n_transparents,
+		&merkle_prover,
+		log_inv_rate,
+	);
+}
+
+#[test]
+fn test_commit_prove_verify_vision() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = VisionMerkleTreeProver::new();
+	let n_transparents = 2;
+	let log_inv_rate = 1;
+
+	commit_prove_verify::<B8, B16, B128, PackedBinaryField2x128b, _>(
+		&commit_meta,
+		n_transparents,
+		&merkle_prover,
+		log_inv_rate,
+	);
+}
+
+#[test]
+fn test_incremental_commit_prove_verify() {
+	type P = PackedBinaryField2x128b;
+
+	// The committed columns of three tables are the parts of the commitment. The parts with 7 and
+	// 5 variables join the FRI instance of the part with 8 at the round oracles committed after one
+	// and three fold rounds.
+	let meta = IncrementalCommitMeta::new(vec![
+		CommitMeta::with_vars([4, 6]),
+		CommitMeta::with_vars([0, 1, 5, 7]),
+		CommitMeta::with_vars([4, 4]),
+	]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_arity_schedule(0, vec![1, 2, 2])
+		.build::<B128, B16>(&meta.part_metas()[1])
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = meta
+		.part_metas()
+		.iter()
+		.flat_map(|part_meta| generate_multilins::<P>(part_meta.n_multilins_by_vars(), &mut rng))
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+
+	let mut committer =
+		IncrementalCommitter::new(&fri_params, &ntt, &merkle_prover, &meta).unwrap();
+	for index in [3, 0, 7, 5, 1, 2, 6, 4] {
+		assert!(!committer.is_complete());
+		committer.add(index, &committed_multilins[index]).unwrap();
+	}
+	assert!(committer.is_complete());
+	assert!(committer.add(3, &committed_multilins[3]).is_err());
+	let output = committer.finish().unwrap();
+	// Each part is committed at its own size.
+	assert_eq!(output.parts[1].codeword.len(), 2 * output.parts[0].codeword.len());
+	assert_eq!(output.parts[1].codeword.len(), 8 * output.parts[2].codeword.len());
+
+	// Add the multilinears one table at a time. Each table is committed as soon as it is added,
+	// and only its root is kept for the commitment.
+	let mut committer =
+		IncrementalCommitter::new(&fri_params, &ntt, &merkle_prover, &meta).unwrap();
+	committer.add_batch(6, &committed_multilins[6..]).unwrap();
+	assert_eq!(committer.part_commitment(2), Some(&output.parts[2].commitment));
+	assert_eq!(committer.part_commitment(0), None);
+	// A batch overlapping an added multilinear is rejected without adding any of it.
+	assert!(matches!(
+		committer.add_batch(4, &committed_multilins[4..7]),
+		Err(Error::CommittedAlreadyAdded { index: 6 })
+	));
+	assert!(matches!(
+		committer.add_batch(0, &committed_multilins[1..3]),
+		Err(Error::CommittedVariablesMismatch { index: 0, .. })
+	));
+	committer.add_batch(2, &committed_multilins[2..5]).unwrap();
+	assert_eq!(committer.part_commitment(1), None);
+	committer.add_batch(5, &committed_multilins[5..6]).unwrap();
+	assert_eq!(committer.part_commitment(1), Some(&output.parts[1].commitment));
+	assert!(!committer.is_complete());
+	committer.add_batch(0, &committed_multilins[..2]).unwrap();
+	assert!(committer.is_complete());
+	assert_eq!(committer.finish().unwrap().commitment, output.commitment);
+
+	let transparent_mles = generate_multilins::<P>(&[1, 1, 0, 0, 1, 1, 1, 1], &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let hal = CpuLayer::<B128>::default();
+	let mut host_mem = vec![B128::ZERO; committed_multilins.len() + transparent_multilins.len()];
+	let dev_mem_size = committed_multilins
+		.iter()
+		.map(|multilin| 1 << (multilin.n_vars() + 1))
+		.chain(
+			transparent_multilins
+				.iter()
+				.map(|multilin| 1 << multilin.n_vars()),
+		)
+		.sum::<usize>();
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.message().write(&output.commitment);
+	prove_incremental(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&meta,
+		&output,
+		&committed_multilins,
+		&transparent_multilins,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+
+	let transparent_polys = transparent_mles
+		.iter()
+		.map(|mle| {
+			transparent::MultilinearExtensionTransparent::<P, P>::from_values_and_mu(
+				mle.evals().to_vec(),
+				mle.n_vars(),
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<B128>)
+		.collect::<Vec<_>>();
+	let proof_bytes = proof.finalize();
+	let verify_incremental_proof = |claims: &[PIOPSumcheckClaim<B128>], commitment: Option<_>| {
+		let mut proof =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof_bytes.clone());
+		let read_commitment = proof.message().read()?;
+		verify_incremental(
+			&meta,
+			merkle_prover.scheme(),
+			&fri_params,
+			&commitment.unwrap_or(read_commitment),
+			&transparent_polys,
+			claims,
+			&mut proof,
+		)?;
+		Ok::<_, Error>(proof)
+	};
+	verify_incremental_proof(&sumcheck_claims, None)
+		.unwrap()
+		.finalize()
+		.unwrap();
+
+	// A single FRI proof opens all parts, so a wrong claim on a part that joins late is rejected.
+	let mut wrong_claims = sumcheck_claims.clone();
+	let wrong_claim = wrong_claims
+		.iter_mut()
+		.find(|claim| meta.committed_location(claim.committed).unwrap().0 == 2)
+		.unwrap();
+	wrong_claim.sum += B128::ONE;
+	assert!(verify_incremental_proof(&wrong_claims, None).is_err());
+
+	// The roots of the parts must hash to the commitment.
+	assert!(matches!(
+		verify_incremental_proof(&sumcheck_claims, Some(output.parts[1].commitment)),
+		Err(Error::VerificationError(VerificationError::IncorrectPartCommitments))
+	));
+}
+
+#[test]
+fn test_incremental_commit_prove_verify_nary() {
+	type P = PackedBinaryField2x128b;
+
+	// The roots of the three parts are hashed with the compression of the quaternary scheme, which
+	// has no Merkle cap.
+	let meta = IncrementalCommitMeta::new(vec![
+		CommitMeta::with_vars([4, 6]),
+		CommitMeta::with_vars([0, 1, 5, 7]),
+		CommitMeta::with_vars([4, 4]),
+	]);
+	let merkle_prover =
+		QuaternaryMerkleTreeProver::<_, Sha256, _>::new(Sha256Compression::default());
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_arity_schedule(0, vec![1, 2, 2])
+		.build::<B128, B16>(&meta.part_metas()[1])
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = meta
+		.part_metas()
+		.iter()
+		.flat_map(|part_meta| generate_multilins::<P>(part_meta.n_multilins_by_vars(), &mut rng))
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+
+	let mut committer =
+		IncrementalCommitter::new(&fri_params, &ntt, &merkle_prover, &meta).unwrap();
+	for (index, multilin) in committed_multilins.iter().enumerate() {
+		committer.add(index, multilin).unwrap();
+	}
+	let output = committer.finish().unwrap();
+	assert_eq!(output.parts.len(), 3);
+
+	let transparent_mles = generate_multilins::<P>(&[1, 1, 0, 0, 1, 1, 1, 1], &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let hal = CpuLayer::<B128>::default();
+	let mut host_mem = vec![B128::ZERO; committed_multilins.len() + transparent_multilins.len()];
+	let dev_mem_size = committed_multilins
+		.iter()
+		.map(|multilin| 1 << (multilin.n_vars() + 1))
+		.chain(
+			transparent_multilins
+				.iter()
+				.map(|multilin| 1 << multilin.n_vars()),
+		)
+		.sum::<usize>();
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.message().write(&output.commitment);
+	prove_incremental(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&meta,
+		&output,
+		&committed_multilins,
+		&transparent_multilins,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+
+	let transparent_polys = transparent_mles
+		.iter()
+		.map(|mle| {
+			transparent::MultilinearExtensionTransparent::<P, P>::from_values_and_mu(
+				mle.evals().to_vec(),
+				mle.n_vars(),
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<B128>)
+		.collect::<Vec<_>>();
+	let mut proof = proof.into_verifier();
+	let commitment = proof.message().read().unwrap();
+	verify_incremental(
+		&meta,
+		merkle_prover.scheme(),
+		&fri_params,
+		&commitment,
+		&transparent_polys,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+	proof.finalize().unwrap();
+
+	// The roots of the parts must hash to the commitment.
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prove_incremental(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&meta,
+		&output,
+		&committed_multilins,
+		&transparent_multilins,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+	assert!(matches!(
+		verify_incremental(
+			&meta,
+			merkle_prover.scheme(),
+			&fri_params,
+			&output.parts[0].commitment,
+			&transparent_polys,
+			&sumcheck_claims,
+			&mut proof.into_verifier(),
+		),
+		Err(Error::VerificationError(VerificationError::IncorrectPartCommitments))
+	));
+}
+
+#[test]
+fn test_incremental_commit_tables_nary() {
+	type P = PackedBinaryField2x128b;
+
+	let meta = IncrementalCommitMeta::new(vec![
+		CommitMeta::with_vars([4, 6]),
+		CommitMeta::with_vars([0, 1, 5, 7]),
+		CommitMeta::with_vars([4, 4]),
+	]);
+	let merkle_prover =
+		QuaternaryMerkleTreeProver::<_, Sha256, _>::new(Sha256Compression::default());
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_arity_schedule(0, vec![1, 2, 2])
+		.build::<B128, B16>(&meta.part_metas()[1])
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = meta
+		.part_metas()
+		.iter()
+		.flat_map(|part_meta| generate_multilins::<P>(part_meta.n_multilins_by_vars(), &mut rng))
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+
+	let mut committer =
+		IncrementalCommitter::new(&fri_params, &ntt, &merkle_prover, &meta).unwrap();
+	for (index, multilin) in committed_multilins.iter().enumerate() {
+		committer.add(index, multilin).unwrap();
+	}
+	let output = committer.finish().unwrap();
+
+	// Adding the multilinears one table at a time commits each table as it is added, and the
+	// roots of the tables are combined into the same commitment.
+	let mut committer =
+		IncrementalCommitter::new(&fri_params, &ntt, &merkle_prover, &meta).unwrap();
+	committer.add_batch(6, &committed_multilins[6..]).unwrap();
+	assert_eq!(committer.part_commitment(2), Some(&output.parts[2].commitment));
+	committer.add_batch(2, &committed_multilins[2..6]).unwrap();
+	assert_eq!(committer.part_commitment(1), Some(&output.parts[1].commitment));
+	committer.add_batch(0, &committed_multilins[..2]).unwrap();
+	assert_eq!(committer.part_commitment(0), Some(&output.parts[0].commitment));
+	let table_output = committer.finish().unwrap();
+	assert_eq!(table_output.commitment, output.commitment);
+	assert_ne!(table_output.commitment, output.parts[0].commitment);
+}
+
+#[test]
+fn test_witness_buffer_commit_matches_commit() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([0, 1, 4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+
+	let expected = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+	let mut buffer = CommittedWitnessBuffer::<P>::new(&commit_meta);
+	assert!(buffer.column_mut(0).is_err());
+	for (index, multilin) in committed_multilins.iter().enumerate() {
+		buffer.write(index, multilin).unwrap();
+	}
+	assert!(buffer.write(0, &committed_multilins[1]).is_err());
+
+	let output = commit_witness_buffer(&fri_params, &ntt, &merkle_prover, &buffer).unwrap();
+	assert_eq!(output.commitment, expected.commitment);
+	assert_eq!(output.codeword, expected.codeword);
+
+	for (column, multilin) in buffer.multilins().unwrap().iter().zip(&committed_multilins) {
+		assert_eq!(column.n_vars(), multilin.n_vars());
+		for i in 0..1 << multilin.n_vars() {
+			assert_eq!(
+				column.evaluate_on_hypercube(i).unwrap(),
+				multilin.evaluate_on_hypercube(i).unwrap()
+			);
+		}
+	}
+}
+
+#[test]
+fn test_streaming_commit_matches_commit() {
+	type P = PackedBinaryField2x128b;
+
+	let mut oracles = MultilinearOracleSet::<B128>::new();
+	let n_varss = [7, 4, 0, 6, 4, 1];
+	for n_vars in n_varss {
+		oracles.add_committed(n_vars, 7);
+	}
+	let (commit_meta, oracle_to_commit_index) = make_oracle_commit_meta(&oracles).unwrap();
+	// A single part is committed as the whole batch, and its root is the commitment.
+	let meta = IncrementalCommitMeta::new(vec![commit_meta]);
+	let commit_meta = &meta.part_metas()[0];
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_storage(StorageConfig::Mapped(std::env::temp_dir()));
+	let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+		commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let witnesses = n_varss
+		.iter()
+		.map(|&n_vars| generate_multilin::<P>(n_vars, &mut rng))
+		.collect::<Vec<_>>();
+
+	let mut committed_multilins = vec![None; commit_meta.total_multilins()];
+	for (oracle_id, witness) in oracles.ids().zip(&witnesses) {
+		let &index = oracle_to_commit_index.get(oracle_id.index()).unwrap();
+		committed_multilins[index] = Some(MLEDirectAdapter::from(witness.clone()));
+	}
+	let committed_multilins = committed_multilins
+		.into_iter()
+		.map(Option::unwrap)
+		.collect::<Vec<_>>();
+	let expected = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+	let witness_chunks = collect_committed_witness_chunks(
+		commit_meta,
+		&oracle_to_commit_index,
+		&oracles,
+		|oracle_id| Ok(witnesses[oracle_id.index()].evals().chunks(3)),
+	)
+	.unwrap();
+	let output = commit_streaming(
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&StorageConfig::Mapped(std::env::temp_dir()),
+		&meta,
+		witness_chunks,
+	)
+	.unwrap();
+	assert!(matches!(output.parts[0].codeword, CodewordStorage::Mapped(_)));
+	assert_eq!(output.commitment, expected.commitment);
+	assert_eq!(output.parts[0].codeword, expected.codeword);
+
+	// Chunks that do not hold exactly the packed evaluations are rejected, and the multilinear
+	// may be added again.
+	let last = commit_meta.total_multilins() - 1;
+	let evals = committed_multilins[last].packed_evals().unwrap();
+	let mut committer =
+		IncrementalCommitter::new(&fri_params, &ntt, &merkle_prover, &meta).unwrap();
+	assert!(matches!(
+		committer.add_chunks(last, [&evals[1..]]),
+		Err(Error::CommittedChunksLengthMismatch { index, .. }) if index == last
+	));
+	assert!(matches!(
+		committer.add_chunks(last, [evals, &evals[..1]]),
+		Err(Error::CommittedChunksLengthMismatch { index, .. }) if index == last
+	));
+	committer.add_chunks(last, [evals]).unwrap();
+	assert!(committer.add_chunks(last, [evals]).is_err());
+}
+
+#[test]
+fn test_mapped_codeword_commit_prove_verify() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([0, 1, 4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let mapped_merkle_prover =
+		BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+			.with_storage(StorageConfig::Mapped(std::env::temp_dir()));
+	let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+
+	let expected = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+	let timings = PhaseTimings::new();
+	let output = commit_with_options(
+		&fri_params,
+		&ntt,
+		&mapped_merkle_prover,
+		&committed_multilins,
+		&PIOPOptions::new()
+			.with_storage(StorageConfig::Mapped(std::env::temp_dir()))
+			.with_observer(&timings),
+	)
+	.unwrap();
+	assert_eq!(timings.count(PIOPPhase::Ntt), 1);
+	assert_eq!(timings.count(PIOPPhase::MerkleCommit), 1);
+	assert!(matches!(output.codeword, CodewordStorage::Mapped(_)));
+	assert_eq!(output.commitment, expected.commitment);
+	assert_eq!(output.codeword, expected.codeword);
+
+	let transparent_mles = generate_multilins::<P>(&[1, 1, 0, 0, 1, 0, 1, 1], &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	prove_verify(
+		&commit_meta,
+		&fri_params,
+		&ntt,
+		&mapped_merkle_prover,
+		output,
+		&committed_multilins,
+		&transparent_mles,
+		&sumcheck_claims,
+	);
+}
+
+#[test]
+fn test_device_committed_commit_prove_verify() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([0, 1, 4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+	let expected = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+	// Simulate witnesses generated on the device by writing them to device memory up front.
+	let device_buffers = committed_multilins
+		.iter()
+		.map(|multilin| {
+			(0..1 << multilin.n_vars())
+				.map(|i| multilin.evaluate_on_hypercube(i).unwrap())
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	let mut device_index = DeviceWitnessIndex::<B128, CpuMemory>::new();
+	device_index
+		.update(
+			device_buffers
+				.iter()
+				.enumerate()
+				.map(|(i, buffer)| (OracleId::from_index(i), buffer.as_slice())),
+		)
+		.unwrap();
+	let device_committed = (0..committed_multilins.len())
+		.map(|i| device_index.get(OracleId::from_index(i)).unwrap())
+		.collect::<Vec<_>>();
+
+	let hal = CpuLayer::<B128>::default();
+	assert!(
+		commit_device::<_, _, _, P, _, _, _>(
+			&hal,
+			&fri_params,
+			&ntt,
+			&merkle_prover,
+			&commit_meta,
+			&device_committed[1..],
+		)
+		.is_err()
+	);
+	let CommitOutput {
+		commitment,
+		committed,
+		codeword,
+	} = commit_device(&hal, &fri_params, &ntt, &merkle_prover, &commit_meta, &device_committed)
+		.unwrap();
+	assert_eq!(commitment, expected.commitment);
+	assert_eq!(codeword, expected.codeword);
+
+	let transparent_mles = generate_multilins::<P>(&[1, 1, 0, 0, 1, 0, 1, 1], &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let dev_mem_size = committed_multilins
+		.iter()
+		.chain(&transparent_multilins)
+		.map(|multilin| 1 << multilin.n_vars())
+		.sum::<usize>();
+	let mut host_mem = vec![B128::ZERO; committed_multilins.len() + transparent_multilins.len()];
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.message().write(&commitment);
+	prove_device_committed(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&commit_meta,
+		&committed,
+		&codeword,
+		&device_committed,
+		&transparent_multilins,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+
+	let mut proof = proof.into_verifier();
+	let commitment = proof.message().read().unwrap();
+	verify_claims(
+		&commit_meta,
+		merkle_prover.scheme(),
+		&fri_params,
+		&commitment,
+		&transparent_mles,
+		&sumcheck_claims,
+		&mut proof,
+	);
+}
+
+#[test]
+fn test_prove_scratch_reused_across_proofs() {
+	type P = PackedBinaryField2x128b;
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let hal = CpuLayer::<B128>::default();
+	let mut scratch = ProveScratch::new();
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mut capacities = Vec::new();
+	for commit_meta in [
+		CommitMeta::with_vars([4, 4, 6]),
+		CommitMeta::with_vars([3, 5]),
+		CommitMeta::with_vars([4, 4, 6]),
+	] {
+		let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+			&commit_meta,
+			merkle_prover.scheme(),
+			SECURITY_BITS,
+			1,
+			SoundnessModel::Provable,
+		)
+		.unwrap();
+		let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+		let committed_multilins =
+			generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+				.into_iter()
+				.map(MLEDirectAdapter::from)
+				.collect::<Vec<_>>();
+		let CommitOutput {
+			commitment,
+			committed,
+			codeword,
+		} = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+		let transparent_multilins_by_vars = commit_meta
+			.n_multilins_by_vars()
+			.iter()
+			.map(|&n_committed| usize::from(n_committed != 0))
+			.collect::<Vec<_>>();
+		let transparent_mles = generate_multilins::<P>(&transparent_multilins_by_vars, &mut rng);
+		let transparent_multilins = transparent_mles
+			.iter()
+			.map(|mle| MLEDirectAdapter::from(mle.clone()))
+			.collect::<Vec<_>>();
+		let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+		let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		proof.message().write(&commitment);
+		let (mut host_alloc, mut dev_alloc) = scratch.allocators(
+			&commit_meta,
+			transparent_multilins
+				.iter()
+				.map(|multilin| multilin.n_vars()),
+		);
+		prove(
+			&hal,
+			&mut host_alloc,
+			&mut dev_alloc,
+			&fri_params,
+			&ntt,
+			&merkle_prover,
+			&commit_meta,
+			&committed,
+			&codeword,
+			&committed_multilins,
+			&transparent_multilins,
+			&sumcheck_claims,
+			&mut proof,
+		)
+		.unwrap();
+		drop((host_alloc, dev_alloc));
+		capacities.push(scratch.capacity());
+
+		let mut proof = proof.into_verifier();
+		let commitment = proof.message().read().unwrap();
+		verify_claims(
+			&commit_meta,
+			merkle_prover.scheme(),
+			&fri_params,
+			&commitment,
+			&transparent_mles,
+			&sumcheck_claims,
+			&mut proof,
+		);
+	}
+
+	// The scratch memory is retained, and not grown for instances no larger than previous ones.
+	assert_eq!(capacities[0], capacities[1]);
+	assert_eq!(capacities[0], capacities[2]);
+}
+
+#[test]
+fn test_commit_prove_verify_batched_systems() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_metas = [
+		CommitMeta::with_vars([4, 6, 7]),
+		CommitMeta::with_vars([4, 5]),
+		CommitMeta::with_vars([6]),
+	];
+	let batch_meta = BatchCommitMeta::new(&commit_metas);
+	let commit_meta = batch_meta.commit_meta();
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+		commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let committed_by_system = commit_metas
+		.iter()
+		.map(|meta| {
+			generate_multilins::<P>(meta.n_multilins_by_vars(), &mut rng)
+				.into_iter()
+				.map(MLEDirectAdapter::from)
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	let transparents_by_system = commit_metas
+		.iter()
+		.map(|meta| {
+			let n_transparents_by_vars = meta
+				.n_multilins_by_vars()
+				.iter()
+				.map(|&n_committed| n_committed.min(1))
+				.collect::<Vec<_>>();
+			generate_multilins::<P>(&n_transparents_by_vars, &mut rng)
+		})
+		.collect::<Vec<_>>();
+	let claims_by_system = committed_by_system
+		.iter()
+		.zip(&transparents_by_system)
+		.map(|(committed, transparents)| {
+			let transparents = transparents
+				.iter()
+				.map(|mle| MLEDirectAdapter::from(mle.clone()))
+				.collect::<Vec<_>>();
+			make_sumcheck_claims(committed, &transparents)
+		})
+		.collect::<Vec<_>>();
+
+	let committed_multilins = batch_meta.merge_committed(&committed_by_system).unwrap();
+	let commit_output = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+	let (transparent_mles, sumcheck_claims) = batch_meta
+		.merge_claims(&transparents_by_system, &claims_by_system, |mle| mle.n_vars())
+		.unwrap();
+
+	prove_verify(
+		commit_meta,
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		commit_output,
+		&committed_multilins,
+		&transparent_mles,
+		&sumcheck_claims,
+	);
+}
+
+#[test]
+fn test_commit_prove_verify_rate_batches() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([4, 5, 6, 6, 7]);
+	let rate_batched_meta = RateBatchedCommitMeta::new(commit_meta, &[1, 2, 1, 3, 2]).unwrap();
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.build_rate_batches::<_, B16>(&rate_batched_meta)
+		.unwrap();
+	let log_inv_rates = fri_params
+		.iter()
+		.map(|params| params.rs_code().log_inv_rate())
+		.collect::<Vec<_>>();
+	assert_eq!(log_inv_rates, vec![1, 2, 3]);
+
+	// A single NTT over the largest evaluation domain encodes every rate batch.
+	let largest_params = fri_params
+		.iter()
+		.max_by_key(|params| params.rs_code().log_len())
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(largest_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let commit_meta = rate_batched_meta.commit_meta();
+	let committed_multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+	let transparent_mles = generate_multilins::<P>(&[0, 0, 0, 0, 1, 1, 1, 1], &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let commit_outputs = commit_rate_batches(
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&rate_batched_meta,
+		&committed_multilins,
+	)
+	.unwrap();
+
+	// The memory required by the joint batch bounds the memory required by each rate batch.
+	let hal = CpuLayer::<B128>::default();
+	let mut host_mem = vec![B128::ZERO; committed_multilins.len() + transparent_multilins.len()];
+	let dev_mem_size = committed_multilins
+		.iter()
+		.map(|multilin| 1 << (multilin.n_vars() + 1))
+		.chain(
+			transparent_multilins
+				.iter()
+				.map(|multilin| 1 << multilin.n_vars()),
+		)
+		.sum::<usize>();
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	for output in &commit_outputs {
+		proof.message().write(&output.commitment);
+	}
+	prove_rate_batches(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&rate_batched_meta,
+		&commit_outputs,
+		&committed_multilins,
+		&transparent_multilins,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+
+	let mut proof = proof.into_verifier();
+	let commitments = (0..rate_batched_meta.n_batches())
+		.map(|_| proof.message().read().unwrap())
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_mles
+		.iter()
+		.map(|mle| {
+			transparent::MultilinearExtensionTransparent::<P, P>::from_values_and_mu(
+				mle.evals().to_vec(),
+				mle.n_vars(),
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<B128>)
+		.collect::<Vec<_>>();
+	verify_rate_batches(
+		&rate_batched_meta,
+		merkle_prover.scheme(),
+		&fri_params,
+		&commitments,
+		&transparent_polys,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+	proof.finalize().unwrap();
+}
+
+#[test]
+fn test_commit_prove_verify_multi_commit() {
+	type P = PackedBinaryField2x128b;
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let make_fri_params = |commit_meta: &CommitMeta| {
+		make_commit_params_with_optimal_arity::<_, B16, _>(
+			commit_meta,
+			merkle_prover.scheme(),
+			SECURITY_BITS,
+			1,
+			SoundnessModel::Provable,
+		)
+		.unwrap()
+	};
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	// A static commitment computed ahead of the proof and a commitment to the proof's witness.
+	let static_meta = CommitMeta::with_vars([4, 6]);
+	let static_fri_params = make_fri_params(&static_meta);
+	let dynamic_meta = CommitMeta::with_vars([5, 7]);
+	let dynamic_fri_params = make_fri_params(&dynamic_meta);
+
+	// A single NTT over the largest evaluation domain encodes both commitments.
+	let largest_params = [&static_fri_params, &dynamic_fri_params]
+		.into_iter()
+		.max_by_key(|params| params.rs_code().log_len())
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(largest_params.rs_code().subspace()).unwrap();
+
+	let static_multilins = generate_multilins::<P>(static_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+	let static_output =
+		commit(&static_fri_params, &ntt, &merkle_prover, &static_multilins).unwrap();
+
+	let dynamic_multilins = generate_multilins::<P>(dynamic_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+	let CommitOutput {
+		commitment,
+		committed,
+		codeword,
+	} = commit(&dynamic_fri_params, &ntt, &merkle_prover, &dynamic_multilins).unwrap();
+
+	let multi_commit_meta = MultiCommitMeta::new(vec![static_meta, dynamic_meta]);
+	let fri_params = [&static_fri_params, &dynamic_fri_params];
+
+	let committed_multilins = static_multilins
+		.iter()
+		.chain(&dynamic_multilins)
+		.cloned()
+		.collect::<Vec<_>>();
+	let transparent_mles = generate_multilins::<P>(&[0, 0, 0, 0, 1, 1, 1, 1], &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let hal = CpuLayer::<B128>::default();
+	let mut host_mem = vec![B128::ZERO; committed_multilins.len() + transparent_multilins.len()];
+	let dev_mem_size = committed_multilins
+		.iter()
+		.map(|multilin| 1 << (multilin.n_vars() + 1))
+		.chain(
+			transparent_multilins
+				.iter()
+				.map(|multilin| 1 << multilin.n_vars()),
+		)
+		.sum::<usize>();
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.observe().write(&static_output.commitment);
+	proof.message().write(&commitment);
+	prove_multi_commit(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&multi_commit_meta,
+		&[&static_output.committed, &committed],
+		&[&static_output.codeword[..], &codeword[..]],
+		&committed_multilins,
+		&transparent_multilins,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+
+	let mut proof = proof.into_verifier();
+	proof.observe().write(&static_output.commitment);
+	let commitment = proof.message().read().unwrap();
+	let transparent_polys = transparent_mles
+		.iter()
+		.map(|mle| {
+			transparent::MultilinearExtensionTransparent::<P, P>::from_values_and_mu(
+				mle.evals().to_vec(),
+				mle.n_vars(),
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<B128>)
+		.collect::<Vec<_>>();
+	verify_multi_commit(
+		&multi_commit_meta,
+		merkle_prover.scheme(),
+		&fri_params,
+		&[static_output.commitment, commitment],
+		&transparent_polys,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+	proof.finalize().unwrap();
+
+	// The verifier rejects a proof opening the wrong number of commitments.
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new().into_verifier();
+	assert!(matches!(
+		verify_multi_commit(
+			&multi_commit_meta,
+			merkle_prover.scheme(),
+			&fri_params[..1],
+			&[commitment],
+			&transparent_polys,
+			&sumcheck_claims,
+			&mut proof,
+		),
+		Err(Error::IncorrectNumberOfCommitments {
+			expected: 2,
+			actual: 1
+		})
+	));
+}
+
+#[test]
+fn test_static_commitment_reused_across_proofs() {
+	type P = PackedBinaryField2x128b;
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let make_transparents = |commit_meta: &CommitMeta, rng: &mut StdRng| {
+		let n_transparents_by_vars = commit_meta
+			.n_multilins_by_vars()
+			.iter()
+			.map(|&n_committed| n_committed.min(1))
+			.collect::<Vec<_>>();
+		generate_multilins::<P>(&n_transparents_by_vars, rng)
+	};
+	let make_claims = |committed: &[MLEDirectAdapter<P>],
+	                   transparents: &[MultilinearExtension<P>]| {
+		let transparents = transparents
+			.iter()
+			.map(|mle| MLEDirectAdapter::from(mle.clone()))
+			.collect::<Vec<_>>();
+		make_sumcheck_claims(committed, &transparents)
+	};
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	// The static batch has 7 variables and the dynamic batch 8, so the static codeword joins the
+	// FRI instance at the round oracle committed after the first fold round.
+	let static_n_vars = [4, 6];
+	let dynamic_n_vars = [4, 5, 7];
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_arity_schedule(0, vec![1, 2, 2])
+		.build::<B128, B16>(&CommitMeta::with_vars(dynamic_n_vars))
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+	let static_multilins = generate_multilins::<P>(
+		CommitMeta::with_vars(static_n_vars).n_multilins_by_vars(),
+		&mut rng,
+	)
+	.into_iter()
+	.map(MLEDirectAdapter::from)
+	.collect::<Vec<_>>();
+	let static_commitment = StaticCommitment::new(
+		CommitMeta::with_vars(static_n_vars),
+		CommitMeta::with_vars(dynamic_n_vars),
+		fri_params,
+		&ntt,
+		&merkle_prover,
+		&static_multilins,
+	)
+	.unwrap();
+
+	let hal = CpuLayer::<B128>::default();
+	for _ in 0..2 {
+		let dynamic_multilins = generate_multilins::<P>(
+			static_commitment.dynamic_meta().n_multilins_by_vars(),
+			&mut rng,
+		)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+		let CommitOutput {
+			commitment,
+			committed,
+			codeword,
+		} = static_commitment
+			.commit_dynamic(&ntt, &merkle_prover, &dynamic_multilins)
+			.unwrap();
+		// Each batch is committed at its own size.
+		assert_eq!(codeword.len(), 2 * static_commitment.codeword().len());
+
+		let static_transparents = make_transparents(static_commitment.commit_meta(), &mut rng);
+		let static_claims = make_claims(&static_multilins, &static_transparents);
+		let dynamic_transparents = make_transparents(static_commitment.dynamic_meta(), &mut rng);
+		let dynamic_claims = make_claims(&dynamic_multilins, &dynamic_transparents);
+		let (transparent_mles, claims) = static_commitment
+			.batch_meta()
+			.merge_claims(
+				&[static_transparents, dynamic_transparents],
+				&[static_claims, dynamic_claims],
+				|mle| mle.n_vars(),
+			)
+			.unwrap();
+		let transparent_multilins = transparent_mles
+			.iter()
+			.map(|mle| MLEDirectAdapter::from(mle.clone()))
+			.collect::<Vec<_>>();
+
+		let mut host_mem =
+			vec![
+				B128::ZERO;
+				static_multilins.len() + dynamic_multilins.len() + transparent_multilins.len()
+			];
+		let dev_mem_size = static_multilins
+			.iter()
+			.chain(&dynamic_multilins)
+			.map(|multilin| 1 << (multilin.n_vars() + 1))
+			.chain(
+				transparent_multilins
+					.iter()
+					.map(|multilin| 1 << multilin.n_vars()),
+			)
+			.sum::<usize>();
+		let mut dev_mem = vec![B128::ZERO; dev_mem_size];
+
+		let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		proof.message().write(&commitment);
+		prove_with_static(
+			&hal,
+			&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+			&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+			&ntt,
+			&merkle_prover,
+			&static_commitment,
+			&committed,
+			&codeword,
+			&static_multilins,
+			&dynamic_multilins,
+			&transparent_multilins,
+			&claims,
+			&mut proof,
+		)
+		.unwrap();
+
+		let transparent_polys = transparent_mles
+			.iter()
+			.map(|mle| {
+				transparent::MultilinearExtensionTransparent::<P, P>::from_values_and_mu(
+					mle.evals().to_vec(),
+					mle.n_vars(),
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>();
+		let transparent_polys = transparent_polys
+			.iter()
+			.map(|poly| poly as &dyn MultivariatePoly<B128>)
+			.collect::<Vec<_>>();
+		let proof_bytes = proof.finalize();
+		let verify_static = |claims: &[PIOPSumcheckClaim<B128>]| {
+			let mut proof =
+				VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof_bytes.clone());
+			let commitment = proof.message().read().unwrap();
+			verify_with_static(
+				static_commitment.meta(),
+				merkle_prover.scheme(),
+				static_commitment.fri_params(),
+				static_commitment.commitment(),
+				&commitment,
+				&transparent_polys,
+				claims,
+				&mut proof,
+			)?;
+			Ok::<_, Error>(proof)
+		};
+		// A single FRI proof opens both the static and the dynamic codeword, so the proof is fully
+		// read by one verification.
+		verify_static(&claims).unwrap().finalize().unwrap();
+
+		// The static codeword is opened by the same FRI proof, so a wrong claim on a static
+		// multilinear is rejected.
+		let static_index = static_commitment
+			.batch_meta()
+			.committed_index(0, 0)
+			.unwrap();
+		let mut wrong_claims = claims.clone();
+		let wrong_claim = wrong_claims
+			.iter_mut()
+			.find(|claim| claim.committed == static_index)
+			.unwrap();
+		wrong_claim.sum += B128::ONE;
+		assert!(verify_static(&wrong_claims).is_err());
+	}
+}
+
+/// A multilinear that exposes no materialized evaluations, like a lazily evaluated transparent.
+#[derive(Debug)]
+struct UnmaterializedMultilinear<P: PackedField>(MLEDirectAdapter<P>);
+
+impl<P: PackedField> MultilinearPoly<P> for UnmaterializedMultilinear<P> {
+	fn n_vars(&self) -> usize {
+		self.0.n_vars()
+	}
+
+	fn log_extension_degree(&self) -> usize {
+		self.0.log_extension_degree()
+	}
+
+	fn evaluate_on_hypercube(&self, index: usize) -> Result<P::Scalar, binius_math::Error> {
+		self.0.evaluate_on_hypercube(index)
+	}
+
+	fn evaluate_on_hypercube_and_scale(
+		&self,
+		index: usize,
+		scalar: P::Scalar,
+	) -> Result<P::Scalar, binius_math::Error> {
+		self.0.evaluate_on_hypercube_and_scale(index, scalar)
+	}
+
+	fn evaluate(&self, query: MultilinearQueryRef<P>) -> Result<P::Scalar, binius_math::Error> {
+		self.0.evaluate(query)
+	}
+
+	fn evaluate_partial_low(
+		&self,
+		query: MultilinearQueryRef<P>,
+	) -> Result<MultilinearExtension<P>, binius_math::Error> {
+		self.0.evaluate_partial_low(query)
+	}
+
+	fn evaluate_partial_high(
+		&self,
+		query: MultilinearQueryRef<P>,
+	) -> Result<MultilinearExtension<P>, binius_math::Error> {
+		self.0.evaluate_partial_high(query)
+	}
+
+	fn evaluate_partial(
+		&self,
+		query: MultilinearQueryRef<P>,
+		start_index: usize,
+	) -> Result<MultilinearExtension<P>, binius_math::Error> {
+		self.0.evaluate_partial(query, start_index)
+	}
+
+	fn zero_pad(
+		&self,
+		n_pad_vars: usize,
+		start_index: usize,
+		nonzero_index: usize,
+	) -> Result<MultilinearExtension<P>, binius_math::Error> {
+		self.0.zero_pad(n_pad_vars, start_index, nonzero_index)
+	}
+
+	fn subcube_partial_low_evals(
+		&self,
+		query: MultilinearQueryRef<P>,
+		subcube_vars: usize,
+		subcube_index: usize,
+		partial_low_evals: &mut [P],
+	) -> Result<(), binius_math::Error> {
+		self.0
+			.subcube_partial_low_evals(query, subcube_vars, subcube_index, partial_low_evals)
+	}
+
+	fn subcube_partial_high_evals(
+		&self,
+		query: MultilinearQueryRef<P>,
+		subcube_vars: usize,
+		subcube_index: usize,
+		partial_high_evals: &mut [P],
+	) -> Result<(), binius_math::Error> {
+		self.0
+			.subcube_partial_high_evals(query, subcube_vars, subcube_index, partial_high_evals)
+	}
+
+	fn subcube_evals(
+		&self,
+		subcube_vars: usize,
+		subcube_index: usize,
+		log_embedding_degree: usize,
+		evals: &mut [P],
+	) -> Result<(), binius_math::Error> {
+		self.0
+			.subcube_evals(subcube_vars, subcube_index, log_embedding_degree, evals)
+	}
+
+	fn packed_evals(&self) -> Option<&[P]> {
+		None
+	}
+}
+
+#[test]
+fn test_prove_verify_unmaterialized_transparents() {
+	type P = PackedBinaryField2x128b;
+
+	// The largest multilinear spans several device write chunks.
+	let commit_meta = CommitMeta::with_vars([0, 4, 6, 13]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(|mle| Either::Left(MLEDirectAdapter::from(mle)))
+		.collect::<Vec<_>>();
+	let CommitOutput {
+		commitment,
+		committed,
+		codeword,
+	} = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+	let transparent_mles = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| Either::Right(UnmaterializedMultilinear(MLEDirectAdapter::from(mle.clone()))))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let dev_mem_size = committed_multilins
+		.iter()
+		.map(|multilin| 3 << multilin.n_vars())
+		.sum::<usize>();
+	let hal = CpuLayer::<B128>::default();
+	let mut host_mem = vec![B128::ZERO; 2 * committed_multilins.len()];
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.message().write(&commitment);
+	prove(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&commit_meta,
+		&committed,
+		&codeword,
+		&committed_multilins,
+		&transparent_multilins,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+
+	let mut proof = proof.into_verifier();
+	let commitment = proof.message().read().unwrap();
+	verify_claims(
+		&commit_meta,
+		merkle_prover.scheme(),
+		&fri_params,
+		&commitment,
+		&transparent_mles,
+		&sumcheck_claims,
+		&mut proof,
+	);
+	proof.finalize().unwrap();
+}
+
+#[test]
+fn test_remap_committed_ids() {
+	let mut old_oracles = MultilinearOracleSet::<B128>::new();
+	let [old_a, old_b, old_c] = [5, 4, 5].map(|n_vars| old_oracles.add_committed(n_vars, 7));
+
+	// The renumbered oracle set orders the oracles with 5 variables differently.
+	let mut new_oracles = MultilinearOracleSet::<B128>::new();
+	let [new_c, new_a, new_b] = [5, 5, 4].map(|n_vars| new_oracles.add_committed(n_vars, 7));
+	let remapping =
+		OracleIdRemapping::from_pairs([(old_a, new_a), (old_b, new_b), (old_c, new_c)]).unwrap();
+
+	let (_, old_index) = make_oracle_commit_meta(&old_oracles).unwrap();
+	let (_, new_index) = make_oracle_commit_meta(&new_oracles).unwrap();
+	let committed_ids = remap_committed_ids(&old_index, &new_index, &remapping).unwrap();
+	for (old_id, new_id) in [(old_a, new_a), (old_b, new_b), (old_c, new_c)] {
+		assert_eq!(committed_ids[old_index[old_id.index()]], new_index[new_id.index()]);
+	}
+	assert_eq!(committed_ids, vec![0, 2, 1]);
+
+	let partial_remapping = OracleIdRemapping::from_pairs([(old_a, new_a)]).unwrap();
+	assert!(remap_committed_ids(&old_index, &new_index, &partial_remapping).is_err());
+}
+
+#[test]
+fn test_commit_prove_verify_multi_point() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = make_commit_params_with_optimal_arity::<_, B16, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+	let commit_output = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+	// Claims at three distinct 4-variate points, one of them shared, and one 7-variate point.
+	let points_4 = repeat_with(|| {
+		repeat_with(|| <B128 as Field>::random(&mut rng))
+			.take(4)
+			.collect()
+	})
+	.take(3)
+	.collect::<Vec<Vec<_>>>();
+	let point_7 = repeat_with(|| <B128 as Field>::random(&mut rng))
+		.take(7)
+		.collect::<Vec<_>>();
+	let eval_claims = [
+		(0, &points_4[0]),
+		(1, &points_4[0]),
+		(1, &points_4[1]),
+		(0, &points_4[2]),
+		(3, &point_7),
+	]
+	.map(|(committed, eval_point): (usize, &Vec<B128>)| {
+		let query = MultilinearQuery::<P>::expand(eval_point);
+		CommittedEvalClaim {
+			committed,
+			eval_point: eval_point.clone(),
+			eval: committed_multilins[committed]
+				.evaluate(query.to_ref())
+				.unwrap(),
+		}
+	});
+
+	let multi_point = MultiPointClaims::new(&commit_meta, &eval_claims).unwrap();
+	assert_eq!(multi_point.eval_points().len(), 4);
+	let transparent_mles = multi_point.transparent_multilins::<P>().unwrap();
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.message().write(&commit_output.commitment);
+	prove_claims(
+		&commit_meta,
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&commit_output.committed,
+		&commit_output.codeword,
+		&committed_multilins,
+		&transparent_mles,
+		multi_point.sumcheck_claims(),
+		&mut proof,
+	);
+
+	let mut proof = proof.into_verifier();
+	let commitment = proof.message().read().unwrap();
+	let transparent_polys = multi_point.transparent_polys();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<B128>)
+		.collect::<Vec<_>>();
+	verify(
+		&commit_meta,
+		merkle_prover.scheme(),
+		&fri_params,
+		&commitment,
+		&transparent_polys,
+		multi_point.sumcheck_claims(),
+		&mut proof,
+	)
+	.unwrap();
+	proof.finalize().unwrap();
+
+	let mismatched_claim = CommittedEvalClaim {
+		committed: 2,
+		eval_point: point_7,
+		eval: B128::ONE,
+	};
+	assert!(matches!(
+		MultiPointClaims::new(&commit_meta, &[mismatched_claim]),
+		Err(Error::SumcheckClaimVariablesMismatch { index: 0 })
+	));
+}
+
+#[test]
+fn test_inspect_proof() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let fri_params = CommitParamsBuilder::new(merkle_prover.scheme(), SECURITY_BITS)
+		.with_arity_schedule(2, vec![3, 2, 1])
+		.build::<B128, B16>(&commit_meta)
+		.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+	let CommitOutput {
+		commitment,
+		committed,
+		codeword,
+	} = commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+	let transparent_multilins_by_vars = commit_meta
+		.n_multilins_by_vars()
+		.iter()
+		.map(|&n_committed| if n_committed == 0 { 0 } else { 2 })
+		.collect::<Vec<_>>();
+	let transparent_mles = generate_multilins::<P>(&transparent_multilins_by_vars, &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prove_claims(
+		&commit_meta,
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&committed,
+		&codeword,
+		&committed_multilins,
+		&transparent_mles,
+		&sumcheck_claims,
+		&mut proof,
+	);
+	let proof = proof.finalize();
+
+	let transparent_polys = transparent_mles
+		.iter()
+		.map(|mle| {
+			transparent::MultilinearExtensionTransparent::<P, P>::from_values_and_mu(
+				mle.evals().to_vec(),
+				mle.n_vars(),
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<B128>)
+		.collect::<Vec<_>>();
+	let inspection = inspect_proof(
+		&commit_meta,
+		merkle_prover.scheme(),
+		&fri_params,
+		&transparent_polys,
+		&sumcheck_claims,
+		&proof,
+	)
+	.unwrap();
+
+	assert_eq!(inspection.len(), proof.len());
+	let mut offset = 0;
+	for section in &inspection.sections {
+		assert_eq!(section.offset, offset);
+		offset += section.len;
+	}
+	let count = |kind| {
+		inspection
+			.sections
+			.iter()
+			.filter(|section| section.contents.kind() == kind)
+			.count()
+	};
+	assert_eq!(count("sumcheck_round"), commit_meta.total_vars());
+	assert_eq!(count("multilinear_evals"), 3);
+	assert_eq!(count("fri_commitment"), fri_params.n_oracles());
+	assert_eq!(count("fri_merkle_layer"), fri_params.n_oracles());
+	assert_eq!(count("fri_query_opening") % fri_params.n_oracles(), 0);
+	assert!(count("fri_query_opening") <= fri_params.n_test_queries() * fri_params.n_oracles());
+	assert_eq!(
+		inspection
+			.len_by_kind()
+			.iter()
+			.map(|(_, len)| len)
+			.sum::<usize>(),
+		proof.len()
+	);
+	assert!(inspection.sections.iter().any(|section| matches!(
+		&section.contents,
+		ProofSectionContents::MultilinearEvals { n_vars: 7, evals } if evals.len() == 3
+	)));
+
+	// The inspected proof is accepted by the verifier.
+	let mut verifier_transcript =
+		VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+	verify(
+		&commit_meta,
+		merkle_prover.scheme(),
+		&fri_params,
+		&commitment,
+		&transparent_polys,
+		&sumcheck_claims,
+		&mut verifier_transcript,
+	)
+	.unwrap();
+
+	assert!(
+		inspect_proof(
+			&commit_meta,
+			merkle_prover.scheme(),
+			&fri_params,
+			&transparent_polys,
+			&sumcheck_claims,
+			&proof[..proof.len() - 1],
+		)
+		.is_err()
+	);
+}
+
+/// Commits, proves, and verifies the sumcheck claims on random multilinears with a commitment
+/// scheme, returning the verification result.
+///
+/// If `perturb_claims` is set, the verifier checks claims whose sums differ from those proven.
+fn commit_prove_verify_with_scheme<F, P, MTScheme, Scheme>(
+	commit_meta: &CommitMeta,
+	scheme: &Scheme,
+	ntt: &SingleThreadedNTT<B16>,
+	merkle_prover: &impl MerkleTreeProver<F, Scheme = MTScheme>,
+	perturb_claims: bool,
+) -> Result<(), Error>
+where
+	F: TowerTop + ExtensionField<B16>,
+	P: PackedFieldIndexable<Scalar = F>
+		+ PackedExtension<B16>
+		+ PackedExtension<F, PackedSubfield = P>,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes + DeserializeBytes>,
+	Scheme: PIOPCommitmentScheme<F, B16>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_multilins = generate_multilins::<P>(commit_meta.n_multilins_by_vars(), &mut rng)
+		.into_iter()
+		.map(MLEDirectAdapter::from)
+		.collect::<Vec<_>>();
+	let CommitOutput {
+		commitment,
+		committed,
+		codeword,
+	} = scheme
+		.commit(ntt, merkle_prover, &committed_multilins)
+		.unwrap();
+
+	let transparent_multilins_by_vars = commit_meta
+		.n_multilins_by_vars()
+		.iter()
+		.map(|&n_committed| if n_committed == 0 { 0 } else { 2 })
+		.collect::<Vec<_>>();
+	let transparent_mles = generate_multilins::<P>(&transparent_multilins_by_vars, &mut rng);
+	let transparent_multilins = transparent_mles
+		.iter()
+		.map(|mle| MLEDirectAdapter::from(mle.clone()))
+		.collect::<Vec<_>>();
+	let sumcheck_claims = make_sumcheck_claims(&committed_multilins, &transparent_multilins);
+
+	let hal = CpuLayer::<F>::default();
+	let mut host_mem = vec![F::ZERO; 1 << 12];
+	let mut dev_mem = vec![F::ZERO; 1 << 12];
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.message().write(&commitment);
+	scheme
+		.prove(
+			&hal,
+			&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+			&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+			ntt,
+			merkle_prover,
+			commit_meta,
+			&committed,
+			&codeword,
+			&committed_multilins,
+			&transparent_multilins,
+			&sumcheck_claims,
+			&mut proof,
+		)
+		.unwrap();
+
+	let transparent_polys = transparent_mles
+		.iter()
+		.map(|mle| {
+			transparent::MultilinearExtensionTransparent::<P, P>::from_values_and_mu(
+				mle.evals().to_vec(),
+				mle.n_vars(),
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let transparent_polys = transparent_polys
+		.iter()
+		.map(|poly| poly as &dyn MultivariatePoly<F>)
+		.collect::<Vec<_>>();
+	let verifier_claims = sumcheck_claims
+		.iter()
+		.map(|claim| PIOPSumcheckClaim {
+			sum: if perturb_claims {
+				claim.sum + F::ONE
+			} else {
+				claim.sum
+			},
+			..claim.clone()
+		})
+		.collect::<Vec<_>>();
+
+	let mut proof = proof.into_verifier();
+	let commitment = proof.message().read().unwrap();
+	scheme.verify(
+		commit_meta,
+		merkle_prover.scheme(),
+		&commitment,
+		&transparent_polys,
+		&verifier_claims,
+		&mut proof,
+	)?;
+	proof.finalize().unwrap();
+	Ok(())
+}
+
+#[test]
+fn test_commit_prove_verify_basefold() {
+	type P = PackedBinaryField2x128b;
+
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let fri_params = make_commit_params_with_optimal_arity::<B128, B16, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+	let basefold_params =
+		BaseFoldParams::<B128, B16>::with_security(&commit_meta, 1, 0.25, SECURITY_BITS, 0)
+			.unwrap();
+
+	commit_prove_verify_with_scheme::<_, P, _, _>(
+		&commit_meta,
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		false,
+	)
+	.unwrap();
+	commit_prove_verify_with_scheme::<_, P, _, _>(
+		&commit_meta,
+		&basefold_params,
+		&ntt,
+		&merkle_prover,
+		false,
+	)
+	.unwrap();
+}
+
+#[test]
+fn test_basefold_rejects_incorrect_claims() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let basefold_params =
+		BaseFoldParams::<B128, B16>::with_security(&commit_meta, 1, 0.25, SECURITY_BITS, 0)
+			.unwrap();
+	let ntt = SingleThreadedNTT::new(commit_meta.total_vars() + 1).unwrap();
+
+	let result = commit_prove_verify_with_scheme::<_, PackedBinaryField2x128b, _, _>(
+		&commit_meta,
+		&basefold_params,
+		&ntt,
+		&merkle_prover,
+		true,
+	);
+	assert!(result.is_err());
+}
+
+/// Returns STIR parameters for the batch, committed as four interleaved messages.
+fn stir_params_for_batch(commit_meta: &CommitMeta) -> STIRParams<B128, B16> {
+	STIRParams::new(commit_meta.total_vars() - 2, 1, 2, vec![4, 2], vec![8, 8], 1).unwrap()
+}
+
+#[test]
+fn test_commit_prove_verify_stir() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let stir_params = stir_params_for_batch(&commit_meta);
+	let ntt = SingleThreadedNTT::with_subspace(stir_params.subspace()).unwrap();
+
+	commit_prove_verify_with_scheme::<_, PackedBinaryField2x128b, _, _>(
+		&commit_meta,
+		&stir_params,
+		&ntt,
+		&merkle_prover,
+		false,
+	)
+	.unwrap();
+}
+
+#[test]
+fn test_stir_rejects_incorrect_claims() {
+	let commit_meta = CommitMeta::with_vars([4, 4, 6, 7]);
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let stir_params = stir_params_for_batch(&commit_meta);
+	let ntt = SingleThreadedNTT::with_subspace(stir_params.subspace()).unwrap();
+
+	let result = commit_prove_verify_with_scheme::<_, PackedBinaryField2x128b, _, _>(
+		&commit_meta,
+		&stir_params,
+		&ntt,
+		&merkle_prover,
+		true,
+	);
+	assert!(result.is_err());
+}
@@ -3,13 +3,25 @@
 use std::{borrow::Borrow, cmp::Ordering, iter, ops::Range};
 
 use binius_field::{BinaryField, ExtensionField, Field, TowerField};
+use binius_macros::{DeserializeBytes, SerializeBytes};
 use binius_math::evaluate_piecewise_multilinear;
-use binius_ntt::{AdditiveNTT, SingleThreadedNTT};
-use binius_utils::{DeserializeBytes, bail};
+use binius_maybe_rayon::prelude::*;
+use binius_ntt::twiddle::PrecomputedTwiddleAccess;
+use binius_utils::{DeserializeBytes, SerializationError, SerializationMode, SerializeBytes, bail};
 use getset::CopyGetters;
+use itertools::izip;
 use tracing::instrument;
 
-use super::error::{Error, VerificationError};
+use super::{
+	commit_params::CommitParamsBuilder,
+	error::{Error, VerificationError},
+	incremental::{IncrementalCommitMeta, combine_part_commitments},
+	multi_commit::MultiCommitMeta,
+	observer::{PIOPPhase, PhaseObserver, observe_phase},
+	options::PIOPOptions,
+	rate_batches::RateBatchedCommitMeta,
+	static_commit::StaticCommitMeta,
+};
 use crate::{
 	composition::{BivariateProduct, IndexComposition},
 	fiat_shamir::{CanSample, Challenger},
@@ -17,7 +29,7 @@ use crate::{
 	piop::util::ResizeableIndex,
 	polynomial::MultivariatePoly,
 	protocols::{
-		fri::{FRIParams, FRIVerifier, estimate_optimal_arity},
+		fri::{self, FRIParams, FRIVerifier, SoundnessModel, VerifierPlan},
 		sumcheck::{
 			CompositeSumClaim, SumcheckClaim, front_loaded::BatchVerifier as SumcheckBatchVerifier,
 		},
@@ -32,10 +44,16 @@ use crate::{
 /// interpolating them into a piecewise multilinear whose evaluations are the concatenation of the
 /// piecewise evaluations. This metadata captures the "shape" of the batch, meaning the number of
 /// variables of all polynomials in the batch.
+///
+/// The committed multilinears are packed into the top field of the tower, so a single batch may
+/// contain multilinears over different subfields. The metadata can optionally record the tower
+/// level of the subfield of each multilinear, see [`Self::with_tower_levels`].
 #[derive(Debug, CopyGetters)]
 pub struct CommitMeta {
 	n_multilins_by_vars: Vec<usize>,
 	offsets_by_vars: Vec<usize>,
+	/// The tower level of each committed multilinear before packing, if recorded.
+	tower_levels: Option<Vec<usize>>,
 	/// The total number of variables of the interpolating multilinear.
 	#[getset(get_copy = "pub")]
 	total_vars: usize,
@@ -64,11 +82,28 @@ impl CommitMeta {
 		Self {
 			offsets_by_vars,
 			n_multilins_by_vars,
+			tower_levels: None,
 			total_vars: total_elems.next_power_of_two().ilog2() as usize,
 			total_multilins,
 		}
 	}
 
+	/// Records the tower level of the subfield of each committed multilinear before packing.
+	///
+	/// ## Arguments
+	///
+	/// * `tower_levels` - the tower level of each committed multilinear, by committed index
+	pub fn with_tower_levels(mut self, tower_levels: Vec<usize>) -> Result<Self, Error> {
+		if tower_levels.len() != self.total_multilins {
+			bail!(Error::IncorrectNumberOfTowerLevels {
+				expected: self.total_multilins,
+				actual: tower_levels.len(),
+			});
+		}
+		self.tower_levels = Some(tower_levels);
+		Ok(self)
+	}
+
 	/// Constructs a new [`CommitMeta`] from a sequence of committed polynomials described by their
 	/// number of variables.
 	pub fn with_vars(n_varss: impl IntoIterator<Item = usize>) -> Self {
@@ -79,6 +114,34 @@ impl CommitMeta {
 		Self::new(n_multilins_by_vars.into_vec())
 	}
 
+	/// Returns the metadata of the batch with a hiding multilinear of `n_vars` variables appended,
+	/// as committed by [`super::commit_hiding`].
+	///
+	/// The hiding multilinear has random evaluations over the top field. Having at least as many
+	/// variables as the batch, it is the last multilinear in commitment order. Since the message is
+	/// written in reverse commitment order, it fills the lower half of the committed message, which
+	/// is the first half of the coefficients of each interleaved Reed–Solomon codeword. The tower
+	/// levels of the batch are not carried over.
+	///
+	/// ## Throws
+	///
+	/// * [`fri::Error::InvalidArgs`] if `n_vars` is less than the number of variables of the batch
+	pub fn with_hiding_multilin(&self, n_vars: usize) -> Result<Self, Error> {
+		if n_vars < self.total_vars {
+			bail!(fri::Error::InvalidArgs(format!(
+				"the hiding multilinear has {n_vars} variables, fewer than the {} of the batch",
+				self.total_vars
+			)));
+		}
+
+		let mut n_multilins_by_vars = self.n_multilins_by_vars.clone();
+		if n_multilins_by_vars.len() <= n_vars {
+			n_multilins_by_vars.resize(n_vars + 1, 0);
+		}
+		n_multilins_by_vars[n_vars] += 1;
+		Ok(Self::new(n_multilins_by_vars))
+	}
+
 	/// Returns the maximum number of variables of any individual multilinear.
 	pub fn max_n_vars(&self) -> usize {
 		self.n_multilins_by_vars.len().saturating_sub(1)
@@ -95,13 +158,124 @@ impl CommitMeta {
 		let start = self.offsets_by_vars[n_vars];
 		start..start + self.n_multilins_by_vars[n_vars]
 	}
+
+	/// Returns the tower level of each committed multilinear before packing, if recorded.
+	pub fn tower_levels(&self) -> Option<&[usize]> {
+		self.tower_levels.as_deref()
+	}
+
+	/// Returns the tower level of a committed multilinear before packing, if recorded.
+	pub fn tower_level(&self, committed: usize) -> Option<usize> {
+		self.tower_levels
+			.as_ref()
+			.and_then(|tower_levels| tower_levels.get(committed).copied())
+	}
+
+	/// Returns the highest tower level of any committed multilinear before packing, if recorded.
+	///
+	/// The committed multilinears can only be packed into the top field of a tower family at or
+	/// above this level. When it is at most 6, evaluation claims can be reduced into the 64-bit
+	/// field with [`binius_field::tower::Canonical64bTowerFamily`], see [`Self::check_top_field`].
+	pub fn max_tower_level(&self) -> Option<usize> {
+		self.tower_levels
+			.as_ref()
+			.map(|tower_levels| tower_levels.iter().copied().max().unwrap_or(0))
+	}
+
+	/// Checks that the committed multilinears can be packed into the top field `F`.
+	///
+	/// Commitments without recorded tower levels pass, as the packing is then up to the caller.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::CommittedTowerLevelTooHigh`] if a committed multilinear is above the tower level
+	///   of `F`
+	pub fn check_top_field<F: BinaryField>(&self) -> Result<(), Error> {
+		let max = F::N_BITS.ilog2() as usize;
+		match self.max_tower_level() {
+			Some(tower_level) if tower_level > max => {
+				Err(Error::CommittedTowerLevelTooHigh { tower_level, max })
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
+/// The version of the byte encoding of [`CommitMeta`], which is written as its first byte.
+const COMMIT_META_ENCODING_VERSION: u8 = 1;
+
+/// Encodes the metadata so that a verifier can load it with [`DeserializeBytes`].
+///
+/// The encoding starts with a version byte, followed by the number of multilinears by number of
+/// variables and the optional tower levels. The derived fields are recomputed on load.
+impl SerializeBytes for CommitMeta {
+	fn serialize(
+		&self,
+		mut write_buf: impl bytes::BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		SerializeBytes::serialize(&COMMIT_META_ENCODING_VERSION, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.n_multilins_by_vars, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.tower_levels, write_buf, mode)
+	}
+}
+
+impl DeserializeBytes for CommitMeta {
+	fn deserialize(
+		mut read_buf: impl bytes::Buf,
+		mode: SerializationMode,
+	) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let version = u8::deserialize(&mut read_buf, mode)?;
+		if version != COMMIT_META_ENCODING_VERSION {
+			return Err(SerializationError::UnsupportedVersion {
+				name: "CommitMeta",
+				version,
+			});
+		}
+
+		let n_multilins_by_vars = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let tower_levels: Option<Vec<usize>> = DeserializeBytes::deserialize(read_buf, mode)?;
+		let commit_meta = Self::new(n_multilins_by_vars);
+		match tower_levels {
+			Some(tower_levels) => commit_meta
+				.with_tower_levels(tower_levels)
+				.map_err(|_| SerializationError::InvalidConstruction { name: "CommitMeta" }),
+			None => Ok(commit_meta),
+		}
+	}
+}
+
+impl serde::Serialize for CommitMeta {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut buf = Vec::new();
+		SerializeBytes::serialize(self, &mut buf, SerializationMode::CanonicalTower)
+			.map_err(serde::ser::Error::custom)?;
+		serializer.serialize_bytes(&buf)
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for CommitMeta {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let buf = <Vec<u8> as serde::Deserialize>::deserialize(deserializer)?;
+		let mut read_buf = buf.as_slice();
+		let commit_meta =
+			DeserializeBytes::deserialize(&mut read_buf, SerializationMode::CanonicalTower)
+				.map_err(serde::de::Error::custom)?;
+		if !read_buf.is_empty() {
+			return Err(serde::de::Error::invalid_length(buf.len(), &"an encoding of CommitMeta"));
+		}
+		Ok(commit_meta)
+	}
 }
 
 /// A sumcheck claim that can be processed by the PIOP compiler.
 ///
 /// These are a specific form of sumcheck claims over products of a committed polynomial and a
 /// transparent polynomial, referencing by index into external vectors.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, SerializeBytes, DeserializeBytes)]
 pub struct PIOPSumcheckClaim<F: Field> {
 	/// Number of variables of the multivariate polynomial the sumcheck claim is about.
 	pub n_vars: usize,
@@ -114,57 +288,83 @@ pub struct PIOPSumcheckClaim<F: Field> {
 	pub sum: F,
 }
 
-fn make_commit_params_with_constant_arity<F, FEncode>(
-	ntt: &impl AdditiveNTT<FEncode>,
-	commit_meta: &CommitMeta,
-	security_bits: usize,
-	log_inv_rate: usize,
-	arity: usize,
-) -> Result<FRIParams<F, FEncode>, Error>
-where
-	F: BinaryField + ExtensionField<FEncode>,
-	FEncode: BinaryField,
-{
-	let params = FRIParams::choose_with_constant_fold_arity(
-		ntt,
-		commit_meta.total_vars(),
-		security_bits,
-		log_inv_rate,
-		arity,
-	)?;
-	Ok(params)
-}
-
 /// Choose commit parameters based on protocol parameters.
 ///
+/// This is [`CommitParamsBuilder`] with the default folding arities.
+///
 /// ## Arguments
 ///
 /// * `commit_meta` - the metadata about the committed batch of multilinears.
 /// * `merkle_scheme` - the Merkle tree commitment scheme used in FRI.
 /// * `security_bits` - the target security level in bits.
 /// * `log_inv_rate` - the binary logarithm of the inverse Reed–Solomon code rate.
+/// * `soundness_model` - the soundness analysis that sizes the number of test queries, either the
+///   provable bound or the more aggressive list-decoding conjecture.
 pub fn make_commit_params_with_optimal_arity<F, FEncode, MTScheme>(
 	commit_meta: &CommitMeta,
-	_merkle_scheme: &MTScheme,
+	merkle_scheme: &MTScheme,
 	security_bits: usize,
 	log_inv_rate: usize,
+	soundness_model: SoundnessModel,
 ) -> Result<FRIParams<F, FEncode>, Error>
 where
 	F: BinaryField + ExtensionField<FEncode>,
 	FEncode: BinaryField,
 	MTScheme: MerkleTreeScheme<F>,
 {
-	// Choose the NTT with the maximum domain size, to be independent of the commit parameters. We
-	// then choose FRI parameters based on a compatible subspace of the NTT, and then create
-	// another NTT object for encoding, using the appropriate subspace.
-	let ntt = SingleThreadedNTT::<FEncode>::new(FEncode::N_BITS)?;
+	CommitParamsBuilder::new(merkle_scheme, security_bits)
+		.with_log_inv_rate(log_inv_rate)
+		.with_soundness_model(soundness_model)
+		.build(commit_meta)
+}
+
+/// Returns the metadata of the batch committed with [`super::commit_hiding`] under the FRI
+/// parameters.
+///
+/// The hiding multilinear has one variable fewer than the FRI parameters. Each FRI query opens a
+/// coset of symbols of every interleaved codeword, which are uniformly random whatever the
+/// committed batch as long as no more of them are opened than the message has random
+/// coefficients, so this is checked against the number of test queries.
+///
+/// ## Throws
+///
+/// * [`fri::Error::InvalidArgs`] if the parameters send no round oracle, in which case the codeword
+///   would be sent in the clear, or have a code of dimension 1
+/// * [`Error::CommitParamsMismatch`] if the batch and the hiding multilinear do not fill the
+///   message of the parameters
+/// * [`Error::HidingQueriesExceedRandomness`] if the queries open too many symbols
+pub(super) fn hiding_commit_meta<F, FEncode>(
+	commit_meta: &CommitMeta,
+	fri_params: &FRIParams<F, FEncode>,
+) -> Result<CommitMeta, Error>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	let Some(&first_arity) = fri_params.fold_arities().first() else {
+		bail!(fri::Error::InvalidArgs("hiding requires at least one round oracle".to_string()));
+	};
+	let log_dim = fri_params.rs_code().log_dim();
+	if log_dim == 0 {
+		bail!(fri::Error::InvalidArgs(
+			"hiding requires a code with at least two message coefficients".to_string()
+		));
+	}
+
+	let hiding_meta = commit_meta.with_hiding_multilin(fri_params.n_fold_rounds() - 1)?;
+	if hiding_meta.total_vars != fri_params.n_fold_rounds() {
+		bail!(Error::CommitParamsMismatch {
+			expected: hiding_meta.total_vars,
+			actual: fri_params.n_fold_rounds(),
+		});
+	}
 
-	let arity = estimate_optimal_arity(
-		commit_meta.total_vars + log_inv_rate,
-		size_of::<MTScheme::Digest>(),
-		size_of::<F>(),
-	);
-	make_commit_params_with_constant_arity(&ntt, commit_meta, security_bits, log_inv_rate, arity)
+	let n_opened = fri_params.n_test_queries() << (first_arity - fri_params.log_batch_size());
+	let n_random = 1 << (log_dim - 1);
+	if n_opened > n_random {
+		bail!(Error::HidingQueriesExceedRandomness { n_opened, n_random });
+	}
+	Ok(hiding_meta)
 }
 
 /// A description of a sumcheck claim arising from a FRI PCS sumcheck.
@@ -282,7 +482,6 @@ pub fn make_sumcheck_claim_descs<F: Field>(
 /// * `claims` - a batch of sumcheck claims referencing committed polynomials in the batch described
 ///   by `commit_meta` and the transparent polynomials in `transparents`
 /// * `transcript` - the verifier transcript.
-#[instrument("piop::verify", skip_all)]
 pub fn verify<'a, F, FEncode, Challenger_, MTScheme>(
 	commit_meta: &CommitMeta,
 	merkle_scheme: &MTScheme,
@@ -296,8 +495,449 @@ where
 	F: TowerField + ExtensionField<FEncode>,
 	FEncode: BinaryField,
 	Challenger_: Challenger,
-	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+{
+	verify_with_options(
+		commit_meta,
+		merkle_scheme,
+		fri_params,
+		commitment,
+		transparents,
+		claims,
+		transcript,
+		&PIOPOptions::default(),
+	)
+}
+
+/// Verifies a batch of sumcheck claims like [`verify`], with the given options.
+///
+/// The start and end of the sumcheck, FRI fold, and FRI query phases are reported to the observer
+/// of `options`.
+#[instrument("piop::verify", skip_all)]
+pub fn verify_with_options<'a, F, FEncode, Challenger_, MTScheme>(
+	commit_meta: &CommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &FRIParams<F, FEncode>,
+	commitment: &MTScheme::Digest,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+	options: &PIOPOptions,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	Challenger_: Challenger,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
 {
+	verify_with_folder(
+		commit_meta,
+		transparents,
+		claims,
+		FRIFoldVerifier::new(fri_params, merkle_scheme, commitment, Vec::new()),
+		transcript,
+		&options.observer(),
+	)
+}
+
+/// A proof checked by [`verify_batch`].
+pub struct BatchVerifyInstance<'a, F: Field, Digest, Challenger_> {
+	/// The commitment to the committed multilinears of the proof.
+	pub commitment: &'a Digest,
+	/// The transparent polynomials of the claims, as for [`verify`].
+	pub transparents: &'a [&'a dyn MultivariatePoly<F>],
+	/// The sumcheck claims of the proof.
+	pub claims: &'a [PIOPSumcheckClaim<F>],
+	/// The transcript of the proof, positioned where [`verify`] would start reading it.
+	pub transcript: &'a mut VerifierTranscript<Challenger_>,
+}
+
+/// Verifies many proofs of sumcheck claims over committed batches with the same shape.
+///
+/// Each proof is checked as by [`verify`], but the FRI query phase parameters, such as the NTT
+/// twiddles of the code domain and the Merkle layer layout, are computed once for the batch in a
+/// [`VerifierPlan`] with precomputed twiddles, and the proofs are checked in parallel.
+///
+/// ## Throws
+///
+/// * [`Error::InvalidBatchedProof`] for one of the invalid proofs, if any
+#[instrument("piop::verify_batch", skip_all)]
+pub fn verify_batch<F, FEncode, Challenger_, MTScheme>(
+	commit_meta: &CommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &FRIParams<F, FEncode>,
+	instances: &mut [BatchVerifyInstance<'_, F, MTScheme::Digest, Challenger_>],
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	Challenger_: Challenger + Send,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes> + Sync,
+{
+	let plan = VerifierPlan::new(fri_params, merkle_scheme)?.precompute_twiddles();
+	instances
+		.par_iter_mut()
+		.enumerate()
+		.try_for_each(|(index, instance)| {
+			let folder =
+				FRIFoldVerifier::new(fri_params, merkle_scheme, instance.commitment, Vec::new())
+					.with_plan(&plan);
+			verify_with_folder(
+				commit_meta,
+				instance.transparents,
+				instance.claims,
+				folder,
+				instance.transcript,
+				&(),
+			)
+			.map_err(|source| Error::InvalidBatchedProof {
+				index,
+				source: Box::new(source),
+			})
+		})
+}
+
+/// Verifies a batch of sumcheck claims proven in zero knowledge with [`super::prove_zk`].
+///
+/// The commitment is to the batch described by `commit_meta` with the hiding multilinear of
+/// [`super::commit_hiding`], and `fri_params` must be chosen for
+/// [`CommitMeta::with_hiding_multilin`] as checked by [`super::prove_zk`]. The verifier reads the
+/// commitment to the masks and the sums of the claims over the masks, samples the coefficient of
+/// the masks, and verifies the claims on the masked committed multilinears as in [`verify`].
+#[instrument("piop::verify_zk", skip_all)]
+pub fn verify_zk<'a, F, FEncode, Challenger_, MTScheme>(
+	commit_meta: &CommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &FRIParams<F, FEncode>,
+	commitment: &MTScheme::Digest,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	Challenger_: Challenger,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+{
+	let hiding_meta = hiding_commit_meta(commit_meta, fri_params)?;
+	// The hiding multilinear is not part of the batch the claims are about.
+	if claims
+		.iter()
+		.any(|claim| claim.committed >= commit_meta.total_multilins)
+	{
+		bail!(Error::InvalidCommittedId {
+			max_index: commit_meta.total_multilins,
+		});
+	}
+
+	let mask_commitment = transcript
+		.message()
+		.read()
+		.map_err(VerificationError::Transcript)?;
+	let mask_sums = transcript
+		.message()
+		.read_scalar_slice::<F>(claims.len())
+		.map_err(VerificationError::Transcript)?;
+
+	let coeff: F = transcript.sample();
+	let masked_claims = iter::zip(claims, &mask_sums)
+		.map(|(claim, &mask_sum)| PIOPSumcheckClaim {
+			sum: claim.sum + coeff * mask_sum,
+			..claim.clone()
+		})
+		.collect::<Vec<_>>();
+
+	verify_with_folder(
+		&hiding_meta,
+		transparents,
+		&masked_claims,
+		FRIFoldVerifier::new(
+			fri_params,
+			merkle_scheme,
+			commitment,
+			vec![(0, &mask_commitment, coeff)],
+		),
+		transcript,
+		&(),
+	)
+}
+
+/// Verifies a batch of sumcheck claims over a static commitment and a dynamic batch proven with
+/// [`super::prove_with_static`].
+///
+/// `meta` describes the static and the dynamic batch, as returned by
+/// [`super::StaticCommitment::meta`], and `fri_params` are the FRI parameters of the longer batch.
+/// The static commitment is known to the verifier out of band, and the dynamic commitment has been
+/// read from the transcript. The verifier observes the static commitment, samples the coefficient
+/// of the shorter codeword, and verifies the claims on each batch with its own batched sumcheck,
+/// the one of the shorter batch starting in the fold round where its codeword joins the FRI
+/// instance. The FRI proof must open the evaluation of the longer batch plus the coefficient times
+/// the evaluation of the shorter batch.
+#[allow(clippy::too_many_arguments)]
+#[instrument("piop::verify_with_static", skip_all)]
+pub fn verify_with_static<'a, F, FEncode, Challenger_, MTScheme>(
+	meta: &StaticCommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &FRIParams<F, FEncode>,
+	static_commitment: &MTScheme::Digest,
+	commitment: &MTScheme::Digest,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	Challenger_: Challenger,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+{
+	transcript.observe().write(static_commitment);
+	let coeff: F = transcript.sample();
+
+	let splits = meta.split_claims(transparents.len(), claims)?;
+	let transparents_by_system = splits
+		.iter()
+		.map(|split| {
+			split
+				.transparent_indices
+				.iter()
+				.map(|&index| transparents[index].borrow())
+				.collect::<Vec<&dyn MultivariatePoly<F>>>()
+		})
+		.collect::<Vec<_>>();
+	let (long_system, short_system) = meta.folded_systems();
+	let entry_round = meta.entry_round();
+	let long_meta = meta.system_meta(long_system);
+	let short_meta = meta.system_meta(short_system);
+	let commitments = [static_commitment, commitment];
+
+	let (long_descs, long_claims) = make_batch_sumcheck_claims(
+		long_meta,
+		&transparents_by_system[long_system],
+		&splits[long_system].claims,
+	)?;
+	let (short_descs, short_claims) = make_batch_sumcheck_claims(
+		short_meta,
+		&transparents_by_system[short_system],
+		&splits[short_system].claims,
+	)?;
+
+	let BatchInterleavedSumcheckFRIOutput {
+		challenges,
+		multilinear_evals,
+		late_multilinear_evals,
+		fri_final,
+	} = verify_interleaved_sumcheck(
+		long_meta.total_vars(),
+		&long_claims,
+		&[(entry_round, short_claims.as_slice())],
+		FRIFoldVerifier::new(
+			fri_params,
+			merkle_scheme,
+			commitments[long_system],
+			vec![(entry_round, commitments[short_system], coeff)],
+		),
+		transcript,
+		&(),
+	)?;
+
+	let long_eval = evaluate_committed_batch(
+		long_meta,
+		&long_descs,
+		multilinear_evals,
+		&transparents_by_system[long_system],
+		&challenges,
+	)?;
+	let short_multilinear_evals = late_multilinear_evals
+		.into_iter()
+		.next()
+		.expect("the claims on the shorter batch are the only late claims");
+	let short_eval = evaluate_committed_batch(
+		short_meta,
+		&short_descs,
+		short_multilinear_evals,
+		&transparents_by_system[short_system],
+		&challenges[entry_round..],
+	)?;
+	if long_eval + coeff * short_eval != fri_final {
+		return Err(VerificationError::IncorrectSumcheckEvaluation.into());
+	}
+
+	Ok(())
+}
+
+/// Verifies a batch of sumcheck claims over an incremental commitment proven with
+/// [`super::prove_incremental`].
+///
+/// `meta` describes the parts of the commitment, and `fri_params` are the FRI parameters the
+/// parts were committed with, as for [`super::IncrementalCommitter::new`]. The verifier reads the
+/// roots of the parts and checks that they hash to `commitment`, samples the coefficient of each
+/// part but the one folded from the first round, and verifies the claims on each part with its own
+/// batched sumcheck, starting in the fold round where the codeword of the part joins the FRI
+/// instance. The FRI proof must open the sum of the evaluations of the parts times their
+/// coefficients.
+///
+/// ## Throws
+///
+/// * [`VerificationError::IncorrectPartCommitments`] if the roots of the parts do not hash to
+///   `commitment`
+#[allow(clippy::too_many_arguments)]
+#[instrument("piop::verify_incremental", skip_all)]
+pub fn verify_incremental<'a, F, FEncode, Challenger_, MTScheme>(
+	meta: &IncrementalCommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &FRIParams<F, FEncode>,
+	commitment: &MTScheme::Digest,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	Challenger_: Challenger,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+{
+	let part_commitments: Vec<MTScheme::Digest> = transcript
+		.message()
+		.read_vec(meta.n_parts())
+		.map_err(VerificationError::Transcript)?;
+	if combine_part_commitments(merkle_scheme, &part_commitments)? != *commitment {
+		return Err(VerificationError::IncorrectPartCommitments.into());
+	}
+	let coeffs = meta.sample_coeffs(transcript);
+
+	let splits = meta.split_claims(transparents.len(), claims)?;
+	let transparents_by_part = splits
+		.iter()
+		.map(|split| {
+			split
+				.transparent_indices
+				.iter()
+				.map(|&index| transparents[index].borrow())
+				.collect::<Vec<&dyn MultivariatePoly<F>>>()
+		})
+		.collect::<Vec<_>>();
+	let batch_claims_by_part = izip!(meta.part_metas(), &transparents_by_part, &splits)
+		.map(|(part_meta, transparents, split)| {
+			make_batch_sumcheck_claims(part_meta, transparents, &split.claims)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let folded_part = meta.folded_part();
+	let late_parts = (0..meta.n_parts())
+		.filter(|&part| part != folded_part)
+		.collect::<Vec<_>>();
+	let late_claims = late_parts
+		.iter()
+		.map(|&part| (meta.entry_round(part), batch_claims_by_part[part].1.as_slice()))
+		.collect::<Vec<_>>();
+	let batched = late_parts
+		.iter()
+		.map(|&part| (meta.entry_round(part), &part_commitments[part], coeffs[part]))
+		.collect();
+
+	let BatchInterleavedSumcheckFRIOutput {
+		challenges,
+		multilinear_evals,
+		late_multilinear_evals,
+		fri_final,
+	} = verify_interleaved_sumcheck(
+		meta.total_vars(),
+		&batch_claims_by_part[folded_part].1,
+		&late_claims,
+		FRIFoldVerifier::new(fri_params, merkle_scheme, &part_commitments[folded_part], batched),
+		transcript,
+		&(),
+	)?;
+
+	let mut eval = evaluate_committed_batch(
+		&meta.part_metas()[folded_part],
+		&batch_claims_by_part[folded_part].0,
+		multilinear_evals,
+		&transparents_by_part[folded_part],
+		&challenges,
+	)?;
+	for (&part, multilinear_evals) in iter::zip(&late_parts, late_multilinear_evals) {
+		eval += coeffs[part]
+			* evaluate_committed_batch(
+				&meta.part_metas()[part],
+				&batch_claims_by_part[part].0,
+				multilinear_evals,
+				&transparents_by_part[part],
+				&challenges[meta.entry_round(part)..],
+			)?;
+	}
+	if eval != fri_final {
+		return Err(VerificationError::IncorrectSumcheckEvaluation.into());
+	}
+
+	Ok(())
+}
+
+/// Verifies a batch of sumcheck claims, where the committed codeword is opened by `folder` in
+/// the interleaved sumcheck and folding protocol.
+pub(super) fn verify_with_folder<'a, F, Challenger_>(
+	commit_meta: &CommitMeta,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	folder: impl InterleavedFoldVerifier<F>,
+	transcript: &mut VerifierTranscript<Challenger_>,
+	observer: &impl PhaseObserver,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	Challenger_: Challenger,
+{
+	let (sumcheck_claim_descs, sumcheck_claims) =
+		make_batch_sumcheck_claims(commit_meta, transparents, claims)?;
+
+	// Interleaved front-loaded sumcheck
+	let BatchInterleavedSumcheckFRIOutput {
+		challenges,
+		multilinear_evals,
+		fri_final,
+		..
+	} = verify_interleaved_sumcheck(
+		commit_meta.total_vars(),
+		&sumcheck_claims,
+		&[],
+		folder,
+		transcript,
+		observer,
+	)?;
+
+	// Verify the committed evals against the FRI final value.
+	let piecewise_eval = evaluate_committed_batch(
+		commit_meta,
+		&sumcheck_claim_descs,
+		multilinear_evals,
+		transparents,
+		&challenges,
+	)?;
+	if piecewise_eval != fri_final {
+		return Err(VerificationError::IncorrectSumcheckEvaluation.into());
+	}
+
+	Ok(())
+}
+
+/// Makes the sumcheck claims on a committed batch, one for each number of variables of the
+/// committed multilinears, in ascending order.
+///
+/// Returns the claim descriptions by number of variables along with the sumcheck claims.
+#[allow(clippy::type_complexity)]
+fn make_batch_sumcheck_claims<'a, F: TowerField>(
+	commit_meta: &CommitMeta,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+) -> Result<
+	(Vec<SumcheckClaimDesc<F>>, Vec<SumcheckClaim<F, IndexComposition<BivariateProduct, 2>>>),
+	Error,
+> {
+	commit_meta.check_top_field::<F>()?;
+
 	// Map of n_vars to sumcheck claim descriptions
 	let sumcheck_claim_descs = make_sumcheck_claim_descs(
 		commit_meta,
@@ -305,15 +945,7 @@ where
 		claims,
 	)?;
 
-	let non_empty_sumcheck_descs = sumcheck_claim_descs
-		.iter()
-		.enumerate()
-		// Keep sumcheck claims with >0 committed multilinears, even with 0 composite claims. This
-		// indicates unconstrained columns, but we still need the final evaluations from the
-		// sumcheck prover in order to derive the final FRI value.
-		.filter(|(_n_vars, desc)| !desc.committed_indices.is_empty());
-	let sumcheck_claims = non_empty_sumcheck_descs
-		.clone()
+	let sumcheck_claims = non_empty_sumcheck_descs(&sumcheck_claim_descs)
 		.map(|(n_vars, desc)| {
 			// Make a single sumcheck claim with compositions of the committed and transparent
 			// polynomials with `n_vars` variables
@@ -324,44 +956,143 @@ where
 			)
 		})
 		.collect::<Result<Vec<_>, _>>()?;
+	Ok((sumcheck_claim_descs, sumcheck_claims))
+}
 
-	// Interleaved front-loaded sumcheck
-	let BatchInterleavedSumcheckFRIOutput {
-		challenges,
-		multilinear_evals,
-		fri_final,
-	} = verify_interleaved_fri_sumcheck(
-		commit_meta.total_vars(),
-		fri_params,
-		merkle_scheme,
-		&sumcheck_claims,
-		commitment,
-		transcript,
-	)?;
+/// Returns the claim descriptions with committed multilinears, which are those with a sumcheck
+/// claim.
+fn non_empty_sumcheck_descs<F: Field>(
+	sumcheck_claim_descs: &[SumcheckClaimDesc<F>],
+) -> impl Iterator<Item = (usize, &SumcheckClaimDesc<F>)> + Clone {
+	sumcheck_claim_descs
+		.iter()
+		.enumerate()
+		// Keep sumcheck claims with >0 committed multilinears, even with 0 composite claims. This
+		// indicates unconstrained columns, but we still need the final evaluations from the
+		// sumcheck prover in order to derive the final FRI value.
+		.filter(|(_n_vars, desc)| !desc.committed_indices.is_empty())
+}
 
+/// Verifies the transparent evaluations output by the sumcheck on a committed batch, and returns
+/// the evaluation of the committed batch at the challenges it is folded with.
+fn evaluate_committed_batch<'a, F: Field>(
+	commit_meta: &CommitMeta,
+	sumcheck_claim_descs: &[SumcheckClaimDesc<F>],
+	multilinear_evals: Vec<Vec<F>>,
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	challenges: &[F],
+) -> Result<F, Error> {
 	let mut piecewise_evals = verify_transparent_evals(
 		commit_meta,
-		non_empty_sumcheck_descs,
+		non_empty_sumcheck_descs(sumcheck_claim_descs),
 		multilinear_evals,
 		transparents,
-		&challenges,
+		challenges,
 	)?;
 
-	// Verify the committed evals against the FRI final value.
 	piecewise_evals.reverse();
 	let n_pieces_by_vars = sumcheck_claim_descs
 		.iter()
 		.map(|desc| desc.n_committed())
 		.collect::<Vec<_>>();
 	let piecewise_eval =
-		evaluate_piecewise_multilinear(&challenges, &n_pieces_by_vars, &mut piecewise_evals)?;
-	if piecewise_eval != fri_final {
-		return Err(VerificationError::IncorrectSumcheckEvaluation.into());
+		evaluate_piecewise_multilinear(challenges, &n_pieces_by_vars, &mut piecewise_evals)?;
+	Ok(piecewise_eval)
+}
+
+/// Verifies a batch of sumcheck claims over the committed multilinears of several independent
+/// commitments.
+///
+/// This mirrors [`super::prove_multi_commit`], verifying the claims routed to each commitment in
+/// order with [`verify`] against that commitment.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_multi_commit<'a, F, FEncode, Challenger_, MTScheme>(
+	multi_commit_meta: &MultiCommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &[&FRIParams<F, FEncode>],
+	commitments: &[MTScheme::Digest],
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	Challenger_: Challenger,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+{
+	for n_commitments in [fri_params.len(), commitments.len()] {
+		if n_commitments != multi_commit_meta.n_commitments() {
+			bail!(Error::IncorrectNumberOfCommitments {
+				expected: multi_commit_meta.n_commitments(),
+				actual: n_commitments,
+			});
+		}
 	}
 
+	let splits = multi_commit_meta.split_claims(transparents.len(), claims)?;
+	for (fri_params, commit_meta, commitment, split) in
+		izip!(fri_params, multi_commit_meta.commit_metas(), commitments, &splits)
+	{
+		let transparents = split
+			.transparent_indices
+			.iter()
+			.map(|&index| transparents[index].borrow())
+			.collect::<Vec<_>>();
+		verify(
+			commit_meta,
+			merkle_scheme,
+			fri_params,
+			commitment,
+			&transparents,
+			&split.claims,
+			transcript,
+		)?;
+	}
 	Ok(())
 }
 
+/// Verifies a batch of sumcheck claims over committed multilinears split into one commitment for
+/// each rate.
+///
+/// This mirrors [`super::prove_rate_batches`], verifying each rate batch in ascending order of rate
+/// as an independent commitment with [`verify_multi_commit`].
+#[allow(clippy::too_many_arguments)]
+pub fn verify_rate_batches<'a, F, FEncode, Challenger_, MTScheme>(
+	rate_batched_meta: &RateBatchedCommitMeta,
+	merkle_scheme: &MTScheme,
+	fri_params: &[FRIParams<F, FEncode>],
+	commitments: &[MTScheme::Digest],
+	transparents: &[impl Borrow<dyn MultivariatePoly<F> + 'a>],
+	claims: &[PIOPSumcheckClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	Challenger_: Challenger,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+{
+	for n_batches in [fri_params.len(), commitments.len()] {
+		if n_batches != rate_batched_meta.n_batches() {
+			bail!(Error::IncorrectNumberOfRateBatches {
+				expected: rate_batched_meta.n_batches(),
+				actual: n_batches,
+			});
+		}
+	}
+
+	verify_multi_commit(
+		rate_batched_meta.multi_commit_meta(),
+		merkle_scheme,
+		&fri_params.iter().collect::<Vec<_>>(),
+		commitments,
+		transparents,
+		claims,
+		transcript,
+	)
+}
+
 // Verify the transparent evals and collect the committed evals.
 #[instrument(skip_all, level = "debug")]
 fn verify_transparent_evals<'a, 'b, F: Field>(
@@ -405,79 +1136,226 @@ fn verify_transparent_evals<'a, 'b, F: Field>(
 struct BatchInterleavedSumcheckFRIOutput<F> {
 	challenges: Vec<F>,
 	multilinear_evals: Vec<Vec<F>>,
+	/// The multilinear evaluations output by each batch of late sumcheck claims.
+	late_multilinear_evals: Vec<Vec<Vec<F>>>,
 	fri_final: F,
 }
 
-/// Runs the interleaved sumcheck & FRI invocation, reducing to committed and transparent
+/// The verifier of the opening of a committed codeword in the interleaved sumcheck and folding
+/// protocol, the counterpart of [`super::prove::InterleavedFolder`].
+pub(super) trait InterleavedFoldVerifier<F: Field> {
+	/// Reads the messages the prover sends after the fold round with the given index.
+	fn observe_fold_round<Challenger_: Challenger>(
+		&mut self,
+		round: usize,
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Error>;
+
+	/// Verifies the terminal messages and the query proofs after the last fold round, returning
+	/// the value that the codeword is folded to.
+	fn verify<Challenger_: Challenger>(
+		self,
+		challenges: &[F],
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<F, Error>;
+}
+
+/// The verifier of the FRI opening of a committed codeword, which reads the round commitments as
+/// the fold rounds proceed.
+struct FRIFoldVerifier<'a, F, FEncode, MTScheme>
+where
+	F: BinaryField,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F>,
+{
+	params: &'a FRIParams<F, FEncode>,
+	merkle_scheme: &'a MTScheme,
+	commitment: &'a MTScheme::Digest,
+	/// The commitments batched into the FRI instance after the given number of fold rounds, and
+	/// their coefficients, such as a mask.
+	batched: Vec<(usize, &'a MTScheme::Digest, F)>,
+	plan: Option<&'a VerifierPlan<FEncode, PrecomputedTwiddleAccess<FEncode>>>,
+	round_commitments: Vec<MTScheme::Digest>,
+	next_commit_round: Option<usize>,
+}
+
+impl<'a, F, FEncode, MTScheme> FRIFoldVerifier<'a, F, FEncode, MTScheme>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F>,
+{
+	fn new(
+		params: &'a FRIParams<F, FEncode>,
+		merkle_scheme: &'a MTScheme,
+		commitment: &'a MTScheme::Digest,
+		batched: Vec<(usize, &'a MTScheme::Digest, F)>,
+	) -> Self {
+		Self {
+			params,
+			merkle_scheme,
+			commitment,
+			batched,
+			plan: None,
+			round_commitments: Vec::with_capacity(params.n_oracles()),
+			next_commit_round: params.fold_arities().first().copied(),
+		}
+	}
+
+	/// Verifies the query phase with a plan precomputed for the parameters.
+	fn with_plan(self, plan: &'a VerifierPlan<FEncode, PrecomputedTwiddleAccess<FEncode>>) -> Self {
+		Self {
+			plan: Some(plan),
+			..self
+		}
+	}
+}
+
+impl<F, FEncode, MTScheme> InterleavedFoldVerifier<F> for FRIFoldVerifier<'_, F, FEncode, MTScheme>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+{
+	fn observe_fold_round<Challenger_: Challenger>(
+		&mut self,
+		round: usize,
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Error> {
+		if self.next_commit_round == Some(round + 1) {
+			let comm = transcript
+				.message()
+				.read()
+				.map_err(VerificationError::Transcript)?;
+			self.round_commitments.push(comm);
+			self.next_commit_round = self
+				.params
+				.fold_arities()
+				.get(self.round_commitments.len())
+				.map(|arity| round + 1 + arity);
+		}
+		Ok(())
+	}
+
+	fn verify<Challenger_: Challenger>(
+		self,
+		challenges: &[F],
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<F, Error> {
+		let mut verifier = FRIVerifier::new(
+			self.params,
+			self.merkle_scheme,
+			self.commitment,
+			&self.round_commitments,
+			challenges,
+		)?;
+		for (fold_round, batched_commitment, coeff) in self.batched {
+			verifier = verifier.with_batched_at_round(fold_round, batched_commitment, coeff)?;
+		}
+		let final_value = match self.plan {
+			Some(plan) => verifier.verify_with_plan(plan, transcript)?,
+			None => verifier.verify(transcript)?,
+		};
+		Ok(final_value)
+	}
+}
+
+/// Runs the interleaved sumcheck & folding invocation, reducing to committed and transparent
 /// multilinear evaluation checks.
 ///
+/// `late_claims` are further batches of sumcheck claims with the round each starts at, for the
+/// multilinears of codewords that join the folded codeword after that many fold rounds. Their
+/// round messages follow those of the first batch in each round from then on, in order.
+///
 /// ## Preconditions
 ///
 /// * `n_rounds` is greater than or equal to the maximum number of variables of any claim
 /// * `claims` are sorted in ascending order by number of variables
 #[instrument(skip_all)]
-fn verify_interleaved_fri_sumcheck<F, FEncode, Challenger_, MTScheme>(
+fn verify_interleaved_sumcheck<F, Challenger_>(
 	n_rounds: usize,
-	fri_params: &FRIParams<F, FEncode>,
-	merkle_scheme: &MTScheme,
 	claims: &[SumcheckClaim<F, IndexComposition<BivariateProduct, 2>>],
-	codeword_commitment: &MTScheme::Digest,
+	late_claims: &[(usize, &[SumcheckClaim<F, IndexComposition<BivariateProduct, 2>>])],
+	mut folder: impl InterleavedFoldVerifier<F>,
 	proof: &mut VerifierTranscript<Challenger_>,
+	observer: &impl PhaseObserver,
 ) -> Result<BatchInterleavedSumcheckFRIOutput<F>, Error>
 where
-	F: TowerField + ExtensionField<FEncode>,
-	FEncode: BinaryField,
+	F: TowerField,
 	Challenger_: Challenger,
-	MTScheme: MerkleTreeScheme<F, Digest: DeserializeBytes>,
 {
-	let mut arities_iter = fri_params.fold_arities().iter();
-	let mut fri_commitments = Vec::with_capacity(fri_params.n_oracles());
-	let mut next_commit_round = arities_iter.next().copied();
-
 	let mut sumcheck_verifier = SumcheckBatchVerifier::new(claims, proof)?;
+	let mut late_sumcheck_verifiers = late_claims
+		.iter()
+		.map(|&(start_round, late_claims)| {
+			Ok((start_round, SumcheckBatchVerifier::new(late_claims, proof)?))
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
 	let mut multilinear_evals = Vec::with_capacity(claims.len());
+	let mut late_multilinear_evals = vec![Vec::new(); late_claims.len()];
 	let mut challenges = Vec::with_capacity(n_rounds);
 	for round_no in 0..n_rounds {
-		let mut reader = proof.message();
-		while let Some(claim_multilinear_evals) = sumcheck_verifier.try_finish_claim(&mut reader)? {
-			multilinear_evals.push(claim_multilinear_evals);
-		}
-		sumcheck_verifier.receive_round_proof(&mut reader)?;
+		let challenge = observe_phase(observer, PIOPPhase::Sumcheck, || {
+			let mut reader = proof.message();
+			while let Some(claim_multilinear_evals) =
+				sumcheck_verifier.try_finish_claim(&mut reader)?
+			{
+				multilinear_evals.push(claim_multilinear_evals);
+			}
+			sumcheck_verifier.receive_round_proof(&mut reader)?;
+			for ((start_round, late_sumcheck_verifier), late_multilinear_evals) in
+				iter::zip(&mut late_sumcheck_verifiers, &mut late_multilinear_evals)
+			{
+				if round_no >= *start_round {
+					while let Some(claim_multilinear_evals) =
+						late_sumcheck_verifier.try_finish_claim(&mut reader)?
+					{
+						late_multilinear_evals.push(claim_multilinear_evals);
+					}
+					late_sumcheck_verifier.receive_round_proof(&mut reader)?;
+				}
+			}
 
-		let challenge = proof.sample();
+			let challenge = proof.sample();
+			sumcheck_verifier.finish_round(challenge)?;
+			for (start_round, late_sumcheck_verifier) in &mut late_sumcheck_verifiers {
+				if round_no >= *start_round {
+					late_sumcheck_verifier.finish_round(challenge)?;
+				}
+			}
+			Ok::<_, Error>(challenge)
+		})?;
 		challenges.push(challenge);
 
-		sumcheck_verifier.finish_round(challenge)?;
-
-		let observe_fri_comm = next_commit_round.is_some_and(|round| round == round_no + 1);
-		if observe_fri_comm {
-			let comm = proof
-				.message()
-				.read()
-				.map_err(VerificationError::Transcript)?;
-			fri_commitments.push(comm);
-			next_commit_round = arities_iter.next().map(|arity| round_no + 1 + arity);
-		}
+		observe_phase(observer, PIOPPhase::FriFold, || folder.observe_fold_round(round_no, proof))?;
 	}
 
-	let mut reader = proof.message();
-	while let Some(claim_multilinear_evals) = sumcheck_verifier.try_finish_claim(&mut reader)? {
-		multilinear_evals.push(claim_multilinear_evals);
-	}
-	sumcheck_verifier.finish()?;
+	observe_phase(observer, PIOPPhase::Sumcheck, || {
+		let mut reader = proof.message();
+		while let Some(claim_multilinear_evals) = sumcheck_verifier.try_finish_claim(&mut reader)? {
+			multilinear_evals.push(claim_multilinear_evals);
+		}
+		sumcheck_verifier.finish()?;
+		for ((_, mut late_sumcheck_verifier), late_multilinear_evals) in
+			iter::zip(late_sumcheck_verifiers, &mut late_multilinear_evals)
+		{
+			while let Some(claim_multilinear_evals) =
+				late_sumcheck_verifier.try_finish_claim(&mut reader)?
+			{
+				late_multilinear_evals.push(claim_multilinear_evals);
+			}
+			late_sumcheck_verifier.finish()?;
+		}
+		Ok::<_, Error>(())
+	})?;
 
-	let verifier = FRIVerifier::new(
-		fri_params,
-		merkle_scheme,
-		codeword_commitment,
-		&fri_commitments,
-		&challenges,
-	)?;
-	let fri_final = verifier.verify(proof)?;
+	let fri_final =
+		observe_phase(observer, PIOPPhase::FriQuery, || folder.verify(&challenges, proof))?;
 
 	Ok(BatchInterleavedSumcheckFRIOutput {
 		challenges,
 		multilinear_evals,
+		late_multilinear_evals,
 		fri_final,
 	})
 }
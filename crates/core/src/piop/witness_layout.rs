@@ -0,0 +1,150 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::borrow::Cow;
+
+use binius_field::{
+	PackedField, TowerField,
+	packed::{get_packed_slice, set_packed_slice},
+};
+use binius_math::{MLEDirectAdapter, MultilinearExtension, MultilinearPoly};
+use binius_utils::bail;
+use bytemuck::zeroed_vec;
+
+use super::{
+	error::Error,
+	prove::{message_slots, packed_committed},
+	verify::CommitMeta,
+};
+use crate::oracle::OracleId;
+
+/// A view of a committed multilinear stored in a [`CommittedWitnessBuffer`].
+pub type CommittedColumn<'a, P> = MLEDirectAdapter<P, Cow<'a, [P]>>;
+
+/// Contiguous storage for the committed multilinears of a batch, laid out as in the interleaved
+/// message that is encoded by [`super::commit`].
+///
+/// Each committed multilinear occupies a fixed slot of the message, in reverse commitment order,
+/// and is stored there in its packed form over `F`. Witness generation writes the committed
+/// columns directly into their slots, so that [`super::commit_witness_buffer`] reads the whole
+/// message with a single sequential copy, instead of gathering every committed multilinear from
+/// its own allocation.
+#[derive(Debug)]
+pub struct CommittedWitnessBuffer<P: PackedField> {
+	/// The number of packed variables and the scalar offset into the message of each committed
+	/// multilinear, by commitment index.
+	slots: Vec<(usize, usize)>,
+	message: Vec<P>,
+}
+
+impl<F, P> CommittedWitnessBuffer<P>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+{
+	/// Allocates a zero-initialized buffer for the batch described by `commit_meta`.
+	pub fn new(commit_meta: &CommitMeta) -> Self {
+		let message_len = 1 << commit_meta.total_vars().saturating_sub(P::LOG_WIDTH);
+		Self {
+			slots: message_slots(commit_meta),
+			message: zeroed_vec(message_len),
+		}
+	}
+
+	/// The number of committed multilinears in the batch.
+	pub fn n_multilins(&self) -> usize {
+		self.slots.len()
+	}
+
+	/// The interleaved message, with each committed multilinear in natural evaluation order.
+	pub fn message(&self) -> &[P] {
+		&self.message
+	}
+
+	/// Returns the number of packed variables and the scalar offset of the committed multilinear
+	/// with the given index.
+	pub(super) fn slot(&self, index: usize) -> Result<(usize, usize), Error> {
+		self.slots
+			.get(index)
+			.copied()
+			.ok_or(Error::InvalidCommittedId {
+				max_index: self.slots.len(),
+			})
+	}
+
+	pub(super) fn slots(&self) -> &[(usize, usize)] {
+		&self.slots
+	}
+
+	/// Returns the packed evaluations of the committed multilinear with the given index for
+	/// writing.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::CommittedSmallerThanPackedField`] if the committed multilinear has fewer
+	///   variables than the packing width of `P`, in which case it shares packed elements with
+	///   other multilinears; use [`Self::write`] instead.
+	pub fn column_mut(&mut self, index: usize) -> Result<&mut [P], Error> {
+		let (n_vars, offset) = self.slot(index)?;
+		if n_vars < P::LOG_WIDTH {
+			bail!(Error::CommittedSmallerThanPackedField { index });
+		}
+		Ok(&mut self.message[offset >> P::LOG_WIDTH..][..1 << (n_vars - P::LOG_WIDTH)])
+	}
+
+	/// Writes the committed multilinear with the given index into its slot.
+	///
+	/// The multilinear may be defined over a subfield of `F`, as with [`super::commit`].
+	pub fn write<M: MultilinearPoly<P>>(
+		&mut self,
+		index: usize,
+		multilin: &M,
+	) -> Result<(), Error> {
+		let (n_vars, offset) = self.slot(index)?;
+		let packed_multilin = packed_committed(OracleId::from_index(index), multilin)?;
+		if packed_multilin.n_vars() != n_vars {
+			bail!(Error::CommittedVariablesMismatch {
+				index,
+				expected: n_vars,
+				actual: packed_multilin.n_vars(),
+			});
+		}
+
+		if n_vars >= P::LOG_WIDTH {
+			let chunk = self.column_mut(index)?;
+			chunk.copy_from_slice(&packed_multilin.evals()[..chunk.len()]);
+		} else {
+			let packed_eval = packed_multilin.evals()[0];
+			for i in 0..1 << n_vars {
+				set_packed_slice(&mut self.message, offset + i, packed_eval.get(i));
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns a view of the committed multilinear with the given index.
+	///
+	/// The view is in packed form over `F`, and may be passed to [`super::prove`] in place of the
+	/// multilinear that was written.
+	pub fn multilin(&self, index: usize) -> Result<CommittedColumn<'_, P>, Error> {
+		let (n_vars, offset) = self.slot(index)?;
+		let evals = if n_vars >= P::LOG_WIDTH {
+			Cow::Borrowed(&self.message[offset >> P::LOG_WIDTH..][..1 << (n_vars - P::LOG_WIDTH)])
+		} else {
+			Cow::Owned(vec![P::from_fn(|i| {
+				if i < 1 << n_vars {
+					get_packed_slice(&self.message, offset + i)
+				} else {
+					F::ZERO
+				}
+			})])
+		};
+		Ok(MultilinearExtension::new(n_vars, evals)?.into())
+	}
+
+	/// Returns views of all committed multilinears in commitment order.
+	pub fn multilins(&self) -> Result<Vec<CommittedColumn<'_, P>>, Error> {
+		(0..self.n_multilins())
+			.map(|index| self.multilin(index))
+			.collect()
+	}
+}
@@ -11,7 +11,7 @@ use bytes::{Buf, BufMut};
 
 use super::error::Error;
 use crate::{
-	oracle::OracleId,
+	oracle::{Error as OracleError, OracleId, OracleIdRemapping},
 	transcript::{TranscriptReader, TranscriptWriter},
 };
 
@@ -26,6 +26,17 @@ pub struct EvalcheckMultilinearClaim<F: Field> {
 	pub eval: F,
 }
 
+impl<F: Field> EvalcheckMultilinearClaim<F> {
+	/// Returns the claim with the oracle ID translated by a remapping.
+	pub fn remap(&self, remapping: &OracleIdRemapping) -> Result<Self, OracleError> {
+		Ok(Self {
+			id: remapping.remap(self.id)?,
+			eval_point: self.eval_point.clone(),
+			eval: self.eval,
+		})
+	}
+}
+
 #[repr(u32)]
 #[derive(Debug)]
 enum EvalcheckNumerics {
@@ -0,0 +1,317 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::{
+		Arc, Mutex,
+		atomic::{AtomicUsize, Ordering},
+	},
+};
+
+use binius_field::PackedField;
+use binius_math::{MultilinearPoly, MultilinearQuery};
+
+use crate::{oracle::OracleId, witness::MultilinearWitness};
+
+/// A key-value store backing the tensor expansions and partial evaluations memoized by
+/// [`super::subclaims::MemoizedData`].
+///
+/// Values are shared through reference counting, so a store may hand out values that it has
+/// since evicted, and a store over shared memory, disk, or a remote cache in a distributed prover
+/// can materialize them on lookup. A store may drop any entry at any time; the prover recomputes
+/// the values that are missing.
+pub trait MemoStore<'a, P: PackedField>: Send + Sync {
+	/// Returns the tensor expansion of the evaluation point, if present.
+	fn get_query(&self, eval_point: &[P::Scalar]) -> Option<Arc<MultilinearQuery<P>>>;
+
+	/// Stores the tensor expansion of the evaluation point.
+	fn insert_query(&mut self, eval_point: &[P::Scalar], query: Arc<MultilinearQuery<P>>);
+
+	/// Returns the partial evaluation of the oracle at the suffix `eval_point`, if present.
+	fn get_partial_eval(
+		&self,
+		id: OracleId,
+		eval_point: &[P::Scalar],
+	) -> Option<MultilinearWitness<'a, P>>;
+
+	/// Stores the partial evaluation of the oracle at the suffix `eval_point`.
+	fn insert_partial_eval(
+		&mut self,
+		id: OracleId,
+		eval_point: &[P::Scalar],
+		partial_eval: MultilinearWitness<'a, P>,
+	);
+
+	/// Returns the number of entries evicted to stay within the size bound of the store.
+	fn n_evictions(&self) -> usize {
+		0
+	}
+}
+
+#[derive(Debug, Clone)]
+enum MemoKey<F> {
+	Query(Vec<F>),
+	PartialEval(OracleId, Vec<F>),
+}
+
+/// An in-process [`MemoStore`], optionally bounded in size.
+///
+/// When the total size of the stored values exceeds the bound, the oldest entries are evicted
+/// first.
+pub struct InMemoryMemoStore<'a, P: PackedField> {
+	queries: HashMap<Vec<P::Scalar>, Arc<MultilinearQuery<P>>>,
+	partial_evals: HashMap<(OracleId, Vec<P::Scalar>), MultilinearWitness<'a, P>>,
+	insertion_order: VecDeque<(MemoKey<P::Scalar>, usize)>,
+	size_bytes: usize,
+	max_size_bytes: Option<usize>,
+	n_evictions: usize,
+}
+
+impl<P: PackedField> InMemoryMemoStore<'_, P> {
+	/// Constructs an unbounded store.
+	pub fn new() -> Self {
+		Self {
+			queries: HashMap::new(),
+			partial_evals: HashMap::new(),
+			insertion_order: VecDeque::new(),
+			size_bytes: 0,
+			max_size_bytes: None,
+			n_evictions: 0,
+		}
+	}
+
+	/// Constructs a store that evicts its oldest entries to keep the total size of the stored
+	/// values within `max_size_bytes`.
+	pub fn with_max_size_bytes(max_size_bytes: usize) -> Self {
+		Self {
+			max_size_bytes: Some(max_size_bytes),
+			..Self::new()
+		}
+	}
+
+	/// Returns the total size of the stored values in bytes.
+	pub const fn size_bytes(&self) -> usize {
+		self.size_bytes
+	}
+
+	/// Returns the number of stored entries.
+	pub fn len(&self) -> usize {
+		self.insertion_order.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.insertion_order.is_empty()
+	}
+
+	fn push(&mut self, key: MemoKey<P::Scalar>, size_bytes: usize) {
+		self.insertion_order.push_back((key, size_bytes));
+		self.size_bytes += size_bytes;
+
+		let Some(max_size_bytes) = self.max_size_bytes else {
+			return;
+		};
+		while self.size_bytes > max_size_bytes {
+			let Some((key, size_bytes)) = self.insertion_order.pop_front() else {
+				break;
+			};
+			match key {
+				MemoKey::Query(eval_point) => {
+					self.queries.remove(&eval_point);
+				}
+				MemoKey::PartialEval(id, eval_point) => {
+					self.partial_evals.remove(&(id, eval_point));
+				}
+			}
+			self.size_bytes -= size_bytes;
+			self.n_evictions += 1;
+		}
+	}
+}
+
+impl<P: PackedField> Default for InMemoryMemoStore<'_, P> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<'a, P: PackedField> MemoStore<'a, P> for InMemoryMemoStore<'a, P> {
+	fn get_query(&self, eval_point: &[P::Scalar]) -> Option<Arc<MultilinearQuery<P>>> {
+		self.queries.get(eval_point).cloned()
+	}
+
+	fn insert_query(&mut self, eval_point: &[P::Scalar], query: Arc<MultilinearQuery<P>>) {
+		if self.queries.contains_key(eval_point) {
+			return;
+		}
+		let size_bytes = size_of_val(query.expansion());
+		self.queries.insert(eval_point.to_vec(), query);
+		self.push(MemoKey::Query(eval_point.to_vec()), size_bytes);
+	}
+
+	fn get_partial_eval(
+		&self,
+		id: OracleId,
+		eval_point: &[P::Scalar],
+	) -> Option<MultilinearWitness<'a, P>> {
+		self.partial_evals.get(&(id, eval_point.to_vec())).cloned()
+	}
+
+	fn insert_partial_eval(
+		&mut self,
+		id: OracleId,
+		eval_point: &[P::Scalar],
+		partial_eval: MultilinearWitness<'a, P>,
+	) {
+		let key = (id, eval_point.to_vec());
+		if self.partial_evals.contains_key(&key) {
+			return;
+		}
+		let size_bytes = partial_eval
+			.packed_evals()
+			.map_or_else(|| size_of::<P::Scalar>() << partial_eval.n_vars(), size_of_val);
+		self.partial_evals.insert(key, partial_eval);
+		self.push(MemoKey::PartialEval(id, eval_point.to_vec()), size_bytes);
+	}
+
+	fn n_evictions(&self) -> usize {
+		self.n_evictions
+	}
+}
+
+/// A cache of tensor expansions of evaluation points that is shared across proofs.
+///
+/// Unlike partial evaluations, which depend on the witness, the expansion of an evaluation point
+/// only depends on the point, so a prover that proves many statements with recurring evaluation
+/// points, such as the suffixes of the ring-switching reduction, can reuse the expansions of
+/// earlier proofs. A cache is attached to the [`super::subclaims::MemoizedData`] of each proof
+/// with [`super::subclaims::MemoizedData::with_expansion_cache`], which consults it for the
+/// expansions missing from its store and records the expansions it computes in it.
+///
+/// The cache is unbounded; callers should [`Self::clear`] it when the evaluation points no longer
+/// recur.
+pub struct ExpansionCache<P: PackedField> {
+	queries: Mutex<HashMap<Vec<P::Scalar>, Arc<MultilinearQuery<P>>>>,
+}
+
+impl<P: PackedField> ExpansionCache<P> {
+	/// Constructs an empty cache.
+	pub fn new() -> Self {
+		Self {
+			queries: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the tensor expansion of the evaluation point, if present.
+	pub fn get(&self, eval_point: &[P::Scalar]) -> Option<Arc<MultilinearQuery<P>>> {
+		self.queries
+			.lock()
+			.expect("mutex is always available")
+			.get(eval_point)
+			.cloned()
+	}
+
+	/// Stores the tensor expansion of the evaluation point.
+	pub fn insert(&self, eval_point: &[P::Scalar], query: Arc<MultilinearQuery<P>>) {
+		self.queries
+			.lock()
+			.expect("mutex is always available")
+			.entry(eval_point.to_vec())
+			.or_insert(query);
+	}
+
+	/// Returns the number of cached expansions.
+	pub fn len(&self) -> usize {
+		self.queries
+			.lock()
+			.expect("mutex is always available")
+			.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Removes all cached expansions.
+	pub fn clear(&self) {
+		self.queries
+			.lock()
+			.expect("mutex is always available")
+			.clear();
+	}
+}
+
+impl<P: PackedField> Default for ExpansionCache<P> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Hit and miss counts of the lookups in a [`super::subclaims::MemoizedData`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoizedDataMetrics {
+	pub query_hits: usize,
+	pub query_misses: usize,
+	pub partial_eval_hits: usize,
+	pub partial_eval_misses: usize,
+	/// The number of entries the store evicted to stay within its size bound.
+	pub evictions: usize,
+}
+
+impl MemoizedDataMetrics {
+	/// The fraction of tensor expansion lookups that were found in the store.
+	pub fn query_hit_rate(&self) -> f64 {
+		hit_rate(self.query_hits, self.query_misses)
+	}
+
+	/// The fraction of partial evaluation lookups that were found in the store.
+	pub fn partial_eval_hit_rate(&self) -> f64 {
+		hit_rate(self.partial_eval_hits, self.partial_eval_misses)
+	}
+}
+
+fn hit_rate(hits: usize, misses: usize) -> f64 {
+	if hits + misses == 0 {
+		0.0
+	} else {
+		hits as f64 / (hits + misses) as f64
+	}
+}
+
+/// Lookup counters that can be updated through shared references from parallel tasks.
+#[derive(Debug, Default)]
+pub(super) struct MemoCounters {
+	query_hits: AtomicUsize,
+	query_misses: AtomicUsize,
+	partial_eval_hits: AtomicUsize,
+	partial_eval_misses: AtomicUsize,
+}
+
+impl MemoCounters {
+	pub fn record_query<T>(&self, lookup: &Option<T>) {
+		let counter = if lookup.is_some() {
+			&self.query_hits
+		} else {
+			&self.query_misses
+		};
+		counter.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub fn record_partial_eval<T>(&self, lookup: &Option<T>) {
+		let counter = if lookup.is_some() {
+			&self.partial_eval_hits
+		} else {
+			&self.partial_eval_misses
+		};
+		counter.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub fn metrics(&self, evictions: usize) -> MemoizedDataMetrics {
+		MemoizedDataMetrics {
+			query_hits: self.query_hits.load(Ordering::Relaxed),
+			query_misses: self.query_misses.load(Ordering::Relaxed),
+			partial_eval_hits: self.partial_eval_hits.load(Ordering::Relaxed),
+			partial_eval_misses: self.partial_eval_misses.load(Ordering::Relaxed),
+			evictions,
+		}
+	}
+}
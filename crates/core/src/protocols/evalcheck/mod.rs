@@ -16,6 +16,7 @@ mod error;
 #[allow(clippy::module_inception)]
 mod evalcheck;
 mod logging;
+mod memo_store;
 mod prove;
 pub mod subclaims;
 #[cfg(test)]
@@ -24,5 +25,6 @@ mod verify;
 
 pub use error::*;
 pub use evalcheck::*;
+pub use memo_store::*;
 pub use prove::*;
 pub use verify::*;
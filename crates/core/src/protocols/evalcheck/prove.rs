@@ -829,9 +829,7 @@ where
 				let partial_eval = match partial_evals.get(oracle_id, suffix) {
 					Some(partial_eval) => partial_eval,
 					None => {
-						let suffix_query = memoized_queries
-							.full_query_readonly(suffix)
-							.ok_or(Error::MissingQuery)?;
+						let suffix_query = memoized_queries.full_query_readonly(suffix);
 
 						let partial_eval = witness_poly
 							.evaluate_partial_high(suffix_query.to_ref())
@@ -849,11 +847,13 @@ where
 					}
 				};
 
-				let prefix_query = memoized_queries
-					.full_query_readonly(&prefix)
-					.ok_or(Error::MissingQuery)?;
+				let prefix_query = memoized_queries.full_query_readonly(&prefix);
 
-				eval = Some(partial_eval.evaluate(prefix_query).map_err(Error::from)?);
+				eval = Some(
+					partial_eval
+						.evaluate(prefix_query.to_ref())
+						.map_err(Error::from)?,
+				);
 				break;
 			}
 		}
@@ -861,9 +861,7 @@ where
 		let eval = match eval {
 			Some(value) => value,
 			None => {
-				let query = memoized_queries
-					.full_query_readonly(&eval_point)
-					.ok_or(Error::MissingQuery)?;
+				let query = memoized_queries.full_query_readonly(&eval_point);
 
 				witness_poly.evaluate(query.to_ref()).map_err(Error::from)?
 			}
@@ -9,7 +9,7 @@
 //!  * one multilin (the multiplier) is transparent (`shift_ind`, `eq_ind`, or tower basis)
 //!  * other multilin is a projection of one of the evalcheck claim multilins to its first variables
 
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 use binius_field::{ExtensionField, Field, PackedExtension, PackedField, TowerField};
 use binius_hal::ComputationBackend;
@@ -23,7 +23,12 @@ use bytemuck::zeroed_vec;
 use itertools::izip;
 use tracing::instrument;
 
-use super::{EvalPoint, EvalPointOracleIdMap, error::Error, evalcheck::EvalcheckMultilinearClaim};
+use super::{
+	EvalPoint, EvalPointOracleIdMap,
+	error::Error,
+	evalcheck::EvalcheckMultilinearClaim,
+	memo_store::{ExpansionCache, InMemoryMemoStore, MemoCounters, MemoStore, MemoizedDataMetrics},
+};
 use crate::{
 	fiat_shamir::Challenger,
 	oracle::{
@@ -389,9 +394,7 @@ where
 		.map(|(suffix, inner_id)| {
 			let inner_multilin = witness_index.get_multilin_poly(inner_id)?;
 
-			let query = memoized_queries
-				.full_query_readonly(suffix)
-				.ok_or(Error::MissingQuery)?;
+			let query = memoized_queries.full_query_readonly(suffix);
 
 			if partial_evals.get(inner_id, suffix).is_some() {
 				return Ok(None);
@@ -440,50 +443,93 @@ where
 
 /// Struct for memoizing tensor expansions of evaluation points and partial evaluations of
 /// multilinears
-#[allow(clippy::type_complexity)]
+///
+/// The values are kept in a [`MemoStore`], which is an unbounded [`InMemoryMemoStore`] by default.
+/// Tensor expansions can additionally be shared across proofs through an [`ExpansionCache`], see
+/// [`Self::with_expansion_cache`]. Lookups are counted, see [`Self::metrics`].
 pub struct MemoizedData<'a, P: PackedField> {
-	query: Vec<(Vec<P::Scalar>, MultilinearQuery<P>)>,
-	partial_evals: EvalPointOracleIdMap<MultilinearWitness<'a, P>, P::Scalar>,
+	store: Box<dyn MemoStore<'a, P> + 'a>,
+	expansion_cache: Option<&'a ExpansionCache<P>>,
+	counters: MemoCounters,
 }
 
 impl<'a, P: PackedField> MemoizedData<'a, P> {
 	#[allow(clippy::new_without_default)]
 	pub fn new() -> Self {
+		Self::with_store(InMemoryMemoStore::new())
+	}
+
+	/// Constructs an instance backed by the given store.
+	pub fn with_store(store: impl MemoStore<'a, P> + 'a) -> Self {
 		Self {
-			query: Vec::new(),
-			partial_evals: EvalPointOracleIdMap::new(),
+			store: Box::new(store),
+			expansion_cache: None,
+			counters: MemoCounters::default(),
 		}
 	}
 
+	/// Shares tensor expansions with other proofs through `expansion_cache`.
+	///
+	/// Expansions missing from the store are looked up in the cache, and the expansions computed
+	/// by this instance are added to it.
+	pub fn with_expansion_cache(mut self, expansion_cache: &'a ExpansionCache<P>) -> Self {
+		self.expansion_cache = Some(expansion_cache);
+		self
+	}
+
+	/// Looks up the tensor expansion of the evaluation point in the store, then in the expansion
+	/// cache.
+	fn get_query(&self, eval_point: &[P::Scalar]) -> Option<Arc<MultilinearQuery<P>>> {
+		self.store.get_query(eval_point).or_else(|| {
+			self.expansion_cache
+				.and_then(|expansion_cache| expansion_cache.get(eval_point))
+		})
+	}
+
+	fn insert_query(&mut self, eval_point: &[P::Scalar], query: Arc<MultilinearQuery<P>>) {
+		if let Some(expansion_cache) = self.expansion_cache {
+			expansion_cache.insert(eval_point, query.clone());
+		}
+		self.store.insert_query(eval_point, query);
+	}
+
 	pub fn full_query(
 		&mut self,
 		eval_point: &[P::Scalar],
-	) -> Result<&MultilinearQuery<P>, binius_hal::Error> {
-		if let Some(index) = self
-			.query
-			.iter()
-			.position(|(memo_eval_point, _)| memo_eval_point.as_slice() == eval_point)
-		{
-			let (_, query) = &self.query[index];
+	) -> Result<Arc<MultilinearQuery<P>>, binius_hal::Error> {
+		let stored = self.store.get_query(eval_point);
+		let in_store = stored.is_some();
+		let query = stored.or_else(|| {
+			self.expansion_cache
+				.and_then(|expansion_cache| expansion_cache.get(eval_point))
+		});
+		self.counters.record_query(&query);
+		if let Some(query) = query {
+			if !in_store {
+				self.store.insert_query(eval_point, query.clone());
+			}
 			return Ok(query);
 		}
 
-		let query = MultilinearQuery::expand(eval_point);
-		self.query.push((eval_point.to_vec(), query));
-
-		let (_, query) = self.query.last().expect("pushed query immediately above");
+		let query = Arc::new(MultilinearQuery::expand(eval_point));
+		self.insert_query(eval_point, query.clone());
 		Ok(query)
 	}
 
 	/// Finds a `MultilinearQuery` corresponding to the given `eval_point`.
-	pub fn full_query_readonly(&self, eval_point: &[P::Scalar]) -> Option<&MultilinearQuery<P>> {
-		self.query
-			.iter()
-			.position(|(memo_eval_point, _)| memo_eval_point.as_slice() == eval_point)
-			.map(|index| {
-				let (_, query) = &self.query[index];
-				query
-			})
+	///
+	/// If neither the store nor the expansion cache has it, for instance because it was evicted,
+	/// the query is expanded and only added to the expansion cache.
+	pub fn full_query_readonly(&self, eval_point: &[P::Scalar]) -> Arc<MultilinearQuery<P>> {
+		let query = self.get_query(eval_point);
+		self.counters.record_query(&query);
+		query.unwrap_or_else(|| {
+			let query = Arc::new(MultilinearQuery::expand(eval_point));
+			if let Some(expansion_cache) = self.expansion_cache {
+				expansion_cache.insert(eval_point, query.clone());
+			}
+			query
+		})
 	}
 
 	#[instrument(skip_all, name = "Evalcheck::memoize_query_par", level = "debug")]
@@ -495,14 +541,17 @@ impl<'a, P: PackedField> MemoizedData<'a, P> {
 
 		let new_queries = deduplicated_eval_points
 			.into_par_iter()
-			.filter(|ep| self.full_query_readonly(ep).is_none())
+			.filter(|ep| self.store.get_query(ep).is_none())
 			.map(|ep| {
-				let query = MultilinearQuery::<P>::expand(ep);
-				(ep.to_vec(), query)
+				let query = self.expansion_cache.and_then(|cache| cache.get(ep));
+				let query = query.unwrap_or_else(|| Arc::new(MultilinearQuery::<P>::expand(ep)));
+				(ep, query)
 			})
 			.collect::<Vec<_>>();
 
-		self.query.extend(new_queries);
+		for (eval_point, query) in new_queries {
+			self.insert_query(eval_point, query);
+		}
 
 		Ok(())
 	}
@@ -522,7 +571,7 @@ impl<'a, P: PackedField> MemoizedData<'a, P> {
 			.for_each(|(claim, meta)| {
 				let inner_id = meta.inner_id;
 				if oracles[inner_id].variant.is_committed() && meta.projected_id.is_some() {
-					let eval_point = claim.eval_point[meta.projected_n_vars..].to_vec().into();
+					let eval_point = &claim.eval_point[meta.projected_n_vars..];
 
 					let projected_id = meta.projected_id.expect("checked above");
 
@@ -530,7 +579,8 @@ impl<'a, P: PackedField> MemoizedData<'a, P> {
 						.get_multilin_poly(projected_id)
 						.expect("witness_index contains projected if projected_id exist");
 
-					self.partial_evals.insert(inner_id, eval_point, projected);
+					self.store
+						.insert_partial_eval(inner_id, eval_point, projected);
 				}
 			});
 	}
@@ -539,8 +589,15 @@ impl<'a, P: PackedField> MemoizedData<'a, P> {
 		&self,
 		id: OracleId,
 		eval_point: &[P::Scalar],
-	) -> Option<&MultilinearWitness<'a, P>> {
-		self.partial_evals.get(id, eval_point)
+	) -> Option<MultilinearWitness<'a, P>> {
+		let partial_eval = self.store.get_partial_eval(id, eval_point);
+		self.counters.record_partial_eval(&partial_eval);
+		partial_eval
+	}
+
+	/// Returns the hit and miss counts of the lookups so far.
+	pub fn metrics(&self) -> MemoizedDataMetrics {
+		self.counters.metrics(self.store.n_evictions())
 	}
 }
 
@@ -1,6 +1,6 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::{array, iter::repeat_with};
+use std::{array, iter::repeat_with, sync::Arc};
 
 use binius_fast_compute::arith_circuit::ArithCircuitPoly;
 use binius_field::{
@@ -21,11 +21,12 @@ use rand::{SeedableRng, rngs::StdRng};
 
 use crate::{
 	fiat_shamir::HasherChallenger,
-	oracle::{MultilinearOracleSet, ShiftVariant},
+	oracle::{MultilinearOracleSet, OracleId, ShiftVariant},
 	polynomial::MultivariatePoly,
 	protocols::evalcheck::{
 		EvalcheckHint, EvalcheckMultilinearClaim, EvalcheckProver, EvalcheckVerifier,
-		deserialize_evalcheck_proof, serialize_evalcheck_proof,
+		ExpansionCache, InMemoryMemoStore, MemoStore, deserialize_evalcheck_proof,
+		serialize_evalcheck_proof, subclaims::MemoizedData,
 	},
 	transcript::ProverTranscript,
 	transparent::select_row::SelectRow,
@@ -732,3 +733,97 @@ pub fn test_zero_padded_zero_vars() {
 		.verify(vec![zero_eval_claim, non_zero_eval_claim], &mut transcript)
 		.unwrap();
 }
+
+#[test]
+fn test_memoized_data_bounded_store() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let eval_points = repeat_with(|| {
+		repeat_with(|| <FExtension as Field>::random(&mut rng))
+			.take(4)
+			.collect::<Vec<_>>()
+	})
+	.take(3)
+	.collect::<Vec<_>>();
+
+	// Each expansion of 4 variables takes 16 scalars, so the store holds two of them.
+	let query_size = 16 * size_of::<FExtension>();
+	let store = InMemoryMemoStore::<PExtension>::with_max_size_bytes(2 * query_size);
+	let mut memoized_data = MemoizedData::with_store(store);
+
+	memoized_data
+		.memoize_query_par(eval_points.iter().map(Vec::as_slice))
+		.unwrap();
+	let metrics = memoized_data.metrics();
+	assert_eq!(metrics.evictions, 1);
+
+	// Lookups of evicted queries are recomputed, so every lookup returns the expansion.
+	for eval_point in &eval_points {
+		let query = memoized_data.full_query_readonly(eval_point);
+		assert_eq!(
+			query.expansion(),
+			MultilinearQuery::<PExtension>::expand(eval_point).expansion()
+		);
+	}
+	let metrics = memoized_data.metrics();
+	assert_eq!(metrics.query_hits, 2);
+	assert_eq!(metrics.query_misses, 1);
+	assert!((metrics.query_hit_rate() - 2.0 / 3.0).abs() < 1e-9);
+
+	let oracle_id = OracleId::from_index(0);
+	assert!(
+		memoized_data
+			.partial_eval(oracle_id, &eval_points[0])
+			.is_none()
+	);
+	assert_eq!(memoized_data.metrics().partial_eval_misses, 1);
+
+	let mut store = InMemoryMemoStore::<PExtension>::new();
+	let partial_eval = MLEDirectAdapter::from(
+		MultilinearExtension::new(2, vec![PExtension::default(); 4]).unwrap(),
+	)
+	.upcast_arc_dyn();
+	store.insert_partial_eval(oracle_id, &eval_points[0], partial_eval);
+	assert_eq!(store.size_bytes(), 4 * size_of::<PExtension>());
+	assert!(store.get_partial_eval(oracle_id, &eval_points[0]).is_some());
+	assert!(store.get_partial_eval(oracle_id, &eval_points[1]).is_none());
+}
+
+#[test]
+fn test_memoized_data_expansion_cache() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let eval_points = repeat_with(|| {
+		repeat_with(|| <FExtension as Field>::random(&mut rng))
+			.take(4)
+			.collect::<Vec<_>>()
+	})
+	.take(3)
+	.collect::<Vec<_>>();
+
+	let cache = ExpansionCache::<PExtension>::new();
+	let mut memoized_data = MemoizedData::new().with_expansion_cache(&cache);
+	memoized_data
+		.memoize_query_par(eval_points[..2].iter().map(Vec::as_slice))
+		.unwrap();
+	let query = memoized_data.full_query(&eval_points[2]).unwrap();
+	assert_eq!(cache.len(), 3);
+	assert!(Arc::ptr_eq(&query, &cache.get(&eval_points[2]).unwrap()));
+
+	// A later proof finds the expansions of the earlier one in the cache.
+	let mut memoized_data = MemoizedData::new().with_expansion_cache(&cache);
+	for eval_point in &eval_points {
+		let query = memoized_data.full_query(eval_point).unwrap();
+		assert!(Arc::ptr_eq(&query, &cache.get(eval_point).unwrap()));
+	}
+	assert_eq!(memoized_data.metrics().query_misses, 0);
+
+	let new_eval_point = vec![FExtension::ONE; 4];
+	let query = memoized_data.full_query_readonly(&new_eval_point);
+	assert_eq!(
+		query.expansion(),
+		MultilinearQuery::<PExtension>::expand(&new_eval_point).expansion()
+	);
+	assert_eq!(cache.len(), 4);
+
+	cache.clear();
+	assert!(cache.is_empty());
+}
@@ -1,14 +1,18 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::marker::PhantomData;
+use std::{iter, marker::PhantomData};
 
 use binius_field::{BinaryField, ExtensionField};
+use binius_macros::{DeserializeBytes, SerializeBytes};
 use binius_ntt::AdditiveNTT;
-use binius_utils::{bail, checked_arithmetics::log2_ceil_usize};
+use binius_utils::{
+	DeserializeBytes, SerializationError, SerializationMode, SerializeBytes, bail,
+	checked_arithmetics::log2_ceil_usize,
+};
 use getset::{CopyGetters, Getters};
 
 use crate::{
-	merkle_tree::MerkleTreeScheme, protocols::fri::Error,
+	fiat_shamir::CanSampleBits, merkle_tree::MerkleTreeScheme, protocols::fri::Error,
 	reed_solomon::reed_solomon::ReedSolomonCode,
 };
 
@@ -22,7 +26,10 @@ where
 	/// The Reed-Solomon code the verifier is testing proximity to.
 	#[getset(get = "pub")]
 	rs_code: ReedSolomonCode<FA>,
-	/// Vector commitment scheme for the codeword oracle.
+	/// The binary logarithm of the number of messages interleaved in the committed codeword.
+	///
+	/// Each symbol of the interleaved codeword holds one symbol of each of the `2^log_batch_size`
+	/// codewords, so the first oracle must fold at least this many variables.
 	#[getset(get_copy = "pub")]
 	log_batch_size: usize,
 	/// The reduction arities between each oracle sent to the verifier.
@@ -30,14 +37,57 @@ where
 	/// The number oracle consistency queries required during the query phase.
 	#[getset(get_copy = "pub")]
 	n_test_queries: usize,
+	/// The number of leading zero bits of the proof-of-work the prover finds before the query
+	/// phase.
+	#[getset(get_copy = "pub")]
+	grinding_bits: usize,
+	/// The soundness analysis that the number of test queries is calculated with.
+	#[getset(get_copy = "pub")]
+	soundness_model: SoundnessModel,
 	_marker: PhantomData<F>,
 }
 
+/// The version of the byte encoding of [`FRIParams`], which is written as its first byte.
+const FRI_PARAMS_ENCODING_VERSION: u8 = 1;
+
+/// The soundness analysis used to choose the number of FRI test queries for a security target.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, SerializeBytes, DeserializeBytes)]
+pub enum SoundnessModel {
+	/// The soundness error proven for the unique decoding regime, where each query fails to
+	/// detect a far codeword with probability `(1 + ρ) / 2` for the code rate `ρ`.
+	#[default]
+	Provable,
+	/// The soundness error under the conjecture that Reed–Solomon codes are list-decodable up to
+	/// capacity, where each query fails to detect a far codeword with probability `ρ`.
+	///
+	/// This needs substantially fewer queries, but its security rests on an unproven conjecture.
+	ConjecturedListDecoding,
+}
+
+impl SoundnessModel {
+	/// The probability that a single test query fails to detect a codeword that is far from the
+	/// code with binary logarithm of the inverse rate `log_inv_rate`.
+	pub(crate) fn per_query_err(self, log_inv_rate: usize) -> f64 {
+		let rate = 2.0f64.powi(-(log_inv_rate as i32));
+		match self {
+			Self::Provable => 0.5 * (1f64 + rate),
+			Self::ConjecturedListDecoding => rate,
+		}
+	}
+}
+
 impl<F, FA> FRIParams<F, FA>
 where
 	F: BinaryField + ExtensionField<FA>,
 	FA: BinaryField,
 {
+	/// Constructs parameters for an interleaved codeword of `2^log_batch_size` codewords of
+	/// `rs_code`.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidFoldAritySequence`] if the arities fold all variables of the message
+	/// * [`Error::FirstFoldArityTooSmall`] if the first arity is less than `log_batch_size`
 	pub fn new(
 		rs_code: ReedSolomonCode<FA>,
 		log_batch_size: usize,
@@ -47,16 +97,193 @@ where
 		if fold_arities.iter().sum::<usize>() >= rs_code.log_dim() + log_batch_size {
 			bail!(Error::InvalidFoldAritySequence)
 		}
+		if fold_arities
+			.first()
+			.is_some_and(|&arity| arity < log_batch_size)
+		{
+			bail!(Error::FirstFoldArityTooSmall);
+		}
 
 		Ok(Self {
 			rs_code,
 			log_batch_size,
 			fold_arities,
 			n_test_queries,
+			grinding_bits: 0,
+			soundness_model: SoundnessModel::Provable,
 			_marker: PhantomData,
 		})
 	}
 
+	/// Constructs parameters for an interleaved codeword over a subfield `FC` of `F`, folded with
+	/// challenges sampled from `F`.
+	///
+	/// An interleaved codeword over `FC` whose batch size is a multiple of the extension degree of
+	/// `F` over `FC` is also an interleaved codeword over `F`, each element of which packs that
+	/// many consecutive elements of `FC` as its coordinates. The protocol runs on the packed
+	/// codeword, so codewords can be committed over a small field while the soundness analysis,
+	/// and so the number of test queries for a security target, is that of the challenge field
+	/// `F`. Such codewords are committed with [`super::commit_interleaved_subfield`].
+	///
+	/// ## Arguments
+	///
+	/// * `log_batch_size` - the binary logarithm of the batch size of the codeword over `FC`.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if the batch size is less than the extension degree
+	pub fn new_for_subfield_codeword<FC>(
+		rs_code: ReedSolomonCode<FA>,
+		log_batch_size: usize,
+		fold_arities: Vec<usize>,
+		n_test_queries: usize,
+	) -> Result<Self, Error>
+	where
+		F: ExtensionField<FC>,
+		FC: BinaryField,
+	{
+		let log_degree = <F as ExtensionField<FC>>::LOG_DEGREE;
+		let Some(log_packed_batch_size) = log_batch_size.checked_sub(log_degree) else {
+			bail!(Error::InvalidArgs(format!(
+				"the log batch size {log_batch_size} over the codeword field is less than the log \
+				extension degree {log_degree} of the challenge field"
+			)));
+		};
+		Self::new(rs_code, log_packed_batch_size, fold_arities, n_test_queries)
+	}
+
+	/// Adds a proof-of-work grinding phase before the query phase, recalculating the number of
+	/// test queries for the security target.
+	///
+	/// Each bit of grinding doubles the expected work of the prover in the grinding phase, and
+	/// reduces the soundness error that the queries must achieve by a factor of two.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if `grinding_bits` exceeds 32 or `security_bits`
+	/// * [`Error::ParameterError`] if the security target cannot be met with the code
+	pub fn with_grinding(self, security_bits: usize, grinding_bits: usize) -> Result<Self, Error> {
+		if grinding_bits > (u32::BITS as usize).min(security_bits) {
+			bail!(Error::InvalidArgs(format!(
+				"grinding_bits {grinding_bits} exceeds the maximum of 32 or the security target \
+				{security_bits}"
+			)));
+		}
+
+		let n_test_queries = calculate_n_test_queries_with_soundness_model::<F, _>(
+			security_bits,
+			grinding_bits,
+			self.soundness_model,
+			&self.rs_code,
+		)?;
+		Ok(Self {
+			n_test_queries,
+			grinding_bits,
+			..self
+		})
+	}
+
+	/// Overrides the number of test queries, bypassing the soundness analysis.
+	///
+	/// The parameters otherwise size the number of test queries for a security target. This sets it
+	/// to `n_test_queries` regardless, for benchmarks and soundness experiments at non-standard
+	/// query counts. Proofs with fewer queries than the soundness analysis calls for do not achieve
+	/// the security target, see [`super::security_report`] for the security they do achieve. Any
+	/// later change that recalculates the number of test queries, such as [`Self::with_grinding`],
+	/// replaces the override.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if `n_test_queries` is zero, as a proof without queries checks
+	///   nothing about the codeword
+	pub fn unsafe_override_n_test_queries(self, n_test_queries: usize) -> Result<Self, Error> {
+		if n_test_queries == 0 {
+			bail!(Error::InvalidArgs("the number of test queries must be positive".to_string()));
+		}
+		Ok(Self {
+			n_test_queries,
+			..self
+		})
+	}
+
+	/// Changes the soundness analysis, recalculating the number of test queries for the security
+	/// target.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ParameterError`] if the security target cannot be met with the code
+	pub fn with_soundness_model(
+		self,
+		security_bits: usize,
+		soundness_model: SoundnessModel,
+	) -> Result<Self, Error> {
+		let n_test_queries = calculate_n_test_queries_with_soundness_model::<F, _>(
+			security_bits,
+			self.grinding_bits,
+			soundness_model,
+			&self.rs_code,
+		)?;
+		Ok(Self {
+			n_test_queries,
+			soundness_model,
+			..self
+		})
+	}
+
+	/// Changes the interleaved batch size, recalculating the number of test queries for the
+	/// security target.
+	///
+	/// The length of the message and of the interleaved codeword are kept, as is the folding
+	/// schedule. The message is split into `2^log_batch_size` messages of a Reed–Solomon code of
+	/// correspondingly smaller dimension, taken from the subspaces of `ntt`. A larger batch size
+	/// shortens the code, which is cheaper to encode and needs slightly fewer queries, but requires
+	/// larger leaves, and so a shallower Merkle tree, for the first oracle.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if `log_batch_size` exceeds the binary logarithm of the message
+	///   length
+	/// * [`Error::FirstFoldArityTooSmall`] if the first arity is less than `log_batch_size`
+	/// * [`Error::ParameterError`] if the security target cannot be met with the code
+	pub fn with_log_batch_size(
+		self,
+		ntt: &impl AdditiveNTT<FA>,
+		security_bits: usize,
+		log_batch_size: usize,
+	) -> Result<Self, Error> {
+		let log_msg_len = self.rs_code.log_dim() + self.log_batch_size;
+		if log_batch_size > log_msg_len {
+			bail!(Error::InvalidArgs(format!(
+				"log_batch_size {log_batch_size} exceeds the log message length {log_msg_len}"
+			)));
+		}
+		if self
+			.fold_arities
+			.first()
+			.is_some_and(|&arity| arity < log_batch_size)
+		{
+			bail!(Error::FirstFoldArityTooSmall);
+		}
+
+		let rs_code = ReedSolomonCode::with_ntt_subspace(
+			ntt,
+			log_msg_len - log_batch_size,
+			self.rs_code.log_inv_rate(),
+		)?;
+		let n_test_queries = calculate_n_test_queries_with_soundness_model::<F, _>(
+			security_bits,
+			self.grinding_bits,
+			self.soundness_model,
+			&rs_code,
+		)?;
+		Ok(Self {
+			rs_code,
+			log_batch_size,
+			n_test_queries,
+			..self
+		})
+	}
+
 	/// Choose commit parameters based on protocol parameters, using a constant fold arity.
 	///
 	/// ## Arguments
@@ -65,19 +292,26 @@ where
 	/// * `security_bits` - the target security level in bits.
 	/// * `log_inv_rate` - the binary logarithm of the inverse Reed–Solomon code rate.
 	/// * `arity` - the folding arity.
+	/// * `soundness_model` - the soundness analysis that sizes the number of test queries.
 	pub fn choose_with_constant_fold_arity(
 		ntt: &impl AdditiveNTT<FA>,
 		log_msg_len: usize,
 		security_bits: usize,
 		log_inv_rate: usize,
 		arity: usize,
+		soundness_model: SoundnessModel,
 	) -> Result<Self, Error> {
 		assert!(arity > 0);
 
 		let log_dim = log_msg_len.saturating_sub(arity);
 		let log_batch_size = log_msg_len.min(arity);
 		let rs_code = ReedSolomonCode::with_ntt_subspace(ntt, log_dim, log_inv_rate)?;
-		let n_test_queries = calculate_n_test_queries::<F, _>(security_bits, &rs_code)?;
+		let n_test_queries = calculate_n_test_queries_with_soundness_model::<F, _>(
+			security_bits,
+			0,
+			soundness_model,
+			&rs_code,
+		)?;
 
 		let cap_height = log2_ceil_usize(n_test_queries);
 		let fold_arities = std::iter::repeat_n(
@@ -133,7 +367,11 @@ where
 		// keep it there, even if we post-facto find out that `fold_arities = []`. the cost of
 		// this is that the prover has to do a nontrivial (though small!) interleaved encoding, as
 		// opposed to a trivial one.
-		Self::new(rs_code, log_batch_size, fold_arities, n_test_queries)
+		let params = Self::new(rs_code, log_batch_size, fold_arities, n_test_queries)?;
+		Ok(Self {
+			soundness_model,
+			..params
+		})
 	}
 
 	pub const fn n_fold_rounds(&self) -> usize {
@@ -168,6 +406,232 @@ where
 	pub fn log_len(&self) -> usize {
 		self.rs_code().log_len() + self.log_batch_size()
 	}
+
+	/// Estimates the size in bytes of a proof with these parameters, see [`estimate_proof_size`].
+	///
+	/// The estimate is for the interleaved codeword, whose length includes the batch.
+	pub fn estimate_proof_size(&self, digest_size: usize) -> usize {
+		estimate_proof_size(
+			self.log_len(),
+			&self.fold_arities,
+			self.n_test_queries,
+			digest_size,
+			size_of::<F>(),
+		)
+	}
+
+	/// The binary logarithm of the length of the terminal codeword, which the prover sends in the
+	/// clear after the last fold round with a commitment.
+	pub fn log_terminal_len(&self) -> usize {
+		self.log_len() - self.fold_arities.iter().sum::<usize>()
+	}
+
+	/// Changes the folding schedule to stop folding when the codeword has length
+	/// `2^log_terminal_len`.
+	///
+	/// The arities of the schedule are kept up to the round that reaches the terminal length, and
+	/// the arity of that round is reduced so that it lands on it exactly. If the schedule
+	/// terminates with a longer codeword, it is extended by repeating its last arity. Terminating
+	/// early trades the commitments and query openings of the last oracles for a longer terminal
+	/// codeword, which pays off for small instances.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if `log_terminal_len` is not in the range `log_inv_rate + 1` to
+	///   [`Self::log_len`]
+	/// * [`Error::FirstFoldArityTooSmall`] if the first arity becomes less than the log batch size
+	pub fn with_log_terminal_len(self, log_terminal_len: usize) -> Result<Self, Error> {
+		let log_inv_rate = self.rs_code.log_inv_rate();
+		if log_terminal_len <= log_inv_rate || log_terminal_len > self.log_len() {
+			bail!(Error::InvalidArgs(format!(
+				"log_terminal_len {log_terminal_len} must be greater than log_inv_rate \
+				{log_inv_rate} and at most log_len {}",
+				self.log_len()
+			)));
+		}
+
+		let last_arity = self.fold_arities.last().copied().unwrap_or(1);
+		let mut log_len = self.log_len();
+		let mut fold_arities = Vec::with_capacity(self.fold_arities.len());
+		for arity in self
+			.fold_arities
+			.iter()
+			.copied()
+			.chain(iter::repeat(last_arity))
+		{
+			if log_len == log_terminal_len {
+				break;
+			}
+			let arity = arity.min(log_len - log_terminal_len);
+			fold_arities.push(arity);
+			log_len -= arity;
+		}
+
+		if fold_arities
+			.first()
+			.is_some_and(|&arity| arity < self.log_batch_size)
+		{
+			bail!(Error::FirstFoldArityTooSmall);
+		}
+		Ok(Self {
+			fold_arities,
+			..self
+		})
+	}
+
+	/// Constructs the parameters of a shorter codeword that is batched into the FRI instance of
+	/// these parameters after `fold_round` fold rounds, see
+	/// [`super::FRIFolder::with_batched_at_round`].
+	///
+	/// The codeword encodes a message with the variables left to fold after `fold_round` rounds,
+	/// with the same rate and on the evaluation domain of the round oracle committed after that
+	/// round, taken from the subspaces of `ntt`. It is not interleaved, is folded with the arities
+	/// of the remaining oracles, and is opened with the same number of test queries.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if no round oracle other than the last is committed after
+	///   `fold_round` fold rounds
+	pub fn batched_codeword_params(
+		&self,
+		ntt: &impl AdditiveNTT<FA>,
+		fold_round: usize,
+	) -> Result<Self, Error> {
+		let oracle = self.batched_oracle(fold_round)?;
+		let rs_code = ReedSolomonCode::with_ntt_subspace(
+			ntt,
+			self.n_fold_rounds() - fold_round,
+			self.rs_code.log_inv_rate(),
+		)?;
+		let params =
+			Self::new(rs_code, 0, self.fold_arities[oracle + 1..].to_vec(), self.n_test_queries)?;
+		Ok(Self {
+			grinding_bits: self.grinding_bits,
+			soundness_model: self.soundness_model,
+			..params
+		})
+	}
+
+	/// The index of the round oracle that a codeword batched after `fold_round` fold rounds is
+	/// added to.
+	///
+	/// The codeword is opened alongside the oracle, so the oracle must not be the last, which is
+	/// sent in the clear.
+	pub(super) fn batched_oracle(&self, fold_round: usize) -> Result<usize, Error> {
+		self.fold_arities
+			.iter()
+			.scan(0, |n_folded, &arity| {
+				*n_folded += arity;
+				Some(*n_folded)
+			})
+			.take(self.n_oracles().saturating_sub(1))
+			.position(|n_folded| n_folded == fold_round)
+			.ok_or_else(|| {
+				Error::InvalidArgs(format!(
+					"no round oracle before the last is committed after {fold_round} fold rounds"
+				))
+			})
+	}
+}
+
+/// Encodes the parameters so that a verifier can load them with [`DeserializeBytes`].
+///
+/// The encoding starts with a version byte, followed by the Reed–Solomon code, including the
+/// basis of its evaluation domain, and the remaining parameters.
+impl<F, FA> SerializeBytes for FRIParams<F, FA>
+where
+	F: BinaryField,
+	FA: BinaryField,
+{
+	fn serialize(
+		&self,
+		mut write_buf: impl bytes::BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		SerializeBytes::serialize(&FRI_PARAMS_ENCODING_VERSION, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.rs_code, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.log_batch_size, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.fold_arities, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.n_test_queries, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.grinding_bits, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.soundness_model, write_buf, mode)
+	}
+}
+
+/// Decodes parameters encoded with [`SerializeBytes`].
+///
+/// The decoded parameters are validated as by the constructors, so that parameters that could
+/// not have been constructed are rejected on load.
+impl<F, FA> DeserializeBytes for FRIParams<F, FA>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+{
+	fn deserialize(
+		mut read_buf: impl bytes::Buf,
+		mode: SerializationMode,
+	) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let version = u8::deserialize(&mut read_buf, mode)?;
+		if version != FRI_PARAMS_ENCODING_VERSION {
+			return Err(SerializationError::UnsupportedVersion {
+				name: "FRIParams",
+				version,
+			});
+		}
+
+		let rs_code = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let log_batch_size = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let fold_arities = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let n_test_queries = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let grinding_bits: usize = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let soundness_model = DeserializeBytes::deserialize(read_buf, mode)?;
+
+		let invalid = SerializationError::InvalidConstruction { name: "FRIParams" };
+		if grinding_bits > u32::BITS as usize {
+			return Err(invalid);
+		}
+		let params = Self::new(rs_code, log_batch_size, fold_arities, n_test_queries)
+			.map_err(|_| invalid)?;
+		Ok(Self {
+			grinding_bits,
+			soundness_model,
+			..params
+		})
+	}
+}
+
+impl<F, FA> serde::Serialize for FRIParams<F, FA>
+where
+	F: BinaryField,
+	FA: BinaryField,
+{
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut buf = Vec::new();
+		SerializeBytes::serialize(self, &mut buf, SerializationMode::CanonicalTower)
+			.map_err(serde::ser::Error::custom)?;
+		serializer.serialize_bytes(&buf)
+	}
+}
+
+impl<'de, F, FA> serde::Deserialize<'de> for FRIParams<F, FA>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+{
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let buf = <Vec<u8> as serde::Deserialize>::deserialize(deserializer)?;
+		let mut read_buf = buf.as_slice();
+		let params =
+			DeserializeBytes::deserialize(&mut read_buf, SerializationMode::CanonicalTower)
+				.map_err(serde::de::Error::custom)?;
+		if !read_buf.is_empty() {
+			return Err(serde::de::Error::invalid_length(buf.len(), &"an encoding of FRIParams"));
+		}
+		Ok(params)
+	}
 }
 
 /// This layer allows minimizing the proof size.
@@ -189,6 +653,27 @@ where
 		})
 }
 
+/// Samples the indices of the test queries of the FRI query phase.
+///
+/// The returned indices are sorted in increasing order and deduplicated, so that the prover opens
+/// each index once and the openings of neighbouring queries are adjacent. Dropping a repeated
+/// index does not affect soundness, since the repeated query performs the exact same checks.
+pub fn sample_query_indices<F, FA>(
+	params: &FRIParams<F, FA>,
+	sampler: &mut impl CanSampleBits<u32>,
+) -> Vec<usize>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+{
+	let mut indices = (0..params.n_test_queries())
+		.map(|_| sampler.sample_bits(params.index_bits()) as usize)
+		.collect::<Vec<_>>();
+	indices.sort_unstable();
+	indices.dedup();
+	indices
+}
+
 /// The type of the termination round codeword in the FRI protocol.
 pub type TerminateCodeword<F> = Vec<F>;
 
@@ -200,6 +685,50 @@ pub fn calculate_n_test_queries<F, FEncode>(
 	security_bits: usize,
 	code: &ReedSolomonCode<FEncode>,
 ) -> Result<usize, Error>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	calculate_n_test_queries_with_grinding::<F, _>(security_bits, 0, code)
+}
+
+/// Calculates the number of test queries required to achieve a target security level when the
+/// queries follow a proof-of-work with `grinding_bits` leading zero bits.
+///
+/// A malicious prover must redo the proof-of-work for every attempt at sampling favourable
+/// queries, so the queries only need to achieve a soundness error `2^grinding_bits` times larger.
+///
+/// Throws [`Error::ParameterError`] if the security level is unattainable given the code
+/// parameters.
+pub fn calculate_n_test_queries_with_grinding<F, FEncode>(
+	security_bits: usize,
+	grinding_bits: usize,
+	code: &ReedSolomonCode<FEncode>,
+) -> Result<usize, Error>
+where
+	F: BinaryField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+{
+	calculate_n_test_queries_with_soundness_model::<F, _>(
+		security_bits,
+		grinding_bits,
+		SoundnessModel::Provable,
+		code,
+	)
+}
+
+/// Calculates the number of test queries required to achieve a target security level under the
+/// given soundness analysis, when the queries follow a proof-of-work with `grinding_bits` leading
+/// zero bits.
+///
+/// Throws [`Error::ParameterError`] if the security level is unattainable given the code
+/// parameters.
+pub fn calculate_n_test_queries_with_soundness_model<F, FEncode>(
+	security_bits: usize,
+	grinding_bits: usize,
+	soundness_model: SoundnessModel,
+	code: &ReedSolomonCode<FEncode>,
+) -> Result<usize, Error>
 where
 	F: BinaryField + ExtensionField<FEncode>,
 	FEncode: BinaryField,
@@ -209,11 +738,15 @@ where
 	// 2 ⋅ ℓ' / |T_{τ}|
 	let folding_err = code.len() as f64 / field_size;
 	// 2^{ℓ' + R} / |T_{τ}|
-	let per_query_err = 0.5 * (1f64 + 2.0f64.powi(-(code.log_inv_rate() as i32)));
-	let allowed_query_err = 2.0_f64.powi(-(security_bits as i32)) - sumcheck_err - folding_err;
-	if allowed_query_err <= 0.0 {
+	let per_query_err = soundness_model.per_query_err(code.log_inv_rate());
+	if per_query_err >= 1.0 {
 		return Err(Error::ParameterError);
 	}
+	let allowed_err = 2.0_f64.powi(-(security_bits as i32)) - sumcheck_err - folding_err;
+	if allowed_err <= 0.0 {
+		return Err(Error::ParameterError);
+	}
+	let allowed_query_err = (allowed_err * 2.0_f64.powi(grinding_bits as i32)).min(1.0);
 	let n_queries = allowed_query_err.log(per_query_err).ceil() as usize;
 	Ok(n_queries)
 }
@@ -251,10 +784,100 @@ pub fn estimate_optimal_arity(
 		.unwrap_or(1)
 }
 
+/// Estimates the size in bytes of a FRI proof with a given folding schedule.
+///
+/// `log_block_length` is the binary logarithm of the block length of the initial interleaved
+/// codeword, and `digest_size` and `field_size` are in bytes. The estimate counts, for every
+/// committed oracle, the opened coset and Merkle path of each query and the decommitted Merkle
+/// layer, and the terminal codeword. It ignores the Merkle path nodes shared between queries.
+pub fn estimate_proof_size(
+	log_block_length: usize,
+	fold_arities: &[usize],
+	n_test_queries: usize,
+	digest_size: usize,
+	field_size: usize,
+) -> usize {
+	let mut log_len = log_block_length;
+	let mut size = 0;
+	for &arity in fold_arities {
+		log_len -= arity;
+		size += oracle_proof_size(log_len, arity, n_test_queries, digest_size, field_size);
+	}
+	size + (field_size << log_len)
+}
+
+/// The size of the openings of a committed oracle with `2^log_n_cosets` cosets of `2^arity`
+/// values each.
+fn oracle_proof_size(
+	log_n_cosets: usize,
+	arity: usize,
+	n_test_queries: usize,
+	digest_size: usize,
+	field_size: usize,
+) -> usize {
+	let layer_depth = log2_ceil_usize(n_test_queries).min(log_n_cosets);
+	let path_len = log_n_cosets - layer_depth;
+	n_test_queries * ((field_size << arity) + path_len * digest_size) + (digest_size << layer_depth)
+}
+
+/// Chooses the FRI folding schedule that minimizes the estimated proof size.
+///
+/// Unlike [`estimate_optimal_arity`], the arity may differ between rounds: the early oracles are
+/// long, so folding them by more variables saves Merkle path nodes, while the late oracles are
+/// short, and smaller cosets save field elements. The schedule is found by dynamic programming
+/// over the length of the oracle, under the proof size model of [`estimate_proof_size`].
+///
+/// ## Arguments
+///
+/// * `log_block_length` - the binary logarithm of the block length of the initial codeword.
+/// * `log_inv_rate` - the binary logarithm of the inverse Reed–Solomon code rate. The terminal
+///   codeword is always longer than `2^log_inv_rate`.
+/// * `n_test_queries` - the number of test queries in the query phase.
+/// * `digest_size` - the size of a Merkle tree digest in bytes.
+/// * `field_size` - the size of a field element in bytes.
+pub fn estimate_optimal_arity_schedule(
+	log_block_length: usize,
+	log_inv_rate: usize,
+	n_test_queries: usize,
+	digest_size: usize,
+	field_size: usize,
+) -> Vec<usize> {
+	let min_log_len = log_inv_rate + 1;
+	if log_block_length <= min_log_len {
+		return Vec::new();
+	}
+
+	// best[log_len - min_log_len] is the least proof size from an oracle of length 2^log_len,
+	// with the arity of its fold, or None if it is sent in the clear.
+	let mut best: Vec<(usize, Option<usize>)> = Vec::with_capacity(log_block_length);
+	for log_len in min_log_len..=log_block_length {
+		let terminal = (field_size << log_len, None);
+		let entry = (1..=log_len - min_log_len)
+			.map(|arity| {
+				let log_n_cosets = log_len - arity;
+				let size =
+					oracle_proof_size(log_n_cosets, arity, n_test_queries, digest_size, field_size)
+						+ best[log_n_cosets - min_log_len].0;
+				(size, Some(arity))
+			})
+			.fold(terminal, |best, entry| if entry.0 < best.0 { entry } else { best });
+		best.push(entry);
+	}
+
+	let mut fold_arities = Vec::new();
+	let mut log_len = log_block_length;
+	while let (_, Some(arity)) = best[log_len - min_log_len] {
+		fold_arities.push(arity);
+		log_len -= arity;
+	}
+	fold_arities
+}
+
 #[cfg(test)]
 mod tests {
 	use assert_matches::assert_matches;
-	use binius_field::{BinaryField32b, BinaryField128b};
+	use binius_field::{BinaryField16b, BinaryField32b, BinaryField128b};
+	use binius_ntt::SingleThreadedNTT;
 
 	use super::*;
 
@@ -274,6 +897,269 @@ mod tests {
 		assert_eq!(n_test_queries, 143);
 	}
 
+	#[test]
+	fn test_calculate_n_test_queries_with_grinding() {
+		let security_bits = 96;
+		let rs_code = ReedSolomonCode::new(28, 1).unwrap();
+		let n_test_queries = calculate_n_test_queries_with_grinding::<
+			BinaryField128b,
+			BinaryField32b,
+		>(security_bits, 0, &rs_code)
+		.unwrap();
+		assert_eq!(n_test_queries, 232);
+
+		// At rate 1/2, each query has a soundness error of 3/4, so 16 bits of grinding save about
+		// 16 / log2(4/3) ≈ 38.5 queries.
+		let n_test_queries = calculate_n_test_queries_with_grinding::<
+			BinaryField128b,
+			BinaryField32b,
+		>(security_bits, 16, &rs_code)
+		.unwrap();
+		assert_eq!(n_test_queries, 194);
+	}
+
+	#[test]
+	fn test_calculate_n_test_queries_conjectured() {
+		let security_bits = 96;
+		let n_test_queries = |log_inv_rate| {
+			let rs_code = ReedSolomonCode::new(28, log_inv_rate).unwrap();
+			calculate_n_test_queries_with_soundness_model::<BinaryField128b, BinaryField32b>(
+				security_bits,
+				0,
+				SoundnessModel::ConjecturedListDecoding,
+				&rs_code,
+			)
+		};
+		assert_eq!(n_test_queries(1).unwrap(), 97);
+		assert_eq!(n_test_queries(2).unwrap(), 49);
+		assert_matches!(n_test_queries(0), Err(Error::ParameterError));
+	}
+
+	#[test]
+	fn test_with_soundness_model() {
+		let rs_code = ReedSolomonCode::<BinaryField32b>::new(28, 1).unwrap();
+		let params = FRIParams::<BinaryField128b, _>::new(rs_code, 0, vec![4; 6], 232).unwrap();
+		assert_eq!(params.soundness_model(), SoundnessModel::Provable);
+
+		let params = params
+			.with_soundness_model(96, SoundnessModel::ConjecturedListDecoding)
+			.unwrap();
+		assert_eq!(params.soundness_model(), SoundnessModel::ConjecturedListDecoding);
+		assert_eq!(params.n_test_queries(), 97);
+
+		// Grinding keeps the soundness model.
+		let params = params.with_grinding(96, 16).unwrap();
+		assert_eq!(params.n_test_queries(), 81);
+	}
+
+	#[test]
+	fn test_with_grinding() {
+		let rs_code = ReedSolomonCode::<BinaryField32b>::new(28, 1).unwrap();
+		let params = FRIParams::<BinaryField128b, _>::new(rs_code, 0, vec![4; 6], 232).unwrap();
+		assert_eq!(params.grinding_bits(), 0);
+
+		let params = params.with_grinding(96, 16).unwrap();
+		assert_eq!(params.grinding_bits(), 16);
+		assert_eq!(params.n_test_queries(), 194);
+
+		assert_matches!(params.with_grinding(96, 33), Err(Error::InvalidArgs(_)));
+	}
+
+	#[test]
+	fn test_subfield_codeword_uses_challenge_field_soundness() {
+		let rs_code = ReedSolomonCode::<BinaryField16b>::new(16, 1).unwrap();
+
+		// Challenges sampled from the codeword field itself cannot reach the security target.
+		assert_matches!(
+			calculate_n_test_queries::<BinaryField32b, _>(96, &rs_code),
+			Err(Error::ParameterError)
+		);
+
+		let n_test_queries = calculate_n_test_queries::<BinaryField128b, _>(96, &rs_code).unwrap();
+		let params = FRIParams::<BinaryField128b, _>::new_for_subfield_codeword::<BinaryField32b>(
+			rs_code,
+			3,
+			vec![2, 2],
+			n_test_queries,
+		)
+		.unwrap();
+		assert_eq!(params.log_batch_size(), 1);
+
+		assert_matches!(
+			FRIParams::<BinaryField128b, _>::new_for_subfield_codeword::<BinaryField32b>(
+				ReedSolomonCode::<BinaryField16b>::new(16, 1).unwrap(),
+				1,
+				vec![2, 2],
+				n_test_queries,
+			),
+			Err(Error::InvalidArgs(_))
+		);
+	}
+
+	#[test]
+	fn test_sample_query_indices_sorted_and_deduplicated() {
+		struct FixedSampler(std::vec::IntoIter<u32>);
+
+		impl CanSampleBits<u32> for FixedSampler {
+			fn sample_bits(&mut self, bits: usize) -> u32 {
+				self.0.next().unwrap() & ((1 << bits) - 1)
+			}
+		}
+
+		let rs_code = ReedSolomonCode::<BinaryField32b>::new(4, 1).unwrap();
+		let params = FRIParams::<BinaryField128b, _>::new(rs_code, 0, vec![1, 1], 6).unwrap();
+		assert_eq!(params.index_bits(), 4);
+
+		let mut sampler = FixedSampler(vec![9, 3, 25, 0, 3, 14].into_iter());
+		assert_eq!(sample_query_indices(&params, &mut sampler), vec![0, 3, 9, 14]);
+		assert!(sampler.0.next().is_none());
+	}
+
+	#[test]
+	fn test_with_log_terminal_len() {
+		let params = || {
+			let rs_code = ReedSolomonCode::<BinaryField32b>::new(16, 1).unwrap();
+			FRIParams::<BinaryField128b, _>::new(rs_code, 2, vec![4, 4, 4], 16).unwrap()
+		};
+		assert_eq!(params().log_terminal_len(), 7);
+
+		// Terminating early cuts the last fold short.
+		let early = params().with_log_terminal_len(10).unwrap();
+		assert_eq!(early.fold_arities(), &[4, 4, 1]);
+		assert_eq!(early.log_terminal_len(), 10);
+		assert_eq!(early.n_final_challenges(), 9);
+
+		// Terminating late repeats the last arity.
+		let late = params().with_log_terminal_len(2).unwrap();
+		assert_eq!(late.fold_arities(), &[4, 4, 4, 4, 1]);
+
+		let unfolded = params().with_log_terminal_len(19).unwrap();
+		assert!(unfolded.fold_arities().is_empty());
+
+		assert_matches!(params().with_log_terminal_len(1), Err(Error::InvalidArgs(_)));
+		assert_matches!(params().with_log_terminal_len(20), Err(Error::InvalidArgs(_)));
+		assert_matches!(params().with_log_terminal_len(18), Err(Error::FirstFoldArityTooSmall));
+	}
+
+	#[test]
+	fn test_with_log_batch_size() {
+		let ntt = SingleThreadedNTT::<BinaryField32b>::new(32).unwrap();
+		let params = || {
+			let rs_code = ReedSolomonCode::with_ntt_subspace(&ntt, 20, 1).unwrap();
+			FRIParams::<BinaryField128b, _>::new(rs_code, 0, vec![4, 4, 4], 0)
+				.unwrap()
+				.with_grinding(96, 0)
+				.unwrap()
+		};
+
+		let batched = params().with_log_batch_size(&ntt, 96, 4).unwrap();
+		assert_eq!(batched.log_batch_size(), 4);
+		assert_eq!(batched.rs_code().log_dim(), 16);
+		assert_eq!(batched.log_len(), params().log_len());
+		assert_eq!(batched.fold_arities(), params().fold_arities());
+		assert!(batched.n_test_queries() <= params().n_test_queries());
+		assert_eq!(
+			batched.estimate_proof_size(32),
+			estimate_proof_size(21, &[4, 4, 4], batched.n_test_queries(), 32, 16)
+		);
+
+		assert_matches!(
+			FRIParams::<BinaryField128b, _>::new(
+				ReedSolomonCode::<BinaryField32b>::new(16, 1).unwrap(),
+				5,
+				vec![4, 4],
+				16
+			),
+			Err(Error::FirstFoldArityTooSmall)
+		);
+		assert_matches!(
+			params().with_log_batch_size(&ntt, 96, 5),
+			Err(Error::FirstFoldArityTooSmall)
+		);
+		assert_matches!(params().with_log_batch_size(&ntt, 96, 21), Err(Error::InvalidArgs(_)));
+	}
+
+	#[test]
+	fn test_unsafe_override_n_test_queries() {
+		let params = || {
+			let rs_code = ReedSolomonCode::<BinaryField32b>::new(16, 1).unwrap();
+			FRIParams::<BinaryField128b, _>::new(rs_code, 0, vec![4; 3], 0)
+				.unwrap()
+				.with_grinding(96, 0)
+				.unwrap()
+		};
+		let n_test_queries = params().n_test_queries();
+
+		assert_matches!(params().unsafe_override_n_test_queries(0), Err(Error::InvalidArgs(_)));
+		let params = params().unsafe_override_n_test_queries(8).unwrap();
+		assert_eq!(params.n_test_queries(), 8);
+		assert_eq!(params.fold_arities(), &[4; 3]);
+
+		// Recalculating the number of test queries replaces the override.
+		let params = params.with_grinding(96, 0).unwrap();
+		assert_eq!(params.n_test_queries(), n_test_queries);
+	}
+
+	#[test]
+	fn test_params_serialization_round_trip() {
+		let ntt = SingleThreadedNTT::<BinaryField32b>::new(24).unwrap();
+		let params = FRIParams::<BinaryField128b, BinaryField32b>::choose_with_constant_fold_arity(
+			&ntt,
+			20,
+			96,
+			1,
+			4,
+			SoundnessModel::ConjecturedListDecoding,
+		)
+		.unwrap()
+		.with_grinding(96, 16)
+		.unwrap();
+
+		let mode = SerializationMode::CanonicalTower;
+		let mut buf = Vec::new();
+		params.serialize(&mut buf, mode).unwrap();
+		let loaded =
+			FRIParams::<BinaryField128b, BinaryField32b>::deserialize(buf.as_slice(), mode)
+				.unwrap();
+		assert_eq!(loaded.rs_code().subspace(), params.rs_code().subspace());
+		assert_eq!(loaded.rs_code().log_dim(), params.rs_code().log_dim());
+		assert_eq!(loaded.log_batch_size(), params.log_batch_size());
+		assert_eq!(loaded.fold_arities(), params.fold_arities());
+		assert_eq!(loaded.n_test_queries(), params.n_test_queries());
+		assert_eq!(loaded.grinding_bits(), params.grinding_bits());
+		assert_eq!(loaded.soundness_model(), params.soundness_model());
+
+		let json = serde_json::to_string(&params).unwrap();
+		let loaded: FRIParams<BinaryField128b, BinaryField32b> =
+			serde_json::from_str(&json).unwrap();
+		let mut loaded_buf = Vec::new();
+		loaded.serialize(&mut loaded_buf, mode).unwrap();
+		assert_eq!(loaded_buf, buf);
+
+		// An unknown version is rejected.
+		let mut other_version = buf.clone();
+		other_version[0] = FRI_PARAMS_ENCODING_VERSION + 1;
+		assert_matches!(
+			FRIParams::<BinaryField128b, BinaryField32b>::deserialize(
+				other_version.as_slice(),
+				mode
+			),
+			Err(SerializationError::UnsupportedVersion { .. })
+		);
+
+		// Parameters that fold all variables of the message are rejected.
+		let invalid = FRIParams::<BinaryField128b, BinaryField32b> {
+			fold_arities: vec![4; 6],
+			..params
+		};
+		let mut invalid_buf = Vec::new();
+		invalid.serialize(&mut invalid_buf, mode).unwrap();
+		assert_matches!(
+			FRIParams::<BinaryField128b, BinaryField32b>::deserialize(invalid_buf.as_slice(), mode),
+			Err(SerializationError::InvalidConstruction { .. })
+		);
+	}
+
 	#[test]
 	fn test_calculate_n_test_queries_unsatisfiable() {
 		let security_bits = 128;
@@ -297,4 +1183,48 @@ mod tests {
 			assert_eq!(estimate_optimal_arity(log_block_length, digest_size, field_size), 6);
 		}
 	}
+
+	#[test]
+	fn test_estimate_optimal_arity_schedule() {
+		let (log_inv_rate, n_test_queries, digest_size, field_size) = (1, 232, 32, 16);
+		for log_block_length in [12, 20, 29] {
+			let schedule = estimate_optimal_arity_schedule(
+				log_block_length,
+				log_inv_rate,
+				n_test_queries,
+				digest_size,
+				field_size,
+			);
+			assert!(schedule.iter().all(|&arity| arity > 0));
+			assert!(schedule.iter().sum::<usize>() < log_block_length - log_inv_rate);
+
+			// The schedule is at least as good as every constant-arity schedule.
+			let size = estimate_proof_size(
+				log_block_length,
+				&schedule,
+				n_test_queries,
+				digest_size,
+				field_size,
+			);
+			for arity in 1..log_block_length - log_inv_rate {
+				let n_folds = (log_block_length - log_inv_rate - 1) / arity;
+				let constant_schedule = vec![arity; n_folds];
+				assert!(
+					size <= estimate_proof_size(
+						log_block_length,
+						&constant_schedule,
+						n_test_queries,
+						digest_size,
+						field_size,
+					)
+				);
+			}
+		}
+
+		// Codewords too short to fold are sent in the clear.
+		assert!(
+			estimate_optimal_arity_schedule(2, 1, n_test_queries, digest_size, field_size)
+				.is_empty()
+		);
+	}
 }
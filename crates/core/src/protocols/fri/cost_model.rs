@@ -0,0 +1,353 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{hint::black_box, iter, time::Instant};
+
+use binius_field::{BinaryField, ExtensionField, util::powers};
+use binius_ntt::{AdditiveNTT, NTTShape, SingleThreadedNTT};
+
+use super::{Error, FRIParams, vcs_optimal_layers_depths_iter};
+use crate::merkle_tree::{MerkleTreeProver, MerkleTreeScheme};
+
+/// Binary logarithm of the number of field elements processed by each calibration benchmark.
+const LOG_CALIBRATION_SIZE: usize = 12;
+
+/// Batch size of the wide-leaf Merkle commitment in the hashing calibration benchmark.
+const CALIBRATION_BATCH_SIZE: usize = 16;
+
+/// Machine-dependent costs of the operations that determine the optimal FRI folding arity.
+///
+/// The folding arity trades Merkle tree depth against coset size: a larger arity means fewer
+/// oracles with shorter authentication paths, but more field elements hashed, sent, and folded per
+/// query. Which of these dominates depends on the relative speed of hashing, compression, and
+/// field arithmetic on the machine running the protocol, as well as on how much the proof size
+/// matters to the deployment. Use [`Self::calibrate`] to measure the first three on the current
+/// machine, and [`estimate_optimal_arity_with_cost_model`] to choose an arity with the result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FRICostModel {
+	/// Time in nanoseconds to hash one byte of Merkle leaf data.
+	pub hash_ns_per_byte: f64,
+	/// Time in nanoseconds for one Merkle compression of two digests.
+	pub compression_ns: f64,
+	/// Time in nanoseconds for one additive NTT butterfly over the encoding field.
+	pub butterfly_ns: f64,
+	/// Cost in nanoseconds assigned to each byte of the proof, accounting for transmission and
+	/// storage.
+	pub proof_byte_ns: f64,
+}
+
+impl Default for FRICostModel {
+	/// Returns a cost model that only counts proof size, matching
+	/// [`super::estimate_optimal_arity`].
+	fn default() -> Self {
+		Self {
+			hash_ns_per_byte: 0.0,
+			compression_ns: 0.0,
+			butterfly_ns: 0.0,
+			proof_byte_ns: 1.0,
+		}
+	}
+}
+
+impl FRICostModel {
+	/// Measures hashing, compression, and NTT throughput with quick microbenchmarks on the current
+	/// machine.
+	///
+	/// The cost assigned to each proof byte is `proof_byte_ns`, which cannot be measured locally.
+	/// The benchmarks process a few thousand field elements each and complete in well under a
+	/// millisecond on typical hardware.
+	pub fn calibrate<F, FEncode, MTProver>(
+		merkle_prover: &MTProver,
+		proof_byte_ns: f64,
+	) -> Result<Self, Error>
+	where
+		F: BinaryField + ExtensionField<FEncode>,
+		FEncode: BinaryField,
+		MTProver: MerkleTreeProver<F>,
+	{
+		let n_elems = 1 << LOG_CALIBRATION_SIZE;
+		let data = powers(F::MULTIPLICATIVE_GENERATOR)
+			.take(n_elems)
+			.collect::<Vec<_>>();
+
+		// Committing with batch size b hashes n / b leaves of b elements each and performs about
+		// n / b compressions, so two batch sizes determine both the per-byte hashing cost and the
+		// per-compression cost.
+		let commit_ns = |batch_size: usize| -> Result<f64, Error> {
+			let start = Instant::now();
+			black_box(
+				merkle_prover
+					.commit(&data, batch_size)
+					.map_err(|err| Error::VectorCommit(Box::new(err)))?,
+			);
+			Ok(start.elapsed().as_nanos() as f64)
+		};
+		let narrow_ns = commit_ns(1)?;
+		let wide_ns = commit_ns(CALIBRATION_BATCH_SIZE)?;
+
+		let n_bytes = (n_elems * size_of::<F>()) as f64;
+		let n_compressions_narrow = n_elems as f64;
+		let n_compressions_wide = (n_elems / CALIBRATION_BATCH_SIZE) as f64;
+		// narrow_ns = n_bytes * h + n_compressions_narrow * c
+		// wide_ns   = n_bytes * h + n_compressions_wide * c
+		let compression_ns =
+			((narrow_ns - wide_ns) / (n_compressions_narrow - n_compressions_wide)).max(0.0);
+		let hash_ns_per_byte =
+			(n_compressions_wide.mul_add(-compression_ns, wide_ns) / n_bytes).max(0.0);
+
+		let ntt = SingleThreadedNTT::<FEncode>::new(LOG_CALIBRATION_SIZE)?;
+		let mut ntt_data = powers(FEncode::MULTIPLICATIVE_GENERATOR)
+			.take(n_elems)
+			.collect::<Vec<_>>();
+		let shape = NTTShape {
+			log_y: LOG_CALIBRATION_SIZE,
+			..Default::default()
+		};
+		let start = Instant::now();
+		ntt.forward_transform(&mut ntt_data, shape, 0, 0, 0)?;
+		black_box(&ntt_data);
+		let ntt_ns = start.elapsed().as_nanos() as f64;
+		let n_butterflies = (LOG_CALIBRATION_SIZE << (LOG_CALIBRATION_SIZE - 1)) as f64;
+
+		Ok(Self {
+			hash_ns_per_byte,
+			compression_ns,
+			butterfly_ns: ntt_ns / n_butterflies,
+			proof_byte_ns,
+		})
+	}
+
+	/// Estimates the total cost of FRI with a constant folding arity.
+	///
+	/// The estimate covers the parts of the protocol that depend on the arity: the prover's Merkle
+	/// commitments to the folded oracles, and, for every test query, the proof size of the opened
+	/// cosets and authentication paths together with the verifier's hashing and folding work.
+	pub fn fri_cost(
+		&self,
+		log_block_length: usize,
+		arity: usize,
+		n_test_queries: usize,
+		digest_size: usize,
+		field_size: usize,
+	) -> f64 {
+		let coset_size = (1 << arity) as f64;
+		// Costs of one opened field element and of one authentication path node.
+		let elem_cost = (field_size as f64)
+			.mul_add(self.proof_byte_ns + self.hash_ns_per_byte, self.butterfly_ns);
+		let path_node_cost = (digest_size as f64).mul_add(self.proof_byte_ns, self.compression_ns);
+		// Cost for the prover to commit to one field element of a folded oracle.
+		let commit_elem_cost =
+			(field_size as f64).mul_add(self.hash_ns_per_byte, self.compression_ns / coset_size);
+
+		let mut prover_cost = 0.0;
+		let mut query_cost = 0.0;
+		let mut log_len = log_block_length;
+		while log_len > arity {
+			// Each oracle, including the initial codeword, is opened at one coset per query.
+			let path_len = (log_len - arity) as f64;
+			query_cost += path_len.mul_add(path_node_cost, coset_size * elem_cost);
+
+			log_len -= arity;
+			// The prover commits to the folded oracle, except for the final one sent in the clear.
+			if log_len > arity {
+				prover_cost += (1usize << log_len) as f64 * commit_elem_cost;
+			}
+		}
+		// The terminal codeword is sent in the clear and hashed by the verifier.
+		let terminal_bytes = ((1usize << log_len) * field_size) as f64;
+		let terminal_cost = terminal_bytes * (self.proof_byte_ns + self.hash_ns_per_byte);
+
+		(n_test_queries as f64).mul_add(query_cost, prover_cost) + terminal_cost
+	}
+}
+
+/// Chooses the FRI folding arity that minimizes the estimated cost under a [`FRICostModel`].
+///
+/// `log_block_length` is the binary logarithm of the block length of the Reed–Solomon code, and
+/// `digest_size` and `field_size` are in bytes.
+pub fn estimate_optimal_arity_with_cost_model(
+	log_block_length: usize,
+	n_test_queries: usize,
+	digest_size: usize,
+	field_size: usize,
+	cost_model: &FRICostModel,
+) -> usize {
+	(1..=log_block_length.max(1))
+		.map(|arity| {
+			let cost = cost_model.fri_cost(
+				log_block_length,
+				arity,
+				n_test_queries,
+				digest_size,
+				field_size,
+			);
+			(arity, cost)
+		})
+		.min_by(|(_, lhs), (_, rhs)| lhs.total_cmp(rhs))
+		.map(|(arity, _)| arity)
+		.unwrap_or(1)
+}
+
+/// Relative costs of the operations performed by the FRI verifier.
+///
+/// Where [`FRICostModel`] measures running time, this model prices the work of the verifier, for
+/// instance the gas consumed by a verifier running in a smart contract: every invocation of the
+/// hash compression function, every field multiplication, and every byte of the transcript. The
+/// units are arbitrary, only the ratios between the costs affect the chosen parameters. See
+/// [`Self::verifier_cost`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FRIVerifierCostModel {
+	/// Cost of one invocation of the hash compression function.
+	pub hash_cost: f64,
+	/// Cost of one multiplication in the field of the committed codeword.
+	pub mul_cost: f64,
+	/// Cost of one byte of the transcript.
+	pub transcript_byte_cost: f64,
+}
+
+impl Default for FRIVerifierCostModel {
+	/// Returns a cost model that only counts proof size, matching
+	/// [`FRIParams::estimate_proof_size`].
+	fn default() -> Self {
+		Self {
+			hash_cost: 0.0,
+			mul_cost: 0.0,
+			transcript_byte_cost: 1.0,
+		}
+	}
+}
+
+impl FRIVerifierCostModel {
+	/// Estimates the cost for the verifier to check a FRI proof with the given parameters.
+	///
+	/// For every committed oracle, the estimate counts reading, hashing, and folding the coset
+	/// opened by each test query, reading and compressing its Merkle path, and reading and
+	/// compressing the Merkle layer chosen by `merkle_scheme` up to the root. It then counts
+	/// reading and hashing the terminal codeword, and re-encoding it to check that it is a
+	/// codeword. Hashing `n` bytes is counted as `n / (2 * digest_size)` invocations, rounded up,
+	/// since a compression absorbs two digests.
+	pub fn verifier_cost<F, FA, MTScheme>(
+		&self,
+		params: &FRIParams<F, FA>,
+		merkle_scheme: &MTScheme,
+	) -> f64
+	where
+		F: BinaryField + ExtensionField<FA>,
+		FA: BinaryField,
+		MTScheme: MerkleTreeScheme<F>,
+	{
+		let digest_size = size_of::<MTScheme::Digest>();
+		let field_size = size_of::<F>();
+		let n_test_queries = params.n_test_queries() as f64;
+		let n_hashes = |n_bytes: usize| n_bytes.div_ceil(2 * digest_size) as f64;
+		let cost = |n_bytes: usize, n_hash_calls: f64, n_muls: usize| {
+			n_hash_calls.mul_add(
+				self.hash_cost,
+				(n_bytes as f64).mul_add(self.transcript_byte_cost, n_muls as f64 * self.mul_cost),
+			)
+		};
+
+		let mut total_cost = 0.0;
+		let mut log_n_cosets = params.log_len();
+		for (&arity, layer_depth) in
+			iter::zip(params.fold_arities(), vcs_optimal_layers_depths_iter(params, merkle_scheme))
+		{
+			log_n_cosets -= arity;
+			let path_len = log_n_cosets - layer_depth;
+			let coset_bytes = field_size << arity;
+			// Folding a coset of 2^arity values takes 2^arity - 1 butterflies.
+			let query_cost = cost(
+				coset_bytes + path_len * digest_size,
+				n_hashes(coset_bytes) + path_len as f64,
+				(1 << arity) - 1,
+			);
+			let layer_cost = cost(digest_size << layer_depth, ((1 << layer_depth) - 1) as f64, 0);
+			total_cost += n_test_queries.mul_add(query_cost, layer_cost);
+		}
+
+		let terminal_bytes = field_size << log_n_cosets;
+		total_cost
+			+ cost(terminal_bytes, n_hashes(terminal_bytes), (log_n_cosets << log_n_cosets) / 2)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::{BinaryField16b, BinaryField128b};
+	use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
+
+	use super::*;
+	use crate::{merkle_tree::BinaryMerkleTreeProver, protocols::fri::SoundnessModel};
+
+	#[test]
+	fn test_calibrate_cost_model() {
+		let merkle_prover =
+			BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+		let cost_model =
+			FRICostModel::calibrate::<BinaryField128b, BinaryField16b, _>(&merkle_prover, 1.0)
+				.unwrap();
+		assert!(cost_model.hash_ns_per_byte.is_finite() && cost_model.hash_ns_per_byte >= 0.0);
+		assert!(cost_model.compression_ns.is_finite() && cost_model.compression_ns >= 0.0);
+		assert!(cost_model.butterfly_ns.is_finite() && cost_model.butterfly_ns >= 0.0);
+
+		let arity = estimate_optimal_arity_with_cost_model(24, 100, 32, 16, &cost_model);
+		assert!((1..=24).contains(&arity));
+	}
+
+	#[test]
+	fn test_cost_model_arity_tracks_relative_costs() {
+		let log_block_length = 24;
+		let proof_size_only = FRICostModel::default();
+		let arity =
+			estimate_optimal_arity_with_cost_model(log_block_length, 100, 32, 16, &proof_size_only);
+
+		// When hashing is expensive, the prover's commitments to the folded oracles dominate, and
+		// folding by more variables before each commitment pays off.
+		let expensive_hashing = FRICostModel {
+			hash_ns_per_byte: 100.0,
+			..proof_size_only
+		};
+		let arity_expensive_hashing = estimate_optimal_arity_with_cost_model(
+			log_block_length,
+			100,
+			32,
+			16,
+			&expensive_hashing,
+		);
+		assert!(arity_expensive_hashing > arity);
+
+		// When compressions are expensive, shorter authentication paths pay off.
+		let expensive_compression = FRICostModel {
+			compression_ns: 10_000.0,
+			..proof_size_only
+		};
+		let arity_expensive_compression = estimate_optimal_arity_with_cost_model(
+			log_block_length,
+			100,
+			32,
+			16,
+			&expensive_compression,
+		);
+		assert!(arity_expensive_compression > arity);
+	}
+
+	#[test]
+	fn test_default_verifier_cost_model_counts_proof_size() {
+		let merkle_prover =
+			BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+		let ntt = SingleThreadedNTT::<BinaryField16b>::new(BinaryField16b::N_BITS).unwrap();
+		for arity in 1..=6 {
+			let params = FRIParams::<BinaryField128b, _>::choose_with_constant_fold_arity(
+				&ntt,
+				18,
+				32,
+				1,
+				arity,
+				SoundnessModel::Provable,
+			)
+			.unwrap();
+			let cost =
+				FRIVerifierCostModel::default().verifier_cost(&params, merkle_prover.scheme());
+			assert_eq!(cost, params.estimate_proof_size(32) as f64);
+		}
+	}
+}
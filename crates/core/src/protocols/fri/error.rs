@@ -2,7 +2,7 @@
 
 use binius_ntt::Error as NttError;
 
-use crate::{reed_solomon, transcript};
+use crate::{merkle_tree, reed_solomon, transcript};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -34,22 +34,75 @@ pub enum Error {
 	EncodeError(#[from] NttError),
 	#[error("vector commit error: {0}")]
 	VectorCommit(#[source] Box<dyn std::error::Error + Send + Sync>),
+	#[error("Merkle tree error: {0}")]
+	MerkleTree(#[from] merkle_tree::Error),
 	#[error("verification error: {0}")]
 	Verification(#[from] VerificationError),
 	#[error("transcript error: {0}")]
 	TranscriptError(#[from] transcript::Error),
+	#[error("compute layer error: {0}")]
+	ComputeLayer(#[from] binius_compute::Error),
+	#[error("allocation error: {0}")]
+	Alloc(#[from] binius_compute::alloc::Error),
+	#[error("I/O error: {0}")]
+	Io(#[from] std::io::Error),
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum VerificationError {
-	#[error("incorrect codeword folding in query round {query_round} at index {index}")]
-	IncorrectFold { query_round: usize, index: usize },
+	#[error(
+		"incorrect codeword folding for the query at index {query_index} in query round \
+		{query_round} at index {index}"
+	)]
+	IncorrectFold {
+		query_index: usize,
+		query_round: usize,
+		index: usize,
+	},
+	#[error("the decommitted layer of oracle {oracle} is invalid: {source}")]
+	InvalidLayer {
+		oracle: usize,
+		#[source]
+		source: merkle_tree::Error,
+	},
+	#[error("the terminate codeword does not match the last commitment: {0}")]
+	InvalidTerminateCodeword(#[source] merkle_tree::Error),
+	#[error(
+		"the opening of oracle {oracle} for the query at index {query_index} is invalid: {source}"
+	)]
+	InvalidCosetOpening {
+		query_index: usize,
+		oracle: usize,
+		#[source]
+		source: merkle_tree::Error,
+	},
+	#[error("the decommitted layer of batched codeword {batch_index} is invalid: {source}")]
+	InvalidBatchedLayer {
+		batch_index: usize,
+		#[source]
+		source: merkle_tree::Error,
+	},
+	#[error(
+		"the opening of batched codeword {batch_index} for the query at index {query_index} is \
+		invalid: {source}"
+	)]
+	InvalidBatchedOpening {
+		query_index: usize,
+		batch_index: usize,
+		#[source]
+		source: merkle_tree::Error,
+	},
 	#[error("the size of the query proof is incorrect, expected {expected}")]
 	IncorrectQueryProofLength { expected: usize },
 	#[error(
 		"the number of values in round {round} of the query proof is incorrect, expected {coset_size}"
 	)]
 	IncorrectQueryProofValuesLength { round: usize, coset_size: usize },
-	#[error("The dimension-1 codeword must contain the same values")]
-	IncorrectDegree,
+	#[error(
+		"The dimension-1 codeword must contain the same values, but differs from the first at \
+		index {index}"
+	)]
+	IncorrectDegree { index: usize },
+	#[error("the proof-of-work nonce does not have {grinding_bits} leading zero bits")]
+	InvalidProofOfWork { grinding_bits: usize },
 }
@@ -20,18 +20,40 @@
 //! verifier. The last oracle the prover sends, they send entirely in the clear to the verifier,
 //! rather than sending with oracle access.
 //!
+//! Several codewords committed independently with the same parameters can share one FRI instance.
+//! The prover folds a random linear combination of the codewords and opens each of them at every
+//! query, so the query cost is paid once rather than once per commitment. See
+//! [`FRIFolder::with_batched`] and [`FRIVerifier::with_batched`]. A shorter codeword joins the
+//! instance at the fold round where the folded codeword reaches its length, and is added to the
+//! round oracle committed then, see [`FRIFolder::with_batched_at_round`].
+//!
 //! [BBHR17]: <https://eccc.weizmann.ac.il/report/2017/134/>
 //! [DP24]: <https://eprint.iacr.org/2024/504>
 
 mod common;
+mod cost_model;
 mod error;
 mod logging;
 mod prove;
+mod prove_device;
+mod security;
+mod storage;
 #[cfg(test)]
 mod tests;
+mod trace;
 mod verify;
 
-pub use common::{FRIParams, TerminateCodeword, calculate_n_test_queries, estimate_optimal_arity};
+pub use common::{
+	FRIParams, SoundnessModel, TerminateCodeword, calculate_n_test_queries,
+	calculate_n_test_queries_with_grinding, calculate_n_test_queries_with_soundness_model,
+	estimate_optimal_arity, estimate_optimal_arity_schedule, estimate_proof_size,
+	sample_query_indices, vcs_optimal_layers_depths_iter,
+};
+pub use cost_model::{FRICostModel, FRIVerifierCostModel, estimate_optimal_arity_with_cost_model};
 pub use error::*;
 pub use prove::*;
+pub use prove_device::*;
+pub use security::{SecurityReport, security_report};
+pub use storage::*;
+pub use trace::{FRIRoundTrace, FRITrace};
 pub use verify::*;
@@ -1,5 +1,7 @@
 // Copyright 2024-2025 Irreducible Inc.
 
+use std::iter;
+
 use binius_field::{
 	BinaryField, ExtensionField, PackedExtension, PackedField, TowerField,
 	packed::{iter_packed_slice_with_offset, len_packed_slice},
@@ -7,20 +9,21 @@ use binius_field::{
 use binius_maybe_rayon::prelude::*;
 use binius_ntt::{AdditiveNTT, fri::fold_interleaved};
 use binius_utils::{SerializeBytes, bail, checked_arithmetics::log2_strict_usize};
-use bytemuck::zeroed_vec;
 use bytes::BufMut;
 use itertools::izip;
 use tracing::instrument;
 
 use super::{
 	TerminateCodeword,
-	common::{FRIParams, vcs_optimal_layers_depths_iter},
+	common::{FRIParams, sample_query_indices, vcs_optimal_layers_depths_iter},
 	error::Error,
 	logging::{MerkleTreeDimensionData, RSEncodeDimensionData, SortAndMergeDimensionData},
+	storage::CodewordStorage,
+	trace::{FRIRoundTrace, FRITrace},
 };
 use crate::{
-	fiat_shamir::{CanSampleBits, Challenger},
-	merkle_tree::{MerkleTreeProver, MerkleTreeScheme},
+	fiat_shamir::Challenger,
+	merkle_tree::{MerkleTreeProver, MerkleTreeScheme, StorageConfig},
 	protocols::fri::logging::FRIFoldData,
 	reed_solomon::reed_solomon::ReedSolomonCode,
 	transcript::{ProverTranscript, TranscriptWriter},
@@ -30,7 +33,7 @@ use crate::{
 pub struct CommitOutput<P, VCSCommitment, VCSCommitted> {
 	pub commitment: VCSCommitment,
 	pub committed: VCSCommitted,
-	pub codeword: Vec<P>,
+	pub codeword: CodewordStorage<P>,
 }
 
 /// Creates a parallel iterator over scalars of subfield elementsAssumes chunk_size to be a power of
@@ -103,6 +106,47 @@ where
 	})
 }
 
+/// Encodes and commits an interleaved message over a subfield `FC` of `F`.
+///
+/// The parameters must be constructed with [`FRIParams::new_for_subfield_codeword`]. The message
+/// is packed into elements of `F`, each of which takes consecutive elements of `FC` as its
+/// coordinates, and committed as an interleaved message over `F`. The FRI proof for the codeword
+/// is then the proof for the packed message, folded with challenges from `F`.
+///
+/// ## Arguments
+///
+/// * `params` - common FRI protocol parameters.
+/// * `ntt` - the NTT instance used to encode the packed message
+/// * `merkle_prover` - the Merkle tree prover to use for committing
+/// * `message` - the interleaved message over `FC` to encode and commit
+pub fn commit_interleaved_subfield<F, FC, FA, P, PC, PA, NTT, MerkleProver, VCS>(
+	params: &FRIParams<F, FA>,
+	ntt: &NTT,
+	merkle_prover: &MerkleProver,
+	message: &[PC],
+) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Error>
+where
+	F: BinaryField + ExtensionField<FC> + ExtensionField<FA>,
+	FC: BinaryField,
+	FA: BinaryField,
+	P: PackedField<Scalar = F>
+		+ PackedExtension<FC, PackedSubfield = PC>
+		+ PackedExtension<FA, PackedSubfield = PA>,
+	PC: PackedField<Scalar = FC>,
+	PA: PackedField<Scalar = FA>,
+	NTT: AdditiveNTT<FA> + Sync,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F>,
+{
+	commit_interleaved(
+		params.rs_code(),
+		params,
+		ntt,
+		merkle_prover,
+		<P as PackedExtension<FC>>::cast_exts(message),
+	)
+}
+
 /// Encodes and commits the input message with a closure for writing the message.
 ///
 /// ## Arguments
@@ -117,6 +161,44 @@ pub fn commit_interleaved_with<F, FA, P, PA, NTT, MerkleProver, VCS>(
 	merkle_prover: &MerkleProver,
 	message_writer: impl FnOnce(&mut [P]),
 ) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Error>
+where
+	F: BinaryField,
+	FA: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FA, PackedSubfield = PA>,
+	PA: PackedField<Scalar = FA>,
+	NTT: AdditiveNTT<FA> + Sync,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F>,
+{
+	commit_interleaved_with_storage(
+		params,
+		ntt,
+		merkle_prover,
+		&StorageConfig::Memory,
+		message_writer,
+	)
+}
+
+/// Encodes and commits the input message with a closure for writing the message, storing the
+/// codeword as configured by `storage`.
+///
+/// With [`StorageConfig::Mapped`], the codeword is written to a memory-mapped temporary file
+/// rather than allocated in memory, see [`CodewordStorage`]. The Merkle tree nodes are stored as
+/// configured on the Merkle tree prover.
+///
+/// ## Arguments
+///
+/// * `params` - common FRI protocol parameters.
+/// * `merkle_prover` - the Merkle tree prover to use for committing
+/// * `storage` - where to store the codeword
+/// * `message_writer` - a closure that writes the interleaved message to encode and commit
+pub fn commit_interleaved_with_storage<F, FA, P, PA, NTT, MerkleProver, VCS>(
+	params: &FRIParams<F, FA>,
+	ntt: &NTT,
+	merkle_prover: &MerkleProver,
+	storage: &StorageConfig,
+	message_writer: impl FnOnce(&mut [P]),
+) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Error>
 where
 	F: BinaryField,
 	FA: BinaryField,
@@ -133,7 +215,10 @@ where
 		todo!("can't handle this case well");
 	}
 
-	let mut encoded = zeroed_vec(1 << (log_elems - P::LOG_WIDTH + rs_code.log_inv_rate()));
+	let mut encoded = CodewordStorage::<P>::zeroed(
+		storage,
+		1 << (log_elems - P::LOG_WIDTH + rs_code.log_inv_rate()),
+	)?;
 
 	let dimensions_data = SortAndMergeDimensionData::new::<F>(log_elems);
 	tracing::debug_span!(
@@ -146,6 +231,73 @@ where
 		message_writer(&mut encoded[..1 << (log_elems - P::LOG_WIDTH)]);
 	});
 
+	commit_interleaved_prefilled(params, ntt, merkle_prover, encoded)
+}
+
+/// Encodes and commits an interleaved message that has already been written to the beginning of a
+/// codeword-sized buffer.
+///
+/// This is useful when the message is assembled incrementally, so that the codeword buffer can be
+/// filled without an intermediate copy. The remainder of the buffer after the message is
+/// overwritten.
+///
+/// ## Arguments
+///
+/// * `params` - common FRI protocol parameters.
+/// * `merkle_prover` - the Merkle tree prover to use for committing
+/// * `encoded` - a buffer with the length of the codeword, whose prefix holds the interleaved
+///   message
+pub fn commit_interleaved_prefilled<F, FA, P, PA, NTT, MerkleProver, VCS>(
+	params: &FRIParams<F, FA>,
+	ntt: &NTT,
+	merkle_prover: &MerkleProver,
+	encoded: impl Into<CodewordStorage<P>>,
+) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Error>
+where
+	F: BinaryField,
+	FA: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FA, PackedSubfield = PA>,
+	PA: PackedField<Scalar = FA>,
+	NTT: AdditiveNTT<FA> + Sync,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F>,
+{
+	let mut encoded: CodewordStorage<P> = encoded.into();
+	encode_interleaved_prefilled(params, ntt, &mut encoded)?;
+	commit_encoded(params, merkle_prover, encoded)
+}
+
+/// Encodes an interleaved message that has already been written to the beginning of a
+/// codeword-sized buffer, in place.
+///
+/// This is the encoding step of [`commit_interleaved_prefilled`], for callers that account for
+/// the encoding and the Merkle tree construction separately.
+pub fn encode_interleaved_prefilled<F, FA, P, PA, NTT>(
+	params: &FRIParams<F, FA>,
+	ntt: &NTT,
+	encoded: &mut [P],
+) -> Result<(), Error>
+where
+	F: BinaryField,
+	FA: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FA, PackedSubfield = PA>,
+	PA: PackedField<Scalar = FA>,
+	NTT: AdditiveNTT<FA> + Sync,
+{
+	let rs_code = params.rs_code();
+	let log_batch_size = params.log_batch_size();
+	let log_elems = rs_code.log_dim() + log_batch_size;
+	if log_elems < P::LOG_WIDTH {
+		bail!(Error::InvalidArgs(
+			"interleaved message must fill at least one packed element".to_string()
+		));
+	}
+	if encoded.len() != 1 << (log_elems - P::LOG_WIDTH + rs_code.log_inv_rate()) {
+		bail!(Error::InvalidArgs(
+			"codeword buffer length does not match code parameters".to_string()
+		));
+	}
+
 	let dimensions_data = RSEncodeDimensionData::new::<F>(log_elems, log_batch_size);
 	tracing::debug_span!(
 		"[task] RS Encode",
@@ -153,7 +305,32 @@ where
 		perfetto_category = "task.main",
 		?dimensions_data
 	)
-	.in_scope(|| rs_code.encode_ext_batch_inplace(ntt, &mut encoded, log_batch_size))?;
+	.in_scope(|| rs_code.encode_ext_batch_inplace(ntt, encoded, log_batch_size))?;
+	Ok(())
+}
+
+/// Commits a codeword encoded by [`encode_interleaved_prefilled`] with a Merkle tree.
+///
+/// This is the Merkle tree step of [`commit_interleaved_prefilled`].
+pub fn commit_encoded<F, FA, P, MerkleProver, VCS>(
+	params: &FRIParams<F, FA>,
+	merkle_prover: &MerkleProver,
+	encoded: impl Into<CodewordStorage<P>>,
+) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Error>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+	P: PackedField<Scalar = F>,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F>,
+{
+	let encoded: CodewordStorage<P> = encoded.into();
+	let log_elems = params.rs_code().log_dim() + params.log_batch_size();
+	if encoded.len() != 1 << params.log_len().saturating_sub(P::LOG_WIDTH) {
+		bail!(Error::InvalidArgs(
+			"codeword buffer length does not match code parameters".to_string()
+		));
+	}
 
 	// Take the first arity as coset_log_len, or use the value such that the number of leaves equals
 	// 1 << log_inv_rate if arities is empty
@@ -190,11 +367,134 @@ where
 	})
 }
 
+/// Encodes and commits the input message with a closure for writing the message, overlapping the
+/// Reed–Solomon encoding with the Merkle tree hashing.
+///
+/// The codeword is encoded one coset of the evaluation domain at a time, and the leaves of each
+/// coset are passed to [`MerkleTreeProver::commit_pieces`], which hashes them in place. With a
+/// prover that hashes leaves as they arrive, such as the
+/// [`BinaryMerkleTreeProver`](crate::merkle_tree::BinaryMerkleTreeProver), the hashing
+/// of each coset runs in parallel with the encoding of the next, so the NTT and the hash function
+/// share the threads of the pool instead of running one after the other. The commitment, tree,
+/// and codeword are identical to those of [`commit_interleaved_with_storage`].
+///
+/// ## Arguments
+///
+/// * `params` - common FRI protocol parameters.
+/// * `ntt` - the NTT instance used to encode the message
+/// * `merkle_prover` - the Merkle tree prover to use for committing
+/// * `storage` - where to store the codeword
+/// * `message_writer` - a closure that writes the interleaved message to encode and commit
+pub fn commit_interleaved_pipelined<F, FA, P, PA, NTT, MerkleProver, VCS>(
+	params: &FRIParams<F, FA>,
+	ntt: &NTT,
+	merkle_prover: &MerkleProver,
+	storage: &StorageConfig,
+	message_writer: impl FnOnce(&mut [P]),
+) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Error>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FA, PackedSubfield = PA>,
+	PA: PackedField<Scalar = FA>,
+	NTT: AdditiveNTT<FA> + Sync,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F>,
+{
+	let rs_code = params.rs_code();
+	let log_batch_size = params.log_batch_size();
+	let log_elems = rs_code.log_dim() + log_batch_size;
+	if log_elems < P::LOG_WIDTH {
+		bail!(Error::InvalidArgs(
+			"interleaved message must fill at least one packed element".to_string()
+		));
+	}
+
+	let coset_len = 1 << (log_elems - P::LOG_WIDTH);
+	let mut encoded = CodewordStorage::<P>::zeroed(storage, coset_len << rs_code.log_inv_rate())?;
+
+	let dimensions_data = SortAndMergeDimensionData::new::<F>(log_elems);
+	tracing::debug_span!(
+		"[task] Sort & Merge",
+		phase = "commit",
+		perfetto_category = "task.main",
+		?dimensions_data
+	)
+	.in_scope(|| {
+		message_writer(&mut encoded[..coset_len]);
+	});
+
+	// Each coset is encoded in place from a copy of the message.
+	let (message, rest) = encoded.split_at_mut(coset_len);
+	for coset in rest.chunks_mut(coset_len) {
+		coset.copy_from_slice(message);
+	}
+
+	// The leaves are the cosets of the first fold, as in `commit_interleaved_prefilled`. A leaf
+	// never spans two cosets of the evaluation domain, as the arity is at most `log_elems`.
+	let coset_log_len = params.fold_arities().first().copied().unwrap_or(log_elems);
+	let log_len = params.log_len() - coset_log_len;
+
+	let dimensions_data = RSEncodeDimensionData::new::<F>(log_elems, log_batch_size);
+	let _span = tracing::debug_span!(
+		"[task] RS Encode & Merkle Tree",
+		phase = "commit",
+		perfetto_category = "task.main",
+		?dimensions_data
+	)
+	.entered();
+
+	let encoded_cosets = encoded
+		.chunks_mut(coset_len)
+		.enumerate()
+		.map(|(index, coset)| {
+			rs_code.encode_ext_batch_coset_inplace(ntt, coset, index, log_batch_size)?;
+			Ok::<&[P], Error>(coset)
+		});
+	let (commitment, committed) = if coset_log_len > P::LOG_WIDTH {
+		merkle_prover.commit_pieces(
+			log_len,
+			1 << coset_log_len,
+			encoded_cosets.map(|coset| {
+				coset.map(|coset| to_par_scalar_big_chunks(coset, 1 << coset_log_len))
+			}),
+		)?
+	} else {
+		merkle_prover.commit_pieces(
+			log_len,
+			1 << coset_log_len,
+			encoded_cosets.map(|coset| {
+				coset.map(|coset| to_par_scalar_small_chunks(coset, 1 << coset_log_len))
+			}),
+		)?
+	};
+
+	Ok(CommitOutput {
+		commitment: commitment.root,
+		committed,
+		codeword: encoded,
+	})
+}
+
 pub enum FoldRoundOutput<VCSCommitment> {
 	NoCommitment,
 	Commitment(VCSCommitment),
 }
 
+/// A committed random codeword that masks the committed codeword in the FRI fold rounds.
+///
+/// With a mask, the prover folds the linear combination `codeword + coeff * mask` rather than the
+/// committed codeword, and opens both committed codewords at each query. The folded oracles and
+/// the terminal codeword are then distributed independently of the committed codeword.
+pub struct FRIMask<'a, F, P, Committed> {
+	/// The encoded mask, with the same code parameters as the committed codeword.
+	pub codeword: &'a [P],
+	/// The Merkle tree commitment to the mask codeword.
+	pub committed: &'a Committed,
+	/// The coefficient of the mask in the folded linear combination.
+	pub coeff: F,
+}
+
 /// A stateful prover for the FRI fold phase.
 pub struct FRIFolder<'a, F, FA, P, NTT, MerkleProver, VCS>
 where
@@ -209,10 +509,20 @@ where
 	merkle_prover: &'a MerkleProver,
 	codeword: &'a [P],
 	codeword_committed: &'a MerkleProver::Committed,
+	/// The codewords batched with the committed codeword, such as a mask.
+	batched: Vec<(&'a [P], &'a MerkleProver::Committed)>,
+	/// The linear combination of the codeword with the batched codewords, which is folded in
+	/// place of the codeword.
+	combined_codeword: Option<Vec<P>>,
+	/// The shorter codewords batched into round oracles, with the index of the oracle each is
+	/// added to and its coefficient.
+	batched_oracles: Vec<(usize, &'a [P], &'a MerkleProver::Committed, F)>,
 	round_committed: Vec<(Vec<F>, MerkleProver::Committed)>,
 	curr_round: usize,
 	next_commit_round: Option<usize>,
 	unprocessed_challenges: Vec<F>,
+	/// The record of the fold rounds, if enabled with [`Self::with_trace`].
+	trace: Option<FRITrace<F, VCS::Digest>>,
 }
 
 impl<'a, F, FA, P, NTT, MerkleProver, VCS> FRIFolder<'a, F, FA, P, NTT, MerkleProver, VCS>
@@ -245,13 +555,158 @@ where
 			merkle_prover,
 			codeword: committed_codeword,
 			codeword_committed: committed,
+			batched: Vec::new(),
+			combined_codeword: None,
+			batched_oracles: Vec::new(),
 			round_committed: Vec::with_capacity(params.n_oracles()),
 			curr_round: 0,
 			next_commit_round,
 			unprocessed_challenges: Vec::with_capacity(params.rs_code().log_dim()),
+			trace: None,
 		})
 	}
 
+	/// Records the challenge and commitment of each fold round, and the terminal codeword, in a
+	/// [`FRITrace`].
+	///
+	/// This is a debugging aid for locating the fold round in which a proof that fails to verify
+	/// diverges from the verifier, and costs a copy of the terminal codeword.
+	pub fn with_trace(mut self) -> Self {
+		self.trace = Some(FRITrace::default());
+		self
+	}
+
+	/// The record of the fold rounds executed so far, if enabled with [`Self::with_trace`].
+	pub const fn trace(&self) -> Option<&FRITrace<F, VCS::Digest>> {
+		self.trace.as_ref()
+	}
+
+	/// Masks the committed codeword with a committed random codeword.
+	///
+	/// This must be called before the first fold round.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if the mask codeword length differs from the committed codeword
+	///   length, if a fold round was already executed, or if the parameters send no round oracles,
+	///   in which case the committed codeword would be decommitted in the clear
+	pub fn with_mask(
+		self,
+		mask: FRIMask<'a, F, P, MerkleProver::Committed>,
+	) -> Result<Self, Error> {
+		self.with_batched(mask.codeword, mask.committed, mask.coeff)
+	}
+
+	/// Batches another committed codeword into this FRI instance.
+	///
+	/// The prover folds the random linear combination of the committed codeword and all batched
+	/// codewords, and opens every committed codeword at each query, so that a single FRI proof
+	/// tests the proximity of all of them. This lets commitments made independently, for example
+	/// at different times, share the cost of the queries. The batched codewords must be encoded
+	/// with the same parameters, and `coeff` must be sampled by the verifier after all the
+	/// commitments are observed. The fully-folded value is the same linear combination of the
+	/// values each codeword would fold to.
+	///
+	/// This must be called before the first fold round.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if the batched codeword length differs from the committed codeword
+	///   length, if a fold round was already executed, or if the parameters send no round oracles,
+	///   in which case the combined codeword would not match any commitment
+	pub fn with_batched(
+		mut self,
+		codeword: &'a [P],
+		committed: &'a MerkleProver::Committed,
+		coeff: F,
+	) -> Result<Self, Error> {
+		if codeword.len() != self.codeword.len() {
+			bail!(Error::InvalidArgs(
+				"batched codeword length must match the committed codeword length".to_string()
+			));
+		}
+		if self.curr_round != 0 {
+			bail!(Error::InvalidArgs(
+				"codewords must be batched before the first fold round".to_string()
+			));
+		}
+		if self.params.n_oracles() == 0 {
+			bail!(Error::InvalidArgs("batching requires at least one round oracle".to_string()));
+		}
+
+		let coeff = P::broadcast(coeff);
+		let combined_codeword = match self.combined_codeword.take() {
+			Some(mut combined) => {
+				combined
+					.par_iter_mut()
+					.zip(codeword.par_iter())
+					.for_each(|(combined, &batched)| *combined += batched * coeff);
+				combined
+			}
+			None => self
+				.codeword
+				.par_iter()
+				.zip(codeword.par_iter())
+				.map(|(&codeword, &batched)| codeword + batched * coeff)
+				.collect(),
+		};
+		self.batched.push((codeword, committed));
+		self.combined_codeword = Some(combined_codeword);
+		Ok(self)
+	}
+
+	/// Batches a shorter committed codeword into this FRI instance after `fold_round` fold rounds.
+	///
+	/// The codeword must be encoded with the parameters returned by
+	/// [`FRIParams::batched_codeword_params`] for `fold_round`, so that it has the length of the
+	/// round oracle committed after that round and lies in the same code. The prover adds the
+	/// codeword times `coeff` to the folded codeword before committing the oracle, and opens the
+	/// codeword alongside the oracle at each query. The fully-folded value is then the value the
+	/// committed codeword folds to plus `coeff` times the value the batched codeword folds to with
+	/// the challenges of the remaining rounds. As with [`Self::with_batched`], `coeff` must be
+	/// sampled by the verifier after all the commitments are observed. With `fold_round` zero, this
+	/// is [`Self::with_batched`].
+	///
+	/// This must be called before the first fold round.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if no round oracle other than the last is committed after
+	///   `fold_round` fold rounds, if the batched codeword does not have the length of that oracle,
+	///   or if a fold round was already executed
+	pub fn with_batched_at_round(
+		mut self,
+		fold_round: usize,
+		codeword: &'a [P],
+		committed: &'a MerkleProver::Committed,
+		coeff: F,
+	) -> Result<Self, Error> {
+		if fold_round == 0 {
+			return self.with_batched(codeword, committed, coeff);
+		}
+
+		let oracle = self.params.batched_oracle(fold_round)?;
+		if len_packed_slice(codeword) != 1 << (self.params.log_len() - fold_round) {
+			bail!(Error::InvalidArgs(
+				"batched codeword length must match the length of its round oracle".to_string()
+			));
+		}
+		if self.curr_round != 0 {
+			bail!(Error::InvalidArgs(
+				"codewords must be batched before the first fold round".to_string()
+			));
+		}
+
+		self.batched_oracles
+			.push((oracle, codeword, committed, coeff));
+		Ok(self)
+	}
+
+	/// The FRI parameters of the folded codeword.
+	pub const fn params(&self) -> &'a FRIParams<F, FA> {
+		self.params
+	}
+
 	/// Number of fold rounds, including the final fold.
 	pub const fn n_rounds(&self) -> usize {
 		self.params.n_fold_rounds()
@@ -284,6 +739,29 @@ where
 		&mut self,
 		challenge: F,
 	) -> Result<FoldRoundOutput<VCS::Digest>, Error> {
+		let output = self.fold_round(challenge)?;
+
+		let is_last_round = self.curr_round == self.n_rounds();
+		if let Some(trace) = &mut self.trace {
+			let commitment = match &output {
+				FoldRoundOutput::NoCommitment => None,
+				FoldRoundOutput::Commitment(commitment) => Some(commitment.clone()),
+			};
+			trace.rounds.push(FRIRoundTrace {
+				challenge,
+				commitment,
+			});
+			if is_last_round {
+				trace.terminate_codeword = Some(match self.round_committed.last() {
+					Some((codeword, _)) => codeword.clone(),
+					None => PackedField::iter_slice(self.codeword).collect(),
+				});
+			}
+		}
+		Ok(output)
+	}
+
+	fn fold_round(&mut self, challenge: F) -> Result<FoldRoundOutput<VCS::Digest>, Error> {
 		self.unprocessed_challenges.push(challenge);
 		self.curr_round += 1;
 
@@ -312,7 +790,7 @@ where
 		)
 		.entered();
 		// Fold the last codeword with the accumulated folding challenges.
-		let folded_codeword = match self.round_committed.last() {
+		let mut folded_codeword = match self.round_committed.last() {
 			Some((prev_codeword, _)) => {
 				// Fold a full codeword committed in the previous FRI round into a codeword with
 				// reduced dimension and rate.
@@ -330,13 +808,28 @@ where
 				// fold rounds.
 				fold_interleaved(
 					self.ntt,
-					self.codeword,
+					self.combined_codeword.as_deref().unwrap_or(self.codeword),
 					&self.unprocessed_challenges,
 					self.params.rs_code().log_len(),
 					self.params.log_batch_size(),
 				)
 			}
 		};
+		// Add the codewords batched into this oracle before it is committed.
+		for &(_, codeword, _, coeff) in self
+			.batched_oracles
+			.iter()
+			.filter(|(oracle, ..)| *oracle == self.round_committed.len())
+		{
+			folded_codeword
+				.par_chunks_mut(P::WIDTH)
+				.zip(codeword.par_iter())
+				.for_each(|(values, batched)| {
+					for (value, batched) in iter::zip(values, batched.iter()) {
+						*value += batched * coeff;
+					}
+				});
+		}
 		drop(fri_fold_span);
 		self.unprocessed_challenges.clear();
 
@@ -399,6 +892,8 @@ where
 			params,
 			codeword,
 			codeword_committed,
+			batched,
+			batched_oracles,
 			round_committed,
 			merkle_prover,
 			..
@@ -408,6 +903,11 @@ where
 			params,
 			codeword,
 			codeword_committed,
+			batched,
+			batched_oracles: batched_oracles
+				.into_iter()
+				.map(|(oracle, codeword, committed, _)| (oracle, codeword, committed))
+				.collect(),
 			round_committed,
 			merkle_prover,
 		};
@@ -422,22 +922,7 @@ where
 		Challenger_: Challenger,
 	{
 		let (terminate_codeword, query_prover) = self.finalize()?;
-		let mut advice = transcript.decommitment();
-		advice.write_scalar_slice(&terminate_codeword);
-
-		let layers = query_prover.vcs_optimal_layers()?;
-		for layer in layers {
-			advice.write_slice(&layer);
-		}
-
-		let params = query_prover.params;
-
-		for _ in 0..params.n_test_queries() {
-			let index = transcript.sample_bits(params.index_bits()) as usize;
-			query_prover.prove_query(index, transcript.decommitment())?;
-		}
-
-		Ok(())
+		query_prover.prove(&terminate_codeword, transcript)
 	}
 }
 
@@ -453,11 +938,14 @@ where
 	params: &'a FRIParams<F, FA>,
 	codeword: &'a [P],
 	codeword_committed: &'a MerkleProver::Committed,
+	batched: Vec<(&'a [P], &'a MerkleProver::Committed)>,
+	/// The codewords batched into round oracles, with the index of the oracle each is added to.
+	batched_oracles: Vec<(usize, &'a [P], &'a MerkleProver::Committed)>,
 	round_committed: Vec<(Vec<F>, MerkleProver::Committed)>,
 	merkle_prover: &'a MerkleProver,
 }
 
-impl<F, FA, P, MerkleProver, VCS> FRIQueryProver<'_, F, FA, P, MerkleProver, VCS>
+impl<'a, F, FA, P, MerkleProver, VCS> FRIQueryProver<'a, F, FA, P, MerkleProver, VCS>
 where
 	F: TowerField + ExtensionField<FA>,
 	FA: BinaryField,
@@ -465,11 +953,68 @@ where
 	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
 	VCS: MerkleTreeScheme<F>,
 {
+	/// Constructs a query prover for oracles that were folded and committed outside of a
+	/// [`FRIFolder`].
+	pub(super) const fn new(
+		params: &'a FRIParams<F, FA>,
+		merkle_prover: &'a MerkleProver,
+		codeword: &'a [P],
+		codeword_committed: &'a MerkleProver::Committed,
+		round_committed: Vec<(Vec<F>, MerkleProver::Committed)>,
+	) -> Self {
+		Self {
+			params,
+			codeword,
+			codeword_committed,
+			batched: Vec::new(),
+			batched_oracles: Vec::new(),
+			round_committed,
+			merkle_prover,
+		}
+	}
+
 	/// Number of oracles sent during the fold rounds.
 	pub fn n_oracles(&self) -> usize {
 		self.params.n_oracles()
 	}
 
+	/// Writes the terminate codeword, the decommitted Merkle layers, and the openings of all FRI
+	/// challenge queries to the transcript.
+	pub(super) fn prove<Challenger_>(
+		&self,
+		terminate_codeword: &[F],
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		Challenger_: Challenger,
+		VCS::Digest: SerializeBytes,
+	{
+		let mut advice = transcript.decommitment();
+		advice.write_scalar_slice(terminate_codeword);
+		let terminate_committed = self
+			.round_committed
+			.last()
+			.map_or(self.codeword_committed, |(_, committed)| committed);
+		self.merkle_prover
+			.prove_vector_opening(terminate_committed, &mut advice)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+
+		let layers = self.vcs_optimal_layers()?;
+		for layer in layers {
+			advice.write_slice(&layer);
+		}
+		for layer in self.batched_optimal_layers()? {
+			advice.write_slice(&layer);
+		}
+
+		transcript.grind(self.params.grinding_bits());
+		for index in sample_query_indices(self.params, transcript) {
+			self.prove_query(index, transcript.decommitment())?;
+		}
+
+		Ok(())
+	}
+
 	/// Proves a FRI challenge query.
 	///
 	/// ## Arguments
@@ -509,9 +1054,20 @@ where
 			first_optimal_layer_depth,
 			&mut advice,
 		)?;
+		for &(batched_codeword, batched_committed) in &self.batched {
+			prove_coset_opening(
+				self.merkle_prover,
+				batched_codeword,
+				batched_committed,
+				index,
+				first_fold_arity,
+				first_optimal_layer_depth,
+				&mut advice,
+			)?;
+		}
 
-		for ((codeword, committed), (arity, optimal_layer_depth)) in
-			izip!(self.round_committed.iter(), arities_and_optimal_layers_depths)
+		for (oracle, ((codeword, committed), (arity, optimal_layer_depth))) in
+			izip!(self.round_committed.iter(), arities_and_optimal_layers_depths).enumerate()
 		{
 			index >>= arity;
 			prove_coset_opening(
@@ -523,6 +1079,21 @@ where
 				optimal_layer_depth,
 				&mut advice,
 			)?;
+			for &(_, batched_codeword, batched_committed) in self
+				.batched_oracles
+				.iter()
+				.filter(|(batched_oracle, ..)| *batched_oracle == oracle)
+			{
+				prove_coset_opening(
+					self.merkle_prover,
+					batched_codeword,
+					batched_committed,
+					index,
+					arity,
+					optimal_layer_depth,
+					&mut advice,
+				)?;
+			}
 		}
 
 		Ok(())
@@ -542,6 +1113,35 @@ where
 			})
 			.collect::<Result<Vec<_>, _>>()
 	}
+
+	/// The layers of the Merkle trees of the batched codewords that their query openings are
+	/// verified against, in the order the codewords were batched, with the codewords batched into
+	/// round oracles after those batched with the committed codeword.
+	pub fn batched_optimal_layers(&self) -> Result<Vec<Vec<VCS::Digest>>, Error> {
+		let optimal_layer_depths =
+			vcs_optimal_layers_depths_iter(self.params, self.merkle_prover.scheme())
+				.collect::<Vec<_>>();
+		let Some(&first_optimal_layer_depth) = optimal_layer_depths.first() else {
+			return Ok(Vec::new());
+		};
+		// A codeword batched into a round oracle is opened with the cosets of the oracle, which
+		// follows the committed codeword in the order of the layers.
+		self.batched
+			.iter()
+			.map(|&(_, committed)| (committed, first_optimal_layer_depth))
+			.chain(
+				self.batched_oracles
+					.iter()
+					.map(|&(oracle, _, committed)| (committed, optimal_layer_depths[oracle + 1])),
+			)
+			.map(|(committed, optimal_layer_depth)| {
+				self.merkle_prover
+					.layer(committed, optimal_layer_depth)
+					.map(|layer| layer.to_vec())
+					.map_err(|err| Error::VectorCommit(Box::new(err)))
+			})
+			.collect()
+	}
 }
 
 fn prove_coset_opening<F, P, MTProver, B>(
@@ -564,7 +1164,17 @@ where
 	advice.write_scalar_iter(values);
 
 	merkle_prover
-		.prove_opening(committed, optimal_layer_depth, coset_index, advice)
+		.prove_opening_from_leaves(
+			committed,
+			optimal_layer_depth,
+			coset_index,
+			|cosets| {
+				iter_packed_slice_with_offset(codeword, cosets.start << log_coset_size)
+					.take(cosets.len() << log_coset_size)
+					.collect()
+			},
+			advice,
+		)
 		.map_err(|err| Error::VectorCommit(Box::new(err)))?;
 
 	Ok(())
@@ -0,0 +1,272 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_compute::{
+	ComputeLayer, ComputeLayerExecutor, ComputeMemory, FSlice, SizedSlice,
+	alloc::{BumpAllocator, ComputeAllocator},
+};
+use binius_field::{BinaryField, ExtensionField, PackedField, TowerField};
+use binius_hash::{PseudoCompressionFunction, multi_digest::ParallelDigest};
+use binius_ntt::AdditiveNTT;
+use binius_utils::{bail, checked_arithmetics::log2_strict_usize};
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
+use tracing::instrument;
+
+use super::{
+	TerminateCodeword,
+	common::FRIParams,
+	error::Error,
+	logging::{FRIFoldData, MerkleTreeDimensionData},
+	prove::{FRIQueryProver, FoldRoundOutput},
+};
+use crate::{
+	fiat_shamir::Challenger,
+	merkle_tree::{BinaryMerkleTree, BinaryMerkleTreeProver, BinaryMerkleTreeScheme, MappableNode},
+	transcript::ProverTranscript,
+};
+
+/// The query prover for the oracles folded by a [`DeviceFRIFolder`].
+pub type DeviceFRIQueryProver<'a, F, FA, P, H, C> = FRIQueryProver<
+	'a,
+	F,
+	FA,
+	P,
+	BinaryMerkleTreeProver<F, H, C>,
+	BinaryMerkleTreeScheme<F, <H as ParallelDigest>::Digest, C>,
+>;
+
+/// A stateful prover for the FRI fold phase that keeps the folded codewords in the device memory
+/// of a compute layer.
+///
+/// Each commitment round folds the previous codeword with
+/// [`ComputeLayerExecutor::fri_fold`] and hashes the leaves of the folded codeword with
+/// [`BinaryMerkleTreeProver::commit_on_device`], so the fold phase runs on the device without
+/// copying any codeword to the host. The folded codewords are copied to the host once, in
+/// [`Self::finalize`], to answer the queries. The commitments and proof are identical to those of
+/// a [`super::FRIFolder`] over the same codeword.
+pub struct DeviceFRIFolder<'a, 'alloc, F, FA, P, Hal, NTT, H, C>
+where
+	F: TowerField,
+	FA: BinaryField,
+	Hal: ComputeLayer<F>,
+	H: ParallelDigest,
+{
+	hal: &'a Hal,
+	dev_alloc: &'a BumpAllocator<'alloc, F, Hal::DevMem>,
+	params: &'a FRIParams<F, FA>,
+	ntt: &'a NTT,
+	merkle_prover: &'a BinaryMerkleTreeProver<F, H, C>,
+	codeword: &'a [P],
+	codeword_dev: FSlice<'a, F, Hal>,
+	codeword_committed: &'a BinaryMerkleTree<Output<H::Digest>>,
+	round_committed: Vec<(FSlice<'a, F, Hal>, BinaryMerkleTree<Output<H::Digest>>)>,
+	curr_round: usize,
+	next_commit_round: Option<usize>,
+	unprocessed_challenges: Vec<F>,
+}
+
+impl<'a, 'alloc, F, FA, P, Hal, NTT, H, C> DeviceFRIFolder<'a, 'alloc, F, FA, P, Hal, NTT, H, C>
+where
+	F: TowerField + ExtensionField<FA>,
+	FA: BinaryField,
+	P: PackedField<Scalar = F>,
+	Hal: ComputeLayer<F>,
+	NTT: AdditiveNTT<FA> + Sync,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset + Clone + Sync>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+	Output<H::Digest>: MappableNode,
+{
+	/// Constructs a new folder.
+	///
+	/// `codeword` and `codeword_dev` are the host and device copies of the committed interleaved
+	/// codeword. The host copy is only read to answer the queries. The folded codewords are
+	/// allocated from `dev_alloc`.
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(
+		hal: &'a Hal,
+		dev_alloc: &'a BumpAllocator<'alloc, F, Hal::DevMem>,
+		params: &'a FRIParams<F, FA>,
+		ntt: &'a NTT,
+		merkle_prover: &'a BinaryMerkleTreeProver<F, H, C>,
+		codeword: &'a [P],
+		codeword_dev: FSlice<'a, F, Hal>,
+		committed: &'a BinaryMerkleTree<Output<H::Digest>>,
+	) -> Result<Self, Error> {
+		let codeword_len = 1 << (params.rs_code().log_len() + params.log_batch_size());
+		if codeword_dev.len() != codeword_len || codeword.len() << P::LOG_WIDTH < codeword_len {
+			bail!(Error::InvalidArgs(
+				"Reed–Solomon code length must match interleaved codeword length".to_string(),
+			));
+		}
+
+		let next_commit_round = params.fold_arities().first().copied();
+		Ok(Self {
+			hal,
+			dev_alloc,
+			params,
+			ntt,
+			merkle_prover,
+			codeword,
+			codeword_dev,
+			codeword_committed: committed,
+			round_committed: Vec::with_capacity(params.n_oracles()),
+			curr_round: 0,
+			next_commit_round,
+			unprocessed_challenges: Vec::with_capacity(params.rs_code().log_dim()),
+		})
+	}
+
+	/// The FRI parameters of the folded codeword.
+	pub const fn params(&self) -> &'a FRIParams<F, FA> {
+		self.params
+	}
+
+	/// Number of fold rounds, including the final fold.
+	pub const fn n_rounds(&self) -> usize {
+		self.params.n_fold_rounds()
+	}
+
+	/// Number of times `execute_fold_round` has been called.
+	pub const fn curr_round(&self) -> usize {
+		self.curr_round
+	}
+
+	/// The length of the current codeword.
+	pub fn current_codeword_len(&self) -> usize {
+		match self.round_committed.last() {
+			Some((codeword, _)) => codeword.len(),
+			None => self.codeword_dev.len(),
+		}
+	}
+
+	fn is_commitment_round(&self) -> bool {
+		self.next_commit_round
+			.is_some_and(|round| round == self.curr_round)
+	}
+
+	/// Executes the next fold round and returns the folded codeword commitment.
+	///
+	/// As for [`super::FRIFolder::execute_fold_round`], the folding challenges are accumulated
+	/// until the next commitment round, when the codeword is folded by all of them at once.
+	pub fn execute_fold_round(
+		&mut self,
+		challenge: F,
+	) -> Result<FoldRoundOutput<Output<H::Digest>>, Error> {
+		self.unprocessed_challenges.push(challenge);
+		self.curr_round += 1;
+
+		if !self.is_commitment_round() {
+			return Ok(FoldRoundOutput::NoCommitment);
+		}
+
+		let (prev_codeword, log_len, log_batch_size) = match self.round_committed.last() {
+			Some(&(codeword, _)) => (codeword, log2_strict_usize(codeword.len()), 0),
+			None => {
+				(self.codeword_dev, self.params.rs_code().log_len(), self.params.log_batch_size())
+			}
+		};
+		let dimensions_data =
+			FRIFoldData::new::<F, FA>(log_len, log_batch_size, self.unprocessed_challenges.len());
+
+		let fri_fold_span = tracing::debug_span!(
+			"[task] FRI Fold",
+			phase = "piop_compiler",
+			perfetto_category = "task.main",
+			?dimensions_data
+		)
+		.entered();
+		let n_fold_challenges = self.unprocessed_challenges.len() - log_batch_size;
+		let mut folded_codeword = self.dev_alloc.alloc(1 << (log_len - n_fold_challenges))?;
+		self.hal.execute(|exec| {
+			exec.fri_fold(
+				self.ntt,
+				log_len,
+				log_batch_size,
+				&self.unprocessed_challenges,
+				prev_codeword,
+				&mut folded_codeword,
+			)?;
+			Ok(Vec::new())
+		})?;
+		let folded_codeword = Hal::DevMem::to_const(folded_codeword);
+		drop(fri_fold_span);
+		self.unprocessed_challenges.clear();
+
+		let coset_size = self
+			.params
+			.fold_arities()
+			.get(self.round_committed.len() + 1)
+			.map(|log| 1 << log)
+			.unwrap_or_else(|| 1 << self.params.n_final_challenges());
+		let dimension_data =
+			MerkleTreeDimensionData::new::<F>(dimensions_data.log_len(), coset_size);
+		let merkle_tree_span = tracing::debug_span!(
+			"[task] Merkle Tree",
+			phase = "piop_compiler",
+			perfetto_category = "task.main",
+			dimensions_data = ?dimension_data
+		)
+		.entered();
+		let (commitment, committed) = self
+			.merkle_prover
+			.commit_on_device(self.hal, folded_codeword, coset_size)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		drop(merkle_tree_span);
+
+		self.round_committed.push((folded_codeword, committed));
+
+		self.next_commit_round = self.next_commit_round.take().and_then(|next_commit_round| {
+			let arity = self.params.fold_arities().get(self.round_committed.len())?;
+			Some(next_commit_round + arity)
+		});
+		Ok(FoldRoundOutput::Commitment(commitment.root))
+	}
+
+	/// Finalizes the FRI folding process.
+	///
+	/// This copies the folded codewords from the device to the host and returns the terminate
+	/// codeword and a query prover instance, as [`super::FRIFolder::finalize`] does.
+	#[instrument(skip_all, name = "fri::DeviceFRIFolder::finalize", level = "debug")]
+	#[allow(clippy::type_complexity)]
+	pub fn finalize(
+		self,
+	) -> Result<(TerminateCodeword<F>, DeviceFRIQueryProver<'a, F, FA, P, H, C>), Error> {
+		if self.curr_round != self.n_rounds() {
+			bail!(Error::EarlyProverFinish);
+		}
+
+		let round_committed = self
+			.round_committed
+			.into_iter()
+			.map(|(codeword_dev, committed)| {
+				let mut codeword = vec![F::ZERO; codeword_dev.len()];
+				self.hal.copy_d2h(codeword_dev, &mut codeword)?;
+				Ok((codeword, committed))
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let terminate_codeword = round_committed
+			.last()
+			.map(|(codeword, _)| codeword.clone())
+			.unwrap_or_else(|| PackedField::iter_slice(self.codeword).collect());
+
+		let query_prover = FRIQueryProver::new(
+			self.params,
+			self.merkle_prover,
+			self.codeword,
+			self.codeword_committed,
+			round_committed,
+		);
+		Ok((terminate_codeword, query_prover))
+	}
+
+	pub fn finish_proof<Challenger_>(
+		self,
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Error>
+	where
+		Challenger_: Challenger,
+	{
+		let (terminate_codeword, query_prover) = self.finalize()?;
+		query_prover.prove(&terminate_codeword, transcript)
+	}
+}
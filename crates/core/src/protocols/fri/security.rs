@@ -0,0 +1,118 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{BinaryField, ExtensionField};
+
+use super::common::FRIParams;
+use crate::merkle_tree::MerkleTreeScheme;
+
+/// An itemized soundness budget of FRI-Binius for a choice of parameters.
+///
+/// Each term is the number of bits of security, `-log2` of the soundness error, that a part of
+/// the protocol contributes. The terms follow the analysis that
+/// [`super::calculate_n_test_queries_with_soundness_model`] sizes the number of test queries with,
+/// so parameters chosen for a security target report at least that many bits in
+/// [`Self::interactive_bits`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecurityReport {
+	/// The sumcheck rounds that bind the committed message to the claimed evaluation, with error
+	/// `2 ⋅ ℓ' / |T_τ|`.
+	pub sumcheck_bits: f64,
+	/// The proximity gaps of the folded codewords, with error `2^{ℓ' + R} / |T_τ|`.
+	pub proximity_gaps_bits: f64,
+	/// The query phase, including the proof-of-work grinding before it.
+	pub query_bits: f64,
+	/// The collision resistance of the Merkle tree digests, which is half of the digest length.
+	pub commitment_bits: f64,
+	/// The bits lost to the Fiat–Shamir transform.
+	///
+	/// A prover attacking the non-interactive protocol can rewind the transcript to resample the
+	/// challenges of any one round, so the error of the interactive protocol is multiplied by the
+	/// number of rounds with verifier randomness: each fold round and the query phase.
+	pub fiat_shamir_slack_bits: f64,
+}
+
+impl SecurityReport {
+	/// The security of the interactive protocol, from the sum of its soundness errors.
+	pub fn interactive_bits(&self) -> f64 {
+		let err = [
+			self.sumcheck_bits,
+			self.proximity_gaps_bits,
+			self.query_bits,
+		]
+		.into_iter()
+		.map(|bits| (-bits).exp2())
+		.sum::<f64>();
+		-err.log2()
+	}
+
+	/// The achieved security of the non-interactive protocol, in bits.
+	///
+	/// This is the interactive security less the Fiat–Shamir slack, and is at most the security
+	/// of the Merkle tree commitments.
+	pub fn security_bits(&self) -> f64 {
+		(self.interactive_bits() - self.fiat_shamir_slack_bits).min(self.commitment_bits)
+	}
+}
+
+/// Returns the itemized soundness budget of FRI-Binius with the given parameters.
+///
+/// The challenges are sampled from `F`, and the Merkle tree digests of `vcs` are assumed to be
+/// as collision-resistant as a hash of the same output length.
+pub fn security_report<F, FA, VCS>(params: &FRIParams<F, FA>, _vcs: &VCS) -> SecurityReport
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+	VCS: MerkleTreeScheme<F>,
+{
+	let code = params.rs_code();
+	let log_field_size = F::N_BITS as f64;
+
+	// 2 ⋅ ℓ' / |T_{τ}|
+	let sumcheck_bits = log_field_size - ((2 * code.log_dim()) as f64).log2();
+	// 2^{ℓ' + R} / |T_{τ}|
+	let proximity_gaps_bits = log_field_size - code.log_len() as f64;
+	let per_query_bits = -params
+		.soundness_model()
+		.per_query_err(code.log_inv_rate())
+		.log2();
+	let query_bits =
+		params.n_test_queries() as f64 * per_query_bits + params.grinding_bits() as f64;
+	let commitment_bits = (8 * size_of::<VCS::Digest>()) as f64 / 2.0;
+	let fiat_shamir_slack_bits = ((params.n_fold_rounds() + 1) as f64).log2();
+
+	SecurityReport {
+		sumcheck_bits,
+		proximity_gaps_bits,
+		query_bits,
+		commitment_bits,
+		fiat_shamir_slack_bits,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::{BinaryField32b, BinaryField128b};
+	use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
+
+	use super::*;
+	use crate::{merkle_tree::BinaryMerkleTreeProver, reed_solomon::reed_solomon::ReedSolomonCode};
+
+	#[test]
+	fn test_security_report_meets_target() {
+		let merkle_prover = BinaryMerkleTreeProver::<BinaryField128b, Groestl256, _>::new(
+			Groestl256ByteCompression,
+		);
+		let rs_code = ReedSolomonCode::<BinaryField32b>::new(28, 1).unwrap();
+		let params = FRIParams::<BinaryField128b, _>::new(rs_code, 0, vec![4; 6], 0)
+			.unwrap()
+			.with_grinding(96, 16)
+			.unwrap();
+
+		let report = security_report(&params, merkle_prover.scheme());
+		assert_eq!(report.commitment_bits, 128.0);
+		assert_eq!(report.proximity_gaps_bits, 128.0 - 29.0);
+		assert!(report.query_bits >= 96.0);
+		assert!(report.interactive_bits() >= 96.0);
+		assert!(report.security_bits() < report.interactive_bits());
+	}
+}
@@ -0,0 +1,133 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{
+	fmt::{self, Debug},
+	marker::PhantomData,
+	mem::size_of,
+	ops::{Deref, DerefMut},
+	path::Path,
+	slice,
+};
+
+use binius_field::PackedField;
+use memmap2::MmapMut;
+
+use super::error::Error;
+use crate::merkle_tree::StorageConfig;
+
+/// An encoded codeword, held either in memory or in a memory-mapped file.
+///
+/// The prover keeps the committed codeword until the FRI query phase, where it only reads the
+/// cosets opened by the queries. With [`StorageConfig::Mapped`], the operating system writes the
+/// pages of the codeword back to the file under memory pressure while it is encoded and folded,
+/// and the query phase loads only the pages of the opened cosets, so codewords larger than the
+/// available memory can be committed.
+pub enum CodewordStorage<P> {
+	Memory(Vec<P>),
+	Mapped(MappedCodeword<P>),
+}
+
+impl<P: PackedField> CodewordStorage<P> {
+	/// Allocates storage for `len` zeroed packed elements as configured.
+	pub fn zeroed(config: &StorageConfig, len: usize) -> Result<Self, Error> {
+		match config {
+			StorageConfig::Memory => Ok(Self::Memory(bytemuck::zeroed_vec(len))),
+			StorageConfig::Mapped(dir) => Ok(Self::Mapped(MappedCodeword::zeroed(dir, len)?)),
+		}
+	}
+}
+
+impl<P> Deref for CodewordStorage<P> {
+	type Target = [P];
+
+	fn deref(&self) -> &[P] {
+		match self {
+			Self::Memory(codeword) => codeword,
+			Self::Mapped(codeword) => codeword,
+		}
+	}
+}
+
+impl<P> DerefMut for CodewordStorage<P> {
+	fn deref_mut(&mut self) -> &mut [P] {
+		match self {
+			Self::Memory(codeword) => codeword,
+			Self::Mapped(codeword) => codeword,
+		}
+	}
+}
+
+impl<P> From<Vec<P>> for CodewordStorage<P> {
+	fn from(codeword: Vec<P>) -> Self {
+		Self::Memory(codeword)
+	}
+}
+
+/// Clones the codeword into memory.
+impl<P: Clone> Clone for CodewordStorage<P> {
+	fn clone(&self) -> Self {
+		Self::Memory(self.to_vec())
+	}
+}
+
+impl<P: PartialEq> PartialEq for CodewordStorage<P> {
+	fn eq(&self, other: &Self) -> bool {
+		**self == **other
+	}
+}
+
+impl<P: Debug> Debug for CodewordStorage<P> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Memory(codeword) => f.debug_tuple("Memory").field(codeword).finish(),
+			Self::Mapped(codeword) => f.debug_tuple("Mapped").field(&codeword.len).finish(),
+		}
+	}
+}
+
+/// A codeword in an anonymous memory-mapped temporary file.
+pub struct MappedCodeword<P> {
+	mmap: MmapMut,
+	len: usize,
+	_phantom: PhantomData<P>,
+}
+
+impl<P: PackedField> MappedCodeword<P> {
+	fn zeroed(dir: &Path, len: usize) -> Result<Self, Error> {
+		let file = tempfile::tempfile_in(dir)?;
+		// Extending the file fills it with zeros. Mappings of zero bytes are not supported on all
+		// platforms.
+		file.set_len((len * size_of::<P>()).max(1) as u64)?;
+		let mmap = unsafe {
+			// SAFETY: the file is unlinked on creation, so no other process modifies it
+			MmapMut::map_mut(&file)?
+		};
+		Ok(Self {
+			mmap,
+			len,
+			_phantom: PhantomData,
+		})
+	}
+}
+
+impl<P> Deref for MappedCodeword<P> {
+	type Target = [P];
+
+	fn deref(&self) -> &[P] {
+		unsafe {
+			// SAFETY: the mapping holds `len` elements, which were zeroed on allocation and are
+			// only written through `deref_mut`. A zeroed PackedField is valid, and the mapping is
+			// page aligned, which suffices for a packed field element.
+			slice::from_raw_parts(self.mmap.as_ptr().cast(), self.len)
+		}
+	}
+}
+
+impl<P> DerefMut for MappedCodeword<P> {
+	fn deref_mut(&mut self) -> &mut [P] {
+		unsafe {
+			// SAFETY: see `deref`
+			slice::from_raw_parts_mut(self.mmap.as_mut_ptr().cast(), self.len)
+		}
+	}
+}
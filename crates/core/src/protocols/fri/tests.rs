@@ -1,16 +1,25 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::{iter::repeat_with, vec};
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	cell::Cell,
+	iter::repeat_with,
+	vec,
+};
 
+use binius_compute::{ComputeLayer, alloc::BumpAllocator, cpu::CpuLayer};
 use binius_field::{
-	BinaryField, BinaryField16b, BinaryField32b, BinaryField128b, ExtensionField,
-	PackedBinaryField16x16b, PackedField, TowerField,
+	BinaryField, BinaryField16b, BinaryField32b, BinaryField128b, ExtensionField, Field,
+	PackedBinaryField16x16b, PackedExtension, PackedField, TowerField,
 	arch::{OptimalUnderlier128b, packed_64::PackedBinaryField4x16b},
 	as_packed_field::{PackScalar, PackedType},
 	underlier::UnderlierType,
 };
 use binius_hal::{ComputationBackendExt, make_portable_backend};
-use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
+use binius_hash::{
+	groestl::{Groestl256, Groestl256ByteCompression},
+	sha2::Sha256Compression,
+};
 use binius_math::{MultilinearExtension, MultilinearQuery, fold_right};
 use binius_maybe_rayon::prelude::ParallelIterator;
 use binius_ntt::{AdditiveNTT, NTTShape, SingleThreadedNTT, fri::fold_interleaved};
@@ -18,17 +27,20 @@ use binius_utils::checked_arithmetics::log2_strict_usize;
 use bytemuck::zeroed_vec;
 use proptest::prelude::*;
 use rand::prelude::*;
+use sha2::Sha256;
 
 use super::to_par_scalar_big_chunks;
 use crate::{
 	fiat_shamir::{CanSample, HasherChallenger},
-	merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver},
+	merkle_tree::{
+		BinaryMerkleTreeProver, MerkleTreeProver, QuaternaryMerkleTreeProver, StorageConfig,
+	},
 	protocols::fri::{
-		self, CommitOutput, FRIFolder, FRIParams, FRIVerifier, FoldRoundOutput,
-		to_par_scalar_small_chunks,
+		self, CommitOutput, DeviceFRIFolder, Error, FRIFolder, FRIParams, FRITrace, FRIVerifier,
+		FoldRoundOutput, VerifierPlan, to_par_scalar_small_chunks,
 	},
 	reed_solomon::reed_solomon::ReedSolomonCode,
-	transcript::ProverTranscript,
+	transcript::{ProverTranscript, VerifierTranscript},
 };
 
 proptest! {
@@ -41,10 +53,10 @@ proptest! {
 fn test_help_fri_compatible_ntt_domains(log_dim: usize, arity: usize) {
 	let ntt = SingleThreadedNTT::<BinaryField32b>::new(32).unwrap();
 
-	let msg = repeat_with(|| BinaryField32b::random(&mut thread_rng()))
+	let msg = repeat_with(|| <BinaryField32b as Field>::random(&mut thread_rng()))
 		.take(1 << (log_dim + arity))
 		.collect::<Vec<_>>();
-	let challenges = repeat_with(|| BinaryField32b::random(&mut thread_rng()))
+	let challenges = repeat_with(|| <BinaryField32b as Field>::random(&mut thread_rng()))
 		.take(arity)
 		.collect::<Vec<_>>();
 
@@ -340,3 +352,764 @@ fn test_parallel_iterator_for_commitments() {
 
 	assert_eq!(collected_smaller, collected_bigger);
 }
+
+#[test]
+fn test_commit_prove_verify_success_quaternary_merkle_tree() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover =
+		QuaternaryMerkleTreeProver::<_, Sha256, _>::new(Sha256Compression::default());
+
+	let log_dimension = 8;
+	let committed_rs_code = ReedSolomonCode::<BinaryField16b>::new(log_dimension, 2).unwrap();
+	let params =
+		FRIParams::new(ReedSolomonCode::new(log_dimension, 2).unwrap(), 0, vec![2, 1, 2], 5)
+			.unwrap();
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	let msg = repeat_with(|| <PackedType<U, F>>::random(&mut rng))
+		.take(committed_rs_code.dim() >> <PackedType<U, F>>::LOG_WIDTH)
+		.collect::<Vec<_>>();
+
+	let CommitOutput {
+		commitment: codeword_commitment,
+		committed: codeword_committed,
+		codeword,
+	} = fri::commit_interleaved(&committed_rs_code, &params, &ntt, &merkle_prover, &msg).unwrap();
+
+	let mut round_prover =
+		FRIFolder::new(&params, &ntt, &merkle_prover, &codeword, &codeword_committed).unwrap();
+
+	let mut prover_challenger = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let mut challenges = Vec::with_capacity(params.n_fold_rounds());
+	let mut round_commitments = Vec::with_capacity(params.n_oracles());
+	for _ in 0..params.n_fold_rounds() {
+		let challenge = prover_challenger.sample();
+		challenges.push(challenge);
+		if let FoldRoundOutput::Commitment(round_commitment) =
+			round_prover.execute_fold_round(challenge).unwrap()
+		{
+			round_commitments.push(round_commitment);
+		}
+	}
+	round_prover.finish_proof(&mut prover_challenger).unwrap();
+
+	let mut verifier_challenger = prover_challenger.into_verifier();
+	let _: Vec<F> = verifier_challenger.sample_vec(params.n_fold_rounds());
+	let verifier = FRIVerifier::new(
+		&params,
+		merkle_prover.scheme(),
+		&codeword_commitment,
+		&round_commitments,
+		&challenges,
+	)
+	.unwrap();
+	let final_fri_value = verifier.verify(&mut verifier_challenger).unwrap();
+
+	let eval_query = make_portable_backend()
+		.multilinear_query::<F>(&challenges)
+		.unwrap();
+	let multilin = MultilinearExtension::from_values_slice(&msg).unwrap();
+	assert_eq!(multilin.evaluate(&eval_query).unwrap(), final_fri_value);
+}
+
+#[test]
+fn test_commit_prove_verify_with_grinding() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let log_dimension = 8;
+	let committed_rs_code = ReedSolomonCode::<BinaryField16b>::new(log_dimension, 2).unwrap();
+	let params = FRIParams::new(ReedSolomonCode::new(log_dimension, 2).unwrap(), 0, vec![2, 2], 1)
+		.unwrap()
+		.with_grinding(32, 8)
+		.unwrap();
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	let msg = repeat_with(|| <PackedType<U, F>>::random(&mut rng))
+		.take(committed_rs_code.dim() >> <PackedType<U, F>>::LOG_WIDTH)
+		.collect::<Vec<_>>();
+
+	let CommitOutput {
+		commitment: codeword_commitment,
+		committed: codeword_committed,
+		codeword,
+	} = fri::commit_interleaved(&committed_rs_code, &params, &ntt, &merkle_prover, &msg).unwrap();
+
+	let mut round_prover =
+		FRIFolder::new(&params, &ntt, &merkle_prover, &codeword, &codeword_committed).unwrap();
+
+	let mut prover_challenger = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let mut challenges = Vec::with_capacity(params.n_fold_rounds());
+	let mut round_commitments = Vec::with_capacity(params.n_oracles());
+	for _ in 0..params.n_fold_rounds() {
+		let challenge = prover_challenger.sample();
+		challenges.push(challenge);
+		if let FoldRoundOutput::Commitment(round_commitment) =
+			round_prover.execute_fold_round(challenge).unwrap()
+		{
+			round_commitments.push(round_commitment);
+		}
+	}
+	round_prover.finish_proof(&mut prover_challenger).unwrap();
+
+	let mut verifier_challenger = prover_challenger.into_verifier();
+	let _: Vec<F> = verifier_challenger.sample_vec(params.n_fold_rounds());
+	let verifier = FRIVerifier::new(
+		&params,
+		merkle_prover.scheme(),
+		&codeword_commitment,
+		&round_commitments,
+		&challenges,
+	)
+	.unwrap();
+	let final_fri_value = verifier.verify(&mut verifier_challenger).unwrap();
+	verifier_challenger.finalize().unwrap();
+
+	let eval_query = make_portable_backend()
+		.multilinear_query::<F>(&challenges)
+		.unwrap();
+	let multilin = MultilinearExtension::from_values_slice(&msg).unwrap();
+	assert_eq!(multilin.evaluate(&eval_query).unwrap(), final_fri_value);
+}
+
+#[test]
+fn test_commit_prove_verify_batched_commitments() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let log_dimension = 8;
+	let committed_rs_code = ReedSolomonCode::<BinaryField16b>::new(log_dimension, 2).unwrap();
+	let params =
+		FRIParams::new(ReedSolomonCode::new(log_dimension, 2).unwrap(), 0, vec![2, 2], 3).unwrap();
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	// Commit to three messages independently, as if they were committed at different times.
+	let msgs = repeat_with(|| {
+		repeat_with(|| <PackedType<U, F>>::random(&mut rng))
+			.take(committed_rs_code.dim() >> <PackedType<U, F>>::LOG_WIDTH)
+			.collect::<Vec<_>>()
+	})
+	.take(3)
+	.collect::<Vec<_>>();
+	let commit_outputs = msgs
+		.iter()
+		.map(|msg| {
+			fri::commit_interleaved(&committed_rs_code, &params, &ntt, &merkle_prover, msg).unwrap()
+		})
+		.collect::<Vec<_>>();
+
+	let mut prover_challenger = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	for commit_output in &commit_outputs {
+		prover_challenger.message().write(&commit_output.commitment);
+	}
+	let coeffs: Vec<F> = prover_challenger.sample_vec(commit_outputs.len() - 1);
+
+	let (first, rest) = commit_outputs.split_first().unwrap();
+	let mut round_prover =
+		FRIFolder::new(&params, &ntt, &merkle_prover, &first.codeword, &first.committed).unwrap();
+	for (commit_output, &coeff) in rest.iter().zip(&coeffs) {
+		round_prover = round_prover
+			.with_batched(&commit_output.codeword, &commit_output.committed, coeff)
+			.unwrap();
+	}
+
+	let mut challenges = Vec::with_capacity(params.n_fold_rounds());
+	let mut round_commitments = Vec::with_capacity(params.n_oracles());
+	for _ in 0..params.n_fold_rounds() {
+		let challenge = prover_challenger.sample();
+		challenges.push(challenge);
+		if let FoldRoundOutput::Commitment(round_commitment) =
+			round_prover.execute_fold_round(challenge).unwrap()
+		{
+			round_commitments.push(round_commitment);
+		}
+	}
+	round_prover.finish_proof(&mut prover_challenger).unwrap();
+
+	let mut verifier_challenger = prover_challenger.into_verifier();
+	let commitments = (0..commit_outputs.len())
+		.map(|_| verifier_challenger.message().read().unwrap())
+		.collect::<Vec<_>>();
+	let _: Vec<F> = verifier_challenger.sample_vec(coeffs.len());
+	let _: Vec<F> = verifier_challenger.sample_vec(params.n_fold_rounds());
+	let mut verifier = FRIVerifier::new(
+		&params,
+		merkle_prover.scheme(),
+		&commitments[0],
+		&round_commitments,
+		&challenges,
+	)
+	.unwrap();
+	for (commitment, &coeff) in commitments[1..].iter().zip(&coeffs) {
+		verifier = verifier.with_batched(commitment, coeff).unwrap();
+	}
+	let final_fri_value = verifier.verify(&mut verifier_challenger).unwrap();
+	verifier_challenger.finalize().unwrap();
+
+	// The folded value is the same linear combination of the evaluations of the messages.
+	let eval_query = make_portable_backend()
+		.multilinear_query::<F>(&challenges)
+		.unwrap();
+	let evals = msgs
+		.iter()
+		.map(|msg| {
+			MultilinearExtension::from_values_slice(msg)
+				.unwrap()
+				.evaluate(&eval_query)
+				.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let expected_value = evals[0]
+		+ evals[1..]
+			.iter()
+			.zip(&coeffs)
+			.map(|(&eval, &coeff)| eval * coeff)
+			.sum::<F>();
+	assert_eq!(expected_value, final_fri_value);
+}
+
+#[test]
+fn test_commit_prove_verify_batched_at_round() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let log_dimension = 8;
+	let params = FRIParams::<F, BinaryField16b>::new(
+		ReedSolomonCode::new(log_dimension, 2).unwrap(),
+		0,
+		vec![2, 2, 2],
+		3,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	// The shorter codeword joins after the first oracle, which is committed after two rounds.
+	let fold_round = 2;
+	let batched_params = params.batched_codeword_params(&ntt, fold_round).unwrap();
+	assert_eq!(batched_params.n_fold_rounds(), params.n_fold_rounds() - fold_round);
+	assert_eq!(batched_params.fold_arities(), &params.fold_arities()[1..]);
+	// The last oracle is sent in the clear, so no codeword can be batched into it.
+	assert!(params.batched_codeword_params(&ntt, 6).is_err());
+	// No oracle is committed after three rounds.
+	assert!(params.batched_codeword_params(&ntt, 3).is_err());
+
+	let [msg, batched_msg] = [&params, &batched_params].map(|params| {
+		repeat_with(|| <PackedType<U, F>>::random(&mut rng))
+			.take(params.rs_code().dim() >> <PackedType<U, F>>::LOG_WIDTH)
+			.collect::<Vec<_>>()
+	});
+	let commit_output =
+		fri::commit_interleaved(params.rs_code(), &params, &ntt, &merkle_prover, &msg).unwrap();
+	let batched_commit_output = fri::commit_interleaved(
+		batched_params.rs_code(),
+		&batched_params,
+		&ntt,
+		&merkle_prover,
+		&batched_msg,
+	)
+	.unwrap();
+
+	let mut prover_challenger = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prover_challenger.message().write(&commit_output.commitment);
+	prover_challenger
+		.message()
+		.write(&batched_commit_output.commitment);
+	let coeff: F = prover_challenger.sample();
+
+	let mut round_prover = FRIFolder::new(
+		&params,
+		&ntt,
+		&merkle_prover,
+		&commit_output.codeword,
+		&commit_output.committed,
+	)
+	.unwrap()
+	.with_batched_at_round(
+		fold_round,
+		&batched_commit_output.codeword,
+		&batched_commit_output.committed,
+		coeff,
+	)
+	.unwrap();
+
+	let mut challenges = Vec::with_capacity(params.n_fold_rounds());
+	let mut round_commitments = Vec::with_capacity(params.n_oracles());
+	for _ in 0..params.n_fold_rounds() {
+		let challenge = prover_challenger.sample();
+		challenges.push(challenge);
+		if let FoldRoundOutput::Commitment(round_commitment) =
+			round_prover.execute_fold_round(challenge).unwrap()
+		{
+			round_commitments.push(round_commitment);
+		}
+	}
+	round_prover.finish_proof(&mut prover_challenger).unwrap();
+	let proof = prover_challenger.finalize();
+
+	let verify = |coeff: F| {
+		let mut verifier_challenger =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+		let commitment = verifier_challenger.message().read().unwrap();
+		let batched_commitment = verifier_challenger.message().read().unwrap();
+		let _: F = verifier_challenger.sample();
+		let _: Vec<F> = verifier_challenger.sample_vec(params.n_fold_rounds());
+		let final_fri_value = FRIVerifier::new(
+			&params,
+			merkle_prover.scheme(),
+			&commitment,
+			&round_commitments,
+			&challenges,
+		)?
+		.with_batched_at_round(fold_round, &batched_commitment, coeff)?
+		.verify(&mut verifier_challenger)?;
+		verifier_challenger.finalize()?;
+		Ok::<_, Error>(final_fri_value)
+	};
+	let final_fri_value = verify(coeff).unwrap();
+
+	// The folded value adds the evaluation of the batched message at the challenges of the rounds
+	// after it joins.
+	let evaluate = |msg: &[PackedType<U, F>], challenges: &[F]| {
+		let eval_query = make_portable_backend()
+			.multilinear_query::<F>(challenges)
+			.unwrap();
+		MultilinearExtension::from_values_slice(msg)
+			.unwrap()
+			.evaluate(&eval_query)
+			.unwrap()
+	};
+	let expected_value =
+		evaluate(&msg, &challenges) + evaluate(&batched_msg, &challenges[fold_round..]) * coeff;
+	assert_eq!(expected_value, final_fri_value);
+
+	// The oracle the batched codeword is added to is inconsistent with any other coefficient.
+	assert!(verify(coeff + F::ONE).is_err());
+}
+
+#[test]
+fn test_device_folder_matches_host_folder() {
+	type F = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let log_dimension = 8;
+	let log_batch_size = 2;
+	let committed_rs_code = ReedSolomonCode::<BinaryField16b>::new(log_dimension, 2).unwrap();
+	let params = FRIParams::new(
+		ReedSolomonCode::new(log_dimension, 2).unwrap(),
+		log_batch_size,
+		vec![3, 2, 2],
+		3,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	let msg = repeat_with(|| <F as Field>::random(&mut rng))
+		.take(committed_rs_code.dim() << log_batch_size)
+		.collect::<Vec<_>>();
+	let CommitOutput {
+		commitment,
+		committed,
+		codeword,
+	} = fri::commit_interleaved(&committed_rs_code, &params, &ntt, &merkle_prover, &msg).unwrap();
+
+	let mut host_folder =
+		FRIFolder::new(&params, &ntt, &merkle_prover, &codeword, &committed).unwrap();
+	let mut host_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	host_transcript.message().write(&commitment);
+	for _ in 0..params.n_fold_rounds() {
+		let challenge = host_transcript.sample();
+		if let FoldRoundOutput::Commitment(round_commitment) =
+			host_folder.execute_fold_round(challenge).unwrap()
+		{
+			host_transcript.message().write(&round_commitment);
+		}
+	}
+	host_folder.finish_proof(&mut host_transcript).unwrap();
+
+	// The CPU compute layer runs the device fold phase in host memory.
+	let hal = CpuLayer::<F>::default();
+	let mut codeword_dev = vec![F::ZERO; codeword.len()];
+	hal.copy_h2d(&codeword, &mut codeword_dev.as_mut_slice())
+		.unwrap();
+	let mut dev_mem = vec![F::ZERO; 1 << params.rs_code().log_len()];
+	let dev_alloc = BumpAllocator::new(dev_mem.as_mut_slice());
+	let mut device_folder = DeviceFRIFolder::new(
+		&hal,
+		&dev_alloc,
+		&params,
+		&ntt,
+		&merkle_prover,
+		&codeword,
+		codeword_dev.as_slice(),
+		&committed,
+	)
+	.unwrap();
+	let mut device_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	device_transcript.message().write(&commitment);
+	for _ in 0..params.n_fold_rounds() {
+		let challenge = device_transcript.sample();
+		if let FoldRoundOutput::Commitment(round_commitment) =
+			device_folder.execute_fold_round(challenge).unwrap()
+		{
+			device_transcript.message().write(&round_commitment);
+		}
+	}
+	device_folder.finish_proof(&mut device_transcript).unwrap();
+
+	assert_eq!(device_transcript.finalize(), host_transcript.finalize());
+}
+
+#[test]
+fn test_commit_prove_verify_subfield_codeword() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+	type FC = BinaryField32b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	// The codeword is over B32, with a batch size of 8, and is folded with B128 challenges.
+	let log_dimension = 6;
+	let params = FRIParams::<F, BinaryField16b>::new_for_subfield_codeword::<FC>(
+		ReedSolomonCode::new(log_dimension, 2).unwrap(),
+		3,
+		vec![2, 2],
+		3,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	let msg = repeat_with(|| <PackedType<U, FC>>::random(&mut rng))
+		.take(1 << (log_dimension + 3 - <PackedType<U, FC>>::LOG_WIDTH))
+		.collect::<Vec<_>>();
+
+	let CommitOutput {
+		commitment: codeword_commitment,
+		committed: codeword_committed,
+		codeword,
+	} = fri::commit_interleaved_subfield::<_, _, _, PackedType<U, F>, _, _, _, _, _>(
+		&params,
+		&ntt,
+		&merkle_prover,
+		&msg,
+	)
+	.unwrap();
+
+	let mut round_prover =
+		FRIFolder::new(&params, &ntt, &merkle_prover, &codeword, &codeword_committed).unwrap();
+
+	let mut prover_challenger = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let mut challenges = Vec::with_capacity(params.n_fold_rounds());
+	let mut round_commitments = Vec::with_capacity(params.n_oracles());
+	for _ in 0..params.n_fold_rounds() {
+		let challenge = prover_challenger.sample();
+		challenges.push(challenge);
+		if let FoldRoundOutput::Commitment(round_commitment) =
+			round_prover.execute_fold_round(challenge).unwrap()
+		{
+			round_commitments.push(round_commitment);
+		}
+	}
+	round_prover.finish_proof(&mut prover_challenger).unwrap();
+
+	let mut verifier_challenger = prover_challenger.into_verifier();
+	let _: Vec<F> = verifier_challenger.sample_vec(params.n_fold_rounds());
+	let verifier = FRIVerifier::new(
+		&params,
+		merkle_prover.scheme(),
+		&codeword_commitment,
+		&round_commitments,
+		&challenges,
+	)
+	.unwrap();
+	let final_fri_value = verifier.verify(&mut verifier_challenger).unwrap();
+	verifier_challenger.finalize().unwrap();
+
+	// The fully-folded value is the evaluation of the message packed into B128 elements.
+	let packed_msg = <PackedType<U, F>>::cast_exts(&msg);
+	let eval_query = make_portable_backend()
+		.multilinear_query::<F>(&challenges)
+		.unwrap();
+	let multilin = MultilinearExtension::from_values_slice(packed_msg).unwrap();
+	assert_eq!(multilin.evaluate(&eval_query).unwrap(), final_fri_value);
+}
+
+#[test]
+fn test_verifier_plan_reused_across_proofs() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let log_dimension = 8;
+	let committed_rs_code = ReedSolomonCode::<BinaryField16b>::new(log_dimension, 2).unwrap();
+	let params =
+		FRIParams::new(ReedSolomonCode::new(log_dimension, 2).unwrap(), 0, vec![3, 2], 2).unwrap();
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	let plan = VerifierPlan::new(&params, merkle_prover.scheme())
+		.unwrap()
+		.precompute_twiddles();
+
+	for _ in 0..2 {
+		let msg = repeat_with(|| <PackedType<U, F>>::random(&mut rng))
+			.take(committed_rs_code.dim() >> <PackedType<U, F>>::LOG_WIDTH)
+			.collect::<Vec<_>>();
+
+		let CommitOutput {
+			commitment: codeword_commitment,
+			committed: codeword_committed,
+			codeword,
+		} = fri::commit_interleaved(&committed_rs_code, &params, &ntt, &merkle_prover, &msg).unwrap();
+
+		let mut round_prover =
+			FRIFolder::new(&params, &ntt, &merkle_prover, &codeword, &codeword_committed).unwrap();
+
+		let mut prover_challenger = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let mut challenges = Vec::with_capacity(params.n_fold_rounds());
+		let mut round_commitments = Vec::with_capacity(params.n_oracles());
+		for _ in 0..params.n_fold_rounds() {
+			let challenge = prover_challenger.sample();
+			challenges.push(challenge);
+			if let FoldRoundOutput::Commitment(round_commitment) =
+				round_prover.execute_fold_round(challenge).unwrap()
+			{
+				round_commitments.push(round_commitment);
+			}
+		}
+		round_prover.finish_proof(&mut prover_challenger).unwrap();
+
+		let mut verifier_challenger = prover_challenger.into_verifier();
+		let _: Vec<F> = verifier_challenger.sample_vec(params.n_fold_rounds());
+		let verifier = FRIVerifier::new(
+			&params,
+			merkle_prover.scheme(),
+			&codeword_commitment,
+			&round_commitments,
+			&challenges,
+		)
+		.unwrap();
+		let final_fri_value = verifier
+			.verify_with_plan(&plan, &mut verifier_challenger)
+			.unwrap();
+		verifier_challenger.finalize().unwrap();
+
+		let eval_query = make_portable_backend()
+			.multilinear_query::<F>(&challenges)
+			.unwrap();
+		let multilin = MultilinearExtension::from_values_slice(&msg).unwrap();
+		assert_eq!(multilin.evaluate(&eval_query).unwrap(), final_fri_value);
+	}
+
+	// A plan built for other fold arities is rejected.
+	let other_params = FRIParams::<F, BinaryField16b>::new(
+		ReedSolomonCode::new(log_dimension, 2).unwrap(),
+		0,
+		vec![2, 3],
+		2,
+	)
+	.unwrap();
+	let other_plan = VerifierPlan::new(&other_params, merkle_prover.scheme()).unwrap();
+	let commitment = Default::default();
+	let round_commitments = vec![Default::default(); params.n_oracles()];
+	let challenges = vec![F::ZERO; params.n_fold_rounds()];
+	let verifier = FRIVerifier::new(
+		&params,
+		merkle_prover.scheme(),
+		&commitment,
+		&round_commitments,
+		&challenges,
+	)
+	.unwrap();
+	let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new().into_verifier();
+	assert!(matches!(
+		verifier.verify_with_plan(&other_plan, &mut transcript),
+		Err(Error::InvalidArgs(_))
+	));
+}
+
+/// Counts the bytes allocated by each thread, to bound the memory of the verifier.
+struct CountingAllocator;
+
+thread_local! {
+	static ALLOCATED_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let _ =
+			ALLOCATED_BYTES.try_with(|allocated| allocated.set(allocated.get() + layout.size()));
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		unsafe { System.dealloc(ptr, layout) }
+	}
+}
+
+#[global_allocator]
+static GLOBAL_ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the result of `f` and the number of bytes it allocated on the current thread.
+fn count_allocated_bytes<T>(f: impl FnOnce() -> T) -> (T, usize) {
+	let start = ALLOCATED_BYTES.with(Cell::get);
+	let result = f();
+	(result, ALLOCATED_BYTES.with(Cell::get) - start)
+}
+
+#[test]
+fn test_verifier_plan_memory_independent_of_codeword_length() {
+	type F = BinaryField128b;
+	type FA = BinaryField32b;
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	// The plan built for a one-shot verification of a code of length 2^28 with few queries
+	// computes the twiddles on the fly, and allocates nothing near the codeword length.
+	let log_dimension = 26;
+	let params = FRIParams::<F, FA>::new(
+		ReedSolomonCode::new(log_dimension, 2).unwrap(),
+		0,
+		vec![4, 4, 4, 4, 4],
+		4,
+	)
+	.unwrap();
+	let (_, allocated) =
+		count_allocated_bytes(|| VerifierPlan::new(&params, merkle_prover.scheme()).unwrap());
+	assert!(allocated < 1 << 16, "the plan allocated {allocated} bytes");
+
+	// Precomputing the twiddles of a shorter code allocates a table as long as its codeword.
+	let params =
+		FRIParams::<F, FA>::new(ReedSolomonCode::new(16, 2).unwrap(), 0, vec![4, 4], 4).unwrap();
+	let plan = VerifierPlan::new(&params, merkle_prover.scheme()).unwrap();
+	let (_, allocated) = count_allocated_bytes(|| plan.precompute_twiddles());
+	assert!(allocated >= (size_of::<FA>() << params.rs_code().log_len()) / 2);
+}
+
+#[test]
+fn test_fold_trace_locates_divergent_round() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let log_dimension = 8;
+	let committed_rs_code = ReedSolomonCode::<BinaryField16b>::new(log_dimension, 2).unwrap();
+	let params =
+		FRIParams::new(ReedSolomonCode::new(log_dimension, 2).unwrap(), 0, vec![3, 2], 2).unwrap();
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	let msg = repeat_with(|| <PackedType<U, F>>::random(&mut rng))
+		.take(committed_rs_code.dim() >> <PackedType<U, F>>::LOG_WIDTH)
+		.collect::<Vec<_>>();
+
+	let CommitOutput {
+		committed: codeword_committed,
+		codeword,
+		..
+	} = fri::commit_interleaved(&committed_rs_code, &params, &ntt, &merkle_prover, &msg).unwrap();
+
+	let mut round_prover =
+		FRIFolder::new(&params, &ntt, &merkle_prover, &codeword, &codeword_committed)
+			.unwrap()
+			.with_trace();
+
+	let mut challenges = Vec::with_capacity(params.n_fold_rounds());
+	let mut round_commitments = Vec::with_capacity(params.n_oracles());
+	for _ in 0..params.n_fold_rounds() {
+		let challenge = <F as Field>::random(&mut rng);
+		challenges.push(challenge);
+		if let FoldRoundOutput::Commitment(round_commitment) =
+			round_prover.execute_fold_round(challenge).unwrap()
+		{
+			round_commitments.push(round_commitment);
+		}
+	}
+
+	let trace = round_prover.trace().unwrap().clone();
+	assert_eq!(trace.rounds.len(), params.n_fold_rounds());
+	assert_eq!(trace.rounds[2].commitment.as_ref(), Some(&round_commitments[0]));
+	assert_eq!(trace.rounds[4].commitment.as_ref(), Some(&round_commitments[1]));
+	assert!(trace.rounds[3].commitment.is_none());
+
+	let (terminate_codeword, _) = round_prover.finalize().unwrap();
+	assert_eq!(trace.terminate_codeword.as_ref(), Some(&terminate_codeword));
+
+	let verifier_trace = FRITrace::from_rounds(&params, &challenges, &round_commitments);
+	assert_eq!(trace.first_divergence(&verifier_trace), None);
+
+	// A verifier that received a different second oracle diverges in the round committing it.
+	let mut round_commitments = round_commitments;
+	round_commitments[1] = Default::default();
+	let verifier_trace = FRITrace::from_rounds(&params, &challenges, &round_commitments);
+	assert_eq!(trace.first_divergence(&verifier_trace), Some(4));
+}
+
+#[test]
+fn test_pipelined_commit_matches_commit() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+	type FA = BinaryField16b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	for (log_inv_rate, log_batch_size, arities) in
+		[(2, 2, vec![3, 2]), (1, 0, vec![]), (3, 1, vec![1, 4])]
+	{
+		let log_dimension = 6;
+		let params = FRIParams::<F, FA>::new(
+			ReedSolomonCode::new(log_dimension, log_inv_rate).unwrap(),
+			log_batch_size,
+			arities,
+			2,
+		)
+		.unwrap();
+		let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+		let msg = repeat_with(|| <PackedType<U, F>>::random(&mut rng))
+			.take(1 << (log_dimension + log_batch_size - <PackedType<U, F>>::LOG_WIDTH))
+			.collect::<Vec<_>>();
+
+		let expected = fri::commit_interleaved_with(&params, &ntt, &merkle_prover, |buffer| {
+			buffer.copy_from_slice(&msg)
+		})
+		.unwrap();
+		let pipelined = fri::commit_interleaved_pipelined(
+			&params,
+			&ntt,
+			&merkle_prover,
+			&StorageConfig::Memory,
+			|buffer| buffer.copy_from_slice(&msg),
+		)
+		.unwrap();
+
+		assert_eq!(pipelined.commitment, expected.commitment);
+		assert_eq!(pipelined.codeword, expected.codeword);
+	}
+}
@@ -0,0 +1,100 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::iter;
+
+use binius_field::{BinaryField, ExtensionField};
+
+use super::{TerminateCodeword, common::FRIParams};
+
+/// A record of the fold phase of FRI, for locating the round in which a failing proof diverges.
+///
+/// A [`super::FRIFolder`] records a trace when constructed with [`super::FRIFolder::with_trace`].
+/// The verifier side of the same rounds is recorded with [`Self::from_rounds`], from the
+/// challenges it sampled and the commitments it received, and [`Self::first_divergence`] finds
+/// the first round in which the two disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FRITrace<F, Digest> {
+	/// The fold rounds, in order.
+	pub rounds: Vec<FRIRoundTrace<F, Digest>>,
+	/// The evaluations of the claimed terminal polynomial, once the last fold round has run.
+	pub terminate_codeword: Option<TerminateCodeword<F>>,
+}
+
+/// A fold round of a [`FRITrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FRIRoundTrace<F, Digest> {
+	/// The folding challenge of the round.
+	pub challenge: F,
+	/// The Merkle root of the folded codeword, if the round commits one.
+	pub commitment: Option<Digest>,
+}
+
+impl<F, Digest> Default for FRITrace<F, Digest> {
+	fn default() -> Self {
+		Self {
+			rounds: Vec::new(),
+			terminate_codeword: None,
+		}
+	}
+}
+
+impl<F: Clone, Digest: Clone> FRITrace<F, Digest> {
+	/// Records the trace of the fold rounds with the given challenges and round commitments.
+	///
+	/// The commitments are assigned to the rounds that commit an oracle under `params`. The
+	/// terminal codeword is not known from the rounds alone and is left empty.
+	pub fn from_rounds<FA>(
+		params: &FRIParams<F, FA>,
+		challenges: &[F],
+		round_commitments: &[Digest],
+	) -> Self
+	where
+		F: BinaryField + ExtensionField<FA>,
+		FA: BinaryField,
+	{
+		let mut commit_rounds = params
+			.fold_arities()
+			.iter()
+			.scan(0, |round, &arity| {
+				*round += arity;
+				Some(*round)
+			})
+			.zip(round_commitments)
+			.peekable();
+		let rounds = iter::zip(1.., challenges)
+			.map(|(round, challenge)| FRIRoundTrace {
+				challenge: challenge.clone(),
+				commitment: commit_rounds
+					.next_if(|&(commit_round, _)| commit_round == round)
+					.map(|(_, commitment)| commitment.clone()),
+			})
+			.collect();
+		Self {
+			rounds,
+			terminate_codeword: None,
+		}
+	}
+}
+
+impl<F: PartialEq, Digest: PartialEq> FRITrace<F, Digest> {
+	/// Returns the index of the first fold round in which the traces differ.
+	///
+	/// If all rounds recorded by both traces agree, the traces differ in the number of rounds or
+	/// in the terminal codeword, and the index is that of the first round recorded by only one of
+	/// them, or the number of rounds. Returns `None` if the traces agree, where a terminal
+	/// codeword recorded by only one of them is not a difference.
+	pub fn first_divergence(&self, other: &Self) -> Option<usize> {
+		if let Some(index) =
+			iter::zip(&self.rounds, &other.rounds).position(|(lhs, rhs)| lhs != rhs)
+		{
+			return Some(index);
+		}
+		if self.rounds.len() != other.rounds.len() {
+			return Some(self.rounds.len().min(other.rounds.len()));
+		}
+		match (&self.terminate_codeword, &other.terminate_codeword) {
+			(Some(lhs), Some(rhs)) if lhs != rhs => Some(self.rounds.len()),
+			_ => None,
+		}
+	}
+}
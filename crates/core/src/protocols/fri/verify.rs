@@ -1,21 +1,28 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::iter;
+use std::{borrow::Cow, iter};
 
 use binius_field::{BinaryField, ExtensionField, TowerField};
 use binius_hal::{ComputationBackend, make_portable_backend};
+use binius_maybe_rayon::prelude::*;
 use binius_ntt::{
-	SingleThreadedNTT,
+	AdditiveNTT, SingleThreadedNTT,
 	fri::{fold_chunk, fold_interleaved_chunk},
+	twiddle::{OnTheFlyTwiddleAccess, PrecomputedTwiddleAccess, TwiddleAccess},
 };
-use binius_utils::{DeserializeBytes, bail};
+use binius_utils::{DeserializeBytes, SerializeBytes, bail};
 use bytes::Buf;
+use either::Either;
 use itertools::izip;
 use tracing::instrument;
 
-use super::{VerificationError, common::vcs_optimal_layers_depths_iter, error::Error};
+use super::{
+	VerificationError,
+	common::{sample_query_indices, vcs_optimal_layers_depths_iter},
+	error::Error,
+};
 use crate::{
-	fiat_shamir::{CanSampleBits, Challenger},
+	fiat_shamir::Challenger,
 	merkle_tree::MerkleTreeScheme,
 	protocols::fri::common::FRIParams,
 	transcript::{TranscriptReader, VerifierTranscript},
@@ -38,6 +45,12 @@ where
 	codeword_commitment: &'a VCS::Digest,
 	/// Received commitments to the round messages.
 	round_commitments: &'a [VCS::Digest],
+	/// Received commitments to the codewords batched with the codeword, such as a mask, and
+	/// their coefficients in the folded linear combination.
+	batched: Vec<(&'a VCS::Digest, F)>,
+	/// Received commitments to the shorter codewords batched into round oracles, with the index of
+	/// the oracle each is added to and its coefficient.
+	batched_oracles: Vec<(usize, &'a VCS::Digest, F)>,
 	/// The challenges for each round.
 	interleave_tensor: Vec<F>,
 	/// The challenges for each round.
@@ -48,7 +61,7 @@ impl<'a, F, FA, VCS> FRIVerifier<'a, F, FA, VCS>
 where
 	F: TowerField + ExtensionField<FA>,
 	FA: BinaryField,
-	VCS: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+	VCS: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
 {
 	#[allow(clippy::too_many_arguments)]
 	pub fn new(
@@ -86,16 +99,78 @@ where
 			vcs,
 			codeword_commitment,
 			round_commitments,
+			batched: Vec::new(),
+			batched_oracles: Vec::new(),
 			interleave_tensor,
 			fold_challenges,
 		})
 	}
 
+	/// Verifies the query phase of a codeword masked with a committed random codeword, as proven
+	/// by a [`super::FRIFolder`] with a [`super::FRIMask`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if the parameters send no round oracles
+	pub fn with_mask(self, mask_commitment: &'a VCS::Digest, coeff: F) -> Result<Self, Error> {
+		self.with_batched(mask_commitment, coeff)
+	}
+
+	/// Verifies the query phase of a random linear combination of independently committed
+	/// codewords, as proven by a [`super::FRIFolder`] with the codewords batched by
+	/// [`super::FRIFolder::with_batched`] in the same order.
+	///
+	/// The returned fully-folded value is the linear combination of the values each codeword
+	/// would fold to, with coefficient one for the codeword given to [`Self::new`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if the parameters send no round oracles
+	pub fn with_batched(mut self, commitment: &'a VCS::Digest, coeff: F) -> Result<Self, Error> {
+		if self.params.n_oracles() == 0 {
+			bail!(Error::InvalidArgs("batching requires at least one round oracle".to_string()));
+		}
+		self.batched.push((commitment, coeff));
+		Ok(self)
+	}
+
+	/// Verifies the query phase of a FRI instance with a shorter codeword batched after
+	/// `fold_round` fold rounds, as proven by a [`super::FRIFolder`] with the codewords batched by
+	/// [`super::FRIFolder::with_batched_at_round`] in the same order.
+	///
+	/// The returned fully-folded value is the value the codeword given to [`Self::new`] folds to
+	/// plus `coeff` times the value the batched codeword folds to with the challenges of the rounds
+	/// after `fold_round`. With `fold_round` zero, this is [`Self::with_batched`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if no round oracle other than the last is committed after
+	///   `fold_round` fold rounds
+	pub fn with_batched_at_round(
+		mut self,
+		fold_round: usize,
+		commitment: &'a VCS::Digest,
+		coeff: F,
+	) -> Result<Self, Error> {
+		if fold_round == 0 {
+			return self.with_batched(commitment, coeff);
+		}
+
+		let oracle = self.params.batched_oracle(fold_round)?;
+		self.batched_oracles.push((oracle, commitment, coeff));
+		Ok(self)
+	}
+
 	/// Number of oracles sent during the fold rounds.
 	pub fn n_oracles(&self) -> usize {
 		self.params.n_oracles()
 	}
 
+	/// Verifies the FRI query phase, returning the fully-folded message value.
+	///
+	/// The verifier's memory usage is proportional to the proof size, which is dominated by the
+	/// query openings and the decommitted Merkle layers, and never to the codeword length. The NTT
+	/// twiddles are computed on the fly rather than tabulated over the code domain.
 	pub fn verify<Challenger_>(
 		&self,
 		transcript: &mut VerifierTranscript<Challenger_>,
@@ -103,98 +178,161 @@ where
 	where
 		Challenger_: Challenger,
 	{
-		let ntt = SingleThreadedNTT::with_subspace(self.params.rs_code().subspace())?;
+		let plan = VerifierPlan::new(self.params, self.vcs)?;
+		self.verify_with_plan(&plan, transcript)
+	}
+
+	/// Verifies the FRI query phase with a precomputed [`VerifierPlan`], returning the
+	/// fully-folded message value.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidArgs`] if the plan was built for parameters of a different shape
+	pub fn verify_with_plan<Challenger_, TA>(
+		&self,
+		plan: &VerifierPlan<FA, TA>,
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<F, Error>
+	where
+		Challenger_: Challenger,
+		TA: TwiddleAccess<FA> + Sync,
+	{
+		plan.check_params(self.params)?;
 
 		// Verify that the last oracle sent is a codeword.
-		let terminate_codeword_len =
-			1 << (self.params.n_final_challenges() + self.params.rs_code().log_inv_rate());
 		let mut advice = transcript.decommitment();
 		let terminate_codeword = advice
-			.read_scalar_slice(terminate_codeword_len)
+			.read_scalar_slice(plan.terminate_codeword_len)
 			.map_err(Error::TranscriptError)?;
-		let final_value = self.verify_last_oracle(&ntt, &terminate_codeword)?;
+		let final_value = self.verify_last_oracle(&plan.ntt, &terminate_codeword, &mut advice)?;
 
 		// Verify that the provided layers match the commitments.
-		let layers = vcs_optimal_layers_depths_iter(self.params, self.vcs)
-			.map(|layer_depth| advice.read_vec(1 << layer_depth))
+		let layers = plan
+			.oracles
+			.iter()
+			.map(|oracle| advice.read_vec(1 << oracle.layer_depth))
 			.collect::<Result<Vec<_>, _>>()?;
-		for (commitment, layer_depth, layer) in izip!(
+		for (oracle, (commitment, layout, layer)) in izip!(
 			iter::once(self.codeword_commitment).chain(self.round_commitments),
-			vcs_optimal_layers_depths_iter(self.params, self.vcs),
+			&plan.oracles,
 			&layers
-		) {
+		)
+		.enumerate()
+		{
 			self.vcs
-				.verify_layer(commitment, layer_depth, layer)
-				.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+				.verify_layer(commitment, layout.layer_depth, layer)
+				.map_err(|source| VerificationError::InvalidLayer { oracle, source })?;
 		}
-
-		// Verify the random openings against the decommitted layers.
-
-		let mut scratch_buffer = self.create_scratch_buffer();
-		for _ in 0..self.params.n_test_queries() {
-			let index = transcript.sample_bits(self.params.index_bits()) as usize;
-			self.verify_query_internal(
-				index,
-				&ntt,
-				&terminate_codeword,
-				&layers,
-				&mut transcript.decommitment(),
-				&mut scratch_buffer,
-			)?
+		// A codeword batched into a round oracle is opened with the cosets of the oracle, which
+		// follows the committed codeword in the plan.
+		let batched_layers = self
+			.batched
+			.iter()
+			.map(|&(commitment, _)| (commitment, 0))
+			.chain(
+				self.batched_oracles
+					.iter()
+					.map(|&(oracle, commitment, _)| (commitment, oracle + 1)),
+			)
+			.enumerate()
+			.map(|(batch_index, (commitment, plan_oracle))| {
+				let layer_depth = plan
+					.oracles
+					.get(plan_oracle)
+					.expect("batching requires a round oracle after the batched codeword")
+					.layer_depth;
+				let layer = advice.read_vec(1 << layer_depth)?;
+				self.vcs
+					.verify_layer(commitment, layer_depth, &layer)
+					.map_err(|source| VerificationError::InvalidBatchedLayer {
+						batch_index,
+						source,
+					})?;
+				Ok(layer)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let grinding_bits = self.params.grinding_bits();
+		if !transcript.check_grinding(grinding_bits)? {
+			bail!(VerificationError::InvalidProofOfWork { grinding_bits });
 		}
 
+		// Read the random openings sequentially, then verify them against the decommitted layers
+		// concurrently, since hashing the Merkle paths dominates the verifier cost.
+		let openings = sample_query_indices(self.params, transcript)
+			.into_iter()
+			.map(|index| self.read_query_openings(plan, index, &mut transcript.decommitment()))
+			.collect::<Result<Vec<_>, _>>()?;
+		openings.par_iter().try_for_each_init(
+			|| self.create_scratch_buffer(),
+			|scratch_buffer, openings| {
+				self.verify_query_openings(
+					openings,
+					plan,
+					&terminate_codeword,
+					&layers,
+					&batched_layers,
+					scratch_buffer,
+				)
+			},
+		)?;
+
 		Ok(final_value)
 	}
 
 	/// Verifies that the last oracle sent is a codeword.
 	///
-	/// Returns the fully-folded message value.
-	pub fn verify_last_oracle(
+	/// The opening of the last oracle against its commitment, such as the salts of its leaves, is
+	/// read from `advice`. Returns the fully-folded message value.
+	pub fn verify_last_oracle<B: Buf>(
 		&self,
-		ntt: &SingleThreadedNTT<FA>,
+		ntt: &impl AdditiveNTT<FA>,
 		terminate_codeword: &[F],
+		advice: &mut TranscriptReader<B>,
 	) -> Result<F, Error> {
 		let n_final_challenges = self.params.n_final_challenges();
 
 		self.vcs
-			.verify_vector(
+			.verify_vector_opening(
 				self.round_commitments
 					.last()
 					.unwrap_or(self.codeword_commitment),
 				terminate_codeword,
 				1 << n_final_challenges,
+				advice,
 			)
-			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+			.map_err(VerificationError::InvalidTerminateCodeword)?;
 
-		let repetition_codeword = if self.n_oracles() != 0 {
+		// The folded entries are checked as they are computed, so that the fully-folded codeword
+		// is never buffered.
+		let mut repetition_codeword = if self.n_oracles() != 0 {
 			let n_prior_challenges = self.fold_challenges.len() - n_final_challenges;
 			let final_challenges = &self.fold_challenges[n_prior_challenges..];
 			let mut scratch_buffer = vec![F::default(); 1 << n_final_challenges];
 
-			terminate_codeword
-				.chunks(1 << n_final_challenges)
-				.enumerate()
-				.map(|(i, coset_values)| {
-					scratch_buffer.copy_from_slice(coset_values);
-					fold_chunk(
-						ntt,
-						n_final_challenges + self.params.rs_code().log_inv_rate(),
-						i,
-						&mut scratch_buffer,
-						final_challenges,
-					)
-				})
-				.collect::<Vec<_>>()
+			Either::Left(
+				terminate_codeword
+					.chunks(1 << n_final_challenges)
+					.enumerate()
+					.map(move |(i, coset_values)| {
+						scratch_buffer.copy_from_slice(coset_values);
+						fold_chunk(
+							ntt,
+							n_final_challenges + self.params.rs_code().log_inv_rate(),
+							i,
+							&mut scratch_buffer,
+							final_challenges,
+						)
+					}),
+			)
 		} else {
 			// When the prover did not send any round oracles, fold the original interleaved
 			// codeword.
 
 			let fold_arity = self.params.rs_code().log_dim() + self.params.log_batch_size();
 			let mut scratch_buffer = vec![F::default(); 1 << self.params.rs_code().log_dim()];
-			terminate_codeword
-				.chunks(1 << fold_arity)
-				.enumerate()
-				.map(|(i, chunk)| {
+			Either::Right(terminate_codeword.chunks(1 << fold_arity).enumerate().map(
+				move |(i, chunk)| {
 					fold_interleaved_chunk(
 						ntt,
 						self.params.rs_code().log_len(),
@@ -205,18 +343,17 @@ where
 						self.fold_challenges,
 						&mut scratch_buffer,
 					)
-				})
-				.collect::<Vec<_>>()
+				},
+			))
 		};
 
-		let final_value = repetition_codeword[0];
+		let final_value = repetition_codeword
+			.next()
+			.expect("the terminate codeword is non-empty");
 
 		// Check that the fully-folded purported codeword is a repetition codeword.
-		if repetition_codeword[1..]
-			.iter()
-			.any(|&entry| entry != final_value)
-		{
-			return Err(VerificationError::IncorrectDegree.into());
+		if let Some(index) = repetition_codeword.position(|entry| entry != final_value) {
+			return Err(VerificationError::IncorrectDegree { index: index + 1 }.into());
 		}
 
 		Ok(final_value)
@@ -231,39 +368,101 @@ where
 	/// ## Arguments
 	///
 	/// * `index` - an index into the original codeword domain
+	/// * `plan` - the precomputed plan for the parameters
+	/// * `layers` - the decommitted Merkle layers of the oracles
+	/// * `batched_layers` - the decommitted Merkle layers of the batched codewords, with the
+	///   codewords batched into round oracles after those batched with the committed codeword
 	/// * `proof` - a query proof
 	pub fn verify_query<B: Buf>(
 		&self,
 		index: usize,
-		ntt: &SingleThreadedNTT<FA>,
+		plan: &VerifierPlan<FA, impl TwiddleAccess<FA>>,
 		terminate_codeword: &[F],
 		layers: &[Vec<VCS::Digest>],
+		batched_layers: &[Vec<VCS::Digest>],
 		advice: &mut TranscriptReader<B>,
 	) -> Result<(), Error> {
-		self.verify_query_internal(
-			index,
-			ntt,
+		plan.check_params(self.params)?;
+		let openings = self.read_query_openings(plan, index, advice)?;
+		self.verify_query_openings(
+			&openings,
+			plan,
 			terminate_codeword,
 			layers,
-			advice,
+			batched_layers,
 			&mut self.create_scratch_buffer(),
 		)
 	}
 
+	/// Reads the coset openings of a FRI challenge query from the transcript without verifying
+	/// them.
+	fn read_query_openings<B: Buf>(
+		&self,
+		plan: &VerifierPlan<FA, impl TwiddleAccess<FA>>,
+		index: usize,
+		advice: &mut TranscriptReader<B>,
+	) -> Result<QueryOpenings<F, VCS::Digest>, Error> {
+		let mut batched_cosets = Vec::with_capacity(self.batched.len());
+		let mut batched_oracle_cosets = Vec::with_capacity(self.batched_oracles.len());
+		let mut read_coset = |coset_index: usize, layout: &OracleLayout| {
+			let values = advice.read_scalar_slice::<F>(1 << layout.arity)?;
+			let path = advice.read_vec(layout.path_len)?;
+			Ok::<_, Error>(CosetOpening {
+				coset_index,
+				tree_depth: layout.log_n_cosets,
+				values,
+				path,
+			})
+		};
+		let cosets = plan
+			.oracles
+			.iter()
+			.enumerate()
+			.map(|(i, layout)| {
+				// The first oracle is opened at the query index itself, as an interleaved chunk,
+				// and is followed by the openings of the batched codewords at the same index.
+				let coset_index = index >> layout.index_shift;
+				let coset = read_coset(coset_index, layout)?;
+				if i == 0 {
+					for _ in 0..self.batched.len() {
+						batched_cosets.push(read_coset(coset_index, layout)?);
+					}
+				}
+				// The round oracle with index `i - 1` is followed by the openings of the codewords
+				// batched into it.
+				for _ in self
+					.batched_oracles
+					.iter()
+					.filter(|&&(oracle, ..)| oracle + 1 == i)
+				{
+					batched_oracle_cosets.push(read_coset(coset_index, layout)?);
+				}
+				Ok(coset)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		Ok(QueryOpenings {
+			index,
+			cosets,
+			batched_cosets,
+			batched_oracle_cosets,
+		})
+	}
+
 	#[instrument(skip_all, name = "fri::FRIVerifier::verify_query", level = "debug")]
-	fn verify_query_internal<B: Buf>(
+	fn verify_query_openings(
 		&self,
-		mut index: usize,
-		ntt: &SingleThreadedNTT<FA>,
+		openings: &QueryOpenings<F, VCS::Digest>,
+		plan: &VerifierPlan<FA, impl TwiddleAccess<FA>>,
 		terminate_codeword: &[F],
 		layers: &[Vec<VCS::Digest>],
-		advice: &mut TranscriptReader<B>,
+		batched_layers: &[Vec<VCS::Digest>],
 		scratch_buffer: &mut [F],
 	) -> Result<(), Error> {
-		let mut arities_iter = self.params.fold_arities().iter().copied();
-
-		let mut layer_digest_and_optimal_layer_depth =
-			iter::zip(layers, vcs_optimal_layers_depths_iter(self.params, self.vcs));
+		let ntt = &plan.ntt;
+		let mut arities_iter = plan.oracles.iter().map(|oracle| oracle.arity);
+		let mut cosets_iter =
+			izip!(&openings.cosets, layers, plan.oracles.iter().map(|oracle| oracle.layer_depth));
 
 		let Some(first_fold_arity) = arities_iter.next() else {
 			// If there are no query proofs, that means that no oracles were sent during the FRI
@@ -272,63 +471,125 @@ where
 			return Ok(());
 		};
 
-		let (first_layer, first_optimal_layer_depth) = layer_digest_and_optimal_layer_depth
+		let (first_coset, first_layer, first_optimal_layer_depth) = cosets_iter
 			.next()
 			.expect("The length should be the same as the amount of proofs.");
 
 		// This is the round of the folding phase that the codeword to be folded is committed to.
 		let mut fold_round = 0;
-		let mut log_n_cosets = self.params.index_bits();
+		let mut index = openings.index;
 
 		// Check the first fold round before the main loop. It is special because in the first
 		// round we need to fold as an interleaved chunk instead of a regular coset.
 		let log_coset_size = first_fold_arity - self.params.log_batch_size();
-		let values = verify_coset_opening(
+		verify_coset_opening(
 			self.vcs,
-			index,
-			first_fold_arity,
+			openings.index,
+			0,
+			first_coset,
 			first_optimal_layer_depth,
-			log_n_cosets,
 			first_layer,
-			advice,
 		)?;
+		let first_values = if self.batched.is_empty() {
+			Cow::Borrowed(first_coset.values.as_slice())
+		} else {
+			let mut values = first_coset.values.clone();
+			for (batch_index, (&(_, coeff), coset, layer)) in
+				izip!(&self.batched, &openings.batched_cosets, batched_layers).enumerate()
+			{
+				self.vcs
+					.verify_opening_path(
+						coset.coset_index,
+						&coset.values,
+						first_optimal_layer_depth,
+						coset.tree_depth,
+						layer,
+						&coset.path,
+					)
+					.map_err(|source| VerificationError::InvalidBatchedOpening {
+						query_index: openings.index,
+						batch_index,
+						source,
+					})?;
+				for (value, &batched) in iter::zip(&mut values, &coset.values) {
+					*value += batched * coeff;
+				}
+			}
+			Cow::Owned(values)
+		};
 		let mut next_value = fold_interleaved_chunk(
 			ntt,
 			self.params.rs_code().log_len(),
 			self.params.log_batch_size(),
 			index,
-			&values,
+			&first_values,
 			&self.interleave_tensor,
 			&self.fold_challenges[fold_round..fold_round + log_coset_size],
 			scratch_buffer,
 		);
 		fold_round += log_coset_size;
 
-		for (i, (arity, (layer, optimal_layer_depth))) in
-			izip!(arities_iter, layer_digest_and_optimal_layer_depth).enumerate()
+		let mut batched_oracle_openings = izip!(
+			&openings.batched_oracle_cosets,
+			batched_layers.get(self.batched.len()..).unwrap_or_default()
+		)
+		.enumerate()
+		.map(|(i, opening)| (self.batched.len() + i, opening));
+		for (i, (arity, (coset, layer, optimal_layer_depth))) in
+			izip!(arities_iter, cosets_iter).enumerate()
 		{
-			let coset_index = index >> arity;
-
-			log_n_cosets -= arity;
+			let coset_index = coset.coset_index;
 
-			let mut values = verify_coset_opening(
+			verify_coset_opening(
 				self.vcs,
-				coset_index,
-				arity,
+				openings.index,
+				i + 1,
+				coset,
 				optimal_layer_depth,
-				log_n_cosets,
 				layer,
-				advice,
 			)?;
 
-			if next_value != values[index % (1 << arity)] {
+			// The oracle with index `i` is the folded codeword plus the codewords batched into it.
+			for &(_, _, coeff) in self
+				.batched_oracles
+				.iter()
+				.filter(|&&(oracle, ..)| oracle == i)
+			{
+				let Some((batch_index, (batched_coset, batched_layer))) =
+					batched_oracle_openings.next()
+				else {
+					bail!(Error::InvalidArgs(
+						"missing the opening or layer of a codeword batched into a round oracle"
+							.to_string()
+					));
+				};
+				self.vcs
+					.verify_opening_path(
+						batched_coset.coset_index,
+						&batched_coset.values,
+						optimal_layer_depth,
+						batched_coset.tree_depth,
+						batched_layer,
+						&batched_coset.path,
+					)
+					.map_err(|source| VerificationError::InvalidBatchedOpening {
+						query_index: openings.index,
+						batch_index,
+						source,
+					})?;
+				next_value += batched_coset.values[index % (1 << arity)] * coeff;
+			}
+
+			if next_value != coset.values[index % (1 << arity)] {
 				return Err(VerificationError::IncorrectFold {
+					query_index: openings.index,
 					query_round: i,
 					index,
 				}
 				.into());
 			}
 
+			let mut values = coset.values.clone();
 			next_value = fold_chunk(
 				ntt,
 				self.params.rs_code().log_len() - fold_round,
@@ -342,6 +603,7 @@ where
 
 		if next_value != terminate_codeword[index] {
 			return Err(VerificationError::IncorrectFold {
+				query_index: openings.index,
 				query_round: self.n_oracles() - 1,
 				index,
 			}
@@ -365,32 +627,167 @@ where
 	}
 }
 
+/// The parts of the FRI query phase verification that only depend on the parameters and the
+/// Merkle tree scheme.
+///
+/// The plan holds the NTT twiddles of the code domain, the Merkle tree layers that the query
+/// openings of each oracle are checked against, and the layout of each query opening in the
+/// proof. A verifier that checks many proofs with the same parameters builds the plan once and
+/// passes it to [`FRIVerifier::verify_with_plan`], rather than recomputing it per proof in
+/// [`FRIVerifier::verify`].
+///
+/// By default the twiddles are computed on the fly, so the plan is small whatever the codeword
+/// length. [`Self::precompute_twiddles`] trades memory proportional to the codeword length for
+/// faster folding, which pays off when the plan is reused across many proofs.
+#[derive(Debug)]
+pub struct VerifierPlan<FA: BinaryField, TA: TwiddleAccess<FA> = OnTheFlyTwiddleAccess<FA>> {
+	ntt: SingleThreadedNTT<FA, TA>,
+	log_len: usize,
+	log_batch_size: usize,
+	terminate_codeword_len: usize,
+	/// The layout of the opening of each committed oracle, in the order the oracles are
+	/// committed.
+	oracles: Vec<OracleLayout>,
+}
+
+impl<FA: BinaryField> VerifierPlan<FA> {
+	/// Builds the plan for verifying proofs with the given parameters and Merkle tree scheme.
+	pub fn new<F, VCS>(params: &FRIParams<F, FA>, vcs: &VCS) -> Result<Self, Error>
+	where
+		F: BinaryField + ExtensionField<FA>,
+		VCS: MerkleTreeScheme<F>,
+	{
+		let ntt = SingleThreadedNTT::with_subspace(params.rs_code().subspace())?;
+
+		let mut index_shift = 0;
+		let oracles = izip!(params.fold_arities(), vcs_optimal_layers_depths_iter(params, vcs))
+			.enumerate()
+			.map(|(i, (&arity, layer_depth))| {
+				if i != 0 {
+					index_shift += arity;
+				}
+				let log_n_cosets = params.index_bits() - index_shift;
+				OracleLayout {
+					arity,
+					index_shift,
+					log_n_cosets,
+					layer_depth,
+					path_len: vcs.opening_path_len(log_n_cosets, layer_depth),
+				}
+			})
+			.collect();
+
+		Ok(Self {
+			ntt,
+			log_len: params.rs_code().log_len(),
+			log_batch_size: params.log_batch_size(),
+			terminate_codeword_len: 1
+				<< (params.n_final_challenges() + params.rs_code().log_inv_rate()),
+			oracles,
+		})
+	}
+
+	/// Returns the plan with all twiddles of the code domain precomputed.
+	///
+	/// The precomputed tables hold as many elements as the codeword has symbols.
+	pub fn precompute_twiddles(self) -> VerifierPlan<FA, PrecomputedTwiddleAccess<FA>> {
+		VerifierPlan {
+			ntt: self.ntt.precompute_twiddles(),
+			log_len: self.log_len,
+			log_batch_size: self.log_batch_size,
+			terminate_codeword_len: self.terminate_codeword_len,
+			oracles: self.oracles,
+		}
+	}
+}
+
+impl<FA: BinaryField, TA: TwiddleAccess<FA>> VerifierPlan<FA, TA> {
+	/// Checks that the plan was built for parameters of the same shape as `params`.
+	fn check_params<F>(&self, params: &FRIParams<F, FA>) -> Result<(), Error>
+	where
+		F: BinaryField + ExtensionField<FA>,
+	{
+		let matches = self.log_len == params.rs_code().log_len()
+			&& self.log_batch_size == params.log_batch_size()
+			&& self.oracles.len() == params.n_oracles()
+			&& iter::zip(&self.oracles, params.fold_arities())
+				.all(|(oracle, &arity)| oracle.arity == arity);
+		if !matches {
+			bail!(Error::InvalidArgs(
+				"the verifier plan was built for different FRI parameters".to_string()
+			));
+		}
+		Ok(())
+	}
+}
+
+/// The position of the opening of a committed oracle for a FRI query.
+#[derive(Debug, Clone, Copy)]
+struct OracleLayout {
+	/// The binary logarithm of the number of values in an opened coset.
+	arity: usize,
+	/// The number of low bits dropped from the query index to get the opened coset index.
+	index_shift: usize,
+	/// The binary logarithm of the number of cosets, which is the depth of the Merkle tree.
+	log_n_cosets: usize,
+	/// The depth of the Merkle tree layer that the openings are verified against.
+	layer_depth: usize,
+	/// The number of digests in the Merkle path of an opening.
+	path_len: usize,
+}
+
+/// The values and Merkle authentication path opened in one committed oracle for a FRI query.
+#[derive(Debug)]
+struct CosetOpening<F, Digest> {
+	coset_index: usize,
+	tree_depth: usize,
+	values: Vec<F>,
+	path: Vec<Digest>,
+}
+
+/// The coset openings of a FRI challenge query, read from the transcript ahead of verification.
+#[derive(Debug)]
+struct QueryOpenings<F, Digest> {
+	/// The index into the original codeword domain.
+	index: usize,
+	/// The coset opening in each committed oracle, in the order the oracles were committed.
+	cosets: Vec<CosetOpening<F, Digest>>,
+	/// The coset openings in the batched codewords, at the same index as in the first oracle.
+	batched_cosets: Vec<CosetOpening<F, Digest>>,
+	/// The coset openings in the codewords batched into round oracles, at the same index as in the
+	/// oracle they are batched into, in the order of the oracles.
+	batched_oracle_cosets: Vec<CosetOpening<F, Digest>>,
+}
+
 /// Verifies that the coset opening provided in the proof is consistent with the VCS commitment.
-#[allow(clippy::too_many_arguments)]
-fn verify_coset_opening<F, MTScheme, B>(
+///
+/// `query_index` and `oracle` identify the opening in the returned error.
+fn verify_coset_opening<F, MTScheme>(
 	vcs: &MTScheme,
-	coset_index: usize,
-	log_coset_size: usize,
+	query_index: usize,
+	oracle: usize,
+	coset: &CosetOpening<F, MTScheme::Digest>,
 	optimal_layer_depth: usize,
-	tree_depth: usize,
 	layer_digests: &[MTScheme::Digest],
-	advice: &mut TranscriptReader<B>,
-) -> Result<Vec<F>, Error>
+) -> Result<(), Error>
 where
 	F: TowerField,
-	MTScheme: MerkleTreeScheme<F>,
-	B: Buf,
+	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes>,
 {
-	let values = advice.read_scalar_slice::<F>(1 << log_coset_size)?;
-	vcs.verify_opening(
-		coset_index,
-		&values,
+	vcs.verify_opening_path(
+		coset.coset_index,
+		&coset.values,
 		optimal_layer_depth,
-		tree_depth,
+		coset.tree_depth,
 		layer_digests,
-		advice,
+		&coset.path,
 	)
-	.map_err(|err| Error::VectorCommit(Box::new(err)))?;
-
-	Ok(values)
+	.map_err(|source| {
+		VerificationError::InvalidCosetOpening {
+			query_index,
+			oracle,
+			source,
+		}
+		.into()
+	})
 }
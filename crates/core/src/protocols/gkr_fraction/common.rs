@@ -0,0 +1,120 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{Field, PackedField, packed::get_packed_slice};
+use binius_maybe_rayon::prelude::*;
+use binius_utils::bail;
+use bytemuck::zeroed_vec;
+use tracing::instrument;
+
+use super::Error;
+
+/// A claim that the fractions of an `n_vars`-variate numerator and denominator multilinear add up
+/// to `numerator / denominator` over the hypercube.
+///
+/// The claimed fraction is the projective output of the fraction addition circuit, and is only
+/// determined by the witness up to the circuit structure, not up to scaling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FractionalSumClaim<F: Field> {
+	pub n_vars: usize,
+	pub numerator: F,
+	pub denominator: F,
+}
+
+/// Fractional sum witness.
+///
+/// Constructs the GKR fraction addition circuit layers from the numerator and denominator
+/// evaluations of the input fractions. There is a total of `n_vars + 1` layers, ordered by
+/// decreasing size, with the last layer containing the single output fraction. Each layer halves
+/// the previous one by adding the fractions that differ in the last variable.
+#[derive(Debug, Clone)]
+pub struct FractionalSumWitness<P: PackedField> {
+	circuit_layers: Vec<FractionLayer<P>>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct FractionLayer<P: PackedField> {
+	pub numerators: Vec<P>,
+	pub denominators: Vec<P>,
+}
+
+impl<P: PackedField> FractionalSumWitness<P> {
+	#[instrument(skip_all, level = "debug", name = "FractionalSumWitness::new")]
+	pub fn new(n_vars: usize, numerators: Vec<P>, denominators: Vec<P>) -> Result<Self, Error> {
+		let packed_len = 1 << n_vars.saturating_sub(P::LOG_WIDTH);
+		if numerators.len() != packed_len || denominators.len() != packed_len {
+			bail!(Error::IncorrectWitnessLength);
+		}
+
+		let mut circuit_layers = Vec::with_capacity(n_vars + 1);
+		circuit_layers.push(FractionLayer {
+			numerators,
+			denominators,
+		});
+		for layer_n_vars in (0..n_vars).rev() {
+			let prev_layer = circuit_layers
+				.last()
+				.expect("circuit_layers is not empty by invariant");
+			let layer_len = 1 << layer_n_vars.saturating_sub(P::LOG_WIDTH);
+			let mut numerators = zeroed_vec::<P>(layer_len);
+			let mut denominators = zeroed_vec::<P>(layer_len);
+
+			// Specialize the _last_ variable to construct the next layer.
+			if layer_n_vars >= P::LOG_WIDTH {
+				let (p_0, p_1) = prev_layer.numerators.split_at(layer_len);
+				let (q_0, q_1) = prev_layer.denominators.split_at(layer_len);
+				(numerators.as_mut_slice(), denominators.as_mut_slice(), p_0, p_1, q_0, q_1)
+					.into_par_iter()
+					.for_each(|(numerator, denominator, &p_0, &p_1, &q_0, &q_1)| {
+						*numerator = p_0 * q_1 + p_1 * q_0;
+						*denominator = q_0 * q_1;
+					});
+			} else {
+				for i in 0..1 << layer_n_vars {
+					let p_0 = get_packed_slice(&prev_layer.numerators, i);
+					let p_1 = get_packed_slice(&prev_layer.numerators, i | 1 << layer_n_vars);
+					let q_0 = get_packed_slice(&prev_layer.denominators, i);
+					let q_1 = get_packed_slice(&prev_layer.denominators, i | 1 << layer_n_vars);
+					numerators[0].set(i, p_0 * q_1 + p_1 * q_0);
+					denominators[0].set(i, q_0 * q_1);
+				}
+			}
+
+			circuit_layers.push(FractionLayer {
+				numerators,
+				denominators,
+			});
+		}
+
+		Ok(Self { circuit_layers })
+	}
+
+	/// Base-two logarithm of the number of inputs to the GKR fraction addition circuit
+	pub fn n_vars(&self) -> usize {
+		self.circuit_layers.len() - 1
+	}
+
+	/// Final numerator and denominator of the GKR fraction addition circuit
+	pub fn fractional_sum_evaluation(&self) -> (P::Scalar, P::Scalar) {
+		let output_layer = self.circuit_layers.last().expect("always n_vars+1 layers");
+		(output_layer.numerators[0].get(0), output_layer.denominators[0].get(0))
+	}
+
+	pub(super) fn into_circuit_layers(self) -> Vec<FractionLayer<P>> {
+		self.circuit_layers
+	}
+}
+
+/// A claim about the evaluations of the numerator and denominator multilinears of a circuit layer
+/// at a specific evaluation point.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FractionLayerClaim<F: Field> {
+	pub eval_point: Vec<F>,
+	pub numerator_eval: F,
+	pub denominator_eval: F,
+}
+
+#[derive(Debug, Default)]
+pub struct FractionalSumBatchProveOutput<F: Field> {
+	// Reduced evalcheck claims for all the initial fractional sum claims
+	pub final_layer_claims: Vec<FractionLayerClaim<F>>,
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::PackedField;
+use binius_math::{ArithCircuit, CompositionPoly};
+use binius_utils::bail;
+
+/// The compositions of a fraction addition gate.
+///
+/// The variables are, in order, the numerators $p_0, p_1$ and the denominators $q_0, q_1$ of the
+/// two input fractions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FractionAddition {
+	/// The numerator of the sum, $p_0 q_1 + p_1 q_0$.
+	Numerator,
+	/// The denominator of the sum, $q_0 q_1$.
+	Denominator,
+}
+
+impl<P: PackedField> CompositionPoly<P> for FractionAddition {
+	fn n_vars(&self) -> usize {
+		4
+	}
+
+	fn degree(&self) -> usize {
+		2
+	}
+
+	fn expression(&self) -> ArithCircuit<P::Scalar> {
+		let [p_0, p_1, q_0, q_1] = [0, 1, 2, 3].map(ArithCircuit::var);
+		match self {
+			Self::Numerator => p_0 * q_1 + p_1 * q_0,
+			Self::Denominator => q_0 * q_1,
+		}
+	}
+
+	fn evaluate(&self, query: &[P]) -> Result<P, binius_math::Error> {
+		let &[p_0, p_1, q_0, q_1] = query else {
+			bail!(binius_math::Error::IncorrectQuerySize {
+				expected: 4,
+				actual: query.len(),
+			});
+		};
+		Ok(match self {
+			Self::Numerator => p_0 * q_1 + p_1 * q_0,
+			Self::Denominator => q_0 * q_1,
+		})
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		0
+	}
+}
@@ -0,0 +1,23 @@
+// Copyright 2025 Irreducible Inc.
+
+use crate::{polynomial::Error as PolynomialError, protocols::sumcheck::Error as SumcheckError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("prover has mismatch between claim and witness")]
+	ProverClaimWitnessMismatch,
+	#[error("witneses and claims have mismatched lengths")]
+	MismatchedWitnessClaimLength,
+	#[error("numerator and denominator must have 2^n_vars values each")]
+	IncorrectWitnessLength,
+	#[error("finalize called prematurely")]
+	PrematureFinalize,
+	#[error("all layer claims in a batch should be for the same layer")]
+	MismatchedEvalPointLength,
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] PolynomialError),
+	#[error("sumcheck failure: {0}")]
+	SumcheckError(#[from] SumcheckError),
+	#[error("Math error: {0}")]
+	MathError(#[from] binius_math::Error),
+}
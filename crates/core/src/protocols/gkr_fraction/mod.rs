@@ -0,0 +1,41 @@
+// Copyright 2025 Irreducible Inc.
+
+//! The fractional sumcheck protocol based on a projective GKR-instantiation.
+//!
+//! A fractional sum claim is that the fractions $p(x) / q(x)$ of a numerator and a denominator
+//! multilinear, summed over the hypercube, add up to a claimed fraction. Sums of fractions are the
+//! core of LogUp lookups and of permutation and multiset arguments based on logarithmic
+//! derivatives.
+//!
+//! The fractions are kept in projective form, as numerator-denominator pairs, so that no field
+//! inversions are needed. The GKR circuit has only one gate type, the fan-in-2 fraction addition
+//!
+//! $$
+//! \frac{p_0}{q_0} + \frac{p_1}{q_1} = \frac{p_0 q_1 + p_1 q_0}{q_0 q_1},
+//! $$
+//!
+//! and the $2^n$ input fractions are added together in $n$ layers to produce the output pair. Each
+//! layer is reduced to the next with one sumcheck over the numerator and denominator claims, like
+//! in the [grand product argument](super::gkr_gpa).
+//!
+//! The output pair is the projective representation of the sum, not the sum itself. Protocols
+//! built on top of this primitive check it as appropriate, for instance that the numerator is zero
+//! and the denominator is nonzero for a LogUp balancing argument.
+//!
+//! See [PH23] Section 3 for further background.
+//!
+//! [PH23]: <https://eprint.iacr.org/2023/1284>
+
+mod common;
+mod compositions;
+mod error;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use common::*;
+pub use compositions::*;
+pub use error::*;
+pub use prove::*;
+pub use verify::*;
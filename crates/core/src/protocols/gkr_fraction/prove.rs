@@ -0,0 +1,275 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{Field, PackedExtension, PackedField, TowerField};
+use binius_hal::ComputationBackend;
+use binius_math::{EvaluationDomainFactory, EvaluationOrder, extrapolate_line_scalar};
+use binius_utils::{
+	bail,
+	sorting::{stable_sort, unsort},
+};
+use itertools::izip;
+use tracing::instrument;
+
+use super::{
+	Error, FractionAddition, FractionalSumClaim, FractionalSumWitness,
+	common::{FractionLayer, FractionLayerClaim, FractionalSumBatchProveOutput},
+};
+use crate::{
+	composition::IndexComposition,
+	fiat_shamir::{CanSample, Challenger},
+	protocols::sumcheck::{
+		BatchSumcheckOutput, CompositeSumClaim,
+		prove::{SumcheckProver, eq_ind::EqIndSumcheckProverBuilder, front_loaded},
+	},
+	transcript::ProverTranscript,
+};
+
+/// Proves batch reduction turning each FractionalSumClaim into a FractionLayerClaim on the
+/// original numerator and denominator multilinears.
+///
+/// REQUIRES:
+/// * witnesses and claims are of the same length
+/// * The ith witness corresponds to the ith claim
+#[instrument(skip_all, name = "gkr_fraction::batch_prove", level = "debug")]
+pub fn batch_prove<F, P, FDomain, Challenger_, Backend>(
+	evaluation_order: EvaluationOrder,
+	witnesses: impl IntoIterator<Item = FractionalSumWitness<P>>,
+	claims: &[FractionalSumClaim<F>],
+	evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+	transcript: &mut ProverTranscript<Challenger_>,
+	backend: &Backend,
+) -> Result<FractionalSumBatchProveOutput<F>, Error>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
+	FDomain: Field,
+	Challenger_: Challenger,
+	Backend: ComputationBackend,
+{
+	let witnesses = witnesses.into_iter().collect::<Vec<_>>();
+	if witnesses.len() != claims.len() {
+		bail!(Error::MismatchedWitnessClaimLength);
+	}
+
+	let prover_states = izip!(witnesses, claims)
+		.map(|(witness, claim)| FractionalSumProverState::new(claim, witness))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let (original_indices, mut sorted_prover_states) =
+		stable_sort(prover_states, |state| state.remaining_layers.len(), true);
+
+	let mut reverse_sorted_final_layer_claims = Vec::with_capacity(claims.len());
+	let mut eval_point = Vec::new();
+
+	loop {
+		process_finished_provers(
+			&mut sorted_prover_states,
+			&mut reverse_sorted_final_layer_claims,
+			&eval_point,
+		)?;
+
+		if sorted_prover_states.is_empty() {
+			break;
+		}
+
+		let BatchSumcheckOutput {
+			challenges,
+			multilinear_evals,
+		} = {
+			let eq_ind_sumcheck_prover = FractionalSumProverState::stage_sumcheck_provers(
+				evaluation_order,
+				&mut sorted_prover_states,
+				evaluation_domain_factory.clone(),
+				&eval_point,
+				backend,
+			)?;
+
+			let batch_sumcheck_prover =
+				front_loaded::BatchProver::new(vec![eq_ind_sumcheck_prover], transcript)?;
+
+			let mut batch_output = batch_sumcheck_prover.run(transcript)?;
+
+			if evaluation_order == EvaluationOrder::HighToLow {
+				batch_output.challenges.reverse();
+			}
+
+			batch_output
+		};
+
+		let layer_challenge = transcript.sample();
+
+		eval_point.copy_from_slice(&challenges);
+		eval_point.push(layer_challenge);
+
+		debug_assert_eq!(multilinear_evals.len(), 1);
+		let multilinear_evals = multilinear_evals
+			.first()
+			.expect("exactly one prover in a batch");
+		for (state, evals) in izip!(&mut sorted_prover_states, multilinear_evals.chunks_exact(4)) {
+			state.update_layer_evals(evals, layer_challenge);
+		}
+	}
+
+	debug_assert_eq!(reverse_sorted_final_layer_claims.len(), claims.len());
+
+	reverse_sorted_final_layer_claims.reverse();
+	let sorted_final_layer_claims = reverse_sorted_final_layer_claims;
+
+	let final_layer_claims = unsort(original_indices, sorted_final_layer_claims);
+	Ok(FractionalSumBatchProveOutput { final_layer_claims })
+}
+
+fn process_finished_provers<F, P>(
+	sorted_prover_states: &mut Vec<FractionalSumProverState<P>>,
+	reverse_sorted_final_layer_claims: &mut Vec<FractionLayerClaim<F>>,
+	eval_point: &[F],
+) -> Result<(), Error>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+{
+	let first_finished =
+		sorted_prover_states.partition_point(|state| !state.remaining_layers.is_empty());
+
+	for state in sorted_prover_states.drain(first_finished..).rev() {
+		reverse_sorted_final_layer_claims.push(state.finalize(eval_point)?);
+	}
+
+	Ok(())
+}
+
+/// Fractional sumcheck protocol state for a single witness
+#[derive(Debug)]
+struct FractionalSumProverState<P>
+where
+	P: PackedField<Scalar: TowerField>,
+{
+	// Remaining layers of the fraction addition circuit, ordered from largest to smallest.
+	// Each step removes the last layer.
+	remaining_layers: Vec<FractionLayer<P>>,
+	// The current eval claims (on a shared eval point).
+	numerator_eval: P::Scalar,
+	denominator_eval: P::Scalar,
+}
+
+impl<F, P> FractionalSumProverState<P>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+{
+	fn new(claim: &FractionalSumClaim<F>, witness: FractionalSumWitness<P>) -> Result<Self, Error> {
+		if claim.n_vars != witness.n_vars()
+			|| witness.fractional_sum_evaluation() != (claim.numerator, claim.denominator)
+		{
+			bail!(Error::ProverClaimWitnessMismatch);
+		}
+
+		let mut remaining_layers = witness.into_circuit_layers();
+		debug_assert_eq!(remaining_layers.len(), claim.n_vars + 1);
+		let _ = remaining_layers
+			.pop()
+			.expect("remaining_layers cannot be empty");
+
+		Ok(Self {
+			remaining_layers,
+			numerator_eval: claim.numerator,
+			denominator_eval: claim.denominator,
+		})
+	}
+
+	#[allow(clippy::type_complexity)]
+	#[instrument(skip_all, level = "debug")]
+	fn stage_sumcheck_provers<'a, FDomain, Backend>(
+		evaluation_order: EvaluationOrder,
+		states: &mut [Self],
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		eq_ind_challenges: &[P::Scalar],
+		backend: &'a Backend,
+	) -> Result<impl SumcheckProver<P::Scalar> + 'a, Error>
+	where
+		FDomain: Field,
+		P: PackedExtension<FDomain>,
+		Backend: ComputationBackend,
+	{
+		let n_vars = eq_ind_challenges.len();
+		let n_claims = states.len();
+		let n_multilinears = n_claims * 4;
+
+		let mut composite_claims = Vec::with_capacity(n_claims * 2);
+		let mut multilinears = Vec::with_capacity(n_multilinears);
+
+		for (i, state) in states.iter_mut().enumerate() {
+			let indices = [4 * i, 4 * i + 1, 4 * i + 2, 4 * i + 3];
+			for (composition, sum) in [
+				(FractionAddition::Numerator, state.numerator_eval),
+				(FractionAddition::Denominator, state.denominator_eval),
+			] {
+				composite_claims.push(CompositeSumClaim {
+					sum,
+					composition: IndexComposition::new(n_multilinears, indices, composition)?,
+				});
+			}
+
+			let FractionLayer {
+				numerators,
+				denominators,
+			} = state
+				.remaining_layers
+				.pop()
+				.expect("not staging more than n_vars times");
+
+			multilinears.extend(split_halves(n_vars, numerators));
+			multilinears.extend(split_halves(n_vars, denominators));
+		}
+
+		let prover = EqIndSumcheckProverBuilder::without_switchover(n_vars, multilinears, backend)
+			.build(
+				evaluation_order,
+				eq_ind_challenges,
+				composite_claims,
+				evaluation_domain_factory,
+			)?;
+
+		Ok(prover)
+	}
+
+	fn update_layer_evals(&mut self, evals: &[F], layer_challenge: F) {
+		let &[p_0, p_1, q_0, q_1] = evals else {
+			unreachable!("evals are chunks of four multilinear evaluations");
+		};
+		self.numerator_eval = extrapolate_line_scalar::<F, F>(p_0, p_1, layer_challenge);
+		self.denominator_eval = extrapolate_line_scalar::<F, F>(q_0, q_1, layer_challenge);
+	}
+
+	fn finalize(self, eval_point: &[F]) -> Result<FractionLayerClaim<F>, Error> {
+		if !self.remaining_layers.is_empty() {
+			bail!(Error::PrematureFinalize);
+		}
+
+		Ok(FractionLayerClaim {
+			eval_point: eval_point.to_vec(),
+			numerator_eval: self.numerator_eval,
+			denominator_eval: self.denominator_eval,
+		})
+	}
+}
+
+/// Splits an `n_vars + 1`-variate multilinear into its restrictions to the last variable being zero
+/// and one.
+fn split_halves<P: PackedField>(n_vars: usize, mut layer: Vec<P>) -> [Vec<P>; 2] {
+	if n_vars >= P::LOG_WIDTH {
+		let evals_1 = layer.split_off(1 << (n_vars - P::LOG_WIDTH));
+		[layer, evals_1]
+	} else {
+		let mut evals_0 = P::zero();
+		let mut evals_1 = P::zero();
+		let only_packed = layer[0];
+
+		for i in 0..1 << n_vars {
+			evals_0.set(i, only_packed.get(i));
+			evals_1.set(i, only_packed.get(i | 1 << n_vars));
+		}
+
+		[vec![evals_0], vec![evals_1]]
+	}
+}
@@ -0,0 +1,150 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::iter::repeat_with;
+
+use binius_field::{
+	BinaryField32b, BinaryField128b, Field, PackedExtension, PackedField, TowerField,
+	arch::OptimalUnderlier256b,
+	as_packed_field::{PackScalar, PackedType},
+	underlier::{UnderlierType, WithUnderlier},
+};
+use binius_hash::groestl::Groestl256;
+use binius_math::{
+	EvaluationOrder, IsomorphicEvaluationDomainFactory, MultilinearExtension, MultilinearQuery,
+};
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{
+	Error, FractionalSumBatchProveOutput, FractionalSumClaim, FractionalSumWitness, batch_prove,
+	batch_verify,
+};
+use crate::{fiat_shamir::HasherChallenger, transcript::ProverTranscript};
+
+fn pack<P: PackedField>(n_vars: usize, values: &[P::Scalar]) -> Vec<P> {
+	let mut packed = vec![P::zero(); 1 << n_vars.saturating_sub(P::LOG_WIDTH)];
+	for (i, &value) in values.iter().enumerate() {
+		packed[i >> P::LOG_WIDTH].set(i % P::WIDTH, value);
+	}
+	packed
+}
+
+fn run_prove_verify_batch_test<U, F, FS>(evaluation_order: EvaluationOrder)
+where
+	U: UnderlierType + PackScalar<F>,
+	PackedType<U, F>: PackedExtension<FS>,
+	F: TowerField,
+	FS: TowerField,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FS>::default();
+	let backend = binius_hal::make_portable_backend();
+
+	let mut claims = Vec::new();
+	let mut witnesses = Vec::new();
+	let mut inputs = Vec::new();
+	for n_vars in [5, 0, 7, 1, 5] {
+		let numerators = repeat_with(|| F::random(&mut rng))
+			.take(1 << n_vars)
+			.collect::<Vec<_>>();
+		let denominators = repeat_with(|| F::random(&mut rng))
+			.take(1 << n_vars)
+			.collect::<Vec<_>>();
+
+		let witness = FractionalSumWitness::<PackedType<U, F>>::new(
+			n_vars,
+			pack(n_vars, &numerators),
+			pack(n_vars, &denominators),
+		)
+		.unwrap();
+		let (numerator, denominator) = witness.fractional_sum_evaluation();
+		let sum = numerators
+			.iter()
+			.zip(&denominators)
+			.map(|(&p, &q)| p * q.invert().unwrap())
+			.sum::<F>();
+		assert_eq!(numerator, sum * denominator);
+		assert_eq!(denominator, denominators.iter().copied().product::<F>());
+
+		claims.push(FractionalSumClaim {
+			n_vars,
+			numerator,
+			denominator,
+		});
+		witnesses.push(witness);
+		inputs.push((numerators, denominators));
+	}
+
+	let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let FractionalSumBatchProveOutput { final_layer_claims } =
+		batch_prove::<_, PackedType<U, F>, FS, _, _>(
+			evaluation_order,
+			witnesses,
+			&claims,
+			domain_factory,
+			&mut prover_transcript,
+			&backend,
+		)
+		.unwrap();
+
+	let mut verifier_transcript = prover_transcript.into_verifier();
+	let verified_final_layer_claims =
+		batch_verify(evaluation_order, claims.clone(), &mut verifier_transcript).unwrap();
+	verifier_transcript.finalize().unwrap();
+
+	assert_eq!(final_layer_claims, verified_final_layer_claims);
+	for (claim, (numerators, denominators)) in verified_final_layer_claims.iter().zip(&inputs) {
+		let query = MultilinearQuery::<F>::expand(&claim.eval_point);
+		let evaluate = |values: &[F]| {
+			MultilinearExtension::from_values(values.to_vec())
+				.unwrap()
+				.evaluate(&query)
+				.unwrap()
+		};
+		assert_eq!(claim.numerator_eval, evaluate(numerators));
+		assert_eq!(claim.denominator_eval, evaluate(denominators));
+	}
+}
+
+#[test]
+fn test_prove_verify_batch_128b() {
+	type F = BinaryField128b;
+	type U = <F as WithUnderlier>::Underlier;
+
+	for evaluation_order in [EvaluationOrder::LowToHigh, EvaluationOrder::HighToLow] {
+		run_prove_verify_batch_test::<U, F, BinaryField32b>(evaluation_order);
+	}
+}
+
+#[test]
+fn test_prove_verify_batch_256b() {
+	for evaluation_order in [EvaluationOrder::LowToHigh, EvaluationOrder::HighToLow] {
+		run_prove_verify_batch_test::<OptimalUnderlier256b, BinaryField128b, BinaryField32b>(
+			evaluation_order,
+		);
+	}
+}
+
+#[test]
+fn test_prove_rejects_incorrect_claim() {
+	type F = BinaryField128b;
+
+	let witness =
+		FractionalSumWitness::<F>::new(1, vec![F::ONE, F::ONE], vec![F::ONE, F::ONE]).unwrap();
+	assert_eq!(witness.fractional_sum_evaluation(), (F::ZERO, F::ONE));
+
+	let claim = FractionalSumClaim {
+		n_vars: 1,
+		numerator: F::ONE,
+		denominator: F::ONE,
+	};
+	let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let result = batch_prove::<_, F, BinaryField32b, _, _>(
+		EvaluationOrder::LowToHigh,
+		[witness],
+		&[claim],
+		IsomorphicEvaluationDomainFactory::<BinaryField32b>::default(),
+		&mut prover_transcript,
+		&binius_hal::make_portable_backend(),
+	);
+	assert!(matches!(result, Err(Error::ProverClaimWitnessMismatch)));
+}
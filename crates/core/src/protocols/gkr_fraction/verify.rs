@@ -0,0 +1,156 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::TowerField;
+use binius_math::{EvaluationOrder, extrapolate_line_scalar};
+use binius_utils::{
+	bail,
+	sorting::{stable_sort, unsort},
+};
+use tracing::instrument;
+
+use super::{Error, FractionAddition, FractionLayerClaim, FractionalSumClaim};
+use crate::{
+	composition::IndexComposition,
+	fiat_shamir::{CanSample, Challenger},
+	polynomial::Error as PolynomialError,
+	protocols::sumcheck::{
+		self, CompositeSumClaim, EqIndSumcheckClaim, eq_ind::ClaimsSortingOrder, front_loaded,
+	},
+	transcript::VerifierTranscript,
+};
+
+/// Verifies batch reduction turning each FractionalSumClaim into a FractionLayerClaim on the
+/// original numerator and denominator multilinears.
+#[instrument(skip_all, name = "gkr_fraction::batch_verify", level = "debug")]
+pub fn batch_verify<F, Challenger_>(
+	evaluation_order: EvaluationOrder,
+	claims: impl IntoIterator<Item = FractionalSumClaim<F>>,
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<Vec<FractionLayerClaim<F>>, Error>
+where
+	F: TowerField,
+	Challenger_: Challenger,
+{
+	let (original_indices, mut sorted_claims) = stable_sort(claims, |claim| claim.n_vars, true);
+	let max_n_vars = sorted_claims.first().map(|claim| claim.n_vars).unwrap_or(0);
+
+	let mut layer_claims = sorted_claims
+		.iter()
+		.map(|claim| FractionLayerClaim {
+			eval_point: vec![],
+			numerator_eval: claim.numerator,
+			denominator_eval: claim.denominator,
+		})
+		.collect::<Vec<_>>();
+
+	let n_claims = sorted_claims.len();
+	let mut reverse_sorted_final_layer_claims = Vec::with_capacity(n_claims);
+
+	for layer_no in 0..=max_n_vars {
+		while sorted_claims
+			.last()
+			.is_some_and(|claim| claim.n_vars == layer_no)
+		{
+			let _ = sorted_claims.pop().expect("must exist");
+			let finished_layer_claim = layer_claims.pop().expect("must exist");
+			reverse_sorted_final_layer_claims.push(finished_layer_claim);
+		}
+
+		if layer_no < max_n_vars {
+			layer_claims = reduce_layer_claim_batch(evaluation_order, &layer_claims, transcript)?;
+		}
+	}
+
+	debug_assert!(layer_claims.is_empty());
+	debug_assert_eq!(reverse_sorted_final_layer_claims.len(), n_claims);
+
+	reverse_sorted_final_layer_claims.reverse();
+	let sorted_final_layer_claims = reverse_sorted_final_layer_claims;
+
+	Ok(unsort(original_indices, sorted_final_layer_claims))
+}
+
+/// Reduces n kth FractionLayerClaims to n (k+1)th FractionLayerClaims
+fn reduce_layer_claim_batch<F, Challenger_>(
+	evaluation_order: EvaluationOrder,
+	claims: &[FractionLayerClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<Vec<FractionLayerClaim<F>>, Error>
+where
+	F: TowerField,
+	Challenger_: Challenger,
+{
+	if claims.is_empty() {
+		return Ok(vec![]);
+	}
+
+	let curr_layer_challenge = &claims[0].eval_point;
+	if !claims
+		.iter()
+		.all(|claim| &claim.eval_point == curr_layer_challenge)
+	{
+		bail!(Error::MismatchedEvalPointLength);
+	}
+
+	let n_vars = curr_layer_challenge.len();
+	let n_multilinears = 4 * claims.len();
+
+	let composite_sums = claims
+		.iter()
+		.enumerate()
+		.flat_map(|(i, claim)| {
+			let indices = [4 * i, 4 * i + 1, 4 * i + 2, 4 * i + 3];
+			[
+				(FractionAddition::Numerator, claim.numerator_eval),
+				(FractionAddition::Denominator, claim.denominator_eval),
+			]
+			.map(|(composition, sum)| {
+				Ok(CompositeSumClaim {
+					composition: IndexComposition::new(n_multilinears, indices, composition)?,
+					sum,
+				})
+			})
+		})
+		.collect::<Result<Vec<_>, PolynomialError>>()?;
+
+	let eq_ind_sumcheck_claims = [EqIndSumcheckClaim::new(
+		n_vars,
+		n_multilinears,
+		composite_sums,
+	)?];
+
+	let regular_sumcheck_claims =
+		sumcheck::eq_ind::reduce_to_regular_sumchecks(&eq_ind_sumcheck_claims)?;
+
+	let batch_sumcheck_verifier =
+		front_loaded::BatchVerifier::new(&regular_sumcheck_claims, transcript)?;
+	let mut batch_sumcheck_output = batch_sumcheck_verifier.run(transcript)?;
+
+	if evaluation_order == EvaluationOrder::HighToLow {
+		batch_sumcheck_output.challenges.reverse();
+	}
+
+	let batch_sumcheck_output = sumcheck::eq_ind::verify_sumcheck_outputs(
+		ClaimsSortingOrder::DescendingVars,
+		&eq_ind_sumcheck_claims,
+		curr_layer_challenge,
+		batch_sumcheck_output,
+	)?;
+
+	let layer_challenge = transcript.sample();
+	let new_layer_challenge = batch_sumcheck_output
+		.challenges
+		.into_iter()
+		.chain(Some(layer_challenge))
+		.collect::<Vec<_>>();
+	let new_layer_claims = batch_sumcheck_output.multilinear_evals[0]
+		.chunks_exact(4)
+		.map(|evals| FractionLayerClaim {
+			eval_point: new_layer_challenge.clone(),
+			numerator_eval: extrapolate_line_scalar::<_, F>(evals[0], evals[1], layer_challenge),
+			denominator_eval: extrapolate_line_scalar::<_, F>(evals[2], evals[3], layer_challenge),
+		})
+		.collect::<Vec<_>>();
+
+	Ok(new_layer_claims)
+}
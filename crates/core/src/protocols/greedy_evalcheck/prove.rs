@@ -26,7 +26,7 @@ pub struct GreedyEvalcheckProveOutput<'a, F: Field, P: PackedField> {
 #[allow(clippy::too_many_arguments)]
 pub fn prove<'a, F, P, DomainField, Challenger_, Backend>(
 	oracles: &mut MultilinearOracleSet<F>,
-	witness_index: &'a mut MultilinearExtensionIndex<P>,
+	witness_index: &mut MultilinearExtensionIndex<'a, P>,
 	claims: impl IntoIterator<Item = EvalcheckMultilinearClaim<F>>,
 	switchover_fn: impl Fn(usize) -> usize + Clone + 'static,
 	transcript: &mut ProverTranscript<Challenger_>,
@@ -0,0 +1,223 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A common interface to the IOPs of Proximity for interleaved Reed–Solomon codes.
+//!
+//! [`LowDegreeTest`] is implemented by the parameters of [`fri`] and [`stir`], so that a protocol
+//! that only needs to test the proximity of a committed codeword to the code can be instantiated
+//! with either, and their proofs can be compared on equal terms.
+//!
+//! The test only establishes that the committed word is close to the code. The [`crate::piop`]
+//! compiler also needs the claims on the committed message to be bound to the codeword: with FRI,
+//! by the value the folding reduces the codeword to, and with STIR, whose folding provides no such
+//! value, by the column checks of [`crate::piop::stir`] alongside the STIR proximity test.
+
+use binius_field::{BinaryField, ExtensionField, PackedExtension, PackedField, TowerField};
+use binius_ntt::AdditiveNTT;
+use binius_utils::{DeserializeBytes, SerializeBytes};
+
+use crate::{
+	fiat_shamir::{CanSample, Challenger},
+	merkle_tree::{MerkleTreeProver, MerkleTreeScheme},
+	protocols::{
+		fri::{self, CommitOutput, FRIFolder, FRIParams, FRIVerifier, FoldRoundOutput},
+		stir::{self, STIRParams},
+	},
+	transcript::{ProverTranscript, VerifierTranscript},
+};
+
+/// An IOP of Proximity for an interleaved Reed–Solomon code over `FA`, with codewords over `F`.
+///
+/// The prover commits to the encoding of a message with [`Self::commit`], writes the commitment
+/// to the transcript, and proves proximity with [`Self::prove`]. The verifier reads the
+/// commitment and checks the proof with [`Self::verify`].
+pub trait LowDegreeTest<F, FA>
+where
+	F: BinaryField,
+	FA: BinaryField,
+{
+	type Error: std::error::Error + Send + Sync + 'static;
+
+	/// The binary logarithm of the length of the interleaved message.
+	fn log_msg_len(&self) -> usize;
+
+	/// Encodes and commits the interleaved message.
+	fn commit<P, NTT, MerkleProver, VCS>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MerkleProver,
+		message: &[P],
+	) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Self::Error>
+	where
+		P: PackedField<Scalar = F> + PackedExtension<FA>,
+		NTT: AdditiveNTT<FA> + Sync,
+		MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+		VCS: MerkleTreeScheme<F>;
+
+	/// Proves that the committed codeword is close to the code.
+	fn prove<P, NTT, MerkleProver, VCS, Challenger_>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MerkleProver,
+		codeword: &[P],
+		committed: &MerkleProver::Committed,
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Self::Error>
+	where
+		P: PackedField<Scalar = F>,
+		NTT: AdditiveNTT<FA> + Sync,
+		MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+		VCS: MerkleTreeScheme<F, Digest: SerializeBytes>,
+		Challenger_: Challenger;
+
+	/// Verifies that the codeword with the given commitment is close to the code.
+	fn verify<VCS, Challenger_>(
+		&self,
+		vcs: &VCS,
+		commitment: &VCS::Digest,
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Self::Error>
+	where
+		VCS: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+		Challenger_: Challenger;
+}
+
+impl<F, FA> LowDegreeTest<F, FA> for FRIParams<F, FA>
+where
+	F: TowerField + ExtensionField<FA>,
+	FA: BinaryField,
+{
+	type Error = fri::Error;
+
+	fn log_msg_len(&self) -> usize {
+		self.rs_code().log_dim() + self.log_batch_size()
+	}
+
+	fn commit<P, NTT, MerkleProver, VCS>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MerkleProver,
+		message: &[P],
+	) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Self::Error>
+	where
+		P: PackedField<Scalar = F> + PackedExtension<FA>,
+		NTT: AdditiveNTT<FA> + Sync,
+		MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+		VCS: MerkleTreeScheme<F>,
+	{
+		fri::commit_interleaved(self.rs_code(), self, ntt, merkle_prover, message)
+	}
+
+	fn prove<P, NTT, MerkleProver, VCS, Challenger_>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MerkleProver,
+		codeword: &[P],
+		committed: &MerkleProver::Committed,
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Self::Error>
+	where
+		P: PackedField<Scalar = F>,
+		NTT: AdditiveNTT<FA> + Sync,
+		MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+		VCS: MerkleTreeScheme<F, Digest: SerializeBytes>,
+		Challenger_: Challenger,
+	{
+		let mut folder = FRIFolder::new(self, ntt, merkle_prover, codeword, committed)?;
+		for _ in 0..self.n_fold_rounds() {
+			let challenge = transcript.sample();
+			if let FoldRoundOutput::Commitment(round_commitment) =
+				folder.execute_fold_round(challenge)?
+			{
+				transcript.message().write(&round_commitment);
+			}
+		}
+		folder.finish_proof(transcript)
+	}
+
+	fn verify<VCS, Challenger_>(
+		&self,
+		vcs: &VCS,
+		commitment: &VCS::Digest,
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Self::Error>
+	where
+		VCS: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+		Challenger_: Challenger,
+	{
+		let mut challenges = Vec::with_capacity(self.n_fold_rounds());
+		let mut round_commitments = Vec::with_capacity(self.n_oracles());
+		let mut next_commit_round = self.fold_arities().first().copied();
+		for round in 1..=self.n_fold_rounds() {
+			challenges.push(transcript.sample());
+			if next_commit_round == Some(round) {
+				round_commitments.push(transcript.message().read()?);
+				next_commit_round = self
+					.fold_arities()
+					.get(round_commitments.len())
+					.map(|arity| round + arity);
+			}
+		}
+
+		FRIVerifier::new(self, vcs, commitment, &round_commitments, &challenges)?
+			.verify(transcript)?;
+		Ok(())
+	}
+}
+
+impl<F, FA> LowDegreeTest<F, FA> for STIRParams<F, FA>
+where
+	F: TowerField + ExtensionField<FA> + PackedExtension<FA, Scalar = F>,
+	FA: BinaryField,
+{
+	type Error = stir::Error;
+
+	fn log_msg_len(&self) -> usize {
+		self.log_dim() + self.log_batch_size()
+	}
+
+	fn commit<P, NTT, MerkleProver, VCS>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MerkleProver,
+		message: &[P],
+	) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Self::Error>
+	where
+		P: PackedField<Scalar = F> + PackedExtension<FA>,
+		NTT: AdditiveNTT<FA> + Sync,
+		MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+		VCS: MerkleTreeScheme<F>,
+	{
+		stir::commit_interleaved(self, ntt, merkle_prover, message)
+	}
+
+	fn prove<P, NTT, MerkleProver, VCS, Challenger_>(
+		&self,
+		ntt: &NTT,
+		merkle_prover: &MerkleProver,
+		codeword: &[P],
+		committed: &MerkleProver::Committed,
+		transcript: &mut ProverTranscript<Challenger_>,
+	) -> Result<(), Self::Error>
+	where
+		P: PackedField<Scalar = F>,
+		NTT: AdditiveNTT<FA> + Sync,
+		MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+		VCS: MerkleTreeScheme<F, Digest: SerializeBytes>,
+		Challenger_: Challenger,
+	{
+		stir::prove(self, ntt, merkle_prover, codeword, committed, transcript)
+	}
+
+	fn verify<VCS, Challenger_>(
+		&self,
+		vcs: &VCS,
+		commitment: &VCS::Digest,
+		transcript: &mut VerifierTranscript<Challenger_>,
+	) -> Result<(), Self::Error>
+	where
+		VCS: MerkleTreeScheme<F, Digest: DeserializeBytes + SerializeBytes>,
+		Challenger_: Challenger,
+	{
+		stir::verify(self, vcs, commitment, transcript)
+	}
+}
@@ -32,8 +32,12 @@
 pub mod evalcheck;
 pub mod fri;
 pub mod gkr_exp;
+pub mod gkr_fraction;
 pub mod gkr_gpa;
 pub mod greedy_evalcheck;
+pub mod low_degree_test;
+pub mod prime_interop;
+pub mod stir;
 pub mod sumcheck;
 
 #[allow(dead_code)]
@@ -0,0 +1,117 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{ExtensionField, Field, TowerField};
+use binius_math::{B1, B64};
+use binius_utils::bail;
+
+use super::error::Error;
+use crate::{
+	oracle::{MultilinearOracleSet, OracleId},
+	protocols::evalcheck::EvalcheckMultilinearClaim,
+};
+
+/// Parameters of a range decomposition shared with a prime-field system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InteropParams {
+	modulus: u128,
+	n_bits: usize,
+}
+
+impl InteropParams {
+	/// Constructs the parameters for values of `n_bits` bits shared with a system over the prime
+	/// field with the given modulus.
+	///
+	/// Every `n_bits`-bit value must be a canonical field element on the prime side, so
+	/// `2^n_bits` may not exceed the modulus.
+	pub fn new(modulus: u128, n_bits: usize) -> Result<Self, Error> {
+		if n_bits > 64 {
+			bail!(Error::TooManyBits { n_bits });
+		}
+		if 1u128 << n_bits > modulus {
+			bail!(Error::ModulusTooSmall { modulus, n_bits });
+		}
+		Ok(Self { modulus, n_bits })
+	}
+
+	/// The modulus of the prime field.
+	pub fn modulus(&self) -> u128 {
+		self.modulus
+	}
+
+	/// The number of bits of each value.
+	pub fn n_bits(&self) -> usize {
+		self.n_bits
+	}
+}
+
+/// The claim jointly established by the range decomposition protocol.
+///
+/// The value column and the `n_bits` bit columns evaluate at `eval_point` to `value_eval` and
+/// `bit_evals`, respectively, and `value_eval` has been checked to be the recomposition of
+/// `bit_evals`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JointClaim<F: Field> {
+	pub params: InteropParams,
+	pub eval_point: Vec<F>,
+	pub value_eval: F,
+	pub bit_evals: Vec<F>,
+}
+
+impl<F: TowerField> JointClaim<F> {
+	/// Returns the evaluation claims on the committed value and bit columns.
+	///
+	/// The recomposition checked by [`super::verify`] only shows that the values are in range if
+	/// the bit columns take values in $\mathbb{F}_2$, so every bit oracle must have tower level 0.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::IncorrectNumberOfBitColumns`] if there is not one bit oracle per bit evaluation
+	/// * [`Error::BitColumnNotBinary`] if a bit oracle has a tower level above 0
+	pub fn eval_claims(
+		&self,
+		oracles: &MultilinearOracleSet<F>,
+		value_id: OracleId,
+		bit_ids: &[OracleId],
+	) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error> {
+		if bit_ids.len() != self.bit_evals.len() {
+			bail!(Error::IncorrectNumberOfBitColumns {
+				expected: self.bit_evals.len(),
+				actual: bit_ids.len(),
+			});
+		}
+		if let Some(&id) = bit_ids.iter().find(|&&id| oracles.tower_level(id) != 0) {
+			bail!(Error::BitColumnNotBinary {
+				id,
+				tower_level: oracles.tower_level(id),
+			});
+		}
+		let claim = |id, eval| EvalcheckMultilinearClaim {
+			id,
+			eval_point: self.eval_point.as_slice().into(),
+			eval,
+		};
+		Ok(std::iter::once(claim(value_id, self.value_eval))
+			.chain(
+				bit_ids
+					.iter()
+					.zip(&self.bit_evals)
+					.map(|(&id, &eval)| claim(id, eval)),
+			)
+			.collect())
+	}
+}
+
+/// Recomposes the evaluation of the value column from the evaluations of its bit columns.
+///
+/// The value column is the sum of the bit columns weighted by the $\mathbb{F}_2$-basis of
+/// $\mathbb{F}_{2^{64}}$, and evaluation is linear.
+pub fn recompose<F>(bit_evals: &[F]) -> F
+where
+	F: ExtensionField<B64>,
+{
+	bit_evals
+		.iter()
+		.enumerate()
+		.map(|(j, &bit_eval)| bit_eval * F::from(<B64 as ExtensionField<B1>>::basis(j)))
+		.sum()
+}
@@ -0,0 +1,37 @@
+// Copyright 2025 Irreducible Inc.
+
+use crate::oracle::OracleId;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("the number of bits {n_bits} exceeds the maximum of 64")]
+	TooManyBits { n_bits: usize },
+	#[error("the prime modulus {modulus} is smaller than 2^{n_bits}")]
+	ModulusTooSmall { modulus: u128, n_bits: usize },
+	#[error("the column length must be a power of two")]
+	ColumnLengthNotPowerOfTwo,
+	#[error("the value {value} at index {index} does not fit in {n_bits} bits")]
+	ValueOutOfRange {
+		index: usize,
+		value: u64,
+		n_bits: usize,
+	},
+	#[error("expected {expected} bit columns, got {actual}")]
+	IncorrectNumberOfBitColumns { expected: usize, actual: usize },
+	#[error("bit column {id} has tower level {tower_level}, expected 0")]
+	BitColumnNotBinary { id: OracleId, tower_level: usize },
+	#[error("the number of variables must all be equal")]
+	NumberOfVariablesMismatch,
+	#[error("math error: {0}")]
+	Math(#[from] binius_math::Error),
+	#[error("transcript error: {0}")]
+	Transcript(#[from] crate::transcript::Error),
+	#[error("verification failure: {0}")]
+	Verification(#[from] VerificationError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+	#[error("the value evaluation does not match the recomposition of the bit evaluations")]
+	IncorrectRecomposition,
+}
@@ -0,0 +1,33 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A sub-protocol linking a Binius-committed column to values committed in a prime-field system.
+//!
+//! Hybrid pipelines prove part of a computation in a prime-field proof system and the rest with
+//! Binius. Values crossing the boundary are integers `v < 2^n_bits` with `2^n_bits` at most the
+//! prime modulus, so that each one has the same canonical representative on both sides. On the
+//! Binius side, a value column over $\mathbb{F}_{2^{64}}$ is decomposed into `n_bits` committed
+//! bit columns, the range decomposition. The protocol samples a random evaluation point, has the
+//! prover send the evaluations of the value column and every bit column there, and checks that
+//! the value evaluation is the recomposition of the bit evaluations. Since the bit columns are
+//! committed over $\mathbb{F}_2$, this shows that every value lies in range, and the bit
+//! evaluations form a joint claim that can be proven on both sides.
+//!
+//! This module only covers the Binius half. The output [`JointClaim`] reduces to evaluation
+//! claims on the committed columns, which are discharged by the usual evalcheck and PCS pipeline.
+//! [`JointClaim::eval_claims`] rejects bit oracles that are not over $\mathbb{F}_2$, since the
+//! recomposition alone does not bound the values otherwise.
+//! The prime-field system must separately prove that the multilinear extension of the bits of its
+//! copy of the column evaluates to the same `bit_evals` at the same point, for instance by
+//! non-native arithmetic over the binary field.
+
+mod common;
+mod error;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use common::*;
+pub use error::*;
+pub use prove::*;
+pub use verify::*;
@@ -0,0 +1,135 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{
+	ExtensionField, PackedField, RepackedExtension, TowerField,
+	as_packed_field::{PackScalar, PackedType},
+	underlier::UnderlierType,
+};
+use binius_math::{
+	B1, B64, MLEEmbeddingAdapter, MultilinearExtension, MultilinearPoly, MultilinearQuery,
+};
+use binius_utils::{bail, checked_arithmetics::log2_strict_usize};
+
+use super::{
+	common::{InteropParams, JointClaim},
+	error::Error,
+};
+use crate::{
+	fiat_shamir::{CanSample, Challenger},
+	transcript::ProverTranscript,
+	witness::MultilinearWitness,
+};
+
+/// The witness of a range decomposition: a value column and its bit columns, least significant
+/// bit first.
+#[derive(Debug, Clone)]
+pub struct RangeDecompositionWitness<'a, P: PackedField> {
+	pub value: MultilinearWitness<'a, P>,
+	pub bits: Vec<MultilinearWitness<'a, P>>,
+}
+
+/// Decomposes a column of integer values into the value column over $\mathbb{F}_{2^{64}}$ and
+/// its bit columns over $\mathbb{F}_2$.
+///
+/// The number of values must be a power of two, and each value must fit in `params.n_bits()`
+/// bits.
+pub fn decompose<U, F>(
+	params: &InteropParams,
+	values: &[u64],
+) -> Result<RangeDecompositionWitness<'static, PackedType<U, F>>, Error>
+where
+	U: UnderlierType + PackScalar<B1> + PackScalar<B64> + PackScalar<F>,
+	F: TowerField + ExtensionField<B1> + ExtensionField<B64>,
+	PackedType<U, F>: RepackedExtension<PackedType<U, B1>> + RepackedExtension<PackedType<U, B64>>,
+{
+	if !values.len().is_power_of_two() {
+		bail!(Error::ColumnLengthNotPowerOfTwo);
+	}
+	let n_vars = log2_strict_usize(values.len());
+	let n_bits = params.n_bits();
+	if let Some((index, &value)) = values
+		.iter()
+		.enumerate()
+		.find(|&(_, &value)| n_bits < 64 && value >> n_bits != 0)
+	{
+		bail!(Error::ValueOutOfRange {
+			index,
+			value,
+			n_bits
+		});
+	}
+
+	let value_mle = MultilinearExtension::new(
+		n_vars,
+		pack_scalars::<PackedType<U, B64>>(values.iter().map(|&value| B64::new(value))),
+	)?;
+	let value = MLEEmbeddingAdapter::<_, PackedType<U, F>>::from(value_mle).upcast_arc_dyn();
+
+	let bits = (0..n_bits)
+		.map(|j| {
+			let bit_mle = MultilinearExtension::new(
+				n_vars,
+				pack_scalars::<PackedType<U, B1>>(
+					values.iter().map(|&value| B1::from((value >> j) as u8 & 1)),
+				),
+			)?;
+			Ok(MLEEmbeddingAdapter::<_, PackedType<U, F>>::from(bit_mle).upcast_arc_dyn())
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	Ok(RangeDecompositionWitness { value, bits })
+}
+
+fn pack_scalars<P: PackedField>(scalars: impl Iterator<Item = P::Scalar>) -> Vec<P> {
+	let scalars = scalars.collect::<Vec<_>>();
+	scalars
+		.chunks(P::WIDTH)
+		.map(|chunk| P::from_scalars(chunk.iter().copied()))
+		.collect()
+}
+
+/// Prove the range decomposition of a value column.
+///
+/// Samples an evaluation point, writes the evaluations of the value column and of each bit
+/// column there to the transcript, and returns the resulting [`JointClaim`].
+pub fn prove<F, P, Challenger_>(
+	params: &InteropParams,
+	witness: &RangeDecompositionWitness<P>,
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<JointClaim<F>, Error>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+	Challenger_: Challenger,
+{
+	if witness.bits.len() != params.n_bits() {
+		bail!(Error::IncorrectNumberOfBitColumns {
+			expected: params.n_bits(),
+			actual: witness.bits.len(),
+		});
+	}
+	let n_vars = witness.value.n_vars();
+	if witness.bits.iter().any(|bit| bit.n_vars() != n_vars) {
+		bail!(Error::NumberOfVariablesMismatch);
+	}
+
+	let eval_point = transcript.sample_vec(n_vars);
+	let query = MultilinearQuery::<P>::expand(&eval_point);
+	let value_eval = witness.value.evaluate(query.to_ref())?;
+	let bit_evals = witness
+		.bits
+		.iter()
+		.map(|bit| bit.evaluate(query.to_ref()))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut writer = transcript.message();
+	writer.write_scalar(value_eval);
+	writer.write_scalar_slice(&bit_evals);
+
+	Ok(JointClaim {
+		params: *params,
+		eval_point,
+		value_eval,
+		bit_evals,
+	})
+}
@@ -0,0 +1,123 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::arch::OptimalUnderlier128b;
+use binius_hash::groestl::Groestl256;
+use binius_math::B128;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use super::{Error, InteropParams, VerificationError, decompose, prove, verify};
+use crate::{
+	fiat_shamir::HasherChallenger,
+	oracle::{MultilinearOracleSet, OracleId},
+	transcript::ProverTranscript,
+};
+
+type U = OptimalUnderlier128b;
+type F = B128;
+
+/// The Goldilocks prime, 2^64 - 2^32 + 1.
+const GOLDILOCKS_MODULUS: u128 = 0xFFFF_FFFF_0000_0001;
+
+fn random_values(rng: &mut impl Rng, n_vars: usize, n_bits: usize) -> Vec<u64> {
+	(0..1 << n_vars)
+		.map(|_| rng.r#gen::<u64>() & ((1 << n_bits) - 1))
+		.collect()
+}
+
+#[test]
+fn test_interop_params() {
+	assert!(InteropParams::new(GOLDILOCKS_MODULUS, 32).is_ok());
+	assert!(InteropParams::new(GOLDILOCKS_MODULUS, 63).is_ok());
+	assert!(matches!(
+		InteropParams::new(GOLDILOCKS_MODULUS, 64),
+		Err(Error::ModulusTooSmall { .. })
+	));
+	assert!(matches!(InteropParams::new(u128::MAX, 65), Err(Error::TooManyBits { .. })));
+}
+
+#[test]
+fn test_prove_verify_range_decomposition() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let n_vars = 6;
+	let params = InteropParams::new(GOLDILOCKS_MODULUS, 32).unwrap();
+	let values = random_values(&mut rng, n_vars, params.n_bits());
+	let witness = decompose::<U, F>(&params, &values).unwrap();
+
+	let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let prover_claim = prove(&params, &witness, &mut prover_transcript).unwrap();
+
+	let mut verifier_transcript = prover_transcript.into_verifier();
+	let verifier_claim = verify::<F, _>(&params, n_vars, &mut verifier_transcript).unwrap();
+	verifier_transcript.finalize().unwrap();
+
+	assert_eq!(prover_claim, verifier_claim);
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let value_id = oracles.add_committed(n_vars, 6);
+	let bit_ids = (0..params.n_bits())
+		.map(|_| oracles.add_committed(n_vars, 0))
+		.collect::<Vec<_>>();
+	let eval_claims = verifier_claim
+		.eval_claims(&oracles, value_id, &bit_ids)
+		.unwrap();
+	assert_eq!(eval_claims.len(), params.n_bits() + 1);
+	assert_eq!(eval_claims[0].eval, verifier_claim.value_eval);
+}
+
+#[test]
+fn test_decompose_rejects_out_of_range_values() {
+	let params = InteropParams::new(GOLDILOCKS_MODULUS, 8).unwrap();
+	assert!(matches!(
+		decompose::<U, F>(&params, &[1, 2, 256, 3]),
+		Err(Error::ValueOutOfRange { index: 2, .. })
+	));
+}
+
+#[test]
+fn test_verify_rejects_inconsistent_decomposition() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let n_vars = 6;
+	let params = InteropParams::new(GOLDILOCKS_MODULUS, 32).unwrap();
+	let values = random_values(&mut rng, n_vars, params.n_bits());
+	let mut witness = decompose::<U, F>(&params, &values).unwrap();
+	// Swap in the bits of different values.
+	let other_values = random_values(&mut rng, n_vars, params.n_bits());
+	witness.bits = decompose::<U, F>(&params, &other_values).unwrap().bits;
+
+	let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prove(&params, &witness, &mut prover_transcript).unwrap();
+
+	let mut verifier_transcript = prover_transcript.into_verifier();
+	assert!(matches!(
+		verify::<F, _>(&params, n_vars, &mut verifier_transcript),
+		Err(Error::Verification(VerificationError::IncorrectRecomposition))
+	));
+}
+
+#[test]
+fn test_eval_claims_rejects_non_binary_bit_columns() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let n_vars = 6;
+	let params = InteropParams::new(GOLDILOCKS_MODULUS, 8).unwrap();
+	let values = random_values(&mut rng, n_vars, params.n_bits());
+	let witness = decompose::<U, F>(&params, &values).unwrap();
+
+	let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prove(&params, &witness, &mut prover_transcript).unwrap();
+	let mut verifier_transcript = prover_transcript.into_verifier();
+	let claim = verify::<F, _>(&params, n_vars, &mut verifier_transcript).unwrap();
+	verifier_transcript.finalize().unwrap();
+
+	// A bit column committed over a larger field is not bounded by the recomposition.
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let value_id = oracles.add_committed(n_vars, 6);
+	let mut bit_ids = (0..params.n_bits())
+		.map(|_| oracles.add_committed(n_vars, 0))
+		.collect::<Vec<_>>();
+	let wide_id = oracles.add_committed(n_vars, 3);
+	bit_ids[5] = wide_id;
+
+	assert!(matches!(
+		claim.eval_claims(&oracles, value_id, &bit_ids),
+		Err(Error::BitColumnNotBinary { id, tower_level: 3 }) if id == wide_id
+	));
+}
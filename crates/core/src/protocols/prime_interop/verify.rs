@@ -0,0 +1,44 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{ExtensionField, TowerField};
+use binius_math::B64;
+use binius_utils::bail;
+
+use super::{
+	common::{InteropParams, JointClaim, recompose},
+	error::{Error, VerificationError},
+};
+use crate::{
+	fiat_shamir::{CanSample, Challenger},
+	transcript::VerifierTranscript,
+};
+
+/// Verify the range decomposition of an `n_vars`-variate value column.
+///
+/// Reads the evaluations of the value column and of each bit column at a sampled point, and
+/// checks that the value evaluation is the recomposition of the bit evaluations.
+pub fn verify<F, Challenger_>(
+	params: &InteropParams,
+	n_vars: usize,
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<JointClaim<F>, Error>
+where
+	F: TowerField + ExtensionField<B64>,
+	Challenger_: Challenger,
+{
+	let eval_point = transcript.sample_vec(n_vars);
+	let mut reader = transcript.message();
+	let value_eval = reader.read_scalar()?;
+	let bit_evals = reader.read_scalar_slice(params.n_bits())?;
+
+	if recompose(&bit_evals) != value_eval {
+		bail!(VerificationError::IncorrectRecomposition);
+	}
+
+	Ok(JointClaim {
+		params: *params,
+		eval_point,
+		value_eval,
+		bit_evals,
+	})
+}
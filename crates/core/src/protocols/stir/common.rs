@@ -0,0 +1,432 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{iter, marker::PhantomData};
+
+use binius_field::{BinaryField, ExtensionField, Field, PackedField};
+use binius_hal::{ComputationBackend, make_portable_backend};
+use binius_math::BinarySubspace;
+use binius_ntt::{AdditiveNTT, fri::fold_interleaved_chunk};
+use binius_utils::{bail, checked_arithmetics::log2_ceil_usize};
+use getset::{CopyGetters, Getters};
+
+use super::error::Error;
+use crate::{fiat_shamir::CanSampleBits, protocols::fri::SoundnessModel};
+
+/// Parameters for a STIR interleaved code proximity protocol.
+///
+/// The protocol runs one round per fold arity. In round $j$ the prover folds the word $f_j$ with
+/// arity $k_j$ and sends the fold, re-encoded with rate reduced by a factor of $2^{k_j - 1}$
+/// relative to $f_j$, over a fresh evaluation domain $L_{j+1}$ of half the size of $L_j$. The
+/// final round sends the folded polynomial in the clear.
+///
+/// The evaluation domains are unions of cosets of a subspace in the chain of subspaces of the
+/// additive NTT, all indexed within [`Self::subspace`]. The domain of round $j$ consists of
+/// $2^{R_j}$ consecutive cosets of dimension $m_j$, where $m_j$ is the binary logarithm of the
+/// degree bound and $R_j$ is the binary logarithm of the inverse rate of the round. The cosets of
+/// each round begin where the folded cosets of the previous round end, so that every domain is
+/// disjoint from the points that the verifier quotients the next word by.
+#[derive(Debug, Getters, CopyGetters)]
+pub struct STIRParams<F, FA>
+where
+	F: BinaryField,
+	FA: BinaryField,
+{
+	/// The subspace containing the evaluation domains of all rounds.
+	#[getset(get = "pub")]
+	subspace: BinarySubspace<FA>,
+	/// The binary logarithm of the dimension of each message in the batch.
+	#[getset(get_copy = "pub")]
+	log_dim: usize,
+	/// The binary logarithm of the inverse rate of the committed code.
+	#[getset(get_copy = "pub")]
+	log_inv_rate: usize,
+	/// The binary logarithm of the number of interleaved messages.
+	#[getset(get_copy = "pub")]
+	log_batch_size: usize,
+	/// The fold arity of each round, where the first includes the interleaving bits.
+	fold_arities: Vec<usize>,
+	/// The number of shift queries of each round, where the last are the final queries.
+	n_queries: Vec<usize>,
+	/// The number of out-of-domain samples of each round before the last.
+	#[getset(get_copy = "pub")]
+	n_ood_samples: usize,
+	_marker: PhantomData<F>,
+}
+
+impl<F, FA> STIRParams<F, FA>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+{
+	/// Constructs STIR parameters with an explicit number of queries per round.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidFoldAritySequence`] if there are no rounds or the arities fold the message
+	///   beyond a constant
+	/// * [`Error::FirstFoldArityTooSmall`] if the first arity does not exceed the log batch size
+	/// * [`Error::FoldArityIsZero`] if a later arity is zero
+	/// * [`Error::InvalidArgs`] if the number of queries per round does not match the number of
+	///   rounds, or a round quotients by at least as many points as its degree bound
+	pub fn new(
+		log_dim: usize,
+		log_inv_rate: usize,
+		log_batch_size: usize,
+		fold_arities: Vec<usize>,
+		n_queries: Vec<usize>,
+		n_ood_samples: usize,
+	) -> Result<Self, Error> {
+		let Some((&first_arity, rest_arities)) = fold_arities.split_first() else {
+			bail!(Error::InvalidFoldAritySequence);
+		};
+		if first_arity <= log_batch_size {
+			bail!(Error::FirstFoldArityTooSmall);
+		}
+		if let Some(index) = rest_arities.iter().position(|&arity| arity == 0) {
+			bail!(Error::FoldArityIsZero { index: index + 1 });
+		}
+		if fold_arities.iter().sum::<usize>() > log_dim + log_batch_size {
+			bail!(Error::InvalidFoldAritySequence);
+		}
+		if n_queries.len() != fold_arities.len() {
+			bail!(Error::InvalidArgs(format!(
+				"got {} query counts, expected one for each of the {} rounds",
+				n_queries.len(),
+				fold_arities.len(),
+			)));
+		}
+
+		let mut params = Self {
+			subspace: BinarySubspace::default(),
+			log_dim,
+			log_inv_rate,
+			log_batch_size,
+			fold_arities,
+			n_queries,
+			n_ood_samples,
+			_marker: PhantomData,
+		};
+
+		for round in 0..params.n_rounds() - 1 {
+			if params.n_queries[round] + n_ood_samples >= 1 << params.round_log_dim(round + 1) {
+				bail!(Error::InvalidArgs(format!(
+					"round {round} quotients by more points than its degree bound"
+				)));
+			}
+		}
+
+		params.subspace = BinarySubspace::with_dim(params.log_domain_size())?;
+		Ok(params)
+	}
+
+	/// Constructs STIR parameters meeting a security target, choosing the number of queries of
+	/// each round for its rate under the given soundness analysis.
+	///
+	/// Out-of-domain samples are only needed to make the decoding of the folded words unique in the
+	/// list-decoding regime, so one is sampled per round under
+	/// [`SoundnessModel::ConjecturedListDecoding`] and none otherwise.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ParameterError`] if the security target cannot be met with the code
+	/// * the errors of [`Self::new`] for invalid arities
+	pub fn with_security(
+		log_dim: usize,
+		log_inv_rate: usize,
+		log_batch_size: usize,
+		fold_arities: Vec<usize>,
+		security_bits: usize,
+		soundness_model: SoundnessModel,
+	) -> Result<Self, Error> {
+		let n_ood_samples = match soundness_model {
+			SoundnessModel::Provable => 0,
+			SoundnessModel::ConjecturedListDecoding => 1,
+		};
+
+		let field_size = 2.0_f64.powi(F::N_BITS as i32);
+		// |L_0| / |F|, which bounds the error of folding and out-of-domain sampling in every round
+		let folding_err = 2.0_f64.powi((log_dim + log_inv_rate) as i32) / field_size;
+		let allowed_err = 2.0_f64.powi(-(security_bits as i32)) - folding_err;
+		if allowed_err <= 0.0 {
+			bail!(Error::ParameterError);
+		}
+
+		let mut round_log_inv_rate = log_inv_rate;
+		let n_queries = fold_arities
+			.iter()
+			.enumerate()
+			.map(|(round, &arity)| {
+				let per_query_err = soundness_model.per_query_err(round_log_inv_rate);
+				if per_query_err >= 1.0 {
+					bail!(Error::ParameterError);
+				}
+				let log_fold = if round == 0 {
+					arity.saturating_sub(log_batch_size)
+				} else {
+					arity
+				};
+				round_log_inv_rate = (round_log_inv_rate + log_fold).saturating_sub(1);
+				Ok(allowed_err.log(per_query_err).ceil() as usize)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		Self::new(log_dim, log_inv_rate, log_batch_size, fold_arities, n_queries, n_ood_samples)
+			.map_err(|err| match err {
+				Error::InvalidArgs(_) => Error::ParameterError,
+				err => err,
+			})
+	}
+
+	/// The number of rounds, including the final round.
+	pub fn n_rounds(&self) -> usize {
+		self.fold_arities.len()
+	}
+
+	/// The fold arity of each round, where the first includes the interleaving bits.
+	pub fn fold_arities(&self) -> &[usize] {
+		&self.fold_arities
+	}
+
+	/// The number of shift queries of the given round, which for the last round are the final
+	/// queries.
+	pub fn n_queries(&self, round: usize) -> usize {
+		self.n_queries[round]
+	}
+
+	/// The binary logarithm of the length of the committed interleaved codeword.
+	pub fn log_len(&self) -> usize {
+		self.log_dim + self.log_inv_rate + self.log_batch_size
+	}
+
+	/// The binary logarithm of the number of coefficients of the final polynomial.
+	pub fn log_final_dim(&self) -> usize {
+		self.round_log_dim(self.n_rounds())
+	}
+
+	/// The dimension of [`Self::subspace`].
+	pub fn log_domain_size(&self) -> usize {
+		self.round_log_dim(0) + log2_ceil_usize(self.coset_offset(self.n_rounds()))
+	}
+
+	/// The number of variables that round `round` folds by, excluding the interleaving bits.
+	pub(super) fn log_fold(&self, round: usize) -> usize {
+		match round {
+			0 => self.fold_arities[0] - self.log_batch_size,
+			_ => self.fold_arities[round],
+		}
+	}
+
+	/// The binary logarithm $m_j$ of the degree bound of the word folded in round `round`.
+	pub(super) fn round_log_dim(&self, round: usize) -> usize {
+		self.log_dim - (0..round).map(|i| self.log_fold(i)).sum::<usize>()
+	}
+
+	/// The binary logarithm $R_j$ of the inverse rate of the word folded in round `round`.
+	pub(super) fn round_log_inv_rate(&self, round: usize) -> usize {
+		self.log_inv_rate + (0..round).map(|i| self.log_fold(i) - 1).sum::<usize>()
+	}
+
+	/// The index of the first coset of the evaluation domain of round `round`.
+	pub(super) fn coset_offset(&self, round: usize) -> usize {
+		(0..round).map(|i| 1 << self.round_log_inv_rate(i)).sum()
+	}
+
+	/// The binary logarithm of the number of leaves in the Merkle tree of the word folded in round
+	/// `round`, which is the number of cosets folded into one value each.
+	pub(super) fn log_n_leaves(&self, round: usize) -> usize {
+		self.round_log_dim(round + 1) + self.round_log_inv_rate(round)
+	}
+
+	/// The dimension of the subspace whose cosets the domain of round `round` consists of.
+	pub(super) fn round_log_domain_size(&self, round: usize) -> usize {
+		self.log_domain_size() - (self.round_log_dim(0) - self.round_log_dim(round))
+	}
+}
+
+/// Samples the indices of the queries of a round, sorted in increasing order and deduplicated.
+pub(super) fn sample_query_indices(
+	n_queries: usize,
+	index_bits: usize,
+	sampler: &mut impl CanSampleBits<u32>,
+) -> Vec<usize> {
+	let mut indices = (0..n_queries)
+		.map(|_| sampler.sample_bits(index_bits) as usize)
+		.collect::<Vec<_>>();
+	indices.sort_unstable();
+	indices.dedup();
+	indices
+}
+
+/// Returns the element at `index` of the subspace with dimension `log_dim` in the NTT's chain of
+/// subspaces.
+///
+/// The elements are indexed by their coordinates in the basis of the subspace, whose first element
+/// is one, so that the codeword index `index` of an NTT over the subspace is evaluated here.
+pub(super) fn domain_point<FA, NTT>(ntt: &NTT, log_dim: usize, index: usize) -> FA
+where
+	FA: BinaryField,
+	NTT: AdditiveNTT<FA>,
+{
+	let point = ntt.get_subspace_eval(ntt.log_domain_size() - log_dim, index >> 1);
+	if index & 1 == 1 {
+		point + FA::ONE
+	} else {
+		point
+	}
+}
+
+/// Folds the leaves of the oracle queried in a round, each into a value of the folded polynomial.
+pub(super) struct LeafFolder<'a, F, FA, NTT> {
+	ntt: &'a NTT,
+	/// The dimension of the subspace that the round's domain consists of cosets of.
+	log_len: usize,
+	log_batch_size: usize,
+	/// The index of the first leaf of the round's domain among all cosets of its subspace.
+	chunk_offset: usize,
+	interleave_tensor: Vec<F>,
+	fold_challenges: &'a [F],
+	_marker: PhantomData<FA>,
+}
+
+impl<'a, F, FA, NTT> LeafFolder<'a, F, FA, NTT>
+where
+	F: BinaryField + ExtensionField<FA> + PackedField<Scalar = F>,
+	FA: BinaryField,
+	NTT: AdditiveNTT<FA>,
+{
+	/// Constructs the folder of round `round` with the round's folding challenges, which in the
+	/// first round begin with the challenges combining the interleaved words.
+	pub fn new(
+		params: &STIRParams<F, FA>,
+		ntt: &'a NTT,
+		round: usize,
+		challenges: &'a [F],
+	) -> Self {
+		let log_batch_size = if round == 0 {
+			params.log_batch_size()
+		} else {
+			0
+		};
+		let (interleave_challenges, fold_challenges) = challenges.split_at(log_batch_size);
+		let interleave_tensor = make_portable_backend()
+			.tensor_product_full_query(interleave_challenges)
+			.expect("number of challenges is less than 32");
+
+		Self {
+			ntt,
+			log_len: params.round_log_domain_size(round),
+			log_batch_size,
+			chunk_offset: params.coset_offset(round) << params.round_log_dim(round + 1),
+			interleave_tensor,
+			fold_challenges,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Returns a scratch buffer for [`Self::fold`].
+	pub fn scratch_buffer(&self) -> Vec<F> {
+		vec![F::ZERO; 1 << self.fold_challenges.len()]
+	}
+
+	/// Folds the values of the leaf at `leaf_index` in the round's domain.
+	pub fn fold(&self, leaf_index: usize, values: &[F], scratch_buffer: &mut [F]) -> F {
+		fold_interleaved_chunk(
+			self.ntt,
+			self.log_len,
+			self.log_batch_size,
+			self.chunk_offset + leaf_index,
+			values,
+			&self.interleave_tensor,
+			self.fold_challenges,
+			scratch_buffer,
+		)
+	}
+}
+
+/// The quotient that defines the word folded in a round in terms of the word committed by the
+/// previous round.
+///
+/// For the committed word $g$, the set of points $G$ with claimed values, the interpolant
+/// $\mathrm{Ans}$ of the claims and the vanishing polynomial $V$ of $G$, the folded word is
+///
+/// $$
+/// f(x) = \frac{g(x) - \mathrm{Ans}(x)}{V(x)} \sum_{i=0}^{|G|} (r x)^i,
+/// $$
+///
+/// where the sum, with the random combination coefficient $r$, corrects the degree bound of the
+/// quotient back to the degree bound of $g$.
+#[derive(Debug)]
+pub(super) struct Quotient<F: Field> {
+	points: Vec<F>,
+	/// The claimed values, each scaled by the barycentric weight of its point.
+	weighted_values: Vec<F>,
+	comb: F,
+}
+
+impl<F: Field> Quotient<F> {
+	/// ## Throws
+	///
+	/// * [`Error::DegenerateQuotient`] if the points are not distinct
+	pub fn new(points: Vec<F>, values: Vec<F>, comb: F) -> Result<Self, Error> {
+		debug_assert_eq!(points.len(), values.len());
+
+		let weighted_values = iter::zip(&points, values)
+			.enumerate()
+			.map(|(i, (&point_i, value))| {
+				let denominator = points
+					.iter()
+					.enumerate()
+					.filter(|&(j, _)| j != i)
+					.map(|(_, &point_j)| point_i - point_j)
+					.product::<F>();
+				let Some(weight) = denominator.invert() else {
+					bail!(Error::DegenerateQuotient);
+				};
+				Ok(value * weight)
+			})
+			.collect::<Result<_, Error>>()?;
+
+		Ok(Self {
+			points,
+			weighted_values,
+			comb,
+		})
+	}
+
+	/// Evaluates the folded word at `x`, given the value of the committed word at `x`.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::DegenerateQuotient`] if `x` is one of the quotiented points
+	pub fn evaluate(&self, x: F, value: F) -> Result<F, Error> {
+		let n_points = self.points.len();
+
+		// suffix_products[i] is the product of (x - p_k) over k >= i.
+		let mut suffix_products = vec![F::ONE; n_points + 1];
+		for i in (0..n_points).rev() {
+			suffix_products[i] = suffix_products[i + 1] * (x - self.points[i]);
+		}
+		let Some(vanishing_inv) = suffix_products[0].invert() else {
+			bail!(Error::DegenerateQuotient);
+		};
+
+		let mut prefix_product = F::ONE;
+		let mut answer = F::ZERO;
+		for (i, (&point, &weighted_value)) in
+			iter::zip(&self.points, &self.weighted_values).enumerate()
+		{
+			answer += weighted_value * prefix_product * suffix_products[i + 1];
+			prefix_product *= x - point;
+		}
+
+		let comb_x = self.comb * x;
+		let mut power = F::ONE;
+		let mut degree_correction = F::ONE;
+		for _ in 0..n_points {
+			power *= comb_x;
+			degree_correction += power;
+		}
+
+		Ok((value - answer) * vanishing_inv * degree_correction)
+	}
+}
@@ -0,0 +1,53 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_ntt::Error as NttError;
+
+use crate::{merkle_tree, transcript};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("cannot calculate parameters satisfying the security target")]
+	ParameterError,
+	#[error("conflicting or incorrect constructor argument: {0}")]
+	InvalidArgs(String),
+	#[error("fold arities must be non-empty and their total must not exceed the message length")]
+	InvalidFoldAritySequence,
+	#[error("fold arity at index {index} in sequence is zero")]
+	FoldArityIsZero { index: usize },
+	#[error("the fold arity for the first fold should exceed the log batch size")]
+	FirstFoldArityTooSmall,
+	#[error("the NTT domain does not contain the subspace of the STIR parameters")]
+	EncoderSubspaceMismatch,
+	#[error("an out-of-domain sample coincides with a quotiented or evaluation domain point")]
+	DegenerateQuotient,
+	#[error("math error: {0}")]
+	Math(#[from] binius_math::Error),
+	#[error("Reed-Solomon encoding error: {0}")]
+	EncodeError(#[from] NttError),
+	#[error("vector commit error: {0}")]
+	VectorCommit(#[source] Box<dyn std::error::Error + Send + Sync>),
+	#[error("verification error: {0}")]
+	Verification(#[from] VerificationError),
+	#[error("transcript error: {0}")]
+	TranscriptError(#[from] transcript::Error),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+	#[error("the decommitted layer of oracle {oracle} is invalid: {source}")]
+	InvalidLayer {
+		oracle: usize,
+		#[source]
+		source: merkle_tree::Error,
+	},
+	#[error("the openings of oracle {oracle} are invalid: {source}")]
+	InvalidOpenings {
+		oracle: usize,
+		#[source]
+		source: merkle_tree::Error,
+	},
+	#[error(
+		"the fold of the final query at index {query_index} does not match the final polynomial"
+	)]
+	IncorrectFold { query_index: usize },
+}
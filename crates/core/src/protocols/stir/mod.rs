@@ -0,0 +1,30 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Implementation of the Shift To Improve Rate (STIR) IOPP over binary fields.
+//!
+//! STIR is an IOP of Proximity for Reed–Solomon codes introduced in [ACFY24]. Like FRI, every
+//! round folds the purported codeword, but rather than sending the fold over the folded domain,
+//! the prover sends it encoded over a new domain that is only half the size of the previous one.
+//! The rate of the code therefore improves in every round, and since a query to a code of lower
+//! rate rejects a far word with higher probability, later rounds need fewer queries. The verifier
+//! ties the new word to the previous one by querying the previous word at random "shift" points
+//! and virtually quotienting the new word by the answers.
+//!
+//! The protocol is implemented for an interleaved code, with the first round combining the
+//! interleaved codewords like the first fold rounds of [`super::fri`]. The evaluation domains are
+//! unions of cosets of the subspaces of an additive NTT, as described in
+//! [`STIRParams`].
+//!
+//! [ACFY24]: <https://eprint.iacr.org/2024/390>
+
+mod common;
+mod error;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use common::STIRParams;
+pub use error::*;
+pub use prove::*;
+pub use verify::*;
@@ -0,0 +1,387 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{
+	BinaryField, ExtensionField, PackedExtension, PackedField, TowerField,
+	packed::{iter_packed_slice_with_offset, len_packed_slice},
+};
+use binius_maybe_rayon::prelude::*;
+use binius_ntt::{AdditiveNTT, NTTShape};
+use binius_utils::{SerializeBytes, bail, checked_arithmetics::log2_strict_usize};
+use bytes::BufMut;
+use tracing::instrument;
+
+use super::{
+	common::{LeafFolder, Quotient, STIRParams, domain_point, sample_query_indices},
+	error::Error,
+};
+use crate::{
+	fiat_shamir::{CanSample, Challenger},
+	merkle_tree::{MerkleTreeProver, MerkleTreeScheme},
+	protocols::fri::{CommitOutput, to_par_scalar_big_chunks, to_par_scalar_small_chunks},
+	transcript::{ProverTranscript, TranscriptWriter},
+};
+
+/// Encodes and commits the input message.
+///
+/// ## Arguments
+///
+/// * `params` - common STIR protocol parameters.
+/// * `ntt` - the NTT, whose domain must contain the subspace of the parameters
+/// * `merkle_prover` - the Merkle tree prover to use for committing
+/// * `message` - the interleaved message to encode and commit
+#[instrument(skip_all, name = "stir::commit_interleaved", level = "debug")]
+pub fn commit_interleaved<F, FA, P, NTT, MerkleProver, VCS>(
+	params: &STIRParams<F, FA>,
+	ntt: &NTT,
+	merkle_prover: &MerkleProver,
+	message: &[P],
+) -> Result<CommitOutput<P, VCS::Digest, MerkleProver::Committed>, Error>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FA>,
+	NTT: AdditiveNTT<FA> + Sync,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F>,
+{
+	let log_batch_size = params.log_batch_size();
+	let log_elems = params.log_dim() + log_batch_size;
+	if log_elems < P::LOG_WIDTH || len_packed_slice(message) != 1 << log_elems {
+		bail!(Error::InvalidArgs(
+			"interleaved message length must match the code parameters and fill a packed element"
+				.to_string()
+		));
+	}
+	check_ntt_subspace(params, ntt)?;
+
+	// The committed codeword consists of the evaluations over the first 2^{R_0} cosets in the
+	// subspace of the parameters, which are adjacent and are encoded by one NTT over the repeated
+	// message that skips the rounds distinguishing the cosets.
+	let log_inv_rate = params.log_inv_rate();
+	let mut codeword = Vec::with_capacity(message.len() << log_inv_rate);
+	for _ in 0..1 << log_inv_rate {
+		codeword.extend_from_slice(message);
+	}
+	let log_code_len = params.log_dim() + log_inv_rate;
+	let shape = NTTShape {
+		log_x: log_batch_size,
+		log_y: log_code_len,
+		..Default::default()
+	};
+	ntt.forward_transform_ext(
+		&mut codeword,
+		shape,
+		0,
+		params.log_domain_size() - log_code_len,
+		log_inv_rate,
+	)?;
+
+	let log_leaf_size = params.fold_arities()[0];
+	let log_n_leaves = params.log_len() - log_leaf_size;
+	let (commitment, committed) = if log_leaf_size > P::LOG_WIDTH {
+		let iterated_big_chunks = to_par_scalar_big_chunks(&codeword, 1 << log_leaf_size);
+
+		merkle_prover
+			.commit_iterated(iterated_big_chunks, log_n_leaves)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?
+	} else {
+		let iterated_small_chunks = to_par_scalar_small_chunks(&codeword, 1 << log_leaf_size);
+
+		merkle_prover
+			.commit_iterated(iterated_small_chunks, log_n_leaves)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?
+	};
+
+	Ok(CommitOutput {
+		commitment: commitment.root,
+		committed,
+		codeword: codeword.into(),
+	})
+}
+
+/// The oracle that a round after the first queries.
+struct RoundOracle<F, Committed> {
+	/// The word committed by the previous round.
+	codeword: Vec<F>,
+	committed: Committed,
+	/// The word folded in the round, which is the quotient of the committed word.
+	word: Vec<F>,
+}
+
+/// Proves that a codeword committed with [`commit_interleaved`] is close to the interleaved
+/// Reed–Solomon code of the parameters.
+///
+/// The commitment to the codeword must have been observed by the transcript beforehand.
+#[instrument(skip_all, name = "stir::prove", level = "debug")]
+pub fn prove<F, FA, P, NTT, MerkleProver, VCS, Challenger_>(
+	params: &STIRParams<F, FA>,
+	ntt: &NTT,
+	merkle_prover: &MerkleProver,
+	codeword: &[P],
+	committed: &MerkleProver::Committed,
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FA> + PackedExtension<FA, Scalar = F>,
+	FA: BinaryField,
+	P: PackedField<Scalar = F>,
+	NTT: AdditiveNTT<FA> + Sync,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F, Digest: SerializeBytes>,
+	Challenger_: Challenger,
+{
+	if len_packed_slice(codeword) != 1 << params.log_len() {
+		bail!(Error::InvalidArgs(
+			"interleaved codeword length does not match the code parameters".to_string()
+		));
+	}
+	check_ntt_subspace(params, ntt)?;
+
+	let log_coset_bits = params.log_domain_size() - params.log_dim();
+	let mut round_oracle = None::<RoundOracle<F, MerkleProver::Committed>>;
+	for round in 0..params.n_rounds() {
+		let log_leaf_size = params.fold_arities()[round];
+		let challenges = transcript.sample_vec(log_leaf_size);
+		let folder = LeafFolder::new(params, ntt, round, &challenges);
+
+		// The values of a leaf of the word folded in this round.
+		let word = round_oracle.as_ref().map(|oracle| oracle.word.as_slice());
+		let leaf_values = |leaf_index: usize| match word {
+			Some(word) => word[leaf_index << log_leaf_size..][..1 << log_leaf_size].to_vec(),
+			None => iter_packed_slice_with_offset(codeword, leaf_index << log_leaf_size)
+				.take(1 << log_leaf_size)
+				.collect(),
+		};
+
+		// The folded polynomial is determined by its evaluations over the first coset of the
+		// folded domain, which are interpolated into its coefficients.
+		let log_next_dim = params.round_log_dim(round + 1);
+		let log_fold_len = params.round_log_domain_size(round + 1);
+		let coset_offset = params.coset_offset(round);
+		let coset_evals = (0..1 << log_next_dim)
+			.into_par_iter()
+			.map_init(
+				|| folder.scratch_buffer(),
+				|scratch_buffer, leaf_index| {
+					folder.fold(leaf_index, &leaf_values(leaf_index), scratch_buffer)
+				},
+			)
+			.collect::<Vec<_>>();
+		let shape = NTTShape {
+			log_y: log_next_dim,
+			..Default::default()
+		};
+		let mut coeffs = coset_evals.clone();
+		ntt.inverse_transform_ext(&mut coeffs, shape, coset_offset, log_coset_bits, 0)?;
+
+		let (oracle_codeword, oracle_committed) = match &round_oracle {
+			Some(oracle) => (OracleCodeword::Round(&oracle.codeword), &oracle.committed),
+			None => (OracleCodeword::Committed(codeword), committed),
+		};
+
+		if round + 1 == params.n_rounds() {
+			transcript.message().write_scalar_slice(&coeffs);
+
+			let indices = sample_query_indices(
+				params.n_queries(round),
+				params.log_n_leaves(round),
+				transcript,
+			);
+			prove_openings(
+				merkle_prover,
+				oracle_codeword,
+				oracle_committed,
+				log_leaf_size,
+				params.log_n_leaves(round),
+				params.n_queries(round),
+				&indices,
+				&mut transcript.decommitment(),
+			)?;
+			break;
+		}
+
+		// Send the folded polynomial, encoded over the cosets of the next domain.
+		let next_coset_offset = params.coset_offset(round + 1);
+		let mut next_codeword =
+			vec![F::ZERO; 1 << (log_next_dim + params.round_log_inv_rate(round + 1))];
+		next_codeword
+			.par_chunks_mut(1 << log_next_dim)
+			.enumerate()
+			.try_for_each(|(i, coset)| {
+				coset.copy_from_slice(&coeffs);
+				ntt.forward_transform_ext(coset, shape, next_coset_offset + i, log_coset_bits, 0)
+			})?;
+		let (commitment, next_committed) = merkle_prover
+			.commit(&next_codeword, 1 << params.fold_arities()[round + 1])
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		transcript.message().write(&commitment.root);
+
+		let n_points = params.n_ood_samples() + params.n_queries(round);
+		let mut points = Vec::with_capacity(n_points);
+		let mut values = Vec::with_capacity(n_points);
+		for _ in 0..params.n_ood_samples() {
+			let point = transcript.sample();
+			let value = evaluate_on_coset(ntt, log_fold_len, coset_offset, &coset_evals, point);
+			transcript.message().write_scalar(value);
+			points.push(point);
+			values.push(value);
+		}
+
+		let comb = transcript.sample();
+		let indices =
+			sample_query_indices(params.n_queries(round), params.log_n_leaves(round), transcript);
+		prove_openings(
+			merkle_prover,
+			oracle_codeword,
+			oracle_committed,
+			log_leaf_size,
+			params.log_n_leaves(round),
+			params.n_queries(round),
+			&indices,
+			&mut transcript.decommitment(),
+		)?;
+
+		let mut scratch_buffer = folder.scratch_buffer();
+		for &index in &indices {
+			let point = domain_point(ntt, log_fold_len, (coset_offset << log_next_dim) + index);
+			points.push(point.into());
+			values.push(folder.fold(index, &leaf_values(index), &mut scratch_buffer));
+		}
+
+		// The next round folds the quotient of the sent word by the answers to the queries.
+		let quotient = Quotient::new(points, values, comb)?;
+		let next_word = next_codeword
+			.par_iter()
+			.enumerate()
+			.map(|(i, &value)| {
+				let point =
+					domain_point(ntt, log_fold_len, (next_coset_offset << log_next_dim) + i);
+				quotient.evaluate(point.into(), value)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		round_oracle = Some(RoundOracle {
+			codeword: next_codeword,
+			committed: next_committed,
+			word: next_word,
+		});
+	}
+
+	Ok(())
+}
+
+/// The committed codeword queried in a round.
+#[derive(Clone, Copy)]
+enum OracleCodeword<'a, F, P> {
+	/// The originally committed interleaved codeword, queried in the first round.
+	Committed(&'a [P]),
+	/// The codeword committed by the previous round.
+	Round(&'a [F]),
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_openings<F, P, MerkleProver, B>(
+	merkle_prover: &MerkleProver,
+	codeword: OracleCodeword<F, P>,
+	committed: &MerkleProver::Committed,
+	log_leaf_size: usize,
+	tree_depth: usize,
+	n_queries: usize,
+	indices: &[usize],
+	advice: &mut TranscriptWriter<B>,
+) -> Result<(), Error>
+where
+	F: TowerField + PackedField<Scalar = F>,
+	P: PackedField<Scalar = F>,
+	MerkleProver: MerkleTreeProver<F>,
+	<MerkleProver::Scheme as MerkleTreeScheme<F>>::Digest: SerializeBytes,
+	B: BufMut,
+{
+	let layer_depth = merkle_prover
+		.scheme()
+		.optimal_verify_layer(n_queries, tree_depth);
+	let layer = merkle_prover
+		.layer(committed, layer_depth)
+		.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+	advice.write_slice(layer);
+
+	for &index in indices {
+		let offset = index << log_leaf_size;
+		match codeword {
+			OracleCodeword::Committed(codeword) => advice.write_scalar_iter(
+				iter_packed_slice_with_offset(codeword, offset).take(1 << log_leaf_size),
+			),
+			OracleCodeword::Round(codeword) => {
+				advice.write_scalar_slice(&codeword[offset..][..1 << log_leaf_size])
+			}
+		}
+	}
+
+	merkle_prover
+		.prove_openings(committed, layer_depth, indices, advice)
+		.map_err(|err| Error::VectorCommit(Box::new(err)))
+}
+
+/// Evaluates the polynomial with the given evaluations over a coset of a subspace at a point.
+///
+/// The evaluations are over the coset at index `coset` among the cosets of the subspace with
+/// dimension `log2(evals.len())` in the subspace with dimension `log_len` of the NTT. Since the
+/// differences between the points of a coset are the non-zero elements of the subspace, the
+/// barycentric weights are all the inverse of their product $C$, and the polynomial is
+///
+/// $$
+/// p(z) = \frac{1}{C} \sum_i y_i \prod_{k \neq i} (z - x_k).
+/// $$
+fn evaluate_on_coset<F, FA, NTT>(
+	ntt: &NTT,
+	log_len: usize,
+	coset: usize,
+	evals: &[F],
+	point: F,
+) -> F
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+	NTT: AdditiveNTT<FA>,
+{
+	let log_dim = log2_strict_usize(evals.len());
+	let shifted_point = point - F::from(domain_point(ntt, log_len, coset << log_dim));
+	let diffs = (0..evals.len())
+		.map(|i| shifted_point - F::from(domain_point(ntt, log_len, i)))
+		.collect::<Vec<_>>();
+
+	let mut suffix_products = vec![F::ONE; evals.len() + 1];
+	for i in (0..evals.len()).rev() {
+		suffix_products[i] = suffix_products[i + 1] * diffs[i];
+	}
+	let mut prefix_product = F::ONE;
+	let mut sum = F::ZERO;
+	for (i, (&eval, &diff)) in evals.iter().zip(&diffs).enumerate() {
+		sum += eval * prefix_product * suffix_products[i + 1];
+		prefix_product *= diff;
+	}
+
+	let subspace_product = (1..evals.len())
+		.map(|i| domain_point(ntt, log_len, i))
+		.product::<FA>();
+	sum * F::from(
+		subspace_product
+			.invert()
+			.expect("the subspace basis is linearly independent"),
+	)
+}
+
+fn check_ntt_subspace<F, FA, NTT>(params: &STIRParams<F, FA>, ntt: &NTT) -> Result<(), Error>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+	NTT: AdditiveNTT<FA>,
+{
+	let log_domain_size = params.log_domain_size();
+	if ntt.log_domain_size() < log_domain_size
+		|| ntt.subspace(ntt.log_domain_size() - log_domain_size) != *params.subspace()
+	{
+		bail!(Error::EncoderSubspaceMismatch);
+	}
+	Ok(())
+}
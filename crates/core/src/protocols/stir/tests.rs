@@ -0,0 +1,132 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::iter::repeat_with;
+
+use binius_field::{BinaryField16b, BinaryField32b, BinaryField128b, Field};
+use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
+use binius_ntt::SingleThreadedNTT;
+use rand::prelude::*;
+
+use super::{STIRParams, error::Error};
+use crate::{
+	fiat_shamir::HasherChallenger,
+	merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver},
+	protocols::{
+		fri::{FRIParams, SoundnessModel},
+		low_degree_test::LowDegreeTest,
+	},
+	reed_solomon::reed_solomon::ReedSolomonCode,
+	transcript::ProverTranscript,
+};
+
+type F = BinaryField128b;
+type FA = BinaryField16b;
+
+fn prove_verify<LDT: LowDegreeTest<F, FA>>(
+	ldt: &LDT,
+	ntt: &SingleThreadedNTT<FA>,
+	message: &[F],
+) -> Result<(), LDT::Error> {
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let commit_output = ldt.commit(ntt, &merkle_prover, message)?;
+
+	let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prover_transcript.message().write(&commit_output.commitment);
+	ldt.prove(
+		ntt,
+		&merkle_prover,
+		&commit_output.codeword,
+		&commit_output.committed,
+		&mut prover_transcript,
+	)?;
+
+	let mut verifier_transcript = prover_transcript.into_verifier();
+	let commitment = verifier_transcript.message().read().unwrap();
+	ldt.verify(merkle_prover.scheme(), &commitment, &mut verifier_transcript)?;
+	verifier_transcript.finalize().unwrap();
+	Ok(())
+}
+
+fn random_message(rng: &mut impl Rng, log_len: usize) -> Vec<F> {
+	repeat_with(|| F::random(&mut *rng))
+		.take(1 << log_len)
+		.collect()
+}
+
+#[test]
+fn test_commit_prove_verify_success() {
+	let mut rng = StdRng::seed_from_u64(0);
+	for n_ood_samples in [0, 1] {
+		let params =
+			STIRParams::<F, FA>::new(8, 1, 2, vec![4, 2, 2], vec![4, 3, 3], n_ood_samples).unwrap();
+		let ntt = SingleThreadedNTT::new(params.log_domain_size()).unwrap();
+		let message = random_message(&mut rng, params.log_msg_len());
+		prove_verify(&params, &ntt, &message).unwrap();
+	}
+}
+
+#[test]
+fn test_commit_prove_verify_single_round() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let params = STIRParams::<F, FA>::new(6, 2, 0, vec![3], vec![5], 1).unwrap();
+	let ntt = SingleThreadedNTT::new(params.log_domain_size()).unwrap();
+	let message = random_message(&mut rng, params.log_msg_len());
+	prove_verify(&params, &ntt, &message).unwrap();
+}
+
+#[test]
+fn test_with_security_reduces_queries_with_rate() {
+	let params = STIRParams::<F, BinaryField32b>::with_security(
+		20,
+		1,
+		0,
+		vec![4, 4, 4],
+		100,
+		SoundnessModel::ConjecturedListDecoding,
+	)
+	.unwrap();
+	assert_eq!(params.n_ood_samples(), 1);
+	assert!(params.n_queries(1) < params.n_queries(0));
+	assert!(params.n_queries(2) < params.n_queries(1));
+}
+
+#[test]
+fn test_fri_and_stir_share_interface() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let rs_code = ReedSolomonCode::<FA>::new(8, 1).unwrap();
+	let fri_params = FRIParams::<F, FA>::new(rs_code, 2, vec![4, 2], 3).unwrap();
+	let fri_ntt = SingleThreadedNTT::new(fri_params.rs_code().log_len()).unwrap();
+
+	let stir_params = STIRParams::<F, FA>::new(8, 1, 2, vec![4, 2, 2], vec![4, 3, 3], 1).unwrap();
+	let stir_ntt = SingleThreadedNTT::new(stir_params.log_domain_size()).unwrap();
+
+	assert_eq!(fri_params.log_msg_len(), stir_params.log_msg_len());
+	let message = random_message(&mut rng, fri_params.log_msg_len());
+	prove_verify(&fri_params, &fri_ntt, &message).unwrap();
+	prove_verify(&stir_params, &stir_ntt, &message).unwrap();
+}
+
+#[test]
+fn test_prove_verify_fails_for_far_word() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let params = STIRParams::<F, FA>::new(8, 1, 2, vec![4, 2, 2], vec![20, 10, 10], 1).unwrap();
+	let ntt = SingleThreadedNTT::new(params.log_domain_size()).unwrap();
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	// Commit to a uniformly random word, which is far from the code with high probability.
+	let word = random_message(&mut rng, params.log_len());
+	let (commitment, committed) = merkle_prover
+		.commit(&word, 1 << params.fold_arities()[0])
+		.unwrap();
+
+	let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prover_transcript.message().write(&commitment.root);
+	super::prove(&params, &ntt, &merkle_prover, &word, &committed, &mut prover_transcript).unwrap();
+
+	let mut verifier_transcript = prover_transcript.into_verifier();
+	let commitment = verifier_transcript.message().read().unwrap();
+	let result =
+		super::verify(&params, merkle_prover.scheme(), &commitment, &mut verifier_transcript);
+	assert!(matches!(result, Err(Error::Verification(_))));
+}
@@ -0,0 +1,180 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{BinaryField, ExtensionField, PackedExtension, TowerField};
+use binius_ntt::{AdditiveNTT, NTTShape, SingleThreadedNTT};
+use binius_utils::{DeserializeBytes, bail};
+use bytes::Buf;
+use tracing::instrument;
+
+use super::{
+	common::{LeafFolder, Quotient, STIRParams, domain_point, sample_query_indices},
+	error::{Error, VerificationError},
+};
+use crate::{
+	fiat_shamir::{CanSample, Challenger},
+	merkle_tree::MerkleTreeScheme,
+	transcript::{TranscriptReader, VerifierTranscript},
+};
+
+/// Verifies a proof generated by [`super::prove`] that the codeword with the given commitment is
+/// close to the interleaved Reed–Solomon code of the parameters.
+///
+/// The commitment must have been observed by the transcript beforehand.
+#[instrument(skip_all, name = "stir::verify", level = "debug")]
+pub fn verify<F, FA, VCS, Challenger_>(
+	params: &STIRParams<F, FA>,
+	vcs: &VCS,
+	commitment: &VCS::Digest,
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<(), Error>
+where
+	F: TowerField + ExtensionField<FA> + PackedExtension<FA, Scalar = F>,
+	FA: BinaryField,
+	VCS: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+	Challenger_: Challenger,
+{
+	let ntt = SingleThreadedNTT::with_subspace(params.subspace())?;
+	let log_coset_bits = params.log_domain_size() - params.log_dim();
+
+	// The commitment to the oracle queried in the current round, and for rounds after the first,
+	// the quotient that defines the folded word in terms of the oracle.
+	let mut oracle_commitment = commitment.clone();
+	let mut quotient = None::<Quotient<F>>;
+	for round in 0..params.n_rounds() {
+		let log_leaf_size = params.fold_arities()[round];
+		let challenges = transcript.sample_vec(log_leaf_size);
+		let folder = LeafFolder::new(params, &ntt, round, &challenges);
+
+		let log_next_dim = params.round_log_dim(round + 1);
+		let log_fold_len = params.round_log_domain_size(round + 1);
+		let coset_offset = params.coset_offset(round);
+		let shape = NTTShape {
+			log_y: log_next_dim,
+			..Default::default()
+		};
+
+		// Folds the opened leaf at the given index into the value of the folded polynomial.
+		let log_len = params.round_log_domain_size(round);
+		let leaf_offset = params.coset_offset(round) << params.round_log_dim(round);
+		let mut scratch_buffer = folder.scratch_buffer();
+		let mut fold_leaf = |index: usize, values: &[F]| -> Result<F, Error> {
+			let word_values = match &quotient {
+				Some(quotient) => values
+					.iter()
+					.enumerate()
+					.map(|(i, &value)| {
+						let point =
+							domain_point(&ntt, log_len, leaf_offset + (index << log_leaf_size) + i);
+						quotient.evaluate(point.into(), value)
+					})
+					.collect::<Result<Vec<_>, _>>()?,
+				None => values.to_vec(),
+			};
+			Ok(folder.fold(index, &word_values, &mut scratch_buffer))
+		};
+
+		if round + 1 == params.n_rounds() {
+			let coeffs = transcript
+				.message()
+				.read_scalar_slice::<F>(1 << log_next_dim)?;
+
+			// Evaluate the final polynomial over the cosets of the folded domain.
+			let mut final_codeword =
+				vec![F::ZERO; 1 << (log_next_dim + params.round_log_inv_rate(round))];
+			for (i, coset) in final_codeword.chunks_mut(1 << log_next_dim).enumerate() {
+				coset.copy_from_slice(&coeffs);
+				ntt.forward_transform_ext(coset, shape, coset_offset + i, log_coset_bits, 0)?;
+			}
+
+			let indices = sample_query_indices(
+				params.n_queries(round),
+				params.log_n_leaves(round),
+				transcript,
+			);
+			let openings = read_and_verify_openings(
+				vcs,
+				&oracle_commitment,
+				round,
+				log_leaf_size,
+				params.log_n_leaves(round),
+				params.n_queries(round),
+				&indices,
+				&mut transcript.decommitment(),
+			)?;
+			for (&index, values) in indices.iter().zip(&openings) {
+				if fold_leaf(index, values)? != final_codeword[index] {
+					bail!(VerificationError::IncorrectFold { query_index: index });
+				}
+			}
+			break;
+		}
+
+		let next_commitment = transcript.message().read::<VCS::Digest>()?;
+
+		let n_points = params.n_ood_samples() + params.n_queries(round);
+		let mut points = Vec::with_capacity(n_points);
+		let mut values = Vec::with_capacity(n_points);
+		for _ in 0..params.n_ood_samples() {
+			points.push(transcript.sample());
+			values.push(transcript.message().read_scalar()?);
+		}
+
+		let comb = transcript.sample();
+		let indices =
+			sample_query_indices(params.n_queries(round), params.log_n_leaves(round), transcript);
+		let openings = read_and_verify_openings(
+			vcs,
+			&oracle_commitment,
+			round,
+			log_leaf_size,
+			params.log_n_leaves(round),
+			params.n_queries(round),
+			&indices,
+			&mut transcript.decommitment(),
+		)?;
+		for (&index, leaf_values) in indices.iter().zip(&openings) {
+			let point = domain_point(&ntt, log_fold_len, (coset_offset << log_next_dim) + index);
+			points.push(point.into());
+			values.push(fold_leaf(index, leaf_values)?);
+		}
+
+		quotient = Some(Quotient::new(points, values, comb)?);
+		oracle_commitment = next_commitment;
+	}
+
+	Ok(())
+}
+
+/// Reads the openings of the leaves at the given indices of a committed oracle and verifies them
+/// against the commitment, returning the values of the leaves.
+#[allow(clippy::too_many_arguments)]
+fn read_and_verify_openings<F, VCS, B>(
+	vcs: &VCS,
+	commitment: &VCS::Digest,
+	oracle: usize,
+	log_leaf_size: usize,
+	tree_depth: usize,
+	n_queries: usize,
+	indices: &[usize],
+	advice: &mut TranscriptReader<B>,
+) -> Result<Vec<Vec<F>>, Error>
+where
+	F: TowerField,
+	VCS: MerkleTreeScheme<F, Digest: DeserializeBytes>,
+	B: Buf,
+{
+	let layer_depth = vcs.optimal_verify_layer(n_queries, tree_depth);
+	let layer = advice.read_vec(1 << layer_depth)?;
+	vcs.verify_layer(commitment, layer_depth, &layer)
+		.map_err(|source| VerificationError::InvalidLayer { oracle, source })?;
+
+	let openings = indices
+		.iter()
+		.map(|_| advice.read_scalar_slice(1 << log_leaf_size))
+		.collect::<Result<Vec<_>, _>>()?;
+	let values = openings.iter().map(Vec::as_slice).collect::<Vec<_>>();
+	vcs.verify_openings(indices, &values, layer_depth, tree_depth, &layer, advice)
+		.map_err(|source| VerificationError::InvalidOpenings { oracle, source })?;
+
+	Ok(openings)
+}
@@ -225,7 +225,7 @@ mod tests {
 	use std::{iter, sync::Arc};
 
 	use binius_field::{
-		BinaryField8b, BinaryField32b, BinaryField128b, ExtensionField, Field,
+		BinaryField1b, BinaryField8b, BinaryField32b, BinaryField128b, ExtensionField, Field,
 		PackedBinaryField1x128b, PackedExtension, PackedField, PackedFieldIndexable,
 		PackedSubfield, RepackedExtension, TowerField,
 		arch::{OptimalUnderlier128b, OptimalUnderlier256b, OptimalUnderlier512b},
@@ -688,6 +688,100 @@ mod tests {
 		}
 	}
 
+	fn test_compare_subfield_first_round_helper<P, FWitness>(
+		evaluation_order: EvaluationOrder,
+		n_vars: usize,
+		n_multilinears: usize,
+	) where
+		P: PackedExtension<BinaryField8b, Scalar = BinaryField128b> + PackedExtension<FWitness>,
+		FWitness: Field,
+		BinaryField128b: ExtensionField<FWitness>,
+	{
+		type FDomain = BinaryField8b;
+		let mut rng = StdRng::seed_from_u64(0);
+
+		let multilins = generate_zero_product_multilinears::<PackedSubfield<P, FWitness>, P>(
+			&mut rng,
+			n_vars,
+			n_multilinears,
+		);
+
+		let composite_claims = [CompositeSumClaim {
+			composition: TestProductComposition::new(n_multilinears),
+			sum: Field::ZERO,
+		}];
+
+		let backend = make_portable_backend();
+		let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+
+		let mut prove_transcript_1 = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let challenges = prove_transcript_1.sample_vec(n_vars);
+
+		let make_prover = || {
+			EqIndSumcheckProverBuilder::with_switchover(
+				multilins.clone(),
+				immediate_switchover_heuristic,
+				&backend,
+			)
+			.unwrap()
+			.build::<FDomain, _>(
+				evaluation_order,
+				&challenges,
+				composite_claims.clone(),
+				domain_factory.clone(),
+			)
+			.unwrap()
+		};
+
+		let BatchSumcheckOutput {
+			challenges: sumcheck_challenges_1,
+			multilinear_evals: multilinear_evals_1,
+		} = sumcheck::batch_prove(
+			vec![make_prover().without_subfield_first_round()],
+			&mut prove_transcript_1,
+		)
+		.unwrap();
+
+		let mut prove_transcript_2 = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let _: Vec<BinaryField128b> = prove_transcript_2.sample_vec(n_vars);
+		let BatchSumcheckOutput {
+			challenges: sumcheck_challenges_2,
+			multilinear_evals: multilinear_evals_2,
+		} = {
+			let prover = make_prover();
+			assert!(prover.has_subfield_first_round());
+			sumcheck::batch_prove(vec![prover], &mut prove_transcript_2).unwrap()
+		};
+
+		assert_eq!(prove_transcript_1.finalize(), prove_transcript_2.finalize());
+		assert_eq!(multilinear_evals_1, multilinear_evals_2);
+		assert_eq!(sumcheck_challenges_1, sumcheck_challenges_2);
+	}
+
+	#[test]
+	fn test_compare_subfield_first_round_to_extension_field() {
+		for evaluation_order in [EvaluationOrder::LowToHigh, EvaluationOrder::HighToLow] {
+			for n_vars in 2..8 {
+				for n_multilinears in 1..5 {
+					test_compare_subfield_first_round_helper::<
+						PackedBinaryField1x128b,
+						BinaryField1b,
+					>(evaluation_order, n_vars, n_multilinears);
+					test_compare_subfield_first_round_helper::<
+						PackedBinaryField1x128b,
+						BinaryField8b,
+					>(evaluation_order, n_vars, n_multilinears);
+					// A packing width of 4 splits each packed subfield eval across several packed
+					// evaluations of the equality indicator.
+					test_compare_subfield_first_round_helper::<
+						PackedType<OptimalUnderlier512b, BinaryField128b>,
+						BinaryField8b,
+					>(evaluation_order, n_vars, n_multilinears);
+				}
+			}
+		}
+	}
+
 	#[test]
 	fn test_compare_eq_ind_prover_to_regular_sumcheck() {
 		for n_vars in 2..8 {
@@ -125,6 +125,8 @@ pub enum Error {
 	Allocation(#[from] binius_compute::alloc::Error),
 	#[error("Transcript error: {0}")]
 	TranscriptError(#[from] crate::transcript::Error),
+	#[error("interactive channel error: {0}")]
+	InteractiveChannel(#[source] Box<dyn std::error::Error + Send + Sync>),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -133,6 +135,8 @@ pub enum VerificationError {
 	NumberOfCoefficients { round: usize, expected: usize },
 	#[error("incorrect number of rounds")]
 	NumberOfRounds,
+	#[error("the proof ends before the round {round} message")]
+	MissingRoundProof { round: usize },
 	#[error("the number of final evaluations must match the number of instances")]
 	NumberOfFinalEvaluations,
 	#[error("the number of reduced multilinear evaluations should conform to the claim shape")]
@@ -8,10 +8,10 @@ use binius_utils::sorting::is_sorted_ascending;
 use bytes::Buf;
 
 use super::{
-	RoundCoeffs, RoundProof,
+	RoundCoeffs,
 	common::batch_weighted_value,
 	error::{Error, VerificationError},
-	verify_sumcheck::compute_expected_batch_composite_evaluation_single_claim,
+	verify_sumcheck::{compute_expected_batch_composite_evaluation_single_claim, read_round_proof},
 };
 use crate::{
 	fiat_shamir::{CanSample, Challenger},
@@ -233,8 +233,7 @@ where
 					None => 0,
 				};
 
-				let proof_vals = transcript.read_scalar_slice(degree)?;
-				let round_proof = RoundProof(RoundCoeffs(proof_vals));
+				let round_proof = read_round_proof(transcript, self.round, degree)?;
 				self.last_coeffs_or_sum = CoeffsOrSums::Coeffs(round_proof.recover(sum));
 				Ok(())
 			}
@@ -18,6 +18,7 @@ pub mod v3;
 pub mod verify_sumcheck;
 pub mod verify_zerocheck;
 pub mod zerocheck;
+pub mod zk;
 
 pub use common::{
 	BatchSumcheckOutput, CompositeSumClaim, RoundCoeffs, RoundProof, SumcheckClaim,
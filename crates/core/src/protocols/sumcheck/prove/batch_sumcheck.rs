@@ -62,6 +62,14 @@ pub trait SumcheckProver<F: Field> {
 	/// Folds the sumcheck multilinears with a new verifier challenge.
 	fn fold(&mut self, challenge: F) -> Result<(), Error>;
 
+	/// Performs the work of the next [`Self::fold`] that does not depend on the challenge.
+	///
+	/// Interactive provers call this after sending a round message and while awaiting the
+	/// verifier's challenge. The default implementation does nothing.
+	fn prepare_fold(&mut self) -> Result<(), Error> {
+		Ok(())
+	}
+
 	/// Finishes the sumcheck proving protocol and returns the evaluations of all multilinears at
 	/// the challenge point.
 	fn finish(self: Box<Self>) -> Result<Vec<F>, Error>;
@@ -85,6 +93,10 @@ impl<F: Field, Prover: SumcheckProver<F> + ?Sized> SumcheckProver<F> for Box<Pro
 		(**self).fold(challenge)
 	}
 
+	fn prepare_fold(&mut self) -> Result<(), Error> {
+		(**self).prepare_fold()
+	}
+
 	fn finish(self: Box<Self>) -> Result<Vec<F>, Error> {
 		(*self).finish()
 	}
@@ -1,14 +1,16 @@
 // Copyright 2024-2025 Irreducible Inc.
 
 use binius_field::{
-	PackedField,
+	ExtensionField, Field, PackedField,
 	packed::{get_packed_slice, packed_from_fn_with_offset},
 };
 use binius_hal::ComputationBackend;
-use binius_math::EvaluationOrder;
+use binius_math::{EvaluationOrder, InterpolationDomain};
 use binius_maybe_rayon::prelude::*;
 use tracing::instrument;
 
+use crate::protocols::sumcheck::{Error, RoundCoeffs};
+
 #[instrument(skip_all, level = "debug")]
 pub fn fold_partial_eq_ind<P, Backend>(
 	evaluation_order: EvaluationOrder,
@@ -71,3 +73,30 @@ pub fn fold_partial_eq_ind<P, Backend>(
 		*partial_eq_ind_evals = Backend::to_hal_slice(updated_evals);
 	}
 }
+
+/// Interpolates the coefficients of a round polynomial from its claimed sum and its evaluations.
+///
+/// The evaluations are at 1, at "infinity" if the degree is at least 2, and then at the nontrivial
+/// evaluation points of `domain`.
+pub(crate) fn round_evals_to_coeffs<F, FDomain>(
+	domain: &InterpolationDomain<FDomain>,
+	last_round_sum: F,
+	mut round_evals: Vec<F>,
+) -> Result<RoundCoeffs<F>, Error>
+where
+	F: ExtensionField<FDomain>,
+	FDomain: Field,
+{
+	// Given $r(1), \ldots, r(d+1)$, letting $s$ be the current round's claimed sum,
+	// we can compute $r(0)$ using the identity $r(0) = s - r(1)$
+	round_evals.insert(0, last_round_sum - round_evals[0]);
+
+	if round_evals.len() > 3 {
+		// The evaluations are ordered as 0, 1, "infinity", then subspace points, whereas
+		// InterpolationDomain expects "infinity" at the last position.
+		let infinity_round_eval = round_evals.remove(2);
+		round_evals.push(infinity_round_eval);
+	}
+
+	Ok(RoundCoeffs(domain.interpolate(&round_evals)?))
+}
@@ -0,0 +1,238 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Distributed sumcheck proving by sharding the hypercube.
+//!
+//! A sumcheck over $n$ variables is split across $2^k$ workers by fixing the top $k$ variables:
+//! worker $s$ holds the restrictions of the multilinears to the subcube where the top variables
+//! encode $s$, which are contiguous chunks of their evaluations. Since the sum over the hypercube
+//! is the sum of the sums over the subcubes, the round polynomial of each of the first $n - k$
+//! rounds, in low-to-high order, is the sum of the round polynomials of the workers. The
+//! coordinator adds up the round messages, writes them to the transcript, and sends the challenge
+//! back to every worker.
+//!
+//! After these rounds, each worker reports the evaluations of its multilinears at the challenges,
+//! which are the partially evaluated multilinears at the vertices of the top $k$ variables. The
+//! coordinator gathers them into multilinears over $k$ variables and proves the last rounds
+//! locally. Workers therefore exchange only one round message and one challenge per round, and
+//! one evaluation per multilinear at the end.
+//!
+//! Workers are any [`SumcheckProver`]s over $n - k$ variables with low-to-high evaluation order.
+//! A worker on another machine is driven by a local [`SumcheckProver`] that forwards the calls
+//! over the network. The coordinator is itself a [`SumcheckProver`], so it is run with
+//! [`super::batch_prove`] and batched with other claims as usual.
+//!
+//! The commitment to a codeword is sharded in the same way, with each worker building the Merkle
+//! subtree over a contiguous range of leaves and the coordinator building the top of the tree with
+//! [`crate::merkle_tree::build_from_subtree_roots`].
+
+use binius_field::{
+	ExtensionField, Field, PackedExtension, PackedField, TowerField, packed::pack_slice,
+};
+use binius_hal::ComputationBackend;
+use binius_math::{
+	CompositionPoly, EvaluationDomainFactory, EvaluationOrder, MLEDirectAdapter,
+	MultilinearExtension,
+};
+use binius_utils::{bail, checked_arithmetics::log2_strict_usize};
+
+use crate::protocols::sumcheck::{
+	common::{CompositeSumClaim, RoundCoeffs, immediate_switchover_heuristic},
+	error::Error,
+	prove::{RegularSumcheckProver, SumcheckProver},
+};
+
+type TailProver<'a, FDomain, P, Composition, Backend> =
+	RegularSumcheckProver<'a, FDomain, P, Composition, MLEDirectAdapter<P, Vec<P>>, Backend>;
+
+enum ShardedState<'a, FDomain, P, Composition, Worker, Backend>
+where
+	FDomain: Field,
+	P: PackedField,
+	Backend: ComputationBackend,
+{
+	Sharded(Vec<Worker>),
+	Tail(TailProver<'a, FDomain, P, Composition, Backend>),
+}
+
+/// The coordinator of a sumcheck sharded across workers by the top hypercube variables.
+///
+/// The last rounds are proven over multilinears of packed field elements `P`.
+///
+/// See the [module documentation](self) for the protocol.
+pub struct ShardedSumcheckProver<'a, FDomain, P, Composition, Worker, DomainFactory, Backend>
+where
+	FDomain: Field,
+	P: PackedField,
+	Backend: ComputationBackend,
+{
+	log_n_shards: usize,
+	/// The number of variables of the shards that are not yet bound to challenges.
+	shard_n_vars: usize,
+	compositions: Vec<Composition>,
+	state: ShardedState<'a, FDomain, P, Composition, Worker, Backend>,
+	domain_factory: Option<DomainFactory>,
+	backend: &'a Backend,
+}
+
+impl<'a, F, FDomain, P, Composition, Worker, DomainFactory, Backend>
+	ShardedSumcheckProver<'a, FDomain, P, Composition, Worker, DomainFactory, Backend>
+where
+	F: TowerField + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F> + PackedExtension<F, PackedSubfield = P> + PackedExtension<FDomain>,
+	Composition: CompositionPoly<P>,
+	Worker: SumcheckProver<F>,
+	DomainFactory: EvaluationDomainFactory<FDomain>,
+	Backend: ComputationBackend,
+{
+	/// Constructs a coordinator for the given workers, in order by the value of the top variables
+	/// of their shards.
+	///
+	/// The workers must prove the given compositions over their shards, and the number of workers
+	/// must be a power of two.
+	pub fn new(
+		workers: Vec<Worker>,
+		compositions: Vec<Composition>,
+		domain_factory: DomainFactory,
+		backend: &'a Backend,
+	) -> Result<Self, Error> {
+		if !workers.len().is_power_of_two() {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+		let shard_n_vars = workers[0].n_vars();
+		for worker in &workers {
+			if worker.n_vars() != shard_n_vars {
+				bail!(Error::NumberOfVariablesMismatch);
+			}
+			if worker.evaluation_order() != EvaluationOrder::LowToHigh {
+				bail!(Error::InconsistentEvaluationOrder);
+			}
+		}
+
+		let mut prover = Self {
+			log_n_shards: log2_strict_usize(workers.len()),
+			shard_n_vars,
+			compositions,
+			state: ShardedState::Sharded(workers),
+			domain_factory: Some(domain_factory),
+			backend,
+		};
+		if shard_n_vars == 0 {
+			prover.switch_to_tail()?;
+		}
+		Ok(prover)
+	}
+
+	/// Gathers the final evaluations of the workers and continues with a local prover over the
+	/// top variables.
+	fn switch_to_tail(&mut self) -> Result<(), Error> {
+		let ShardedState::Sharded(workers) = &mut self.state else {
+			bail!(Error::ExpectedFinish);
+		};
+		let shard_evals = std::mem::take(workers)
+			.into_iter()
+			.map(|worker| Box::new(worker).finish())
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let n_multilinears = shard_evals[0].len();
+		if shard_evals
+			.iter()
+			.any(|evals| evals.len() != n_multilinears)
+		{
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+		let multilinears = (0..n_multilinears)
+			.map(|i| {
+				let values = shard_evals.iter().map(|evals| evals[i]).collect::<Vec<_>>();
+				let multilinear =
+					MultilinearExtension::new(self.log_n_shards, pack_slice::<P>(&values))?;
+				Ok(MLEDirectAdapter::from(multilinear))
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let composite_claims = std::mem::take(&mut self.compositions)
+			.into_iter()
+			.map(|composition| {
+				let sum = shard_evals
+					.iter()
+					.map(|evals| {
+						let query = evals.iter().copied().map(P::broadcast).collect::<Vec<_>>();
+						Ok(composition.evaluate(&query)?.get(0))
+					})
+					.sum::<Result<F, Error>>()?;
+				Ok(CompositeSumClaim { composition, sum })
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let domain_factory = self.domain_factory.take().ok_or(Error::ExpectedFinish)?;
+		let tail = RegularSumcheckProver::<FDomain, _, _, _, _>::new(
+			EvaluationOrder::LowToHigh,
+			multilinears,
+			composite_claims,
+			domain_factory,
+			immediate_switchover_heuristic,
+			self.backend,
+		)?;
+		self.state = ShardedState::Tail(tail);
+		Ok(())
+	}
+}
+
+impl<F, FDomain, P, Composition, Worker, DomainFactory, Backend> SumcheckProver<F>
+	for ShardedSumcheckProver<'_, FDomain, P, Composition, Worker, DomainFactory, Backend>
+where
+	F: TowerField + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F> + PackedExtension<F, PackedSubfield = P> + PackedExtension<FDomain>,
+	Composition: CompositionPoly<P>,
+	Worker: SumcheckProver<F>,
+	DomainFactory: EvaluationDomainFactory<FDomain>,
+	Backend: ComputationBackend,
+{
+	fn n_vars(&self) -> usize {
+		match &self.state {
+			ShardedState::Sharded(_) => self.shard_n_vars + self.log_n_shards,
+			ShardedState::Tail(tail) => tail.n_vars(),
+		}
+	}
+
+	fn evaluation_order(&self) -> EvaluationOrder {
+		EvaluationOrder::LowToHigh
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		match &mut self.state {
+			ShardedState::Sharded(workers) => {
+				let mut round_coeffs = RoundCoeffs::default();
+				for worker in workers {
+					round_coeffs += &worker.execute(batch_coeff)?;
+				}
+				Ok(round_coeffs)
+			}
+			ShardedState::Tail(tail) => tail.execute(batch_coeff),
+		}
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		match &mut self.state {
+			ShardedState::Sharded(workers) => {
+				for worker in workers.iter_mut() {
+					worker.fold(challenge)?;
+				}
+				self.shard_n_vars -= 1;
+				if self.shard_n_vars == 0 {
+					self.switch_to_tail()?;
+				}
+				Ok(())
+			}
+			ShardedState::Tail(tail) => tail.fold(challenge),
+		}
+	}
+
+	fn finish(self: Box<Self>) -> Result<Vec<F>, Error> {
+		match self.state {
+			ShardedState::Sharded(_) => bail!(Error::ExpectedFold),
+			ShardedState::Tail(tail) => Box::new(tail).finish(),
+		}
+	}
+}
@@ -3,14 +3,16 @@
 use std::{cmp::Reverse, marker::PhantomData, ops::Range};
 
 use binius_fast_compute::arith_circuit::ArithCircuitPoly;
-use binius_field::{ExtensionField, Field, PackedExtension, PackedField, TowerField, util::eq};
+use binius_field::{
+	ExtensionField, Field, PackedExtension, PackedField, PackedSubfield, TowerField, util::eq,
+};
 use binius_hal::{
-	ComputationBackend, Error as HalError, SumcheckEvaluator, SumcheckMultilinear,
-	make_portable_backend,
+	ComputationBackend, Error as HalError, SubfieldSumcheckEvaluator, SumcheckEvaluator,
+	SumcheckMultilinear, make_portable_backend,
 };
 use binius_math::{
-	CompositionPoly, EvaluationDomainFactory, EvaluationOrder, InterpolationDomain,
-	MLEDirectAdapter, MultilinearPoly, RowsBatchRef,
+	ArithCircuit, ArithCircuitStep, CompositionPoly, EvaluationDomainFactory, EvaluationOrder,
+	InterpolationDomain, MLEDirectAdapter, MultilinearPoly, RowsBatchRef,
 };
 use binius_maybe_rayon::prelude::*;
 use binius_utils::bail;
@@ -338,6 +340,18 @@ where
 			backend,
 		)?;
 
+		// Evaluate the first round with subfield arithmetic when every multilinear is defined over
+		// the domain subfield and every composition has its constants in it.
+		let subfield_compositions = state
+			.subfield_log_extension_degree()
+			.filter(|&log_degree| log_degree >= <F as ExtensionField<FDomain>>::LOG_DEGREE)
+			.and_then(|_| {
+				compositions
+					.iter()
+					.map(|(composition, _)| SubfieldComposition::new(composition))
+					.collect::<Option<Vec<_>>>()
+			});
+
 		let eq_ind_prefix_eval = F::ONE;
 		let eq_ind_challenges = eq_ind_challenges.to_vec();
 		let first_round_eval_1s = self.first_round_eval_1s;
@@ -349,6 +363,7 @@ where
 			eq_ind_partial_evals,
 			eq_ind_challenges,
 			compositions,
+			subfield_compositions,
 			domains,
 			first_round_eval_1s,
 			backend: PhantomData,
@@ -374,6 +389,84 @@ impl<F: Field> ConstEvalSuffix<F> {
 	}
 }
 
+/// A composition and its leading term, with all constants in the subfield `FBase`, evaluated over
+/// packed `FBase` elements in the first round.
+#[derive(Debug)]
+struct SubfieldComposition<FBase: Field> {
+	composition: SubfieldCircuit<FBase>,
+	composition_at_infinity: SubfieldCircuit<FBase>,
+}
+
+impl<FBase: Field> SubfieldComposition<FBase> {
+	/// Converts a composition over an extension field, if all its constants lie in `FBase`.
+	fn new<P>(composition: &impl CompositionPoly<P>) -> Option<Self>
+	where
+		P: PackedField<Scalar: ExtensionField<FBase>>,
+	{
+		let expr = composition.expression();
+		Some(Self {
+			composition: SubfieldCircuit::new(&expr)?,
+			composition_at_infinity: SubfieldCircuit::new(&expr.leading_term())?,
+		})
+	}
+}
+
+/// The steps of an arithmetic circuit with constants in the field `F`.
+#[derive(Debug)]
+struct SubfieldCircuit<F: Field> {
+	steps: Vec<ArithCircuitStep<F>>,
+}
+
+impl<F: Field> SubfieldCircuit<F> {
+	fn new<FE: ExtensionField<F>>(circuit: &ArithCircuit<FE>) -> Option<Self> {
+		let steps = circuit
+			.steps()
+			.iter()
+			.map(|step| {
+				let step = match *step {
+					ArithCircuitStep::Const(value) => {
+						let base = value.get_base(0);
+						if FE::from(base) != value {
+							return None;
+						}
+						ArithCircuitStep::Const(base)
+					}
+					ArithCircuitStep::Var(index) => ArithCircuitStep::Var(index),
+					ArithCircuitStep::Add(left, right) => ArithCircuitStep::Add(left, right),
+					ArithCircuitStep::Mul(left, right) => ArithCircuitStep::Mul(left, right),
+					ArithCircuitStep::Pow(base, exp) => ArithCircuitStep::Pow(base, exp),
+				};
+				Some(step)
+			})
+			.collect::<Option<Vec<_>>>()?;
+		Some(Self { steps })
+	}
+
+	/// Evaluates the circuit on each column of `batch_query`, with the semantics of
+	/// [`CompositionPoly::batch_evaluate`].
+	fn batch_evaluate<P: PackedField<Scalar = F>>(
+		&self,
+		batch_query: &RowsBatchRef<P>,
+		evals: &mut [P],
+	) {
+		let mut step_evals = Vec::<P>::with_capacity(self.steps.len());
+		for (column, eval) in evals.iter_mut().enumerate() {
+			step_evals.clear();
+			for step in &self.steps {
+				let step_eval = match *step {
+					ArithCircuitStep::Add(left, right) => step_evals[left] + step_evals[right],
+					ArithCircuitStep::Mul(left, right) => step_evals[left] * step_evals[right],
+					ArithCircuitStep::Pow(base, exp) => PackedField::pow(step_evals[base], exp),
+					ArithCircuitStep::Const(value) => P::broadcast(value),
+					ArithCircuitStep::Var(index) => batch_query.row(index)[column],
+				};
+				step_evals.push(step_eval);
+			}
+			*eval = step_evals.pop().unwrap_or_default();
+		}
+	}
+}
+
 #[derive(Debug, Getters)]
 pub struct EqIndSumcheckProver<'a, FDomain, P, Composition, M, Backend>
 where
@@ -389,6 +482,7 @@ where
 	eq_ind_challenges: Vec<P::Scalar>,
 	#[getset(get = "pub")]
 	compositions: Vec<(Composition, ConstEvalSuffix<P::Scalar>)>,
+	subfield_compositions: Option<Vec<SubfieldComposition<FDomain>>>,
 	domains: Vec<InterpolationDomain<FDomain>>,
 	first_round_eval_1s: Option<Vec<P::Scalar>>,
 	backend: PhantomData<Backend>,
@@ -427,6 +521,33 @@ where
 	}
 }
 
+impl<F, FDomain, P, Composition, M, Backend>
+	EqIndSumcheckProver<'_, FDomain, P, Composition, M, Backend>
+where
+	F: Field,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputationBackend,
+{
+	/// Whether the first round evaluates the compositions over the domain subfield.
+	///
+	/// [`EqIndSumcheckProverBuilder::build`] selects this when all multilinears are defined over
+	/// `FDomain` or its subfields, and all composition constants lie in `FDomain`. The
+	/// compositions are then evaluated with subfield arithmetic, and only their evaluations are
+	/// multiplied by the equality indicator in the extension field.
+	pub const fn has_subfield_first_round(&self) -> bool {
+		self.subfield_compositions.is_some()
+	}
+
+	/// Evaluates the first round over the extension field even if the witness admits subfield
+	/// arithmetic.
+	pub fn without_subfield_first_round(mut self) -> Self {
+		self.subfield_compositions = None;
+		self
+	}
+}
+
 pub fn eq_ind_expand<P, Backend>(
 	evaluation_order: EvaluationOrder,
 	eq_ind_challenges: &[P::Scalar],
@@ -541,6 +662,9 @@ where
 		let first_round_eval_1s = self.first_round_eval_1s.take();
 		let have_first_round_eval_1s = first_round_eval_1s.is_some();
 
+		// Subfield compositions are only used in the first round.
+		let subfield_compositions = self.subfield_compositions.take();
+
 		let eq_ind_challenges = match self.state.evaluation_order() {
 			EvaluationOrder::LowToHigh => &self.eq_ind_challenges[self.n_vars.min(round + 1)..],
 			EvaluationOrder::HighToLow => {
@@ -551,7 +675,8 @@ where
 		let evaluators = self
 			.compositions
 			.iter_mut()
-			.map(|(composition, const_eval_suffix)| {
+			.enumerate()
+			.map(|(index, (composition, const_eval_suffix))| {
 				let composition_at_infinity =
 					ArithCircuitPoly::new(composition.expression().leading_term());
 
@@ -561,6 +686,9 @@ where
 					n_rounds_remaining,
 					composition,
 					composition_at_infinity,
+					subfield_composition: subfield_compositions
+						.as_ref()
+						.map(|subfield_compositions| &subfield_compositions[index]),
 					have_first_round_eval_1s,
 					eq_ind_challenges,
 					eq_ind_partial_evals,
@@ -582,7 +710,12 @@ where
 			})
 			.collect::<Vec<_>>();
 
-		let round_evals = self.state.calculate_round_evals(&evaluators)?;
+		let round_evals = if subfield_compositions.is_some() {
+			self.state
+				.calculate_subfield_round_evals::<FDomain, _, _>(&evaluators)?
+		} else {
+			self.state.calculate_round_evals(&evaluators)?
+		};
 
 		let prime_coeffs = self.state.calculate_round_coeffs_from_evals(
 			&interpolators,
@@ -643,22 +776,26 @@ where
 	}
 }
 
-struct Evaluator<'a, P, Composition>
+struct Evaluator<'a, FDomain, P, Composition>
 where
+	FDomain: Field,
 	P: PackedField,
 {
 	n_rounds_remaining: usize,
 	composition: &'a Composition,
 	composition_at_infinity: ArithCircuitPoly<P::Scalar>,
+	subfield_composition: Option<&'a SubfieldComposition<FDomain>>,
 	have_first_round_eval_1s: bool,
 	eq_ind_challenges: &'a [P::Scalar],
 	eq_ind_partial_evals: &'a [P],
 	const_eval_suffix: &'a ConstEvalSuffix<P::Scalar>,
 }
 
-impl<P, Composition> SumcheckEvaluator<P, Composition> for Evaluator<'_, P, Composition>
+impl<FDomain, P, Composition> SumcheckEvaluator<P, Composition>
+	for Evaluator<'_, FDomain, P, Composition>
 where
-	P: PackedField<Scalar: TowerField>,
+	FDomain: Field,
+	P: PackedExtension<FDomain, Scalar: TowerField>,
 	Composition: CompositionPoly<P>,
 {
 	fn eval_point_indices(&self) -> Range<usize> {
@@ -730,6 +867,53 @@ where
 	}
 }
 
+impl<FDomain, P, Composition> SubfieldSumcheckEvaluator<FDomain, P, Composition>
+	for Evaluator<'_, FDomain, P, Composition>
+where
+	FDomain: Field,
+	P: PackedExtension<FDomain, Scalar: TowerField>,
+	Composition: CompositionPoly<P>,
+{
+	fn process_subfield_subcube_at_eval_point(
+		&self,
+		subcube_vars: usize,
+		subcube_index: usize,
+		is_infinity_point: bool,
+		batch_query: &RowsBatchRef<PackedSubfield<P, FDomain>>,
+	) -> P {
+		let subfield_composition = self
+			.subfield_composition
+			.expect("subfield round evals are only calculated with subfield compositions");
+		let row_len = batch_query.row_len();
+
+		stackalloc_with_default(row_len, |evals| {
+			if is_infinity_point {
+				subfield_composition
+					.composition_at_infinity
+					.batch_evaluate(batch_query, evals);
+			} else {
+				subfield_composition
+					.composition
+					.batch_evaluate(batch_query, evals);
+			}
+
+			// Each packed subfield eval spans several packed extension field elements of the
+			// equality indicator. Spread its scalars over the bases of the extension field
+			// scalars they multiply, so that the product is computed with subfield arithmetic.
+			let log_embedding_degree = <P::Scalar as ExtensionField<FDomain>>::LOG_DEGREE;
+			let subcube_start = subcube_index << subcube_vars.saturating_sub(P::LOG_WIDTH);
+			(0..1 << subcube_vars.saturating_sub(P::LOG_WIDTH))
+				.map(|i| {
+					let spread_eval = evals[i >> log_embedding_degree]
+						.spread(P::LOG_WIDTH, i & ((1 << log_embedding_degree) - 1));
+					let eq_ind = self.eq_ind_partial_evals[subcube_start + i];
+					P::cast_ext(P::cast_base(eq_ind) * spread_eval)
+				})
+				.sum::<P>()
+		})
+	}
+}
+
 struct Interpolator<'a, F, FDomain>
 where
 	F: Field,
@@ -6,10 +6,12 @@ use binius_field::{Field, TowerField};
 use binius_utils::sorting::is_sorted_ascending;
 use bytes::BufMut;
 
-use super::{batch_sumcheck::SumcheckProver, logging::PIOPCompilerFoldData};
+use super::{
+	batch_sumcheck::SumcheckProver, interactive::InteractiveChannel, logging::PIOPCompilerFoldData,
+};
 use crate::{
 	fiat_shamir::{CanSample, Challenger},
-	protocols::sumcheck::{BatchSumcheckOutput, Error, RoundCoeffs},
+	protocols::sumcheck::{BatchSumcheckOutput, Error, RoundCoeffs, RoundProof},
 	transcript::{ProverTranscript, TranscriptWriter},
 };
 
@@ -106,18 +108,37 @@ where
 	where
 		B: BufMut,
 	{
+		self.finish_claim_provers_with(|claim_multilinear_evals| {
+			transcript.write_scalar_slice(claim_multilinear_evals);
+			Ok(())
+		})
+	}
+
+	fn finish_claim_provers_with(
+		&mut self,
+		mut send_evals: impl FnMut(&[F]) -> Result<(), Error>,
+	) -> Result<(), Error> {
 		while let Some((prover, _)) = self.provers.front() {
 			if prover.n_vars() != self.round {
 				break;
 			}
 			let (prover, _) = self.provers.pop_front().expect("front returned Some");
 			let claim_multilinear_evals = Box::new(prover).finish()?;
-			transcript.write_scalar_slice(&claim_multilinear_evals);
+			send_evals(&claim_multilinear_evals)?;
 			self.multilinear_evals.push(claim_multilinear_evals);
 		}
 		Ok(())
 	}
 
+	fn compute_round_proof(&mut self) -> Result<RoundProof<F>, Error> {
+		let mut round_coeffs = RoundCoeffs::default();
+		for (prover, batch_coeff) in &mut self.provers {
+			let prover_coeffs = prover.execute(*batch_coeff)?;
+			round_coeffs += &(prover_coeffs * *batch_coeff);
+		}
+		Ok(round_coeffs.truncate())
+	}
+
 	/// Computes the round message and writes it to the proof transcript.
 	pub fn send_round_proof<B>(&mut self, transcript: &mut TranscriptWriter<B>) -> Result<(), Error>
 	where
@@ -125,17 +146,22 @@ where
 	{
 		self.finish_claim_provers(transcript)?;
 
-		let mut round_coeffs = RoundCoeffs::default();
-		for (prover, batch_coeff) in &mut self.provers {
-			let prover_coeffs = prover.execute(*batch_coeff)?;
-			round_coeffs += &(prover_coeffs * *batch_coeff);
-		}
-
-		let round_proof = round_coeffs.truncate();
+		let round_proof = self.compute_round_proof()?;
 		transcript.write_scalar_slice(round_proof.coeffs());
 		Ok(())
 	}
 
+	/// Performs the work of the next [`Self::receive_challenge`] that does not depend on the
+	/// challenge.
+	///
+	/// This may be called after [`Self::send_round_proof`] while awaiting the challenge.
+	pub fn prepare_challenge(&mut self) -> Result<(), Error> {
+		for (prover, _) in &mut self.provers {
+			prover.prepare_fold()?;
+		}
+		Ok(())
+	}
+
 	/// Finishes an interaction round by reducing the instance with the verifier challenge.
 	pub fn receive_challenge(&mut self, challenge: F) -> Result<(), Error> {
 		for (prover, _) in &mut self.provers {
@@ -195,4 +221,45 @@ where
 			multilinear_evals,
 		})
 	}
+
+	/// Proves a front-loaded batch sumcheck protocol execution with an interactive verifier.
+	///
+	/// Unlike [`Self::run`], the challenges are received from the verifier over a channel rather
+	/// than sampled with Fiat–Shamir. While each challenge is in flight, the prover performs the
+	/// challenge-independent part of the next fold with [`Self::prepare_challenge`], and then
+	/// blocks on the channel. No work that depends on the challenge is started before it arrives.
+	/// The batching coefficients must have been received from the verifier beforehand and passed
+	/// to [`Self::new_prebatched`].
+	pub fn run_interactive<Channel>(
+		mut self,
+		channel: &mut Channel,
+	) -> Result<BatchSumcheckOutput<F>, Error>
+	where
+		Channel: InteractiveChannel<F>,
+	{
+		let round_count = self.total_rounds();
+
+		let mut challenges = Vec::with_capacity(round_count);
+		for _round_no in 0..round_count {
+			self.finish_claim_provers_with(|evals| channel.send_multilinear_evals(evals))?;
+			let round_proof = self.compute_round_proof()?;
+			channel.send_round_proof(&round_proof)?;
+
+			self.prepare_challenge()?;
+			let challenge = channel.receive_challenge()?;
+			challenges.push(challenge);
+
+			self.receive_challenge(challenge)?;
+		}
+
+		self.finish_claim_provers_with(|evals| channel.send_multilinear_evals(evals))?;
+		if !self.provers.is_empty() {
+			return Err(Error::ExpectedFold);
+		}
+
+		Ok(BatchSumcheckOutput {
+			challenges,
+			multilinear_evals: self.multilinear_evals,
+		})
+	}
 }
@@ -0,0 +1,30 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Interfaces for proving sumchecks with a genuinely interactive verifier.
+//!
+//! In a non-interactive execution, the prover derives the verifier challenges from the transcript
+//! with Fiat–Shamir, so there is nothing to overlap with computing a challenge. With an
+//! interactive verifier, each challenge incurs a round trip, during which the prover performs the
+//! challenge-independent part of its next fold. The prover does not speculate on the challenge.
+//! See [`super::front_loaded::BatchProver::run_interactive`].
+
+use binius_field::Field;
+
+use crate::protocols::sumcheck::{Error, RoundProof};
+
+/// A connection to an interactive sumcheck verifier.
+///
+/// Messages must be delivered to the verifier in the order that they are sent.
+pub trait InteractiveChannel<F: Field> {
+	/// Sends the evaluations of the multilinears of a finished claim to the verifier.
+	fn send_multilinear_evals(&mut self, evals: &[F]) -> Result<(), Error>;
+
+	/// Sends a round message to the verifier.
+	///
+	/// The message must be on its way to the verifier when this returns, as the prover performs its
+	/// challenge-independent work before calling [`Self::receive_challenge`].
+	fn send_round_proof(&mut self, round_proof: &RoundProof<F>) -> Result<(), Error>;
+
+	/// Receives the verifier's challenge for the current round, blocking until it arrives.
+	fn receive_challenge(&mut self) -> Result<F, Error>;
+}
@@ -3,21 +3,26 @@
 mod batch_sumcheck;
 mod batch_zerocheck;
 pub(crate) mod common;
+pub mod distributed;
 pub mod eq_ind;
 pub mod front_loaded;
+pub mod interactive;
 pub mod logging;
 pub mod oracles;
 pub mod prover_state;
 pub mod regular_sumcheck;
+pub mod streaming;
 pub mod univariate;
 pub mod zerocheck;
 
 pub use batch_sumcheck::{SumcheckProver, batch_prove};
 pub use batch_zerocheck::{ZerocheckProver, batch_prove as batch_prove_zerocheck};
+pub use interactive::InteractiveChannel;
 pub use logging::PIOPCompilerFoldData;
 pub use oracles::{
 	constraint_set_sumcheck_prover, constraint_set_zerocheck_prover, split_constraint_set,
 };
 pub use prover_state::{ProverState, SumcheckInterpolator};
 pub use regular_sumcheck::RegularSumcheckProver;
+pub use streaming::StreamingSumcheckProver;
 pub use zerocheck::ZerocheckProverImpl;
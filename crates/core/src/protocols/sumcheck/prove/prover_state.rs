@@ -1,7 +1,10 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use binius_field::{Field, PackedExtension, PackedField, util::powers};
-use binius_hal::{ComputationBackend, RoundEvals, SumcheckEvaluator, SumcheckMultilinear};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedField, util::powers};
+use binius_hal::{
+	ComputationBackend, RoundEvals, SubfieldSumcheckEvaluator, SumcheckEvaluator,
+	SumcheckMultilinear,
+};
 use binius_math::{
 	CompositionPoly, EvaluationOrder, MultilinearPoly, MultilinearQuery, evaluate_univariate,
 };
@@ -264,6 +267,63 @@ where
 		)?)
 	}
 
+	/// Returns the smallest log degree of the multilinears over the subfields they are defined
+	/// over, if no variables have been folded yet and all multilinears are transparent.
+	///
+	/// When this is at least the log degree of `F` over a subfield `FBase`, the round evaluations
+	/// can be calculated over `FBase` with [`Self::calculate_subfield_round_evals`].
+	pub fn subfield_log_extension_degree(&self) -> Option<usize> {
+		if !self.challenges.is_empty() {
+			return None;
+		}
+
+		self.multilinears
+			.iter()
+			.map(|multilinear| match multilinear {
+				SumcheckMultilinear::Transparent { multilinear, .. } => {
+					Some(multilinear.log_extension_degree())
+				}
+				SumcheckMultilinear::Folded { .. } => None,
+			})
+			.collect::<Option<Vec<_>>>()?
+			.into_iter()
+			.min()
+	}
+
+	/// Calculate the accumulated evaluations for the first sumcheck round over the subfield
+	/// `FBase` of the multilinears.
+	///
+	/// See [`Self::subfield_log_extension_degree`] for the rounds and multilinears this supports.
+	pub fn calculate_subfield_round_evals<FBase, Evaluator, Composition>(
+		&self,
+		evaluators: &[Evaluator],
+	) -> Result<Vec<RoundEvals<F>>, Error>
+	where
+		FBase: ExtensionField<FDomain>,
+		F: ExtensionField<FBase>,
+		P: PackedExtension<FBase>,
+		Evaluator: SubfieldSumcheckEvaluator<FBase, P, Composition> + Sync,
+		Composition: CompositionPoly<P>,
+	{
+		let _scope = tracing::debug_span!(
+			"calculate_subfield_round_evals",
+			n_vars = self.n_vars,
+			n_multilinears = self.multilinears.len(),
+			n_compositions = evaluators.len(),
+		)
+		.entered();
+
+		Ok(self
+			.backend
+			.sumcheck_compute_subfield_round_evals::<FBase, FDomain, _, _, _, Composition>(
+				self.evaluation_order,
+				self.n_vars,
+				&self.multilinears,
+				evaluators,
+				&self.nontrivial_evaluation_points,
+			)?)
+	}
+
 	/// Calculate the batched round coefficients from the domain evaluations.
 	///
 	/// This both performs the polynomial interpolation over the evaluations and the mixing with
@@ -0,0 +1,326 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{iter, marker::PhantomData};
+
+use binius_fast_compute::arith_circuit::ArithCircuitPoly;
+use binius_field::{ExtensionField, Field, PackedField, TowerField, util::powers};
+use binius_math::{
+	CompositionPoly, EvaluationDomainFactory, EvaluationOrder, InterpolationDomain,
+	MultilinearPoly, eq_ind_partial_eval, evaluate_univariate, extrapolate_line_scalar,
+};
+use binius_maybe_rayon::prelude::*;
+use binius_utils::bail;
+use itertools::izip;
+use tracing::instrument;
+
+use crate::protocols::sumcheck::{
+	common::{
+		CompositeSumClaim, RoundCoeffs, equal_n_vars_check, get_nontrivial_evaluation_points,
+		interpolation_domains_for_composition_degrees,
+	},
+	error::Error,
+	prove::{SumcheckProver, common::round_evals_to_coeffs},
+};
+
+/// Sumcheck prover that never materializes folded multilinears.
+///
+/// Where [`super::RegularSumcheckProver`] folds the multilinears with each round's challenge, this
+/// prover reads the original witness again in every round and specializes it to the accumulated
+/// challenges on the fly. Each value of a partially specialized multilinear is the inner product
+/// of a subcube of the witness with the tensor expansion of the challenges, which is kept as the
+/// tensor product of the expansions of the lower and upper halves of the challenges. The extra
+/// memory is thus on the order of the square root of the multilinear size, rather than half of the
+/// witness, at the cost of reading the whole witness once per round. This makes it possible to
+/// prove instances whose folded multilinears do not fit in memory.
+///
+/// The round polynomials are interpolated over the same domains as
+/// [`super::RegularSumcheckProver`], so the two provers produce identical proofs.
+pub struct StreamingSumcheckProver<FDomain, P, Composition, M>
+where
+	FDomain: Field,
+	P: PackedField,
+{
+	evaluation_order: EvaluationOrder,
+	n_vars: usize,
+	multilinears: Vec<M>,
+	compositions: Vec<Composition>,
+	compositions_at_infinity: Vec<ArithCircuitPoly<P::Scalar>>,
+	domains: Vec<InterpolationDomain<FDomain>>,
+	nontrivial_evaluation_points: Vec<P::Scalar>,
+	/// The challenges of the previous rounds, in the order of the variables they are bound to.
+	challenges: Vec<P::Scalar>,
+	last_coeffs_or_sums: CoeffsOrSums<P::Scalar>,
+	_p_marker: PhantomData<P>,
+}
+
+impl<F, FDomain, P, Composition, M> StreamingSumcheckProver<FDomain, P, Composition, M>
+where
+	F: TowerField + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<F>,
+	M: MultilinearPoly<P> + Send + Sync,
+{
+	#[instrument(skip_all, level = "debug", name = "StreamingSumcheckProver::new")]
+	pub fn new(
+		evaluation_order: EvaluationOrder,
+		multilinears: Vec<M>,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+	) -> Result<Self, Error> {
+		let n_vars = equal_n_vars_check(&multilinears)?;
+		let composite_claims = composite_claims.into_iter().collect::<Vec<_>>();
+
+		for claim in &composite_claims {
+			if claim.composition.n_vars() != multilinears.len() {
+				bail!(Error::InvalidComposition {
+					actual: claim.composition.n_vars(),
+					expected: multilinears.len(),
+				});
+			}
+		}
+
+		let domains = interpolation_domains_for_composition_degrees(
+			evaluation_domain_factory,
+			composite_claims
+				.iter()
+				.map(|composite_claim| composite_claim.composition.degree()),
+		)?;
+
+		let nontrivial_evaluation_points = get_nontrivial_evaluation_points(&domains)?
+			.into_iter()
+			.map(F::from)
+			.collect();
+
+		let claimed_sums = composite_claims
+			.iter()
+			.map(|composite_claim| composite_claim.sum)
+			.collect();
+
+		let compositions_at_infinity = composite_claims
+			.iter()
+			.map(|claim| ArithCircuitPoly::new(claim.composition.expression().leading_term()))
+			.collect();
+
+		let compositions = composite_claims
+			.into_iter()
+			.map(|claim| claim.composition)
+			.collect();
+
+		Ok(Self {
+			evaluation_order,
+			n_vars,
+			multilinears,
+			compositions,
+			compositions_at_infinity,
+			domains,
+			nontrivial_evaluation_points,
+			challenges: Vec::with_capacity(n_vars),
+			last_coeffs_or_sums: CoeffsOrSums::Sums(claimed_sums),
+			_p_marker: PhantomData,
+		})
+	}
+
+	/// Evaluates a multilinear, specialized to the challenges, at a point of the hypercube over the
+	/// remaining variables.
+	fn specialized_eval(
+		&self,
+		tensor: &ChallengeTensor<F>,
+		multilinear: &M,
+		free_index: usize,
+	) -> Result<F, Error> {
+		let n_bound_vars = self.challenges.len();
+		let n_free_vars = self.n_vars - n_bound_vars;
+
+		let mut eval = F::ZERO;
+		for (hi_index, &hi_weight) in tensor.hi.iter().enumerate() {
+			let mut partial_eval = F::ZERO;
+			for (lo_index, &lo_weight) in tensor.lo.iter().enumerate() {
+				let bound_index = lo_index | hi_index << tensor.n_lo_vars;
+				let index = match self.evaluation_order {
+					EvaluationOrder::LowToHigh => bound_index | free_index << n_bound_vars,
+					EvaluationOrder::HighToLow => free_index | bound_index << n_free_vars,
+				};
+				partial_eval += multilinear.evaluate_on_hypercube_and_scale(index, lo_weight)?;
+			}
+			eval += hi_weight * partial_eval;
+		}
+		Ok(eval)
+	}
+
+	/// Computes the evaluations of each composition's round polynomial at 1, at "infinity" if the
+	/// degree is at least 2, and at the nontrivial evaluation points, in one pass over the witness.
+	fn calculate_round_evals(&self) -> Result<Vec<Vec<F>>, Error> {
+		let n_free_vars = self.n_vars - self.challenges.len() - 1;
+		let tensor = ChallengeTensor::new(&self.challenges);
+
+		let zero_round_evals = || {
+			self.compositions
+				.iter()
+				.map(|composition| vec![F::ZERO; composition.degree().max(1)])
+				.collect::<Vec<_>>()
+		};
+
+		(0..1 << n_free_vars)
+			.into_par_iter()
+			.try_fold(zero_round_evals, |mut round_evals, i| {
+				let (free_index_0, free_index_1) = match self.evaluation_order {
+					EvaluationOrder::LowToHigh => (i << 1, i << 1 | 1),
+					EvaluationOrder::HighToLow => (i, i | 1 << n_free_vars),
+				};
+				let evals_0 = self
+					.multilinears
+					.iter()
+					.map(|multilinear| self.specialized_eval(&tensor, multilinear, free_index_0))
+					.collect::<Result<Vec<_>, _>>()?;
+				let evals_1 = self
+					.multilinears
+					.iter()
+					.map(|multilinear| self.specialized_eval(&tensor, multilinear, free_index_1))
+					.collect::<Result<Vec<_>, _>>()?;
+				let evals_inf = iter::zip(&evals_0, &evals_1)
+					.map(|(&eval_0, &eval_1)| eval_1 - eval_0)
+					.collect::<Vec<_>>();
+
+				let mut evals_z = vec![F::ZERO; self.multilinears.len()];
+				for (round_evals, composition, composition_at_infinity) in
+					izip!(&mut round_evals, &self.compositions, &self.compositions_at_infinity)
+				{
+					round_evals[0] += composition.evaluate(&evals_1)?;
+
+					if composition.degree() >= 2 {
+						round_evals[1] +=
+							CompositionPoly::<F>::evaluate(composition_at_infinity, &evals_inf)?;
+					}
+
+					for (round_eval, &point) in iter::zip(
+						round_evals.iter_mut().skip(2),
+						&self.nontrivial_evaluation_points,
+					) {
+						for (eval_z, &eval_0, &eval_1) in izip!(&mut evals_z, &evals_0, &evals_1) {
+							*eval_z = extrapolate_line_scalar(eval_0, eval_1, point);
+						}
+						*round_eval += composition.evaluate(&evals_z)?;
+					}
+				}
+				Ok::<_, Error>(round_evals)
+			})
+			.try_reduce(zero_round_evals, |mut lhs, rhs| {
+				for (lhs_evals, rhs_evals) in iter::zip(&mut lhs, rhs) {
+					for (lhs_eval, rhs_eval) in iter::zip(lhs_evals, rhs_evals) {
+						*lhs_eval += rhs_eval;
+					}
+				}
+				Ok(lhs)
+			})
+	}
+}
+
+impl<F, FDomain, P, Composition, M> SumcheckProver<F>
+	for StreamingSumcheckProver<FDomain, P, Composition, M>
+where
+	F: TowerField + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<F>,
+	M: MultilinearPoly<P> + Send + Sync,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn evaluation_order(&self) -> EvaluationOrder {
+		self.evaluation_order
+	}
+
+	#[instrument("StreamingSumcheckProver::execute", skip_all, level = "debug")]
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		if self.challenges.len() == self.n_vars {
+			bail!(Error::ExpectedFinish);
+		}
+
+		let CoeffsOrSums::Sums(ref sums) = self.last_coeffs_or_sums else {
+			bail!(Error::ExpectedFold);
+		};
+
+		let round_evals = self.calculate_round_evals()?;
+		let round_coeffs = izip!(&self.domains, sums, round_evals)
+			.map(|(domain, &sum, evals)| round_evals_to_coeffs(domain, sum, evals))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let batched_coeffs = iter::zip(&round_coeffs, powers(batch_coeff))
+			.fold(RoundCoeffs::default(), |accum, (coeffs, scalar)| {
+				accum + &(coeffs.clone() * scalar)
+			});
+		self.last_coeffs_or_sums = CoeffsOrSums::Coeffs(round_coeffs);
+		Ok(batched_coeffs)
+	}
+
+	#[instrument("StreamingSumcheckProver::fold", skip_all, level = "debug")]
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		if self.challenges.len() == self.n_vars {
+			bail!(Error::ExpectedFinish);
+		}
+
+		match self.last_coeffs_or_sums {
+			CoeffsOrSums::Coeffs(ref round_coeffs) => {
+				let new_sums = round_coeffs
+					.iter()
+					.map(|coeffs| evaluate_univariate(&coeffs.0, challenge))
+					.collect();
+				self.last_coeffs_or_sums = CoeffsOrSums::Sums(new_sums);
+			}
+			CoeffsOrSums::Sums(_) => {
+				bail!(Error::ExpectedExecution);
+			}
+		}
+
+		// Only the challenge is recorded; the witness is specialized to it in later rounds.
+		match self.evaluation_order {
+			EvaluationOrder::LowToHigh => self.challenges.push(challenge),
+			EvaluationOrder::HighToLow => self.challenges.insert(0, challenge),
+		}
+		Ok(())
+	}
+
+	fn finish(self: Box<Self>) -> Result<Vec<F>, Error> {
+		match self.last_coeffs_or_sums {
+			CoeffsOrSums::Coeffs(_) => bail!(Error::ExpectedFold),
+			CoeffsOrSums::Sums(_) if self.challenges.len() < self.n_vars => {
+				bail!(Error::ExpectedExecution)
+			}
+			CoeffsOrSums::Sums(_) => {}
+		}
+
+		let tensor = ChallengeTensor::new(&self.challenges);
+		self.multilinears
+			.iter()
+			.map(|multilinear| self.specialized_eval(&tensor, multilinear, 0))
+			.collect()
+	}
+}
+
+/// The tensor expansion of a point, factored as the tensor product of the expansions of the
+/// lower and upper halves of its coordinates.
+struct ChallengeTensor<F> {
+	n_lo_vars: usize,
+	lo: Vec<F>,
+	hi: Vec<F>,
+}
+
+impl<F: Field> ChallengeTensor<F> {
+	fn new(point: &[F]) -> Self {
+		let n_lo_vars = point.len() / 2;
+		Self {
+			n_lo_vars,
+			lo: eq_ind_partial_eval(&point[..n_lo_vars]),
+			hi: eq_ind_partial_eval(&point[n_lo_vars..]),
+		}
+	}
+}
+
+#[derive(Debug)]
+enum CoeffsOrSums<F: Field> {
+	Coeffs(Vec<RoundCoeffs<F>>),
+	Sums(Vec<F>),
+}
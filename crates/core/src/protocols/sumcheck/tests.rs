@@ -5,6 +5,7 @@ use std::{
 	sync::Arc,
 };
 
+use binius_fast_compute::arith_circuit::ArithCircuitPoly;
 use binius_field::{
 	BinaryField, BinaryField8b, BinaryField32b, BinaryField128b, ExtensionField, Field,
 	PackedBinaryField1x128b, PackedBinaryField4x32b, PackedExtension, PackedField,
@@ -18,8 +19,8 @@ use binius_hal::{ComputationBackend, ComputationBackendExt, make_portable_backen
 use binius_hash::groestl::Groestl256;
 use binius_math::{
 	ArithCircuit, CompositionPoly, EvaluationDomainFactory, EvaluationOrder,
-	IsomorphicEvaluationDomainFactory, MLEEmbeddingAdapter, MultilinearExtension, MultilinearPoly,
-	MultilinearQuery,
+	IsomorphicEvaluationDomainFactory, MLEDirectAdapter, MLEEmbeddingAdapter, MultilinearExtension,
+	MultilinearPoly, MultilinearQuery,
 };
 use binius_maybe_rayon::{current_num_threads, prelude::*};
 use binius_utils::checked_arithmetics::log2_ceil_usize;
@@ -27,13 +28,16 @@ use itertools::izip;
 use rand::{Rng, SeedableRng, rngs::StdRng};
 
 use super::{
-	BatchSumcheckOutput, SumcheckClaim,
+	BatchSumcheckOutput, Error, SumcheckClaim, VerificationError,
 	common::CompositeSumClaim,
 	front_loaded::BatchVerifier as FrontLoadedBatchVerifier,
+	immediate_switchover_heuristic,
 	prove::{
-		RegularSumcheckProver, batch_prove, front_loaded::BatchProver as FrontLoadedBatchProver,
+		RegularSumcheckProver, StreamingSumcheckProver, batch_prove,
+		distributed::ShardedSumcheckProver, front_loaded::BatchProver as FrontLoadedBatchProver,
 	},
 	verify_sumcheck::batch_verify,
+	zk::{MaskedSumcheckProver, MaskingPolynomial, mask_claim},
 };
 use crate::{
 	composition::index_composition,
@@ -43,7 +47,7 @@ use crate::{
 		sumcheck::prove::SumcheckProver,
 		test_utils::{AddOneComposition, TestProductComposition},
 	},
-	transcript::ProverTranscript,
+	transcript::{ProverTranscript, VerifierTranscript},
 };
 
 #[derive(Debug, Clone)]
@@ -416,7 +420,23 @@ fn prove_verify_batch_with_evaluation_order(
 
 	let prover_sample = CanSample::<FE>::sample(&mut prover_transcript);
 
-	let mut verifier_transcript = prover_transcript.into_verifier();
+	let proof = prover_transcript.finalize();
+
+	#[cfg(feature = "reference")]
+	{
+		let mut reference_transcript =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+		let reference_output = crate::reference::sumcheck::batch_verify(
+			evaluation_order,
+			&claims,
+			&mut reference_transcript,
+		)
+		.unwrap();
+		assert_eq!(prover_output, reference_output);
+		assert_eq!(prover_sample, CanSample::<FE>::sample(&mut reference_transcript));
+	}
+
+	let mut verifier_transcript = VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof);
 	let verifier_output =
 		batch_verify(evaluation_order, &claims, &mut verifier_transcript).unwrap();
 
@@ -458,6 +478,44 @@ fn test_prove_verify_batch_constant_polys() {
 	]);
 }
 
+#[test]
+fn test_verify_reports_missing_round_proof() {
+	type P = PackedBinaryField4x32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	type PE = PackedBinaryField1x128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let backend = make_portable_backend();
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let (_, claim, prover) = make_test_sumcheck::<FE, FDomain, P, PE, _>(
+		EvaluationOrder::LowToHigh,
+		&TestSumcheckClaimShape {
+			n_vars: 4,
+			degree: 3,
+		},
+		&mut rng,
+		&domain_factory,
+		&backend,
+	);
+
+	// Debug assertions in the transcript would interleave extra data with the round messages.
+	let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prover_transcript.set_debug(false);
+	batch_prove(vec![prover], &mut prover_transcript).unwrap();
+	let mut proof = prover_transcript.finalize();
+
+	// Keep the first two round messages, of three coefficients each.
+	proof.truncate(2 * 3 * size_of::<FE>());
+	let mut verifier_transcript = VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof);
+	verifier_transcript.set_debug(false);
+	assert!(matches!(
+		batch_verify(EvaluationOrder::LowToHigh, &[claim], &mut verifier_transcript),
+		Err(Error::Verification(VerificationError::MissingRoundProof { round: 2 }))
+	));
+}
+
 fn prove_verify_batch_front_loaded(claim_shapes: &[TestSumcheckClaimShape]) {
 	for evaluation_order in [EvaluationOrder::LowToHigh, EvaluationOrder::HighToLow] {
 		test_prove_verify_batch_front_loaded_with_evaluation_order(evaluation_order, claim_shapes);
@@ -543,3 +601,240 @@ fn test_prove_verify_batch_front_loaded() {
 		},
 	]);
 }
+
+#[test]
+fn test_prove_verify_masked_sumcheck() {
+	type F = BinaryField128b;
+	type P = PackedBinaryField1x128b;
+
+	let n_vars = 6;
+	let n_multilinears = 3;
+	for evaluation_order in [EvaluationOrder::LowToHigh, EvaluationOrder::HighToLow] {
+		let mut rng = StdRng::seed_from_u64(0);
+		let multilins = generate_random_multilinears::<P>(&mut rng, n_vars, n_multilinears)
+			.into_iter()
+			.map(MLEEmbeddingAdapter::<_, P, _>::from)
+			.collect::<Vec<_>>();
+		let composition = TestProductComposition::new(n_multilinears);
+		let sum = compute_composite_sum(&multilins, &composition);
+		let composite_claim = CompositeSumClaim {
+			composition: &composition,
+			sum,
+		};
+		let claim =
+			SumcheckClaim::new(n_vars, n_multilinears, vec![composite_claim.clone()]).unwrap();
+
+		let mask = MaskingPolynomial::<F>::random(n_vars, claim.max_individual_degree(), &mut rng);
+		let masked_claim = mask_claim(&claim, mask.sum()).unwrap();
+
+		let backend = make_portable_backend();
+		let prover = RegularSumcheckProver::<BinaryField8b, _, _, _, _>::new(
+			evaluation_order,
+			multilins.iter().collect(),
+			[composite_claim],
+			IsomorphicEvaluationDomainFactory::<BinaryField8b>::default(),
+			immediate_switchover_heuristic,
+			&backend,
+		)
+		.unwrap();
+		let prover = MaskedSumcheckProver::new(prover, 1, mask.clone()).unwrap();
+
+		let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let prover_output = batch_prove(vec![prover], &mut prover_transcript).unwrap();
+
+		let mut verifier_transcript = prover_transcript.into_verifier();
+		let verifier_output =
+			batch_verify(evaluation_order, &[masked_claim], &mut verifier_transcript).unwrap();
+		verifier_transcript.finalize().unwrap();
+		assert_eq!(prover_output, verifier_output);
+
+		let BatchSumcheckOutput {
+			challenges,
+			multilinear_evals,
+		} = verifier_output;
+		let query = MultilinearQuery::<P>::expand(&challenges);
+		for (multilinear, &eval) in iter::zip(&multilins, &multilinear_evals[0]) {
+			assert_eq!(multilinear.evaluate(query.to_ref()).unwrap(), eval);
+		}
+		let mask_eval = multilinear_evals[0][n_multilinears];
+		assert_eq!(mask.evaluate(&challenges).unwrap(), mask_eval);
+		let weights = mask.eval_weights(&challenges).unwrap();
+		assert_eq!(
+			iter::zip(weights, mask.coeffs())
+				.map(|(weight, coeff)| weight * coeff)
+				.sum::<F>(),
+			mask_eval
+		);
+	}
+}
+
+#[test]
+fn test_prove_verify_sharded_sumcheck() {
+	type F = BinaryField128b;
+	type P = PackedBinaryField1x128b;
+	type FDomain = BinaryField8b;
+
+	let n_vars = 7;
+	let n_multilinears = 3;
+	let mut rng = StdRng::seed_from_u64(0);
+	let multilins = generate_random_multilinears::<P>(&mut rng, n_vars, n_multilinears);
+	let adapted_multilins = multilins
+		.iter()
+		.map(|multilin| MLEDirectAdapter::from(multilin.to_ref()))
+		.collect::<Vec<_>>();
+	let composition = TestProductComposition::new(n_multilinears);
+	let sum = compute_composite_sum(&adapted_multilins, &composition);
+	let claim = SumcheckClaim::new(
+		n_vars,
+		n_multilinears,
+		vec![CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+	)
+	.unwrap();
+
+	let backend = make_portable_backend();
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+
+	let unsharded_prover = RegularSumcheckProver::<FDomain, _, _, _, _>::new(
+		EvaluationOrder::LowToHigh,
+		adapted_multilins.iter().collect(),
+		[CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+		domain_factory.clone(),
+		immediate_switchover_heuristic,
+		&backend,
+	)
+	.unwrap();
+	let mut unsharded_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let unsharded_output = batch_prove(vec![unsharded_prover], &mut unsharded_transcript).unwrap();
+
+	for log_n_shards in [1, 3, n_vars] {
+		let shard_n_vars = n_vars - log_n_shards;
+		let workers = (0..1 << log_n_shards)
+			.map(|shard| {
+				let shard_multilins = multilins
+					.iter()
+					.map(|multilin| {
+						let evals =
+							&multilin.evals()[shard << shard_n_vars..(shard + 1) << shard_n_vars];
+						MLEDirectAdapter::from(
+							MultilinearExtension::from_values_slice(evals).unwrap(),
+						)
+					})
+					.collect::<Vec<_>>();
+				let shard_sum = compute_composite_sum(&shard_multilins, &composition);
+				RegularSumcheckProver::<FDomain, _, _, _, _>::new(
+					EvaluationOrder::LowToHigh,
+					shard_multilins,
+					[CompositeSumClaim {
+						composition: &composition,
+						sum: shard_sum,
+					}],
+					domain_factory.clone(),
+					immediate_switchover_heuristic,
+					&backend,
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>();
+		let prover = ShardedSumcheckProver::<FDomain, P, _, _, _, _>::new(
+			workers,
+			vec![&composition],
+			domain_factory.clone(),
+			&backend,
+		)
+		.unwrap();
+
+		let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let prover_output = batch_prove(vec![prover], &mut prover_transcript).unwrap();
+		assert_eq!(prover_output, unsharded_output);
+
+		let mut verifier_transcript = prover_transcript.into_verifier();
+		let verifier_output = batch_verify::<F, _, _>(
+			EvaluationOrder::LowToHigh,
+			&[claim.clone()],
+			&mut verifier_transcript,
+		)
+		.unwrap();
+		verifier_transcript.finalize().unwrap();
+		assert_eq!(prover_output, verifier_output);
+	}
+}
+
+#[test]
+fn test_streaming_sumcheck_matches_regular() {
+	type F = BinaryField128b;
+	type P = PackedBinaryField1x128b;
+	type FDomain = BinaryField8b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let backend = make_portable_backend();
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+
+	for evaluation_order in [EvaluationOrder::LowToHigh, EvaluationOrder::HighToLow] {
+		for n_vars in 1..7 {
+			for n_multilinears in 1..4 {
+				let multilins = generate_random_multilinears::<P>(&mut rng, n_vars, n_multilinears)
+					.into_iter()
+					.map(MLEDirectAdapter::from)
+					.collect::<Vec<_>>();
+
+				// Batch compositions of different degrees, so that the round polynomials are
+				// interpolated over different domains.
+				let product = (1..n_multilinears)
+					.fold(ArithCircuit::<F>::var(0), |product, i| product * ArithCircuit::var(i));
+				let compositions = [
+					ArithCircuitPoly::with_n_vars(n_multilinears, product.clone()).unwrap(),
+					ArithCircuitPoly::with_n_vars(
+						n_multilinears,
+						product.clone() * ArithCircuit::var(0) + ArithCircuit::one(),
+					)
+					.unwrap(),
+				];
+				let composite_claims = compositions
+					.iter()
+					.map(|composition| CompositeSumClaim {
+						composition,
+						sum: compute_composite_sum(&multilins, composition),
+					})
+					.collect::<Vec<_>>();
+
+				let regular_prover = RegularSumcheckProver::<FDomain, _, _, _, _>::new(
+					evaluation_order,
+					multilins.iter().collect(),
+					composite_claims.clone(),
+					domain_factory.clone(),
+					immediate_switchover_heuristic,
+					&backend,
+				)
+				.unwrap();
+				let mut regular_transcript =
+					ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				let regular_output =
+					batch_prove(vec![regular_prover], &mut regular_transcript).unwrap();
+
+				let streaming_prover = StreamingSumcheckProver::<FDomain, P, _, _>::new(
+					evaluation_order,
+					multilins.iter().collect(),
+					composite_claims,
+					domain_factory.clone(),
+				)
+				.unwrap();
+				let mut streaming_transcript =
+					ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				let streaming_output =
+					batch_prove(vec![streaming_prover], &mut streaming_transcript).unwrap();
+
+				assert_eq!(streaming_output, regular_output);
+				assert_eq!(
+					CanSample::<F>::sample(&mut streaming_transcript),
+					CanSample::<F>::sample(&mut regular_transcript)
+				);
+			}
+		}
+	}
+}
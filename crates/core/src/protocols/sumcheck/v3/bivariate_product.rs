@@ -3,8 +3,8 @@
 use std::{iter, slice};
 
 use binius_compute::{
-	ComputeLayer, ComputeLayerExecutor, ComputeMemory, FSlice, KernelBuffer, KernelExecutor,
-	KernelMemMap, SizedSlice, SlicesBatch,
+	ComputeLayer, ComputeLayerExecutor, ComputeMemory, FSlice, FSliceMut, KernelBuffer,
+	KernelExecutor, KernelMemMap, SizedSlice, SlicesBatch,
 	alloc::{BumpAllocator, ComputeAllocator, HostBumpAllocator},
 };
 use binius_field::{Field, TowerField, util::powers};
@@ -33,6 +33,9 @@ pub struct BivariateSumcheckProver<'a, 'alloc, F: Field, Hal: ComputeLayer<F>> {
 	multilins: Vec<SumcheckMultilinear<'a, F, Hal::DevMem>>,
 	compositions: Vec<IndexComposition<BivariateProduct, 2>>,
 	last_coeffs_or_sums: PhaseState<F>,
+	/// Fold buffers prepared ahead of the next challenge by [`SumcheckProver::prepare_fold`], one
+	/// for each multilinear that is not yet owned by the prover.
+	prepared_folds: Option<Vec<Option<FSliceMut<'a, F, Hal>>>>,
 }
 
 impl<'a, 'alloc, F, Hal> BivariateSumcheckProver<'a, 'alloc, F, Hal>
@@ -46,23 +49,37 @@ where
 		host_alloc: &'a HostBumpAllocator<'a, F>,
 		claim: &SumcheckClaim<F, IndexComposition<BivariateProduct, 2>>,
 		multilins: Vec<FSlice<'a, F, Hal>>,
+	) -> Result<Self, Error> {
+		// Wrap multilinear witness inputs as SumcheckMultilinears.
+		let multilins = multilins
+			.into_iter()
+			.map(SumcheckMultilinear::PreFold)
+			.collect();
+		Self::new_with_multilins(hal, dev_alloc, host_alloc, claim, multilins)
+	}
+
+	/// Constructs a prover from multilinears that may be owned by the prover.
+	///
+	/// Multilinears given as [`SumcheckMultilinear::PostFold`] are mutable device buffers owned by
+	/// the prover, which are folded in place rather than into newly allocated buffers. They
+	/// therefore require no additional device memory beyond their own evaluations.
+	pub fn new_with_multilins(
+		hal: &'a Hal,
+		dev_alloc: &'a BumpAllocator<'alloc, F, Hal::DevMem>,
+		host_alloc: &'a HostBumpAllocator<'a, F>,
+		claim: &SumcheckClaim<F, IndexComposition<BivariateProduct, 2>>,
+		multilins: Vec<SumcheckMultilinear<'a, F, Hal::DevMem>>,
 	) -> Result<Self, Error> {
 		let n_vars = claim.n_vars();
 
 		// Check shape of multilinear witness inputs.
 		assert_eq!(claim.n_multilinears(), multilins.len());
 		for multilin in &multilins {
-			if multilin.len() != 1 << n_vars {
+			if multilin.const_slice().len() != 1 << n_vars {
 				bail!(Error::NumberOfVariablesMismatch);
 			}
 		}
 
-		// Wrap multilinear witness inputs as SumcheckMultilinears.
-		let multilins = multilins
-			.into_iter()
-			.map(SumcheckMultilinear::PreFold)
-			.collect();
-
 		let (compositions, sums) = claim
 			.composite_sums()
 			.iter()
@@ -78,9 +95,23 @@ where
 			multilins,
 			compositions,
 			last_coeffs_or_sums: PhaseState::InitialSums(sums),
+			prepared_folds: None,
 		})
 	}
 
+	/// Allocates a buffer for the folded evaluations of a multilinear and copies in the
+	/// evaluations of its low half.
+	fn alloc_fold_buffer(
+		hal: &Hal,
+		dev_alloc: &'a BumpAllocator<'alloc, F, Hal::DevMem>,
+		evals_0: FSlice<'_, F, Hal>,
+	) -> Result<FSliceMut<'a, F, Hal>, binius_compute::Error> {
+		let mut folded_evals = dev_alloc.alloc(evals_0.len())?;
+		// This is kind of sketchy to do a copy without an execution context.
+		hal.copy_d2d(evals_0, &mut folded_evals)?;
+		Ok(folded_evals)
+	}
+
 	/// Returns the amount of host memory this sumcheck requires.
 	pub fn required_host_memory(
 		claim: &SumcheckClaim<F, IndexComposition<BivariateProduct, 2>>,
@@ -164,20 +195,26 @@ where
 			}
 		}
 
+		let prepared_folds = match self.prepared_folds.take() {
+			Some(prepared_folds) => prepared_folds,
+			None => iter::repeat_with(|| None)
+				.take(self.multilins.len())
+				.collect(),
+		};
+
 		// Fold the multilinears
 		let _ = self.hal.execute(|exec| {
-			self.multilins = exec.map(self.multilins.drain(..), |exec, multilin| {
+			let multilins = iter::zip(self.multilins.drain(..), prepared_folds);
+			self.multilins = exec.map(multilins, |exec, (multilin, prepared_fold)| {
 				let folded_evals = match multilin {
 					SumcheckMultilinear::PreFold(evals) => {
 						debug_assert_eq!(evals.len(), 1 << self.n_vars_remaining);
 
 						let (evals_0, evals_1) = Hal::DevMem::split_half(evals);
-						// Allocate new buffer for the folded evaluations and copy in evals_0.
-						let mut folded_evals =
-							self.dev_alloc.alloc(1 << (self.n_vars_remaining - 1))?;
-						// This is kind of sketchy to do a copy without an execution context.
-						self.hal.copy_d2d(evals_0, &mut folded_evals)?;
-
+						let mut folded_evals = match prepared_fold {
+							Some(folded_evals) => folded_evals,
+							None => Self::alloc_fold_buffer(self.hal, self.dev_alloc, evals_0)?,
+						};
 						exec.extrapolate_line(&mut folded_evals, evals_1, challenge)?;
 						folded_evals
 					}
@@ -202,6 +239,28 @@ where
 		Ok(())
 	}
 
+	fn prepare_fold(&mut self) -> Result<(), Error> {
+		if self.n_vars_remaining == 0 || self.prepared_folds.is_some() {
+			return Ok(());
+		}
+
+		// Allocating the folded buffers and copying in the low halves does not depend on the
+		// challenge, leaving only the line extrapolation to `fold`.
+		let prepared_folds = self
+			.multilins
+			.iter()
+			.map(|multilin| match multilin {
+				SumcheckMultilinear::PreFold(evals) => {
+					let (evals_0, _) = Hal::DevMem::split_half(Hal::DevMem::narrow(evals));
+					Ok(Some(Self::alloc_fold_buffer(self.hal, self.dev_alloc, evals_0)?))
+				}
+				SumcheckMultilinear::PostFold(_) => Ok(None),
+			})
+			.collect::<Result<_, Error>>()?;
+		self.prepared_folds = Some(prepared_folds);
+		Ok(())
+	}
+
 	fn finish(self: Box<Self>) -> Result<Vec<F>, Error> {
 		match self.last_coeffs_or_sums {
 			PhaseState::Coeffs(_) => {
@@ -403,6 +462,8 @@ pub enum PhaseState<F: Field> {
 
 #[cfg(test)]
 mod tests {
+	use std::iter::repeat_with;
+
 	use binius_compute::cpu::CpuLayer;
 	use binius_compute_test_utils::bivariate_sumcheck::{
 		generic_test_bivariate_sumcheck_prove_verify, generic_test_calculate_round_evals,
@@ -412,10 +473,15 @@ mod tests {
 		BinaryField128b, PackedField, arch::OptimalUnderlier, as_packed_field::PackedType,
 		tower::CanonicalTowerFamily,
 	};
-	use binius_math::B128;
+	use binius_math::{B128, MultilinearExtension, MultilinearQuery};
 	use bytemuck::zeroed_vec;
+	use rand::{SeedableRng, rngs::StdRng};
 
 	use super::*;
+	use crate::protocols::sumcheck::{
+		RoundProof,
+		prove::{InteractiveChannel, front_loaded::BatchProver},
+	};
 
 	#[test]
 	fn test_calculate_round_evals() {
@@ -480,4 +546,98 @@ mod tests {
 			n_compositions,
 		)
 	}
+
+	/// A verifier on the other end of an interactive channel, which checks each round message
+	/// against the running sum.
+	struct TestVerifierChannel {
+		rng: StdRng,
+		sum: B128,
+		challenges: Vec<B128>,
+		multilinear_evals: Vec<Vec<B128>>,
+	}
+
+	impl InteractiveChannel<B128> for TestVerifierChannel {
+		fn send_multilinear_evals(&mut self, evals: &[B128]) -> Result<(), Error> {
+			self.multilinear_evals.push(evals.to_vec());
+			Ok(())
+		}
+
+		fn send_round_proof(&mut self, round_proof: &RoundProof<B128>) -> Result<(), Error> {
+			let coeffs = round_proof.clone().recover(self.sum);
+			let challenge = <B128 as Field>::random(&mut self.rng);
+			self.sum = evaluate_univariate(&coeffs.0, challenge);
+			self.challenges.push(challenge);
+			Ok(())
+		}
+
+		fn receive_challenge(&mut self) -> Result<B128, Error> {
+			Ok(*self.challenges.last().expect("a round proof was sent"))
+		}
+	}
+
+	#[test]
+	fn test_bivariate_sumcheck_prove_interactive() {
+		type Hal = CpuLayer<B128>;
+
+		let n_vars = 6;
+		let mut rng = StdRng::seed_from_u64(0);
+		let evals = repeat_with(|| {
+			repeat_with(|| <B128 as Field>::random(&mut rng))
+				.take(1 << n_vars)
+				.collect::<Vec<_>>()
+		})
+		.take(3)
+		.collect::<Vec<_>>();
+		let composition = IndexComposition::new(3, [0, 2], BivariateProduct::default()).unwrap();
+		let sum = iter::zip(&evals[0], &evals[2])
+			.map(|(&a, &b)| a * b)
+			.sum::<B128>();
+		let claim =
+			SumcheckClaim::new(n_vars, 3, vec![CompositeSumClaim { composition, sum }]).unwrap();
+
+		let hal = Hal::default();
+		let mut host_mem = zeroed_vec(Prover::required_host_memory(&claim));
+		let mut dev_mem = evals.concat();
+		dev_mem.extend(zeroed_vec::<B128>(Prover::required_device_memory(&claim)));
+		let (dev_multilins, dev_mem) = dev_mem.split_at_mut(3 << n_vars);
+		let host_alloc = HostBumpAllocator::new(host_mem.as_mut_slice());
+		let dev_alloc = BumpAllocator::<_, <Hal as ComputeLayer<B128>>::DevMem>::new(dev_mem);
+
+		type Prover<'a, 'alloc> = BivariateSumcheckProver<'a, 'alloc, B128, Hal>;
+		let prover = Prover::new(
+			&hal,
+			&dev_alloc,
+			&host_alloc,
+			&claim,
+			dev_multilins.chunks(1 << n_vars).collect(),
+		)
+		.unwrap();
+
+		let batch_coeff = <B128 as Field>::random(&mut rng);
+		let batch_prover = BatchProver::new_prebatched(vec![batch_coeff], vec![prover]).unwrap();
+		let mut channel = TestVerifierChannel {
+			rng,
+			sum: sum * batch_coeff,
+			challenges: Vec::new(),
+			multilinear_evals: Vec::new(),
+		};
+		let output = batch_prover.run_interactive(&mut channel).unwrap();
+
+		assert_eq!(output.challenges, channel.challenges);
+		assert_eq!(output.multilinear_evals, channel.multilinear_evals);
+		let multilinear_evals = &output.multilinear_evals[0];
+		assert_eq!(channel.sum, batch_coeff * multilinear_evals[0] * multilinear_evals[2]);
+
+		// The prover folds from the highest variable to the lowest.
+		let mut point = output.challenges;
+		point.reverse();
+		for (evals, &eval) in iter::zip(&evals, multilinear_evals) {
+			let mle = MultilinearExtension::new(n_vars, evals.as_slice()).unwrap();
+			assert_eq!(
+				mle.evaluate(&MultilinearQuery::<B128>::expand(&point))
+					.unwrap(),
+				eval
+			);
+		}
+	}
 }
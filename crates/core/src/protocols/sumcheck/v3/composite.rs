@@ -0,0 +1,544 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{iter, slice};
+
+use binius_compute::{
+	ComputeLayer, ComputeLayerExecutor, ComputeMemory, FSlice, KernelBuffer, KernelExecutor,
+	KernelMemMap, SizedSlice, SlicesBatch,
+	alloc::{BumpAllocator, ComputeAllocator, HostBumpAllocator},
+};
+use binius_field::{ExtensionField, Field, TowerField, util::powers};
+use binius_math::{
+	CompositionPoly, EvaluationDomainFactory, EvaluationOrder, InterpolationDomain,
+	evaluate_univariate,
+};
+use binius_utils::bail;
+use itertools::{chain, izip};
+
+use crate::protocols::sumcheck::{
+	CompositeSumClaim, Error, RoundCoeffs, SumcheckClaim,
+	common::{get_nontrivial_evaluation_points, interpolation_domains_for_composition_degrees},
+	prove::{SumcheckProver, common::round_evals_to_coeffs},
+};
+
+/// Sumcheck prover implementation for arbitrary compositions over large-field multilinears.
+///
+/// This implements the [`SumcheckProver`] interface. The implementation uses a [`ComputeLayer`]
+/// instance for expensive operations and the input multilinears are provided as device memory
+/// slices. Each round, the round polynomial is evaluated over the hypercube with
+/// [`KernelExecutor::sum_composition_evals_at_point`], and the multilinears are folded with
+/// [`ComputeLayerExecutor::fold_multilinear`] into newly allocated device buffers. The round
+/// polynomials are interpolated over the same domains as
+/// [`crate::protocols::sumcheck::prove::RegularSumcheckProver`], and the variables are bound in
+/// high-to-low order.
+///
+/// Zerocheck claims can be proven by passing the tensor expansion of the zerocheck challenges as
+/// an additional multilinear, and multiplying the compositions by it.
+pub struct CompositeSumcheckProver<'a, 'alloc, F, FDomain, Hal, Composition>
+where
+	F: Field,
+	FDomain: Field,
+	Hal: ComputeLayer<F>,
+{
+	hal: &'a Hal,
+	dev_alloc: &'a BumpAllocator<'alloc, F, Hal::DevMem>,
+	host_alloc: &'a HostBumpAllocator<'a, F>,
+	n_vars_initial: usize,
+	n_vars_remaining: usize,
+	multilins: Vec<FSlice<'a, F, Hal>>,
+	compositions: Vec<Composition>,
+	domains: Vec<InterpolationDomain<FDomain>>,
+	nontrivial_evaluation_points: Vec<F>,
+	last_coeffs_or_sums: CoeffsOrSums<F>,
+}
+
+impl<'a, 'alloc, F, FDomain, Hal, Composition>
+	CompositeSumcheckProver<'a, 'alloc, F, FDomain, Hal, Composition>
+where
+	F: TowerField + ExtensionField<FDomain>,
+	FDomain: Field,
+	Hal: ComputeLayer<F>,
+	Composition: CompositionPoly<F> + Clone,
+{
+	pub fn new(
+		hal: &'a Hal,
+		dev_alloc: &'a BumpAllocator<'alloc, F, Hal::DevMem>,
+		host_alloc: &'a HostBumpAllocator<'a, F>,
+		claim: &SumcheckClaim<F, Composition>,
+		multilins: Vec<FSlice<'a, F, Hal>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+	) -> Result<Self, Error> {
+		let n_vars = claim.n_vars();
+
+		// Check shape of multilinear witness inputs.
+		assert_eq!(claim.n_multilinears(), multilins.len());
+		for multilin in &multilins {
+			if multilin.len() != 1 << n_vars {
+				bail!(Error::NumberOfVariablesMismatch);
+			}
+		}
+
+		let (compositions, sums): (Vec<_>, Vec<_>) = claim
+			.composite_sums()
+			.iter()
+			.map(|CompositeSumClaim { composition, sum }| (composition.clone(), *sum))
+			.unzip();
+
+		let domains = interpolation_domains_for_composition_degrees(
+			evaluation_domain_factory,
+			compositions.iter().map(|composition| composition.degree()),
+		)?;
+		let nontrivial_evaluation_points = get_nontrivial_evaluation_points(&domains)?
+			.into_iter()
+			.map(F::from)
+			.collect();
+
+		Ok(Self {
+			hal,
+			dev_alloc,
+			host_alloc,
+			n_vars_initial: n_vars,
+			n_vars_remaining: n_vars,
+			multilins,
+			compositions,
+			domains,
+			nontrivial_evaluation_points,
+			last_coeffs_or_sums: CoeffsOrSums::Sums(sums),
+		})
+	}
+
+	/// Returns the amount of host memory this sumcheck requires.
+	pub fn required_host_memory(claim: &SumcheckClaim<F, Composition>) -> usize {
+		// In `finish()`, prover allocates a temporary host buffer for each of the fully folded
+		// multilinear evaluations.
+		claim.n_multilinears()
+	}
+
+	/// Returns the amount of device memory this sumcheck requires.
+	pub fn required_device_memory(claim: &SumcheckClaim<F, Composition>) -> usize {
+		// In `fold()`, prover allocates device buffers for each of the folded multilinears in
+		// every round, each half the size of the previous round's, padded to the alignment.
+		let folded_size = (0..claim.n_vars())
+			.map(|n_vars| (1 << n_vars).max(Hal::DevMem::ALIGNMENT))
+			.sum::<usize>();
+		claim.n_multilinears() * folded_size
+	}
+}
+
+impl<F, FDomain, Hal, Composition> SumcheckProver<F>
+	for CompositeSumcheckProver<'_, '_, F, FDomain, Hal, Composition>
+where
+	F: TowerField + ExtensionField<FDomain>,
+	FDomain: Field,
+	Hal: ComputeLayer<F>,
+	Composition: CompositionPoly<F>,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars_initial
+	}
+
+	fn evaluation_order(&self) -> EvaluationOrder {
+		EvaluationOrder::HighToLow
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		let sums = match self.last_coeffs_or_sums {
+			CoeffsOrSums::Coeffs(_) => {
+				bail!(Error::ExpectedFold);
+			}
+			CoeffsOrSums::Sums(ref sums) => sums,
+		};
+
+		let round_evals = calculate_round_evals(
+			self.hal,
+			self.n_vars_remaining,
+			&self.multilins,
+			&self.compositions,
+			&self.nontrivial_evaluation_points,
+		)?;
+		let round_coeffs = izip!(&self.domains, sums, round_evals)
+			.map(|(domain, &sum, evals)| round_evals_to_coeffs(domain, sum, evals))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let batched_coeffs = iter::zip(&round_coeffs, powers(batch_coeff))
+			.fold(RoundCoeffs::default(), |accum, (coeffs, scalar)| {
+				accum + &(coeffs.clone() * scalar)
+			});
+		self.last_coeffs_or_sums = CoeffsOrSums::Coeffs(round_coeffs);
+		Ok(batched_coeffs)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		if self.n_vars_remaining == 0 {
+			bail!(Error::ExpectedFinish);
+		}
+
+		// Update the stored multilinear sums.
+		match self.last_coeffs_or_sums {
+			CoeffsOrSums::Coeffs(ref round_coeffs) => {
+				let new_sums = round_coeffs
+					.iter()
+					.map(|coeffs| evaluate_univariate(&coeffs.0, challenge))
+					.collect();
+				self.last_coeffs_or_sums = CoeffsOrSums::Sums(new_sums);
+			}
+			CoeffsOrSums::Sums(_) => {
+				bail!(Error::ExpectedExecution);
+			}
+		}
+
+		// Fold the multilinears into newly allocated buffers.
+		let folded_multilins = self
+			.multilins
+			.iter()
+			.map(|_| self.dev_alloc.alloc(1 << (self.n_vars_remaining - 1)))
+			.collect::<Result<Vec<_>, _>>()?;
+		let mut multilins = Vec::new();
+		let _ = self.hal.execute(|exec| {
+			multilins = exec.map(
+				iter::zip(&self.multilins, folded_multilins),
+				|exec, (&evals, mut folded_evals)| {
+					exec.fold_multilinear(evals, challenge, &mut folded_evals)?;
+					Ok(Hal::DevMem::to_const(folded_evals))
+				},
+			)?;
+			Ok(Vec::new())
+		})?;
+		self.multilins = multilins;
+
+		self.n_vars_remaining -= 1;
+		Ok(())
+	}
+
+	fn finish(self: Box<Self>) -> Result<Vec<F>, Error> {
+		match self.last_coeffs_or_sums {
+			CoeffsOrSums::Coeffs(_) => {
+				bail!(Error::ExpectedFold);
+			}
+			CoeffsOrSums::Sums(_) => match self.n_vars_remaining {
+				0 => {}
+				_ => bail!(Error::ExpectedExecution),
+			},
+		};
+
+		// Copy the fully folded multilinear evaluations to the host.
+		let buffer = self.host_alloc.alloc(self.multilins.len())?;
+		for (&multilin, dst_i) in iter::zip(&self.multilins, &mut *buffer) {
+			debug_assert_eq!(multilin.len(), 1);
+			self.hal.copy_d2h(multilin, slice::from_mut(dst_i))?;
+		}
+		Ok(buffer.to_vec())
+	}
+}
+
+/// Calculates the evaluations of the round polynomials of a sumcheck over compositions of
+/// partially specialized multilinear polynomials.
+///
+/// This performs round evaluation for a sumcheck prover over arbitrary compositions of
+/// multilinear polynomials, defined over the same field as the sumcheck challenges, using
+/// high-to-low variable binding order.
+///
+/// For a composition of degree $d$, the round polynomial is evaluated at $d$ points: 1, then, if
+/// $d \ge 2$, the "infinity" point, followed by the first $d - 2$ of the nontrivial evaluation
+/// points. This is the order expected by [`binius_hal::ComputationBackend`] round evaluations.
+/// The meaning of the infinity evaluation point is described in the documentation of
+/// [`binius_math::EvaluationDomain`]; its evaluation is the sum of the leading homogeneous
+/// component of the composition over the differences of the multilinear halves.
+///
+/// ## Returns
+///
+/// Returns the summed evaluations of each composition at its evaluation points.
+pub fn calculate_round_evals<F, Hal, Composition>(
+	hal: &Hal,
+	n_vars: usize,
+	multilins: &[FSlice<'_, F, Hal>],
+	compositions: &[Composition],
+	nontrivial_evaluation_points: &[F],
+) -> Result<Vec<Vec<F>>, Error>
+where
+	F: TowerField,
+	Hal: ComputeLayer<F>,
+	Composition: CompositionPoly<F>,
+{
+	let degrees = compositions
+		.iter()
+		.map(|composition| composition.degree())
+		.collect::<Vec<_>>();
+	let max_degree = degrees.iter().copied().max().unwrap_or(0);
+	if nontrivial_evaluation_points.len() < max_degree.saturating_sub(2) {
+		bail!(Error::IncorrectSumcheckEvaluationDomain);
+	}
+
+	let evaluators = compositions
+		.iter()
+		.map(|composition| hal.compile_expr(&composition.expression()))
+		.collect::<Result<Vec<_>, _>>()?;
+	let evaluators_at_infinity = compositions
+		.iter()
+		.map(|composition| hal.compile_expr(&composition.expression().leading_term()))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	// n_vars - 1 is the number of variables in the halves of the split multilinear.
+	let split_n_vars = n_vars - 1;
+	let n_multilins = multilins.len();
+	let (multilins_0, multilins_1): (Vec<_>, Vec<_>) = multilins
+		.iter()
+		.map(|&multilin| Hal::DevMem::split_half(multilin))
+		.unzip();
+	// Evaluations of the multilinears at the infinity point are only needed for compositions of
+	// degree at least 2.
+	let n_infinity_buffers = if max_degree >= 2 { n_multilins } else { 0 };
+	let kernel_mappings = chain!(multilins_0, multilins_1)
+		.map(|data| KernelMemMap::Chunked {
+			data,
+			log_min_chunk_size: 0,
+		})
+		.chain(
+			iter::repeat_with(|| KernelMemMap::Local {
+				log_size: split_n_vars,
+			})
+			.take(n_infinity_buffers),
+		)
+		.collect();
+
+	let evals = hal.execute(|exec| {
+		exec.accumulate_kernels(
+			|local_exec, log_chunks, mut buffers| {
+				let log_chunk_size = split_n_vars - log_chunks;
+
+				let (inputs, evals_inf) = buffers.split_at_mut(2 * n_multilins);
+				let (inputs_0, inputs_1) = inputs.split_at(n_multilins);
+				let evals_0 = SlicesBatch::new(
+					inputs_0.iter().map(|buffer| buffer.to_ref()).collect(),
+					1 << log_chunk_size,
+				);
+				let evals_1 = SlicesBatch::new(
+					inputs_1.iter().map(|buffer| buffer.to_ref()).collect(),
+					1 << log_chunk_size,
+				);
+
+				// Extrapolate the multilinear evaluations at the point Infinity.
+				for (&eval_0, &eval_1, eval_inf) in
+					izip!(evals_0.iter(), evals_1.iter(), evals_inf.iter_mut())
+				{
+					let KernelBuffer::Mut(eval_inf) = eval_inf else {
+						panic!(
+							"exec_kernels did not create the mapped buffers struct according to the mapping"
+						);
+					};
+					local_exec.add(log_chunk_size, eval_0, eval_1, eval_inf)?;
+				}
+				let evals_inf = SlicesBatch::new(
+					evals_inf.iter().map(|buffer| buffer.to_ref()).collect(),
+					1 << log_chunk_size,
+				);
+
+				let mut round_evals = Vec::new();
+				for (&degree, evaluator, evaluator_at_infinity) in
+					izip!(&degrees, &evaluators, &evaluators_at_infinity)
+				{
+					let mut acc_1 = local_exec.decl_value(F::ZERO)?;
+					local_exec.sum_composition_evals(&evals_1, evaluator, F::ONE, &mut acc_1)?;
+					round_evals.push(acc_1);
+
+					if degree >= 2 {
+						let mut acc_inf = local_exec.decl_value(F::ZERO)?;
+						local_exec.sum_composition_evals(
+							&evals_inf,
+							evaluator_at_infinity,
+							F::ONE,
+							&mut acc_inf,
+						)?;
+						round_evals.push(acc_inf);
+					}
+
+					for &point in &nontrivial_evaluation_points[..degree.saturating_sub(2)] {
+						let mut acc = local_exec.decl_value(F::ZERO)?;
+						local_exec.sum_composition_evals_at_point(
+							&evals_0,
+							&evals_1,
+							point,
+							evaluator,
+							F::ONE,
+							&mut acc,
+						)?;
+						round_evals.push(acc);
+					}
+				}
+				Ok(round_evals)
+			},
+			kernel_mappings,
+		)
+	})?;
+
+	let mut evals = evals.into_iter();
+	let round_evals = degrees
+		.iter()
+		.map(|&degree| evals.by_ref().take(degree.max(1)).collect())
+		.collect();
+	Ok(round_evals)
+}
+
+#[derive(Debug)]
+enum CoeffsOrSums<F: Field> {
+	Coeffs(Vec<RoundCoeffs<F>>),
+	Sums(Vec<F>),
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use binius_compute::cpu::CpuLayer;
+	use binius_fast_compute::{arith_circuit::ArithCircuitPoly, layer::FastCpuLayer};
+	use binius_field::{
+		BinaryField8b, BinaryField128b, PackedBinaryField1x128b, arch::OptimalUnderlier,
+		as_packed_field::PackedType, tower::CanonicalTowerFamily,
+	};
+	use binius_hal::make_portable_backend;
+	use binius_hash::groestl::Groestl256;
+	use binius_math::{
+		ArithCircuit, IsomorphicEvaluationDomainFactory, MLEDirectAdapter, MultilinearExtension,
+		MultilinearPoly, MultilinearQuery,
+	};
+	use bytemuck::{must_cast_slice, zeroed_vec};
+	use rand::{SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::{
+		fiat_shamir::HasherChallenger,
+		protocols::sumcheck::{
+			self, BatchSumcheckOutput, immediate_switchover_heuristic, prove::RegularSumcheckProver,
+		},
+		transcript::ProverTranscript,
+	};
+
+	type F = BinaryField128b;
+	type FDomain = BinaryField8b;
+
+	/// Proves a sumcheck over compositions of degrees 1 to 4 with the compute layer and with the
+	/// portable backend, checking that the transcripts and outputs agree.
+	fn test_compare_with_regular_prover<Hal: ComputeLayer<F>>(
+		hal: &Hal,
+		dev_mem: <Hal::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+		n_vars: usize,
+	) {
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_multilins = 4;
+
+		let evals = repeat_with(|| {
+			repeat_with(|| <F as Field>::random(&mut rng))
+				.take(1 << n_vars)
+				.collect::<Vec<_>>()
+		})
+		.take(n_multilins)
+		.collect::<Vec<_>>();
+		let multilins = evals
+			.iter()
+			.map(|evals| {
+				let mle = MultilinearExtension::new(
+					n_vars,
+					must_cast_slice::<_, PackedBinaryField1x128b>(evals),
+				)
+				.unwrap();
+				MLEDirectAdapter::from(mle)
+			})
+			.collect::<Vec<_>>();
+
+		let var = ArithCircuit::<F>::var;
+		let compositions = [
+			var(0) + var(3),
+			var(0) * var(1) + var(2),
+			var(1) * var(2) * var(3) + var(0) * var(0),
+			var(0) * var(1) * var(2) * var(3) + var(1),
+		]
+		.map(ArithCircuitPoly::new);
+		let composite_sums = compositions
+			.into_iter()
+			.map(|composition| {
+				let sum = (0..1 << n_vars)
+					.map(|i| {
+						let row = evals.iter().map(|evals| evals[i]).collect::<Vec<_>>();
+						CompositionPoly::<F>::evaluate(&composition, &row).unwrap()
+					})
+					.sum();
+				CompositeSumClaim { composition, sum }
+			})
+			.collect::<Vec<_>>();
+		let claim = SumcheckClaim::new(n_vars, n_multilins, composite_sums.clone()).unwrap();
+
+		let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+		let backend = make_portable_backend();
+		let regular_prover = RegularSumcheckProver::<FDomain, _, _, _, _>::new(
+			EvaluationOrder::HighToLow,
+			multilins.clone(),
+			composite_sums,
+			domain_factory.clone(),
+			immediate_switchover_heuristic,
+			&backend,
+		)
+		.unwrap();
+		let mut transcript_1 = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let output_1 = sumcheck::batch_prove(vec![regular_prover], &mut transcript_1).unwrap();
+
+		type Prover<'a, 'alloc, Hal> =
+			CompositeSumcheckProver<'a, 'alloc, F, FDomain, Hal, ArithCircuitPoly<F>>;
+		let mut host_mem = zeroed_vec(Prover::<Hal>::required_host_memory(&claim));
+		let host_alloc = HostBumpAllocator::new(host_mem.as_mut_slice());
+		let dev_alloc = BumpAllocator::<F, Hal::DevMem>::new(dev_mem);
+		let dev_multilins = evals
+			.iter()
+			.map(|evals| {
+				let mut dev_multilin = dev_alloc.alloc(evals.len()).unwrap();
+				hal.copy_h2d(evals, &mut dev_multilin).unwrap();
+				Hal::DevMem::to_const(dev_multilin)
+			})
+			.collect::<Vec<_>>();
+		assert!(dev_alloc.capacity() >= Prover::<Hal>::required_device_memory(&claim));
+
+		let prover =
+			Prover::new(hal, &dev_alloc, &host_alloc, &claim, dev_multilins, domain_factory)
+				.unwrap();
+		let mut transcript_2 = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let output_2 = sumcheck::batch_prove(vec![prover], &mut transcript_2).unwrap();
+
+		assert_eq!(transcript_1.finalize(), transcript_2.finalize());
+		assert_eq!(output_1.challenges, output_2.challenges);
+		assert_eq!(output_1.multilinear_evals, output_2.multilinear_evals);
+
+		let BatchSumcheckOutput {
+			mut challenges,
+			multilinear_evals,
+		} = output_2;
+		challenges.reverse(); // Reverse challenges because of high-to-low variable binding
+		let query = MultilinearQuery::expand(&challenges);
+		for (multilin, &eval) in iter::zip(multilins, &multilinear_evals[0]) {
+			assert_eq!(multilin.evaluate(query.to_ref()).unwrap(), eval);
+		}
+	}
+
+	#[test]
+	fn test_composite_sumcheck_compare_with_regular_prover() {
+		let hal = <CpuLayer<F>>::default();
+		for n_vars in 1..8 {
+			let mut dev_mem = zeroed_vec(1 << (n_vars + 3));
+			test_compare_with_regular_prover(&hal, dev_mem.as_mut_slice(), n_vars);
+		}
+	}
+
+	#[test]
+	fn test_composite_sumcheck_compare_with_regular_prover_fast() {
+		type Packed = PackedType<OptimalUnderlier, F>;
+		type Hal = FastCpuLayer<CanonicalTowerFamily, Packed>;
+
+		let hal = Hal::default();
+		for n_vars in 1..8 {
+			let mut dev_mem = zeroed_vec(1 << (n_vars + 5 - Packed::LOG_WIDTH));
+			let dev_mem =
+				<<Hal as ComputeLayer<F>>::DevMem as ComputeMemory<F>>::FSliceMut::new_slice(
+					&mut dev_mem,
+				);
+			test_compare_with_regular_prover(&hal, dev_mem, n_vars);
+		}
+	}
+}
@@ -9,3 +9,4 @@
 
 pub mod bivariate_mlecheck;
 pub mod bivariate_product;
+pub mod composite;
@@ -2,7 +2,8 @@
 
 use binius_field::{Field, TowerField};
 use binius_math::{CompositionPoly, EvaluationOrder, evaluate_univariate};
-use binius_utils::{bail, sorting::is_sorted_ascending};
+use binius_utils::{SerializationError, bail, sorting::is_sorted_ascending};
+use bytes::Buf;
 use itertools::izip;
 
 use super::{
@@ -12,7 +13,7 @@ use super::{
 };
 use crate::{
 	fiat_shamir::{CanSample, Challenger},
-	transcript::VerifierTranscript,
+	transcript::{self, TranscriptReader, VerifierTranscript},
 };
 
 /// Verify a batched sumcheck protocol execution.
@@ -74,8 +75,7 @@ where
 			active_index += 1;
 		}
 
-		let coeffs = transcript.message().read_scalar_slice(max_degree)?;
-		let round_proof = RoundProof(RoundCoeffs(coeffs));
+		let round_proof = read_round_proof(&mut transcript.message(), round_no, max_degree)?;
 
 		let challenge = transcript.sample();
 		challenges.push(challenge);
@@ -167,3 +167,25 @@ pub fn interpolate_round_proof<F: Field>(round_proof: RoundProof<F>, sum: F, cha
 	let coeffs = round_proof.recover(sum);
 	evaluate_univariate(&coeffs.0, challenge)
 }
+
+/// Reads the truncated round polynomial of a sumcheck round from the transcript.
+///
+/// A proof that ends before the round message is reported with the round number, so that a
+/// prover that runs fewer rounds than the verifier expects can be told apart from other
+/// transcript errors.
+pub(super) fn read_round_proof<F: TowerField, B: Buf>(
+	transcript: &mut TranscriptReader<B>,
+	round: usize,
+	degree: usize,
+) -> Result<RoundProof<F>, Error> {
+	let coeffs = transcript
+		.read_scalar_slice(degree)
+		.map_err(|err| match err {
+			transcript::Error::NotEnoughBytes
+			| transcript::Error::Serialization(SerializationError::NotEnoughBytes) => {
+				VerificationError::MissingRoundProof { round }.into()
+			}
+			err => Error::from(err),
+		})?;
+	Ok(RoundProof(RoundCoeffs(coeffs)))
+}
@@ -0,0 +1,334 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Masking of sumcheck round messages for claims involving secret columns.
+//!
+//! The round messages of a sumcheck over secret multilinears leak partial sums of their
+//! compositions. Following [CFS17] and [XZZ+19], the prover samples a random masking polynomial
+//! $g$, the verifier learns its sum $\sigma_g$ over the hypercube, and the two run the sumcheck on
+//! the claim extended with $g$ as an extra composite, batched with the next power of the batching
+//! coefficient. Each round message then contains a random univariate polynomial of full degree,
+//! hiding the round polynomials of the original claim. At the end, the prover reports the
+//! evaluation $g(r)$ alongside the multilinear evaluations.
+//!
+//! In characteristic two, any sum of univariates sums to zero over all but one variable of the
+//! hypercube, so the usual mask $\sum_i g_i(X_i)$ would only blind the last round. We use instead
+//!
+//! $$
+//! g(X) = \sum_{i=0}^{n-1} g_i(X_i) \prod_{j \ne i} X_j,
+//! $$
+//!
+//! whose round polynomial in the variable $X_i$ is $g_i$ scaled by the product of the challenges
+//! so far, plus a linear term.
+//!
+//! The evaluation $g(r)$ is the inner product of the coefficients of the $g_i$ with the public
+//! weights returned by [`MaskingPolynomial::eval_weights`]. The prover commits the coefficients
+//! alongside the witness and the claimed $g(r)$ is proven as a PIOP sumcheck claim against those
+//! weights.
+//!
+//! The mask only hides the round messages: the prover still reports the evaluations of the
+//! multilinears of the claim at the challenge point. Secret multilinears must therefore be blinded
+//! before the sumcheck, which the constraint system prover does by extending each secret column
+//! with one variable of random evaluations and restricting the claim to the original half, and the
+//! commitment must be hiding. Claims without secret columns should use the unmasked sumcheck.
+//!
+//! [CFS17]: <https://eprint.iacr.org/2017/305>
+//! [XZZ+19]: <https://eprint.iacr.org/2019/317>
+
+use binius_field::{Field, PackedField};
+use binius_math::{ArithCircuit, CompositionPoly, EvaluationOrder, evaluate_univariate};
+use binius_utils::bail;
+use rand::RngCore;
+
+use super::{
+	common::{CompositeSumClaim, RoundCoeffs, SumcheckClaim},
+	error::Error,
+	prove::SumcheckProver,
+};
+
+/// A random masking polynomial $g(X) = \sum_i g_i(X_i) \prod_{j \ne i} X_j$.
+#[derive(Debug, Clone)]
+pub struct MaskingPolynomial<F: Field> {
+	/// The coefficients of each univariate $g_i$, in ascending order by degree.
+	coeffs: Vec<Vec<F>>,
+}
+
+impl<F: Field> MaskingPolynomial<F> {
+	/// Samples a masking polynomial over `n_vars` variables, with univariates of the given degree.
+	///
+	/// The degree must equal the maximum individual degree of the masked claim, so that the masked
+	/// round messages have the same length as the original ones.
+	pub fn random(n_vars: usize, degree: usize, mut rng: impl RngCore) -> Self {
+		let coeffs = (0..n_vars)
+			.map(|_| (0..=degree).map(|_| F::random(&mut rng)).collect())
+			.collect();
+		Self { coeffs }
+	}
+
+	pub fn n_vars(&self) -> usize {
+		self.coeffs.len()
+	}
+
+	pub fn degree(&self) -> usize {
+		self.coeffs.first().map_or(0, |coeffs| coeffs.len() - 1)
+	}
+
+	/// The coefficients of all univariates, concatenated in order by variable.
+	pub fn coeffs(&self) -> Vec<F> {
+		self.coeffs.concat()
+	}
+
+	/// The sum of the masking polynomial over the hypercube.
+	pub fn sum(&self) -> F {
+		// Only the points with all but at most one coordinate equal to one contribute.
+		self.coeffs
+			.iter()
+			.map(|coeffs| {
+				evaluate_univariate(coeffs, F::ZERO) + evaluate_univariate(coeffs, F::ONE)
+			})
+			.sum()
+	}
+
+	/// Evaluates the masking polynomial at a point.
+	pub fn evaluate(&self, point: &[F]) -> Result<F, Error> {
+		Ok(self
+			.eval_weights(point)?
+			.into_iter()
+			.zip(self.coeffs())
+			.map(|(weight, coeff)| weight * coeff)
+			.sum())
+	}
+
+	/// The weights of the coefficients in the evaluation at a point.
+	///
+	/// The evaluation at `point` is the inner product of [`Self::coeffs`] with the returned
+	/// vector, so the verifier can check a claimed evaluation against committed coefficients.
+	pub fn eval_weights(&self, point: &[F]) -> Result<Vec<F>, Error> {
+		if point.len() != self.n_vars() {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+		Ok(mask_eval_weights(self.degree(), point))
+	}
+
+	/// Computes the round polynomial in the variable `var`, with the variables in `bound` bound to
+	/// their challenges and the others summed over the hypercube.
+	fn round_coeffs(&self, bound: &[(usize, F)], var: usize) -> RoundCoeffs<F> {
+		let bound_product = bound.iter().map(|&(_, challenge)| challenge).product::<F>();
+
+		let mut linear = F::ZERO;
+		for (i, coeffs) in self.coeffs.iter().enumerate() {
+			if i == var {
+				continue;
+			}
+			if let Some(&(_, challenge)) = bound.iter().find(|&&(j, _)| j == i) {
+				linear += evaluate_univariate(coeffs, challenge)
+					* product_excluding(bound.iter().copied(), i);
+			} else {
+				linear += bound_product
+					* (evaluate_univariate(coeffs, F::ZERO) + evaluate_univariate(coeffs, F::ONE));
+			}
+		}
+
+		let mut round_coeffs = RoundCoeffs(
+			self.coeffs[var]
+				.iter()
+				.map(|&coeff| coeff * bound_product)
+				.collect(),
+		);
+		round_coeffs += &RoundCoeffs(vec![F::ZERO, linear]);
+		round_coeffs
+	}
+}
+
+/// The weights of the coefficients of a masking polynomial of the given degree in its evaluation
+/// at a point, as returned by [`MaskingPolynomial::eval_weights`].
+///
+/// The weights do not depend on the coefficients, so the verifier computes them from the degree.
+pub fn mask_eval_weights<F: Field>(degree: usize, point: &[F]) -> Vec<F> {
+	(0..point.len())
+		.flat_map(|i| {
+			let others = product_excluding(point.iter().copied().enumerate(), i);
+			std::iter::successors(Some(others), move |&weight| Some(weight * point[i]))
+				.take(degree + 1)
+		})
+		.collect()
+}
+
+fn product_excluding<F: Field>(values: impl Iterator<Item = (usize, F)>, excluded: usize) -> F {
+	values
+		.filter(|&(j, _)| j != excluded)
+		.map(|(_, value)| value)
+		.product()
+}
+
+/// A composition of a masked sumcheck claim.
+///
+/// The masked claim has one more multilinear than the original claim, whose evaluation stands for
+/// the evaluation of the masking polynomial.
+#[derive(Debug, Clone)]
+pub enum MaskedComposition<C> {
+	/// A composition of the original claim, over all but the last multilinear.
+	Inner(C),
+	/// The masking polynomial, as the last of `n_vars` multilinears.
+	Mask { n_vars: usize },
+}
+
+impl<P: PackedField, C: CompositionPoly<P>> CompositionPoly<P> for MaskedComposition<C> {
+	fn n_vars(&self) -> usize {
+		match self {
+			Self::Inner(composition) => composition.n_vars() + 1,
+			Self::Mask { n_vars } => *n_vars,
+		}
+	}
+
+	fn degree(&self) -> usize {
+		match self {
+			Self::Inner(composition) => composition.degree(),
+			Self::Mask { .. } => 1,
+		}
+	}
+
+	fn expression(&self) -> ArithCircuit<P::Scalar> {
+		match self {
+			Self::Inner(composition) => composition.expression(),
+			Self::Mask { n_vars } => ArithCircuit::var(n_vars - 1),
+		}
+	}
+
+	fn evaluate(&self, query: &[P]) -> Result<P, binius_math::Error> {
+		let n_vars = CompositionPoly::<P>::n_vars(self);
+		if query.len() != n_vars {
+			bail!(binius_math::Error::IncorrectQuerySize {
+				expected: n_vars,
+				actual: query.len(),
+			});
+		}
+		match self {
+			Self::Inner(composition) => composition.evaluate(&query[..n_vars - 1]),
+			Self::Mask { .. } => Ok(query[n_vars - 1]),
+		}
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		match self {
+			Self::Inner(composition) => composition.binary_tower_level(),
+			Self::Mask { .. } => 0,
+		}
+	}
+}
+
+/// Extends a sumcheck claim with a masking polynomial whose sum over the hypercube is `mask_sum`.
+///
+/// The returned claim is verified with the usual sumcheck verifier, against a
+/// [`MaskedSumcheckProver`]. The last multilinear evaluation of the output is the claimed
+/// evaluation of the masking polynomial.
+pub fn mask_claim<F, C>(
+	claim: &SumcheckClaim<F, C>,
+	mask_sum: F,
+) -> Result<SumcheckClaim<F, MaskedComposition<C>>, Error>
+where
+	F: Field,
+	C: CompositionPoly<F> + Clone,
+{
+	let n_multilinears = claim.n_multilinears() + 1;
+	let composite_sums = claim
+		.composite_sums()
+		.iter()
+		.map(|composite_sum| CompositeSumClaim {
+			composition: MaskedComposition::Inner(composite_sum.composition.clone()),
+			sum: composite_sum.sum,
+		})
+		.chain(std::iter::once(CompositeSumClaim {
+			composition: MaskedComposition::Mask {
+				n_vars: n_multilinears,
+			},
+			sum: mask_sum,
+		}))
+		.collect();
+	SumcheckClaim::new(claim.n_vars(), n_multilinears, composite_sums)
+}
+
+/// A sumcheck prover that masks the round messages of an inner prover.
+///
+/// The inner prover proves the composites of the claim passed to [`mask_claim`], and the masking
+/// polynomial is mixed in as one extra composite. The final evaluations of the inner multilinears
+/// are returned as the inner prover computes them, so they hide the witness only if the inner
+/// multilinears are blinded, as described in the [module documentation](self).
+#[derive(Debug)]
+pub struct MaskedSumcheckProver<F: Field, Prover> {
+	inner: Prover,
+	mask: MaskingPolynomial<F>,
+	n_composites: usize,
+	bound: Vec<(usize, F)>,
+}
+
+impl<F: Field, Prover: SumcheckProver<F>> MaskedSumcheckProver<F, Prover> {
+	/// Wraps a prover for a claim with `n_composites` composite sums.
+	pub fn new(
+		inner: Prover,
+		n_composites: usize,
+		mask: MaskingPolynomial<F>,
+	) -> Result<Self, Error> {
+		if mask.n_vars() != inner.n_vars() {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+		Ok(Self {
+			inner,
+			mask,
+			n_composites,
+			bound: Vec::new(),
+		})
+	}
+
+	fn current_var(&self) -> usize {
+		let round = self.bound.len();
+		match self.inner.evaluation_order() {
+			EvaluationOrder::LowToHigh => round,
+			EvaluationOrder::HighToLow => self.mask.n_vars() - 1 - round,
+		}
+	}
+}
+
+impl<F: Field, Prover: SumcheckProver<F>> SumcheckProver<F> for MaskedSumcheckProver<F, Prover> {
+	fn n_vars(&self) -> usize {
+		self.inner.n_vars()
+	}
+
+	fn evaluation_order(&self) -> EvaluationOrder {
+		self.inner.evaluation_order()
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		let mut round_coeffs = self.inner.execute(batch_coeff)?;
+		let mask_coeffs = self.mask.round_coeffs(&self.bound, self.current_var());
+		round_coeffs += &(mask_coeffs * batch_coeff.pow(self.n_composites as u64));
+		Ok(round_coeffs)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		self.inner.fold(challenge)?;
+		let var = self.current_var();
+		self.bound.push((var, challenge));
+		Ok(())
+	}
+
+	fn prepare_fold(&mut self) -> Result<(), Error> {
+		self.inner.prepare_fold()
+	}
+
+	fn finish(self: Box<Self>) -> Result<Vec<F>, Error> {
+		let Self {
+			inner,
+			mask,
+			mut bound,
+			..
+		} = *self;
+		let mut multilinear_evals = Box::new(inner).finish()?;
+		bound.sort_unstable_by_key(|&(var, _)| var);
+		let point = bound
+			.into_iter()
+			.map(|(_, challenge)| challenge)
+			.collect::<Vec<_>>();
+		multilinear_evals.push(mask.evaluate(&point)?);
+		Ok(multilinear_evals)
+	}
+}
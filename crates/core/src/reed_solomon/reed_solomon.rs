@@ -7,7 +7,8 @@
 use binius_field::{BinaryField, ExtensionField, PackedExtension, PackedField};
 use binius_math::BinarySubspace;
 use binius_ntt::{AdditiveNTT, NTTShape, SingleThreadedNTT};
-use binius_utils::bail;
+use binius_utils::{DeserializeBytes, SerializationError, SerializationMode, SerializeBytes, bail};
+use bytes::{Buf, BufMut};
 use getset::{CopyGetters, Getters};
 
 use super::error::Error;
@@ -156,6 +157,61 @@ impl<F: BinaryField> ReedSolomonCode<F> {
 		Ok(())
 	}
 
+	/// Encode one coset of a batch of interleaved messages in-place in a provided buffer.
+	///
+	/// The buffer holds the interleaved message and is overwritten with the coset at index `coset`
+	/// of the codeword output by [`Self::encode_batch_inplace`].
+	///
+	/// ## Throws
+	///
+	/// * If the `code` buffer does not have capacity for `dim() << log_batch_size` field elements.
+	/// * If `coset` is not less than `inv_rate()`.
+	fn encode_batch_coset_inplace<P: PackedField<Scalar = F>, NTT: AdditiveNTT<F> + Sync>(
+		&self,
+		ntt: &NTT,
+		code: &mut [P],
+		coset: usize,
+		log_batch_size: usize,
+	) -> Result<(), Error> {
+		if ntt.subspace(ntt.log_domain_size() - self.log_len()) != self.subspace {
+			bail!(Error::EncoderSubspaceMismatch);
+		}
+		let expected_buffer_len =
+			1 << (self.log_dim() + log_batch_size).saturating_sub(P::LOG_WIDTH);
+		if code.len() != expected_buffer_len {
+			bail!(Error::IncorrectBufferLength {
+				expected: expected_buffer_len,
+				actual: code.len(),
+			});
+		}
+
+		let _scope = tracing::trace_span!(
+			"Reed–Solomon encode coset",
+			log_dim = self.log_dim(),
+			log_batch_size = log_batch_size,
+			symbol_bits = F::N_BITS,
+		)
+		.entered();
+
+		// If the message is less than the packing width, repeat it to fill one packed element, as
+		// in `encode_batch_inplace`.
+		if self.log_dim() + log_batch_size < P::LOG_WIDTH {
+			let repeated_values = code[0]
+				.into_iter()
+				.take(1 << (self.log_dim() + log_batch_size))
+				.cycle();
+			code[0] = P::from_scalars(repeated_values);
+		}
+
+		let shape = NTTShape {
+			log_x: log_batch_size,
+			log_y: self.log_dim(),
+			..Default::default()
+		};
+		ntt.forward_transform(code, shape, coset, self.log_inv_rate, 0)?;
+		Ok(())
+	}
+
 	/// Encode a batch of interleaved messages of extension field elements in-place in a provided
 	/// buffer.
 	///
@@ -181,4 +237,65 @@ impl<F: BinaryField> ReedSolomonCode<F> {
 			log_batch_size + PE::Scalar::LOG_DEGREE,
 		)
 	}
+
+	/// Encode one coset of a batch of interleaved messages of extension field elements in-place in
+	/// a provided buffer.
+	///
+	/// The interleaved codeword output by [`Self::encode_ext_batch_inplace`] is the concatenation
+	/// of `inv_rate()` cosets, each with the length of the interleaved message. This method takes
+	/// the interleaved message in the buffer and overwrites it with the coset at index `coset`, so
+	/// that the cosets of a codeword can be encoded independently of each other.
+	///
+	/// ## Preconditions
+	///
+	/// * `PE::Scalar::DEGREE` must be a power of two.
+	///
+	/// ## Throws
+	///
+	/// * If the `code` buffer does not have capacity for `dim() << log_batch_size` field elements.
+	/// * If `coset` is not less than `inv_rate()`.
+	pub fn encode_ext_batch_coset_inplace<PE: PackedExtension<F>, NTT: AdditiveNTT<F> + Sync>(
+		&self,
+		ntt: &NTT,
+		code: &mut [PE],
+		coset: usize,
+		log_batch_size: usize,
+	) -> Result<(), Error> {
+		self.encode_batch_coset_inplace(
+			ntt,
+			PE::cast_bases_mut(code),
+			coset,
+			log_batch_size + PE::Scalar::LOG_DEGREE,
+		)
+	}
+}
+
+impl<F: BinaryField> SerializeBytes for ReedSolomonCode<F> {
+	fn serialize(
+		&self,
+		mut write_buf: impl BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		SerializeBytes::serialize(self.subspace.basis(), &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.log_dimension, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.log_inv_rate, write_buf, mode)
+	}
+}
+
+impl<F: BinaryField> DeserializeBytes for ReedSolomonCode<F> {
+	fn deserialize(
+		mut read_buf: impl Buf,
+		mode: SerializationMode,
+	) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let basis: Vec<F> = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let log_dimension = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let log_inv_rate = DeserializeBytes::deserialize(read_buf, mode)?;
+		Self::with_subspace(BinarySubspace::new_unchecked(basis), log_dimension, log_inv_rate)
+			.map_err(|_| SerializationError::InvalidConstruction {
+				name: "ReedSolomonCode",
+			})
+	}
 }
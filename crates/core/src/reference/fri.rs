@@ -0,0 +1,181 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Reference implementation of FRI folding.
+//!
+//! The FRI verifier checks each queried coset of an oracle against the folded value in the next
+//! oracle. The functions here compute the folds directly from the definitions in [DP24], Def. 3.6
+//! and Lemma 3.9, without the in-place chunked evaluation of [`binius_ntt::fri`].
+//!
+//! [DP24]: <https://eprint.iacr.org/2024/504>
+
+use binius_field::{BinaryField, ExtensionField, Field};
+use binius_ntt::AdditiveNTT;
+
+/// FRI-folds an interleaved codeword using the given challenges.
+///
+/// The first `log_batch_size` challenges combine the interleaved codewords with the coefficients
+/// of the multilinear equality indicator, and each remaining challenge folds the combined codeword
+/// in half. This computes the same result as [`binius_ntt::fri::fold_interleaved`].
+///
+/// ## Panics
+///
+/// * if `codeword.len()` is not `2^(log_len + log_batch_size)`
+/// * if there are fewer than `log_batch_size` or more than `log_len + log_batch_size` challenges
+pub fn fold_interleaved<F, FS, NTT>(
+	ntt: &NTT,
+	codeword: &[F],
+	challenges: &[F],
+	log_len: usize,
+	log_batch_size: usize,
+) -> Vec<F>
+where
+	F: BinaryField + ExtensionField<FS>,
+	FS: BinaryField,
+	NTT: AdditiveNTT<FS>,
+{
+	assert_eq!(codeword.len(), 1 << (log_len + log_batch_size));
+	assert!(challenges.len() >= log_batch_size);
+	assert!(challenges.len() <= log_len + log_batch_size);
+
+	let (interleave_challenges, fold_challenges) = challenges.split_at(log_batch_size);
+	let combined = codeword
+		.chunks(1 << log_batch_size)
+		.map(|values| {
+			values
+				.iter()
+				.enumerate()
+				.map(|(j, &value)| eq_ind(interleave_challenges, j) * value)
+				.sum()
+		})
+		.collect::<Vec<F>>();
+	fold(ntt, log_len, 0, combined, fold_challenges)
+}
+
+/// FRI-folds a coset of a (non-interleaved) codeword into a single value.
+///
+/// The coset is the `coset_index`-th chunk of size `2^challenges.len()` of a codeword of length
+/// `2^log_len`. This computes the same result as [`binius_ntt::fri::fold_chunk`].
+///
+/// ## Panics
+///
+/// * if `values.len()` is not `2^challenges.len()`
+/// * if there are more than `log_len` challenges
+pub fn fold_coset<F, FS, NTT>(
+	ntt: &NTT,
+	log_len: usize,
+	coset_index: usize,
+	values: &[F],
+	challenges: &[F],
+) -> F
+where
+	F: BinaryField + ExtensionField<FS>,
+	FS: BinaryField,
+	NTT: AdditiveNTT<FS>,
+{
+	assert_eq!(values.len(), 1 << challenges.len());
+	assert!(challenges.len() <= log_len);
+
+	let folded = fold(ntt, log_len, coset_index, values.to_vec(), challenges);
+	folded[0]
+}
+
+/// Folds the chunk with the given index of a codeword of length `2^log_len` once per challenge.
+fn fold<F, FS, NTT>(
+	ntt: &NTT,
+	mut log_len: usize,
+	chunk_index: usize,
+	mut values: Vec<F>,
+	challenges: &[F],
+) -> Vec<F>
+where
+	F: BinaryField + ExtensionField<FS>,
+	FS: BinaryField,
+	NTT: AdditiveNTT<FS>,
+{
+	for &challenge in challenges {
+		let half_len = values.len() / 2;
+		let offset = chunk_index * half_len;
+		values = (0..half_len)
+			.map(|i| {
+				// Recover the evaluations (u, v) of the odd and even parts of the polynomial
+				// through an inverse additive NTT butterfly, then interpolate the line through
+				// them at the challenge.
+				let twiddle = ntt.get_subspace_eval(ntt.log_domain_size() - log_len, offset + i);
+				let (x0, x1) = (values[2 * i], values[2 * i + 1]);
+				let v = x0 + x1;
+				let u = x0 + v * twiddle;
+				u + challenge * (v - u)
+			})
+			.collect();
+		log_len -= 1;
+	}
+	values
+}
+
+/// Evaluates the multilinear equality indicator of the boolean point with the given index at the
+/// given point.
+fn eq_ind<F: Field>(point: &[F], index: usize) -> F {
+	point
+		.iter()
+		.enumerate()
+		.map(|(k, &coord)| {
+			if (index >> k) & 1 == 1 {
+				coord
+			} else {
+				F::ONE - coord
+			}
+		})
+		.product()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use binius_field::{BinaryField16b, BinaryField128b};
+	use binius_ntt::SingleThreadedNTT;
+	use rand::{SeedableRng, rngs::StdRng};
+
+	use super::*;
+
+	#[test]
+	fn test_reference_fold_matches_optimized() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let ntt = SingleThreadedNTT::<BinaryField16b>::new(10).unwrap();
+
+		let log_len = 8;
+		for log_batch_size in [0, 2] {
+			let codeword = repeat_with(|| BinaryField128b::random(&mut rng))
+				.take(1 << (log_len + log_batch_size))
+				.collect::<Vec<_>>();
+			let challenges = repeat_with(|| BinaryField128b::random(&mut rng))
+				.take(log_batch_size + 3)
+				.collect::<Vec<_>>();
+
+			assert_eq!(
+				fold_interleaved(&ntt, &codeword, &challenges, log_len, log_batch_size),
+				binius_ntt::fri::fold_interleaved(
+					&ntt,
+					&codeword,
+					&challenges,
+					log_len,
+					log_batch_size
+				)
+			);
+		}
+
+		let codeword = repeat_with(|| BinaryField128b::random(&mut rng))
+			.take(1 << log_len)
+			.collect::<Vec<_>>();
+		let challenges = repeat_with(|| BinaryField128b::random(&mut rng))
+			.take(2)
+			.collect::<Vec<_>>();
+		for (coset_index, coset) in codeword.chunks(4).enumerate() {
+			let mut scratch = coset.to_vec();
+			assert_eq!(
+				fold_coset(&ntt, log_len, coset_index, coset, &challenges),
+				binius_ntt::fri::fold_chunk(&ntt, log_len, coset_index, &mut scratch, &challenges)
+			);
+		}
+	}
+}
@@ -0,0 +1,148 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Reference implementation of binary Merkle tree commitments and openings.
+//!
+//! The functions here produce the same digests as
+//! [`crate::merkle_tree::BinaryMerkleTreeProver`] and accept the same authentication paths as
+//! [`crate::merkle_tree::BinaryMerkleTreeScheme`], computing them sequentially from their
+//! definitions.
+
+use binius_field::TowerField;
+use binius_hash::{PseudoCompressionFunction, hash_serialize};
+use digest::{Digest, Output, core_api::BlockSizeUser};
+
+/// Computes the digests of a layer of the Merkle tree committing to a batch of vectors.
+///
+/// The leaves are the hashes of consecutive chunks of `batch_size` elements of `data`, and each
+/// inner node is the compression of its two children. The layer at depth 0 contains the root.
+///
+/// ## Panics
+///
+/// * if `batch_size` is zero or the number of chunks is not a power of two
+/// * if `layer_depth` exceeds the depth of the tree
+pub fn layer<F, H, C>(
+	compression: &C,
+	data: &[F],
+	batch_size: usize,
+	layer_depth: usize,
+) -> Vec<Output<H>>
+where
+	F: TowerField,
+	H: Digest + BlockSizeUser,
+	C: PseudoCompressionFunction<Output<H>, 2>,
+{
+	assert!(batch_size != 0 && data.len() % batch_size == 0);
+	let n_leaves = data.len() / batch_size;
+	assert!(n_leaves.is_power_of_two());
+	let depth = n_leaves.ilog2() as usize;
+	assert!(layer_depth <= depth);
+
+	let mut digests = data
+		.chunks(batch_size)
+		.map(|values| hash_leaf::<F, H>(values))
+		.collect::<Vec<_>>();
+	for _ in layer_depth..depth {
+		digests = digests
+			.chunks(2)
+			.map(|pair| compression.compress([pair[0].clone(), pair[1].clone()]))
+			.collect();
+	}
+	digests
+}
+
+/// Computes the root of the Merkle tree committing to a batch of vectors.
+///
+/// See [`layer`].
+pub fn root<F, H, C>(compression: &C, data: &[F], batch_size: usize) -> Output<H>
+where
+	F: TowerField,
+	H: Digest + BlockSizeUser,
+	C: PseudoCompressionFunction<Output<H>, 2>,
+{
+	layer::<F, H, C>(compression, data, batch_size, 0)[0].clone()
+}
+
+/// Checks an opening of the leaf at `index` against the layer of the tree at depth
+/// `layer_depth`.
+///
+/// The branch holds the sibling digests ordered from the leaf up to, but not including, the layer,
+/// so its length determines the depth of the tree.
+pub fn verify_opening<F, H, C>(
+	compression: &C,
+	layer_digests: &[Output<H>],
+	index: usize,
+	values: &[F],
+	branch: &[Output<H>],
+) -> bool
+where
+	F: TowerField,
+	H: Digest + BlockSizeUser,
+	C: PseudoCompressionFunction<Output<H>, 2>,
+{
+	let mut digest = hash_leaf::<F, H>(values);
+	let mut index = index;
+	for sibling in branch {
+		digest = if index % 2 == 0 {
+			compression.compress([digest, sibling.clone()])
+		} else {
+			compression.compress([sibling.clone(), digest])
+		};
+		index /= 2;
+	}
+	layer_digests.get(index) == Some(&digest)
+}
+
+fn hash_leaf<F: TowerField, H: Digest + BlockSizeUser>(values: &[F]) -> Output<H> {
+	hash_serialize::<F, H>(values)
+		.expect("values are of TowerField type which we expect to be serializable")
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use binius_field::{BinaryField16b, Field};
+	use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
+	use rand::{SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver};
+
+	#[test]
+	fn test_reference_merkle_tree_matches_optimized() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let compression = Groestl256ByteCompression;
+		let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(compression.clone());
+
+		let batch_size = 4;
+		let data = repeat_with(|| BinaryField16b::random(&mut rng))
+			.take(32 * batch_size)
+			.collect::<Vec<_>>();
+		let (commitment, tree) = merkle_prover.commit(&data, batch_size).unwrap();
+
+		assert_eq!(root::<_, Groestl256, _>(&compression, &data, batch_size), commitment.root);
+		for layer_depth in 0..=5 {
+			let layer_digests =
+				layer::<_, Groestl256, _>(&compression, &data, batch_size, layer_depth);
+			assert_eq!(layer_digests, merkle_prover.layer(&tree, layer_depth).unwrap());
+
+			for (index, values) in data.chunks(batch_size).enumerate() {
+				let branch = tree.branch(index, layer_depth).unwrap();
+				assert!(verify_opening::<_, Groestl256, _>(
+					&compression,
+					&layer_digests,
+					index,
+					values,
+					&branch
+				));
+				assert!(!verify_opening::<_, Groestl256, _>(
+					&compression,
+					&layer_digests,
+					index ^ 1,
+					values,
+					&branch
+				));
+			}
+		}
+	}
+}
@@ -0,0 +1,15 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Straightforward reference implementations of the verifiers.
+//!
+//! The implementations in this module mirror the optimized verifier-side routines of the
+//! protocols, such as [`crate::protocols::sumcheck::batch_verify`], but are written to be obviously
+//! correct rather than fast. They do not share code with the optimized implementations beyond the
+//! transcript and the basic data types, so that they can serve as oracles for differential
+//! testing and fuzzing of optimized implementations and of third-party backends.
+//!
+//! This module is only available with the `reference` feature.
+
+pub mod fri;
+pub mod merkle_tree;
+pub mod sumcheck;
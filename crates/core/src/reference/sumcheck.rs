@@ -0,0 +1,110 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Reference verifier for the batched sumcheck protocol.
+
+use binius_field::{Field, TowerField};
+use binius_math::{CompositionPoly, EvaluationOrder};
+use binius_utils::bail;
+
+use crate::{
+	fiat_shamir::{CanSample, Challenger},
+	protocols::sumcheck::{BatchSumcheckOutput, Error, SumcheckClaim, VerificationError},
+	transcript::VerifierTranscript,
+};
+
+/// Verifies a batched sumcheck protocol execution.
+///
+/// Reads the same transcript as [`crate::protocols::sumcheck::batch_verify`] and returns the same
+/// output. The claims must be in descending order by number of variables. Each claim is mixed in
+/// with a fresh batching coefficient $\beta$ sampled just before the first round over its number
+/// of variables, and its $i$-th composite sum is weighted by $\beta^{i+1}$.
+pub fn batch_verify<F, Composition, Challenger_>(
+	evaluation_order: EvaluationOrder,
+	claims: &[SumcheckClaim<F, Composition>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<BatchSumcheckOutput<F>, Error>
+where
+	F: TowerField,
+	Composition: CompositionPoly<F>,
+	Challenger_: Challenger,
+{
+	for pair in claims.windows(2) {
+		if pair[0].n_vars() < pair[1].n_vars() {
+			bail!(Error::ClaimsOutOfOrder);
+		}
+	}
+
+	let n_rounds = claims.first().map_or(0, |claim| claim.n_vars());
+
+	let mut batch_coeffs = Vec::with_capacity(claims.len());
+	let mut challenges = Vec::with_capacity(n_rounds);
+	let mut sum = F::ZERO;
+	let mut degree = 0;
+	for n_vars in (0..=n_rounds).rev() {
+		for claim in claims.iter().filter(|claim| claim.n_vars() == n_vars) {
+			let batch_coeff: F = transcript.sample();
+			for (i, composite_sum) in claim.composite_sums().iter().enumerate() {
+				sum += pow(batch_coeff, i + 1) * composite_sum.sum;
+			}
+			batch_coeffs.push(batch_coeff);
+			degree = degree.max(claim.max_individual_degree());
+		}
+
+		if n_vars == 0 {
+			break;
+		}
+
+		// The prover sends all coefficients of the round polynomial r(X) but the leading one, which
+		// is determined by the constraint r(0) + r(1) = sum.
+		let mut coeffs = transcript.message().read_scalar_slice::<F>(degree)?;
+		let r_0 = coeffs.first().copied().unwrap_or(F::ZERO);
+		let r_1_without_leading = coeffs.iter().copied().sum::<F>();
+		coeffs.push(sum - r_0 - r_1_without_leading);
+		debug_assert_eq!(evaluate(&coeffs, F::ZERO) + evaluate(&coeffs, F::ONE), sum);
+
+		let challenge: F = transcript.sample();
+		sum = evaluate(&coeffs, challenge);
+		challenges.push(challenge);
+	}
+
+	let mut multilinear_evals = Vec::with_capacity(claims.len());
+	for claim in claims {
+		multilinear_evals.push(
+			transcript
+				.message()
+				.read_scalar_slice::<F>(claim.n_multilinears())?,
+		);
+	}
+
+	let mut expected_sum = F::ZERO;
+	for ((claim, &batch_coeff), evals) in claims.iter().zip(&batch_coeffs).zip(&multilinear_evals) {
+		for (i, composite_sum) in claim.composite_sums().iter().enumerate() {
+			expected_sum += pow(batch_coeff, i + 1) * composite_sum.composition.evaluate(evals)?;
+		}
+	}
+	if sum != expected_sum {
+		bail!(VerificationError::IncorrectBatchEvaluation);
+	}
+
+	if evaluation_order == EvaluationOrder::HighToLow {
+		challenges.reverse();
+	}
+
+	Ok(BatchSumcheckOutput {
+		challenges,
+		multilinear_evals,
+	})
+}
+
+fn pow<F: Field>(base: F, exp: usize) -> F {
+	(0..exp).fold(F::ONE, |acc, _| acc * base)
+}
+
+/// Evaluates the univariate polynomial with the given coefficients, lowest degree first.
+fn evaluate<F: Field>(coeffs: &[F], x: F) -> F {
+	coeffs
+		.iter()
+		.enumerate()
+		.map(|(i, &coeff)| coeff * pow(x, i))
+		.sum()
+}
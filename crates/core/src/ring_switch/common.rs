@@ -1,21 +1,38 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::sync::Arc;
+use std::{collections::HashMap, fmt, sync::Arc};
 
 use binius_field::{Field, TowerField};
-use binius_utils::sparse_index::SparseIndex;
+use binius_utils::{checked_arithmetics::log2_ceil_usize, sparse_index::SparseIndex};
 
-use super::error::Error;
+use super::{error::Error, virtual_claims::decompose_non_interactive};
 use crate::{
 	oracle::{MultilinearOracleSet, MultilinearPolyOracle, MultilinearPolyVariant},
 	piop::CommitMeta,
 	protocols::evalcheck::EvalcheckMultilinearClaim,
 };
 
+/// The lowest tower level at which committed multilinears are packed by their subfield.
+///
+/// Tower families only provide the subfields at levels 0 and 3 through 7, so multilinears at tower
+/// levels 1 and 2 are packed by their bit decomposition instead. The bit decomposition of a
+/// multilinear $P$ at tower level $\iota$ is the multilinear $Q$ over $T_0$ with $\iota$ more
+/// variables, where $Q(b, v)$ is the coordinate of $P(v)$ on the basis element
+/// $\beta_b = \prod_{k < \iota} \alpha_k^{b_k}$. It has the same packed evaluations as $P$, and
+///
+/// $$
+/// P(r) = \prod_{k < \iota} (1 + \alpha_k) \cdot Q(x, r),
+/// \quad x_k = \frac{\alpha_k}{1 + \alpha_k},
+/// $$
+///
+/// so an evaluation claim on $P$ is reduced as an evaluation claim on $Q$.
+pub const MIN_SUBFIELD_PACKING_TOWER_LEVEL: usize = 3;
+
 /// A prefix of an evaluation claim query.
 ///
 /// For an evaluation point $(z_0, ..., z_\ell)$, the prefix is $(z_0, ..., z_{\kappa-1})$, where
-/// $\kappa$ is the binary logarithm of the embedding degree.
+/// $\kappa$ is the binary logarithm of the embedding degree. Evaluation points with fewer than
+/// $\kappa$ coordinates are padded with zeros, see [`lift_eval_point`].
 #[derive(Debug)]
 pub struct EvalClaimPrefixDesc<F: Field> {
 	pub prefix: Vec<F>,
@@ -31,7 +48,7 @@ impl<F: Field> EvalClaimPrefixDesc<F> {
 ///
 /// For an evaluation point $(z_0, ..., z_\ell)$, the prefix is $(z_\kappa, ..., z_{\ell-1})$,
 /// where $\kappa$ is the binary logarithm of the embedding degree.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EvalClaimSuffixDesc<F: Field> {
 	pub suffix: Arc<[F]>,
 	pub kappa: usize,
@@ -39,25 +56,76 @@ pub struct EvalClaimSuffixDesc<F: Field> {
 
 /// An incomplete [`crate::piop::PIOPSumcheckClaim`] for which the sum has not been determined yet.
 #[derive(Debug)]
-pub struct PIOPSumcheckClaimDesc<'a, F: Field> {
+pub struct PIOPSumcheckClaimDesc<F: Field> {
 	/// Index of the committed multilinear, referencing the commit metadata.
 	pub committed_idx: usize,
 	/// Index of the suffix descriptor, referencing the slice in an [`EvalClaimSystem`].
 	pub suffix_desc_idx: usize,
-	pub eval_claim: &'a EvalcheckMultilinearClaim<F>,
+	/// The evaluation claim on the committed multilinear, after claims on virtual oracles have
+	/// been decomposed onto their committed ancestors.
+	pub eval_claim: EvalcheckMultilinearClaim<F>,
+	/// The evaluation at the point given by the prefix and suffix descriptors.
+	///
+	/// This differs from the evaluation of the claim for multilinears packed by their bit
+	/// decomposition, see [`MIN_SUBFIELD_PACKING_TOWER_LEVEL`].
+	pub eval: F,
 }
 
 /// A system of relations required to verify multilinear evaluation claims using the batched
 /// FRI-Binius protocol.
 ///
-/// Fields are public because this is internal to the module.
+/// The descriptors are exposed read-only through accessors, and [`Self::summary`] condenses them
+/// into a report suitable for logging.
 #[derive(Debug)]
 pub struct EvalClaimSystem<'a, F: Field> {
-	pub commit_meta: &'a CommitMeta,
-	pub prefix_descs: Vec<EvalClaimPrefixDesc<F>>,
-	pub suffix_descs: Vec<EvalClaimSuffixDesc<F>>,
-	pub sumcheck_claim_descs: Vec<PIOPSumcheckClaimDesc<'a, F>>,
-	pub eval_claim_to_prefix_desc_index: Vec<usize>,
+	pub(crate) commit_meta: &'a CommitMeta,
+	pub(crate) prefix_descs: Vec<EvalClaimPrefixDesc<F>>,
+	pub(crate) suffix_descs: Vec<EvalClaimSuffixDesc<F>>,
+	pub(crate) sumcheck_claim_descs: Vec<PIOPSumcheckClaimDesc<F>>,
+	pub(crate) eval_claim_to_prefix_desc_index: Vec<usize>,
+	pub(crate) security_bits: Option<usize>,
+}
+
+/// A summary of the reduction performed by an [`EvalClaimSystem`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalClaimSystemSummary {
+	/// The number of PIOP sumcheck claims, one per evaluation claim.
+	pub n_sumcheck_claims: usize,
+	/// The number of distinct evaluation point prefixes.
+	pub n_prefix_descs: usize,
+	/// The number of distinct evaluation point suffixes.
+	pub n_suffix_descs: usize,
+	/// The number of variables of the transparent multilinear for each suffix descriptor.
+	pub transparent_n_vars: Vec<usize>,
+	/// The number of variables of each PIOP sumcheck claim.
+	pub claim_n_vars: Vec<usize>,
+	/// The largest binary logarithm of an embedding degree among the claims.
+	pub max_kappa: usize,
+}
+
+impl EvalClaimSystemSummary {
+	/// The total number of scalars in the transparent multilinears.
+	pub fn transparent_size(&self) -> usize {
+		self.transparent_n_vars
+			.iter()
+			.map(|&n_vars| 1 << n_vars)
+			.sum()
+	}
+}
+
+impl fmt::Display for EvalClaimSystemSummary {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"{} sumcheck claims, {} prefix descriptors, {} suffix descriptors, max kappa {}, \
+			 {} transparent scalars",
+			self.n_sumcheck_claims,
+			self.n_prefix_descs,
+			self.n_suffix_descs,
+			self.max_kappa,
+			self.transparent_size(),
+		)
+	}
 }
 
 impl<'a, F: TowerField> EvalClaimSystem<'a, F> {
@@ -68,16 +136,31 @@ impl<'a, F: TowerField> EvalClaimSystem<'a, F> {
 	/// * `commit_meta` - metadata about the polynomial commitment.
 	/// * `oracle_to_commit_index` - a sparse index mapping oracle IDs to IDs in the commit
 	///   metadata.
-	/// * `eval_claims` - the evaluation claims on committed multilinear polynomials.
+	/// * `eval_claims` - the evaluation claims on committed multilinear polynomials. Claims on
+	///   repeating and projected oracles are decomposed onto their committed ancestors here. Claims
+	///   on linear combinations and shifted oracles require an interaction, and must be decomposed
+	///   with [`super::prove_virtual_claims`] and [`super::verify_virtual_claims`] first, as
+	///   [`crate::constraint_system::prove`] and [`crate::constraint_system::verify`] do.
 	pub fn new(
 		oracles: &MultilinearOracleSet<F>,
 		commit_meta: &'a CommitMeta,
 		oracle_to_commit_index: &SparseIndex<usize>,
-		eval_claims: &'a [EvalcheckMultilinearClaim<F>],
+		eval_claims: &[EvalcheckMultilinearClaim<F>],
 	) -> Result<Self, Error> {
+		if let Some(tower_level) = commit_meta.max_tower_level() {
+			if tower_level > F::TOWER_LEVEL {
+				return Err(Error::CommittedTowerLevelAboveTopField {
+					tower_level,
+					max: F::TOWER_LEVEL,
+				});
+			}
+		}
+
+		let eval_claims = decompose_non_interactive(oracles, eval_claims)?;
+
 		// Sort evaluation claims in ascending order by number of packed variables. This must
 		// happen before we do any further index mapping.
-		let mut eval_claims = eval_claims.iter().collect::<Vec<_>>();
+		let mut eval_claims = coalesce_identical_claims(&eval_claims);
 		eval_claims.sort_by_key(|claim| match oracles[claim.id] {
 			// The number of packed variables is n_vars + tower_level - F::TOWER_LEVEL. Just use
 			// n_vars + tower_level as the sort key because we haven't checked that the subtraction
@@ -112,11 +195,22 @@ impl<'a, F: TowerField> EvalClaimSystem<'a, F> {
 					.get(oracle.id().index())
 					.copied()
 					.ok_or_else(|| Error::OracleToCommitIndexMissingEntry { id: eval_claim.id })?;
+				if let Some(tower_level) = commit_meta.tower_level(committed_idx) {
+					if tower_level != oracle.binary_tower_level() {
+						return Err(Error::CommittedTowerLevelMismatch {
+							id: eval_claim.id,
+							expected: tower_level,
+							actual: oracle.binary_tower_level(),
+						});
+					}
+				}
 				let suffix_desc_idx = eval_claim_to_suffix_desc_index[i];
+				let (_, eval_scale) = bit_decomposition_coords::<F>(oracle.binary_tower_level());
 				Ok(PIOPSumcheckClaimDesc {
 					committed_idx,
 					suffix_desc_idx,
-					eval_claim,
+					eval: eval_claim.eval * eval_scale.invert_or_zero(),
+					eval_claim: eval_claim.clone(),
 				})
 			})
 			.collect::<Result<Vec<_>, _>>()?;
@@ -127,9 +221,108 @@ impl<'a, F: TowerField> EvalClaimSystem<'a, F> {
 			suffix_descs,
 			sumcheck_claim_descs,
 			eval_claim_to_prefix_desc_index,
+			security_bits: None,
 		})
 	}
 
+	/// Returns the bits of security of the reduction against a false evaluation claim.
+	///
+	/// The prover can only cheat if the mixing challenges or the row-batching challenges hit a root
+	/// of a nonzero multilinear in that many variables, which by the Schwartz-Zippel lemma happens
+	/// with probability at most $(\lceil \log_2 n \rceil + \kappa) / |F|$ for $n$ sumcheck claims
+	/// and the largest embedding degree $2^\kappa$.
+	pub fn soundness_bits(&self) -> usize {
+		let n_challenges = log2_ceil_usize(self.n_sumcheck_claims()) + self.max_claim_kappa();
+		F::N_BITS.saturating_sub(log2_ceil_usize(n_challenges))
+	}
+
+	/// Sets the target bits of security of the reduction.
+	///
+	/// The target is required to reduce into a top field narrower than 128 bits, such as the one of
+	/// [`binius_field::tower::Canonical64bTowerFamily`], where the challenges fall short of the
+	/// usual security levels.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InsufficientSoundness`] if [`Self::soundness_bits`] is below `security_bits`
+	pub fn with_security_bits(mut self, security_bits: usize) -> Result<Self, Error> {
+		let soundness_bits = self.soundness_bits();
+		if soundness_bits < security_bits {
+			return Err(Error::InsufficientSoundness {
+				soundness_bits,
+				security_bits,
+			});
+		}
+		self.security_bits = Some(security_bits);
+		Ok(self)
+	}
+
+	/// Checks that the system may be reduced into its top field, which requires a target set with
+	/// [`Self::with_security_bits`] when the field is narrower than 128 bits.
+	pub(super) fn check_security_bits(&self) -> Result<(), Error> {
+		if self.security_bits.is_none() && F::N_BITS < 128 {
+			return Err(Error::SecurityBitsRequired { n_bits: F::N_BITS });
+		}
+		Ok(())
+	}
+}
+
+impl<'a, F: Field> EvalClaimSystem<'a, F> {
+	/// Returns the metadata about the polynomial commitment.
+	pub fn commit_meta(&self) -> &CommitMeta {
+		self.commit_meta
+	}
+
+	/// Returns the distinct evaluation point prefixes.
+	pub fn prefix_descs(&self) -> &[EvalClaimPrefixDesc<F>] {
+		&self.prefix_descs
+	}
+
+	/// Returns the distinct evaluation point suffixes, each of which determines one transparent
+	/// multilinear of the reduced claims.
+	pub fn suffix_descs(&self) -> &[EvalClaimSuffixDesc<F>] {
+		&self.suffix_descs
+	}
+
+	/// Returns the PIOP sumcheck claim descriptors, one per evaluation claim, in ascending order by
+	/// number of packed variables.
+	pub fn sumcheck_claim_descs(&self) -> &[PIOPSumcheckClaimDesc<F>] {
+		&self.sumcheck_claim_descs
+	}
+
+	/// Returns the index of the prefix descriptor of each sumcheck claim descriptor.
+	pub fn eval_claim_to_prefix_desc_index(&self) -> &[usize] {
+		&self.eval_claim_to_prefix_desc_index
+	}
+
+	/// Returns the number of PIOP sumcheck claims the system reduces to.
+	pub fn n_sumcheck_claims(&self) -> usize {
+		self.sumcheck_claim_descs.len()
+	}
+
+	/// Returns the number of variables of each PIOP sumcheck claim.
+	pub fn claim_n_vars(&self) -> impl Iterator<Item = usize> + '_ {
+		self.sumcheck_claim_descs
+			.iter()
+			.map(|claim_desc| self.suffix_descs[claim_desc.suffix_desc_idx].suffix.len())
+	}
+
+	/// Returns a summary of the reduction for logging.
+	pub fn summary(&self) -> EvalClaimSystemSummary {
+		EvalClaimSystemSummary {
+			n_sumcheck_claims: self.n_sumcheck_claims(),
+			n_prefix_descs: self.prefix_descs.len(),
+			n_suffix_descs: self.suffix_descs.len(),
+			transparent_n_vars: self
+				.suffix_descs
+				.iter()
+				.map(|desc| desc.suffix.len())
+				.collect(),
+			claim_n_vars: self.claim_n_vars().collect(),
+			max_kappa: self.max_claim_kappa(),
+		}
+	}
+
 	pub fn max_claim_kappa(&self) -> usize {
 		self.prefix_descs
 			.iter()
@@ -139,6 +332,29 @@ impl<'a, F: TowerField> EvalClaimSystem<'a, F> {
 	}
 }
 
+/// Removes exact duplicates from the evaluation claims, keeping the first occurrence of each.
+///
+/// Several constraints frequently reference the same column at the same point, so the list of
+/// evaluation claims often contains identical entries. Each is reduced to a single sumcheck claim,
+/// since the prover and the verifier coalesce the claims in the same way. Claims on the same
+/// oracle and point with different evaluations are kept, so that the inconsistency is caught.
+fn coalesce_identical_claims<F: Field>(
+	eval_claims: &[EvalcheckMultilinearClaim<F>],
+) -> Vec<&EvalcheckMultilinearClaim<F>> {
+	let mut claims_by_oracle = HashMap::<_, Vec<&EvalcheckMultilinearClaim<F>>>::new();
+	eval_claims
+		.iter()
+		.filter(|&claim| {
+			let oracle_claims = claims_by_oracle.entry(claim.id).or_default();
+			if oracle_claims.contains(&claim) {
+				return false;
+			}
+			oracle_claims.push(claim);
+			true
+		})
+		.collect()
+}
+
 #[allow(clippy::type_complexity)]
 fn group_claims_by_eval_point<F: TowerField>(
 	oracles: &MultilinearOracleSet<F>,
@@ -152,6 +368,7 @@ fn group_claims_by_eval_point<F: TowerField>(
 	for claim in claims {
 		let &MultilinearPolyOracle {
 			id,
+			n_vars,
 			tower_level,
 			variant: MultilinearPolyVariant::Committed,
 			..
@@ -159,24 +376,32 @@ fn group_claims_by_eval_point<F: TowerField>(
 		else {
 			return Err(Error::EvalcheckClaimForDerivedPoly { id: claim.id });
 		};
-		let kappa = F::TOWER_LEVEL.checked_sub(tower_level).ok_or_else(|| {
-			Error::OracleTowerLevelTooHigh {
+		if tower_level > F::TOWER_LEVEL {
+			return Err(Error::OracleTowerLevelTooHigh {
 				id,
 				max: F::TOWER_LEVEL,
-			}
-		})?;
-
-		let (prefix, suffix) = if claim.eval_point.len() < kappa {
-			// If evaluation point is less than kappa, pad the evaluation point with 0s
-			let mut prefix = Vec::with_capacity(kappa);
-			prefix.extend_from_slice(&claim.eval_point);
-			prefix.resize(kappa, F::ZERO);
-			(prefix, &[][..])
+			});
+		}
+		if claim.eval_point.len() != n_vars {
+			return Err(Error::IncorrectEvalPointLength {
+				id,
+				expected: n_vars,
+				actual: claim.eval_point.len(),
+			});
+		}
+
+		// Multilinears at low tower levels are claimed on by the evaluation point of their bit
+		// decomposition.
+		let (bit_coords, _) = bit_decomposition_coords::<F>(tower_level);
+		let eval_point = [bit_coords, claim.eval_point.to_vec()].concat();
+		let kappa = if tower_level < MIN_SUBFIELD_PACKING_TOWER_LEVEL {
+			F::TOWER_LEVEL
 		} else {
-			let (prefix, suffix) = claim.eval_point.split_at(kappa);
-			(prefix.to_vec(), suffix)
+			F::TOWER_LEVEL - tower_level
 		};
 
+		let (prefix, suffix) = lift_eval_point(eval_point, kappa);
+
 		let prefix_id = prefix_descs
 			.iter()
 			.position(|desc| desc.prefix == prefix)
@@ -189,11 +414,11 @@ fn group_claims_by_eval_point<F: TowerField>(
 
 		let suffix_id = suffix_descs
 			.iter()
-			.position(|desc| &*desc.suffix == suffix && desc.kappa == kappa)
+			.position(|desc| *desc.suffix == *suffix && desc.kappa == kappa)
 			.unwrap_or_else(|| {
 				let index = suffix_descs.len();
 				suffix_descs.push(EvalClaimSuffixDesc {
-					suffix: suffix.to_vec().into(),
+					suffix: suffix.into(),
 					kappa,
 				});
 				index
@@ -203,3 +428,42 @@ fn group_claims_by_eval_point<F: TowerField>(
 
 	Ok((prefix_descs, claim_to_prefix_index, suffix_descs, claim_to_suffix_index))
 }
+
+/// Splits an evaluation point into its prefix of $\kappa$ coordinates and the remaining suffix.
+///
+/// A multilinear with $n < \kappa$ variables does not fill a single packed evaluation. It is
+/// committed as the multilinear with $\kappa$ variables that repeats its evaluations, see
+/// [`crate::piop::prove`], which does not depend on the $\kappa - n$ variables added after its own.
+/// The evaluation point is lifted to $\kappa$ coordinates by padding it with zeros, at which the
+/// lifted multilinear evaluates to the claimed evaluation, and the suffix is empty.
+fn lift_eval_point<F: Field>(mut eval_point: Vec<F>, kappa: usize) -> (Vec<F>, Vec<F>) {
+	if eval_point.len() < kappa {
+		eval_point.resize(kappa, F::ZERO);
+		(eval_point, Vec::new())
+	} else {
+		let suffix = eval_point.split_off(kappa);
+		(eval_point, suffix)
+	}
+}
+
+/// Returns the evaluation point coordinates of the bit variables of the bit decomposition of a
+/// multilinear at the given tower level, and the factor relating their evaluations.
+///
+/// The result is empty with a factor of one at tower levels that are packed by their subfield. See
+/// [`MIN_SUBFIELD_PACKING_TOWER_LEVEL`].
+fn bit_decomposition_coords<F: TowerField>(tower_level: usize) -> (Vec<F>, F) {
+	if tower_level >= MIN_SUBFIELD_PACKING_TOWER_LEVEL {
+		return (Vec::new(), F::ONE);
+	}
+	(0..tower_level)
+		.map(|k| {
+			let alpha =
+				<F as TowerField>::basis(0, 1 << k).expect("tower_level is at most F::TOWER_LEVEL");
+			let scale = F::ONE + alpha;
+			(alpha * scale.invert_or_zero(), scale)
+		})
+		.fold((Vec::with_capacity(tower_level), F::ONE), |(mut coords, product), (coord, scale)| {
+			coords.push(coord);
+			(coords, product * scale)
+		})
+}
@@ -5,9 +5,8 @@ use std::{iter, marker::PhantomData, sync::Arc};
 use binius_field::{
 	ExtensionField, Field, PackedExtension, PackedField, TowerField, packed::pack_slice,
 };
-use binius_math::{
-	MultilinearExtension, MultilinearQuery, MultilinearQueryRef, tensor_prod_eq_ind,
-};
+use binius_math::{MultilinearExtension, MultilinearQuery, MultilinearQueryRef};
+use binius_maybe_rayon::prelude::*;
 use binius_utils::bail;
 use bytemuck::zeroed_vec;
 
@@ -17,6 +16,10 @@ use crate::{
 	tensor_algebra::TensorAlgebra,
 };
 
+/// Base-2 logarithm of the number of scalars processed per parallel task when computing the
+/// row-batched eq indicator.
+const LOG_PAR_CHUNK_SIZE: usize = 12;
+
 /// Information about the row-batching coefficients.
 #[derive(Debug)]
 pub struct RowBatchCoeffs<F> {
@@ -74,26 +77,46 @@ where
 		})
 	}
 
+	/// Computes the multilinear extension from the tensor expansion of `z_vals`, which is shared
+	/// by all ring-switching indicators with the same evaluation point suffix.
 	pub fn multilinear_extension<P: PackedField<Scalar = F> + PackedExtension<FSub>>(
 		&self,
+		z_vals_expansion: &[P],
 	) -> Result<MultilinearExtension<P>, Error> {
-		let mut evals = zeroed_vec::<P>(1 << self.z_vals.len().saturating_sub(P::LOG_WIDTH));
-		evals[0].set(0, self.mixing_coeff);
-		tensor_prod_eq_ind(0, &mut evals, &self.z_vals)?;
+		if z_vals_expansion.len() != 1 << self.z_vals.len().saturating_sub(P::LOG_WIDTH) {
+			bail!(Error::InvalidArgs(
+				"RingSwitchEqInd::multilinear_extension expects the tensor expansion of z_vals"
+					.into()
+			));
+		}
+		let evals = z_vals_expansion
+			.par_iter()
+			.map(|&eval| eval * self.mixing_coeff)
+			.collect::<Vec<_>>();
 
 		let subfield_vector = <P as PackedExtension<FSub>>::cast_bases(&evals);
 
-		let subfield_vector_mle =
-			MultilinearExtension::new(self.z_vals.len() + F::LOG_DEGREE, subfield_vector)?;
-
 		let row_batch_coeffs = pack_slice(&self.row_batch_coeffs.coeffs()[0..F::DEGREE]);
 		let row_batching_query_expansion =
 			MultilinearQuery::with_expansion(F::LOG_DEGREE, row_batch_coeffs)?;
-
-		let partial_low_eval = subfield_vector_mle
-			.evaluate_partial_low(MultilinearQueryRef::new(&row_batching_query_expansion))?;
-
-		Ok(partial_low_eval)
+		let row_batching_query = MultilinearQueryRef::new(&row_batching_query_expansion);
+
+		// Each packed output element depends only on the packed subfield element at the same
+		// index, so the row-batching partial evaluation can be split into independent chunks.
+		let n_vars = self.z_vals.len();
+		let chunk_n_vars = n_vars.min(LOG_PAR_CHUNK_SIZE.max(P::LOG_WIDTH));
+		let packed_chunk_len = 1 << chunk_n_vars.saturating_sub(P::LOG_WIDTH);
+
+		let mut partial_low_evals = zeroed_vec::<P>(evals.len());
+		subfield_vector
+			.par_chunks(packed_chunk_len)
+			.zip(partial_low_evals.par_chunks_mut(packed_chunk_len))
+			.try_for_each(|(subfield_chunk, out_chunk)| {
+				MultilinearExtension::new(chunk_n_vars + F::LOG_DEGREE, subfield_chunk)?
+					.evaluate_partial_low_into(row_batching_query, out_chunk)
+			})?;
+
+		Ok(MultilinearExtension::new(n_vars, partial_low_evals)?)
 	}
 }
 
@@ -147,12 +170,10 @@ mod tests {
 
 	use super::*;
 
-	#[test]
-	fn test_evaluation_consistency() {
+	fn check_evaluation_consistency(ell: usize) {
 		type FS = BinaryField8b;
 		type F = BinaryField128b;
 		let kappa = <TensorAlgebra<FS, F>>::kappa();
-		let ell = 10;
 		let mut rng = StdRng::seed_from_u64(0);
 
 		let n_vars = ell - kappa;
@@ -175,11 +196,25 @@ mod tests {
 
 		let mixing_coeff = <F as Field>::random(&mut rng);
 
+		let z_vals_query = MultilinearQuery::<F>::expand(&z_vals);
 		let rs_eq = RingSwitchEqInd::<FS, _>::new(z_vals, row_batch_coeffs, mixing_coeff).unwrap();
-		let mle = rs_eq.multilinear_extension::<F>().unwrap();
+		let mle = rs_eq
+			.multilinear_extension::<F>(z_vals_query.expansion())
+			.unwrap();
 
 		let val1 = rs_eq.evaluate(&eval_point).unwrap();
 		let val2 = mle.evaluate(&eval_query).unwrap();
 		assert_eq!(val1, val2);
 	}
+
+	#[test]
+	fn test_evaluation_consistency() {
+		check_evaluation_consistency(10);
+	}
+
+	#[test]
+	fn test_evaluation_consistency_multiple_chunks() {
+		// Large enough that the row-batching evaluation is split across several parallel chunks.
+		check_evaluation_consistency(LOG_PAR_CHUNK_SIZE + 8);
+	}
 }
@@ -6,6 +6,36 @@ use crate::{oracle::OracleId, polynomial, transcript};
 pub enum Error {
 	#[error("committed oracle {id} tower level exceeds maximum of {max}")]
 	OracleTowerLevelTooHigh { id: OracleId, max: usize },
+	#[error(
+		"committed oracle {id} has tower level {actual}, but the commit metadata records {expected}"
+	)]
+	CommittedTowerLevelMismatch {
+		id: OracleId,
+		expected: usize,
+		actual: usize,
+	},
+	#[error(
+		"the commit metadata records tower level {tower_level}, above the top field at tower level \
+		{max}"
+	)]
+	CommittedTowerLevelAboveTopField { tower_level: usize, max: usize },
+	#[error("reducing into a {n_bits}-bit top field requires an explicit security target")]
+	SecurityBitsRequired { n_bits: usize },
+	#[error(
+		"the reduction has {soundness_bits} bits of security, below the target of {security_bits}"
+	)]
+	InsufficientSoundness {
+		soundness_bits: usize,
+		security_bits: usize,
+	},
+	#[error(
+		"evaluation claim on committed oracle {id} has {actual} coordinates, but the oracle has {expected} variables"
+	)]
+	IncorrectEvalPointLength {
+		id: OracleId,
+		expected: usize,
+		actual: usize,
+	},
 	#[error("packing degree {kappa} not supported")]
 	PackingDegreeNotSupported { kappa: usize },
 	#[error("cannot call function when argument tower heights do not match")]
@@ -26,6 +56,10 @@ pub enum Error {
 	Polynomial(#[from] polynomial::Error),
 	#[error("HAL error: {0}")]
 	HAL(#[from] binius_hal::Error),
+	#[error("compute layer error: {0}")]
+	ComputeLayer(#[from] binius_compute::Error),
+	#[error("allocation error: {0}")]
+	Alloc(#[from] binius_compute::alloc::Error),
 	#[error("verification error: {0}")]
 	VerificationError(#[from] VerificationError),
 }
@@ -36,6 +70,14 @@ pub enum VerificationError {
 	IncorrectEvaluation,
 	#[error("the claimed row-batched sum is inconsistent with the tensor evaluation")]
 	IncorrectRowBatchedSum,
+	#[error(
+		"the evaluations of the inner oracles of linear combination {id} are inconsistent with its claimed evaluation"
+	)]
+	IncorrectLinearCombinationEvaluation { id: OracleId },
+	#[error(
+		"the evaluation of the inner oracle of shifted oracle {id} is inconsistent with the shift sumcheck"
+	)]
+	IncorrectShiftedEvaluation { id: OracleId },
 	#[error("Transcript error: {0}")]
 	Transcript(#[from] transcript::Error),
 }
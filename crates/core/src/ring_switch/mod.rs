@@ -29,12 +29,18 @@ mod eq_ind;
 mod error;
 mod logging;
 mod prove;
+mod reduced_instance;
+mod tensor_arena;
 #[cfg(test)]
 mod tests;
 mod tower_tensor_algebra;
 mod verify;
+mod virtual_claims;
 
 pub use common::*;
 pub use error::*;
 pub use prove::*;
+pub use reduced_instance::ReducedInstance;
+pub use tensor_arena::{prove_dev_mem_size, prove_host_mem_size};
 pub use verify::*;
+pub use virtual_claims::{prove_virtual_claims, verify_virtual_claims};
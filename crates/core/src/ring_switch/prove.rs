@@ -1,30 +1,37 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::{iter, sync::Arc};
+use std::{borrow::Cow, iter, sync::Arc};
 
-use binius_field::{PackedField, PackedFieldIndexable};
+use binius_compute::{
+	ComputeLayer, ComputeLayerExecutor, ComputeMemory, FSliceMut, SizedSlice, SubfieldSlice,
+	alloc::{BumpAllocator, ComputeAllocator, HostBumpAllocator},
+	cpu::CpuMemory,
+};
+use binius_field::{
+	Field, PackedExtension, PackedField, PackedFieldIndexable, PackedSubfield, TowerField,
+	tower::{PackedTopField, TopTowerFamily},
+};
 use binius_math::{
-	B1, B8, B16, B32, B64, B128, MLEDirectAdapter, MultilinearPoly, MultilinearQuery, PackedTop,
-	TowerTop,
+	MLEDirectAdapter, MLEEmbeddingAdapter, MultilinearExtension, MultilinearPoly, MultilinearQuery,
 };
 use binius_maybe_rayon::prelude::*;
 use binius_utils::checked_arithmetics::log2_ceil_usize;
 use tracing::instrument;
 
 use super::{
-	common::{EvalClaimPrefixDesc, EvalClaimSystem, PIOPSumcheckClaimDesc},
+	common::{EvalClaimSystem, PIOPSumcheckClaimDesc},
 	eq_ind::RowBatchCoeffs,
 	error::Error,
 	logging::MLEFoldHisgDimensionsData,
+	reduced_instance::ReducedInstance,
+	tensor_arena::{TensorArena, prove_host_mem_size},
 	tower_tensor_algebra::TowerTensorAlgebra,
 };
 use crate::{
 	fiat_shamir::{CanSample, Challenger},
-	piop::PIOPSumcheckClaim,
+	piop::{PIOPSumcheckClaim, padded_packed_eval},
 	protocols::evalcheck::subclaims::MemoizedData,
-	ring_switch::{
-		common::EvalClaimSuffixDesc, eq_ind::RingSwitchEqInd, logging::CalculateRingSwitchEqIndData,
-	},
+	ring_switch::{common::EvalClaimSuffixDesc, eq_ind::RingSwitchEqInd},
 	transcript::ProverTranscript,
 	witness::MultilinearWitness,
 };
@@ -35,24 +42,106 @@ pub struct ReducedWitness<P: PackedField> {
 	pub sumcheck_claims: Vec<PIOPSumcheckClaim<P::Scalar>>,
 }
 
-pub fn prove<F, P, M, Challenger_>(
-	system: &EvalClaimSystem<F>,
+/// Reduces the evaluation claims of an [`EvalClaimSystem`] to PIOP sumcheck claims.
+///
+/// The packing subfields are the ones of the tower family `Tower`. This works for any tower family,
+/// including the AES tower, so that witnesses need not be converted to the canonical basis.
+///
+/// The claims are reduced into the top field of the tower family, `Tower::Top`. When no committed
+/// multilinear is above tower level 6, see [`crate::piop::CommitMeta::max_tower_level`], the
+/// claims can be reduced into the 64-bit field with
+/// [`binius_field::tower::Canonical64bTowerFamily`], which makes the subsequent sumcheck and FRI
+/// arithmetic cheaper. A top field narrower than 128 bits requires a security target, set with
+/// [`EvalClaimSystem::with_security_bits`].
+///
+/// The tensor algebra elements of the reduction are allocated in one buffer of host memory. Use
+/// [`prove_with_host_alloc`] to take them from an allocator shared with [`crate::piop::prove`].
+pub fn prove<Tower, P, M, Challenger_>(
+	system: &EvalClaimSystem<Tower::Top>,
 	witnesses: &[M],
 	transcript: &mut ProverTranscript<Challenger_>,
 	memoized_data: MemoizedData<P>,
 ) -> Result<ReducedWitness<P>, Error>
 where
-	F: TowerTop + PackedTop<Scalar = F>,
-	P: PackedFieldIndexable<Scalar = F> + PackedTop,
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
+	P: PackedFieldIndexable<Scalar = Tower::Top> + PackedTopField<Tower>,
 	M: MultilinearPoly<P> + Sync,
 	Challenger_: Challenger,
 {
+	let mut host_mem = vec![Tower::Top::ZERO; prove_host_mem_size(system)];
+	prove_with_host_alloc::<Tower, _, _, _>(
+		system,
+		witnesses,
+		transcript,
+		memoized_data,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+	)
+}
+
+/// Reduces the evaluation claims of an [`EvalClaimSystem`] to PIOP sumcheck claims, as [`prove`],
+/// allocating the tensor algebra elements of the reduction from `host_alloc`.
+///
+/// The reduction takes [`prove_host_mem_size`] elements from `host_alloc`, and they are reclaimed
+/// by the allocator when this returns, so the same allocator can be passed to
+/// [`crate::piop::prove`] afterwards.
+pub fn prove_with_host_alloc<Tower, P, M, Challenger_>(
+	system: &EvalClaimSystem<Tower::Top>,
+	witnesses: &[M],
+	transcript: &mut ProverTranscript<Challenger_>,
+	mut memoized_data: MemoizedData<P>,
+	host_alloc: &mut impl ComputeAllocator<Tower::Top, CpuMemory>,
+) -> Result<ReducedWitness<P>, Error>
+where
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
+	P: PackedFieldIndexable<Scalar = Tower::Top> + PackedTopField<Tower>,
+	M: MultilinearPoly<P> + Sync,
+	Challenger_: Challenger,
+{
+	let instance = prove_instance::<Tower, _, _, _>(
+		system,
+		witnesses,
+		transcript,
+		&mut memoized_data,
+		host_alloc,
+	)?;
+	instance.into_witness::<Tower, P>(&mut memoized_data)
+}
+
+/// Runs the interaction of the ring-switching reduction of an [`EvalClaimSystem`], as
+/// [`prove_with_host_alloc`], without creating the transparent multilinears of the reduced claims.
+///
+/// The returned [`ReducedInstance`] can be serialized and handed to a separate process that runs
+/// the PIOP, which creates the transparent multilinears with [`ReducedInstance::into_witness`].
+pub fn prove_instance<Tower, P, M, Challenger_>(
+	system: &EvalClaimSystem<Tower::Top>,
+	witnesses: &[M],
+	transcript: &mut ProverTranscript<Challenger_>,
+	memoized_data: &mut MemoizedData<P>,
+	host_alloc: &mut impl ComputeAllocator<Tower::Top, CpuMemory>,
+) -> Result<ReducedInstance<Tower::Top>, Error>
+where
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
+	P: PackedFieldIndexable<Scalar = Tower::Top> + PackedTopField<Tower>,
+	M: MultilinearPoly<P> + Sync,
+	Challenger_: Challenger,
+{
+	system.check_security_bits()?;
 	if witnesses.len() != system.commit_meta.total_multilins() {
 		return Err(Error::InvalidWitness(
 			"witness length does not match the number of multilinears".into(),
 		));
 	}
 
+	// The nested allocator returns its memory to `host_alloc` when it is dropped.
+	let host_alloc = HostBumpAllocator::new(host_alloc.remaining());
+	let TensorArena {
+		mut claim_elems,
+		mut prefix_elems,
+	} = TensorArena::new(system, host_alloc.alloc(prove_host_mem_size(system))?);
+
 	// Sample enough randomness to batch tensor elements corresponding to claims that share an
 	// evaluation point prefix.
 	let n_mixing_challenges = log2_ceil_usize(system.sumcheck_claim_descs.len());
@@ -69,76 +158,301 @@ where
 	let mixing_coeffs = MultilinearQuery::expand(&mixing_challenges).into_expansion();
 
 	// For each evaluation point prefix, send one batched partial evaluation.
-	let tensor_elems = compute_partial_evals(system, witnesses, memoized_data)?;
-	let scaled_tensor_elems = scale_tensor_elems(tensor_elems, &mixing_coeffs);
-	let mixed_tensor_elems = mix_tensor_elems_for_prefixes(
-		&scaled_tensor_elems,
-		&system.prefix_descs,
-		&system.eval_claim_to_prefix_desc_index,
+	compute_partial_evals::<Tower, _, _>(
+		system,
+		witnesses,
+		&mixing_coeffs,
+		memoized_data,
+		&mut claim_elems,
 	)?;
+	mix_tensor_elems_for_prefixes(
+		&claim_elems,
+		&mut prefix_elems,
+		&system.eval_claim_to_prefix_desc_index,
+	);
 	drop(mle_fold_high_span);
 	let mut writer = transcript.message();
-	for (mixed_tensor_elem, prefix_desc) in iter::zip(mixed_tensor_elems, &system.prefix_descs) {
-		debug_assert_eq!(mixed_tensor_elem.vertical_elems().len(), 1 << prefix_desc.kappa());
-		writer.write_scalar_slice(mixed_tensor_elem.vertical_elems());
+	for (mixed_tensor_elem, prefix_desc) in iter::zip(&prefix_elems, &system.prefix_descs) {
+		debug_assert_eq!(mixed_tensor_elem.len(), 1 << prefix_desc.kappa());
+		writer.write_scalar_slice(mixed_tensor_elem);
 	}
 
 	// Sample the row-batching randomness.
 	let row_batch_challenges = transcript.sample_vec(system.max_claim_kappa());
-	let row_batch_coeffs = Arc::new(RowBatchCoeffs::new(
-		MultilinearQuery::<F, _>::expand(&row_batch_challenges).into_expansion(),
-	));
+	let row_batch_coeffs =
+		MultilinearQuery::<Tower::Top, _>::expand(&row_batch_challenges).into_expansion();
 
 	let row_batched_evals =
-		compute_row_batched_sumcheck_evals(scaled_tensor_elems, row_batch_coeffs.coeffs());
+		compute_row_batched_sumcheck_evals::<Tower>(claim_elems, &row_batch_coeffs)?;
 	transcript.message().write_scalar_slice(&row_batched_evals);
 
-	// Create the reduced PIOP sumcheck witnesses.
-	let dimensions_data = CalculateRingSwitchEqIndData::new(system.suffix_descs.iter());
-	let calculate_ring_switch_eq_ind_span = tracing::debug_span!(
-		"[task] Calculate Ring Switch Eq Ind",
+	Ok(ReducedInstance::new(system, row_batch_coeffs, &mixing_coeffs, row_batched_evals))
+}
+
+/// Reduces the evaluation claims of an [`EvalClaimSystem`] to PIOP sumcheck claims, as
+/// [`prove_with_host_alloc`], computing the partial evaluations and the row-batched evaluations of
+/// the claims with the compute layer `hal`.
+///
+/// The packed evaluations of the committed multilinears with claims are copied to device memory,
+/// where they are partially evaluated at the tensor expansions of the evaluation point suffixes.
+/// Only the tensor algebra elements of the claims are copied back to the host, to be mixed and
+/// written to the transcript. The proof is the same as the one of [`prove`].
+///
+/// The reduction takes [`prove_host_mem_size`] elements from `host_alloc` and
+/// [`super::prove_dev_mem_size`] elements from `dev_alloc`, which are reclaimed by the allocators
+/// when this returns, so the same allocators can be passed to [`crate::piop::prove`] afterwards.
+pub fn prove_with_hal<Hal, Tower, P, M, Challenger_>(
+	hal: &Hal,
+	system: &EvalClaimSystem<Tower::Top>,
+	witnesses: &[M],
+	transcript: &mut ProverTranscript<Challenger_>,
+	mut memoized_data: MemoizedData<P>,
+	host_alloc: &mut impl ComputeAllocator<Tower::Top, CpuMemory>,
+	dev_alloc: &mut impl ComputeAllocator<Tower::Top, Hal::DevMem>,
+) -> Result<ReducedWitness<P>, Error>
+where
+	Hal: ComputeLayer<Tower::Top>,
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
+	P: PackedFieldIndexable<Scalar = Tower::Top> + PackedTopField<Tower>,
+	M: MultilinearPoly<P> + Sync,
+	Challenger_: Challenger,
+{
+	system.check_security_bits()?;
+	if witnesses.len() != system.commit_meta.total_multilins() {
+		return Err(Error::InvalidWitness(
+			"witness length does not match the number of multilinears".into(),
+		));
+	}
+
+	// The nested allocators return their memory to the caller's allocators when they are dropped.
+	let host_alloc = HostBumpAllocator::new(host_alloc.remaining());
+	let dev_alloc = BumpAllocator::<_, Hal::DevMem>::new(dev_alloc.remaining());
+	let TensorArena {
+		mut claim_elems,
+		mut prefix_elems,
+	} = TensorArena::new(system, host_alloc.alloc(prove_host_mem_size(system))?);
+
+	let n_mixing_challenges = log2_ceil_usize(system.sumcheck_claim_descs.len());
+	let mixing_challenges = transcript.sample_vec(n_mixing_challenges);
+	let dimensions_data = MLEFoldHisgDimensionsData::new(witnesses);
+	let mle_fold_high_span = tracing::debug_span!(
+		"[task] (Ring Switch) MLE Fold High",
 		phase = "ring_switch",
 		perfetto_category = "task.main",
 		?dimensions_data,
 	)
 	.entered();
 
-	let ring_switch_eq_inds = make_ring_switch_eq_inds::<_, P>(
-		&system.sumcheck_claim_descs,
-		&system.suffix_descs,
-		row_batch_coeffs,
-		&mixing_coeffs,
-	)?;
-	drop(calculate_ring_switch_eq_ind_span);
+	let mixing_coeffs = MultilinearQuery::expand(&mixing_challenges).into_expansion();
+
+	// The partial evaluations stay on the device for row-batching, unscaled by the mixing
+	// coefficients, which are applied on the host instead.
+	let dev_claim_elems =
+		compute_partial_evals_with_hal::<_, _, P, _>(hal, &dev_alloc, system, witnesses)?;
+	for ((dev_elems, elems), &mixing_coeff) in
+		iter::zip(iter::zip(&dev_claim_elems, &mut claim_elems), &mixing_coeffs)
+	{
+		hal.copy_d2h(Hal::DevMem::as_const(dev_elems), elems)?;
+		for elem in elems.iter_mut() {
+			*elem *= mixing_coeff;
+		}
+	}
+	mix_tensor_elems_for_prefixes(
+		&claim_elems,
+		&mut prefix_elems,
+		&system.eval_claim_to_prefix_desc_index,
+	);
+	drop(mle_fold_high_span);
+	let mut writer = transcript.message();
+	for (mixed_tensor_elem, prefix_desc) in iter::zip(&prefix_elems, &system.prefix_descs) {
+		debug_assert_eq!(mixed_tensor_elem.len(), 1 << prefix_desc.kappa());
+		writer.write_scalar_slice(mixed_tensor_elem);
+	}
+
+	let row_batch_challenges = transcript.sample_vec(system.max_claim_kappa());
+	let row_batch_coeffs =
+		MultilinearQuery::<Tower::Top, _>::expand(&row_batch_challenges).into_expansion();
+
+	let row_batched_evals = compute_row_batched_sumcheck_evals_with_hal(
+		hal,
+		&dev_alloc,
+		&dev_claim_elems,
+		&row_batch_coeffs,
+	)?
+	.into_iter()
+	.zip(&mixing_coeffs)
+	.map(|(eval, &mixing_coeff)| eval * mixing_coeff)
+	.collect::<Vec<_>>();
+	transcript.message().write_scalar_slice(&row_batched_evals);
+
+	ReducedInstance::new(system, row_batch_coeffs, &mixing_coeffs, row_batched_evals)
+		.into_witness::<Tower, P>(&mut memoized_data)
+}
 
-	let sumcheck_claims = iter::zip(&system.sumcheck_claim_descs, row_batched_evals)
-		.enumerate()
-		.map(|(idx, (claim_desc, eval))| {
+/// Computes the partial evaluation of the witness of each claim as a tensor algebra element in
+/// device memory, without scaling it by the claim's mixing coefficient.
+///
+/// The packed evaluations of a witness are a matrix of subfield elements with $2^\kappa$ rows,
+/// one column per packed element, so the partial evaluation at the suffix of the evaluation
+/// point is the left fold of the matrix with the tensor expansion of the suffix. Each committed
+/// multilinear and each suffix expansion is copied to, or computed on, the device once, however
+/// many claims share it.
+#[instrument(skip_all)]
+fn compute_partial_evals_with_hal<'a, Hal, F, P, M>(
+	hal: &Hal,
+	dev_alloc: &'a BumpAllocator<'_, F, Hal::DevMem>,
+	system: &EvalClaimSystem<F>,
+	witnesses: &[M],
+) -> Result<Vec<FSliceMut<'a, F, Hal>>, Error>
+where
+	Hal: ComputeLayer<F>,
+	F: TowerField,
+	P: PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P>,
+{
+	let mut dev_witnesses = vec![None; witnesses.len()];
+	for claim_desc in &system.sumcheck_claim_descs {
+		let dev_witness = &mut dev_witnesses[claim_desc.committed_idx];
+		if dev_witness.is_none() {
+			let packed_evals = packed_witness_evals(&witnesses[claim_desc.committed_idx])?;
+			let mut dev_evals = dev_alloc.alloc(packed_evals.len())?;
+			hal.copy_h2d(&packed_evals, &mut dev_evals)?;
+			*dev_witness = Some(Hal::DevMem::to_const(dev_evals));
+		}
+	}
+
+	// The expansions start from the vector (1, 0, ...), which is expanded in place.
+	let mut dev_expansions = system
+		.suffix_descs
+		.iter()
+		.map(|suffix_desc| {
+			let mut expansion = dev_alloc.alloc(1 << suffix_desc.suffix.len())?;
+			let init_len = expansion.len().min(Hal::DevMem::ALIGNMENT);
+			let mut init = hal.host_alloc(init_len);
+			let init = init.as_mut();
+			init.fill(F::ZERO);
+			init[0] = F::ONE;
+			hal.copy_h2d(init, &mut Hal::DevMem::slice_mut(&mut expansion, ..init_len))?;
+			Ok(expansion)
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	let mut dev_claim_elems = system
+		.sumcheck_claim_descs
+		.iter()
+		.map(|claim_desc| {
+			dev_alloc.alloc(1 << system.suffix_descs[claim_desc.suffix_desc_idx].kappa)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	hal.execute(|exec| {
+		for (expansion, suffix_desc) in iter::zip(&mut dev_expansions, &system.suffix_descs) {
+			exec.tensor_expand(0, &suffix_desc.suffix, expansion)?;
+		}
+		for (claim_desc, elems) in iter::zip(&system.sumcheck_claim_descs, &mut dev_claim_elems) {
 			let suffix_desc = &system.suffix_descs[claim_desc.suffix_desc_idx];
-			PIOPSumcheckClaim {
-				n_vars: suffix_desc.suffix.len(),
-				committed: claim_desc.committed_idx,
-				transparent: idx,
-				sum: eval,
+			let dev_witness = dev_witnesses[claim_desc.committed_idx]
+				.expect("the witness of each claim is copied to the device above");
+			exec.fold_left(
+				SubfieldSlice::new(dev_witness, F::TOWER_LEVEL - suffix_desc.kappa),
+				Hal::DevMem::as_const(&dev_expansions[claim_desc.suffix_desc_idx]),
+				elems,
+			)?;
+		}
+		Ok(Vec::new())
+	})?;
+	Ok(dev_claim_elems)
+}
+
+/// Returns the packed evaluations of a committed multilinear as big field elements.
+///
+/// A multilinear with fewer hypercube evaluations than a packed element holds has one packed
+/// evaluation, which repeats them.
+fn packed_witness_evals<F, P, M>(witness: &M) -> Result<Cow<'_, [F]>, Error>
+where
+	F: TowerField,
+	P: PackedFieldIndexable<Scalar = F>,
+	M: MultilinearPoly<P>,
+{
+	let n_vars = witness.n_vars();
+	let log_extension_degree = witness.log_extension_degree();
+	if n_vars < log_extension_degree {
+		return Ok(Cow::Owned(vec![padded_packed_eval(witness)]));
+	}
+
+	let packed_evals = witness.packed_evals().ok_or_else(|| {
+		Error::InvalidWitness("committed multilinear must have packed evaluations".into())
+	})?;
+	Ok(Cow::Borrowed(&P::unpack_scalars(packed_evals)[..1 << (n_vars - log_extension_degree)]))
+}
+
+/// Computes the row-batched evaluation of each tensor algebra element in device memory.
+///
+/// Folding the vertical elements of an element with the row-batching coefficients is the inner
+/// product of its subfield components with the tensor product of the extension basis and the
+/// coefficients. That query is copied to the device once for each packing degree.
+#[instrument(skip_all)]
+fn compute_row_batched_sumcheck_evals_with_hal<Hal, F>(
+	hal: &Hal,
+	dev_alloc: &BumpAllocator<'_, F, Hal::DevMem>,
+	claim_elems: &[FSliceMut<'_, F, Hal>],
+	row_batch_coeffs: &[F],
+) -> Result<Vec<F>, Error>
+where
+	Hal: ComputeLayer<F>,
+	F: TowerField,
+{
+	let max_kappa = row_batch_coeffs.len().ilog2() as usize;
+	let mut dev_queries = vec![None; max_kappa + 1];
+	for elems in claim_elems {
+		let kappa = elems.len().ilog2() as usize;
+		let dev_query = &mut dev_queries[kappa];
+		if dev_query.is_none() {
+			let iota = F::TOWER_LEVEL - kappa;
+			let mut query = hal.host_alloc(1 << (2 * kappa));
+			for (i, query_chunk) in query.as_mut().chunks_exact_mut(1 << kappa).enumerate() {
+				let basis = <F as TowerField>::basis(iota, i).expect("i is in range 0..1 << kappa");
+				for (query_elem, &coeff) in iter::zip(query_chunk, row_batch_coeffs) {
+					*query_elem = basis * coeff;
+				}
 			}
-		})
-		.collect::<Vec<_>>();
+			let mut dev_query_mut = dev_alloc.alloc(1 << (2 * kappa))?;
+			hal.copy_h2d(query.as_mut(), &mut dev_query_mut)?;
+			*dev_query = Some(Hal::DevMem::to_const(dev_query_mut));
+		}
+	}
 
-	Ok(ReducedWitness {
-		transparents: ring_switch_eq_inds,
-		sumcheck_claims,
-	})
+	let evals = hal.execute(|exec| {
+		claim_elems
+			.iter()
+			.map(|elems| {
+				let kappa = elems.len().ilog2() as usize;
+				let dev_query =
+					dev_queries[kappa].expect("the query of each packing degree is copied above");
+				exec.inner_product(
+					SubfieldSlice::new(Hal::DevMem::as_const(elems), F::TOWER_LEVEL - kappa),
+					dev_query,
+				)
+			})
+			.collect()
+	})?;
+	Ok(evals)
 }
 
+/// Computes the partial evaluation of the witness of each claim as a tensor algebra element, scaled
+/// by the claim's mixing coefficient, into the claim's elements of the [`TensorArena`].
 #[instrument(skip_all)]
-fn compute_partial_evals<F, P, M>(
-	system: &EvalClaimSystem<F>,
+fn compute_partial_evals<Tower, P, M>(
+	system: &EvalClaimSystem<Tower::Top>,
 	witnesses: &[M],
-	mut memoized_data: MemoizedData<P>,
-) -> Result<Vec<TowerTensorAlgebra<F>>, Error>
+	mixing_coeffs: &[Tower::Top],
+	memoized_data: &mut MemoizedData<P>,
+	claim_elems: &mut [&mut [Tower::Top]],
+) -> Result<(), Error>
 where
-	F: TowerTop,
-	P: PackedField<Scalar = F>,
+	Tower: TopTowerFamily,
+	P: PackedTopField<Tower>,
 	M: MultilinearPoly<P> + Sync,
 {
 	let suffixes = system
@@ -147,175 +461,217 @@ where
 		.map(|desc| Arc::as_ref(&desc.suffix))
 		.collect::<Vec<_>>();
 
+	// The distinct suffixes are expanded in parallel, and each expansion is itself split across
+	// threads by `tensor_prod_eq_ind`, so a few long suffixes keep the pool as busy as many short
+	// ones.
 	memoized_data.memoize_query_par(suffixes)?;
 
-	let tensor_elems = system
+	// Precondition
+	assert!(system.sumcheck_claim_descs.len() <= mixing_coeffs.len());
+
+	system
 		.sumcheck_claim_descs
 		.par_iter()
-		.map(
-			|PIOPSumcheckClaimDesc {
-			     committed_idx,
-			     suffix_desc_idx,
-			     eval_claim,
-			 }| {
+		.zip(mixing_coeffs)
+		.zip(claim_elems)
+		.try_for_each(
+			|(
+				(
+					PIOPSumcheckClaimDesc {
+						committed_idx,
+						suffix_desc_idx,
+						eval_claim,
+						..
+					},
+					&mixing_coeff,
+				),
+				elems,
+			)| {
 				let suffix_desc = &system.suffix_descs[*suffix_desc_idx];
+				let witness = &witnesses[*committed_idx];
 
-				let mut elems = if let Some(partial_eval) =
+				// Multilinears at low tower levels are reduced through their bit decomposition,
+				// whose partial evaluations are not memoized.
+				let n_bit_vars = suffix_desc
+					.kappa
+					.saturating_sub(witness.log_extension_degree());
+				let memoized_partial_eval = if n_bit_vars == 0 {
 					memoized_data.partial_eval(eval_claim.id, Arc::as_ref(&suffix_desc.suffix))
-				{
-					PackedField::iter_slice(
-						partial_eval.packed_evals().expect("packed_evals exist"),
-					)
-					.take((1 << suffix_desc.kappa).min(1 << partial_eval.n_vars()))
-					.collect::<Vec<_>>()
 				} else {
-					let suffix_query = memoized_data
-						.full_query_readonly(&suffix_desc.suffix)
-						.expect("memoized above");
-					let partial_eval =
-						witnesses[*committed_idx].evaluate_partial_high(suffix_query.into())?;
-					PackedField::iter_slice(partial_eval.evals())
-						.take((1 << suffix_desc.kappa).min(1 << partial_eval.n_vars()))
-						.collect::<Vec<_>>()
+					None
 				};
 
-				if elems.len() < (1 << suffix_desc.kappa) {
-					elems = elems
-						.into_iter()
-						.cycle()
-						.take(1 << suffix_desc.kappa)
-						.collect();
+				if let Some(partial_eval) = memoized_partial_eval {
+					write_scaled_partial_eval(
+						elems,
+						partial_eval.packed_evals().expect("packed_evals exist"),
+						partial_eval.n_vars(),
+						mixing_coeff,
+					);
+				} else {
+					let suffix_query = memoized_data.full_query_readonly(&suffix_desc.suffix);
+					let partial_eval = if n_bit_vars == 0 {
+						witness.evaluate_partial_high(suffix_query.to_ref())?
+					} else {
+						bit_decomposition::<Tower, _, _>(witness, n_bit_vars)?
+							.evaluate_partial_high(suffix_query.to_ref())?
+					};
+					write_scaled_partial_eval(
+						elems,
+						partial_eval.evals(),
+						partial_eval.n_vars(),
+						mixing_coeff,
+					);
 				}
-				TowerTensorAlgebra::new(suffix_desc.kappa, elems)
+				Ok(())
 			},
 		)
-		.collect::<Result<Vec<_>, _>>()?;
+}
 
-	Ok(tensor_elems)
+/// Writes the evaluations of a partial evaluation, scaled by `mixing_coeff`, to the vertical
+/// elements of a tensor algebra element, repeating them if there are fewer than the elements.
+fn write_scaled_partial_eval<P: PackedField>(
+	elems: &mut [P::Scalar],
+	packed_evals: &[P],
+	n_vars: usize,
+	mixing_coeff: P::Scalar,
+) {
+	let (evals, repeated) = elems.split_at_mut(elems.len().min(1 << n_vars));
+	for (elem, eval) in iter::zip(&mut *evals, PackedField::iter_slice(packed_evals)) {
+		*elem = eval * mixing_coeff;
+	}
+	for (elem, &eval) in iter::zip(repeated, evals.iter().cycle()) {
+		*elem = eval;
+	}
 }
 
-fn scale_tensor_elems<F>(
-	tensor_elems: Vec<TowerTensorAlgebra<F>>,
-	mixing_coeffs: &[F],
-) -> Vec<TowerTensorAlgebra<F>>
+type BitDecomposition<'a, P, FSub> =
+	MLEEmbeddingAdapter<PackedSubfield<P, FSub>, P, &'a [PackedSubfield<P, FSub>]>;
+
+/// Returns the bit decomposition of a committed multilinear at a tower level below
+/// [`super::MIN_SUBFIELD_PACKING_TOWER_LEVEL`], which has the same packed evaluations.
+fn bit_decomposition<Tower, P, M>(
+	multilin: &M,
+	n_bit_vars: usize,
+) -> Result<BitDecomposition<'_, P, Tower::B1>, Error>
 where
-	F: TowerTop,
+	Tower: TopTowerFamily,
+	P: PackedTopField<Tower>,
+	M: MultilinearPoly<P>,
 {
-	// Precondition
-	assert!(tensor_elems.len() <= mixing_coeffs.len());
-
-	tensor_elems
-		.into_par_iter()
-		.zip(mixing_coeffs)
-		.map(|(tensor_elem, &mixing_coeff)| tensor_elem.scale_vertical(mixing_coeff))
-		.collect()
+	let packed_evals = multilin.packed_evals().ok_or_else(|| {
+		Error::InvalidWitness("committed multilinear must have packed evaluations".into())
+	})?;
+	let bits = MultilinearExtension::new(
+		multilin.n_vars() + n_bit_vars,
+		<P as PackedExtension<Tower::B1>>::cast_bases(packed_evals),
+	)?;
+	Ok(MLEEmbeddingAdapter::from(bits))
 }
 
-fn mix_tensor_elems_for_prefixes<F>(
-	scaled_tensor_elems: &[TowerTensorAlgebra<F>],
-	prefix_descs: &[EvalClaimPrefixDesc<F>],
+/// Sums the scaled tensor algebra elements of the claims sharing each evaluation point prefix into
+/// the prefix's elements of the [`TensorArena`].
+///
+/// The vertical elements of each prefix are summed in parallel, each over the claims sharing the
+/// prefix in ascending order, so the work is balanced when most claims share a few prefixes and
+/// the result does not depend on thread scheduling.
+#[instrument(skip_all)]
+fn mix_tensor_elems_for_prefixes<F: Field>(
+	claim_elems: &[&mut [F]],
+	prefix_elems: &mut [&mut [F]],
 	eval_claim_to_prefix_desc_index: &[usize],
-) -> Result<Vec<TowerTensorAlgebra<F>>, Error>
-where
-	F: TowerTop,
-{
+) {
 	// Precondition
-	assert_eq!(scaled_tensor_elems.len(), eval_claim_to_prefix_desc_index.len());
+	assert_eq!(claim_elems.len(), eval_claim_to_prefix_desc_index.len());
 
-	let mut batched_tensor_elems = prefix_descs
-		.iter()
-		.map(|desc| TowerTensorAlgebra::zero(desc.kappa()))
-		.collect::<Result<Vec<_>, _>>()?;
-	for (tensor_elem, &desc_index) in
-		iter::zip(scaled_tensor_elems, eval_claim_to_prefix_desc_index)
-	{
-		let mixed_val = &mut batched_tensor_elems[desc_index];
-		debug_assert_eq!(mixed_val.kappa(), tensor_elem.kappa());
-		mixed_val.add_assign(tensor_elem)?;
+	// Group the claim indices by prefix descriptor so that each prefix can be accumulated
+	// independently.
+	let mut claims_by_prefix = vec![Vec::new(); prefix_elems.len()];
+	for (claim_index, &desc_index) in eval_claim_to_prefix_desc_index.iter().enumerate() {
+		claims_by_prefix[desc_index].push(claim_index);
 	}
-	Ok(batched_tensor_elems)
+
+	prefix_elems
+		.par_iter_mut()
+		.zip(claims_by_prefix)
+		.for_each(|(mixed_elems, claim_indices)| {
+			mixed_elems
+				.par_iter_mut()
+				.enumerate()
+				.for_each(|(i, mixed_elem)| {
+					*mixed_elem = claim_indices
+						.iter()
+						.map(|&claim_index| claim_elems[claim_index][i])
+						.sum();
+				});
+		});
 }
 
 #[instrument(skip_all)]
-fn compute_row_batched_sumcheck_evals<F>(
-	tensor_elems: Vec<TowerTensorAlgebra<F>>,
-	row_batch_coeffs: &[F],
-) -> Vec<F>
+fn compute_row_batched_sumcheck_evals<Tower>(
+	claim_elems: Vec<&mut [Tower::Top]>,
+	row_batch_coeffs: &[Tower::Top],
+) -> Result<Vec<Tower::Top>, Error>
 where
-	F: TowerTop + PackedTop,
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
 {
-	tensor_elems
+	claim_elems
 		.into_par_iter()
-		.map(|tensor_elem| tensor_elem.fold_vertical(row_batch_coeffs))
-		.collect()
-}
-
-fn make_ring_switch_eq_inds<F, P>(
-	sumcheck_claim_descs: &[PIOPSumcheckClaimDesc<F>],
-	suffix_descs: &[EvalClaimSuffixDesc<F>],
-	row_batch_coeffs: Arc<RowBatchCoeffs<F>>,
-	mixing_coeffs: &[F],
-) -> Result<Vec<MultilinearWitness<'static, P>>, Error>
-where
-	F: TowerTop,
-	P: PackedFieldIndexable<Scalar = F> + PackedTop,
-{
-	sumcheck_claim_descs
-		.par_iter()
-		.zip(mixing_coeffs)
-		.map(|(claim_desc, &mixing_coeff)| {
-			let suffix_desc = &suffix_descs[claim_desc.suffix_desc_idx];
-			make_ring_switch_eq_ind(suffix_desc, row_batch_coeffs.clone(), mixing_coeff)
+		.map(|elems| {
+			let kappa = elems.len().ilog2() as usize;
+			TowerTensorAlgebra::<Tower>::fold_vertical_in_place(kappa, elems, row_batch_coeffs)
 		})
 		.collect()
 }
 
-fn make_ring_switch_eq_ind<F, P>(
-	suffix_desc: &EvalClaimSuffixDesc<F>,
-	row_batch_coeffs: Arc<RowBatchCoeffs<F>>,
-	mixing_coeff: F,
+pub(super) fn make_ring_switch_eq_ind<Tower, P>(
+	suffix_desc: &EvalClaimSuffixDesc<Tower::Top>,
+	suffix_expansion: &[P],
+	row_batch_coeffs: Arc<RowBatchCoeffs<Tower::Top>>,
+	mixing_coeff: Tower::Top,
 ) -> Result<MultilinearWitness<'static, P>, Error>
 where
-	F: TowerTop,
-	P: PackedFieldIndexable<Scalar = F> + PackedTop,
+	Tower: TopTowerFamily,
+	P: PackedFieldIndexable<Scalar = Tower::Top> + PackedTopField<Tower>,
 {
-	let eq_ind = match F::TOWER_LEVEL - suffix_desc.kappa {
-		0 => RingSwitchEqInd::<B1, _>::new(
+	let eq_ind = match Tower::Top::TOWER_LEVEL - suffix_desc.kappa {
+		0 => RingSwitchEqInd::<Tower::B1, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?
-		.multilinear_extension::<P>(),
-		3 => RingSwitchEqInd::<B8, _>::new(
+		.multilinear_extension::<P>(suffix_expansion),
+		3 => RingSwitchEqInd::<Tower::B8, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?
-		.multilinear_extension(),
-		4 => RingSwitchEqInd::<B16, _>::new(
+		.multilinear_extension(suffix_expansion),
+		4 => RingSwitchEqInd::<Tower::B16, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?
-		.multilinear_extension(),
-		5 => RingSwitchEqInd::<B32, _>::new(
+		.multilinear_extension(suffix_expansion),
+		5 => RingSwitchEqInd::<Tower::B32, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?
-		.multilinear_extension(),
-		6 => RingSwitchEqInd::<B64, _>::new(
+		.multilinear_extension(suffix_expansion),
+		6 => RingSwitchEqInd::<Tower::B64, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?
-		.multilinear_extension(),
-		7 => RingSwitchEqInd::<B128, _>::new(
+		.multilinear_extension(suffix_expansion),
+		iota if iota == Tower::Top::TOWER_LEVEL => RingSwitchEqInd::<Tower::Top, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?
-		.multilinear_extension(),
+		.multilinear_extension(suffix_expansion),
 		_ => Err(Error::PackingDegreeNotSupported {
 			kappa: suffix_desc.kappa,
 		}),
@@ -0,0 +1,258 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{iter, sync::Arc};
+
+use binius_field::{
+	Field, PackedFieldIndexable, TowerField,
+	tower::{PackedTopField, TopTowerFamily},
+};
+use binius_macros::{DeserializeBytes, SerializeBytes};
+use binius_maybe_rayon::prelude::*;
+use binius_utils::{DeserializeBytes, SerializationError, SerializationMode, SerializeBytes};
+
+use super::{
+	common::{EvalClaimSuffixDesc, EvalClaimSystem},
+	eq_ind::RowBatchCoeffs,
+	error::Error,
+	logging::CalculateRingSwitchEqIndData,
+	prove::{self, ReducedWitness},
+	verify::{self, ReducedClaim},
+};
+use crate::{piop::PIOPSumcheckClaim, protocols::evalcheck::subclaims::MemoizedData};
+
+/// The version of the byte encoding of [`ReducedInstance`], which is written as its first byte.
+const REDUCED_INSTANCE_ENCODING_VERSION: u8 = 1;
+
+/// The PIOP sumcheck claims that the ring-switching reduction of an [`EvalClaimSystem`] reduces
+/// to, with the randomness that determines their transparent polynomials.
+///
+/// The transparent polynomial of each claim is a ring-switching equality indicator, which is
+/// determined by the evaluation point suffix of the claim, its mixing coefficient, and the
+/// row-batching coefficients. Unlike [`ReducedClaim`] and [`ReducedWitness`], which hold the
+/// transparent polynomials, the instance can be serialized, so that a pipelined prover can hand it
+/// to a separate process, or machine, that runs the PIOP. The transparents are reconstructed there
+/// with [`Self::into_witness`] or [`Self::into_claim`].
+///
+/// Instances are produced by [`super::prove_instance`] and [`super::verify_instance`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReducedInstance<F: TowerField> {
+	row_batch_coeffs: Vec<F>,
+	suffix_descs: Vec<EvalClaimSuffixDesc<F>>,
+	transparent_descs: Vec<TransparentDesc<F>>,
+	sumcheck_claims: Vec<PIOPSumcheckClaim<F>>,
+}
+
+/// The ring-switching equality indicator of a sumcheck claim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SerializeBytes, DeserializeBytes)]
+struct TransparentDesc<F: Field> {
+	suffix_desc_idx: usize,
+	mixing_coeff: F,
+}
+
+impl<F: TowerField> ReducedInstance<F> {
+	/// Constructs the reduced instance of a system from the randomness of the reduction and the
+	/// row-batched evaluations of its claims.
+	pub(super) fn new(
+		system: &EvalClaimSystem<F>,
+		row_batch_coeffs: Vec<F>,
+		mixing_coeffs: &[F],
+		row_batched_evals: Vec<F>,
+	) -> Self {
+		let transparent_descs = iter::zip(&system.sumcheck_claim_descs, mixing_coeffs)
+			.map(|(claim_desc, &mixing_coeff)| TransparentDesc {
+				suffix_desc_idx: claim_desc.suffix_desc_idx,
+				mixing_coeff,
+			})
+			.collect();
+		let sumcheck_claims = iter::zip(&system.sumcheck_claim_descs, row_batched_evals)
+			.enumerate()
+			.map(|(idx, (claim_desc, eval))| {
+				let suffix_desc = &system.suffix_descs[claim_desc.suffix_desc_idx];
+				PIOPSumcheckClaim {
+					n_vars: suffix_desc.suffix.len(),
+					committed: claim_desc.committed_idx,
+					transparent: idx,
+					sum: eval,
+				}
+			})
+			.collect();
+		Self {
+			row_batch_coeffs,
+			suffix_descs: system.suffix_descs.clone(),
+			transparent_descs,
+			sumcheck_claims,
+		}
+	}
+
+	/// Returns the PIOP sumcheck claims, whose transparent indices reference the transparents
+	/// reconstructed by [`Self::into_witness`] and [`Self::into_claim`].
+	pub fn sumcheck_claims(&self) -> &[PIOPSumcheckClaim<F>] {
+		&self.sumcheck_claims
+	}
+
+	/// Reconstructs the prover's transparent multilinears of the sumcheck claims.
+	///
+	/// The tensor expansions of the evaluation point suffixes are memoized in `memoized_data`.
+	pub fn into_witness<Tower, P>(
+		self,
+		memoized_data: &mut MemoizedData<P>,
+	) -> Result<ReducedWitness<P>, Error>
+	where
+		Tower: TopTowerFamily<Top = F>,
+		P: PackedFieldIndexable<Scalar = Tower::Top> + PackedTopField<Tower>,
+	{
+		let Self {
+			row_batch_coeffs,
+			suffix_descs,
+			transparent_descs,
+			sumcheck_claims,
+		} = self;
+
+		let dimensions_data = CalculateRingSwitchEqIndData::new(suffix_descs.iter());
+		let _span = tracing::debug_span!(
+			"[task] Calculate Ring Switch Eq Ind",
+			phase = "ring_switch",
+			perfetto_category = "task.main",
+			?dimensions_data,
+		)
+		.entered();
+
+		memoized_data.memoize_query_par(suffix_descs.iter().map(|desc| &*desc.suffix))?;
+		let memoized_data = &*memoized_data;
+
+		let row_batch_coeffs = Arc::new(RowBatchCoeffs::new(row_batch_coeffs));
+		let transparents = transparent_descs
+			.par_iter()
+			.map(|transparent_desc| {
+				let suffix_desc = &suffix_descs[transparent_desc.suffix_desc_idx];
+				let suffix_query = memoized_data.full_query_readonly(&suffix_desc.suffix);
+				prove::make_ring_switch_eq_ind::<Tower, _>(
+					suffix_desc,
+					suffix_query.expansion(),
+					row_batch_coeffs.clone(),
+					transparent_desc.mixing_coeff,
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(ReducedWitness {
+			transparents,
+			sumcheck_claims,
+		})
+	}
+
+	/// Reconstructs the verifier's transparent polynomials of the sumcheck claims.
+	pub fn into_claim<'a, Tower>(self) -> Result<ReducedClaim<'a, F>, Error>
+	where
+		Tower: TopTowerFamily<Top = F>,
+		F: PackedTopField<Tower>,
+	{
+		let row_batch_coeffs = Arc::new(RowBatchCoeffs::new(self.row_batch_coeffs));
+		let transparents = self
+			.transparent_descs
+			.iter()
+			.map(|transparent_desc| {
+				verify::make_ring_switch_eq_ind::<Tower>(
+					&self.suffix_descs[transparent_desc.suffix_desc_idx],
+					row_batch_coeffs.clone(),
+					transparent_desc.mixing_coeff,
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(ReducedClaim {
+			transparents,
+			sumcheck_claims: self.sumcheck_claims,
+		})
+	}
+}
+
+/// Encodes the instance so that the PIOP can be run on it in another process.
+///
+/// The encoding starts with a version byte, followed by the row-batching coefficients, the
+/// evaluation point suffixes with their packing degrees, the suffix index and mixing coefficient
+/// of each transparent, and the sumcheck claims.
+impl<F: TowerField> SerializeBytes for ReducedInstance<F> {
+	fn serialize(
+		&self,
+		mut write_buf: impl bytes::BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		SerializeBytes::serialize(&REDUCED_INSTANCE_ENCODING_VERSION, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.row_batch_coeffs, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.suffix_descs.len(), &mut write_buf, mode)?;
+		for suffix_desc in &self.suffix_descs {
+			SerializeBytes::serialize(&*suffix_desc.suffix, &mut write_buf, mode)?;
+			SerializeBytes::serialize(&suffix_desc.kappa, &mut write_buf, mode)?;
+		}
+		SerializeBytes::serialize(&self.transparent_descs, &mut write_buf, mode)?;
+		SerializeBytes::serialize(&self.sumcheck_claims, write_buf, mode)
+	}
+}
+
+/// Decodes an instance encoded with [`SerializeBytes`].
+///
+/// Instances whose claims reference missing transparents or suffixes, or whose claims and
+/// transparents disagree in the number of variables, are rejected on load.
+impl<F: TowerField> DeserializeBytes for ReducedInstance<F> {
+	fn deserialize(
+		mut read_buf: impl bytes::Buf,
+		mode: SerializationMode,
+	) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let version = u8::deserialize(&mut read_buf, mode)?;
+		if version != REDUCED_INSTANCE_ENCODING_VERSION {
+			return Err(SerializationError::UnsupportedVersion {
+				name: "ReducedInstance",
+				version,
+			});
+		}
+
+		let invalid = SerializationError::InvalidConstruction {
+			name: "ReducedInstance",
+		};
+		let row_batch_coeffs = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let n_suffix_descs: usize = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let suffix_descs = (0..n_suffix_descs)
+			.map(|_| {
+				let suffix: Vec<F> = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+				let kappa: usize = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+				if kappa > F::TOWER_LEVEL {
+					return Err(invalid.clone());
+				}
+				Ok(EvalClaimSuffixDesc {
+					suffix: suffix.into(),
+					kappa,
+				})
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let transparent_descs: Vec<TransparentDesc<F>> =
+			DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let sumcheck_claims: Vec<PIOPSumcheckClaim<F>> =
+			DeserializeBytes::deserialize(read_buf, mode)?;
+
+		if transparent_descs
+			.iter()
+			.any(|desc| desc.suffix_desc_idx >= suffix_descs.len())
+		{
+			return Err(invalid);
+		}
+		for claim in &sumcheck_claims {
+			let Some(transparent_desc) = transparent_descs.get(claim.transparent) else {
+				return Err(invalid);
+			};
+			if claim.n_vars != suffix_descs[transparent_desc.suffix_desc_idx].suffix.len() {
+				return Err(invalid);
+			}
+		}
+
+		Ok(Self {
+			row_batch_coeffs,
+			suffix_descs,
+			transparent_descs,
+			sumcheck_claims,
+		})
+	}
+}
@@ -0,0 +1,113 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{collections::BTreeSet, mem};
+
+use binius_compute::ComputeMemory;
+use binius_field::Field;
+
+use super::common::EvalClaimSystem;
+
+/// Returns the number of field elements of host memory that [`super::prove_with_host_alloc`]
+/// allocates for the tensor algebra elements of an [`EvalClaimSystem`].
+///
+/// The memory holds one tensor algebra element for each sumcheck claim and one for each evaluation
+/// point prefix, each of $2^\kappa$ vertical subring elements.
+pub fn prove_host_mem_size<F: Field>(system: &EvalClaimSystem<F>) -> usize {
+	let claim_elems_size = system
+		.sumcheck_claim_descs
+		.iter()
+		.map(|claim_desc| 1 << system.suffix_descs[claim_desc.suffix_desc_idx].kappa)
+		.sum::<usize>();
+	let prefix_elems_size = system
+		.prefix_descs
+		.iter()
+		.map(|desc| 1 << desc.kappa())
+		.sum::<usize>();
+	claim_elems_size + prefix_elems_size
+}
+
+/// Returns the number of field elements of device memory that [`super::prove_with_hal`] allocates
+/// for an [`EvalClaimSystem`] in the device memory `Mem`.
+///
+/// The memory holds the packed evaluations of each committed multilinear with claims, the tensor
+/// expansion of each evaluation point suffix, one tensor algebra element for each sumcheck claim,
+/// and the row-batching query of each packing degree. Each buffer is padded to the alignment of
+/// `Mem`.
+pub fn prove_dev_mem_size<F: Field, Mem: ComputeMemory<F>>(system: &EvalClaimSystem<F>) -> usize {
+	let aligned = |size: usize| size.max(Mem::ALIGNMENT);
+
+	let mut committed_idxs = BTreeSet::new();
+	let mut kappas = BTreeSet::new();
+	let mut witnesses_size = 0;
+	let mut claim_elems_size = 0;
+	for claim_desc in &system.sumcheck_claim_descs {
+		let suffix_desc = &system.suffix_descs[claim_desc.suffix_desc_idx];
+		if committed_idxs.insert(claim_desc.committed_idx) {
+			witnesses_size += aligned(1 << suffix_desc.suffix.len());
+		}
+		kappas.insert(suffix_desc.kappa);
+		claim_elems_size += aligned(1 << suffix_desc.kappa);
+	}
+	let expansions_size = system
+		.suffix_descs
+		.iter()
+		.map(|desc| aligned(1 << desc.suffix.len()))
+		.sum::<usize>();
+	let queries_size = kappas
+		.into_iter()
+		.map(|kappa| aligned(1 << (2 * kappa)))
+		.sum::<usize>();
+	witnesses_size + expansions_size + claim_elems_size + queries_size
+}
+
+/// The tensor algebra elements of the ring-switching prover, laid out in one slice of host memory.
+///
+/// Each element is represented by its vertical subring elements, a subslice of length $2^\kappa$.
+/// Laying the elements out in one allocation, which is reused across proofs when taken from a
+/// shared allocator, avoids allocating scratch memory for each claim.
+pub(super) struct TensorArena<'a, F> {
+	/// The partial evaluation of each sumcheck claim, scaled by its mixing coefficient.
+	pub claim_elems: Vec<&'a mut [F]>,
+	/// The sum of the scaled elements of the claims sharing each evaluation point prefix.
+	pub prefix_elems: Vec<&'a mut [F]>,
+}
+
+impl<'a, F: Field> TensorArena<'a, F> {
+	/// Partitions host memory into the tensor algebra elements of a system.
+	///
+	/// ## Preconditions
+	///
+	/// * `host_mem` must have length [`prove_host_mem_size`] of `system`
+	pub fn new(system: &EvalClaimSystem<F>, host_mem: &'a mut [F]) -> Self {
+		debug_assert_eq!(host_mem.len(), prove_host_mem_size(system));
+
+		let (claim_elems, host_mem) = split_elems(
+			host_mem,
+			system
+				.sumcheck_claim_descs
+				.iter()
+				.map(|claim_desc| system.suffix_descs[claim_desc.suffix_desc_idx].kappa),
+		);
+		let (prefix_elems, _) =
+			split_elems(host_mem, system.prefix_descs.iter().map(|desc| desc.kappa()));
+		Self {
+			claim_elems,
+			prefix_elems,
+		}
+	}
+}
+
+/// Splits the first elements of `host_mem` into consecutive subslices of length $2^\kappa$ for each
+/// $\kappa$, returning the subslices and the remaining memory.
+fn split_elems<F>(
+	mut host_mem: &mut [F],
+	kappas: impl Iterator<Item = usize>,
+) -> (Vec<&mut [F]>, &mut [F]) {
+	let mut elems = Vec::with_capacity(kappas.size_hint().0);
+	for kappa in kappas {
+		let (elem, rest) = mem::take(&mut host_mem).split_at_mut(1 << kappa);
+		elems.push(elem);
+		host_mem = rest;
+	}
+	(elems, host_mem)
+}
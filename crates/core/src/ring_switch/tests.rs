@@ -1,36 +1,50 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::{cmp::Ordering, iter::repeat_with};
+use std::{
+	cmp::Ordering,
+	iter::{self, repeat_with},
+};
 
-use binius_compute::cpu::CpuLayer;
+use binius_compute::{
+	alloc::{ComputeAllocator, HostBumpAllocator},
+	cpu::{CpuLayer, CpuMemory},
+};
 use binius_field::{
-	ExtensionField, Field, PackedField, PackedFieldIndexable, TowerField,
+	AESTowerField128b, BinaryField2b as B2, BinaryField4b as B4, ExtensionField, Field,
+	PackedField, TowerField,
 	arch::OptimalUnderlier128b,
 	as_packed_field::{PackScalar, PackedType},
+	tower::{
+		AESTowerFamily, Canonical64bTowerFamily, CanonicalTowerFamily, TopTowerFamily,
+		TopTowerUnderlier, TowerUnderlier,
+	},
 	underlier::UnderlierType,
 };
 use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
 use binius_math::{
 	B1, B8, B16, B32, B64, B128, MLEEmbeddingAdapter, MultilinearExtension, MultilinearPoly,
-	MultilinearQuery, PackedTop, TowerTop, TowerUnderlier,
+	MultilinearQuery,
 };
 use binius_ntt::SingleThreadedNTT;
-use binius_utils::{DeserializeBytes, SerializeBytes};
+use binius_utils::{DeserializeBytes, SerializationError, SerializationMode, SerializeBytes};
 use rand::prelude::*;
 
 use super::{
-	common::EvalClaimSystem,
-	prove,
+	ReducedInstance,
+	common::{EvalClaimSystem, MIN_SUBFIELD_PACKING_TOWER_LEVEL},
+	prove, prove_dev_mem_size, prove_host_mem_size, prove_instance, prove_virtual_claims,
+	prove_with_hal, prove_with_host_alloc,
 	verify::{ReducedClaim, verify},
+	verify_instance, verify_virtual_claims,
 };
 use crate::{
 	fiat_shamir::HasherChallenger,
 	merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver, MerkleTreeScheme},
-	oracle::{MultilinearOracleSet, OracleId},
+	oracle::{MultilinearOracleSet, OracleId, ShiftVariant},
 	piop,
 	protocols::{
-		evalcheck::{EvalcheckMultilinearClaim, subclaims::MemoizedData},
-		fri::CommitOutput,
+		evalcheck::{EvalcheckMultilinearClaim, ExpansionCache, subclaims::MemoizedData},
+		fri::{CommitOutput, SoundnessModel},
 	},
 	ring_switch::prove::ReducedWitness,
 	transcript::ProverTranscript,
@@ -55,13 +69,13 @@ where
 	MLEEmbeddingAdapter::from(mle).upcast_arc_dyn()
 }
 
-fn generate_multilinears<U, F>(
+fn generate_multilinears<U, Tower>(
 	mut rng: impl Rng,
-	oracles: &MultilinearOracleSet<F>,
-) -> MultilinearExtensionIndex<PackedType<U, F>>
+	oracles: &MultilinearOracleSet<Tower::Top>,
+) -> MultilinearExtensionIndex<PackedType<U, Tower::Top>>
 where
-	U: TowerUnderlier + PackScalar<F>,
-	F: TowerTop,
+	U: TopTowerUnderlier<Tower>,
+	Tower: TopTowerFamily,
 {
 	let mut witness_index = MultilinearExtensionIndex::new();
 
@@ -69,12 +83,48 @@ where
 		if oracle.variant.is_committed() {
 			let n_vars = oracle.n_vars();
 			let witness = match oracle.binary_tower_level() {
-				0 => generate_multilinear::<U, B1, F>(&mut rng, n_vars),
-				3 => generate_multilinear::<U, B8, F>(&mut rng, n_vars),
-				4 => generate_multilinear::<U, B16, F>(&mut rng, n_vars),
-				5 => generate_multilinear::<U, B32, F>(&mut rng, n_vars),
-				6 => generate_multilinear::<U, B64, F>(&mut rng, n_vars),
-				7 => generate_multilinear::<U, B128, F>(&mut rng, n_vars),
+				0 => generate_multilinear::<U, Tower::B1, Tower::Top>(&mut rng, n_vars),
+				3 => generate_multilinear::<U, Tower::B8, Tower::Top>(&mut rng, n_vars),
+				4 => generate_multilinear::<U, Tower::B16, Tower::Top>(&mut rng, n_vars),
+				5 => generate_multilinear::<U, Tower::B32, Tower::Top>(&mut rng, n_vars),
+				6 => generate_multilinear::<U, Tower::B64, Tower::Top>(&mut rng, n_vars),
+				level if level == Tower::Top::TOWER_LEVEL => {
+					generate_multilinear::<U, Tower::Top, Tower::Top>(&mut rng, n_vars)
+				}
+				_ => panic!("unsupported tower level"),
+			};
+			witness_index
+				.update_multilin_poly([(oracle.id(), witness)])
+				.unwrap();
+		}
+	}
+
+	witness_index
+}
+
+/// Generates witnesses for committed oracles over the canonical tower, including those at tower
+/// levels 1 and 2, which are not subfields of every tower family.
+fn generate_canonical_multilinears<U>(
+	mut rng: impl Rng,
+	oracles: &MultilinearOracleSet<B128>,
+) -> MultilinearExtensionIndex<PackedType<U, B128>>
+where
+	U: TowerUnderlier<CanonicalTowerFamily> + PackScalar<B2> + PackScalar<B4>,
+{
+	let mut witness_index = MultilinearExtensionIndex::new();
+
+	for oracle in oracles.polys() {
+		if oracle.variant.is_committed() {
+			let n_vars = oracle.n_vars();
+			let witness = match oracle.binary_tower_level() {
+				0 => generate_multilinear::<U, B1, B128>(&mut rng, n_vars),
+				1 => generate_multilinear::<U, B2, B128>(&mut rng, n_vars),
+				2 => generate_multilinear::<U, B4, B128>(&mut rng, n_vars),
+				3 => generate_multilinear::<U, B8, B128>(&mut rng, n_vars),
+				4 => generate_multilinear::<U, B16, B128>(&mut rng, n_vars),
+				5 => generate_multilinear::<U, B32, B128>(&mut rng, n_vars),
+				6 => generate_multilinear::<U, B64, B128>(&mut rng, n_vars),
+				7 => generate_multilinear::<U, B128, B128>(&mut rng, n_vars),
 				_ => panic!("unsupported tower level"),
 			};
 			witness_index
@@ -109,21 +159,33 @@ where
 	}
 }
 
-fn check_eval_point_consistency<F: Field>(system: &EvalClaimSystem<F>) {
-	for (i, claim_desc) in system.sumcheck_claim_descs.iter().enumerate() {
-		let prefix_desc_idx = system.eval_claim_to_prefix_desc_index[i];
-		let prefix_desc = &system.prefix_descs[prefix_desc_idx];
-		let suffix_desc = &system.suffix_descs[claim_desc.suffix_desc_idx];
+fn check_eval_point_consistency<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	system: &EvalClaimSystem<F>,
+) {
+	for (i, claim_desc) in system.sumcheck_claim_descs().iter().enumerate() {
+		let prefix_desc_idx = system.eval_claim_to_prefix_desc_index()[i];
+		let prefix_desc = &system.prefix_descs()[prefix_desc_idx];
+		let suffix_desc = &system.suffix_descs()[claim_desc.suffix_desc_idx];
 		assert_eq!(prefix_desc.kappa(), suffix_desc.kappa);
 
-		let eval_point = &*system.sumcheck_claim_descs[i].eval_claim.eval_point;
+		// Claims on multilinears packed by their bit decomposition have the coordinates of the
+		// bit variables prepended to the evaluation point.
+		let tower_level = oracles[claim_desc.eval_claim.id].binary_tower_level();
+		let n_bit_vars = if tower_level < MIN_SUBFIELD_PACKING_TOWER_LEVEL {
+			tower_level
+		} else {
+			0
+		};
+		let eval_point = &*claim_desc.eval_claim.eval_point;
+		let full_point = [prefix_desc.prefix.clone(), suffix_desc.suffix.to_vec()].concat();
 		if suffix_desc.suffix.is_empty() {
-			assert_eq!(&prefix_desc.prefix[..eval_point.len()], eval_point);
+			// Evaluation points with fewer than kappa coordinates are padded with zeros.
+			let (lifted_point, padding) = full_point[n_bit_vars..].split_at(eval_point.len());
+			assert_eq!(lifted_point, eval_point);
+			assert!(padding.iter().all(|&coord| coord == F::ZERO));
 		} else {
-			assert_eq!(
-				&[prefix_desc.prefix.clone(), suffix_desc.suffix.to_vec()].concat(),
-				eval_point
-			);
+			assert_eq!(&full_point[n_bit_vars..], eval_point);
 		}
 	}
 }
@@ -183,18 +245,23 @@ where
 	eval_claims
 }
 
-fn with_test_instance_from_oracles<U, F, R>(
+#[allow(clippy::type_complexity)]
+fn with_test_instance_from_oracles<U, Tower, R>(
 	mut rng: R,
-	oracles: &MultilinearOracleSet<F>,
-	func: impl FnOnce(R, EvalClaimSystem<F>, Vec<MultilinearWitness<PackedType<U, F>>>),
+	oracles: &MultilinearOracleSet<Tower::Top>,
+	func: impl FnOnce(
+		R,
+		EvalClaimSystem<Tower::Top>,
+		Vec<MultilinearWitness<PackedType<U, Tower::Top>>>,
+	),
 ) where
-	U: TowerUnderlier + PackScalar<F>,
-	F: TowerTop,
+	U: TopTowerUnderlier<Tower>,
+	Tower: TopTowerFamily,
 	R: Rng,
 {
 	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(oracles).unwrap();
 
-	let witness_index = generate_multilinears::<U, F>(&mut rng, oracles);
+	let witness_index = generate_multilinears::<U, Tower>(&mut rng, oracles);
 	let witnesses = piop::collect_committed_witnesses::<U, _>(
 		&commit_meta,
 		&oracle_to_commit_index,
@@ -208,7 +275,7 @@ fn with_test_instance_from_oracles<U, F, R>(
 	// Finish setting up the test case
 	let system =
 		EvalClaimSystem::new(oracles, &commit_meta, &oracle_to_commit_index, &eval_claims).unwrap();
-	check_eval_point_consistency(&system);
+	check_eval_point_consistency(oracles, &system);
 
 	func(rng, system, witnesses)
 }
@@ -227,50 +294,621 @@ fn make_test_oracle_set<F: TowerField>() -> MultilinearOracleSet<F> {
 	oracles
 }
 
+#[test]
+fn test_eval_claim_system_summary() {
+	type U = OptimalUnderlier128b;
+
+	let rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set();
+
+	with_test_instance_from_oracles::<U, CanonicalTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, _witnesses| {
+			let summary = system.summary();
+			assert_eq!(summary.n_sumcheck_claims, system.sumcheck_claim_descs().len());
+			assert_eq!(summary.n_prefix_descs, system.prefix_descs().len());
+			assert_eq!(summary.n_suffix_descs, system.suffix_descs().len());
+			assert_eq!(summary.transparent_n_vars.len(), summary.n_suffix_descs);
+			assert_eq!(summary.claim_n_vars.len(), summary.n_sumcheck_claims);
+			assert_eq!(summary.max_kappa, 7 - 3);
+
+			// Each claim is on the packed multilinear of its committed oracle.
+			for (claim_desc, &n_vars) in system
+				.sumcheck_claim_descs()
+				.iter()
+				.zip(&summary.claim_n_vars)
+			{
+				let oracle = &oracles[claim_desc.eval_claim.id];
+				assert_eq!(
+					n_vars,
+					(oracle.n_vars() + oracle.binary_tower_level()).saturating_sub(7)
+				);
+			}
+			assert!(summary.to_string().contains("sumcheck claims"));
+		},
+	);
+}
+
 #[test]
 fn test_prove_verify_claim_reduction_with_naive_validation() {
 	type U = OptimalUnderlier128b;
-	type F = B128;
 
 	let rng = StdRng::seed_from_u64(0);
 	let oracles = make_test_oracle_set();
 
-	with_test_instance_from_oracles::<U, F, _>(rng, &oracles, |_rng, system, witnesses| {
+	with_test_instance_from_oracles::<U, CanonicalTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, witnesses| {
+			let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+
+			let ReducedWitness {
+				transparents: transparent_witnesses,
+				sumcheck_claims: prover_sumcheck_claims,
+			} = prove::<CanonicalTowerFamily, _, _, _>(
+				&system,
+				&witnesses,
+				&mut proof,
+				MemoizedData::new(),
+			)
+			.unwrap();
+
+			let mut proof = proof.into_verifier();
+			let ReducedClaim {
+				transparents: _,
+				sumcheck_claims: verifier_sumcheck_claims,
+			} = verify::<CanonicalTowerFamily, _>(&system, &mut proof).unwrap();
+
+			assert_eq!(prover_sumcheck_claims, verifier_sumcheck_claims);
+
+			let report = piop::validate_sumcheck_witness(
+				&witnesses,
+				&transparent_witnesses,
+				&prover_sumcheck_claims,
+			)
+			.unwrap();
+			assert_eq!(report.first_mismatch(), None);
+		},
+	);
+}
+
+#[test]
+fn test_prove_verify_claim_reduction_aes_tower() {
+	type U = OptimalUnderlier128b;
+
+	let rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set::<AESTowerField128b>();
+
+	with_test_instance_from_oracles::<U, AESTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, witnesses| {
+			let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+
+			let ReducedWitness {
+				transparents: transparent_witnesses,
+				sumcheck_claims: prover_sumcheck_claims,
+			} = prove::<AESTowerFamily, _, _, _>(&system, &witnesses, &mut proof, MemoizedData::new())
+				.unwrap();
+
+			let mut proof = proof.into_verifier();
+			let ReducedClaim {
+				transparents: _,
+				sumcheck_claims: verifier_sumcheck_claims,
+			} = verify::<AESTowerFamily, _>(&system, &mut proof).unwrap();
+			proof.finalize().unwrap();
+
+			assert_eq!(prover_sumcheck_claims, verifier_sumcheck_claims);
+			let report = piop::validate_sumcheck_witness(
+				&witnesses,
+				&transparent_witnesses,
+				&prover_sumcheck_claims,
+			)
+			.unwrap();
+			assert_eq!(report.first_mismatch(), None);
+		},
+	);
+}
+
+#[test]
+fn test_prove_verify_claim_reduction_64b_top_field() {
+	type U = OptimalUnderlier128b;
+	type Tower = Canonical64bTowerFamily;
+
+	let rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set::<B64>();
+
+	let (commit_meta, _) = piop::make_oracle_commit_meta(&oracles).unwrap();
+	assert!(commit_meta.max_tower_level().unwrap() <= B64::TOWER_LEVEL);
+
+	with_test_instance_from_oracles::<U, Tower, _>(rng, &oracles, |_rng, system, witnesses| {
+		assert_eq!(system.max_claim_kappa(), B64::TOWER_LEVEL - 3);
+
+		// The reduction into a 64-bit field requires an explicit security target.
+		let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		assert!(matches!(
+			prove::<Tower, _, _, _>(&system, &witnesses, &mut proof, MemoizedData::new()),
+			Err(super::Error::SecurityBitsRequired { n_bits: 64 })
+		));
+		let system = system.with_security_bits(SECURITY_BITS).unwrap();
+
 		let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
 
 		let ReducedWitness {
 			transparents: transparent_witnesses,
 			sumcheck_claims: prover_sumcheck_claims,
-		} = prove(&system, &witnesses, &mut proof, MemoizedData::new()).unwrap();
+		} = prove::<Tower, _, _, _>(&system, &witnesses, &mut proof, MemoizedData::new()).unwrap();
 
 		let mut proof = proof.into_verifier();
 		let ReducedClaim {
 			transparents: _,
 			sumcheck_claims: verifier_sumcheck_claims,
-		} = verify(&system, &mut proof).unwrap();
+		} = verify::<Tower, _>(&system, &mut proof).unwrap();
+		proof.finalize().unwrap();
 
 		assert_eq!(prover_sumcheck_claims, verifier_sumcheck_claims);
-
-		piop::validate_sumcheck_witness(
+		let report = piop::validate_sumcheck_witness(
 			&witnesses,
 			&transparent_witnesses,
 			&prover_sumcheck_claims,
 		)
 		.unwrap();
+		assert_eq!(report.first_mismatch(), None);
 	});
 }
 
-fn commit_prove_verify_piop<U, F, MTScheme, MTProver>(
+#[test]
+fn test_prove_verify_claim_reduction_many_claims_per_prefix() {
+	type U = OptimalUnderlier128b;
+
+	let rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::new();
+	// Many claims share each prefix.
+	for _ in 0..129 {
+		oracles.add_committed(6, 5);
+	}
+
+	with_test_instance_from_oracles::<U, CanonicalTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, witnesses| {
+			assert!(system.prefix_descs().len() < system.n_sumcheck_claims());
+
+			let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			let ReducedWitness {
+				sumcheck_claims: prover_sumcheck_claims,
+				..
+			} = prove::<CanonicalTowerFamily, _, _, _>(
+				&system,
+				&witnesses,
+				&mut proof,
+				MemoizedData::new(),
+			)
+			.unwrap();
+
+			let mut proof = proof.into_verifier();
+			let ReducedClaim {
+				sumcheck_claims: verifier_sumcheck_claims,
+				..
+			} = verify::<CanonicalTowerFamily, _>(&system, &mut proof).unwrap();
+			proof.finalize().unwrap();
+
+			assert_eq!(prover_sumcheck_claims, verifier_sumcheck_claims);
+		},
+	);
+}
+
+#[test]
+fn test_prove_with_host_alloc_matches_prove() {
+	type U = OptimalUnderlier128b;
+
+	let rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set();
+
+	with_test_instance_from_oracles::<U, CanonicalTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, witnesses| {
+			let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			let reduced = prove::<CanonicalTowerFamily, _, _, _>(
+				&system,
+				&witnesses,
+				&mut proof,
+				MemoizedData::new(),
+			)
+			.unwrap();
+
+			let host_mem_size = prove_host_mem_size(&system);
+			let mut host_mem = vec![B128::ZERO; host_mem_size + 1];
+			let mut host_alloc = HostBumpAllocator::new(host_mem.as_mut_slice());
+			let mut shared_proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			let shared_reduced = prove_with_host_alloc::<CanonicalTowerFamily, _, _, _>(
+				&system,
+				&witnesses,
+				&mut shared_proof,
+				MemoizedData::new(),
+				&mut host_alloc,
+			)
+			.unwrap();
+
+			// The tensor algebra elements are reclaimed by the shared allocator.
+			assert_eq!(host_alloc.capacity(), host_mem_size + 1);
+			assert_eq!(reduced.sumcheck_claims, shared_reduced.sumcheck_claims);
+			assert_eq!(proof.finalize(), shared_proof.finalize());
+
+			let mut host_mem = vec![B128::ZERO; host_mem_size - 1];
+			let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			assert!(matches!(
+				prove_with_host_alloc::<CanonicalTowerFamily, _, _, _>(
+					&system,
+					&witnesses,
+					&mut proof,
+					MemoizedData::new(),
+					&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+				),
+				Err(super::Error::Alloc(_))
+			));
+		},
+	);
+}
+
+fn check_prove_with_hal_matches_prove(
+	system: &EvalClaimSystem<B128>,
+	witnesses: &[MultilinearWitness<PackedType<OptimalUnderlier128b, B128>>],
+) {
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let reduced =
+		prove::<CanonicalTowerFamily, _, _, _>(system, witnesses, &mut proof, MemoizedData::new())
+			.unwrap();
+
+	let hal = CpuLayer::<B128>::default();
+	let host_mem_size = prove_host_mem_size(system);
+	let dev_mem_size = prove_dev_mem_size::<_, CpuMemory>(system);
+	let mut host_mem = vec![B128::ZERO; host_mem_size];
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size + 1];
+	let mut host_alloc = HostBumpAllocator::new(host_mem.as_mut_slice());
+	let mut dev_alloc = HostBumpAllocator::new(dev_mem.as_mut_slice());
+	let mut hal_proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let hal_reduced = prove_with_hal::<_, CanonicalTowerFamily, _, _, _>(
+		&hal,
+		system,
+		witnesses,
+		&mut hal_proof,
+		MemoizedData::new(),
+		&mut host_alloc,
+		&mut dev_alloc,
+	)
+	.unwrap();
+
+	// The device buffers are reclaimed by the shared allocator.
+	assert_eq!(dev_alloc.capacity(), dev_mem_size + 1);
+	assert_eq!(reduced.sumcheck_claims, hal_reduced.sumcheck_claims);
+	assert_eq!(proof.finalize(), hal_proof.finalize());
+
+	let mut dev_mem = vec![B128::ZERO; dev_mem_size - 1];
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	assert!(matches!(
+		prove_with_hal::<_, CanonicalTowerFamily, _, _, _>(
+			&hal,
+			system,
+			witnesses,
+			&mut proof,
+			MemoizedData::new(),
+			&mut host_alloc,
+			&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		),
+		Err(super::Error::Alloc(_))
+	));
+}
+
+#[test]
+fn test_prove_with_hal_matches_prove() {
+	type U = OptimalUnderlier128b;
+
+	let rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set();
+
+	with_test_instance_from_oracles::<U, CanonicalTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, witnesses| check_prove_with_hal_matches_prove(&system, &witnesses),
+	);
+}
+
+#[test]
+fn test_prove_with_hal_matches_prove_small_tower_levels() {
+	type U = OptimalUnderlier128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::<B128>::new();
+	oracles.add_committed(10, 1);
+	oracles.add_committed(8, 0);
+	oracles.add_committed(6, 1); // data is exactly one packed field element
+	oracles.add_committed(4, 2); // data is less than one packed field element
+	oracles.add_committed(2, 3);
+	oracles.add_committed(6, 7);
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+
+	let witness_index = generate_canonical_multilinears::<U>(&mut rng, &oracles);
+	let witnesses = piop::collect_committed_witnesses::<U, _>(
+		&commit_meta,
+		&oracle_to_commit_index,
+		&oracles,
+		&witness_index,
+	)
+	.unwrap();
+	let eval_claims = setup_test_eval_claims::<U, _>(&mut rng, &oracles, &witness_index);
+	let system =
+		EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &eval_claims)
+			.unwrap();
+
+	check_prove_with_hal_matches_prove(&system, &witnesses);
+}
+
+#[test]
+fn test_prove_with_shared_expansion_cache() {
+	type U = OptimalUnderlier128b;
+
+	let rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set();
+
+	with_test_instance_from_oracles::<U, CanonicalTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, witnesses| {
+			let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			let reduced = prove::<CanonicalTowerFamily, _, _, _>(
+				&system,
+				&witnesses,
+				&mut proof,
+				MemoizedData::new(),
+			)
+			.unwrap();
+			let transcript = proof.finalize();
+
+			// The second proof reuses the suffix expansions cached by the first.
+			let cache = ExpansionCache::new();
+			for _ in 0..2 {
+				let mut cached_proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+				let cached_reduced = prove::<CanonicalTowerFamily, _, _, _>(
+					&system,
+					&witnesses,
+					&mut cached_proof,
+					MemoizedData::new().with_expansion_cache(&cache),
+				)
+				.unwrap();
+
+				assert_eq!(cache.len(), system.suffix_descs().len());
+				assert_eq!(reduced.sumcheck_claims, cached_reduced.sumcheck_claims);
+				assert_eq!(transcript, cached_proof.finalize());
+
+				let report = piop::validate_sumcheck_witness(
+					&witnesses,
+					&cached_reduced.transparents,
+					&cached_reduced.sumcheck_claims,
+				)
+				.unwrap();
+				assert_eq!(report.first_mismatch(), None);
+			}
+		},
+	);
+}
+
+#[test]
+fn test_eval_claims_with_fewer_coordinates_than_kappa() {
+	type U = OptimalUnderlier128b;
+	type F = B128;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	oracles.add_committed(0, 3);
+	oracles.add_committed(2, 3);
+	oracles.add_committed(2, 0);
+	oracles.add_committed(3, 1);
+	oracles.add_committed(4, 3);
+	oracles.add_committed(6, 7);
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+
+	let witness_index = generate_canonical_multilinears::<U>(&mut rng, &oracles);
+	let eval_claims = setup_test_eval_claims::<U, _>(&mut rng, &oracles, &witness_index);
+	let system =
+		EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &eval_claims)
+			.unwrap();
+	check_eval_point_consistency(&oracles, &system);
+
+	for claim_desc in system.sumcheck_claim_descs() {
+		let oracle = &oracles[claim_desc.eval_claim.id];
+		let suffix_desc = &system.suffix_descs()[claim_desc.suffix_desc_idx];
+		let n_bit_vars = if oracle.binary_tower_level() < MIN_SUBFIELD_PACKING_TOWER_LEVEL {
+			oracle.binary_tower_level()
+		} else {
+			0
+		};
+		assert_eq!(
+			suffix_desc.suffix.len(),
+			(oracle.n_vars() + n_bit_vars).saturating_sub(suffix_desc.kappa)
+		);
+	}
+
+	// Claims must have one coordinate per variable of the oracle.
+	let mut short_claims = eval_claims.clone();
+	let short_claim = short_claims
+		.iter_mut()
+		.find(|claim| oracles[claim.id].n_vars() == 2)
+		.unwrap();
+	short_claim.eval_point = short_claim.eval_point.slice(0..1);
+	assert!(matches!(
+		EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &short_claims),
+		Err(super::Error::IncorrectEvalPointLength {
+			expected: 2,
+			actual: 1,
+			..
+		})
+	));
+}
+
+#[test]
+fn test_reduced_instance_serialization_round_trip() {
+	type U = OptimalUnderlier128b;
+
+	let rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set();
+	let mode = SerializationMode::CanonicalTower;
+
+	with_test_instance_from_oracles::<U, CanonicalTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, witnesses| {
+			let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			let mut host_mem = vec![B128::ZERO; prove_host_mem_size(&system)];
+			let instance = prove_instance::<CanonicalTowerFamily, _, _, _>(
+				&system,
+				&witnesses,
+				&mut proof,
+				&mut MemoizedData::new(),
+				&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+			)
+			.unwrap();
+
+			// The PIOP prover loads the instance in another process.
+			let mut buf = Vec::new();
+			instance.serialize(&mut buf, mode).unwrap();
+			let loaded = ReducedInstance::<B128>::deserialize(buf.as_slice(), mode).unwrap();
+			assert_eq!(loaded, instance);
+			let witness = loaded
+				.into_witness::<CanonicalTowerFamily, _>(&mut MemoizedData::new())
+				.unwrap();
+			let report = piop::validate_sumcheck_witness(
+				&witnesses,
+				&witness.transparents,
+				&witness.sumcheck_claims,
+			)
+			.unwrap();
+			assert_eq!(report.first_mismatch(), None);
+
+			let mut proof = proof.into_verifier();
+			let verifier_instance =
+				verify_instance::<CanonicalTowerFamily, _>(&system, &mut proof).unwrap();
+			proof.finalize().unwrap();
+			assert_eq!(verifier_instance, instance);
+
+			let mut buf = Vec::new();
+			verifier_instance.serialize(&mut buf, mode).unwrap();
+			let ReducedClaim {
+				transparents,
+				sumcheck_claims,
+			} = ReducedInstance::<B128>::deserialize(buf.as_slice(), mode)
+				.unwrap()
+				.into_claim::<CanonicalTowerFamily>()
+				.unwrap();
+			assert_eq!(sumcheck_claims, witness.sumcheck_claims);
+			for (transparent, transparent_witness) in
+				iter::zip(&transparents, &witness.transparents)
+			{
+				let point =
+					random_eval_point::<B128>(StdRng::seed_from_u64(1), transparent.n_vars());
+				let query = MultilinearQuery::<PackedType<U, B128>>::expand(&point);
+				assert_eq!(
+					transparent.evaluate(&point).unwrap(),
+					transparent_witness.evaluate(query.to_ref()).unwrap()
+				);
+			}
+
+			// Encodings of other versions and truncated encodings are rejected.
+			let mut wrong_version = buf.clone();
+			wrong_version[0] += 1;
+			assert!(matches!(
+				ReducedInstance::<B128>::deserialize(wrong_version.as_slice(), mode),
+				Err(SerializationError::UnsupportedVersion { .. })
+			));
+			assert!(ReducedInstance::<B128>::deserialize(&buf[..buf.len() - 1], mode).is_err());
+		},
+	);
+}
+
+#[test]
+fn test_identical_eval_claims_are_coalesced() {
+	type U = OptimalUnderlier128b;
+	type F = B128;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set::<F>();
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+
+	let witness_index = generate_multilinears::<U, CanonicalTowerFamily>(&mut rng, &oracles);
+	let witnesses = piop::collect_committed_witnesses::<U, _>(
+		&commit_meta,
+		&oracle_to_commit_index,
+		&oracles,
+		&witness_index,
+	)
+	.unwrap();
+
+	let eval_claims = setup_test_eval_claims::<U, _>(&mut rng, &oracles, &witness_index);
+	let unique_system =
+		EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &eval_claims)
+			.unwrap();
+
+	// Duplicate every claim, interleaving the copies with the originals.
+	let duplicated_claims = eval_claims
+		.iter()
+		.flat_map(|claim| [claim.clone(), claim.clone()])
+		.chain(eval_claims.iter().cloned())
+		.collect::<Vec<_>>();
+	let system =
+		EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &duplicated_claims)
+			.unwrap();
+	check_eval_point_consistency(&oracles, &system);
+	assert_eq!(system.n_sumcheck_claims(), unique_system.n_sumcheck_claims());
+	assert_eq!(system.summary(), unique_system.summary());
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let ReducedWitness {
+		transparents: transparent_witnesses,
+		sumcheck_claims: prover_sumcheck_claims,
+	} = prove::<CanonicalTowerFamily, _, _, _>(&system, &witnesses, &mut proof, MemoizedData::new())
+		.unwrap();
+
+	let mut proof = proof.into_verifier();
+	let ReducedClaim {
+		transparents: _,
+		sumcheck_claims: verifier_sumcheck_claims,
+	} = verify::<CanonicalTowerFamily, _>(&system, &mut proof).unwrap();
+	proof.finalize().unwrap();
+
+	assert_eq!(prover_sumcheck_claims, verifier_sumcheck_claims);
+	assert_eq!(prover_sumcheck_claims.len(), eval_claims.len());
+	let report = piop::validate_sumcheck_witness(
+		&witnesses,
+		&transparent_witnesses,
+		&prover_sumcheck_claims,
+	)
+	.unwrap();
+	assert_eq!(report.first_mismatch(), None);
+
+	// Claims on the same oracle and point with different evaluations are not coalesced.
+	let mut conflicting_claims = eval_claims.clone();
+	let mut conflicting_claim = eval_claims[0].clone();
+	conflicting_claim.eval += F::ONE;
+	conflicting_claims.push(conflicting_claim);
+	let system =
+		EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &conflicting_claims)
+			.unwrap();
+	assert_eq!(system.n_sumcheck_claims(), eval_claims.len() + 1);
+}
+
+fn commit_prove_verify_piop<MTScheme, MTProver>(
 	merkle_prover: &MTProver,
-	oracles: &MultilinearOracleSet<F>,
+	oracles: &MultilinearOracleSet<B128>,
 	log_inv_rate: usize,
 ) where
-	U: TowerUnderlier + PackScalar<F>,
-	PackedType<U, F>: PackedFieldIndexable + PackedTop,
-	F: TowerTop + PackedTop<Scalar = F>,
-	MTScheme: MerkleTreeScheme<F, Digest: SerializeBytes + DeserializeBytes>,
-	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	MTScheme: MerkleTreeScheme<B128, Digest: SerializeBytes + DeserializeBytes>,
+	MTProver: MerkleTreeProver<B128, Scheme = MTScheme>,
 {
+	type U = OptimalUnderlier128b;
+	type F = B128;
+
 	let mut rng = StdRng::seed_from_u64(0);
 	let merkle_scheme = merkle_prover.scheme();
 
@@ -281,11 +919,12 @@ fn commit_prove_verify_piop<U, F, MTScheme, MTProver>(
 		merkle_scheme,
 		SECURITY_BITS,
 		log_inv_rate,
+		SoundnessModel::Provable,
 	)
 	.unwrap();
 	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
 
-	let witness_index = generate_multilinears::<U, _>(&mut rng, oracles);
+	let witness_index = generate_canonical_multilinears::<U>(&mut rng, oracles);
 	let committed_multilins = piop::collect_committed_witnesses::<U, _>(
 		&commit_meta,
 		&oracle_to_commit_index,
@@ -305,7 +944,7 @@ fn commit_prove_verify_piop<U, F, MTScheme, MTProver>(
 	// Finish setting up the test case
 	let system =
 		EvalClaimSystem::new(oracles, &commit_meta, &oracle_to_commit_index, &eval_claims).unwrap();
-	check_eval_point_consistency(&system);
+	check_eval_point_consistency(oracles, &system);
 
 	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
 	proof.message().write(&commitment);
@@ -313,7 +952,13 @@ fn commit_prove_verify_piop<U, F, MTScheme, MTProver>(
 	let ReducedWitness {
 		transparents: transparent_multilins,
 		sumcheck_claims,
-	} = prove(&system, &committed_multilins, &mut proof, MemoizedData::new()).unwrap();
+	} = prove::<CanonicalTowerFamily, _, _, _>(
+		&system,
+		&committed_multilins,
+		&mut proof,
+		MemoizedData::new(),
+	)
+	.unwrap();
 
 	let hal = CpuLayer::<F>::default();
 	let host_mem_size_committed = committed_multilins.len();
@@ -332,13 +977,13 @@ fn commit_prove_verify_piop<U, F, MTScheme, MTProver>(
 
 	piop::prove(
 		&hal,
-		&mut host_mem,
-		&mut dev_mem,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
 		&fri_params,
 		&ntt,
 		merkle_prover,
 		&commit_meta,
-		committed,
+		&committed,
 		&codeword,
 		&committed_multilins,
 		&transparent_multilins,
@@ -353,7 +998,7 @@ fn commit_prove_verify_piop<U, F, MTScheme, MTProver>(
 	let ReducedClaim {
 		transparents,
 		sumcheck_claims,
-	} = verify(&system, &mut proof).unwrap();
+	} = verify::<CanonicalTowerFamily, _>(&system, &mut proof).unwrap();
 
 	piop::verify(
 		&commit_meta,
@@ -368,13 +1013,553 @@ fn commit_prove_verify_piop<U, F, MTScheme, MTProver>(
 }
 
 #[test]
-fn test_prove_verify_piop_integration() {
+fn test_eval_claim_system_checks_committed_tower_levels() {
 	type U = OptimalUnderlier128b;
 	type F = B128;
 
+	let mut rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set::<F>();
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+	let witness_index = generate_multilinears::<U, CanonicalTowerFamily>(&mut rng, &oracles);
+	let eval_claims = setup_test_eval_claims::<U, _>(&mut rng, &oracles, &witness_index);
+
+	// The commit metadata of the oracle set records the mix of tower levels in the batch.
+	let tower_levels = commit_meta.tower_levels().unwrap().to_vec();
+	assert!(
+		tower_levels
+			.iter()
+			.any(|&tower_level| tower_level != tower_levels[0])
+	);
+	EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &eval_claims).unwrap();
+
+	let wrong_tower_levels = tower_levels.iter().map(|&level| level ^ 1).collect();
+	let wrong_commit_meta = piop::CommitMeta::new(commit_meta.n_multilins_by_vars().to_vec())
+		.with_tower_levels(wrong_tower_levels)
+		.unwrap();
+	assert!(matches!(
+		EvalClaimSystem::new(&oracles, &wrong_commit_meta, &oracle_to_commit_index, &eval_claims),
+		Err(super::Error::CommittedTowerLevelMismatch { .. })
+	));
+}
+
+#[test]
+fn test_64b_top_field_rejects_insufficient_soundness() {
+	type U = OptimalUnderlier128b;
+
+	let rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set::<B64>();
+
+	with_test_instance_from_oracles::<U, Canonical64bTowerFamily, _>(
+		rng,
+		&oracles,
+		|_rng, system, _witnesses| {
+			let soundness_bits = system.soundness_bits();
+			assert!(matches!(
+				system.with_security_bits(soundness_bits + 1),
+				Err(super::Error::InsufficientSoundness { .. })
+			));
+		},
+	);
+}
+
+#[test]
+fn test_64b_top_field_rejects_128b_committed() {
+	type U = OptimalUnderlier128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set::<B64>();
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+	let witness_index = generate_multilinears::<U, Canonical64bTowerFamily>(&mut rng, &oracles);
+	let eval_claims = setup_test_eval_claims::<U, _>(&mut rng, &oracles, &witness_index);
+
+	// Record one committed multilinear at the 128-bit tower level.
+	let mut tower_levels = commit_meta.tower_levels().unwrap().to_vec();
+	tower_levels[0] = B128::TOWER_LEVEL;
+	let commit_meta_128b = piop::CommitMeta::new(commit_meta.n_multilins_by_vars().to_vec())
+		.with_tower_levels(tower_levels)
+		.unwrap();
+
+	assert!(matches!(
+		EvalClaimSystem::new(&oracles, &commit_meta_128b, &oracle_to_commit_index, &eval_claims),
+		Err(super::Error::CommittedTowerLevelAboveTopField {
+			tower_level: 7,
+			max: 6
+		})
+	));
+
+	let merkle_prover =
+		BinaryMerkleTreeProver::<B64, Groestl256, _>::new(Groestl256ByteCompression);
+	assert!(matches!(
+		piop::make_commit_params_with_optimal_arity::<B64, B32, _>(
+			&commit_meta_128b,
+			merkle_prover.scheme(),
+			SECURITY_BITS,
+			1,
+			SoundnessModel::Provable,
+		),
+		Err(piop::Error::CommittedTowerLevelTooHigh {
+			tower_level: 7,
+			max: 6
+		})
+	));
+	piop::make_commit_params_with_optimal_arity::<B64, B32, _>(
+		&commit_meta,
+		merkle_prover.scheme(),
+		SECURITY_BITS,
+		1,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+}
+
+#[test]
+fn test_prove_verify_piop_integration_64b_top_field() {
+	type U = OptimalUnderlier128b;
+	type Tower = Canonical64bTowerFamily;
+	type F = B64;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let oracles = make_test_oracle_set::<F>();
+	let log_inv_rate = 1;
+	let merkle_prover = BinaryMerkleTreeProver::<F, Groestl256, _>::new(Groestl256ByteCompression);
+	let merkle_scheme = merkle_prover.scheme();
+
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+
+	let fri_params = piop::make_commit_params_with_optimal_arity::<_, B32, _>(
+		&commit_meta,
+		merkle_scheme,
+		SECURITY_BITS,
+		log_inv_rate,
+		SoundnessModel::Provable,
+	)
+	.unwrap();
+	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace()).unwrap();
+
+	let witness_index = generate_multilinears::<U, Tower>(&mut rng, &oracles);
+	let committed_multilins = piop::collect_committed_witnesses::<U, _>(
+		&commit_meta,
+		&oracle_to_commit_index,
+		&oracles,
+		&witness_index,
+	)
+	.unwrap();
+
+	let CommitOutput {
+		commitment,
+		committed,
+		codeword,
+	} = piop::commit(&fri_params, &ntt, &merkle_prover, &committed_multilins).unwrap();
+
+	let eval_claims = setup_test_eval_claims::<U, _>(&mut rng, &oracles, &witness_index);
+	let system =
+		EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &eval_claims)
+			.unwrap()
+			.with_security_bits(SECURITY_BITS)
+			.unwrap();
+	check_eval_point_consistency(&oracles, &system);
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	proof.message().write(&commitment);
+
+	let ReducedWitness {
+		transparents: transparent_multilins,
+		sumcheck_claims,
+	} = prove::<Tower, _, _, _>(&system, &committed_multilins, &mut proof, MemoizedData::new())
+		.unwrap();
+
+	let hal = CpuLayer::<F>::default();
+	let dev_mem_size = committed_multilins
+		.iter()
+		.chain(&transparent_multilins)
+		.map(|multilin| 1 << (multilin.n_vars() + 1))
+		.sum::<usize>();
+	let mut host_mem = vec![F::ZERO; committed_multilins.len() + transparent_multilins.len()];
+	let mut dev_mem = vec![F::ZERO; dev_mem_size];
+
+	piop::prove(
+		&hal,
+		&mut HostBumpAllocator::new(host_mem.as_mut_slice()),
+		&mut HostBumpAllocator::new(dev_mem.as_mut_slice()),
+		&fri_params,
+		&ntt,
+		&merkle_prover,
+		&commit_meta,
+		&committed,
+		&codeword,
+		&committed_multilins,
+		&transparent_multilins,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+
+	let mut proof = proof.into_verifier();
+	let commitment = proof.message().read().unwrap();
+
+	let ReducedClaim {
+		transparents,
+		sumcheck_claims,
+	} = verify::<Tower, _>(&system, &mut proof).unwrap();
+
+	piop::verify(
+		&commit_meta,
+		merkle_scheme,
+		&fri_params,
+		&commitment,
+		&transparents,
+		&sumcheck_claims,
+		&mut proof,
+	)
+	.unwrap();
+	proof.finalize().unwrap();
+}
+
+#[test]
+fn test_prove_verify_piop_integration() {
 	let oracles = make_test_oracle_set();
 	let log_inv_rate = 2;
 	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
 
-	commit_prove_verify_piop::<U, F, _, _>(&merkle_prover, &oracles, log_inv_rate);
+	commit_prove_verify_piop(&merkle_prover, &oracles, log_inv_rate);
+}
+
+#[test]
+fn test_prove_verify_piop_integration_small_tower_levels() {
+	let mut oracles = MultilinearOracleSet::new();
+	oracles.add_committed(10, 1);
+	oracles.add_committed(9, 2);
+	oracles.add_committed(8, 0);
+	oracles.add_committed(8, 2);
+	oracles.add_committed(6, 1); // data is exactly one packed field element
+	oracles.add_committed(4, 2); // data is less than one packed field element
+	oracles.add_committed(2, 1);
+	oracles.add_committed(8, 3);
+	let log_inv_rate = 1;
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	commit_prove_verify_piop(&merkle_prover, &oracles, log_inv_rate);
+}
+
+#[test]
+fn test_prove_verify_virtual_claims() {
+	type U = OptimalUnderlier128b;
+	type F = B128;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let a = oracles.add_committed(8, 3);
+	let b = oracles.add_committed(8, 5);
+	let c = oracles.add_committed(6, 3);
+	let [c0, c1, c2, offset, projected_value] = [(); 5].map(|_| <F as Field>::random(&mut rng));
+	let lincom = oracles
+		.add_linear_combination_with_offset(8, offset, [(a, c0), (b, c1)])
+		.unwrap();
+	let repeating = oracles.add_repeating(c, 2).unwrap();
+	let nested_lincom = oracles
+		.add_linear_combination(8, [(lincom, F::ONE), (repeating, c2)])
+		.unwrap();
+	let projected = oracles.add_projected(a, vec![projected_value], 3).unwrap();
+	let packed = oracles.add_packed(a, 1).unwrap();
+
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+	let witness_index = generate_multilinears::<U, CanonicalTowerFamily>(&mut rng, &oracles);
+	let witnesses = piop::collect_committed_witnesses::<U, _>(
+		&commit_meta,
+		&oracle_to_commit_index,
+		&oracles,
+		&witness_index,
+	)
+	.unwrap();
+
+	let eval_point = random_eval_point::<F>(&mut rng, 8);
+	let [a_eval, b_eval] =
+		[a, b].map(|id| make_eval_claim::<U, F>(id, eval_point.clone(), &witness_index).eval);
+	let c_eval = make_eval_claim::<U, F>(c, eval_point[..6].to_vec(), &witness_index).eval;
+	let projected_point = random_eval_point::<F>(&mut rng, 7);
+	let projected_eval = make_eval_claim::<U, F>(
+		a,
+		[
+			&projected_point[..3],
+			&[projected_value],
+			&projected_point[3..],
+		]
+		.concat(),
+		&witness_index,
+	)
+	.eval;
+
+	let eval_claims = vec![
+		EvalcheckMultilinearClaim {
+			id: nested_lincom,
+			eval_point: eval_point.clone().into(),
+			eval: offset + c0 * a_eval + c1 * b_eval + c2 * c_eval,
+		},
+		EvalcheckMultilinearClaim {
+			id: projected,
+			eval_point: projected_point.into(),
+			eval: projected_eval,
+		},
+		make_eval_claim::<U, F>(b, eval_point.clone(), &witness_index),
+	];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let prover_committed_claims = prove_virtual_claims(
+		&oracles,
+		&oracle_to_commit_index,
+		&witnesses,
+		&eval_claims,
+		&mut proof,
+	)
+	.unwrap();
+	assert!(
+		prover_committed_claims
+			.iter()
+			.all(|claim| oracles[claim.id].variant.is_committed())
+	);
+	assert_eq!(prover_committed_claims.len(), 5);
+
+	let system = EvalClaimSystem::new(
+		&oracles,
+		&commit_meta,
+		&oracle_to_commit_index,
+		&prover_committed_claims,
+	)
+	.unwrap();
+	let ReducedWitness {
+		transparents: transparent_witnesses,
+		sumcheck_claims: prover_sumcheck_claims,
+	} = prove::<CanonicalTowerFamily, _, _, _>(&system, &witnesses, &mut proof, MemoizedData::new())
+		.unwrap();
+
+	let mut proof = proof.into_verifier();
+	let verifier_committed_claims =
+		verify_virtual_claims(&oracles, &eval_claims, &mut proof).unwrap();
+	assert_eq!(prover_committed_claims, verifier_committed_claims);
+
+	let system = EvalClaimSystem::new(
+		&oracles,
+		&commit_meta,
+		&oracle_to_commit_index,
+		&verifier_committed_claims,
+	)
+	.unwrap();
+	let ReducedClaim {
+		transparents: _,
+		sumcheck_claims: verifier_sumcheck_claims,
+	} = verify::<CanonicalTowerFamily, _>(&system, &mut proof).unwrap();
+	proof.finalize().unwrap();
+
+	assert_eq!(prover_sumcheck_claims, verifier_sumcheck_claims);
+	let report = piop::validate_sumcheck_witness(
+		&witnesses,
+		&transparent_witnesses,
+		&prover_sumcheck_claims,
+	)
+	.unwrap();
+	assert_eq!(report.first_mismatch(), None);
+
+	// A claimed evaluation inconsistent with the inner evaluations is rejected.
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prove_virtual_claims(&oracles, &oracle_to_commit_index, &witnesses, &eval_claims, &mut proof)
+		.unwrap();
+	let mut wrong_eval_claims = eval_claims.clone();
+	wrong_eval_claims[0].eval += F::ONE;
+	let mut proof = proof.into_verifier();
+	assert!(matches!(
+		verify_virtual_claims(&oracles, &wrong_eval_claims, &mut proof),
+		Err(super::Error::VerificationError(
+			super::VerificationError::IncorrectLinearCombinationEvaluation { id }
+		)) if id == nested_lincom
+	));
+
+	// Packed oracles require a sumcheck over the tower basis to be reduced, and are left to
+	// evalcheck.
+	let packed_claims = [EvalcheckMultilinearClaim {
+		id: packed,
+		eval_point: eval_point[..7].to_vec().into(),
+		eval: F::ZERO,
+	}];
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	assert!(matches!(
+		prove_virtual_claims(
+			&oracles,
+			&oracle_to_commit_index,
+			&witnesses,
+			&packed_claims,
+			&mut proof,
+		),
+		Err(super::Error::EvalcheckClaimForDerivedPoly { id }) if id == packed
+	));
+}
+
+#[test]
+fn test_prove_verify_shifted_virtual_claims() {
+	type U = OptimalUnderlier128b;
+	type F = B128;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let a = oracles.add_committed(8, 7);
+	let b = oracles.add_committed(8, 5);
+	let block_size = 5;
+	let shift_offset = 3;
+	let shifted = oracles
+		.add_shifted(a, shift_offset, block_size, ShiftVariant::CircularLeft)
+		.unwrap();
+	let lincom = oracles
+		.add_linear_combination(8, [(shifted, F::ONE), (b, F::ONE)])
+		.unwrap();
+
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+	let witness_index = generate_multilinears::<U, CanonicalTowerFamily>(&mut rng, &oracles);
+	let witnesses = piop::collect_committed_witnesses::<U, _>(
+		&commit_meta,
+		&oracle_to_commit_index,
+		&oracles,
+		&witness_index,
+	)
+	.unwrap();
+
+	// Shift the witness of the inner oracle circularly within each block.
+	let a_witness = witness_index.get_multilin_poly(a).unwrap();
+	let block_len = 1 << block_size;
+	let shifted_evals = (0..1 << 8)
+		.map(|i| {
+			let block_start = i - i % block_len;
+			let unshifted_index = (i % block_len + block_len - shift_offset) % block_len;
+			a_witness
+				.evaluate_on_hypercube(block_start + unshifted_index)
+				.unwrap()
+		})
+		.collect::<Vec<F>>();
+	let shifted_mle = MultilinearExtension::from_values(shifted_evals).unwrap();
+
+	let eval_point = random_eval_point::<F>(&mut rng, 8);
+	let shifted_eval: F = shifted_mle
+		.evaluate(MultilinearQuery::<F>::expand(&eval_point).to_ref())
+		.unwrap();
+	let b_eval = make_eval_claim::<U, F>(b, eval_point.clone(), &witness_index).eval;
+	let eval_claims = vec![
+		EvalcheckMultilinearClaim {
+			id: shifted,
+			eval_point: eval_point.clone().into(),
+			eval: shifted_eval,
+		},
+		EvalcheckMultilinearClaim {
+			id: lincom,
+			eval_point: eval_point.into(),
+			eval: shifted_eval + b_eval,
+		},
+	];
+
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let prover_committed_claims = prove_virtual_claims(
+		&oracles,
+		&oracle_to_commit_index,
+		&witnesses,
+		&eval_claims,
+		&mut proof,
+	)
+	.unwrap();
+	assert_eq!(
+		prover_committed_claims
+			.iter()
+			.map(|claim| claim.id)
+			.collect::<Vec<_>>(),
+		vec![a, a, b]
+	);
+	// The reduced claims are true evaluations of the committed witnesses.
+	for claim in &prover_committed_claims {
+		assert_eq!(
+			*claim,
+			make_eval_claim::<U, F>(claim.id, claim.eval_point.to_vec(), &witness_index)
+		);
+	}
+
+	let mut proof = proof.into_verifier();
+	let verifier_committed_claims =
+		verify_virtual_claims(&oracles, &eval_claims, &mut proof).unwrap();
+	proof.finalize().unwrap();
+	assert_eq!(prover_committed_claims, verifier_committed_claims);
+
+	EvalClaimSystem::new(
+		&oracles,
+		&commit_meta,
+		&oracle_to_commit_index,
+		&verifier_committed_claims,
+	)
+	.unwrap();
+
+	// A claimed evaluation inconsistent with the shifted witness fails the shift sumcheck.
+	let mut wrong_eval_claims = eval_claims.clone();
+	wrong_eval_claims[0].eval += F::ONE;
+	let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prove_virtual_claims(
+		&oracles,
+		&oracle_to_commit_index,
+		&witnesses,
+		&wrong_eval_claims,
+		&mut proof,
+	)
+	.unwrap();
+	let mut proof = proof.into_verifier();
+	assert!(matches!(
+		verify_virtual_claims(&oracles, &wrong_eval_claims, &mut proof),
+		Err(super::Error::VerificationError(
+			super::VerificationError::IncorrectShiftedEvaluation { id }
+		)) if id == shifted
+	));
+}
+
+#[test]
+fn test_virtual_claims_malformed_eval_points() {
+	type F = B128;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let a = oracles.add_committed(6, 3);
+	let repeating = oracles.add_repeating(a, 2).unwrap();
+	let projected = oracles
+		.add_projected(a, vec![<F as Field>::random(&mut rng)], 5)
+		.unwrap();
+	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles).unwrap();
+
+	// Claims on repeating and projected oracles are decomposed by the claim system directly.
+	let eval_claims = [
+		EvalcheckMultilinearClaim {
+			id: repeating,
+			eval_point: random_eval_point::<F>(&mut rng, 8).into(),
+			eval: F::ZERO,
+		},
+		EvalcheckMultilinearClaim {
+			id: projected,
+			eval_point: random_eval_point::<F>(&mut rng, 5).into(),
+			eval: F::ZERO,
+		},
+	];
+	let system =
+		EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &eval_claims)
+			.unwrap();
+	assert_eq!(system.n_sumcheck_claims(), 2);
+
+	// Evaluation points shorter than the repeated or projected variables are rejected without
+	// panicking, by the claim system and by the verifier.
+	for (id, n_coords) in [(repeating, 1), (projected, 0)] {
+		let short_claims = [EvalcheckMultilinearClaim {
+			id,
+			eval_point: random_eval_point::<F>(&mut rng, n_coords).into(),
+			eval: F::ZERO,
+		}];
+		assert!(matches!(
+			EvalClaimSystem::new(&oracles, &commit_meta, &oracle_to_commit_index, &short_claims),
+			Err(super::Error::IncorrectEvalPointLength { id: claim_id, .. }) if claim_id == id
+		));
+
+		let mut proof = ProverTranscript::<HasherChallenger<Groestl256>>::new().into_verifier();
+		assert!(matches!(
+			verify_virtual_claims(&oracles, &short_claims, &mut proof),
+			Err(super::Error::IncorrectEvalPointLength { id: claim_id, .. }) if claim_id == id
+		));
+	}
 }
@@ -2,22 +2,23 @@
 
 use std::{iter, sync::Arc};
 
-use binius_field::{Field, TowerField};
-use binius_math::{
-	B1, B8, B16, B32, B64, B128, MultilinearExtension, MultilinearQuery, PackedTop, TowerTop,
+use binius_field::{
+	Field, TowerField,
+	tower::{PackedTopField, TopTowerFamily},
 };
+use binius_math::{MultilinearExtension, MultilinearQuery};
 use binius_utils::checked_arithmetics::log2_ceil_usize;
 use bytes::Buf;
 use itertools::izip;
 
-use super::eq_ind::RowBatchCoeffs;
+use super::{eq_ind::RowBatchCoeffs, reduced_instance::ReducedInstance};
 use crate::{
 	fiat_shamir::{CanSample, Challenger},
 	piop::PIOPSumcheckClaim,
 	polynomial::MultivariatePoly,
 	ring_switch::{
-		Error, EvalClaimSuffixDesc, EvalClaimSystem, PIOPSumcheckClaimDesc, VerificationError,
-		eq_ind::RingSwitchEqInd, tower_tensor_algebra::TowerTensorAlgebra,
+		Error, EvalClaimSuffixDesc, EvalClaimSystem, VerificationError, eq_ind::RingSwitchEqInd,
+		tower_tensor_algebra::TowerTensorAlgebra,
 	},
 	transcript::{TranscriptReader, VerifierTranscript},
 };
@@ -28,14 +29,42 @@ pub struct ReducedClaim<'a, F: Field> {
 	pub sumcheck_claims: Vec<PIOPSumcheckClaim<F>>,
 }
 
-pub fn verify<'a, F, Challenger_>(
-	system: &'a EvalClaimSystem<F>,
+/// Verifies the reduction of the evaluation claims of an [`EvalClaimSystem`] to PIOP sumcheck
+/// claims.
+///
+/// The packing subfields are the ones of the tower family `Tower`, whose top field is
+/// `Tower::Top`, and which may be narrower than 128 bits, as for
+/// [`binius_field::tower::Canonical64bTowerFamily`]. Such a narrower top field requires a security
+/// target, set with [`EvalClaimSystem::with_security_bits`].
+pub fn verify<'a, Tower, Challenger_>(
+	system: &'a EvalClaimSystem<Tower::Top>,
 	transcript: &mut VerifierTranscript<Challenger_>,
-) -> Result<ReducedClaim<'a, F>, Error>
+) -> Result<ReducedClaim<'a, Tower::Top>, Error>
 where
-	F: TowerTop + PackedTop<Scalar = F>,
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
 	Challenger_: Challenger,
 {
+	verify_instance::<Tower, _>(system, transcript)?.into_claim::<Tower>()
+}
+
+/// Verifies the interaction of the ring-switching reduction of an [`EvalClaimSystem`], as
+/// [`verify`], without creating the transparent polynomials of the reduced claims.
+///
+/// The returned [`ReducedInstance`] can be serialized and handed to a separate process that
+/// verifies the PIOP, which creates the transparent polynomials with
+/// [`ReducedInstance::into_claim`].
+pub fn verify_instance<Tower, Challenger_>(
+	system: &EvalClaimSystem<Tower::Top>,
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<ReducedInstance<Tower::Top>, Error>
+where
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
+	Challenger_: Challenger,
+{
+	system.check_security_bits()?;
+
 	// Sample enough randomness to batch tensor elements corresponding to claims that share an
 	// evaluation point prefix.
 	let n_mixing_challenges = log2_ceil_usize(system.sumcheck_claim_descs.len());
@@ -45,13 +74,12 @@ where
 	// For each evaluation point prefix, receive one batched tensor algebra element and verify
 	// that it is consistent with the evaluation claims.
 	let tensor_elems =
-		verify_receive_tensor_elems(system, &mixing_coeffs, &mut transcript.message())?;
+		verify_receive_tensor_elems::<Tower, _>(system, &mixing_coeffs, &mut transcript.message())?;
 
 	// Sample the row-batching randomness.
 	let row_batch_challenges = transcript.sample_vec(system.max_claim_kappa());
-	let row_batch_coeffs = Arc::new(RowBatchCoeffs::new(
-		MultilinearQuery::<F, _>::expand(&row_batch_challenges).into_expansion(),
-	));
+	let row_batch_coeffs =
+		MultilinearQuery::<Tower::Top, _>::expand(&row_batch_challenges).into_expansion();
 
 	// For each original evaluation claim, receive the row-batched evaluation claim.
 	let row_batched_evals = transcript
@@ -66,51 +94,26 @@ where
 		&system.eval_claim_to_prefix_desc_index,
 	);
 	for (expected, tensor_elem) in iter::zip(mixed_row_batched_evals, tensor_elems) {
-		if tensor_elem.fold_vertical(row_batch_coeffs.coeffs()) != expected {
+		if tensor_elem.fold_vertical(&row_batch_coeffs) != expected {
 			return Err(VerificationError::IncorrectRowBatchedSum.into());
 		}
 	}
 
-	// Create the reduced PIOP sumcheck claims.
-	let ring_switch_eq_inds = make_ring_switch_eq_inds(
-		&system.sumcheck_claim_descs,
-		&system.suffix_descs,
-		&row_batch_coeffs,
-		&mixing_coeffs,
-	)?;
-	let sumcheck_claims = iter::zip(&system.sumcheck_claim_descs, row_batched_evals)
-		.enumerate()
-		.map(|(idx, (claim_desc, eval))| {
-			let suffix_desc = &system.suffix_descs[claim_desc.suffix_desc_idx];
-			PIOPSumcheckClaim {
-				n_vars: suffix_desc.suffix.len(),
-				committed: claim_desc.committed_idx,
-				transparent: idx,
-				sum: eval,
-			}
-		})
-		.collect::<Vec<_>>();
-
-	Ok(ReducedClaim {
-		transparents: ring_switch_eq_inds,
-		sumcheck_claims,
-	})
+	Ok(ReducedInstance::new(system, row_batch_coeffs, &mixing_coeffs, row_batched_evals))
 }
 
-fn verify_receive_tensor_elems<F, B>(
-	system: &EvalClaimSystem<F>,
-	mixing_coeffs: &[F],
+fn verify_receive_tensor_elems<Tower, B>(
+	system: &EvalClaimSystem<Tower::Top>,
+	mixing_coeffs: &[Tower::Top],
 	transcript: &mut TranscriptReader<B>,
-) -> Result<Vec<TowerTensorAlgebra<F>>, Error>
+) -> Result<Vec<TowerTensorAlgebra<Tower>>, Error>
 where
-	F: TowerTop + PackedTop<Scalar = F>,
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
 	B: Buf,
 {
 	let expected_tensor_elem_evals = compute_mixed_evaluations(
-		system
-			.sumcheck_claim_descs
-			.iter()
-			.map(|desc| desc.eval_claim.eval),
+		system.sumcheck_claim_descs.iter().map(|desc| desc.eval),
 		system.prefix_descs.len(),
 		&system.eval_claim_to_prefix_desc_index,
 		mixing_coeffs,
@@ -122,9 +125,9 @@ where
 		let tensor_elem =
 			TowerTensorAlgebra::new(kappa, transcript.read_scalar_slice(1 << kappa)?)?;
 
-		let query = MultilinearQuery::<F>::expand(&desc.prefix);
+		let query = MultilinearQuery::<Tower::Top>::expand(&desc.prefix);
 		let tensor_elem_eval =
-			MultilinearExtension::<F, _>::new(kappa, tensor_elem.vertical_elems())
+			MultilinearExtension::<Tower::Top, _>::new(kappa, tensor_elem.vertical_elems())
 				.expect("tensor_elem has length 1 << kappa")
 				.evaluate(&query)
 				.expect("query has kappa variables");
@@ -169,58 +172,42 @@ fn accumulate_evaluations_by_prefixes<F: TowerField>(
 	batched_evals
 }
 
-fn make_ring_switch_eq_inds<F>(
-	sumcheck_claim_descs: &[PIOPSumcheckClaimDesc<F>],
-	suffix_descs: &[EvalClaimSuffixDesc<F>],
-	row_batch_coeffs: &Arc<RowBatchCoeffs<F>>,
-	mixing_coeffs: &[F],
-) -> Result<Vec<Box<dyn MultivariatePoly<F>>>, Error>
-where
-	F: TowerTop + PackedTop<Scalar = F>,
-{
-	iter::zip(sumcheck_claim_descs, mixing_coeffs)
-		.map(|(claim_desc, &mixing_coeff)| {
-			let suffix_desc = &suffix_descs[claim_desc.suffix_desc_idx];
-			make_ring_switch_eq_ind(suffix_desc, row_batch_coeffs.clone(), mixing_coeff)
-		})
-		.collect()
-}
-
-fn make_ring_switch_eq_ind<F>(
-	suffix_desc: &EvalClaimSuffixDesc<F>,
-	row_batch_coeffs: Arc<RowBatchCoeffs<F>>,
-	mixing_coeff: F,
-) -> Result<Box<dyn MultivariatePoly<F>>, Error>
+pub(super) fn make_ring_switch_eq_ind<Tower>(
+	suffix_desc: &EvalClaimSuffixDesc<Tower::Top>,
+	row_batch_coeffs: Arc<RowBatchCoeffs<Tower::Top>>,
+	mixing_coeff: Tower::Top,
+) -> Result<Box<dyn MultivariatePoly<Tower::Top>>, Error>
 where
-	F: TowerTop + PackedTop<Scalar = F>,
+	Tower: TopTowerFamily,
+	Tower::Top: PackedTopField<Tower>,
 {
-	let eq_ind = match F::TOWER_LEVEL - suffix_desc.kappa {
-		0 => Box::new(RingSwitchEqInd::<B1, _>::new(
+	let eq_ind = match Tower::Top::TOWER_LEVEL - suffix_desc.kappa {
+		0 => Box::new(RingSwitchEqInd::<Tower::B1, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?) as Box<dyn MultivariatePoly<_>>,
-		3 => Box::new(RingSwitchEqInd::<B8, _>::new(
+		3 => Box::new(RingSwitchEqInd::<Tower::B8, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?) as Box<dyn MultivariatePoly<_>>,
-		4 => Box::new(RingSwitchEqInd::<B16, _>::new(
+		4 => Box::new(RingSwitchEqInd::<Tower::B16, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?) as Box<dyn MultivariatePoly<_>>,
-		5 => Box::new(RingSwitchEqInd::<B32, _>::new(
+		5 => Box::new(RingSwitchEqInd::<Tower::B32, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?) as Box<dyn MultivariatePoly<_>>,
-		6 => Box::new(RingSwitchEqInd::<B64, _>::new(
+		6 => Box::new(RingSwitchEqInd::<Tower::B64, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
 		)?) as Box<dyn MultivariatePoly<_>>,
-		7 => Box::new(RingSwitchEqInd::<B128, _>::new(
+		iota if iota == Tower::Top::TOWER_LEVEL => Box::new(RingSwitchEqInd::<Tower::Top, _>::new(
 			suffix_desc.suffix.clone(),
 			row_batch_coeffs,
 			mixing_coeff,
@@ -0,0 +1,629 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{iter, marker::PhantomData};
+
+use binius_field::{PackedField, TowerField};
+use binius_math::{MultilinearPoly, MultilinearQuery};
+use binius_utils::sparse_index::SparseIndex;
+use itertools::chain;
+
+use super::error::{Error, VerificationError};
+use crate::{
+	fiat_shamir::{CanSample, Challenger},
+	oracle::{MultilinearOracleSet, MultilinearPolyVariant, OracleId, Shifted},
+	polynomial::MultivariatePoly,
+	protocols::evalcheck::EvalcheckMultilinearClaim,
+	transcript::{ProverTranscript, VerifierTranscript},
+	transparent::shift_ind::ShiftIndPartialEval,
+};
+
+/// Decomposes evaluation claims on virtual oracles into evaluation claims on their committed
+/// ancestors, for an [`super::EvalClaimSystem`].
+///
+/// Claims on committed oracles are kept as they are. A claim on a repeating or projected oracle
+/// is a claim on its inner oracle at the correspondingly extended or truncated point. A claim on
+/// a linear combination is split into claims on its inner oracles at the same point, whose
+/// evaluations the prover writes to the transcript, and which must be consistent with the claimed
+/// evaluation. A claim on a shifted oracle with a committed inner oracle is reduced by a sumcheck
+/// over the shift block, whose round polynomials the prover writes to the transcript, to a claim
+/// on the inner oracle at the sumcheck challenges. The decomposition recurses until every claim is
+/// on a committed oracle, so callers need not run evalcheck on claims that only involve these
+/// oracles.
+///
+/// Claims on other virtual oracles, such as packed oracles, are rejected with
+/// [`Error::EvalcheckClaimForDerivedPoly`]. They must be reduced with evalcheck first.
+///
+/// The decomposition is checked by [`verify_virtual_claims`].
+///
+/// ## Arguments
+///
+/// * `oracles` - the oracle set the claims refer to
+/// * `oracle_to_commit_index` - a sparse index mapping oracle IDs to IDs in the commit metadata
+/// * `witnesses` - the committed multilinear witnesses, in commitment order
+/// * `eval_claims` - the evaluation claims on committed or virtual oracles
+pub fn prove_virtual_claims<F, P, M, Challenger_>(
+	oracles: &MultilinearOracleSet<F>,
+	oracle_to_commit_index: &SparseIndex<usize>,
+	witnesses: &[M],
+	eval_claims: &[EvalcheckMultilinearClaim<F>],
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P>,
+	Challenger_: Challenger,
+{
+	decompose_virtual_claims(
+		oracles,
+		eval_claims,
+		&mut ProverInteraction {
+			oracles,
+			oracle_to_commit_index,
+			witnesses,
+			transcript,
+			_p_marker: PhantomData,
+		},
+	)
+}
+
+/// Verifies the decomposition of evaluation claims on virtual oracles by
+/// [`prove_virtual_claims`], returning the evaluation claims on their committed ancestors.
+pub fn verify_virtual_claims<F, Challenger_>(
+	oracles: &MultilinearOracleSet<F>,
+	eval_claims: &[EvalcheckMultilinearClaim<F>],
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error>
+where
+	F: TowerField,
+	Challenger_: Challenger,
+{
+	decompose_virtual_claims(oracles, eval_claims, &mut VerifierInteraction { transcript })
+}
+
+/// Decomposes the evaluation claims on virtual oracles that are determined by the claim alone,
+/// which are claims on repeating and projected oracles, without a transcript.
+///
+/// This is the decomposition applied by [`super::EvalClaimSystem::new`]. Claims that require an
+/// interaction, on linear combinations and shifted oracles, are rejected with
+/// [`Error::EvalcheckClaimForDerivedPoly`], and must be decomposed with [`prove_virtual_claims`]
+/// and [`verify_virtual_claims`] beforehand.
+pub(super) fn decompose_non_interactive<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	eval_claims: &[EvalcheckMultilinearClaim<F>],
+) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error> {
+	decompose_virtual_claims(oracles, eval_claims, &mut NonInteractive)
+}
+
+/// The interaction of the decomposition of a claim on a virtual oracle whose evaluation is not
+/// determined by the claim alone.
+trait Interaction<F: TowerField> {
+	/// Returns the evaluations of the inner oracles of the linear combination `id` at the
+	/// evaluation point.
+	fn linear_combination_evals(
+		&mut self,
+		id: OracleId,
+		inner_ids: &[OracleId],
+		eval_point: &[F],
+	) -> Result<Vec<F>, Error>;
+
+	/// Reduces an evaluation claim on the shifted oracle `id` to an evaluation claim on its inner
+	/// oracle.
+	///
+	/// The length of the evaluation point must be at least the block size of the shift.
+	fn shifted_claim(
+		&mut self,
+		id: OracleId,
+		shifted: &Shifted,
+		eval_point: &[F],
+		eval: F,
+	) -> Result<EvalcheckMultilinearClaim<F>, Error>;
+}
+
+struct ProverInteraction<'a, F: TowerField, P, M, Challenger_> {
+	oracles: &'a MultilinearOracleSet<F>,
+	oracle_to_commit_index: &'a SparseIndex<usize>,
+	witnesses: &'a [M],
+	transcript: &'a mut ProverTranscript<Challenger_>,
+	_p_marker: PhantomData<P>,
+}
+
+impl<F, P, M, Challenger_> Interaction<F> for ProverInteraction<'_, F, P, M, Challenger_>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P>,
+	Challenger_: Challenger,
+{
+	fn linear_combination_evals(
+		&mut self,
+		_id: OracleId,
+		inner_ids: &[OracleId],
+		eval_point: &[F],
+	) -> Result<Vec<F>, Error> {
+		let evals = inner_ids
+			.iter()
+			.map(|&id| {
+				evaluate_oracle(
+					self.oracles,
+					self.oracle_to_commit_index,
+					self.witnesses,
+					id,
+					eval_point,
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		self.transcript.message().write_scalar_slice(&evals);
+		Ok(evals)
+	}
+
+	fn shifted_claim(
+		&mut self,
+		id: OracleId,
+		shifted: &Shifted,
+		eval_point: &[F],
+		_eval: F,
+	) -> Result<EvalcheckMultilinearClaim<F>, Error> {
+		let (inner_evals, shift_ind_evals) = shifted_sumcheck_multilinears(
+			self.oracles,
+			self.oracle_to_commit_index,
+			self.witnesses,
+			id,
+			shifted,
+			eval_point,
+		)?;
+		prove_shifted_claim(shifted, eval_point, inner_evals, shift_ind_evals, self.transcript)
+	}
+}
+
+struct VerifierInteraction<'a, Challenger_> {
+	transcript: &'a mut VerifierTranscript<Challenger_>,
+}
+
+impl<F, Challenger_> Interaction<F> for VerifierInteraction<'_, Challenger_>
+where
+	F: TowerField,
+	Challenger_: Challenger,
+{
+	fn linear_combination_evals(
+		&mut self,
+		_id: OracleId,
+		inner_ids: &[OracleId],
+		_eval_point: &[F],
+	) -> Result<Vec<F>, Error> {
+		Ok(self
+			.transcript
+			.message()
+			.read_scalar_slice(inner_ids.len())?)
+	}
+
+	fn shifted_claim(
+		&mut self,
+		id: OracleId,
+		shifted: &Shifted,
+		eval_point: &[F],
+		eval: F,
+	) -> Result<EvalcheckMultilinearClaim<F>, Error> {
+		verify_shifted_claim(id, shifted, eval_point, eval, self.transcript)
+	}
+}
+
+struct NonInteractive;
+
+impl<F: TowerField> Interaction<F> for NonInteractive {
+	fn linear_combination_evals(
+		&mut self,
+		id: OracleId,
+		_inner_ids: &[OracleId],
+		_eval_point: &[F],
+	) -> Result<Vec<F>, Error> {
+		Err(Error::EvalcheckClaimForDerivedPoly { id })
+	}
+
+	fn shifted_claim(
+		&mut self,
+		id: OracleId,
+		_shifted: &Shifted,
+		_eval_point: &[F],
+		_eval: F,
+	) -> Result<EvalcheckMultilinearClaim<F>, Error> {
+		Err(Error::EvalcheckClaimForDerivedPoly { id })
+	}
+}
+
+/// Decomposes the claims depth-first, in order, running the interaction for claims on linear
+/// combinations and shifted oracles with `interaction`.
+///
+/// The evaluation point of every claim, including the derived ones, is checked to have as many
+/// coordinates as its oracle has variables, so malformed claims are rejected with an error.
+fn decompose_virtual_claims<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	eval_claims: &[EvalcheckMultilinearClaim<F>],
+	interaction: &mut impl Interaction<F>,
+) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error> {
+	let mut committed_claims = Vec::with_capacity(eval_claims.len());
+	let mut pending = eval_claims.iter().rev().cloned().collect::<Vec<_>>();
+	while let Some(claim) = pending.pop() {
+		let EvalcheckMultilinearClaim {
+			id,
+			eval_point,
+			eval,
+		} = claim;
+		let oracle = &oracles[id];
+		if eval_point.len() != oracle.n_vars() {
+			return Err(Error::IncorrectEvalPointLength {
+				id,
+				expected: oracle.n_vars(),
+				actual: eval_point.len(),
+			});
+		}
+		match &oracle.variant {
+			MultilinearPolyVariant::Committed => {
+				committed_claims.push(EvalcheckMultilinearClaim {
+					id,
+					eval_point,
+					eval,
+				});
+			}
+			&MultilinearPolyVariant::Repeating {
+				id: inner_id,
+				log_count,
+			} => {
+				let n_vars = repeating_inner_n_vars(id, &eval_point, log_count)?;
+				pending.push(EvalcheckMultilinearClaim {
+					id: inner_id,
+					eval_point: eval_point.slice(0..n_vars),
+					eval,
+				});
+			}
+			MultilinearPolyVariant::Projected(projected) => {
+				pending.push(EvalcheckMultilinearClaim {
+					id: projected.id(),
+					eval_point: projected_inner_point(
+						id,
+						&eval_point,
+						projected.values(),
+						projected.start_index(),
+					)?
+					.into(),
+					eval,
+				});
+			}
+			MultilinearPolyVariant::LinearCombination(linear_combination) => {
+				let inner_ids = linear_combination.polys().collect::<Vec<_>>();
+				let evals = interaction.linear_combination_evals(id, &inner_ids, &eval_point)?;
+				let combined_eval = linear_combination.offset()
+					+ iter::zip(&evals, linear_combination.coefficients())
+						.map(|(&eval, coeff)| eval * coeff)
+						.sum::<F>();
+				if combined_eval != eval {
+					return Err(
+						VerificationError::IncorrectLinearCombinationEvaluation { id }.into()
+					);
+				}
+
+				// The inner claims are pushed in reverse, so that they are decomposed in order.
+				for (inner_id, eval) in iter::zip(inner_ids, evals).rev() {
+					pending.push(EvalcheckMultilinearClaim {
+						id: inner_id,
+						eval_point: eval_point.clone(),
+						eval,
+					});
+				}
+			}
+			MultilinearPolyVariant::Shifted(shifted) => {
+				check_shift_block_size(id, &eval_point, shifted)?;
+				pending.push(interaction.shifted_claim(id, shifted, &eval_point, eval)?);
+			}
+			_ => return Err(Error::EvalcheckClaimForDerivedPoly { id }),
+		}
+	}
+	Ok(committed_claims)
+}
+
+/// Reduces an evaluation claim on a shifted oracle to an evaluation claim on its inner oracle.
+///
+/// For a shift with block size $b$ of the inner multilinear $I$, and an evaluation point $(r, s)$
+/// with $b$ coordinates in $r$, the shifted multilinear evaluates to
+///
+/// $$
+/// \sum_{x \in B_b} \textsf{shift-ind}(x, r) \cdot I(x, s).
+/// $$
+///
+/// This is proven with a sumcheck over $b$ variables of the product of the shift indicator and the
+/// partial evaluation of $I$, from the low-indexed variable to the high-indexed one. Each round,
+/// the prover sends the constant and the leading coefficient of the quadratic round polynomial.
+/// The linear coefficient follows from the sum of the round. At the end, the prover sends the
+/// evaluation of $I$ at the sumcheck challenges $\rho$ followed by $s$, which is the reduced claim.
+fn prove_shifted_claim<F, Challenger_>(
+	shifted: &Shifted,
+	eval_point: &[F],
+	mut inner_evals: Vec<F>,
+	mut shift_ind_evals: Vec<F>,
+	transcript: &mut ProverTranscript<Challenger_>,
+) -> Result<EvalcheckMultilinearClaim<F>, Error>
+where
+	F: TowerField,
+	Challenger_: Challenger,
+{
+	let block_size = shifted.block_size();
+	debug_assert_eq!(inner_evals.len(), 1 << block_size);
+	debug_assert_eq!(shift_ind_evals.len(), 1 << block_size);
+
+	let mut challenges = Vec::with_capacity(block_size);
+	for _ in 0..block_size {
+		let (constant_coeff, leading_coeff) = iter::zip(
+			inner_evals.chunks_exact(2),
+			shift_ind_evals.chunks_exact(2),
+		)
+		.fold((F::ZERO, F::ZERO), |(constant_coeff, leading_coeff), (inner, shift_ind)| {
+			(
+				constant_coeff + inner[0] * shift_ind[0],
+				leading_coeff + (inner[1] - inner[0]) * (shift_ind[1] - shift_ind[0]),
+			)
+		});
+		transcript
+			.message()
+			.write_scalar_slice(&[constant_coeff, leading_coeff]);
+
+		let challenge = transcript.sample();
+		fold_low_var(&mut inner_evals, challenge);
+		fold_low_var(&mut shift_ind_evals, challenge);
+		challenges.push(challenge);
+	}
+
+	let inner_eval = inner_evals[0];
+	transcript.message().write_scalar(inner_eval);
+
+	Ok(EvalcheckMultilinearClaim {
+		id: shifted.id(),
+		eval_point: [challenges.as_slice(), &eval_point[block_size..]]
+			.concat()
+			.into(),
+		eval: inner_eval,
+	})
+}
+
+/// Verifies the reduction of an evaluation claim on a shifted oracle by [`prove_shifted_claim`].
+fn verify_shifted_claim<F, Challenger_>(
+	id: OracleId,
+	shifted: &Shifted,
+	eval_point: &[F],
+	eval: F,
+	transcript: &mut VerifierTranscript<Challenger_>,
+) -> Result<EvalcheckMultilinearClaim<F>, Error>
+where
+	F: TowerField,
+	Challenger_: Challenger,
+{
+	let block_size = shifted.block_size();
+
+	let mut sum = eval;
+	let mut challenges = Vec::with_capacity(block_size);
+	for _ in 0..block_size {
+		let coeffs = transcript.message().read_scalar_slice::<F>(2)?;
+		let (constant_coeff, leading_coeff) = (coeffs[0], coeffs[1]);
+		// The round polynomial evaluates to the sum at 0 and 1, which determines its linear
+		// coefficient in characteristic 2.
+		let linear_coeff = sum - leading_coeff;
+
+		let challenge = transcript.sample();
+		sum = constant_coeff + challenge * (linear_coeff + challenge * leading_coeff);
+		challenges.push(challenge);
+	}
+
+	let inner_eval = transcript.message().read_scalar::<F>()?;
+	let shift_ind_eval = shift_ind(shifted, &eval_point[..block_size])?.evaluate(&challenges)?;
+	if inner_eval * shift_ind_eval != sum {
+		return Err(VerificationError::IncorrectShiftedEvaluation { id }.into());
+	}
+
+	Ok(EvalcheckMultilinearClaim {
+		id: shifted.id(),
+		eval_point: [challenges.as_slice(), &eval_point[block_size..]]
+			.concat()
+			.into(),
+		eval: inner_eval,
+	})
+}
+
+/// Returns the hypercube evaluations of the two multilinears of the sumcheck reducing a claim on
+/// a shifted oracle: the partial evaluation of the inner oracle at the coordinates above the shift
+/// block, and the shift indicator at the coordinates of the shift block.
+///
+/// Only shifts of committed oracles are supported, since the partial evaluation of the inner
+/// oracle is taken from its witness.
+fn shifted_sumcheck_multilinears<F, P, M>(
+	oracles: &MultilinearOracleSet<F>,
+	oracle_to_commit_index: &SparseIndex<usize>,
+	witnesses: &[M],
+	id: OracleId,
+	shifted: &Shifted,
+	eval_point: &[F],
+) -> Result<(Vec<F>, Vec<F>), Error>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P>,
+{
+	if !oracles[shifted.id()].variant.is_committed() {
+		return Err(Error::EvalcheckClaimForDerivedPoly { id });
+	}
+	let witness = committed_witness(oracle_to_commit_index, witnesses, shifted.id())?;
+
+	let block_size = shifted.block_size();
+	let (block_point, high_point) = eval_point.split_at(block_size);
+	let partial_eval =
+		witness.evaluate_partial_high(MultilinearQuery::<P>::expand(high_point).to_ref())?;
+	let inner_evals = PackedField::iter_slice(partial_eval.evals())
+		.take(1 << block_size)
+		.collect();
+	let shift_ind_evals = shift_ind(shifted, block_point)?
+		.multilinear_extension::<F>()?
+		.into_evals();
+	Ok((inner_evals, shift_ind_evals))
+}
+
+/// Returns the shift indicator of a shifted oracle, partially evaluated at the coordinates of the
+/// shift block.
+fn shift_ind<F: TowerField>(
+	shifted: &Shifted,
+	block_point: &[F],
+) -> Result<ShiftIndPartialEval<F>, Error> {
+	Ok(ShiftIndPartialEval::new(
+		shifted.block_size(),
+		shifted.shift_offset(),
+		shifted.shift_variant(),
+		block_point.to_vec(),
+	)?)
+}
+
+/// Folds the lowest-indexed variable of a multilinear given by its hypercube evaluations.
+fn fold_low_var<F: TowerField>(evals: &mut Vec<F>, challenge: F) {
+	let half_len = evals.len() / 2;
+	for i in 0..half_len {
+		let (lo, hi) = (evals[2 * i], evals[2 * i + 1]);
+		evals[i] = lo + challenge * (hi - lo);
+	}
+	evals.truncate(half_len);
+}
+
+/// Returns the witness of a committed oracle.
+fn committed_witness<'a, M>(
+	oracle_to_commit_index: &SparseIndex<usize>,
+	witnesses: &'a [M],
+	id: OracleId,
+) -> Result<&'a M, Error> {
+	oracle_to_commit_index
+		.get(id.index())
+		.and_then(|&committed_idx| witnesses.get(committed_idx))
+		.ok_or(Error::OracleToCommitIndexMissingEntry { id })
+}
+
+/// Evaluates an oracle at a point from the witnesses of its committed ancestors.
+fn evaluate_oracle<F, P, M>(
+	oracles: &MultilinearOracleSet<F>,
+	oracle_to_commit_index: &SparseIndex<usize>,
+	witnesses: &[M],
+	id: OracleId,
+	eval_point: &[F],
+) -> Result<F, Error>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P>,
+{
+	let oracle = &oracles[id];
+	if eval_point.len() != oracle.n_vars() {
+		return Err(Error::IncorrectEvalPointLength {
+			id,
+			expected: oracle.n_vars(),
+			actual: eval_point.len(),
+		});
+	}
+	match &oracle.variant {
+		MultilinearPolyVariant::Committed => {
+			let witness = committed_witness(oracle_to_commit_index, witnesses, id)?;
+			let query = MultilinearQuery::<P>::expand(eval_point);
+			Ok(witness.evaluate(query.to_ref())?)
+		}
+		&MultilinearPolyVariant::Repeating {
+			id: inner_id,
+			log_count,
+		} => evaluate_oracle(
+			oracles,
+			oracle_to_commit_index,
+			witnesses,
+			inner_id,
+			&eval_point[..repeating_inner_n_vars(id, eval_point, log_count)?],
+		),
+		MultilinearPolyVariant::Projected(projected) => evaluate_oracle(
+			oracles,
+			oracle_to_commit_index,
+			witnesses,
+			projected.id(),
+			&projected_inner_point(id, eval_point, projected.values(), projected.start_index())?,
+		),
+		MultilinearPolyVariant::LinearCombination(linear_combination) => {
+			let mut eval = linear_combination.offset();
+			for (inner_id, coeff) in
+				iter::zip(linear_combination.polys(), linear_combination.coefficients())
+			{
+				eval += coeff
+					* evaluate_oracle(
+						oracles,
+						oracle_to_commit_index,
+						witnesses,
+						inner_id,
+						eval_point,
+					)?;
+			}
+			Ok(eval)
+		}
+		MultilinearPolyVariant::Shifted(shifted) => {
+			check_shift_block_size(id, eval_point, shifted)?;
+			let (inner_evals, shift_ind_evals) = shifted_sumcheck_multilinears(
+				oracles,
+				oracle_to_commit_index,
+				witnesses,
+				id,
+				shifted,
+				eval_point,
+			)?;
+			Ok(iter::zip(inner_evals, shift_ind_evals)
+				.map(|(inner_eval, shift_ind_eval)| inner_eval * shift_ind_eval)
+				.sum())
+		}
+		_ => Err(Error::EvalcheckClaimForDerivedPoly { id }),
+	}
+}
+
+/// Returns the number of variables of the inner oracle of the repeating oracle `id`.
+fn repeating_inner_n_vars<F: TowerField>(
+	id: OracleId,
+	eval_point: &[F],
+	log_count: usize,
+) -> Result<usize, Error> {
+	eval_point
+		.len()
+		.checked_sub(log_count)
+		.ok_or(Error::IncorrectEvalPointLength {
+			id,
+			expected: log_count,
+			actual: eval_point.len(),
+		})
+}
+
+/// Returns the evaluation point of the inner oracle of the projected oracle `id`.
+fn projected_inner_point<F: TowerField>(
+	id: OracleId,
+	eval_point: &[F],
+	values: &[F],
+	start_index: usize,
+) -> Result<Vec<F>, Error> {
+	if start_index > eval_point.len() {
+		return Err(Error::IncorrectEvalPointLength {
+			id,
+			expected: start_index,
+			actual: eval_point.len(),
+		});
+	}
+	let (lo, hi) = eval_point.split_at(start_index);
+	Ok(chain!(lo, values, hi).copied().collect())
+}
+
+/// Checks that the evaluation point of a claim on the shifted oracle `id` covers the shift block.
+fn check_shift_block_size<F: TowerField>(
+	id: OracleId,
+	eval_point: &[F],
+	shifted: &Shifted,
+) -> Result<(), Error> {
+	if eval_point.len() < shifted.block_size() {
+		return Err(Error::IncorrectEvalPointLength {
+			id,
+			expected: shifted.block_size(),
+			actual: eval_point.len(),
+		});
+	}
+	Ok(())
+}
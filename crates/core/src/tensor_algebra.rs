@@ -147,8 +147,24 @@ impl<F: Field, FE: ExtensionField<F> + PackedExtension<F>> TensorAlgebra<F, FE>
 	/// ## Preconditions
 	///
 	/// * `coeffs` must have length $2^\kappa$
-	pub fn fold_vertical(self, coeffs: &[FE]) -> FE {
-		inner_product_unchecked::<FE, _>(self.transpose().elems, coeffs.iter().copied())
+	pub fn fold_vertical(mut self, coeffs: &[FE]) -> FE {
+		Self::fold_vertical_in_place(&mut self.elems, coeffs)
+	}
+
+	/// Fold a tensor algebra element, given by its vertical subring elements, into a field element
+	/// by scaling the rows and accumulating.
+	///
+	/// This does the same as [`Self::fold_vertical`] on borrowed elements, which are transposed in
+	/// place and so are left in an unspecified state.
+	///
+	/// ## Preconditions
+	///
+	/// * `elems` must have length $2^\kappa$
+	/// * `coeffs` must have length $2^\kappa$
+	pub fn fold_vertical_in_place(elems: &mut [FE], coeffs: &[FE]) -> FE {
+		square_transpose(Self::kappa(), FE::cast_bases_mut(elems))
+			.expect("elems has length 2^kappa by precondition");
+		inner_product_unchecked::<FE, _>(elems.iter().copied(), coeffs.iter().copied())
 	}
 }
 
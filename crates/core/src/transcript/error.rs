@@ -6,6 +6,12 @@ pub enum Error {
 	TranscriptNotEmpty { remaining: usize },
 	#[error("Not enough bytes in the buffer")]
 	NotEnoughBytes,
+	#[error("Attempted to read {len} values, exceeding the limit of {limit}")]
+	ReadLimitExceeded { len: usize, limit: usize },
+	#[error("The prover transcript has no sink")]
+	NoSink,
+	#[error("Failed to write the proof to the sink: {0}")]
+	Io(#[from] std::io::Error),
 	#[error("Serialization error: {0}")]
 	Serialization(#[from] binius_utils::SerializationError),
 }
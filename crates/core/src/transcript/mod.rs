@@ -14,7 +14,13 @@
 
 mod error;
 
-use std::{fs::File, io::Write, iter::repeat_with, slice};
+use std::{
+	fmt,
+	fs::File,
+	io::{self, Write},
+	iter::repeat_with,
+	slice,
+};
 
 use binius_field::{PackedField, TowerField};
 use binius_utils::{DeserializeBytes, SerializationMode, SerializeBytes};
@@ -32,7 +38,34 @@ use crate::fiat_shamir::{CanSample, CanSampleBits, Challenger};
 #[derive(Debug)]
 pub struct ProverTranscript<Challenger> {
 	combined: FiatShamirBuf<BytesMut, Challenger>,
+	/// The state of the challenger before any prover messages, from which the verifier replays.
+	initial_challenger: Challenger,
 	debug_assertions: bool,
+	sink: Option<TranscriptSink>,
+}
+
+/// The default number of buffered proof bytes above which a streaming prover transcript flushes
+/// to its sink.
+pub const DEFAULT_FLUSH_THRESHOLD: usize = 1 << 16;
+
+/// The destination of a streaming prover transcript.
+struct TranscriptSink {
+	writer: Box<dyn Write + Send>,
+	flush_threshold: usize,
+	/// The number of proof bytes written to the sink so far.
+	bytes_written: u64,
+	/// The first error returned by the writer, reported when the transcript is finalized.
+	error: Option<io::Error>,
+}
+
+impl fmt::Debug for TranscriptSink {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TranscriptSink")
+			.field("flush_threshold", &self.flush_threshold)
+			.field("bytes_written", &self.bytes_written)
+			.field("error", &self.error)
+			.finish_non_exhaustive()
+	}
 }
 
 /// Verifier transcript over some Challenger that reads from the internal tape and `CanSample<F:
@@ -40,12 +73,20 @@ pub struct ProverTranscript<Challenger> {
 ///
 /// You must manually call the destructor with `finalize()` to check anything that's written is
 /// fully read out
+///
+/// The proof is untrusted input, so the readers returned by the transcript never allocate for more
+/// values than the proof has bytes left, and reject any single read of more than
+/// [`Self::max_read_len`] values. See [`Self::set_max_read_len`].
 #[derive(Debug, Clone)]
 pub struct VerifierTranscript<Challenger> {
 	combined: FiatShamirBuf<Bytes, Challenger>,
 	debug_assertions: bool,
+	max_read_len: usize,
 }
 
+/// The default maximum number of values in a single vector read from a verifier transcript.
+pub const DEFAULT_MAX_READ_LEN: usize = 1 << 24;
+
 #[derive(Debug, Default, Clone)]
 struct FiatShamirBuf<Inner, Challenger> {
 	buffer: Inner,
@@ -105,27 +146,125 @@ impl<Challenger_: Default + Challenger> ProverTranscript<Challenger_> {
 	/// By default debug assertions are set to the feature flag `debug_assertions`. You may also
 	/// change the debug flag with [`Self::set_debug`].
 	pub fn new() -> Self {
+		Self::with_challenger(Challenger_::default())
+	}
+}
+
+impl<Challenger_: Default + Challenger> Default for ProverTranscript<Challenger_> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Challenger_: Challenger> ProverTranscript<Challenger_> {
+	/// Creates a new prover transcript starting from the given challenger state.
+	///
+	/// This allows binding the challenges to a seed absorbed before any prover messages, see
+	/// [`crate::fiat_shamir::HasherChallenger::with_seed`]. The verifier must start from the same
+	/// state with [`VerifierTranscript::with_challenger`].
+	pub fn with_challenger(challenger: Challenger_) -> Self {
 		Self {
-			combined: Default::default(),
+			combined: FiatShamirBuf {
+				buffer: BytesMut::default(),
+				challenger: challenger.clone(),
+			},
+			initial_challenger: challenger,
 			debug_assertions: cfg!(debug_assertions),
+			sink: None,
 		}
 	}
 
+	/// Converts the transcript into a verifier transcript over the proof bytes.
+	///
+	/// The verifier starts from the same challenger state as the prover, including any seed given
+	/// to [`Self::with_challenger`].
+	///
+	/// ## Panics
+	///
+	/// Panics if a sink was set with [`Self::set_sink`], see [`Self::finalize`].
 	pub fn into_verifier(self) -> VerifierTranscript<Challenger_> {
+		let initial_challenger = self.initial_challenger.clone();
 		let transcript = self.finalize();
 
-		VerifierTranscript::new(transcript)
+		VerifierTranscript::with_challenger(transcript, initial_challenger)
 	}
-}
 
-impl<Challenger_: Default + Challenger> Default for ProverTranscript<Challenger_> {
-	fn default() -> Self {
-		Self::new()
+	/// Streams the proof to a writer while proving.
+	///
+	/// The proof tape is append-only, so every byte written to it is final. Whenever a challenge is
+	/// sampled and more than `flush_threshold` bytes are buffered, the buffered bytes are written
+	/// to `writer` and dropped from memory. Any bytes buffered before this call are written at the
+	/// next flush. The proof must then be completed with [`Self::finalize_to_sink`], as
+	/// [`Self::finalize`] and [`Self::into_verifier`] panic on a transcript with a sink.
+	pub fn set_sink(&mut self, writer: impl Write + Send + 'static, flush_threshold: usize) {
+		self.sink = Some(TranscriptSink {
+			writer: Box::new(writer),
+			flush_threshold,
+			bytes_written: 0,
+			error: None,
+		});
 	}
-}
 
-impl<Challenger_: Challenger> ProverTranscript<Challenger_> {
+	/// Writes the buffered proof bytes to the sink, if there is one.
+	///
+	/// Errors of the writer are deferred to [`Self::finalize_to_sink`], so that flushing can happen
+	/// from infallible methods such as challenge sampling.
+	pub fn flush(&mut self) {
+		let Some(sink) = &mut self.sink else {
+			return;
+		};
+		if sink.error.is_some() {
+			return;
+		}
+		let buffer = &mut self.combined.buffer;
+		match sink
+			.writer
+			.write_all(buffer)
+			.and_then(|()| sink.writer.flush())
+		{
+			Ok(()) => {
+				sink.bytes_written += buffer.len() as u64;
+				buffer.clear();
+			}
+			Err(err) => sink.error = Some(err),
+		}
+	}
+
+	/// Flushes the buffered bytes if there is a sink and they exceed its threshold.
+	fn maybe_flush(&mut self) {
+		if self
+			.sink
+			.as_ref()
+			.is_some_and(|sink| self.combined.buffer.len() > sink.flush_threshold)
+		{
+			self.flush();
+		}
+	}
+
+	/// Writes the rest of the proof to the sink and returns the total number of proof bytes
+	/// written to it.
+	///
+	/// Returns [`Error::NoSink`] if [`Self::set_sink`] was not called.
+	pub fn finalize_to_sink(mut self) -> Result<u64, Error> {
+		self.flush();
+		let sink = self.sink.take().ok_or(Error::NoSink)?;
+		if let Some(err) = sink.error {
+			return Err(err.into());
+		}
+		Ok(sink.bytes_written)
+	}
+
+	/// Returns the proof bytes.
+	///
+	/// ## Panics
+	///
+	/// Panics if a sink was set with [`Self::set_sink`], as part of the proof may already have been
+	/// flushed to it. Use [`Self::finalize_to_sink`] instead.
 	pub fn finalize(self) -> Vec<u8> {
+		assert!(
+			self.sink.is_none(),
+			"a transcript with a sink must be finalized with finalize_to_sink"
+		);
 		let transcript = self.combined.buffer.to_vec();
 
 		// Dumps the transcript to the path set in the BINIUS_DUMP_PROOF env variable.
@@ -210,17 +349,26 @@ impl<Challenger_: Challenger> ProverTranscript<Challenger_> {
 
 impl<Challenger_: Default + Challenger> VerifierTranscript<Challenger_> {
 	pub fn new(vec: Vec<u8>) -> Self {
+		Self::with_challenger(vec, Challenger_::default())
+	}
+}
+
+impl<Challenger_: Challenger> VerifierTranscript<Challenger_> {
+	/// Creates a new verifier transcript for the given proof, starting from the given challenger
+	/// state.
+	///
+	/// See [`ProverTranscript::with_challenger`].
+	pub fn with_challenger(vec: Vec<u8>, challenger: Challenger_) -> Self {
 		Self {
 			combined: FiatShamirBuf {
-				challenger: Challenger_::default(),
+				challenger,
 				buffer: Bytes::from(vec),
 			},
 			debug_assertions: cfg!(debug_assertions),
+			max_read_len: DEFAULT_MAX_READ_LEN,
 		}
 	}
-}
 
-impl<Challenger_: Challenger> VerifierTranscript<Challenger_> {
 	pub fn finalize(self) -> Result<(), Error> {
 		if self.combined.buffer.has_remaining() {
 			return Err(Error::TranscriptNotEmpty {
@@ -234,6 +382,19 @@ impl<Challenger_: Challenger> VerifierTranscript<Challenger_> {
 		self.debug_assertions = debug;
 	}
 
+	/// The maximum number of values in a single vector read from the transcript.
+	pub const fn max_read_len(&self) -> usize {
+		self.max_read_len
+	}
+
+	/// Sets the maximum number of values in a single vector read from the transcript.
+	///
+	/// Reads of longer vectors fail with [`Error::ReadLimitExceeded`] before anything is
+	/// allocated. Defaults to [`DEFAULT_MAX_READ_LEN`].
+	pub const fn set_max_read_len(&mut self, max_read_len: usize) {
+		self.max_read_len = max_read_len;
+	}
+
 	/// Returns a writable buffer that only observes the data written, without reading it from the
 	/// proof tape.
 	///
@@ -257,6 +418,7 @@ impl<Challenger_: Challenger> VerifierTranscript<Challenger_> {
 		TranscriptReader {
 			buffer: &mut self.combined.buffer,
 			debug_assertions: self.debug_assertions,
+			max_read_len: self.max_read_len,
 		}
 	}
 
@@ -270,6 +432,7 @@ impl<Challenger_: Challenger> VerifierTranscript<Challenger_> {
 		TranscriptReader {
 			buffer: &mut self.combined,
 			debug_assertions: self.debug_assertions,
+			max_read_len: self.max_read_len,
 		}
 	}
 }
@@ -289,9 +452,20 @@ impl<Challenger> Drop for VerifierTranscript<Challenger> {
 pub struct TranscriptReader<'a, B: Buf> {
 	buffer: &'a mut B,
 	debug_assertions: bool,
+	max_read_len: usize,
 }
 
-impl<B: Buf> TranscriptReader<'_, B> {
+impl<'a, B: Buf> TranscriptReader<'a, B> {
+	/// Returns a reader of the data in `buffer`, which is neither observed nor read from a proof
+	/// tape.
+	pub(crate) const fn from_buffer(buffer: &'a mut B) -> Self {
+		Self {
+			buffer,
+			debug_assertions: false,
+			max_read_len: DEFAULT_MAX_READ_LEN,
+		}
+	}
+
 	pub const fn buffer(&mut self) -> &mut B {
 		self.buffer
 	}
@@ -302,6 +476,7 @@ impl<B: Buf> TranscriptReader<'_, B> {
 	}
 
 	pub fn read_vec<T: DeserializeBytes>(&mut self, n: usize) -> Result<Vec<T>, Error> {
+		self.check_read_len(n)?;
 		let mode = SerializationMode::CanonicalTower;
 		let mut buffer = self.buffer();
 		repeat_with(move || T::deserialize(&mut buffer, mode).map_err(Into::into))
@@ -334,6 +509,7 @@ impl<B: Buf> TranscriptReader<'_, B> {
 	}
 
 	pub fn read_scalar_slice<F: TowerField>(&mut self, len: usize) -> Result<Vec<F>, Error> {
+		self.check_read_len(len)?;
 		let mut elems = vec![F::default(); len];
 		self.read_scalar_slice_into(&mut elems)?;
 		Ok(elems)
@@ -347,6 +523,7 @@ impl<B: Buf> TranscriptReader<'_, B> {
 		&mut self,
 		len: usize,
 	) -> Result<Vec<P>, Error> {
+		self.check_read_len(len)?;
 		let mut packed = Vec::with_capacity(len);
 		for _ in 0..len {
 			packed.push(self.read_packed()?);
@@ -362,6 +539,23 @@ impl<B: Buf> TranscriptReader<'_, B> {
 			assert_eq!(msg_bytes, buffer);
 		}
 	}
+
+	/// Checks that a vector of `len` values may be read, before allocating for it.
+	///
+	/// Every value occupies at least one byte of the proof, so a vector longer than the remaining
+	/// proof can be rejected up front.
+	fn check_read_len(&self, len: usize) -> Result<(), Error> {
+		if len > self.max_read_len {
+			return Err(Error::ReadLimitExceeded {
+				len,
+				limit: self.max_read_len,
+			});
+		}
+		if len > self.buffer.remaining() {
+			return Err(Error::NotEnoughBytes);
+		}
+		Ok(())
+	}
 }
 
 pub struct TranscriptWriter<'a, B: BufMut> {
@@ -463,6 +657,7 @@ where
 	Challenger_: Challenger,
 {
 	fn sample(&mut self) -> F {
+		self.maybe_flush();
 		let mode = SerializationMode::CanonicalTower;
 		DeserializeBytes::deserialize(self.combined.challenger.sampler(), mode)
 			.expect("challenger has infinite buffer")
@@ -501,10 +696,66 @@ where
 	Challenger_: Challenger,
 {
 	fn sample_bits(&mut self, bits: usize) -> u32 {
+		self.maybe_flush();
 		sample_bits_reader(self.combined.challenger.sampler(), bits)
 	}
 }
 
+impl<Challenger_> ProverTranscript<Challenger_>
+where
+	Challenger_: Challenger,
+{
+	/// Finds a proof-of-work nonce and writes it to the transcript.
+	///
+	/// The nonce is the least `u64` such that the first `bits` bits sampled after observing it are
+	/// all zero. Sending the nonce costs the prover about `2^bits` challenger evaluations and makes
+	/// the challenges sampled afterwards that much more expensive to bias. Nothing is written when
+	/// `bits` is zero.
+	///
+	/// ## Preconditions
+	///
+	/// * `bits` must be at most 32
+	pub fn grind(&mut self, bits: usize) -> u64 {
+		assert!(bits <= u32::BITS as usize);
+		if bits == 0 {
+			return 0;
+		}
+
+		self.maybe_flush();
+		let nonce = (0..u64::MAX)
+			.find(|&nonce| {
+				let mut challenger = self.combined.challenger.clone();
+				challenger.observer().put_u64_le(nonce);
+				sample_bits_reader(challenger.sampler(), bits) == 0
+			})
+			.expect("a nonce is found with overwhelming probability");
+		self.message().write(&nonce);
+		// Sample the proof-of-work bits as the verifier does, to keep the challengers in sync.
+		let pow_bits = self.sample_bits(bits);
+		debug_assert_eq!(pow_bits, 0);
+		nonce
+	}
+}
+
+impl<Challenger_> VerifierTranscript<Challenger_>
+where
+	Challenger_: Challenger,
+{
+	/// Reads a proof-of-work nonce written by [`ProverTranscript::grind`] and checks it.
+	///
+	/// Returns whether the first `bits` bits sampled after observing the nonce are all zero.
+	/// Nothing is read when `bits` is zero.
+	pub fn check_grinding(&mut self, bits: usize) -> Result<bool, Error> {
+		assert!(bits <= u32::BITS as usize);
+		if bits == 0 {
+			return Ok(true);
+		}
+
+		self.message().read::<u64>()?;
+		Ok(self.sample_bits(bits) == 0)
+	}
+}
+
 /// Helper functions for serializing native types
 pub fn read_u64<B: Buf>(transcript: &mut TranscriptReader<B>) -> Result<u64, Error> {
 	let mut as_bytes = [0; size_of::<u64>()];
@@ -701,4 +952,194 @@ mod tests {
 			.message()
 			.read_debug("test_transcript_debug_should_fail");
 	}
+
+	#[test]
+	fn test_read_limits() {
+		let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		prover_transcript
+			.message()
+			.write_scalar_slice(&[BinaryField32b::new(1); 8]);
+
+		let proof = prover_transcript.finalize();
+
+		// A length beyond the remaining proof is rejected before allocating.
+		let mut verifier_transcript =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+		assert!(matches!(
+			verifier_transcript
+				.message()
+				.read_scalar_slice::<BinaryField32b>(1 << 20),
+			Err(Error::NotEnoughBytes)
+		));
+
+		let mut verifier_transcript =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof);
+		verifier_transcript.set_max_read_len(4);
+		assert!(matches!(
+			verifier_transcript.message().read_vec::<BinaryField32b>(8),
+			Err(Error::ReadLimitExceeded { len: 8, limit: 4 })
+		));
+		for _ in 0..2 {
+			let values = verifier_transcript
+				.message()
+				.read_scalar_slice::<BinaryField32b>(4)
+				.unwrap();
+			assert_eq!(values, vec![BinaryField32b::new(1); 4]);
+		}
+		verifier_transcript.finalize().unwrap();
+	}
+
+	#[test]
+	fn test_seeded_transcripts() {
+		let seeded_challenger = || HasherChallenger::<Groestl256>::with_seed(b"binius-v1");
+
+		let mut prover_transcript = ProverTranscript::with_challenger(seeded_challenger());
+		prover_transcript
+			.message()
+			.write_scalar(BinaryField128b::new(0x1234));
+		let prover_sample: BinaryField128b = prover_transcript.sample();
+		let proof = prover_transcript.finalize();
+
+		let mut verifier_transcript =
+			VerifierTranscript::with_challenger(proof.clone(), seeded_challenger());
+		verifier_transcript
+			.message()
+			.read_scalar::<BinaryField128b>()
+			.unwrap();
+		let verifier_sample: BinaryField128b = verifier_transcript.sample();
+		assert_eq!(verifier_sample, prover_sample);
+		verifier_transcript.finalize().unwrap();
+
+		// A verifier with a different seed derives different challenges from the same proof.
+		let mut unseeded_transcript =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof);
+		unseeded_transcript
+			.message()
+			.read_scalar::<BinaryField128b>()
+			.unwrap();
+		let unseeded_sample: BinaryField128b = unseeded_transcript.sample();
+		assert_ne!(unseeded_sample, prover_sample);
+		unseeded_transcript.finalize().unwrap();
+	}
+
+	#[test]
+	fn test_seeded_transcript_into_verifier() {
+		let mut prover_transcript = ProverTranscript::with_challenger(
+			HasherChallenger::<Groestl256>::with_seed(b"binius-v1"),
+		);
+		prover_transcript
+			.message()
+			.write_scalar(BinaryField128b::new(0x1234));
+		let prover_samples: [BinaryField128b; 2] =
+			[prover_transcript.sample(), prover_transcript.sample()];
+
+		let mut verifier_transcript = prover_transcript.into_verifier();
+		verifier_transcript
+			.message()
+			.read_scalar::<BinaryField128b>()
+			.unwrap();
+		let verifier_samples: [BinaryField128b; 2] =
+			[verifier_transcript.sample(), verifier_transcript.sample()];
+		assert_eq!(verifier_samples, prover_samples);
+		verifier_transcript.finalize().unwrap();
+	}
+
+	#[test]
+	fn test_grinding() {
+		let grinding_bits = 12;
+
+		let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		prover_transcript
+			.message()
+			.write_scalar(BinaryField128b::new(0x1234));
+		let nonce = prover_transcript.grind(grinding_bits);
+		let prover_sample: BinaryField128b = prover_transcript.sample();
+		let proof = prover_transcript.finalize();
+
+		let mut verifier_transcript =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(proof.clone());
+		verifier_transcript
+			.message()
+			.read_scalar::<BinaryField128b>()
+			.unwrap();
+		assert!(verifier_transcript.check_grinding(grinding_bits).unwrap());
+		let verifier_sample: BinaryField128b = verifier_transcript.sample();
+		assert_eq!(verifier_sample, prover_sample);
+		verifier_transcript.finalize().unwrap();
+
+		// The prover finds the least nonce, so any smaller nonce is rejected.
+		assert!(nonce > 0);
+		let mut tampered_proof = proof;
+		let nonce_offset = tampered_proof.len() - size_of::<u64>();
+		tampered_proof[nonce_offset..].copy_from_slice(&(nonce - 1).to_le_bytes());
+		let mut verifier_transcript =
+			VerifierTranscript::<HasherChallenger<Groestl256>>::new(tampered_proof);
+		verifier_transcript
+			.message()
+			.read_scalar::<BinaryField128b>()
+			.unwrap();
+		assert!(!verifier_transcript.check_grinding(grinding_bits).unwrap());
+	}
+
+	#[derive(Clone, Default)]
+	struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+	impl Write for SharedBuffer {
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+			self.0.lock().unwrap().extend_from_slice(buf);
+			Ok(buf.len())
+		}
+
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn test_streaming_transcript() {
+		let mut rng = thread_rng();
+		let values = repeat_with(|| <BinaryField128b as binius_field::Field>::random(&mut rng))
+			.take(64)
+			.collect::<Vec<_>>();
+
+		let prove = |transcript: &mut ProverTranscript<HasherChallenger<Groestl256>>| {
+			let mut samples = Vec::new();
+			for chunk in values.chunks(8) {
+				transcript.message().write_scalar_slice(chunk);
+				transcript.decommitment().write_bytes(b"advice");
+				samples.push(CanSample::<BinaryField128b>::sample(transcript));
+			}
+			transcript.message().write_scalar(values[0]);
+			samples
+		};
+
+		let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let samples = prove(&mut transcript);
+		let proof = transcript.finalize();
+
+		let sink = SharedBuffer::default();
+		let mut streaming_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		streaming_transcript.set_sink(sink.clone(), 100);
+		let streaming_samples = prove(&mut streaming_transcript);
+		// Flushes happen while proving, before the transcript is finalized.
+		let flushed_len = sink.0.lock().unwrap().len();
+		assert!(flushed_len > 0 && flushed_len < proof.len());
+		let bytes_written = streaming_transcript.finalize_to_sink().unwrap();
+
+		assert_eq!(streaming_samples, samples);
+		assert_eq!(bytes_written, proof.len() as u64);
+		assert_eq!(*sink.0.lock().unwrap(), proof);
+
+		let transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		assert!(matches!(transcript.finalize_to_sink(), Err(Error::NoSink)));
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_streaming_transcript_finalize_fails() {
+		let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		transcript.set_sink(SharedBuffer::default(), 0);
+		transcript.message().write_bytes(b"proof");
+		transcript.finalize();
+	}
 }
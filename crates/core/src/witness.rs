@@ -1,11 +1,16 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::{fmt::Debug, sync::Arc};
+use std::{collections::HashSet, fmt::Debug, sync::Arc};
 
+use binius_compute::{ComputeMemory, SizedSlice};
 use binius_field::PackedField;
 use binius_math::MultilinearPoly;
+use binius_maybe_rayon::prelude::*;
 
-use crate::{oracle::OracleId, polynomial::Error as PolynomialError};
+use crate::{
+	oracle::{Error as OracleError, OracleId, OracleIdRemapping},
+	polynomial::Error as PolynomialError,
+};
 
 pub type MultilinearWitness<'a, P> = Arc<dyn MultilinearPoly<P> + Send + Sync + 'a>;
 
@@ -41,8 +46,14 @@ pub enum Error {
 		field_log_extension_degree: usize,
 		entry_log_extension_degree: usize,
 	},
+	#[error("a witness generator is already registered for oracle {id}")]
+	DuplicateWitnessGenerator { id: OracleId },
+	#[error("the witness generators for oracles {ids:?} have cyclic dependencies")]
+	WitnessGeneratorCycle { ids: Vec<OracleId> },
 	#[error("polynomial error: {0}")]
 	Polynomial(#[from] PolynomialError),
+	#[error("oracle error: {0}")]
+	Oracle(#[from] OracleError),
 	#[error("HAL error: {0}")]
 	HalError(#[from] binius_hal::Error),
 	#[error("Math error: {0}")]
@@ -105,4 +116,319 @@ where
 		}
 		Ok(())
 	}
+
+	/// Returns the index with the witness of each oracle moved to its ID in a remapping.
+	///
+	/// Fails if the index has a witness for an oracle without a new ID.
+	pub fn remap(self, remapping: &OracleIdRemapping) -> Result<Self, Error> {
+		let mut remapped = Self::new();
+		for (index, entry) in self.entries.into_iter().enumerate() {
+			if let Some(entry) = entry {
+				let id = remapping.remap(OracleId::from_index(index))?;
+				if id.index() >= remapped.entries.len() {
+					remapped.entries.resize_with(id.index() + 1, || None);
+				}
+				remapped.entries[id.index()] = Some(entry);
+			}
+		}
+		Ok(remapped)
+	}
+}
+
+/// Data structure that indexes witnesses resident in compute-layer device memory by oracle ID.
+///
+/// Witnesses generated directly on a device, for example by a GPU kernel, are registered here
+/// instead of in a [`MultilinearExtensionIndex`], so that they can be committed and opened by
+/// [`crate::piop::commit_device`] and [`crate::piop::prove_device_committed`] without being
+/// copied through host memory first.
+///
+/// Each entry holds the hypercube evaluations of the witness over the device field, in natural
+/// order. Committed witnesses over subfields are stored in their packed form, as for
+/// [`crate::piop::commit`].
+pub struct DeviceWitnessIndex<'a, F, Mem: ComputeMemory<F>> {
+	entries: Vec<Option<Mem::FSlice<'a>>>,
+}
+
+impl<F, Mem: ComputeMemory<F>> Default for DeviceWitnessIndex<'_, F, Mem> {
+	fn default() -> Self {
+		Self {
+			entries: Vec::new(),
+		}
+	}
+}
+
+impl<F, Mem: ComputeMemory<F>> Debug for DeviceWitnessIndex<'_, F, Mem> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("DeviceWitnessIndex")
+			.field("entries", &self.entries)
+			.finish()
+	}
+}
+
+impl<'a, F, Mem: ComputeMemory<F>> DeviceWitnessIndex<'a, F, Mem> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn get(&self, id: OracleId) -> Result<Mem::FSlice<'a>, Error> {
+		self.entries
+			.get(id.index())
+			.copied()
+			.flatten()
+			.ok_or(Error::MissingWitness { id })
+	}
+
+	/// Whether has data for the given oracle id.
+	pub fn has(&self, id: OracleId) -> bool {
+		self.entries.get(id.index()).is_some_and(Option::is_some)
+	}
+
+	/// Registers device buffers as the witnesses of the given oracles.
+	///
+	/// The length of each buffer must be a power of two.
+	pub fn update(
+		&mut self,
+		witnesses: impl IntoIterator<Item = (OracleId, Mem::FSlice<'a>)>,
+	) -> Result<(), Error> {
+		for (id, data) in witnesses {
+			if !data.len().is_power_of_two() {
+				return Err(Error::MathError(binius_math::Error::PowerOfTwoLengthRequired));
+			}
+			let id_index = id.index();
+			if id_index >= self.entries.len() {
+				self.entries.resize_with(id_index + 1, || None);
+			}
+			self.entries[id_index] = Some(data);
+		}
+		Ok(())
+	}
+}
+
+type WitnessGenerateFn<'a, P> = Box<
+	dyn FnOnce(&MultilinearExtensionIndex<'a, P>) -> Result<MultilinearWitness<'a, P>, Error>
+		+ Send
+		+ 'a,
+>;
+
+struct WitnessGenerator<'a, P: PackedField> {
+	id: OracleId,
+	dependencies: Vec<OracleId>,
+	generate: WitnessGenerateFn<'a, P>,
+}
+
+/// Schedules witness generation for oracles whose witnesses depend on other witnesses.
+///
+/// Each generator declares the oracles whose witnesses it reads. The scheduler runs the
+/// generators in topological order of their dependencies, in waves: every generator whose
+/// dependencies are available in the index runs in parallel with the others in its wave, and its
+/// output is added to the index before the next wave starts. Witnesses generated outside the
+/// scheduler, such as committed columns, may be depended upon as long as they are in the index
+/// before [`Self::run`] is called.
+pub struct WitnessScheduler<'a, P: PackedField> {
+	generators: Vec<WitnessGenerator<'a, P>>,
+	ids: HashSet<OracleId>,
+}
+
+impl<P: PackedField> Default for WitnessScheduler<'_, P> {
+	fn default() -> Self {
+		Self {
+			generators: Vec::new(),
+			ids: HashSet::new(),
+		}
+	}
+}
+
+impl<P: PackedField> Debug for WitnessScheduler<'_, P> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("WitnessScheduler")
+			.field(
+				"generators",
+				&self
+					.generators
+					.iter()
+					.map(|generator| (generator.id, &generator.dependencies))
+					.collect::<Vec<_>>(),
+			)
+			.finish()
+	}
+}
+
+impl<'a, P: PackedField> WitnessScheduler<'a, P> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers a generator for the witness of oracle `id`, which reads the witnesses of
+	/// `dependencies` from the index.
+	pub fn add_generator(
+		&mut self,
+		id: OracleId,
+		dependencies: impl IntoIterator<Item = OracleId>,
+		generate: impl FnOnce(
+			&MultilinearExtensionIndex<'a, P>,
+		) -> Result<MultilinearWitness<'a, P>, Error>
+		+ Send
+		+ 'a,
+	) -> Result<(), Error> {
+		if !self.ids.insert(id) {
+			return Err(Error::DuplicateWitnessGenerator { id });
+		}
+		self.generators.push(WitnessGenerator {
+			id,
+			dependencies: dependencies.into_iter().collect(),
+			generate: Box::new(generate),
+		});
+		Ok(())
+	}
+
+	/// Runs all registered generators and adds their witnesses to the index.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::MissingWitness`] if a dependency is neither in the index nor generated
+	/// * [`Error::WitnessGeneratorCycle`] if the dependencies of some generators are cyclic
+	pub fn run(self, index: &mut MultilinearExtensionIndex<'a, P>) -> Result<(), Error> {
+		let Self {
+			mut generators,
+			mut ids,
+		} = self;
+
+		for generator in &generators {
+			if let Some(&id) = generator
+				.dependencies
+				.iter()
+				.find(|&&dep| !ids.contains(&dep) && !index.has(dep))
+			{
+				return Err(Error::MissingWitness { id });
+			}
+		}
+
+		while !generators.is_empty() {
+			let (ready, blocked): (Vec<_>, Vec<_>) =
+				generators.into_iter().partition(|generator| {
+					generator
+						.dependencies
+						.iter()
+						.all(|&dep| !ids.contains(&dep))
+				});
+			if ready.is_empty() {
+				return Err(Error::WitnessGeneratorCycle {
+					ids: blocked.iter().map(|generator| generator.id).collect(),
+				});
+			}
+
+			let index_ref = &*index;
+			let witnesses = ready
+				.into_par_iter()
+				.map(|generator| Ok((generator.id, (generator.generate)(index_ref)?)))
+				.collect::<Result<Vec<_>, Error>>()?;
+			for (id, _) in &witnesses {
+				ids.remove(id);
+			}
+			index.update_multilin_poly(witnesses)?;
+
+			generators = blocked;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::{BinaryField128b, Field, PackedBinaryField1x128b};
+	use binius_math::MultilinearExtension;
+
+	use super::*;
+
+	type P = PackedBinaryField1x128b;
+
+	fn constant_witness(value: u128) -> MultilinearWitness<'static, P> {
+		MultilinearExtension::new(1, vec![P::set_single(BinaryField128b::new(value)); 2])
+			.unwrap()
+			.specialize_arc_dyn()
+	}
+
+	fn add_generator(
+		scheduler: &mut WitnessScheduler<'static, P>,
+		id: usize,
+		dependencies: &[usize],
+	) -> Result<(), Error> {
+		let dependencies = dependencies
+			.iter()
+			.copied()
+			.map(OracleId::from_index)
+			.collect::<Vec<_>>();
+		scheduler.add_generator(
+			OracleId::from_index(id),
+			dependencies.clone(),
+			move |index: &MultilinearExtensionIndex<P>| {
+				// Each witness is the sum of its dependencies plus one.
+				let mut value = BinaryField128b::ONE;
+				for dep in dependencies {
+					value += index.get_multilin_poly(dep)?.evaluate_on_hypercube(0)?;
+				}
+				Ok(constant_witness(value.val()))
+			},
+		)
+	}
+
+	fn witness_value(index: &MultilinearExtensionIndex<P>, id: usize) -> BinaryField128b {
+		index
+			.get_multilin_poly(OracleId::from_index(id))
+			.unwrap()
+			.evaluate_on_hypercube(0)
+			.unwrap()
+	}
+
+	#[test]
+	fn test_witness_scheduler_runs_in_dependency_order() {
+		let mut index = MultilinearExtensionIndex::new();
+		index
+			.update_multilin_poly([(OracleId::from_index(0), constant_witness(2))])
+			.unwrap();
+
+		// Registered in reverse dependency order, with a diamond 1 -> {2, 3} -> 4.
+		let mut scheduler = WitnessScheduler::new();
+		add_generator(&mut scheduler, 4, &[2, 3]).unwrap();
+		add_generator(&mut scheduler, 3, &[1]).unwrap();
+		add_generator(&mut scheduler, 2, &[1, 0]).unwrap();
+		add_generator(&mut scheduler, 1, &[0]).unwrap();
+		scheduler.run(&mut index).unwrap();
+
+		let one = BinaryField128b::ONE;
+		let w0 = BinaryField128b::new(2);
+		let w1 = w0 + one;
+		let w2 = w1 + w0 + one;
+		let w3 = w1 + one;
+		assert_eq!(witness_value(&index, 1), w1);
+		assert_eq!(witness_value(&index, 2), w2);
+		assert_eq!(witness_value(&index, 3), w3);
+		assert_eq!(witness_value(&index, 4), w2 + w3 + one);
+	}
+
+	#[test]
+	fn test_witness_scheduler_errors() {
+		let mut scheduler = WitnessScheduler::new();
+		add_generator(&mut scheduler, 1, &[]).unwrap();
+		assert!(matches!(
+			add_generator(&mut scheduler, 1, &[]),
+			Err(Error::DuplicateWitnessGenerator { .. })
+		));
+
+		let mut scheduler = WitnessScheduler::new();
+		add_generator(&mut scheduler, 1, &[0]).unwrap();
+		assert!(matches!(
+			scheduler.run(&mut MultilinearExtensionIndex::new()),
+			Err(Error::MissingWitness { id }) if id == OracleId::from_index(0)
+		));
+
+		let mut scheduler = WitnessScheduler::new();
+		add_generator(&mut scheduler, 0, &[]).unwrap();
+		add_generator(&mut scheduler, 1, &[2]).unwrap();
+		add_generator(&mut scheduler, 2, &[1]).unwrap();
+		assert!(matches!(
+			scheduler.run(&mut MultilinearExtensionIndex::new()),
+			Err(Error::WitnessGeneratorCycle { ids }) if ids.len() == 2
+		));
+	}
 }
@@ -24,17 +24,21 @@ use binius_field::{
 	as_packed_field::{PackScalar, PackedType},
 	linear_transformation::{PackedTransformationFactory, Transformation},
 	make_aes_to_binary_packed_transformer, make_binary_to_aes_packed_transformer,
+	packed::{get_packed_slice, set_packed_slice},
 	tower::{PackedTop, TowerFamily},
 	tower_levels::TowerLevel16,
 	underlier::{NumCast, UnderlierWithBitOps, WithUnderlier},
 	unpack_if_possible, unpack_if_possible_mut,
 	util::inner_product_par,
 };
-use binius_math::{ArithCircuit, CompositionPoly, RowsBatchRef, tensor_prod_eq_ind};
+use binius_hash::multi_digest::ParallelDigest;
+use binius_math::{
+	ArithCircuit, CompositionPoly, RowsBatchRef, extrapolate_line_scalar, tensor_prod_eq_ind,
+};
 use binius_maybe_rayon::{
 	iter::{
-		IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
-		ParallelIterator,
+		IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+		IntoParallelRefMutIterator, ParallelIterator,
 	},
 	prelude::ParallelBridge,
 	slice::{ParallelSlice, ParallelSliceMut},
@@ -45,12 +49,13 @@ use binius_utils::{
 	rayon::get_log_max_threads,
 };
 use bytemuck::{Pod, zeroed_vec};
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use itertools::izip;
 use thread_local::ThreadLocal;
 
 use crate::{
 	arith_circuit::ArithCircuitPoly,
-	memory::{PackedMemory, PackedMemorySliceMut},
+	memory::{PackedMemory, PackedMemorySlice, PackedMemorySliceMut},
 };
 
 /// Optimized CPU implementation of the compute layer.
@@ -487,6 +492,70 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		Ok(())
 	}
 
+	fn fold_multilinear(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		challenge: T::B128,
+		folded: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+	) -> Result<(), Error> {
+		if !folded.len().is_power_of_two() || evals.len() != 2 * folded.len() {
+			return Err(Error::InputValidation(format!(
+				"evals length {} must be twice the folded length {}, which must be a power of two",
+				evals.len(),
+				folded.len()
+			)));
+		}
+
+		let folded_len = folded.len();
+		let evals = evals.as_slice();
+		if folded_len >= P::WIDTH {
+			let (evals_0, evals_1) = evals.split_at(folded_len >> P::LOG_WIDTH);
+			let challenge = P::broadcast(challenge);
+			folded
+				.as_slice_mut()
+				.par_iter_mut()
+				.zip(evals_0.par_iter().zip(evals_1.par_iter()))
+				.for_each(|(out, (&x0, &x1))| *out = x0 + (x1 - x0) * challenge);
+		} else {
+			// The halves share a single packed element, so the scalars are read by index.
+			let folded = folded.as_slice_mut();
+			for i in 0..folded_len {
+				let x0 = get_packed_slice(evals, i);
+				let x1 = get_packed_slice(evals, folded_len + i);
+				set_packed_slice(folded, i, extrapolate_line_scalar(x0, x1, challenge));
+			}
+		}
+
+		Ok(())
+	}
+
+	fn hash_leaves<H>(
+		&mut self,
+		data: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		prefix: &[u8],
+		digests: &mut [MaybeUninit<Output<H::Digest>>],
+	) -> Result<(), Error>
+	where
+		H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	{
+		if digests.is_empty() || data.len() % digests.len() != 0 {
+			return Err(Error::InputValidation(format!(
+				"data length {} is not a multiple of the number of digests {}",
+				data.len(),
+				digests.len()
+			)));
+		}
+
+		// Leaves may be shorter than a packed element, so the scalars are read by index.
+		let packed = data.as_slice();
+		let leaf_len = data.len() / digests.len();
+		let leaves = (0..digests.len())
+			.into_par_iter()
+			.map(|i| (i * leaf_len..(i + 1) * leaf_len).map(|j| get_packed_slice(packed, j)));
+		H::new_with_prefix(prefix).digest(leaves, digests);
+		Ok(())
+	}
+
 	fn compute_composite(
 		&mut self,
 		inputs: &SlicesBatch<<Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>>,
@@ -774,6 +843,51 @@ impl<T: TowerFamily, P: PackedTop<T>> KernelExecutor<T::B128> for FastKernelExec
 		Ok(())
 	}
 
+	fn sum_composition_evals_at_point(
+		&mut self,
+		evals_0: &SlicesBatch<<Self::Mem as ComputeMemory<T::B128>>::FSlice<'_>>,
+		evals_1: &SlicesBatch<<Self::Mem as ComputeMemory<T::B128>>::FSlice<'_>>,
+		point: T::B128,
+		composition: &Self::ExprEval,
+		batch_coeff: T::B128,
+		accumulator: &mut Self::Value,
+	) -> Result<(), Error> {
+		if evals_0.n_rows() != evals_1.n_rows() || evals_0.row_len() != evals_1.row_len() {
+			return Err(Error::InputValidation(
+				"evals_0 and evals_1 must have the same shape".to_string(),
+			));
+		}
+
+		// Extrapolate the rows to the point packed-wise, which is also correct for chunks that
+		// are shorter than a packed element, as their scalars are stored in the first element.
+		let point = P::broadcast(point);
+		let rows = zip(evals_0.iter(), evals_1.iter())
+			.map(|(row_0, row_1)| {
+				zip(row_0.as_slice(), row_1.as_slice())
+					.map(|(&x0, &x1)| x0 + (x1 - x0) * point)
+					.collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>();
+
+		let row_len = evals_0.row_len();
+		let inputs = rows
+			.iter()
+			.map(|row| {
+				if row_len >= P::WIDTH {
+					PackedMemorySlice::new_slice(row)
+				} else {
+					PackedMemorySlice::new_owned(row, 0, row_len)
+				}
+			})
+			.collect();
+		self.sum_composition_evals(
+			&SlicesBatch::new(inputs, row_len),
+			composition,
+			batch_coeff,
+			accumulator,
+		)
+	}
+
 	fn add(
 		&mut self,
 		log_len: usize,
@@ -187,3 +187,31 @@ fn test_compute_composite() {
 		log_len,
 	);
 }
+
+#[test]
+fn test_fold_multilinear() {
+	type P = PackedBinaryField2x128b;
+	let compute = <FastCpuLayer<CanonicalTowerFamily, P>>::default();
+	// The smallest length folds within a single packed element.
+	for log_len in [0, 10] {
+		let mut device_memory = vec![P::zero(); 1 << (log_len + 3 - P::LOG_WIDTH)];
+		binius_compute_test_utils::layer::test_generic_fold_multilinear(
+			&compute,
+			PackedMemorySliceMut::new_slice(&mut device_memory),
+			log_len,
+		);
+	}
+}
+
+#[test]
+fn test_sum_composition_evals_at_point() {
+	type P = PackedBinaryField2x128b;
+	let log_len = 10;
+	let compute = <FastCpuLayer<CanonicalTowerFamily, P>>::default();
+	let mut device_memory = vec![P::zero(); 1 << (log_len + 3 - P::LOG_WIDTH)];
+	binius_compute_test_utils::layer::test_generic_sum_composition_evals_at_point(
+		&compute,
+		PackedMemorySliceMut::new_slice(&mut device_memory),
+		log_len,
+	);
+}
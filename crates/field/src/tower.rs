@@ -18,6 +18,9 @@ use crate::{
 };
 
 /// A trait that groups a family of related [`TowerField`]s as associated types.
+///
+/// The associated types are the tower levels 0 and 3 through 7, so `B128` is always a 128-bit
+/// field. Families truncated below that are [`TopTowerFamily`]s only.
 pub trait TowerFamily: Sized + 'static + Sync + Send {
 	type B1: TowerField + TryFrom<Self::B128>;
 	type B8: TowerField + TryFrom<Self::B128> + ExtensionField<Self::B1>;
@@ -41,6 +44,43 @@ pub trait TowerFamily: Sized + 'static + Sync + Send {
 		+ ExtensionField<Self::B64>;
 }
 
+/// A family of related [`TowerField`]s up to a top field, which may be narrower than 128 bits.
+///
+/// Every [`TowerFamily`] is a family with its `B128` field at the top. Truncated families, such as
+/// [`Canonical64bTowerFamily`], have a narrower top field, which is then also one of the subfields.
+pub trait TopTowerFamily: Sized + 'static + Sync + Send {
+	type B1: TowerField + TryFrom<Self::Top>;
+	type B8: TowerField + TryFrom<Self::Top> + ExtensionField<Self::B1>;
+	type B16: TowerField + TryFrom<Self::Top> + ExtensionField<Self::B1> + ExtensionField<Self::B8>;
+	type B32: TowerField
+		+ TryFrom<Self::Top>
+		+ ExtensionField<Self::B1>
+		+ ExtensionField<Self::B8>
+		+ ExtensionField<Self::B16>;
+	type B64: TowerField
+		+ TryFrom<Self::Top>
+		+ ExtensionField<Self::B1>
+		+ ExtensionField<Self::B8>
+		+ ExtensionField<Self::B16>
+		+ ExtensionField<Self::B32>;
+	/// The top field of the family, into which protocols such as ring switching reduce.
+	type Top: TowerField
+		+ ExtensionField<Self::B1>
+		+ ExtensionField<Self::B8>
+		+ ExtensionField<Self::B16>
+		+ ExtensionField<Self::B32>
+		+ ExtensionField<Self::B64>;
+}
+
+impl<Tower: TowerFamily> TopTowerFamily for Tower {
+	type B1 = <Tower as TowerFamily>::B1;
+	type B8 = <Tower as TowerFamily>::B8;
+	type B16 = <Tower as TowerFamily>::B16;
+	type B32 = <Tower as TowerFamily>::B32;
+	type B64 = <Tower as TowerFamily>::B64;
+	type Top = <Tower as TowerFamily>::B128;
+}
+
 pub trait ProverTowerFamily: TowerFamily {
 	type FastB128: TowerField + From<Self::B128> + Into<Self::B128> + ExtensionField<Self::B1>;
 
@@ -88,6 +128,25 @@ impl ProverTowerFamily for CanonicalTowerFamily {
 	}
 }
 
+/// The canonical Fan-Paar tower family truncated at the 64-bit field.
+///
+/// This is not a [`TowerFamily`], since it has no 128-bit level. It is a [`TopTowerFamily`] whose
+/// top field is [`BinaryField64b`], which is also the `B64` type, so that protocols reducing into
+/// the top field, such as ring switching, work over the 64-bit extension. This is only possible
+/// when no committed multilinear is above tower level 6, and the challenges sampled from the top
+/// field then give less than 64 bits of security.
+#[derive(Debug, Default)]
+pub struct Canonical64bTowerFamily;
+
+impl TopTowerFamily for Canonical64bTowerFamily {
+	type B1 = BinaryField1b;
+	type B8 = BinaryField8b;
+	type B16 = BinaryField16b;
+	type B32 = BinaryField32b;
+	type B64 = BinaryField64b;
+	type Top = BinaryField64b;
+}
+
 /// The tower defined by Fan-Paar extensions built on top of the Rijndael field.
 #[derive(Debug)]
 pub struct AESTowerFamily;
@@ -135,6 +194,26 @@ trait_set! {
 	pub trait ProverTowerUnderlier<Tower: ProverTowerFamily> =
 		TowerUnderlier<Tower> + PackScalar<Tower::FastB128>;
 
+	/// An underlier with associated packed types for the fields of a [`TopTowerFamily`].
+	pub trait TopTowerUnderlier<Tower: TopTowerFamily> =
+		UnderlierType
+		+ PackScalar<Tower::B1>
+		+ PackScalar<Tower::B8>
+		+ PackScalar<Tower::B16>
+		+ PackScalar<Tower::B32>
+		+ PackScalar<Tower::B64>
+		+ PackScalar<Tower::Top>;
+
+	/// A packed field type over the top field of a [`TopTowerFamily`].
+	pub trait PackedTopField<Tower: TopTowerFamily> =
+		PackedField<Scalar=Tower::Top>
+		+ PackedExtension<Tower::B1>
+		+ PackedExtension<Tower::B8>
+		+ PackedExtension<Tower::B16>
+		+ PackedExtension<Tower::B32>
+		+ PackedExtension<Tower::B64>
+		+ PackedExtension<Tower::Top>;
+
 	/// A packed field type that is the top packed field in a tower.
 	pub trait PackedTop<Tower: TowerFamily> =
 		PackedField<Scalar=Tower::B128>
@@ -5,7 +5,7 @@ use std::{
 	ops::{Deref, DerefMut},
 };
 
-use binius_field::{Field, PackedExtension, PackedField};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedField};
 use binius_math::{
 	CompositionPoly, EvaluationOrder, MultilinearExtension, MultilinearPoly, MultilinearQuery,
 	MultilinearQueryRef,
@@ -13,7 +13,7 @@ use binius_math::{
 use binius_maybe_rayon::iter::FromParallelIterator;
 use tracing::instrument;
 
-use crate::{Error, RoundEvals, SumcheckEvaluator, SumcheckMultilinear};
+use crate::{Error, RoundEvals, SubfieldSumcheckEvaluator, SumcheckEvaluator, SumcheckMultilinear};
 
 /// HAL-managed memory containing the result of its operations.
 pub trait HalSlice<P: Debug + Send + Sync>:
@@ -61,6 +61,27 @@ pub trait ComputationBackend: Send + Sync + Debug {
 		Evaluator: SumcheckEvaluator<P, Composition> + Sync,
 		Composition: CompositionPoly<P>;
 
+	/// Calculate the accumulated evaluations for the first sumcheck round over the subfield
+	/// `FBase` of the multilinears.
+	///
+	/// All multilinears must be transparent multilinears defined over `FBase`, with no variables
+	/// folded yet, otherwise [`Error::SubfieldRoundEvalsUnsupported`] is returned.
+	fn sumcheck_compute_subfield_round_evals<FBase, FDomain, P, M, Evaluator, Composition>(
+		&self,
+		evaluation_order: EvaluationOrder,
+		n_vars: usize,
+		multilinears: &[SumcheckMultilinear<P, M>],
+		evaluators: &[Evaluator],
+		nontrivial_evaluation_points: &[FDomain],
+	) -> Result<Vec<RoundEvals<P::Scalar>>, Error>
+	where
+		FBase: ExtensionField<FDomain>,
+		FDomain: Field,
+		P: PackedExtension<FBase, Scalar: ExtensionField<FBase>>,
+		M: MultilinearPoly<P> + Send + Sync,
+		Evaluator: SubfieldSumcheckEvaluator<FBase, P, Composition> + Sync,
+		Composition: CompositionPoly<P>;
+
 	/// Sumcheck round
 	fn sumcheck_fold_multilinears<P, M>(
 		&self,
@@ -128,6 +149,32 @@ where
 		)
 	}
 
+	fn sumcheck_compute_subfield_round_evals<FBase, FDomain, P, M, Evaluator, Composition>(
+		&self,
+		evaluation_order: EvaluationOrder,
+		n_vars: usize,
+		multilinears: &[SumcheckMultilinear<P, M>],
+		evaluators: &[Evaluator],
+		nontrivial_evaluation_points: &[FDomain],
+	) -> Result<Vec<RoundEvals<P::Scalar>>, Error>
+	where
+		FBase: ExtensionField<FDomain>,
+		FDomain: Field,
+		P: PackedExtension<FBase, Scalar: ExtensionField<FBase>>,
+		M: MultilinearPoly<P> + Send + Sync,
+		Evaluator: SubfieldSumcheckEvaluator<FBase, P, Composition> + Sync,
+		Composition: CompositionPoly<P>,
+	{
+		T::sumcheck_compute_subfield_round_evals(
+			self,
+			evaluation_order,
+			n_vars,
+			multilinears,
+			evaluators,
+			nontrivial_evaluation_points,
+		)
+	}
+
 	fn sumcheck_fold_multilinears<P, M>(
 		&self,
 		evaluation_order: EvaluationOrder,
@@ -2,7 +2,7 @@
 
 use std::fmt::Debug;
 
-use binius_field::{Field, PackedExtension, PackedField};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedField};
 use binius_math::{
 	CompositionPoly, EvaluationOrder, MultilinearExtension, MultilinearPoly, MultilinearQueryRef,
 	eq_ind_partial_eval,
@@ -10,8 +10,10 @@ use binius_math::{
 use tracing::instrument;
 
 use crate::{
-	ComputationBackend, Error, RoundEvals, SumcheckEvaluator, SumcheckMultilinear,
-	sumcheck_folding::fold_multilinears, sumcheck_round_calculation::calculate_round_evals,
+	ComputationBackend, Error, RoundEvals, SubfieldSumcheckEvaluator, SumcheckEvaluator,
+	SumcheckMultilinear,
+	sumcheck_folding::fold_multilinears,
+	sumcheck_round_calculation::{calculate_round_evals, calculate_subfield_round_evals},
 };
 
 /// Implementation of ComputationBackend for the default Backend that uses the CPU for all
@@ -64,6 +66,31 @@ impl ComputationBackend for CpuBackend {
 		)
 	}
 
+	fn sumcheck_compute_subfield_round_evals<FBase, FDomain, P, M, Evaluator, Composition>(
+		&self,
+		evaluation_order: EvaluationOrder,
+		n_vars: usize,
+		multilinears: &[SumcheckMultilinear<P, M>],
+		evaluators: &[Evaluator],
+		nontrivial_evaluation_points: &[FDomain],
+	) -> Result<Vec<RoundEvals<P::Scalar>>, Error>
+	where
+		FBase: ExtensionField<FDomain>,
+		FDomain: Field,
+		P: PackedExtension<FBase, Scalar: ExtensionField<FBase>>,
+		M: MultilinearPoly<P> + Send + Sync,
+		Evaluator: SubfieldSumcheckEvaluator<FBase, P, Composition> + Sync,
+		Composition: CompositionPoly<P>,
+	{
+		calculate_subfield_round_evals(
+			evaluation_order,
+			n_vars,
+			multilinears,
+			evaluators,
+			nontrivial_evaluation_points,
+		)
+	}
+
 	fn sumcheck_fold_multilinears<P, M>(
 		&self,
 		evaluation_order: EvaluationOrder,
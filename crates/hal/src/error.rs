@@ -17,6 +17,8 @@ pub enum Error {
 	NoScratchSpace,
 	#[error("incorrect multilinear access destination slice lengths")]
 	IncorrectDestSliceLengths,
+	#[error("subfield round evaluation requires unfolded multilinears defined over the subfield")]
+	SubfieldRoundEvalsUnsupported,
 	#[error("{0}")]
 	FieldError(#[from] binius_field::Error),
 }
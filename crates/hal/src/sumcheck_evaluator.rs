@@ -2,7 +2,7 @@
 
 use std::ops::Range;
 
-use binius_field::{Field, PackedField};
+use binius_field::{Field, PackedExtension, PackedField, PackedSubfield};
 use binius_math::RowsBatchRef;
 
 /// Evaluations of a polynomial at a set of evaluation points.
@@ -64,3 +64,30 @@ pub trait SumcheckEvaluator<P: PackedField, Composition> {
 		0
 	}
 }
+
+/// A [`SumcheckEvaluator`] that can evaluate its composition over multilinear evaluations in a
+/// subfield `FBase` of the packed field scalars.
+///
+/// When all multilinears of the first round are defined over `FBase`, the composition can be
+/// evaluated with subfield arithmetic, and only the result is lifted to the extension field, for
+/// example by multiplying it by the equality indicator. See
+/// [`crate::ComputationBackend::sumcheck_compute_subfield_round_evals`].
+pub trait SubfieldSumcheckEvaluator<FBase, P, Composition>:
+	SumcheckEvaluator<P, Composition>
+where
+	FBase: Field,
+	P: PackedExtension<FBase>,
+{
+	/// Compute composition evals over a subcube from multilinear evals in the subfield.
+	///
+	/// This is the subfield counterpart of
+	/// [`SumcheckEvaluator::process_subcube_at_eval_point`], with the same meaning of the
+	/// arguments and of the returned packed sum.
+	fn process_subfield_subcube_at_eval_point(
+		&self,
+		subcube_vars: usize,
+		subcube_index: usize,
+		is_infinity_point: bool,
+		batch_query: &RowsBatchRef<PackedSubfield<P, FBase>>,
+	) -> P;
+}
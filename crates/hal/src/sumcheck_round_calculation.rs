@@ -4,10 +4,11 @@
 //!
 //! This is one of the core computational tasks in the sumcheck proving algorithm.
 
-use std::iter;
+use std::{iter, ops::Range};
 
 use binius_field::{
-	Field, PackedExtension, PackedField, PackedSubfield, packed::get_packed_slice_checked,
+	ExtensionField, Field, PackedExtension, PackedField, PackedSubfield,
+	packed::get_packed_slice_checked,
 };
 use binius_math::{
 	CompositionPoly, EvaluationOrder, MultilinearPoly, MultilinearQuery, MultilinearQueryRef,
@@ -20,7 +21,7 @@ use itertools::{Either, Itertools, izip};
 use stackalloc::stackalloc_with_iter;
 
 use crate::{
-	Error, RoundEvals, SumcheckEvaluator, SumcheckMultilinear,
+	Error, RoundEvals, SubfieldSumcheckEvaluator, SumcheckEvaluator, SumcheckMultilinear,
 	common::{MAX_SRC_SUBCUBE_LOG_BITS, subcube_vars_for_bits},
 };
 
@@ -80,7 +81,7 @@ trait SumcheckMultilinearAccess<P: PackedField> {
 
 /// Calculate the accumulated evaluations for an arbitrary sumcheck round.
 ///
-/// See [`calculate_first_round_evals`] for an optimized version of this method
+/// See [`calculate_subfield_round_evals`] for an optimized version of this method
 /// that works over small fields in the first round.
 pub(crate) fn calculate_round_evals<FDomain, F, P, M, Evaluator, Composition>(
 	evaluation_order: EvaluationOrder,
@@ -104,7 +105,7 @@ where
 	let tensor_query = tensor_query.unwrap_or_else(|| empty_query.to_ref());
 
 	match evaluation_order {
-		EvaluationOrder::LowToHigh => calculate_round_evals_with_access(
+		EvaluationOrder::LowToHigh => calculate_round_evals_specialized(
 			LowToHighAccess,
 			n_vars,
 			tensor_query,
@@ -112,7 +113,7 @@ where
 			evaluators,
 			finite_evaluation_points,
 		),
-		EvaluationOrder::HighToLow => calculate_round_evals_with_access(
+		EvaluationOrder::HighToLow => calculate_round_evals_specialized(
 			HighToLowAccess,
 			n_vars,
 			tensor_query,
@@ -123,12 +124,307 @@ where
 	}
 }
 
-fn calculate_round_evals_with_access<FDomain, F, P, M, Evaluator, Access, Composition>(
+/// Calculate the accumulated evaluations for the first sumcheck round over a subfield.
+///
+/// This is an optimized version of [`calculate_round_evals`] for the case where every multilinear
+/// is a transparent multilinear defined over the subfield `FBase`, with no variables folded yet.
+/// Multilinear evaluations are read and extrapolated in `FBase`, and the evaluators evaluate the
+/// compositions with subfield arithmetic, delaying the multiplication by extension field values,
+/// like the equality indicator, until the compositions have been evaluated.
+pub(crate) fn calculate_subfield_round_evals<FBase, FDomain, F, P, M, Evaluator, Composition>(
+	evaluation_order: EvaluationOrder,
+	n_vars: usize,
+	multilinears: &[SumcheckMultilinear<P, M>],
+	evaluators: &[Evaluator],
+	nontrivial_evaluation_points: &[FDomain],
+) -> Result<Vec<RoundEvals<F>>, Error>
+where
+	FBase: ExtensionField<FDomain>,
+	FDomain: Field,
+	F: ExtensionField<FBase>,
+	P: PackedExtension<FBase, Scalar = F>,
+	M: MultilinearPoly<P> + Sync,
+	Evaluator: SubfieldSumcheckEvaluator<FBase, P, Composition> + Sync,
+	Composition: CompositionPoly<P>,
+{
+	assert!(n_vars > 0, "Computing round evaluations requires at least a single variable.");
+
+	let log_embedding_degree = <F as ExtensionField<FBase>>::LOG_DEGREE;
+	let subfield_multilinears = multilinears
+		.iter()
+		.map(|multilinear| match multilinear {
+			SumcheckMultilinear::Transparent { multilinear, .. }
+				if multilinear.n_vars() == n_vars
+					&& multilinear.log_extension_degree() >= log_embedding_degree =>
+			{
+				Ok(multilinear)
+			}
+			_ => Err(Error::SubfieldRoundEvalsUnsupported),
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let eval_point_indices = union_eval_point_indices::<P, _, Composition>(evaluators);
+
+	// Check that finite evaluation points  are of correct length (accounted for 0, 1 & infinity
+	// point).
+	if nontrivial_evaluation_points.len() != eval_point_indices.end.saturating_sub(3) {
+		bail!(Error::IncorrectNontrivialEvalPointsLength);
+	}
+
+	let nontrivial_evaluation_points = nontrivial_evaluation_points
+		.iter()
+		.map(|&eval_point| PackedSubfield::<P, FBase>::broadcast(eval_point.into()))
+		.collect::<Vec<_>>();
+
+	let n_multilinears = multilinears.len();
+	let n_round_evals = evaluators
+		.iter()
+		.map(|evaluator| evaluator.eval_point_indices().len());
+
+	// The working set is measured in subfield bits, allowing for larger subcubes.
+	let subcube_vars = subcube_vars_for_bits::<PackedSubfield<P, FBase>>(
+		MAX_SRC_SUBCUBE_LOG_BITS,
+		n_vars - 1,
+		0,
+		n_vars - 1,
+	);
+
+	let (subcube_count_by_evaluator, subcube_count_by_multilinear) =
+		subcube_counts::<P, _, Composition>(n_vars, subcube_vars, n_multilinears, evaluators);
+
+	// Evaluations at 0 & 1 are interleaved with low-to-high evaluation order, and need to be read
+	// into scratch space before deinterleaving, like in `LowToHighAccess`.
+	let scratch_space_len = match evaluation_order {
+		EvaluationOrder::LowToHigh => {
+			Some(1 << (subcube_vars + 1).saturating_sub(PackedSubfield::<P, FBase>::LOG_WIDTH))
+		}
+		EvaluationOrder::HighToLow => None,
+	};
+
+	let index_vars = n_vars - 1 - subcube_vars;
+	let packed_accumulators = (0..1 << index_vars)
+		.into_par_iter()
+		.try_fold(
+			|| {
+				ParFoldStates::<PackedSubfield<P, FBase>, P>::new(
+					scratch_space_len,
+					n_multilinears,
+					n_round_evals.clone(),
+					subcube_vars,
+				)
+			},
+			|mut par_fold_states, subcube_index| {
+				let ParFoldStates {
+					multilinear_evals,
+					scratch_space,
+					round_evals,
+				} = &mut par_fold_states;
+
+				for (multilinear, evals, &subcube_count) in izip!(
+					&subfield_multilinears,
+					multilinear_evals.iter_mut(),
+					&subcube_count_by_multilinear
+				) {
+					if subcube_index >= subcube_count {
+						continue;
+					}
+
+					// Subfield evaluations are embedded into the packed extension field elements
+					// in the order of the packed subfield elements.
+					match scratch_space {
+						Some(scratch_space) => {
+							multilinear.subcube_evals(
+								subcube_vars + 1,
+								subcube_index,
+								log_embedding_degree,
+								P::cast_exts_mut(scratch_space),
+							)?;
+							deinterleave(scratch_space, &mut evals.evals_0, &mut evals.evals_1);
+						}
+						None => {
+							multilinear.subcube_evals(
+								subcube_vars,
+								subcube_index,
+								log_embedding_degree,
+								P::cast_exts_mut(&mut evals.evals_0),
+							)?;
+							multilinear.subcube_evals(
+								subcube_vars,
+								subcube_index | 1 << index_vars,
+								log_embedding_degree,
+								P::cast_exts_mut(&mut evals.evals_1),
+							)?;
+						}
+					}
+				}
+
+				for eval_point_index in eval_point_indices.clone() {
+					// Infinity point requires special evaluation rules, see
+					// `calculate_round_evals_with_access`.
+					let is_infinity_point = eval_point_index == 2;
+
+					let evals_z_iter =
+						izip!(multilinear_evals.iter_mut(), &subcube_count_by_multilinear).map(
+							|(evals, &subcube_count)| match eval_point_index {
+								// This multilinear is not accessed, return arbitrary slice
+								_ if subcube_index >= subcube_count => evals.evals_0.as_slice(),
+								0 => evals.evals_0.as_slice(),
+								1 => evals.evals_1.as_slice(),
+								2 => {
+									// infinity point
+									izip!(&mut evals.evals_z, &evals.evals_0, &evals.evals_1)
+										.for_each(|(eval_z, &eval_0, &eval_1)| {
+											*eval_z = eval_1 - eval_0;
+										});
+
+									evals.evals_z.as_slice()
+								}
+								3.. => {
+									// Account for the gap occupied by the 0, 1 & infinity point
+									let eval_point =
+										nontrivial_evaluation_points[eval_point_index - 3];
+
+									izip!(&mut evals.evals_z, &evals.evals_0, &evals.evals_1)
+										.for_each(|(eval_z, &eval_0, &eval_1)| {
+											*eval_z = extrapolate_lines(eval_0, eval_1, eval_point);
+										});
+
+									evals.evals_z.as_slice()
+								}
+							},
+						);
+
+					let row_len =
+						1 << subcube_vars.saturating_sub(PackedSubfield::<P, FBase>::LOG_WIDTH);
+					stackalloc_with_iter(n_multilinears, evals_z_iter, |evals_z| {
+						let evals_z = RowsBatchRef::new(evals_z, row_len);
+
+						for (evaluator, round_evals, &subcube_count) in
+							izip!(evaluators, round_evals.iter_mut(), &subcube_count_by_evaluator)
+						{
+							let eval_point_indices = evaluator.eval_point_indices();
+							if !eval_point_indices.contains(&eval_point_index)
+								|| subcube_index >= subcube_count
+							{
+								continue;
+							}
+
+							round_evals[eval_point_index - eval_point_indices.start] += evaluator
+								.process_subfield_subcube_at_eval_point(
+									subcube_vars,
+									subcube_index,
+									is_infinity_point,
+									&evals_z,
+								);
+						}
+					});
+				}
+
+				Ok(par_fold_states)
+			},
+		)
+		.map(
+			|states: Result<ParFoldStates<PackedSubfield<P, FBase>, P>, Error>| -> Result<_, Error> {
+				Ok(states?.round_evals)
+			},
+		)
+		// Simply sum up the fold partitions.
+		.try_reduce(
+			|| zero_packed_round_evals::<P, _, Composition>(evaluators),
+			|lhs, rhs| Ok(add_packed_round_evals(lhs, rhs)),
+		)?;
+
+	Ok(finalize_round_evals::<F, P, _, Composition>(
+		n_vars,
+		subcube_vars,
+		eval_point_indices,
+		packed_accumulators,
+		evaluators,
+		subcube_count_by_evaluator,
+	))
+}
+
+/// Computes the union of all evaluation point index ranges.
+fn union_eval_point_indices<P, Evaluator, Composition>(evaluators: &[Evaluator]) -> Range<usize>
+where
+	P: PackedField,
+	Evaluator: SumcheckEvaluator<P, Composition>,
+{
+	evaluators
+		.iter()
+		.map(|evaluator| evaluator.eval_point_indices())
+		.reduce(|range1, range2| range1.start.min(range2.start)..range1.end.max(range2.end))
+		.unwrap_or(0..0)
+}
+
+/// Value of the `EVAL_POINTS_END` parameter of [`calculate_round_evals_with_access`] selecting the
+/// kernel that handles any number of evaluation points.
+const DYNAMIC_EVAL_POINTS_END: usize = 0;
+
+/// Dispatches to a round evaluation kernel monomorphized for the composition degree.
+///
+/// A round polynomial of degree $d$ is evaluated at the $d + 1$ points with indices `0..d + 1`.
+/// Compositions of degree 2 to 4 cover nearly all constraints in practice, and for those the end
+/// of the evaluation point range is fixed at compile time, so that the per-subcube loop over
+/// evaluation points is fully unrolled. Other degrees use the dynamic kernel.
+fn calculate_round_evals_specialized<FDomain, F, P, M, Evaluator, Access, Composition>(
+	access: Access,
+	n_vars: usize,
+	tensor_query: MultilinearQueryRef<P>,
+	multilinears: &[SumcheckMultilinear<P, M>],
+	evaluators: &[Evaluator],
+	nontrivial_evaluation_points: &[FDomain],
+) -> Result<Vec<RoundEvals<F>>, Error>
+where
+	FDomain: Field,
+	F: Field,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
+	M: MultilinearPoly<P> + Sync,
+	Evaluator: SumcheckEvaluator<P, Composition> + Sync,
+	Access: SumcheckMultilinearAccess<P> + Sync,
+	Composition: CompositionPoly<P>,
+{
+	let eval_point_indices = union_eval_point_indices::<P, _, Composition>(evaluators);
+
+	macro_rules! dispatch {
+		($eval_points_end:expr) => {
+			calculate_round_evals_with_access::<_, _, _, _, _, _, _, { $eval_points_end }>(
+				access,
+				n_vars,
+				tensor_query,
+				multilinears,
+				evaluators,
+				eval_point_indices,
+				nontrivial_evaluation_points,
+			)
+		};
+	}
+
+	match eval_point_indices.end {
+		3 => dispatch!(3),
+		4 => dispatch!(4),
+		5 => dispatch!(5),
+		_ => dispatch!(DYNAMIC_EVAL_POINTS_END),
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+fn calculate_round_evals_with_access<
+	FDomain,
+	F,
+	P,
+	M,
+	Evaluator,
+	Access,
+	Composition,
+	const EVAL_POINTS_END: usize,
+>(
 	access: Access,
 	n_vars: usize,
 	tensor_query: MultilinearQueryRef<P>,
 	multilinears: &[SumcheckMultilinear<P, M>],
 	evaluators: &[Evaluator],
+	eval_point_indices: Range<usize>,
 	nontrivial_evaluation_points: &[FDomain],
 ) -> Result<Vec<RoundEvals<F>>, Error>
 where
@@ -145,12 +441,13 @@ where
 		.iter()
 		.map(|evaluator| evaluator.eval_point_indices().len());
 
-	// Compute the union of all evaluation point index ranges.
-	let eval_point_indices = evaluators
-		.iter()
-		.map(|evaluator| evaluator.eval_point_indices())
-		.reduce(|range1, range2| range1.start.min(range2.start)..range1.end.max(range2.end))
-		.unwrap_or(0..0);
+	// Specialized kernels have the end of the evaluation point range as a compile-time constant.
+	let eval_point_indices = if EVAL_POINTS_END == DYNAMIC_EVAL_POINTS_END {
+		eval_point_indices
+	} else {
+		debug_assert_eq!(eval_point_indices.end, EVAL_POINTS_END);
+		eval_point_indices.start..EVAL_POINTS_END
+	};
 
 	// Check that finite evaluation points  are of correct length (accounted for 0, 1 & infinity
 	// point).
@@ -167,33 +464,21 @@ where
 		n_vars - 1,
 	);
 
-	let subcube_count_by_evaluator = evaluators
-		.iter()
-		.map(|evaluator| {
-			((1 << (n_vars - 1)) - evaluator.const_eval_suffix()).div_ceil(1 << subcube_vars)
-		})
-		.collect::<Vec<_>>();
-
-	let mut subcube_count_by_multilinear = vec![0; n_multilinears];
-
-	for (&evaluator_subcube_count, evaluator) in izip!(&subcube_count_by_evaluator, evaluators) {
-		let used_vars = evaluator.composition().expression().vars_usage();
-
-		for (multilinear_subcube_count, usage_flag) in
-			izip!(&mut subcube_count_by_multilinear, used_vars)
-		{
-			if usage_flag {
-				*multilinear_subcube_count =
-					(*multilinear_subcube_count).max(evaluator_subcube_count);
-			}
-		}
-	}
+	let (subcube_count_by_evaluator, subcube_count_by_multilinear) =
+		subcube_counts::<P, _, Composition>(n_vars, subcube_vars, n_multilinears, evaluators);
 
 	let index_vars = n_vars - 1 - subcube_vars;
 	let packed_accumulators = (0..1 << index_vars)
 		.into_par_iter()
 		.try_fold(
-			|| ParFoldStates::new(&access, n_multilinears, n_round_evals.clone(), subcube_vars),
+			|| {
+				ParFoldStates::new(
+					access.scratch_space_len(subcube_vars),
+					n_multilinears,
+					n_round_evals.clone(),
+					subcube_vars,
+				)
+			},
 			|mut par_fold_states, subcube_index| {
 				let ParFoldStates {
 					multilinear_evals,
@@ -305,26 +590,99 @@ where
 		})
 		// Simply sum up the fold partitions.
 		.try_reduce(
-			|| {
-				evaluators
-					.iter()
-					.map(|evaluator| vec![P::zero(); evaluator.eval_point_indices().len()])
-					.collect()
-			},
-			|lhs, rhs| {
-				let sum = izip!(lhs, rhs)
-					.map(|(mut lhs_vals, rhs_vals)| {
-						for (lhs_val, rhs_val) in lhs_vals.iter_mut().zip(rhs_vals) {
-							*lhs_val += rhs_val;
-						}
-						lhs_vals
-					})
-					.collect();
-				Ok(sum)
-			},
+			|| zero_packed_round_evals::<P, _, Composition>(evaluators),
+			|lhs, rhs| Ok(add_packed_round_evals(lhs, rhs)),
 		)?;
 
-	let round_evals = izip!(packed_accumulators, evaluators, subcube_count_by_evaluator)
+	Ok(finalize_round_evals::<F, P, _, Composition>(
+		n_vars,
+		subcube_vars,
+		eval_point_indices,
+		packed_accumulators,
+		evaluators,
+		subcube_count_by_evaluator,
+	))
+}
+
+/// Returns the number of subcubes that are not in the constant evaluation suffix, for each
+/// evaluator and for each multilinear.
+///
+/// A multilinear needs to be accessed on the subcubes of every evaluator whose composition
+/// reads it.
+fn subcube_counts<P, Evaluator, Composition>(
+	n_vars: usize,
+	subcube_vars: usize,
+	n_multilinears: usize,
+	evaluators: &[Evaluator],
+) -> (Vec<usize>, Vec<usize>)
+where
+	P: PackedField,
+	Evaluator: SumcheckEvaluator<P, Composition>,
+	Composition: CompositionPoly<P>,
+{
+	let subcube_count_by_evaluator = evaluators
+		.iter()
+		.map(|evaluator| {
+			((1 << (n_vars - 1)) - evaluator.const_eval_suffix()).div_ceil(1 << subcube_vars)
+		})
+		.collect::<Vec<_>>();
+
+	let mut subcube_count_by_multilinear = vec![0; n_multilinears];
+
+	for (&evaluator_subcube_count, evaluator) in izip!(&subcube_count_by_evaluator, evaluators) {
+		let used_vars = evaluator.composition().expression().vars_usage();
+
+		for (multilinear_subcube_count, usage_flag) in
+			izip!(&mut subcube_count_by_multilinear, used_vars)
+		{
+			if usage_flag {
+				*multilinear_subcube_count =
+					(*multilinear_subcube_count).max(evaluator_subcube_count);
+			}
+		}
+	}
+
+	(subcube_count_by_evaluator, subcube_count_by_multilinear)
+}
+
+fn zero_packed_round_evals<P, Evaluator, Composition>(evaluators: &[Evaluator]) -> Vec<Vec<P>>
+where
+	P: PackedField,
+	Evaluator: SumcheckEvaluator<P, Composition>,
+{
+	evaluators
+		.iter()
+		.map(|evaluator| vec![P::zero(); evaluator.eval_point_indices().len()])
+		.collect()
+}
+
+fn add_packed_round_evals<P: PackedField>(lhs: Vec<Vec<P>>, rhs: Vec<Vec<P>>) -> Vec<Vec<P>> {
+	izip!(lhs, rhs)
+		.map(|(mut lhs_vals, rhs_vals)| {
+			for (lhs_val, rhs_val) in lhs_vals.iter_mut().zip(rhs_vals) {
+				*lhs_val += rhs_val;
+			}
+			lhs_vals
+		})
+		.collect()
+}
+
+/// Sums the packed round evaluations of each evaluator and adds the sums over the constant
+/// evaluation suffixes.
+fn finalize_round_evals<F, P, Evaluator, Composition>(
+	n_vars: usize,
+	subcube_vars: usize,
+	eval_point_indices: Range<usize>,
+	packed_accumulators: Vec<Vec<P>>,
+	evaluators: &[Evaluator],
+	subcube_count_by_evaluator: Vec<usize>,
+) -> Vec<RoundEvals<F>>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	Evaluator: SumcheckEvaluator<P, Composition>,
+{
+	izip!(packed_accumulators, evaluators, subcube_count_by_evaluator)
 		.map(|(packed_round_evals, evaluator, subcube_count)| {
 			let mut round_evals = packed_round_evals
 				.into_iter()
@@ -343,9 +701,7 @@ where
 
 			RoundEvals(round_evals)
 		})
-		.collect();
-
-	Ok(round_evals)
+		.collect()
 }
 
 // Evals of a single multilinear over a subcube, at 0/1 and some interpolated point.
@@ -368,24 +724,27 @@ impl<P: PackedField> MultilinearEvals<P> {
 }
 
 /// Parallel fold state, consisting of scratch area and result accumulator.
+///
+/// Multilinear evaluations are in `PEval`, which is a subfield of the accumulator `PAccum` when
+/// round evaluations are calculated over a subfield.
 #[derive(Debug)]
-struct ParFoldStates<P: PackedField> {
+struct ParFoldStates<PEval: PackedField, PAccum: PackedField = PEval> {
 	// Evaluations at 0, 1 and domain points, per MLE. Scratch space.
-	multilinear_evals: Vec<MultilinearEvals<P>>,
+	multilinear_evals: Vec<MultilinearEvals<PEval>>,
 
 	// Additional scratch space.
-	scratch_space: Option<Vec<P>>,
+	scratch_space: Option<Vec<PEval>>,
 
 	// Accumulated sums of evaluations over univariate domain.
 	//
 	// Each element of the outer vector corresponds to one composite polynomial. Each element of
 	// an inner vector contains the evaluations at different points.
-	round_evals: Vec<Vec<P>>,
+	round_evals: Vec<Vec<PAccum>>,
 }
 
-impl<P: PackedField> ParFoldStates<P> {
+impl<PEval: PackedField, PAccum: PackedField> ParFoldStates<PEval, PAccum> {
 	fn new(
-		access: &impl SumcheckMultilinearAccess<P>,
+		scratch_space_len: Option<usize>,
 		n_multilinears: usize,
 		n_round_evals: impl Iterator<Item = usize>,
 		subcube_vars: usize,
@@ -394,9 +753,7 @@ impl<P: PackedField> ParFoldStates<P> {
 			multilinear_evals: (0..n_multilinears)
 				.map(|_| MultilinearEvals::new(subcube_vars))
 				.collect(),
-			scratch_space: access
-				.scratch_space_len(subcube_vars)
-				.map(|len| zeroed_vec(len)),
+			scratch_space: scratch_space_len.map(|len| zeroed_vec(len)),
 			round_evals: n_round_evals
 				.map(|n_round_evals| zeroed_vec(n_round_evals))
 				.collect(),
@@ -479,27 +836,32 @@ impl<P: PackedField> SumcheckMultilinearAccess<P> for LowToHighAccess {
 		// Evaluations at 0 & 1 are interleaved (the substituted variable is the lowest one), need
 		// to deinterleave them first. This requires scratch space to enable simple linear time
 		// algorithm.
-		let zeros = P::default();
-		let interleaved_tuples = if scratch_space.len() == 1 {
-			Either::Left(iter::once((scratch_space.first().expect("len==1"), &zeros)))
+		deinterleave(scratch_space, evals_0, evals_1);
+
+		Ok(())
+	}
+}
+
+/// Deinterleaves subcube evaluations with the lowest variable substituted for 0 and 1.
+fn deinterleave<P: PackedField>(interleaved: &[P], evals_0: &mut [P], evals_1: &mut [P]) {
+	let zeros = P::default();
+	let interleaved_tuples = if interleaved.len() == 1 {
+		Either::Left(iter::once((interleaved.first().expect("len==1"), &zeros)))
+	} else {
+		Either::Right(interleaved.iter().tuples())
+	};
+
+	for ((&interleaved_0, &interleaved_1), evals_0, evals_1) in
+		izip!(interleaved_tuples, evals_0, evals_1)
+	{
+		let (deinterleaved_0, deinterleaved_1) = if P::LOG_WIDTH > 0 {
+			P::unzip(interleaved_0, interleaved_1, 0)
 		} else {
-			Either::Right(scratch_space.iter().tuples())
+			(interleaved_0, interleaved_1)
 		};
 
-		for ((&interleaved_0, &interleaved_1), evals_0, evals_1) in
-			izip!(interleaved_tuples, evals_0, evals_1)
-		{
-			let (deinterleaved_0, deinterleaved_1) = if P::LOG_WIDTH > 0 {
-				P::unzip(interleaved_0, interleaved_1, 0)
-			} else {
-				(interleaved_0, interleaved_1)
-			};
-
-			*evals_0 = deinterleaved_0;
-			*evals_1 = deinterleaved_1;
-		}
-
-		Ok(())
+		*evals_0 = deinterleaved_0;
+		*evals_1 = deinterleaved_1;
 	}
 }
 
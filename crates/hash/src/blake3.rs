@@ -0,0 +1,68 @@
+// Copyright 2025 Irreducible Inc.
+
+//! [BLAKE3] hashing for Merkle trees over non-algebraic hashes.
+//!
+//! BLAKE3 is several times faster than Grøstl-256 in software, so it is a good choice for
+//! commitments that are never verified inside a Binius constraint system.
+//!
+//! [BLAKE3]: <https://github.com/BLAKE3-team/BLAKE3-specs>
+
+pub use blake3::Hasher as Blake3;
+use digest::Output;
+
+use crate::{CompressionFunction, PseudoCompressionFunction};
+
+/// A compression function for BLAKE3 digests.
+///
+/// The two-to-one compression is the BLAKE3 keyed hash of the 64-byte concatenation of the
+/// digests, which is a single application of the BLAKE3 compression function. The key is derived
+/// from a context string to separate the compression from the hash of the leaves.
+#[derive(Debug, Clone)]
+pub struct Blake3Compression {
+	key: [u8; 32],
+}
+
+impl Default for Blake3Compression {
+	fn default() -> Self {
+		Self {
+			key: blake3::derive_key("BINIUS BLAKE3 COMPRESS", &[]),
+		}
+	}
+}
+
+impl PseudoCompressionFunction<Output<Blake3>, 2> for Blake3Compression {
+	fn compress(&self, input: [Output<Blake3>; 2]) -> Output<Blake3> {
+		let mut block = [0u8; 64];
+		let (half0, half1) = block.split_at_mut(32);
+		half0.copy_from_slice(&input[0]);
+		half1.copy_from_slice(&input[1]);
+		(*blake3::keyed_hash(&self.key, &block).as_bytes()).into()
+	}
+}
+
+impl CompressionFunction<Output<Blake3>, 2> for Blake3Compression {}
+
+#[cfg(test)]
+mod tests {
+	use digest::Digest;
+
+	use super::*;
+
+	#[test]
+	fn test_blake3_digest_matches_reference() {
+		let data = b"The quick brown fox jumps over the lazy dog";
+		assert_eq!(Blake3::digest(data).as_slice(), blake3::hash(data).as_bytes());
+	}
+
+	#[test]
+	fn test_blake3_compression_is_domain_separated() {
+		let left = Blake3::digest(b"left");
+		let right = Blake3::digest(b"right");
+		let compressed = Blake3Compression::default().compress([left, right]);
+
+		let mut concatenated = left.to_vec();
+		concatenated.extend_from_slice(&right);
+		assert_ne!(compressed, Blake3::digest(&concatenated));
+		assert_ne!(compressed, Blake3Compression::default().compress([right, left]));
+	}
+}
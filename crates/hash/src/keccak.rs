@@ -0,0 +1,49 @@
+// Copyright 2025 Irreducible Inc.
+
+//! [Keccak-256] hashing for Merkle trees verified on the EVM.
+//!
+//! Keccak-256 is the hash function the EVM exposes as the `KECCAK256` opcode, so Merkle openings
+//! over Keccak-256 digests are cheap to verify in an Ethereum smart contract.
+//!
+//! [Keccak-256]: <https://keccak.team/keccak.html>
+
+use digest::{Digest, Output};
+pub use sha3::Keccak256;
+
+use crate::{CompressionFunction, PseudoCompressionFunction};
+
+/// A compression function for Keccak-256 digests.
+///
+/// The two-to-one compression is the Keccak-256 hash of the 64-byte concatenation of the digests,
+/// which is `keccak256(abi.encodePacked(left, right))` in Solidity. The compression is not domain
+/// separated from the hash of the leaves, as the depth of a tree is fixed by the verifier and so
+/// leaves are never mistaken for inner nodes.
+#[derive(Debug, Default, Clone)]
+pub struct Keccak256Compression;
+
+impl PseudoCompressionFunction<Output<Keccak256>, 2> for Keccak256Compression {
+	fn compress(&self, input: [Output<Keccak256>; 2]) -> Output<Keccak256> {
+		Keccak256::new_with_prefix(input[0])
+			.chain_update(input[1])
+			.finalize()
+	}
+}
+
+impl CompressionFunction<Output<Keccak256>, 2> for Keccak256Compression {}
+
+#[cfg(test)]
+mod tests {
+	use hex_literal::hex;
+
+	use super::*;
+
+	#[test]
+	fn test_keccak256_compression_matches_solidity() {
+		// keccak256(abi.encodePacked(bytes32(0), bytes32(0)))
+		let zero = Output::<Keccak256>::default();
+		assert_eq!(
+			Keccak256Compression.compress([zero, zero]).as_slice(),
+			hex!("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5")
+		);
+	}
+}
@@ -8,18 +8,22 @@
 //! so can be arithmetized efficiently with a Binius constraint system.
 //!
 //! This crate also provides an implementation of [Vision Mark-32], a cryptographic sponge function
-//! designed for efficient Binius arithmetization.
+//! designed for efficient Binius arithmetization, and compression functions for SHA-256,
+//! [BLAKE3], and Keccak-256 digests, for commitments that are not verified in a constraint system.
 //!
 //! [Grøstl-256]: <https://www.groestl.info/>
 //! [Vision Mark-32]: <https://eprint.iacr.org/2024/633>
+//! [BLAKE3]: <https://github.com/BLAKE3-team/BLAKE3-specs>
 
 #![cfg_attr(
 	all(target_arch = "x86_64", feature = "nightly_features"),
 	feature(stdarch_x86_avx512)
 )]
 
+pub mod blake3;
 pub mod compression;
 pub mod groestl;
+pub mod keccak;
 pub mod multi_digest;
 pub mod permutation;
 mod serialization;
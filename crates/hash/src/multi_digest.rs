@@ -123,10 +123,18 @@ impl<D: MultiDigest<N, Digest: Send> + Send + Sync, const N: usize> ParallelDige
 			buffers,
 			|buffers, (data, out_chunk)| {
 				let mut hasher = self.0.clone();
+				let n_items = data.len();
 				for (buf, chunk) in buffers.iter_mut().zip(data.into_iter()) {
 					buf.clear();
 					chunk.serialize(buf);
 				}
+				// The multi-digest hashes inputs of equal length, so the unused rows of a partial
+				// last chunk repeat the first row and their digests are discarded.
+				let (used, unused) = buffers.split_at_mut(n_items);
+				for buf in unused {
+					buf.clear();
+					buf.extend_from_slice(&used[0]);
+				}
 				let data = array::from_fn(|i| buffers[i].as_ref());
 				hasher.update(data);
 
@@ -333,4 +341,12 @@ mod tests {
 			check_parallel_digest_consistency::<ParallelMultidigestImpl<MockMultiDigest, 4>>(data);
 		}
 	}
+
+	#[test]
+	fn test_partial_last_chunk() {
+		for n_hashes in [1, 9, 33] {
+			let data = generate_mock_data(n_hashes, 16);
+			check_parallel_digest_consistency::<crate::Vision32ParallelDigest>(data);
+		}
+	}
 }
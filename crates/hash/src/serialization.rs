@@ -2,6 +2,8 @@
 
 use std::{borrow::Borrow, cmp::min};
 
+use binius_field::TowerField;
+use binius_maybe_rayon::prelude::*;
 use binius_utils::{SerializationError, SerializationMode, SerializeBytes};
 use bytes::{BufMut, buf::UninitSlice};
 use digest::{
@@ -9,6 +11,8 @@ use digest::{
 	core_api::{Block, BlockSizeUser},
 };
 
+use crate::multi_digest;
+
 /// Adapter that wraps a [`Digest`] references and exposes the [`BufMut`] interface.
 ///
 /// This adapter is useful so that structs that implement [`SerializeBytes`] can be serialized
@@ -72,7 +76,19 @@ where
 	T: SerializeBytes,
 	D: Digest + BlockSizeUser,
 {
-	let mut hasher = D::new();
+	hash_serialize_with_prefix::<T, D>(&[], items)
+}
+
+/// Hashes a sequence of serializable items after the bytes of `prefix`.
+pub fn hash_serialize_with_prefix<T, D>(
+	prefix: &[u8],
+	items: impl IntoIterator<Item = impl Borrow<T>>,
+) -> Result<Output<D>, SerializationError>
+where
+	T: SerializeBytes,
+	D: Digest + BlockSizeUser,
+{
+	let mut hasher = D::new_with_prefix(prefix);
 	{
 		let mut buffer = HashBuffer::new(&mut hasher);
 		for item in items {
@@ -83,11 +99,69 @@ where
 	Ok(hasher.finalize())
 }
 
+/// Hashes consecutive chunks of tower field elements, returning one digest per chunk.
+///
+/// This produces the same digests as calling [`hash_serialize`] on every chunk, but serializes and
+/// hashes the chunks in parallel through
+/// [`ParallelDigest`](multi_digest::ParallelDigest). When `H` is backed by a
+/// [`MultiDigest`](crate::multi_digest::MultiDigest) implementation, such as
+/// [`Groestl256Parallel`](crate::groestl::Groestl256Parallel), several chunks are hashed at once
+/// using SIMD instructions.
+///
+/// ## Panics
+///
+/// Panics if `chunk_size` is zero or does not divide the number of elements.
+pub fn hash_serialize_batch<F, H>(elems: &[F], chunk_size: usize) -> Vec<Output<H::Digest>>
+where
+	F: TowerField,
+	H: multi_digest::ParallelDigest,
+{
+	hash_serialize_batch_with_prefix::<F, H>(&[], elems, chunk_size)
+}
+
+/// Hashes consecutive chunks of tower field elements as [`hash_serialize_batch`], each after the
+/// bytes of `prefix`.
+///
+/// This produces the same digests as calling [`hash_serialize_with_prefix`] on every chunk.
+pub fn hash_serialize_batch_with_prefix<F, H>(
+	prefix: &[u8],
+	elems: &[F],
+	chunk_size: usize,
+) -> Vec<Output<H::Digest>>
+where
+	F: TowerField,
+	H: multi_digest::ParallelDigest,
+{
+	assert!(
+		chunk_size != 0 && elems.len() % chunk_size == 0,
+		"chunk size must be non-zero and divide the number of elements"
+	);
+
+	let n_chunks = elems.len() / chunk_size;
+	let mut digests = Vec::with_capacity(n_chunks);
+	H::new_with_prefix(prefix).digest(
+		elems
+			.par_chunks(chunk_size)
+			.map(|chunk| chunk.iter().copied()),
+		&mut digests.spare_capacity_mut()[..n_chunks],
+	);
+	unsafe {
+		// SAFETY: ParallelDigest::digest initializes every output digest
+		digests.set_len(n_chunks);
+	}
+	digests
+}
+
 #[cfg(test)]
 mod tests {
+	use std::iter::repeat_with;
+
+	use binius_field::{BinaryField32b, Field};
 	use groestl_crypto::Groestl256;
+	use rand::{SeedableRng, rngs::StdRng};
 
 	use super::*;
+	use crate::groestl::Groestl256Parallel;
 
 	#[test]
 	fn test_hash_buffer_updates() {
@@ -105,4 +179,53 @@ mod tests {
 		}
 		assert_eq!(hasher.finalize(), expected_digest);
 	}
+
+	#[test]
+	fn test_hash_serialize_batch_matches_hash_serialize() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let elems = repeat_with(|| BinaryField32b::random(&mut rng))
+			.take(7 * 16)
+			.collect::<Vec<_>>();
+
+		for chunk_size in [7, 16, 7 * 16] {
+			let expected = elems
+				.chunks(chunk_size)
+				.map(|chunk| hash_serialize::<BinaryField32b, Groestl256>(chunk).unwrap())
+				.collect::<Vec<_>>();
+
+			assert_eq!(hash_serialize_batch::<_, Groestl256>(&elems, chunk_size), expected);
+			assert_eq!(hash_serialize_batch::<_, Groestl256Parallel>(&elems, chunk_size), expected);
+		}
+	}
+
+	#[test]
+	fn test_hash_serialize_with_prefix() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let elems = repeat_with(|| BinaryField32b::random(&mut rng))
+			.take(7 * 16)
+			.collect::<Vec<_>>();
+		let prefix = [1, 2, 3];
+
+		let expected = elems
+			.chunks(7)
+			.map(|chunk| {
+				let mut bytes = prefix.to_vec();
+				for elem in chunk {
+					elem.serialize(&mut bytes, SerializationMode::CanonicalTower)
+						.unwrap();
+				}
+				Groestl256::digest(&bytes)
+			})
+			.collect::<Vec<_>>();
+		for (chunk, expected) in elems.chunks(7).zip(&expected) {
+			assert_eq!(
+				hash_serialize_with_prefix::<BinaryField32b, Groestl256>(&prefix, chunk).unwrap(),
+				*expected
+			);
+		}
+		assert_eq!(
+			hash_serialize_batch_with_prefix::<_, Groestl256Parallel>(&prefix, &elems, 7),
+			expected
+		);
+	}
 }
@@ -2,11 +2,21 @@
 
 use bytemuck::{bytes_of_mut, must_cast};
 use digest::{Digest, core_api::Block};
-use sha2::{Sha256, compress256, digest::Output};
+pub use sha2::Sha256;
+use sha2::{compress256, digest::Output};
 
 use crate::{CompressionFunction, PseudoCompressionFunction};
 
-/// A two-to-one compression function for SHA-256 digests.
+/// A compression function for SHA-256 digests.
+///
+/// The compression function is evaluated with the SHA-NI instructions on x86-64 and the
+/// cryptographic extension on ARMv8 when the CPU supports them, as detected at runtime, and in
+/// software otherwise. See [`is_hardware_accelerated`].
+///
+/// The two-to-one compression is a single application of the SHA-256 compression function with a
+/// domain-separated initial state. The `N`-to-one compressions, for `N` of 4 and 8, chain `N / 2`
+/// applications over the consecutive 64-byte blocks of the input, so an `N`-ary Merkle tree costs
+/// `N / 2` applications per node where the equivalent binary subtree costs `N - 1`.
 #[derive(Debug, Clone)]
 pub struct Sha256Compression {
 	initial_state: [u32; 8],
@@ -21,15 +31,56 @@ impl Default for Sha256Compression {
 	}
 }
 
-impl PseudoCompressionFunction<Output<Sha256>, 2> for Sha256Compression {
-	fn compress(&self, input: [Output<Sha256>; 2]) -> Output<Sha256> {
+impl Sha256Compression {
+	/// Applies the compression function to the blocks of concatenated pairs of digests.
+	fn compress_pairs(&self, input: &[Output<Sha256>]) -> Output<Sha256> {
+		let mut blocks = [<Block<Sha256>>::default(); MAX_ARITY / 2];
+		for (block, pair) in blocks.iter_mut().zip(input.chunks_exact(2)) {
+			block.as_mut_slice()[..32].copy_from_slice(pair[0].as_slice());
+			block.as_mut_slice()[32..].copy_from_slice(pair[1].as_slice());
+		}
 		let mut ret = self.initial_state;
-		let mut block = <Block<Sha256>>::default();
-		block.as_mut_slice()[..32].copy_from_slice(input[0].as_slice());
-		block.as_mut_slice()[32..].copy_from_slice(input[1].as_slice());
-		compress256(&mut ret, &[block]);
+		compress256(&mut ret, &blocks[..input.len() / 2]);
 		must_cast::<[u32; 8], [u8; 32]>(ret).into()
 	}
 }
 
-impl CompressionFunction<Output<Sha256>, 2> for Sha256Compression {}
+/// The largest number of digests compressed at once.
+const MAX_ARITY: usize = 8;
+
+macro_rules! impl_sha256_compression {
+	($($n:literal),*) => {
+		$(
+			impl PseudoCompressionFunction<Output<Sha256>, $n> for Sha256Compression {
+				fn compress(&self, input: [Output<Sha256>; $n]) -> Output<Sha256> {
+					self.compress_pairs(&input)
+				}
+			}
+
+			impl CompressionFunction<Output<Sha256>, $n> for Sha256Compression {}
+		)*
+	};
+}
+
+impl_sha256_compression!(2, 4, 8);
+
+/// Returns whether SHA-256 hashing and compression run on dedicated CPU instructions.
+///
+/// The `sha2` crate detects the SHA-NI extension on x86-64 and the SHA2 cryptographic extension
+/// on AArch64 at runtime and falls back to a software implementation without them. This reports
+/// the same detection, for example to check that a prover deployment is not silently running the
+/// slow path.
+pub fn is_hardware_accelerated() -> bool {
+	cfg_if::cfg_if! {
+		if #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] {
+			std::arch::is_x86_feature_detected!("sha")
+				&& std::arch::is_x86_feature_detected!("sse2")
+				&& std::arch::is_x86_feature_detected!("ssse3")
+				&& std::arch::is_x86_feature_detected!("sse4.1")
+		} else if #[cfg(target_arch = "aarch64")] {
+			std::arch::is_aarch64_feature_detected!("sha2")
+		} else {
+			false
+		}
+	}
+}
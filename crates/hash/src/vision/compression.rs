@@ -1,9 +1,16 @@
 // Copyright 2025 Irreducible Inc.
 
+use binius_field::{
+	PackedAESBinaryField8x32b, PackedBinaryField8x32b, PackedField,
+	linear_transformation::Transformation,
+};
 use digest::{Digest, Output};
 
-use super::digest::VisionHasherDigest;
-use crate::PseudoCompressionFunction;
+use super::{
+	digest::{TRANS_AES_TO_CANONICAL, TRANS_CANONICAL_TO_AES, Vision32Digest, VisionHasherDigest},
+	permutation::PERMUTATION,
+};
+use crate::{CompressionFunction, PseudoCompressionFunction, permutation::Permutation};
 
 /// One-way compression function that compresses two 32-byte strings into a single 32-byte string.
 #[derive(Debug, Default, Clone)]
@@ -17,3 +24,60 @@ impl PseudoCompressionFunction<Output<VisionHasherDigest>, 2> for Vision32Compre
 			.finalize()
 	}
 }
+
+/// Compression function for [`Vision32Digest`]s by a single truncated Vision Mark-32 permutation.
+///
+/// The two digests fill the rate of the permutation state and the capacity is zero. The output is
+/// the first eight elements of the permuted state, which is collision resistant if the
+/// permutation is ideal. This is a third of the cost of [`Vision32Compression`], which absorbs
+/// the digests as bytes and then a padding block, and needs no byte decomposition when verified
+/// in a constraint system.
+///
+/// The compression is also defined on the byte representation of the digests, so it can be used
+/// with the byte-oriented Merkle tree implementations.
+#[derive(Debug, Default, Clone)]
+pub struct Vision32PermutationCompression;
+
+impl PseudoCompressionFunction<Vision32Digest, 2> for Vision32PermutationCompression {
+	fn compress(&self, input: [Vision32Digest; 2]) -> Vision32Digest {
+		let mut state = [PackedAESBinaryField8x32b::zero(); 3];
+		for (state, digest) in state.iter_mut().zip(input) {
+			*state =
+				TRANS_CANONICAL_TO_AES.transform(&PackedBinaryField8x32b::from_scalars(digest.0));
+		}
+
+		PERMUTATION.permute_mut(&mut state);
+
+		let output: PackedBinaryField8x32b = TRANS_AES_TO_CANONICAL.transform(&state[0]);
+		Vision32Digest(std::array::from_fn(|i| output.get(i)))
+	}
+}
+
+impl CompressionFunction<Vision32Digest, 2> for Vision32PermutationCompression {}
+
+impl PseudoCompressionFunction<Output<VisionHasherDigest>, 2> for Vision32PermutationCompression {
+	fn compress(&self, input: [Output<VisionHasherDigest>; 2]) -> Output<VisionHasherDigest> {
+		PseudoCompressionFunction::<Vision32Digest, 2>::compress(self, input.map(Into::into)).into()
+	}
+}
+
+impl CompressionFunction<Output<VisionHasherDigest>, 2> for Vision32PermutationCompression {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_permutation_compression_agrees_on_bytes_and_field_elements() {
+		let left = VisionHasherDigest::digest(b"left");
+		let right = VisionHasherDigest::digest(b"right");
+
+		let compression = Vision32PermutationCompression;
+		let compressed_bytes = compression.compress([left, right]);
+		let compressed =
+			compression.compress([Vision32Digest::from(left), Vision32Digest::from(right)]);
+		assert_eq!(Vision32Digest::from(compressed_bytes), compressed);
+		assert_ne!(compressed_bytes, Vision32Compression.compress([left, right]));
+		assert_ne!(compressed_bytes, compression.compress([right, left]));
+	}
+}
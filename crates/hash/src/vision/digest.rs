@@ -3,13 +3,17 @@
 use std::{array, mem::MaybeUninit};
 
 use binius_field::{
-	AesToBinaryTransformation, BinaryField8b, BinaryToAesTransformation, ByteSlicedAES32x32b,
-	PackedAESBinaryField8x32b, PackedBinaryField8x32b, PackedExtensionIndexable, PackedField,
-	PackedFieldIndexable, linear_transformation::Transformation,
-	make_aes_to_binary_packed_transformer, make_binary_to_aes_packed_transformer,
-	underlier::WithUnderlier,
+	AesToBinaryTransformation, BinaryField8b, BinaryField32b, BinaryToAesTransformation,
+	ByteSlicedAES32x32b, Field, PackedAESBinaryField8x32b, PackedBinaryField8x32b,
+	PackedExtensionIndexable, PackedField, PackedFieldIndexable,
+	linear_transformation::Transformation, make_aes_to_binary_packed_transformer,
+	make_binary_to_aes_packed_transformer, underlier::WithUnderlier,
 };
-use binius_utils::mem::slice_assume_init_mut;
+use binius_utils::{
+	DeserializeBytes, SerializationError, SerializationMode, SerializeBytes,
+	mem::slice_assume_init_mut,
+};
+use bytes::{Buf, BufMut};
 use digest::{
 	FixedOutput, FixedOutputReset, HashMarker, OutputSizeUser, Reset, Update,
 	consts::{U32, U96},
@@ -30,9 +34,9 @@ const PADDING_START: u8 = 0x80;
 const PADDING_END: u8 = 0x01;
 
 lazy_static! {
-	static ref TRANS_AES_TO_CANONICAL: AesToBinaryTransformation<PackedAESBinaryField8x32b, PackedBinaryField8x32b> =
+	pub(super) static ref TRANS_AES_TO_CANONICAL: AesToBinaryTransformation<PackedAESBinaryField8x32b, PackedBinaryField8x32b> =
 		make_aes_to_binary_packed_transformer::<PackedAESBinaryField8x32b, PackedBinaryField8x32b>();
-	static ref TRANS_CANONICAL_TO_AES: BinaryToAesTransformation<PackedBinaryField8x32b, PackedAESBinaryField8x32b> =
+	pub(super) static ref TRANS_CANONICAL_TO_AES: BinaryToAesTransformation<PackedBinaryField8x32b, PackedAESBinaryField8x32b> =
 		make_binary_to_aes_packed_transformer::<PackedBinaryField8x32b, PackedAESBinaryField8x32b>();
 
 
@@ -352,15 +356,72 @@ impl MultiDigest<HASHES_PER_BYTE_SLICED_PERMUTATION> for VisionHasherDigestByteS
 pub type Vision32ParallelDigest =
 	ParallelMultidigestImpl<VisionHasherDigestByteSliced, HASHES_PER_BYTE_SLICED_PERMUTATION>;
 
+/// A Vision Mark-32 digest as a vector of 32-bit binary field elements.
+///
+/// This is the same value as the 32-byte output of [`VisionHasherDigest`], read as eight
+/// little-endian field elements in the canonical tower basis. Merkle trees over field element
+/// digests are cheap to verify inside a Binius constraint system, as the digests need not be
+/// decomposed into bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Vision32Digest(pub [BinaryField32b; 8]);
+
+impl From<digest::Output<VisionHasherDigest>> for Vision32Digest {
+	fn from(bytes: digest::Output<VisionHasherDigest>) -> Self {
+		Self(array::from_fn(|i| {
+			BinaryField32b::new(u32::from_le_bytes(
+				bytes[i * 4..i * 4 + 4]
+					.try_into()
+					.expect("chunk is 4 bytes"),
+			))
+		}))
+	}
+}
+
+impl From<Vision32Digest> for digest::Output<VisionHasherDigest> {
+	fn from(digest: Vision32Digest) -> Self {
+		let mut bytes = Self::default();
+		for (chunk, value) in bytes.chunks_exact_mut(4).zip(digest.0) {
+			chunk.copy_from_slice(&value.val().to_le_bytes());
+		}
+		bytes
+	}
+}
+
+impl SerializeBytes for Vision32Digest {
+	fn serialize(
+		&self,
+		mut write_buf: impl BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		self.0
+			.iter()
+			.try_for_each(|value| value.serialize(&mut write_buf, mode))
+	}
+}
+
+impl DeserializeBytes for Vision32Digest {
+	fn deserialize(
+		mut read_buf: impl Buf,
+		mode: SerializationMode,
+	) -> Result<Self, SerializationError> {
+		let mut values = [BinaryField32b::ZERO; 8];
+		for value in &mut values {
+			*value = BinaryField32b::deserialize(&mut read_buf, mode)?;
+		}
+		Ok(Self(values))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use std::{array, mem::MaybeUninit};
 
+	use binius_utils::{DeserializeBytes, SerializationMode, SerializeBytes};
 	use digest::Digest;
 	use hex_literal::hex;
 
 	use super::{
-		HASHES_PER_BYTE_SLICED_PERMUTATION, MultiDigest, VisionHasherDigest,
+		HASHES_PER_BYTE_SLICED_PERMUTATION, MultiDigest, Vision32Digest, VisionHasherDigest,
 		VisionHasherDigestByteSliced,
 	};
 
@@ -482,4 +543,22 @@ mod tests {
 			array::from_fn::<_, { HASHES_PER_BYTE_SLICED_PERMUTATION }, _>(|i| &data_2[i][..]),
 		]);
 	}
+
+	#[test]
+	fn test_vision32_digest_bytes_roundtrip() {
+		let bytes = VisionHasherDigest::digest(b"field digest");
+		let digest = Vision32Digest::from(bytes);
+		assert_eq!(digest::Output::<VisionHasherDigest>::from(digest), bytes);
+
+		let mut serialized = Vec::new();
+		digest
+			.serialize(&mut serialized, SerializationMode::CanonicalTower)
+			.unwrap();
+		assert_eq!(serialized, bytes.as_slice());
+		assert_eq!(
+			Vision32Digest::deserialize(serialized.as_slice(), SerializationMode::CanonicalTower)
+				.unwrap(),
+			digest
+		);
+	}
 }
@@ -6,7 +6,7 @@ use binius_core::constraint_system::channel::{Boundary, ChannelId, FlushDirectio
 use binius_field::{Field, PackedExtension, PackedField, TowerField};
 
 use super::{
-	B1, B8, B16, B32, B64, B128, constraint_system::ConstraintSystem, error::Error,
+	B1, B2, B4, B8, B16, B32, B64, B128, constraint_system::ConstraintSystem, error::Error,
 	witness::WitnessIndex,
 };
 
@@ -60,6 +60,8 @@ pub fn tally<P>(
 where
 	P: PackedField<Scalar = B128>
 		+ PackedExtension<B1>
+		+ PackedExtension<B2>
+		+ PackedExtension<B4>
 		+ PackedExtension<B8>
 		+ PackedExtension<B16>
 		+ PackedExtension<B32>
@@ -2,7 +2,9 @@
 
 //! Utilities for testing M3 constraint systems and gadgets.
 use anyhow::Result;
-use binius_core::{constraint_system::channel::Boundary, fiat_shamir::HasherChallenger};
+use binius_core::constraint_system::{
+	PresetChallenger, PresetCompression, PresetHash, SecurityParams, channel::Boundary,
+};
 use binius_fast_compute::{layer::FastCpuLayer, memory::PackedMemorySliceMut};
 use binius_field::{
 	BinaryField128bPolyval, PackedField, PackedFieldIndexable, TowerField,
@@ -11,12 +13,11 @@ use binius_field::{
 	tower::CanonicalTowerFamily,
 	underlier::UnderlierType,
 };
-use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
 use binius_utils::env::boolean_env_flag_set;
 use bytemuck::zeroed_vec;
 
 use super::{
-	B1, B8, B16, B32, B64,
+	B1, B2, B4, B8, B16, B32, B64,
 	constraint_system::ConstraintSystem,
 	table::TableId,
 	witness::{TableFiller, TableWitnessSegment},
@@ -77,6 +78,8 @@ pub fn validate_system_witness<U>(
 ) where
 	U: UnderlierType
 		+ PackScalar<B1>
+		+ PackScalar<B2>
+		+ PackScalar<B4>
 		+ PackScalar<B8>
 		+ PackScalar<B16>
 		+ PackScalar<B32>
@@ -104,6 +107,8 @@ pub fn validate_system_witness_with_prove_verify<U>(
 ) where
 	U: UnderlierType
 		+ PackScalar<B1>
+		+ PackScalar<B2>
+		+ PackScalar<B4>
 		+ PackScalar<B8>
 		+ PackScalar<B16>
 		+ PackScalar<B32>
@@ -130,8 +135,7 @@ pub fn validate_system_witness_with_prove_verify<U>(
 	.unwrap();
 
 	if prove_verify {
-		const LOG_INV_RATE: usize = 1;
-		const SECURITY_BITS: usize = 100;
+		let security_params = SecurityParams::preset_100_bit();
 
 		let hal = FastCpuLayer::<CanonicalTowerFamily, PackedType<U, B128>>::default();
 
@@ -140,22 +144,21 @@ pub fn validate_system_witness_with_prove_verify<U>(
 
 		let dev_mem = PackedMemorySliceMut::new_slice(&mut dev_mem_owned);
 
-		let ccs_digest = ccs.digest::<Groestl256>();
+		let ccs_digest = ccs.digest::<PresetHash>();
 		let proof = binius_core::constraint_system::prove::<
 			_,
 			U,
 			CanonicalTowerFamily,
-			Groestl256,
-			Groestl256ByteCompression,
-			HasherChallenger<Groestl256>,
+			PresetHash,
+			PresetCompression,
+			PresetChallenger,
 			_,
 		>(
 			&hal,
 			&mut host_mem,
 			dev_mem,
 			&ccs,
-			LOG_INV_RATE,
-			SECURITY_BITS,
+			&security_params,
 			&ccs_digest,
 			&statement.boundaries,
 			&statement.table_sizes,
@@ -167,10 +170,10 @@ pub fn validate_system_witness_with_prove_verify<U>(
 		binius_core::constraint_system::verify::<
 			U,
 			CanonicalTowerFamily,
-			Groestl256,
-			Groestl256ByteCompression,
-			HasherChallenger<Groestl256>,
-		>(&ccs, LOG_INV_RATE, SECURITY_BITS, &ccs_digest, &statement.boundaries, proof)
+			PresetHash,
+			PresetCompression,
+			PresetChallenger,
+		>(&ccs, &security_params, &ccs_digest, &statement.boundaries, proof)
 		.unwrap();
 	}
 }
@@ -5,10 +5,13 @@
 //! The primitive data types are fields in the canonical tower.
 
 use binius_field::{
-	BinaryField1b, BinaryField8b, BinaryField16b, BinaryField32b, BinaryField64b, BinaryField128b,
+	BinaryField1b, BinaryField2b, BinaryField4b, BinaryField8b, BinaryField16b, BinaryField32b,
+	BinaryField64b, BinaryField128b,
 };
 
 pub type B1 = BinaryField1b;
+pub type B2 = BinaryField2b;
+pub type B4 = BinaryField4b;
 pub type B8 = BinaryField8b;
 pub type B16 = BinaryField16b;
 pub type B32 = BinaryField32b;
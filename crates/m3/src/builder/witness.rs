@@ -36,7 +36,7 @@ use super::{
 	constraint_system::OracleMapping,
 	error::Error,
 	table::{self, Table, TableId},
-	types::{B1, B8, B16, B32, B64, B128},
+	types::{B1, B2, B4, B8, B16, B32, B64, B128},
 };
 use crate::builder::multi_iter::MultiIterator;
 
@@ -171,6 +171,8 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 	) -> MultilinearWitness<'a, P>
 	where
 		P: PackedExtension<B1>
+			+ PackedExtension<B2>
+			+ PackedExtension<B4>
 			+ PackedExtension<B8>
 			+ PackedExtension<B16>
 			+ PackedExtension<B32>
@@ -189,6 +191,8 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 	pub fn into_multilinear_extension_index(self) -> MultilinearExtensionIndex<'alloc, P>
 	where
 		P: PackedExtension<B1>
+			+ PackedExtension<B2>
+			+ PackedExtension<B4>
 			+ PackedExtension<B8>
 			+ PackedExtension<B16>
 			+ PackedExtension<B32>
@@ -284,6 +288,8 @@ impl<'cs, 'alloc, P> WitnessIndex<'cs, 'alloc, P>
 where
 	P: PackedField<Scalar: TowerField>
 		+ PackedExtension<B1>
+		+ PackedExtension<B2>
+		+ PackedExtension<B4>
 		+ PackedExtension<B8>
 		+ PackedExtension<B16>
 		+ PackedExtension<B32>
@@ -323,6 +329,8 @@ fn multilin_poly_from_underlier_data<P>(
 ) -> Arc<dyn MultilinearPoly<P> + Send + Sync + '_>
 where
 	P: PackedExtension<B1>
+		+ PackedExtension<B2>
+		+ PackedExtension<B4>
 		+ PackedExtension<B8>
 		+ PackedExtension<B16>
 		+ PackedExtension<B32>
@@ -333,6 +341,12 @@ where
 		0 => MultilinearExtension::new(n_vars, PackedExtension::<B1>::cast_bases(data))
 			.unwrap()
 			.specialize_arc_dyn(),
+		1 => MultilinearExtension::new(n_vars, PackedExtension::<B2>::cast_bases(data))
+			.unwrap()
+			.specialize_arc_dyn(),
+		2 => MultilinearExtension::new(n_vars, PackedExtension::<B4>::cast_bases(data))
+			.unwrap()
+			.specialize_arc_dyn(),
 		3 => MultilinearExtension::new(n_vars, PackedExtension::<B8>::cast_bases(data))
 			.unwrap()
 			.specialize_arc_dyn(),
@@ -1154,6 +1168,8 @@ impl<'a, P> TableWitnessSegment<'a, P>
 where
 	P: PackedField<Scalar: TowerField>
 		+ PackedExtension<B1>
+		+ PackedExtension<B2>
+		+ PackedExtension<B4>
 		+ PackedExtension<B8>
 		+ PackedExtension<B16>
 		+ PackedExtension<B32>
@@ -1174,6 +1190,12 @@ where
 			0 => Box::new(WitnessColViewImpl(Ref::map(col_ref, |packed| {
 				PackedExtension::<B1>::cast_bases(packed)
 			}))),
+			1 => Box::new(WitnessColViewImpl(Ref::map(col_ref, |packed| {
+				PackedExtension::<B2>::cast_bases(packed)
+			}))),
+			2 => Box::new(WitnessColViewImpl(Ref::map(col_ref, |packed| {
+				PackedExtension::<B4>::cast_bases(packed)
+			}))),
 			3 => Box::new(WitnessColViewImpl(Ref::map(col_ref, |packed| {
 				PackedExtension::<B8>::cast_bases(packed)
 			}))),
@@ -1208,6 +1230,12 @@ where
 			0 => Box::new(WitnessColViewImpl(RefMut::map(col_ref, |packed| {
 				PackedExtension::<B1>::cast_bases_mut(packed)
 			}))),
+			1 => Box::new(WitnessColViewImpl(RefMut::map(col_ref, |packed| {
+				PackedExtension::<B2>::cast_bases_mut(packed)
+			}))),
+			2 => Box::new(WitnessColViewImpl(RefMut::map(col_ref, |packed| {
+				PackedExtension::<B4>::cast_bases_mut(packed)
+			}))),
 			3 => Box::new(WitnessColViewImpl(RefMut::map(col_ref, |packed| {
 				PackedExtension::<B8>::cast_bases_mut(packed)
 			}))),
@@ -0,0 +1,55 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{
+	arch::OptimalUnderlier128b, arithmetic_traits::Square, as_packed_field::PackedType,
+	packed::set_packed_slice,
+};
+use binius_m3::builder::{
+	B2, B4, B128, ConstraintSystem, WitnessIndex,
+	test_utils::{ClosureFiller, validate_system_witness_with_prove_verify},
+};
+use bumpalo::Bump;
+
+#[test]
+fn test_committed_b2_b4_columns() {
+	let allocator = Bump::new();
+	let mut cs = ConstraintSystem::<B128>::new();
+
+	let mut table = cs.add_table("small_tower_columns");
+	let table_id = table.id();
+
+	let b2_in = table.add_committed::<B2, 1>("b2_in");
+	let b2_square = table.add_committed::<B2, 1>("b2_square");
+	let b4_in = table.add_committed::<B4, 4>("b4_in");
+	let b4_square = table.add_committed::<B4, 4>("b4_square");
+
+	table.assert_zero("b2_square", b2_in * b2_in - b2_square);
+	table.assert_zero("b4_square", b4_in * b4_in - b4_square);
+
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+
+	witness
+		.fill_table_sequential(
+			&ClosureFiller::new(table_id, |events, index| {
+				let mut b2_in = index.get_mut(b2_in)?;
+				let mut b2_square = index.get_mut(b2_square)?;
+				let mut b4_in = index.get_mut(b4_in)?;
+				let mut b4_square = index.get_mut(b4_square)?;
+				for (i, &&event) in events.iter().enumerate() {
+					let b2 = B2::from(event % 4);
+					set_packed_slice(&mut b2_in, i, b2);
+					set_packed_slice(&mut b2_square, i, b2.square());
+					for j in 0..4 {
+						let b4 = B4::from((event + j as u8) % 16);
+						set_packed_slice(&mut b4_in, 4 * i + j, b4);
+						set_packed_slice(&mut b4_square, 4 * i + j, b4.square());
+					}
+				}
+				Ok(())
+			}),
+			&(0..1 << 6).collect::<Vec<u8>>(),
+		)
+		.unwrap();
+
+	validate_system_witness_with_prove_verify::<OptimalUnderlier128b>(&cs, witness, vec![], true);
+}
@@ -22,6 +22,17 @@ trait_set::trait_set! {
 		+ ExtensionField<B64>
 		+ ExtensionField<B128>;
 
+	/// The top field in a tower of at least 64 bits.
+	///
+	/// Every [`TowerTop`] is one, and so is [`B64`], the top field of a tower truncated at 64 bits.
+	pub trait TowerTop64 =
+		TowerField
+		+ ExtensionField<B1>
+		+ ExtensionField<B8>
+		+ ExtensionField<B16>
+		+ ExtensionField<B32>
+		+ ExtensionField<B64>;
+
 	/// A packed field type that is the top packed field in a tower.
 	pub trait PackedTop =
 		PackedField
@@ -107,6 +107,16 @@ cfg_if::cfg_if! {
 				marker: PhantomData,
 			})
 		}
+
+		#[inline(always)]
+		pub fn in_place_scope<'scope, OP, R>(op: OP) -> R
+		where
+			OP: FnOnce(&Scope<'scope>) -> R,
+		{
+			op(&Scope {
+				marker: PhantomData,
+			})
+		}
 	} else {
 		pub use rayon::*;
 	}
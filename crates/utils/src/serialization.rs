@@ -48,6 +48,8 @@ pub enum SerializationError {
 	FromUtf8Error(#[from] std::string::FromUtf8Error),
 	#[error("Invalid construction of {name}")]
 	InvalidConstruction { name: &'static str },
+	#[error("Unsupported version {version} of the encoding of {name}")]
+	UnsupportedVersion { name: &'static str, version: u8 },
 	#[error("usize {size} is too large to serialize (max is {max})", max = u32::MAX)]
 	UsizeTooLarge { size: usize },
 }
@@ -3,7 +3,10 @@
 use std::iter::repeat_with;
 
 use anyhow::Result;
-use binius_core::{constraint_system, fiat_shamir::HasherChallenger};
+use binius_core::{
+	constraint_system::{self, SecurityParams},
+	fiat_shamir::HasherChallenger,
+};
 use binius_fast_compute::{layer::FastCpuLayer, memory::PackedMemorySliceMut};
 use binius_field::{
 	PackedExtension, PackedFieldIndexable, PackedSubfield, arch::OptimalUnderlier,
@@ -80,13 +83,15 @@ where
 }
 
 fn main() -> Result<()> {
-	const SECURITY_BITS: usize = 100;
-
 	adjust_thread_pool()
 		.as_ref()
 		.expect("failed to init thread pool");
 
 	let args = Args::parse();
+	let security_params = SecurityParams {
+		log_inv_rate: args.log_inv_rate as usize,
+		..SecurityParams::preset_100_bit()
+	};
 
 	let _guard = init_tracing().expect("failed to initialize tracing");
 
@@ -136,8 +141,7 @@ fn main() -> Result<()> {
 		&mut host_mem,
 		dev_mem,
 		&ccs,
-		args.log_inv_rate as usize,
-		SECURITY_BITS,
+		&security_params,
 		&cs_digest,
 		&statement.boundaries,
 		&statement.table_sizes,
@@ -154,14 +158,7 @@ fn main() -> Result<()> {
 		Groestl256,
 		Groestl256ByteCompression,
 		HasherChallenger<Groestl256>,
-	>(
-		&ccs,
-		args.log_inv_rate as usize,
-		SECURITY_BITS,
-		&cs_digest,
-		&statement.boundaries,
-		proof,
-	)
+	>(&ccs, &security_params, &cs_digest, &statement.boundaries, proof)
 	.unwrap();
 
 	Ok(())